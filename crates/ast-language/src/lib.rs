@@ -225,14 +225,17 @@ impl_lang_expando!(Swift, language_swift, 'µ');
 // Stub Language without preprocessing
 // Language Name, tree-sitter-name, alias, extension
 impl_lang!(Bash, language_bash);
+impl_lang!(Dart, language_dart);
 impl_lang!(Java, language_java);
 impl_lang!(JavaScript, language_javascript);
 impl_lang!(Json, language_json);
 impl_lang!(Lua, language_lua);
+impl_lang!(Nim, language_nim);
 impl_lang!(Scala, language_scala);
 impl_lang!(Tsx, language_tsx);
 impl_lang!(TypeScript, language_typescript);
 impl_lang!(Yaml, language_yaml);
+impl_lang!(Zig, language_zig);
 // See ripgrep for extensions
 // https://github.com/BurntSushi/ripgrep/blob/master/crates/ignore/src/default_types.rs
 
@@ -244,6 +247,7 @@ pub enum SupportLang {
     Cpp,
     CSharp,
     Css,
+    Dart,
     Go,
     Elixir,
     Haskell,
@@ -253,6 +257,7 @@ pub enum SupportLang {
     Json,
     Kotlin,
     Lua,
+    Nim,
     Php,
     Python,
     Ruby,
@@ -262,15 +267,16 @@ pub enum SupportLang {
     Tsx,
     TypeScript,
     Yaml,
+    Zig,
 }
 
 impl SupportLang {
     pub const fn all_langs() -> &'static [SupportLang] {
         use SupportLang::*;
         &[
-            Bash, C, Cpp, CSharp, Css, Elixir, Go, Haskell, Html, Java,
-            JavaScript, Json, Kotlin, Lua, Php, Python, Ruby, Rust, Scala,
-            Swift, Tsx, TypeScript, Yaml,
+            Bash, C, Cpp, CSharp, Css, Dart, Elixir, Go, Haskell, Html, Java,
+            JavaScript, Json, Kotlin, Lua, Nim, Php, Python, Ruby, Rust,
+            Scala, Swift, Tsx, TypeScript, Yaml, Zig,
         ]
     }
 
@@ -359,6 +365,7 @@ impl_aliases! {
   Cpp => &["cc", "c++", "cpp", "cxx"],
   CSharp => &["cs", "csharp"],
   Css => &["css"],
+  Dart => &["dart"],
   Elixir => &["ex", "elixir"],
   Go => &["go", "golang"],
   Haskell => &["hs", "haskell"],
@@ -368,6 +375,7 @@ impl_aliases! {
   Json => &["json"],
   Kotlin => &["kotlin", "kt"],
   Lua => &["lua"],
+  Nim => &["nim"],
   Php => &["php"],
   Python => &["py", "python"],
   Ruby => &["rb", "ruby"],
@@ -377,6 +385,7 @@ impl_aliases! {
   TypeScript => &["ts", "typescript"],
   Tsx => &["tsx"],
   Yaml => &["yaml", "yml"],
+  Zig => &["zig"],
 }
 
 /// Implements the language names and aliases.
@@ -403,6 +412,7 @@ macro_rules! execute_lang_method {
       S::Cpp => Cpp.$method($($pname,)*),
       S::CSharp => CSharp.$method($($pname,)*),
       S::Css => Css.$method($($pname,)*),
+      S::Dart => Dart.$method($($pname,)*),
       S::Elixir => Elixir.$method($($pname,)*),
       S::Go => Go.$method($($pname,)*),
       S::Haskell => Haskell.$method($($pname,)*),
@@ -412,6 +422,7 @@ macro_rules! execute_lang_method {
       S::Json => Json.$method($($pname,)*),
       S::Kotlin => Kotlin.$method($($pname,)*),
       S::Lua => Lua.$method($($pname,)*),
+      S::Nim => Nim.$method($($pname,)*),
       S::Php => Php.$method($($pname,)*),
       S::Python => Python.$method($($pname,)*),
       S::Ruby => Ruby.$method($($pname,)*),
@@ -421,6 +432,7 @@ macro_rules! execute_lang_method {
       S::Tsx => Tsx.$method($($pname,)*),
       S::TypeScript => TypeScript.$method($($pname,)*),
       S::Yaml => Yaml.$method($($pname,)*),
+      S::Zig => Zig.$method($($pname,)*),
     }
   }
 }
@@ -473,6 +485,7 @@ fn extensions(lang: SupportLang) -> &'static [&'static str] {
         Cpp => &["cc", "hpp", "cpp", "c++", "hh", "cxx", "cu", "ino"],
         CSharp => &["cs"],
         Css => &["css", "scss"],
+        Dart => &["dart"],
         Elixir => &["ex", "exs"],
         Go => &["go"],
         Haskell => &["hs"],
@@ -482,6 +495,7 @@ fn extensions(lang: SupportLang) -> &'static [&'static str] {
         Json => &["json"],
         Kotlin => &["kt", "ktm", "kts"],
         Lua => &["lua"],
+        Nim => &["nim", "nims"],
         Php => &["php"],
         Python => &["py", "py3", "pyi", "bzl"],
         Ruby => &["rb", "rbw", "gemspec"],
@@ -491,6 +505,7 @@ fn extensions(lang: SupportLang) -> &'static [&'static str] {
         TypeScript => &["ts", "cts", "mts"],
         Tsx => &["tsx"],
         Yaml => &["yaml", "yml"],
+        Zig => &["zig"],
     }
 }
 
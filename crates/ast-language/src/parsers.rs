@@ -58,6 +58,9 @@ pub fn language_c_sharp() -> TSLanguage {
 pub fn language_css() -> TSLanguage {
     into_napi_lang!(tree_sitter_css::LANGUAGE)
 }
+pub fn language_dart() -> TSLanguage {
+    into_lang!(tree_sitter_dart)
+}
 pub fn language_elixir() -> TSLanguage {
     into_lang!(tree_sitter_elixir)
 }
@@ -85,6 +88,9 @@ pub fn language_kotlin() -> TSLanguage {
 pub fn language_lua() -> TSLanguage {
     into_lang!(tree_sitter_lua)
 }
+pub fn language_nim() -> TSLanguage {
+    into_lang!(tree_sitter_nim)
+}
 pub fn language_php() -> TSLanguage {
     into_lang!(tree_sitter_php, LANGUAGE_PHP_ONLY)
 }
@@ -112,3 +118,6 @@ pub fn language_typescript() -> TSLanguage {
 pub fn language_yaml() -> TSLanguage {
     into_lang!(tree_sitter_yaml)
 }
+pub fn language_zig() -> TSLanguage {
+    into_lang!(tree_sitter_zig)
+}
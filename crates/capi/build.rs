@@ -0,0 +1,26 @@
+//! Generates `include/outgrep.h` from the crate's `extern "C"` functions on
+//! every build, the same way `crates/core/build.rs` generates the man page
+//! and shell completions rather than checking them in.
+
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_path = PathBuf::from(&crate_dir).join("include/outgrep.h");
+
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file(&out_path);
+        }
+        Err(err) => {
+            println!("cargo:warning=failed to generate outgrep.h: {}", err);
+        }
+    }
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=src/search.rs");
+    println!("cargo:rerun-if-changed=src/outline.rs");
+    println!("cargo:rerun-if-changed=src/metrics.rs");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,108 @@
+//! Shared C ABI plumbing: turning `*const c_char` inputs into Rust values,
+//! turning `Result`s into either a heap-allocated JSON string or a `NULL`
+//! plus a message retrievable through [`outgrep_last_error`], and freeing
+//! strings this crate handed back across the boundary.
+
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    path::PathBuf,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(message: impl std::fmt::Display) {
+    let message = CString::new(message.to_string()).unwrap_or_else(|_| {
+        CString::new("<error message contained a nul byte>").unwrap()
+    });
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// Return a description of the most recent error on this thread, or `NULL`
+/// if the last call into this library succeeded.
+///
+/// The returned pointer is owned by this library and is only valid until
+/// the next call into it on the same thread; callers who need to keep it
+/// around must copy it.
+#[no_mangle]
+pub extern "C" fn outgrep_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow().as_ref().map_or(std::ptr::null(), |s| s.as_ptr())
+    })
+}
+
+/// Free a string previously returned by any `outgrep_*_json` function.
+///
+/// # Safety
+///
+/// `s` must either be `NULL` (a no-op) or a pointer this library itself
+/// returned that hasn't already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn outgrep_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must be `NULL` or a valid, nul-terminated string, and it must
+/// remain valid for the duration of this call.
+pub(crate) unsafe fn path_from_c(
+    ptr: *const c_char,
+) -> Result<PathBuf, &'static str> {
+    if ptr.is_null() {
+        return Err("path argument was NULL");
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(PathBuf::from)
+        .map_err(|_| "path argument was not valid UTF-8")
+}
+
+/// # Safety
+///
+/// `ptr` must be `NULL` or a valid, nul-terminated string, and it must
+/// remain valid for the duration of this call.
+pub(crate) unsafe fn str_from_c(
+    ptr: *const c_char,
+) -> Result<&'static str, &'static str> {
+    if ptr.is_null() {
+        return Err("string argument was NULL");
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| "string argument was not valid UTF-8")
+}
+
+/// Serialize `value` to JSON and hand it back as an owned C string, or
+/// record `err` as the last error and return `NULL`.
+pub(crate) fn ok_json_or_error<T: serde::Serialize>(
+    result: Result<T, impl std::fmt::Display>,
+) -> *mut c_char {
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(json) => match CString::new(json) {
+                Ok(c) => {
+                    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+                    c.into_raw()
+                }
+                Err(e) => {
+                    set_last_error(e);
+                    std::ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_last_error(e);
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            set_last_error(e);
+            std::ptr::null_mut()
+        }
+    }
+}
@@ -0,0 +1,113 @@
+/*!
+A stable C ABI over a slice of outgrep's search, outline, and metrics
+functionality, for embedding outgrep directly into non-Rust tools (editors,
+Python/Node bindings) instead of shelling out to the `og` binary.
+
+## Functionality
+
+- `outgrep_search_json`: run a regex search over one file.
+- `outgrep_outline_json`: extract a file's symbol outline.
+- `outgrep_metrics_json`: calculate code metrics for one file.
+- `outgrep_last_error` / `outgrep_free_string`: error reporting and cleanup
+  shared by all three.
+
+Every `outgrep_*_json` function returns a heap-allocated, nul-terminated
+JSON string that the caller must eventually pass to `outgrep_free_string`,
+or `NULL` on failure, in which case `outgrep_last_error` describes what
+went wrong on the calling thread.
+
+## Usage
+
+```c
+#include "outgrep.h"
+
+char *json = outgrep_search_json("TODO", "src/main.rs");
+if (json == NULL) {
+    fprintf(stderr, "search failed: %s\n", outgrep_last_error());
+} else {
+    puts(json);
+    outgrep_free_string(json);
+}
+```
+
+## Architecture
+
+This crate is a thin FFI shim, not a reimplementation: `outgrep_search_json`
+calls into the same `grep` facade crate `crates/core/search.rs` builds its
+`SearchWorker` on, and `outgrep_outline_json`/`outgrep_metrics_json` call
+straight into `ripgrep::diagnostics`. `SearchWorker` itself isn't used here
+because it's built around CLI flag parsing (`HiArgs`), not a reusable
+library entry point.
+
+A header (`include/outgrep.h`) is regenerated by `build.rs` on every build
+via `cbindgen`, the same way `crates/core/build.rs` regenerates the man
+page and shell completions rather than checking generated output in.
+
+## Dependencies
+
+- `grep`: the regex matcher and searcher backing `outgrep_search_json`.
+- `ripgrep`: the `diagnostics` module backing the outline and metrics
+  functions.
+- `serde_json`: encodes every response as JSON.
+*/
+
+mod ffi;
+mod metrics;
+mod outline;
+mod search;
+
+use std::os::raw::c_char;
+
+use ffi::{ok_json_or_error, path_from_c, str_from_c};
+
+pub use ffi::{outgrep_free_string, outgrep_last_error};
+
+/// Run a regex search for `pattern` over the file at `path` and return the
+/// matches as a JSON array of `{"line_number": ..., "text": ...}` objects.
+///
+/// # Safety
+///
+/// `pattern` and `path` must each be `NULL` or a valid, nul-terminated,
+/// UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn outgrep_search_json(
+    pattern: *const c_char,
+    path: *const c_char,
+) -> *mut c_char {
+    let result = (|| -> anyhow::Result<_> {
+        let pattern = str_from_c(pattern).map_err(anyhow::Error::msg)?;
+        let path = path_from_c(path).map_err(anyhow::Error::msg)?;
+        search::search_file(pattern, &path)
+    })();
+    ok_json_or_error(result)
+}
+
+/// Extract the symbol outline for the file at `path` as JSON.
+///
+/// # Safety
+///
+/// `path` must be `NULL` or a valid, nul-terminated, UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn outgrep_outline_json(
+    path: *const c_char,
+) -> *mut c_char {
+    let result = path_from_c(path)
+        .map_err(anyhow::Error::msg)
+        .and_then(|path| outline::outline_file(&path));
+    ok_json_or_error(result)
+}
+
+/// Calculate code metrics for the file at `path` as JSON.
+///
+/// # Safety
+///
+/// `path` must be `NULL` or a valid, nul-terminated, UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn outgrep_metrics_json(
+    path: *const c_char,
+) -> *mut c_char {
+    let result = path_from_c(path)
+        .map_err(anyhow::Error::msg)
+        .and_then(|path| metrics::metrics_for_file(&path));
+    ok_json_or_error(result)
+}
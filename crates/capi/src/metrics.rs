@@ -0,0 +1,14 @@
+//! `outgrep_metrics_json`: calculate code metrics for a single file.
+//!
+//! Delegates to [`ripgrep::diagnostics::MetricsCalculator`], the same
+//! calculator `--analyze` uses.
+
+use std::{fs, path::Path};
+
+use ripgrep::diagnostics::{CodeMetrics, MetricsCalculator};
+
+pub(crate) fn metrics_for_file(path: &Path) -> anyhow::Result<CodeMetrics> {
+    let content = fs::read_to_string(path)?;
+    MetricsCalculator::calculate_metrics(path, &content)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+}
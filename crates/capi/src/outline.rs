@@ -0,0 +1,17 @@
+//! `outgrep_outline_json`: extract a file's symbol outline.
+//!
+//! Delegates straight to [`ripgrep::diagnostics::extract_ast_structure`],
+//! the same tree-sitter-backed extractor `--tree`/`--analyze` use.
+
+use std::path::Path;
+
+use ripgrep::diagnostics::AstStructure;
+
+pub(crate) fn outline_file(path: &Path) -> anyhow::Result<AstStructure> {
+    ripgrep::diagnostics::extract_ast_structure(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{}: unsupported language, unreadable, or empty",
+            path.display()
+        )
+    })
+}
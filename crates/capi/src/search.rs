@@ -0,0 +1,56 @@
+//! `outgrep_search_json`: run a regex search over a single file.
+//!
+//! This calls straight into the `grep` facade crate -- the same
+//! `grep::regex`/`grep::searcher` machinery `crates/core/search.rs` builds
+//! its much larger `SearchWorker` on top of -- rather than reaching into
+//! `SearchWorker` itself, which is tied to CLI flag parsing and isn't part
+//! of any crate's public API.
+
+use std::path::Path;
+
+use grep::{
+    regex::RegexMatcherBuilder,
+    searcher::{Sink, SinkMatch},
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub(crate) struct SearchMatch {
+    line_number: Option<u64>,
+    text: String,
+}
+
+struct CollectMatches<'a> {
+    matches: &'a mut Vec<SearchMatch>,
+}
+
+impl<'a> Sink for CollectMatches<'a> {
+    type Error = std::io::Error;
+
+    fn matched(
+        &mut self,
+        _searcher: &grep::searcher::Searcher,
+        mat: &SinkMatch<'_>,
+    ) -> Result<bool, std::io::Error> {
+        let text = String::from_utf8_lossy(mat.bytes()).into_owned();
+        self.matches.push(SearchMatch {
+            line_number: mat.line_number(),
+            text: text.trim_end_matches(['\n', '\r']).to_string(),
+        });
+        Ok(true)
+    }
+}
+
+pub(crate) fn search_file(
+    pattern: &str,
+    path: &Path,
+) -> anyhow::Result<Vec<SearchMatch>> {
+    let matcher = RegexMatcherBuilder::new().build(pattern)?;
+    let mut matches = Vec::new();
+    grep::searcher::Searcher::new().search_path(
+        &matcher,
+        path,
+        CollectMatches { matches: &mut matches },
+    )?;
+    Ok(matches)
+}
@@ -0,0 +1,120 @@
+/*!
+AST-symbol multiline matching, for `--ast-multiline`.
+
+Normally the search pattern is matched line by line, so a pattern spanning
+a formatted call chain or a multi-line function signature only matches
+with a hand-written `(?s)` regex and knowledge of exactly how the file
+happens to be wrapped. This flag instead parses the whole file with
+tree-sitter, joins each function, class, type, and module definition's
+text onto a single line with runs of whitespace collapsed to one space,
+and matches the ordinary search pattern against that normalized text
+instead of against the file's lines.
+*/
+
+use outgrep_ast_language::SupportLang;
+
+use crate::diagnostics::types::SymbolInfo;
+
+/// A single AST symbol definition, with its text normalized for
+/// `--ast-multiline` matching.
+#[derive(Debug, Clone)]
+pub(crate) struct AstSymbolText {
+    /// 1-based line the symbol starts on.
+    pub(crate) line: usize,
+    /// 1-based, character-based column the symbol starts on.
+    pub(crate) column: usize,
+    /// The AST node kind backing this symbol, e.g. `function_item`.
+    pub(crate) symbol_type: String,
+    /// The symbol's name.
+    pub(crate) name: String,
+    /// The symbol's source text, joined onto one line with runs of
+    /// whitespace collapsed to a single space.
+    pub(crate) text: String,
+}
+
+/// Parse `content` as `lang` and return every function, class, type, and
+/// module definition with its text normalized onto a single line, in the
+/// order they appear in the file.
+///
+/// Returns an empty list if `lang` isn't supported by the AST layer or if
+/// `content` fails to parse, the same as a file with no matches.
+pub(crate) fn ast_symbol_texts(
+    lang: SupportLang,
+    content: &str,
+) -> Vec<AstSymbolText> {
+    let Some(structure) =
+        crate::diagnostics::ast_extractor::extract_ast_info_for_language(
+            lang, content,
+        )
+    else {
+        return Vec::new();
+    };
+    let symbols = structure.symbols;
+    let mut texts: Vec<AstSymbolText> = symbols
+        .functions
+        .into_iter()
+        .chain(symbols.classes)
+        .chain(symbols.types)
+        .chain(symbols.modules)
+        .map(|s| normalize(content, s))
+        .collect();
+    texts.sort_by_key(|s| (s.line, s.column));
+    texts
+}
+
+/// Slice `symbol`'s byte range out of `content` and collapse its
+/// whitespace onto a single line.
+fn normalize(content: &str, symbol: SymbolInfo) -> AstSymbolText {
+    let text = content
+        .get(symbol.range.clone())
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    AstSymbolText {
+        line: symbol.line as usize,
+        column: symbol.column as usize,
+        symbol_type: symbol.symbol_type,
+        name: symbol.name,
+        text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_a_multiline_signature_onto_one_line() {
+        let content = "fn read(\n    path: &Path,\n    mode: &str,\n) -> Result<Vec<u8>> {\n    todo!()\n}\n";
+        let symbols = ast_symbol_texts(SupportLang::Rust, content);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(
+            symbols[0].text,
+            "fn read( path: &Path, mode: &str, ) -> Result<Vec<u8>> { todo!() }"
+        );
+    }
+
+    #[test]
+    fn matches_a_pattern_spanning_a_call_chain() {
+        let content = "fn build() {\n    Foo::new()\n        .bar()\n        .baz();\n}\n";
+        let symbols = ast_symbol_texts(SupportLang::Rust, content);
+        assert_eq!(symbols.len(), 1);
+        assert!(symbols[0].text.contains("Foo::new() .bar() .baz();"));
+    }
+
+    #[test]
+    fn reports_symbol_type_and_name() {
+        let content = "fn helper() {}\n";
+        let symbols = ast_symbol_texts(SupportLang::Rust, content);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].symbol_type, "function_item");
+        assert_eq!(symbols[0].name, "helper");
+    }
+
+    #[test]
+    fn no_symbols_returns_empty() {
+        let content = "let x = 1;\n";
+        assert!(ast_symbol_texts(SupportLang::Rust, content).is_empty());
+    }
+}
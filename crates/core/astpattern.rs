@@ -0,0 +1,105 @@
+/*!
+Structural AST pattern search over source code, for `--pattern`.
+
+Normally the search pattern is matched against lines of text (or, with a
+regex engine, byte spans within them). This flag instead parses the whole
+file with tree-sitter and matches an ast-grep style pattern -- source code
+containing metavariables like `$COND` or `$X` -- against nodes of the
+parsed tree, the same structural matching engine already vendored for
+`--find-duplicates` and the AST-config rule system. Results are reported at
+the granularity of whole matched nodes, labeled with the line/column the
+match starts at, since a structural match rarely lines up with a single
+line of text.
+*/
+
+use outgrep_ast_core::matcher::Pattern;
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+
+/// A single AST node matched by a [`AstPatternQuery`].
+#[derive(Debug, Clone)]
+pub(crate) struct AstMatch {
+    /// 1-based line the match starts on.
+    pub(crate) line: usize,
+    /// 1-based, character-based column the match starts on.
+    pub(crate) column: usize,
+    /// The exact source text of the matched node.
+    pub(crate) text: String,
+}
+
+/// A parsed `--pattern` query: an ast-grep style pattern paired with the
+/// `--lang` it was parsed as, e.g. `if ($COND) { return $X; }` for
+/// [`SupportLang::Rust`].
+#[derive(Debug, Clone)]
+pub(crate) struct AstPatternQuery {
+    lang: SupportLang,
+    pattern: Pattern,
+}
+
+impl AstPatternQuery {
+    /// Parse `expr` as a structural pattern for `lang`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `expr` doesn't parse as valid syntax for `lang`.
+    pub(crate) fn parse(
+        lang: SupportLang,
+        expr: &str,
+    ) -> anyhow::Result<AstPatternQuery> {
+        let pattern = Pattern::try_new(expr, lang).map_err(|e| {
+            anyhow::anyhow!("invalid --pattern for {}: {}", lang, e)
+        })?;
+        Ok(AstPatternQuery { lang, pattern })
+    }
+
+    /// Parse `content` as this query's language and return every node
+    /// matching its pattern, in the order they're found.
+    pub(crate) fn matches(&self, content: &str) -> Vec<AstMatch> {
+        let root = self.lang.ast_grep(content);
+        root.root()
+            .find_all(&self.pattern)
+            .map(|node_match| {
+                let pos = node_match.start_pos();
+                AstMatch {
+                    line: pos.line() + 1,
+                    column: pos.column(&node_match) + 1,
+                    text: node_match.text().into_owned(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_if_return_with_metavars() {
+        let query = AstPatternQuery::parse(
+            SupportLang::Rust,
+            "if $COND { return $X; }",
+        )
+        .unwrap();
+        let content =
+            "fn f(b: bool) -> i32 {\n    if b { return 1; }\n    0\n}\n";
+        let matches = query.matches(content);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 2);
+        assert_eq!(matches[0].text, "if b { return 1; }");
+    }
+
+    #[test]
+    fn no_match_returns_empty() {
+        let query =
+            AstPatternQuery::parse(SupportLang::Rust, "while $COND {}")
+                .unwrap();
+        let content = "fn f() {}\n";
+        assert!(query.matches(content).is_empty());
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        assert!(AstPatternQuery::parse(SupportLang::Rust, "(((").is_err());
+    }
+}
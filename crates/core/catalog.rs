@@ -0,0 +1,118 @@
+/*!
+A small catalog of user-facing strings, with locale selection.
+
+Banners, summaries, and error strings used to be hard-coded English
+scattered across whichever module happened to print them, which made
+wording inconsistent (some strings said "ripgrep", others "outgrep") and
+gave localization nowhere to hook in. This module centralizes the most
+visible of those strings behind a `Message` enum and a `Locale` that
+`Message::text` consults, so future translations only need to add a new
+`text_<locale>` method instead of hunting down call sites.
+
+TODO: only `Locale::En` is implemented. This covers the version banner,
+the "nothing searched" hint, the pattern-required error, and the `--doctor`
+and `--usage-summary` report headers; the much larger set of flag help text
+and less commonly seen error strings has not been migrated yet.
+*/
+
+use std::sync::OnceLock;
+
+/// The set of locales outgrep knows how to speak.
+///
+/// Only `En` is implemented today; anything else falls back to it. See
+/// `Locale::detect` for how the active locale is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Locale {
+    En,
+}
+
+impl Locale {
+    /// Determine the active locale from `OUTGREP_LOCALE`, falling back to
+    /// the standard `LANG` environment variable, and finally to `En` if
+    /// neither is set or recognized.
+    fn detect() -> Locale {
+        let raw = std::env::var("OUTGREP_LOCALE")
+            .or_else(|_| std::env::var("LANG"))
+            .unwrap_or_default();
+        match raw.split(['_', '.']).next() {
+            Some("en") | None => Locale::En,
+            Some(_) => Locale::En,
+        }
+    }
+}
+
+fn active_locale() -> Locale {
+    static LOCALE: OnceLock<Locale> = OnceLock::new();
+    *LOCALE.get_or_init(Locale::detect)
+}
+
+/// A user-facing message outgrep can print, keyed by name so its wording
+/// lives in one place instead of scattered across `println!`/`err_message!`
+/// call sites.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Message {
+    /// The product name shown in the `--version` banner and report titles.
+    ProductName,
+    /// Shown when a search implicitly walked the working directory but
+    /// found nothing to search, e.g. because of overly broad ignore rules.
+    NothingSearched,
+    /// Shown when a search was requested without a pattern.
+    PatternRequired,
+    /// Title line of the `--doctor` report.
+    DoctorReportTitle,
+    /// Shown at the end of `--doctor` when every check passed.
+    DoctorAllOk,
+    /// Shown at the end of `--doctor` when at least one check needs
+    /// attention.
+    DoctorNeedsAttention,
+    /// Title line of the `--usage-summary` report.
+    UsageSummaryTitle,
+    /// Shown by `--usage-summary` when no history has been recorded yet.
+    UsageSummaryEmpty,
+}
+
+impl Message {
+    /// Return this message's text in the active locale.
+    pub(crate) fn text(self) -> &'static str {
+        match active_locale() {
+            Locale::En => self.text_en(),
+        }
+    }
+
+    fn text_en(self) -> &'static str {
+        match self {
+            Message::ProductName => "outgrep",
+            Message::NothingSearched => {
+                "No files were searched, which means outgrep probably \
+                 applied a filter you didn't expect.\n\
+                 Running with --debug will show why files are being \
+                 skipped."
+            }
+            Message::PatternRequired => {
+                "outgrep requires at least one pattern to execute a search"
+            }
+            Message::DoctorReportTitle => "Outgrep Doctor",
+            Message::DoctorAllOk => "Everything looks good.",
+            Message::DoctorNeedsAttention => {
+                "Some checks need attention; see the fixes above."
+            }
+            Message::UsageSummaryTitle => "Outgrep Usage Summary",
+            Message::UsageSummaryEmpty => "No search history recorded yet.",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrecognized_lang_falls_back_to_english() {
+        assert_eq!(Locale::En, Locale::detect());
+    }
+
+    #[test]
+    fn product_name_is_outgrep_not_ripgrep() {
+        assert_eq!("outgrep", Message::ProductName.text());
+    }
+}
@@ -0,0 +1,82 @@
+/*!
+Implements `og --definition IDENT`, a repo-wide lookup for where an
+identifier is defined.
+
+Like [`crate::symbols`], this walks the search paths and parses each file
+with the AST extraction behind `--tree --syntax`
+([`crate::diagnostics::extract_ast_structure`]), but instead of printing
+every symbol it finds, it only prints the ones named `IDENT`. Because the
+AST layer only records symbol *definitions* -- functions, classes, types,
+and modules -- and never the places those names are merely called or
+mentioned, this naturally distinguishes a definition site from the many
+plain-text references `og IDENT` would otherwise turn up.
+*/
+
+use crate::diagnostics::extract_ast_structure;
+use crate::diagnostics::types::SymbolInfo;
+use crate::flags::HiArgs;
+
+/// Print every definition of `ident` found under `args`'s search paths,
+/// respecting the walker's usual ignore rules. Returns whether any
+/// definitions were found.
+pub(crate) fn run(args: &HiArgs, ident: &str) -> anyhow::Result<bool> {
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut found_any = false;
+    for haystack in haystacks {
+        let path = haystack.path();
+        // Unsupported languages and files that fail to parse have no
+        // definitions to report, the same as a file with no matches.
+        let Some(structure) = extract_ast_structure(path) else { continue };
+        let mut definitions: Vec<&SymbolInfo> = structure
+            .symbols
+            .functions
+            .iter()
+            .chain(&structure.symbols.classes)
+            .chain(&structure.symbols.types)
+            .chain(&structure.symbols.modules)
+            .filter(|symbol| symbol.name == ident)
+            .collect();
+        if definitions.is_empty() {
+            continue;
+        }
+        definitions.sort_by_key(|s| s.line);
+        found_any = true;
+
+        if args.json_output() {
+            for symbol in &definitions {
+                let message = serde_json::json!({
+                    "type": "definition",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "name": symbol.name,
+                        "symbol_type": symbol.symbol_type,
+                        "line_number": symbol.line,
+                        "column": symbol.column,
+                    },
+                });
+                println!("{}", message);
+            }
+        } else {
+            for symbol in &definitions {
+                println!(
+                    "{}:{}:{} {} {}",
+                    path.display(),
+                    symbol.line,
+                    symbol.column,
+                    symbol.symbol_type,
+                    symbol.name
+                );
+            }
+        }
+    }
+
+    if !found_any && !args.json_output() {
+        println!("No definition of '{}' found under the search paths.", ident);
+    }
+    Ok(found_any)
+}
@@ -36,38 +36,59 @@ pub fn extract_ast_structure(file_path: &Path) -> Option<AstStructure> {
     extract_ast_info_for_language(language, &content)
 }
 
+/// Build an [`AstStructure`] from an already-parsed root node.
+///
+/// This is the shared tail of [`extract_ast_info_for_language`] and is
+/// also used by [`crate::diagnostics::parse_cache::ParseCache`], which
+/// keeps its own root node alive across incremental re-parses instead of
+/// parsing from scratch each time.
+pub(crate) fn build_ast_structure<D: Doc>(
+    language: SupportLang,
+    root: &Node<D>,
+) -> Option<AstStructure> {
+    let content = root.text();
+    if root.range().start == 0 && root.range().end == 0 && !content.is_empty()
+    {
+        return None; // Parsing failed
+    }
+
+    // Extract basic syntax highlighting (simplified)
+    let syntax_tokens = extract_syntax_tokens(root);
+
+    // Extract root AST nodes (with depth limit to avoid huge structures)
+    let root_nodes = if let Some(node_info) = extract_node_info(root, 3, 0) {
+        vec![node_info]
+    } else {
+        Vec::new()
+    };
+
+    // Extract symbol information
+    let symbols = extract_symbols(language, root);
+
+    // Extract embedded-language regions (e.g. <script>/<style> in HTML)
+    let injections =
+        crate::diagnostics::injections::find_injections(language, &content);
+
+    Some(AstStructure {
+        language: format!("{:?}", language), // Use Debug format for now
+        root_nodes,
+        syntax_tokens,
+        symbols,
+        injections,
+    })
+}
+
 /// Extract AST information for a specific language and content.
-fn extract_ast_info_for_language(language: SupportLang, content: &str) -> Option<AstStructure> {
+pub(crate) fn extract_ast_info_for_language(
+    language: SupportLang,
+    content: &str,
+) -> Option<AstStructure> {
     macro_rules! extract_ast {
         ($lang_impl:expr) => {{
             // Try to parse the source with ast-grep
             let ast_grep = $lang_impl.ast_grep(content);
-            
-            // Check if parsing actually succeeded by trying to get the root
             let root = ast_grep.root();
-            if root.range().start == 0 && root.range().end == 0 && !content.is_empty() {
-                return None; // Parsing failed
-            }
-            
-            // Extract basic syntax highlighting (simplified)
-            let syntax_tokens = extract_syntax_tokens(&root);
-            
-            // Extract root AST nodes (with depth limit to avoid huge structures)
-            let root_nodes = if let Some(node_info) = extract_node_info(&root, 3, 0) {
-                vec![node_info]
-            } else {
-                Vec::new()
-            };
-            
-            // Extract symbol information
-            let symbols = extract_symbols(&root);
-
-            Some(AstStructure {
-                language: format!("{:?}", language), // Use Debug format for now
-                root_nodes,
-                syntax_tokens,
-                symbols,
-            })
+            return build_ast_structure(language, &root);
         }};
     }
 
@@ -95,6 +116,9 @@ fn extract_ast_info_for_language(language: SupportLang, content: &str) -> Option
         SupportLang::Json => extract_ast!(outgrep_ast_language::Json),
         SupportLang::Yaml => extract_ast!(outgrep_ast_language::Yaml),
         SupportLang::Tsx => extract_ast!(outgrep_ast_language::Tsx),
+        SupportLang::Zig => extract_ast!(outgrep_ast_language::Zig),
+        SupportLang::Dart => extract_ast!(outgrep_ast_language::Dart),
+        SupportLang::Nim => extract_ast!(outgrep_ast_language::Nim),
     }
 }
 
@@ -311,15 +335,18 @@ fn is_named_entity(kind: &str) -> bool {
 }
 
 /// Extract symbol information for the symbol summary.
-fn extract_symbols<D: Doc>(node: &Node<D>) -> AstSymbolSummary {
+fn extract_symbols<D: Doc>(
+    language: SupportLang,
+    node: &Node<D>,
+) -> AstSymbolSummary {
     let mut symbols = AstSymbolSummary::default();
-    
+
     // Traverse the AST and collect symbols
     for ast_node in node.dfs() {
         let kind = ast_node.kind();
         let range = ast_node.range();
         let start_pos = ast_node.start_pos();
-        
+
         if let Some(name) = extract_symbol_name(&ast_node) {
             let symbol_info = SymbolInfo {
                 name,
@@ -327,6 +354,10 @@ fn extract_symbols<D: Doc>(node: &Node<D>) -> AstSymbolSummary {
                 range,
                 line: (start_pos.line() + 1) as u32, // 1-based line numbers
                 column: (start_pos.column(&ast_node) + 1) as u32, // 1-based column numbers
+                doc_comment: extract_doc_comment(&ast_node),
+                signature: crate::diagnostics::signature::extract_signature(
+                    language, &ast_node,
+                ),
             };
             
             // Categorize symbol by type
@@ -351,6 +382,62 @@ fn extract_symbols<D: Doc>(node: &Node<D>) -> AstSymbolSummary {
     symbols
 }
 
+/// Check if a node type represents a comment, across the languages we
+/// support (tree-sitter grammars name these differently).
+fn is_comment_kind(kind: &str) -> bool {
+    matches!(kind, "comment" | "line_comment" | "block_comment")
+}
+
+/// Strip a single comment line's leading marker (`///`, `//!`, `//`, `#`,
+/// or the `*`/`/**`/`*/` used in block comments) and surrounding
+/// whitespace, so doc comments read the same regardless of language.
+fn strip_comment_marker(line: &str) -> String {
+    let line = line.trim();
+    let line = line
+        .strip_prefix("///")
+        .or_else(|| line.strip_prefix("//!"))
+        .or_else(|| line.strip_prefix("//"))
+        .or_else(|| line.strip_prefix("/**"))
+        .or_else(|| line.strip_prefix("/*"))
+        .unwrap_or(line);
+    let line = line.strip_suffix("*/").unwrap_or(line);
+    let line = line.strip_prefix('#').unwrap_or(line);
+    let line = line.strip_prefix('*').unwrap_or(line);
+    line.trim().to_string()
+}
+
+/// Extract the leading doc comment block immediately preceding `node`, if
+/// any. Consecutive comment siblings directly above the symbol (no blank
+/// statement in between) are treated as a single block, oldest first.
+fn extract_doc_comment<D: Doc>(node: &Node<D>) -> Option<String> {
+    let mut comments = Vec::new();
+    let mut current = node.prev();
+    while let Some(sibling) = current {
+        if !is_comment_kind(&sibling.kind()) {
+            break;
+        }
+        comments.push(sibling.text().to_string());
+        current = sibling.prev();
+    }
+    if comments.is_empty() {
+        return None;
+    }
+    comments.reverse();
+    let text = comments
+        .iter()
+        .flat_map(|c| c.lines())
+        .map(strip_comment_marker)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,4 +476,31 @@ mod tests {
         // Clean up
         let _ = std::fs::remove_file(&temp_file);
     }
+
+    #[test]
+    fn test_ast_extraction_rust_doc_comment() {
+        let temp_file = std::env::temp_dir().join("test_doc_comment.rs");
+        std::fs::write(
+            &temp_file,
+            "/// Adds two numbers together.\n/// Returns their sum.\nfn add(a: i32, b: i32) -> i32 { a + b }",
+        )
+        .unwrap();
+
+        let ast_structure = extract_ast_structure(&temp_file);
+        assert!(ast_structure.is_some());
+
+        let ast = ast_structure.unwrap();
+        let add_fn = ast
+            .symbols
+            .functions
+            .iter()
+            .find(|f| f.name == "add")
+            .expect("add function should be extracted");
+        assert_eq!(
+            Some("Adds two numbers together.\nReturns their sum.".to_string()),
+            add_fn.doc_comment
+        );
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,79 @@
+//! Groups `FileWatcher` events into debounced, deduped batches, so a
+//! consumer that maintains derived state (an index, a dashboard) can apply
+//! a burst of edits atomically instead of reacting to each event as it
+//! arrives.
+
+use crate::diagnostics::types::FileChangeEvent;
+use crate::diagnostics::watcher::FileWatcher;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A group of `FileChangeEvent`s observed within a single debounce window.
+///
+/// `generation` increases by one with every batch, so a consumer can tell
+/// whether it's still looking at the latest batch it applied.
+#[derive(Debug, Clone)]
+pub struct ChangeSet {
+    pub generation: u64,
+    pub events: Vec<FileChangeEvent>,
+}
+
+/// Groups the events from a `FileWatcher` into debounced, deduped
+/// `ChangeSet`s.
+pub struct ChangeBatcher {
+    watcher: FileWatcher,
+    debounce: Duration,
+    generation: u64,
+}
+
+impl ChangeBatcher {
+    /// Wrap `watcher`, grouping its events into batches separated by at
+    /// least `debounce` of inactivity.
+    pub fn new(watcher: FileWatcher, debounce: Duration) -> Self {
+        ChangeBatcher { watcher, debounce, generation: 0 }
+    }
+
+    /// Wait for and return the next batch of changes, or `None` once the
+    /// underlying watcher's channel has closed.
+    ///
+    /// A batch starts with whatever event arrives next, and keeps growing
+    /// for as long as another event arrives within `debounce` of the last
+    /// one. Events are then deduplicated by path, keeping only the most
+    /// recent event per path (in its original order) -- a consumer
+    /// rebuilding current state only cares what a path looks like now, not
+    /// every intermediate write it went through this window.
+    pub async fn next_batch(&mut self) -> Option<ChangeSet> {
+        let first = self.watcher.next_event().await?;
+        let mut events = vec![first];
+        while let Some(event) =
+            self.watcher.next_event_timeout(self.debounce).await
+        {
+            events.push(event);
+        }
+
+        self.generation += 1;
+        Some(ChangeSet {
+            generation: self.generation,
+            events: coalesce_by_path(events),
+        })
+    }
+}
+
+/// Collapse `events` down to the most recent event for each path, preserving
+/// the order in which each path was first seen.
+fn coalesce_by_path(events: Vec<FileChangeEvent>) -> Vec<FileChangeEvent> {
+    let mut order: Vec<PathBuf> = Vec::new();
+    let mut latest: HashMap<PathBuf, FileChangeEvent> = HashMap::new();
+    for event in events {
+        let key = event.path().to_path_buf();
+        if !latest.contains_key(&key) {
+            order.push(key.clone());
+        }
+        latest.insert(key, event);
+    }
+    order
+        .into_iter()
+        .map(|key| latest.remove(&key).expect("key was just inserted"))
+        .collect()
+}
@@ -19,21 +19,52 @@ impl CompilerDiagnosticsRunner {
     }
 
     /// Run Rust compiler diagnostics using cargo check
+    ///
+    /// Scoped to the crate that owns `file_path` (via `cargo check -p`)
+    /// rather than the whole workspace, so `--watch`'s diagnostics-on-save
+    /// stays fast even in large multi-crate workspaces.
     fn run_rust_diagnostics(file_path: &Path) -> Option<FileDiagnostics> {
         // Check if we're in a Rust project (has Cargo.toml)
         let project_root = Self::find_project_root(file_path, "Cargo.toml")?;
-        
-        let output = Command::new("cargo")
-            .arg("check")
-            .arg("--message-format=json")
-            .arg("--quiet")
-            .current_dir(project_root)
-            .output()
-            .ok()?;
+
+        let mut command = Command::new("cargo");
+        command.arg("check").arg("--message-format=json").arg("--quiet");
+        if let Some(package_name) = Self::find_cargo_package_name(project_root)
+        {
+            command.arg("-p").arg(package_name);
+        }
+
+        let output = command.current_dir(project_root).output().ok()?;
 
         Self::parse_rust_diagnostics(&output.stdout, file_path)
     }
 
+    /// Read the `[package] name` out of `manifest_dir`'s Cargo.toml, if it
+    /// has one (a workspace root manifest with no `[package]` section, e.g.
+    /// a pure virtual workspace, returns `None`).
+    fn find_cargo_package_name(manifest_dir: &Path) -> Option<String> {
+        let manifest =
+            std::fs::read_to_string(manifest_dir.join("Cargo.toml")).ok()?;
+
+        let mut in_package_section = false;
+        for line in manifest.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') {
+                in_package_section = trimmed == "[package]";
+                continue;
+            }
+            if !in_package_section {
+                continue;
+            }
+            let Some(value) = trimmed.strip_prefix("name") else { continue };
+            let Some(value) = value.trim_start().strip_prefix('=') else {
+                continue;
+            };
+            return Some(value.trim().trim_matches('"').to_string());
+        }
+        None
+    }
+
     /// Run TypeScript/JavaScript diagnostics using tsc or eslint
     fn run_typescript_diagnostics(file_path: &Path) -> Option<FileDiagnostics> {
         // First try TypeScript compiler
@@ -46,15 +77,24 @@ impl CompilerDiagnosticsRunner {
     }
 
     /// Run TSC diagnostics
+    ///
+    /// Scoped to the tsconfig.json project that owns `file_path` when one
+    /// exists, so `--watch`'s diagnostics-on-save checks the file the way
+    /// an IDE would (with its project's compiler options, path mappings,
+    /// etc.) instead of type-checking it in isolation.
     fn run_tsc_diagnostics(file_path: &Path) -> Option<FileDiagnostics> {
-        let output = Command::new("npx")
-            .arg("tsc")
-            .arg("--noEmit")
-            .arg("--pretty")
-            .arg("false")
-            .arg(file_path)
-            .output()
-            .ok()?;
+        let mut command = Command::new("npx");
+        command.arg("tsc").arg("--noEmit").arg("--pretty").arg("false");
+        match Self::find_project_root(file_path, "tsconfig.json") {
+            Some(project_root) => {
+                command.arg("--project").arg(project_root);
+            }
+            None => {
+                command.arg(file_path);
+            }
+        }
+
+        let output = command.output().ok()?;
 
         Self::parse_tsc_diagnostics(&output.stdout, file_path)
     }
@@ -282,9 +322,17 @@ impl CompilerDiagnosticsRunner {
         // Extract line and column from parentheses
         let paren_start = location_part.rfind('(')?;
         let paren_end = location_part.rfind(')')?;
+
+        // When tsc is run project-wide (see `run_tsc_diagnostics`), its
+        // output covers every file in the project, not just `file_path`.
+        // Only keep diagnostics that are actually about `file_path`.
+        let reported_file = Path::new(location_part[..paren_start].trim());
+        if reported_file.file_name() != file_path.file_name() {
+            return None;
+        }
         let coords = &location_part[paren_start + 1..paren_end];
         let coord_parts: Vec<&str> = coords.split(',').collect();
-        
+
         if coord_parts.len() != 2 {
             return None;
         }
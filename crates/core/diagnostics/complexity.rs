@@ -0,0 +1,533 @@
+//! AST-based cyclomatic and cognitive complexity calculation.
+//!
+//! The line-based heuristics in `metrics.rs` guess at complexity by matching
+//! substrings like `"if "` or `"&&"` against each line of source, which
+//! double-counts constructs split across lines, misses multi-statement
+//! lines, and can't distinguish a match arm from a `match` keyword appearing
+//! in a comment. This module instead walks the real AST (via
+//! `outgrep-ast-core`) and counts decision points directly off node kinds.
+//!
+//! The set of node kinds that count as a decision point is configurable per
+//! language through `ComplexityRules`, rather than hard-coded per the old
+//! one-function-per-language design.
+
+use std::path::Path;
+
+use outgrep_ast_core::{tree_sitter::LanguageExt, Doc, Node};
+use outgrep_ast_language::SupportLang;
+
+/// The node kinds that contribute to complexity for a single language.
+///
+/// These are intentionally data, not code, so a caller (or, eventually, a
+/// config file) can override them per language without touching the walker
+/// itself.
+#[derive(Debug, Clone)]
+pub struct ComplexityRules {
+    /// Node kinds that each add one decision point on their own, e.g. `if`,
+    /// `while`, `for`, `catch`, and ternary expressions.
+    pub decision_kinds: Vec<&'static str>,
+    /// Node kinds for a `match`/`switch` expression itself. Each arm beyond
+    /// the first adds one decision point to cyclomatic complexity; the whole
+    /// construct adds a single, unnested point to cognitive complexity.
+    pub branch_container_kinds: Vec<&'static str>,
+    /// Node kinds for the individual arms/cases inside a
+    /// `branch_container_kinds` node.
+    pub branch_arm_kinds: Vec<&'static str>,
+    /// Node kinds for a binary boolean expression (e.g. `a && b`).
+    pub boolean_operator_kinds: Vec<&'static str>,
+    /// The operator token text that counts as logical AND/OR within a
+    /// `boolean_operator_kinds` node (e.g. `"&&"`, `"||"`, `"and"`, `"or"`).
+    pub boolean_operator_tokens: Vec<&'static str>,
+    /// Node kinds for a fallible short-circuiting expression, e.g. Rust's
+    /// `?` operator (`try_expression`), which introduces an implicit early
+    /// return and so counts like a decision point.
+    pub try_operator_kinds: Vec<&'static str>,
+    /// Node kinds that mark a function/method definition, used for
+    /// `function_count`.
+    pub function_kinds: Vec<&'static str>,
+}
+
+impl ComplexityRules {
+    /// Default rules for a given language, based on its tree-sitter grammar.
+    ///
+    /// Returns `None` for languages this module has no rules for yet; the
+    /// caller should fall back to a language-agnostic heuristic in that
+    /// case.
+    pub fn for_language(lang: SupportLang) -> Option<ComplexityRules> {
+        use SupportLang::*;
+        let rules = match lang {
+            Rust => ComplexityRules {
+                decision_kinds: vec![
+                    "if_expression",
+                    "while_expression",
+                    "while_let_expression",
+                    "for_expression",
+                    "loop_expression",
+                ],
+                branch_container_kinds: vec!["match_expression"],
+                branch_arm_kinds: vec!["match_arm"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec!["try_expression"],
+                function_kinds: vec!["function_item"],
+            },
+            JavaScript | TypeScript | Tsx => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "while_statement",
+                    "do_statement",
+                    "for_statement",
+                    "for_in_statement",
+                    "conditional_expression",
+                    "catch_clause",
+                ],
+                branch_container_kinds: vec!["switch_statement"],
+                branch_arm_kinds: vec!["switch_case"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||", "??"],
+                try_operator_kinds: vec!["optional_chain"],
+                function_kinds: vec![
+                    "function_declaration",
+                    "method_definition",
+                    "arrow_function",
+                    "function_expression",
+                    "generator_function_declaration",
+                ],
+            },
+            Python => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "elif_clause",
+                    "while_statement",
+                    "for_statement",
+                    "except_clause",
+                    "conditional_expression",
+                ],
+                branch_container_kinds: vec!["match_statement"],
+                branch_arm_kinds: vec!["case_clause"],
+                boolean_operator_kinds: vec!["boolean_operator"],
+                boolean_operator_tokens: vec!["and", "or"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["function_definition"],
+            },
+            Java => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "while_statement",
+                    "do_statement",
+                    "for_statement",
+                    "enhanced_for_statement",
+                    "catch_clause",
+                    "ternary_expression",
+                ],
+                branch_container_kinds: vec!["switch_expression"],
+                branch_arm_kinds: vec!["switch_block_statement_group"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["method_declaration"],
+            },
+            Go => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "for_statement",
+                ],
+                branch_container_kinds: vec![
+                    "expression_switch_statement",
+                    "type_switch_statement",
+                ],
+                branch_arm_kinds: vec![
+                    "expression_case",
+                    "default_case",
+                    "type_case",
+                ],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec![],
+                function_kinds: vec![
+                    "function_declaration",
+                    "method_declaration",
+                ],
+            },
+            C | Cpp => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "while_statement",
+                    "do_statement",
+                    "for_statement",
+                    "catch_clause",
+                    "conditional_expression",
+                ],
+                branch_container_kinds: vec!["switch_statement"],
+                branch_arm_kinds: vec!["case_statement"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["function_definition"],
+            },
+            CSharp => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "while_statement",
+                    "do_statement",
+                    "for_statement",
+                    "for_each_statement",
+                    "catch_clause",
+                    "conditional_expression",
+                ],
+                branch_container_kinds: vec!["switch_statement"],
+                branch_arm_kinds: vec!["switch_section"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["method_declaration"],
+            },
+            Ruby => ComplexityRules {
+                decision_kinds: vec![
+                    "if",
+                    "unless",
+                    "while",
+                    "until",
+                    "for",
+                    "rescue",
+                ],
+                branch_container_kinds: vec!["case"],
+                branch_arm_kinds: vec!["when"],
+                boolean_operator_kinds: vec!["binary"],
+                boolean_operator_tokens: vec!["&&", "||", "and", "or"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["method"],
+            },
+            Php => ComplexityRules {
+                decision_kinds: vec![
+                    "if_statement",
+                    "while_statement",
+                    "do_statement",
+                    "for_statement",
+                    "foreach_statement",
+                    "catch_clause",
+                    "conditional_expression",
+                ],
+                branch_container_kinds: vec!["switch_statement"],
+                branch_arm_kinds: vec!["case_statement"],
+                boolean_operator_kinds: vec!["binary_expression"],
+                boolean_operator_tokens: vec!["&&", "||"],
+                try_operator_kinds: vec![],
+                function_kinds: vec!["function_definition", "method_declaration"],
+            },
+            _ => return None,
+        };
+        Some(rules)
+    }
+}
+
+/// The result of an AST-based complexity calculation for one file.
+#[derive(Debug)]
+pub struct AstComplexity {
+    pub cyclomatic_complexity: u32,
+    pub cognitive_complexity: u32,
+    pub function_count: u32,
+    /// Deepest nesting of decision/branch constructs found anywhere in the
+    /// file (e.g. an `if` inside a `for` inside a `match` arm is depth 3).
+    pub max_nesting_depth: u32,
+    /// Line count of the longest function/method body in the file, or 0 if
+    /// the file has none.
+    pub max_function_length: u32,
+    /// Mean line count across all function/method bodies in the file, or 0.0
+    /// if the file has none.
+    pub avg_function_length: f64,
+}
+
+/// Calculate complexity for `content` by walking its AST, using the default
+/// `ComplexityRules` for `path`'s language.
+///
+/// Returns `None` if the file's language isn't supported for AST parsing or
+/// we have no complexity rules for it, so the caller can fall back to a
+/// language-agnostic heuristic.
+pub fn calculate(path: &Path, content: &str) -> Option<AstComplexity> {
+    let lang = SupportLang::from_path(path)?;
+    let rules = ComplexityRules::for_language(lang)?;
+    calculate_with_rules(lang, content, &rules)
+}
+
+/// Calculate complexity for `content`, already known to be `lang`, using the
+/// given rules. Exposed separately from `calculate` so callers (and tests)
+/// can supply custom rules.
+pub fn calculate_with_rules(
+    lang: SupportLang,
+    content: &str,
+    rules: &ComplexityRules,
+) -> Option<AstComplexity> {
+    macro_rules! walk_with {
+        ($lang_impl:expr) => {{
+            let ast_grep = $lang_impl.ast_grep(content);
+            let root = ast_grep.root();
+            if root.range().start == 0
+                && root.range().end == 0
+                && !content.is_empty()
+            {
+                return None;
+            }
+            let mut walker = ComplexityWalker::new(rules);
+            walker.walk(root, 0);
+            walker.finish()
+        }};
+    }
+
+    use SupportLang::*;
+    let result = match lang {
+        Rust => walk_with!(outgrep_ast_language::Rust),
+        JavaScript => walk_with!(outgrep_ast_language::JavaScript),
+        TypeScript => walk_with!(outgrep_ast_language::TypeScript),
+        Tsx => walk_with!(outgrep_ast_language::Tsx),
+        Python => walk_with!(outgrep_ast_language::Python),
+        Java => walk_with!(outgrep_ast_language::Java),
+        Go => walk_with!(outgrep_ast_language::Go),
+        C => walk_with!(outgrep_ast_language::C),
+        Cpp => walk_with!(outgrep_ast_language::Cpp),
+        CSharp => walk_with!(outgrep_ast_language::CSharp),
+        Ruby => walk_with!(outgrep_ast_language::Ruby),
+        Php => walk_with!(outgrep_ast_language::Php),
+        _ => return None,
+    };
+    Some(result)
+}
+
+/// Walks an AST once, accumulating cyclomatic and cognitive complexity
+/// according to a `ComplexityRules`.
+struct ComplexityWalker<'a> {
+    rules: &'a ComplexityRules,
+    cyclomatic: u32,
+    cognitive: u32,
+    function_count: u32,
+    max_nesting_depth: u32,
+    function_lengths: Vec<u32>,
+}
+
+impl<'a> ComplexityWalker<'a> {
+    fn new(rules: &'a ComplexityRules) -> ComplexityWalker<'a> {
+        ComplexityWalker {
+            rules,
+            cyclomatic: 1, // base complexity for the file/function
+            cognitive: 0,
+            function_count: 0,
+            max_nesting_depth: 0,
+            function_lengths: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> AstComplexity {
+        let max_function_length =
+            self.function_lengths.iter().copied().max().unwrap_or(0);
+        let avg_function_length = if self.function_lengths.is_empty() {
+            0.0
+        } else {
+            self.function_lengths.iter().sum::<u32>() as f64
+                / self.function_lengths.len() as f64
+        };
+        AstComplexity {
+            cyclomatic_complexity: self.cyclomatic,
+            cognitive_complexity: self.cognitive,
+            function_count: self.function_count,
+            max_nesting_depth: self.max_nesting_depth,
+            max_function_length,
+            avg_function_length,
+        }
+    }
+
+    /// Visit `node` and its descendants, where `depth` is the current
+    /// cognitive nesting depth (only incremented by decision constructs, not
+    /// every AST node).
+    fn walk<D: Doc>(&mut self, node: Node<D>, depth: u32) {
+        let kind = node.kind();
+        let kind = kind.as_ref();
+
+        let mut child_depth = depth;
+
+        if kind_list_has(&self.rules.function_kinds, kind) {
+            self.function_count += 1;
+            let lines = node.end_pos().line() - node.start_pos().line() + 1;
+            self.function_lengths.push(lines as u32);
+        } else if kind_list_has(&self.rules.decision_kinds, kind) {
+            self.cyclomatic += 1;
+            self.cognitive += 1 + depth;
+            child_depth = depth + 1;
+            self.max_nesting_depth = self.max_nesting_depth.max(child_depth);
+        } else if kind_list_has(&self.rules.branch_container_kinds, kind) {
+            // The whole switch/match adds one unnested cognitive point
+            // (mirroring how a single switch, regardless of its number of
+            // cases, is usually scored), but each arm beyond the first adds
+            // its own cyclomatic decision point, since each is an
+            // independent path through the code.
+            // Arms are either direct children (e.g. Go) or children of the
+            // construct's body node (e.g. Rust's `match_block`).
+            let arm_count = node
+                .children()
+                .map(|c| {
+                    if kind_list_has(&self.rules.branch_arm_kinds, &c.kind()) {
+                        1
+                    } else {
+                        c.children()
+                            .filter(|gc| {
+                                kind_list_has(
+                                    &self.rules.branch_arm_kinds,
+                                    &gc.kind(),
+                                )
+                            })
+                            .count()
+                    }
+                })
+                .sum::<usize>() as u32;
+            self.cyclomatic += arm_count.saturating_sub(1);
+            self.cognitive += 1 + depth;
+            child_depth = depth + 1;
+            self.max_nesting_depth = self.max_nesting_depth.max(child_depth);
+        } else if kind_list_has(&self.rules.try_operator_kinds, kind) {
+            self.cyclomatic += 1;
+            self.cognitive += 1;
+        } else if kind_list_has(&self.rules.boolean_operator_kinds, kind) {
+            if let Some(op) = self.boolean_operator_token(&node) {
+                self.cyclomatic += 1;
+                // A run of the same boolean operator (e.g. `a && b && c`)
+                // is one cognitive increment, not one per `&&`: only count
+                // it if our immediate parent isn't the same chain.
+                let parent_is_same_chain = node
+                    .parent()
+                    .map(|p| {
+                        p.kind().as_ref() == kind
+                            && self
+                                .boolean_operator_token(&p)
+                                .as_deref()
+                                == Some(op.as_str())
+                    })
+                    .unwrap_or(false);
+                if !parent_is_same_chain {
+                    self.cognitive += 1;
+                }
+            }
+        }
+
+        for child in node.children() {
+            self.walk(child, child_depth);
+        }
+    }
+
+    /// If `node` is a boolean binary operator matching one of
+    /// `boolean_operator_tokens`, return that token's text.
+    fn boolean_operator_token<D: Doc>(&self, node: &Node<D>) -> Option<String> {
+        // Most grammars expose the operator as a field directly on the
+        // binary expression node (e.g. Rust, JS, C-family). Python's
+        // `boolean_operator` node has no such field, so fall back to
+        // scanning immediate children for a token matching one of ours.
+        if let Some(op_field) = node.field("operator") {
+            let text = op_field.text();
+            return kind_list_has(&self.rules.boolean_operator_tokens, &text)
+                .then(|| text.to_string());
+        }
+        node.children().find_map(|child| {
+            let text = child.text();
+            kind_list_has(&self.rules.boolean_operator_tokens, &text)
+                .then(|| text.to_string())
+        })
+    }
+}
+
+/// Returns true if `kind` matches one of the entries in `list`.
+///
+/// A plain `Vec<&'static str>::contains` can't be used here because its
+/// argument type must match the vector's element type exactly, while `kind`
+/// borrows from the AST node and so never has a `'static` lifetime.
+fn kind_list_has(list: &[&'static str], kind: &str) -> bool {
+    list.iter().any(|&k| k == kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_rust_if_and_boolean_chain() {
+        let code = r#"
+fn f(a: bool, b: bool, c: bool) -> i32 {
+    if a && b && c {
+        1
+    } else {
+        0
+    }
+}
+"#;
+        let result =
+            calculate(&PathBuf::from("test.rs"), code).expect("rust supported");
+        // base(1) + if(1) + two `&&` operators(2) = 4
+        assert_eq!(result.cyclomatic_complexity, 4);
+        assert_eq!(result.function_count, 1);
+    }
+
+    #[test]
+    fn test_rust_match_arms_count_minus_one() {
+        let code = r#"
+fn f(x: i32) -> i32 {
+    match x {
+        1 => 1,
+        2 => 2,
+        _ => 0,
+    }
+}
+"#;
+        let result =
+            calculate(&PathBuf::from("test.rs"), code).expect("rust supported");
+        // base(1) + (3 arms - 1) = 3
+        assert_eq!(result.cyclomatic_complexity, 3);
+    }
+
+    #[test]
+    fn test_rust_try_operator_counts() {
+        let code = r#"
+fn f() -> Result<i32, ()> {
+    let x = g()?;
+    Ok(x)
+}
+fn g() -> Result<i32, ()> { Ok(1) }
+"#;
+        let result =
+            calculate(&PathBuf::from("test.rs"), code).expect("rust supported");
+        // base(1) + try(1) = 2
+        assert_eq!(result.cyclomatic_complexity, 2);
+    }
+
+    #[test]
+    fn test_unsupported_language_returns_none() {
+        assert!(calculate(&PathBuf::from("test.unknownlang"), "x").is_none());
+    }
+
+    #[test]
+    fn test_nesting_depth_tracks_deepest_decision() {
+        let code = r#"
+fn f(a: bool, b: bool) -> i32 {
+    if a {
+        if b {
+            1
+        } else {
+            0
+        }
+    } else {
+        0
+    }
+}
+"#;
+        let result =
+            calculate(&PathBuf::from("test.rs"), code).expect("rust supported");
+        assert_eq!(result.max_nesting_depth, 2);
+    }
+
+    #[test]
+    fn test_function_length_tracks_line_span() {
+        let code = "fn short() {\n    1;\n}\n\nfn long() {\n    1;\n    2;\n    3;\n    4;\n}\n";
+        let result =
+            calculate(&PathBuf::from("test.rs"), code).expect("rust supported");
+        assert_eq!(result.function_count, 2);
+        assert_eq!(result.max_function_length, 6);
+        assert_eq!(result.avg_function_length, 4.5);
+    }
+}
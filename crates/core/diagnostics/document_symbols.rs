@@ -0,0 +1,102 @@
+/*!
+`document_symbols`: a hierarchical, LSP-`DocumentSymbol`-shaped outline of a
+file's functions, classes, types, and modules, for editor plugins that want
+a symbol tree without depending on the ast-lsp server.
+
+This builds on [`extract_ast_structure`] the same way `--symbols` does, but
+nests each symbol under the smallest symbol whose byte range contains it
+(e.g. a method under its class) instead of returning the flat, per-kind
+buckets `AstSymbolSummary` stores them in.
+*/
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::ast_extractor::extract_ast_structure;
+use super::types::{AstSymbolSummary, SymbolInfo};
+
+/// One symbol in a document's outline, shaped like LSP's `DocumentSymbol`:
+/// a name, kind, source range, nested `children`, and (for flat consumers
+/// that don't want to walk the tree) the name of its immediate `container`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSymbol {
+    /// Name of the symbol.
+    pub name: String,
+    /// Symbol kind, e.g. `"function_item"`, `"struct_item"`, `"mod_item"`
+    /// -- the same AST node kind strings `SymbolInfo::symbol_type` reports.
+    pub kind: String,
+    /// Byte range in source.
+    pub range: std::ops::Range<usize>,
+    /// Line number (1-based).
+    pub line: u32,
+    /// Column number (1-based).
+    pub column: u32,
+    /// Name of the immediate enclosing symbol, if any.
+    pub container: Option<String>,
+    /// Symbols nested inside this one (e.g. a class's methods).
+    pub children: Vec<DocumentSymbol>,
+}
+
+/// Build a hierarchical outline of `path`'s symbols.
+///
+/// Returns `None` for unsupported languages, unreadable or empty files (the
+/// cases [`extract_ast_structure`] returns `None` for), and for files with
+/// no symbols.
+pub fn document_symbols(path: &Path) -> Option<Vec<DocumentSymbol>> {
+    let structure = extract_ast_structure(path)?;
+    nest_summary(structure.symbols)
+}
+
+/// Nest an already-extracted [`AstSymbolSummary`]'s flat, per-kind buckets
+/// into a `DocumentSymbol` tree, without re-parsing the file. Callers that
+/// already have a `structure.symbols` in hand (e.g. `--symbols`) should use
+/// this instead of [`document_symbols`] to avoid a redundant AST parse.
+pub fn nest_summary(summary: AstSymbolSummary) -> Option<Vec<DocumentSymbol>> {
+    let mut flat: Vec<SymbolInfo> = summary
+        .functions
+        .into_iter()
+        .chain(summary.classes)
+        .chain(summary.types)
+        .chain(summary.modules)
+        .collect();
+    if flat.is_empty() {
+        return None;
+    }
+    // Outer symbols first, so a level's scan sees an enclosing symbol
+    // before the symbols it contains.
+    flat.sort_by(|a, b| {
+        a.range.start.cmp(&b.range.start).then(b.range.end.cmp(&a.range.end))
+    });
+    let (roots, _) = nest_level(&flat, usize::MAX, None);
+    Some(roots)
+}
+
+/// Consume a run of `symbols` that starts before `end`, nesting each one's
+/// own children recursively. Returns the built nodes and how many entries
+/// of `symbols` were consumed, so the caller can resume scanning siblings.
+fn nest_level(
+    symbols: &[SymbolInfo],
+    end: usize,
+    container: Option<&str>,
+) -> (Vec<DocumentSymbol>, usize) {
+    let mut nodes = Vec::new();
+    let mut i = 0;
+    while i < symbols.len() && symbols[i].range.start < end {
+        let sym = &symbols[i];
+        i += 1;
+        let (children, consumed) =
+            nest_level(&symbols[i..], sym.range.end, Some(&sym.name));
+        i += consumed;
+        nodes.push(DocumentSymbol {
+            name: sym.name.clone(),
+            kind: sym.symbol_type.clone(),
+            range: sym.range.clone(),
+            line: sym.line,
+            column: sym.column,
+            container: container.map(str::to_string),
+            children,
+        });
+    }
+    (nodes, i)
+}
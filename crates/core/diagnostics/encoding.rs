@@ -0,0 +1,129 @@
+use std::path::Path;
+
+/// The text encoding detected for a source file, from its byte-order mark.
+///
+/// This only distinguishes the encodings that would otherwise make a file
+/// look binary to [`std::fs::read_to_string`] -- UTF-16 -- from the UTF-8
+/// (with or without a BOM) that the rest of the diagnostics pipeline
+/// assumes. Anything without a recognized BOM is treated as UTF-8.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// Name used to tag file records in JSON output.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "utf-8",
+            TextEncoding::Utf16Le => "utf-16le",
+            TextEncoding::Utf16Be => "utf-16be",
+        }
+    }
+}
+
+/// Detect a byte-order mark at the start of `bytes` and decode the rest as
+/// UTF-8 text, transcoding UTF-16 first.
+///
+/// Without this, a UTF-16 file reads as invalid UTF-8 and every caller that
+/// uses [`std::fs::read_to_string`] silently treats it as unreadable -- it
+/// drops out of `--tree --analyze`'s metrics and shows up as a single
+/// "binary file changed" line in [`crate::diagnostics::git::GitAnalyzer::get_semantic_diff`].
+/// Detecting the BOM and transcoding here lets both keep working on
+/// UTF-16 sources.
+pub fn decode_source_bytes(bytes: &[u8]) -> Option<(String, TextEncoding)> {
+    if let Some(body) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(body, TextEncoding::Utf16Le);
+    }
+    if let Some(body) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(body, TextEncoding::Utf16Be);
+    }
+    let text = if let Some(body) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        body
+    } else {
+        bytes
+    };
+    std::str::from_utf8(text).ok().map(|s| (s.to_string(), TextEncoding::Utf8))
+}
+
+/// Decode a BOM-stripped UTF-16 byte body into a `String`, respecting the
+/// endianness the BOM indicated.
+fn decode_utf16(
+    body: &[u8],
+    encoding: TextEncoding,
+) -> Option<(String, TextEncoding)> {
+    if body.len() % 2 != 0 {
+        return None;
+    }
+    let units = body.chunks_exact(2).map(|pair| match encoding {
+        TextEncoding::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+        TextEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+        TextEncoding::Utf8 => {
+            unreachable!("decode_utf16 is only called with a UTF-16 encoding")
+        }
+    });
+    let text =
+        char::decode_utf16(units).collect::<Result<String, _>>().ok()?;
+    Some((text, encoding))
+}
+
+/// Read `path` from disk, transcoding UTF-16 content (detected by its BOM)
+/// to UTF-8 so it can be treated like any other text file.
+pub fn read_source_file(path: &Path) -> Option<(String, TextEncoding)> {
+    let bytes = std::fs::read(path).ok()?;
+    decode_source_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_plain_utf8() {
+        let (text, encoding) = decode_source_bytes(b"fn main() {}").unwrap();
+        assert_eq!(text, "fn main() {}");
+        assert_eq!(encoding, TextEncoding::Utf8);
+    }
+
+    #[test]
+    fn strips_utf8_bom() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"hello");
+        let (text, encoding) = decode_source_bytes(&bytes).unwrap();
+        assert_eq!(text, "hello");
+        assert_eq!(encoding, TextEncoding::Utf8);
+    }
+
+    #[test]
+    fn decodes_utf16_le() {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "hi".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let (text, encoding) = decode_source_bytes(&bytes).unwrap();
+        assert_eq!(text, "hi");
+        assert_eq!(encoding, TextEncoding::Utf16Le);
+    }
+
+    #[test]
+    fn decodes_utf16_be() {
+        let mut bytes = vec![0xFE, 0xFF];
+        for unit in "hi".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        let (text, encoding) = decode_source_bytes(&bytes).unwrap();
+        assert_eq!(text, "hi");
+        assert_eq!(encoding, TextEncoding::Utf16Be);
+    }
+
+    #[test]
+    fn rejects_odd_length_utf16_body() {
+        let bytes = vec![0xFF, 0xFE, 0x41];
+        assert_eq!(decode_source_bytes(&bytes), None);
+    }
+}
@@ -34,6 +34,19 @@ impl GitAnalyzer {
         }
     }
 
+    /// Check whether the repository has sparse-checkout enabled
+    /// (`core.sparseCheckout` is true).
+    ///
+    /// In a sparse checkout, paths outside the configured cone are absent
+    /// from the worktree by design, even though Git still tracks them in
+    /// the index. Callers use this to avoid treating those intentionally
+    /// absent paths as deletions.
+    pub fn is_sparse_checkout(&self) -> bool {
+        let Some(repo) = &self.repo else { return false };
+        let Ok(config) = repo.config() else { return false };
+        config.get_bool("core.sparseCheckout").unwrap_or(false)
+    }
+
     /// Get repository status for all files
     pub fn get_status(&self) -> Result<HashMap<PathBuf, GitFileStatus>, Box<dyn std::error::Error>> {
         let repo = match &self.repo {
@@ -44,25 +57,39 @@ impl GitAnalyzer {
         let mut status_options = StatusOptions::new();
         status_options.include_untracked(true);
         status_options.include_ignored(false);
-        
+
         let statuses = repo.statuses(Some(&mut status_options))?;
         let mut file_statuses = HashMap::new();
+        let sparse_checkout = self.is_sparse_checkout();
+        let repo_root = repo.workdir();
 
         for entry in statuses.iter() {
             let path = PathBuf::from(entry.path().unwrap_or(""));
             let status = entry.status();
-            
-            let git_status = if status.contains(Status::INDEX_NEW) 
-                || status.contains(Status::INDEX_MODIFIED) 
-                || status.contains(Status::INDEX_DELETED) 
-                || status.contains(Status::INDEX_RENAMED) 
+
+            // In a sparse checkout, a path outside the cone is absent from
+            // the worktree on purpose. libgit2 doesn't know about the
+            // sparse-checkout cone and reports it as a worktree deletion,
+            // which would otherwise show up as a confusing "modified" file
+            // that nobody deleted. Treat it as clean instead.
+            if sparse_checkout
+                && status == Status::WT_DELETED
+                && repo_root.map_or(false, |root| !root.join(&path).exists())
+            {
+                continue;
+            }
+
+            let git_status = if status.contains(Status::INDEX_NEW)
+                || status.contains(Status::INDEX_MODIFIED)
+                || status.contains(Status::INDEX_DELETED)
+                || status.contains(Status::INDEX_RENAMED)
                 || status.contains(Status::INDEX_TYPECHANGE) {
                 GitFileStatus::Staged
             } else if status.contains(Status::WT_NEW) {
                 GitFileStatus::Untracked
-            } else if status.contains(Status::WT_MODIFIED) 
-                || status.contains(Status::WT_DELETED) 
-                || status.contains(Status::WT_RENAMED) 
+            } else if status.contains(Status::WT_MODIFIED)
+                || status.contains(Status::WT_DELETED)
+                || status.contains(Status::WT_RENAMED)
                 || status.contains(Status::WT_TYPECHANGE) {
                 GitFileStatus::Modified
             } else if status.contains(Status::CONFLICTED) {
@@ -70,7 +97,7 @@ impl GitAnalyzer {
             } else {
                 continue; // Skip clean files
             };
-            
+
             file_statuses.insert(path, git_status);
         }
 
@@ -272,7 +299,22 @@ impl GitAnalyzer {
     }
 
     /// Get the content of a file at HEAD for diff comparison
-    pub fn get_file_at_head(&self, path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn get_file_at_head(
+        &self,
+        path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let content = self.get_file_bytes_at_head(path)?;
+        Ok(String::from_utf8_lossy(&content).to_string())
+    }
+
+    /// Get the raw bytes of a file at HEAD, without assuming an encoding.
+    /// Split out from [`Self::get_file_at_head`] so callers that need to
+    /// detect a byte-order mark (e.g. [`Self::get_semantic_diff`]) can do
+    /// so before any lossy UTF-8 conversion mangles a UTF-16 blob.
+    fn get_file_bytes_at_head(
+        &self,
+        path: &Path,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let repo = match &self.repo {
             Some(repo) => repo,
             None => return Err("Not a Git repository".into()),
@@ -281,31 +323,176 @@ impl GitAnalyzer {
         let head = repo.head()?;
         let head_commit = head.peel_to_commit()?;
         let head_tree = head_commit.tree()?;
-        
+
         // Convert path to relative path from repo root
         let relative_path_buf = self.path_relative_to_repo(path)?;
         let relative_path = relative_path_buf.as_path();
-        
+
         // Get the tree entry for this path
         let tree_entry = head_tree.get_path(relative_path)?;
         let object = tree_entry.to_object(repo)?;
-        
+
         // Convert to blob and get content
         let blob = object.into_blob().map_err(|_| "Object is not a blob")?;
-        let content = blob.content();
-        
-        // Convert bytes to string
-        Ok(String::from_utf8_lossy(content).to_string())
+        Ok(blob.content().to_vec())
     }
 
-    /// Get semantic diff for a file using diffsitter
-    pub fn get_semantic_diff(&self, path: &Path) -> Result<String, Box<dyn std::error::Error>> {
-        // Get current file content
+    /// Get the content of a file as it existed at an arbitrary revision
+    /// (anything `git2::Repository::revparse_single` accepts: a commit sha,
+    /// a branch or tag name, or a relative ref like `HEAD~3`).
+    pub fn get_file_at_revision(
+        &self,
+        path: &Path,
+        revision: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return Err("Not a Git repository".into()),
+        };
+
+        let commit = repo.revparse_single(revision)?.peel_to_commit()?;
+        let tree = commit.tree()?;
+
+        let relative_path_buf = self.path_relative_to_repo(path)?;
+        let tree_entry = tree.get_path(relative_path_buf.as_path())?;
+        let object = tree_entry.to_object(repo)?;
+        let blob = object.into_blob().map_err(|_| "Object is not a blob")?;
+
+        Ok(String::from_utf8_lossy(blob.content()).to_string())
+    }
+
+    /// Get the semantic diff for a file as structured line records rather
+    /// than a pre-rendered string, so programmatic callers (e.g. a library
+    /// consumer, or [`Self::fallback_diff`]'s terminal renderer) don't have
+    /// to scrape ANSI-colored text.
+    ///
+    /// `base` is compared the same way [`Self::get_file_at_revision`] does:
+    /// `None` means HEAD, `Some(revision)` accepts anything
+    /// `git2::Repository::revparse_single` understands.
+    pub fn get_diff_hunks(
+        &self,
+        path: &Path,
+        base: Option<&str>,
+    ) -> Result<Vec<DiffHunk>, Box<dyn std::error::Error>> {
         let current_content = std::fs::read_to_string(path)?;
-        
+        let base_content = match base {
+            Some(revision) => self.get_file_at_revision(path, revision)?,
+            None => self.get_file_at_head(path)?,
+        };
+        Ok(diff_hunks(&base_content, &current_content))
+    }
+
+    /// List the commits in `range` (a revspec like `HEAD~50..HEAD` or
+    /// `main..feature`), oldest first, as short hex sha strings.
+    ///
+    /// This is used to walk a slice of history for `--history` semantic
+    /// search, so a commit can be used both to look up blobs with
+    /// [`GitAnalyzer::get_file_at_revision`] and to label results for the
+    /// end user.
+    pub fn list_revisions_in_range(
+        &self,
+        range: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return Err("Not a Git repository".into()),
+        };
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_range(range)?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+        let mut shas = Vec::new();
+        for oid in revwalk {
+            let oid = oid?;
+            shas.push(oid.to_string()[..12].to_string());
+        }
+        Ok(shas)
+    }
+
+    /// Summarize per-author line ownership for `path` via `git blame`, for
+    /// the "who wrote this" section of a single-file report.
+    pub fn blame_summary(
+        &self,
+        path: &Path,
+    ) -> Result<BlameSummary, Box<dyn std::error::Error>> {
+        let repo = match &self.repo {
+            Some(repo) => repo,
+            None => return Err("Not a Git repository".into()),
+        };
+
+        let relative_path = self.path_relative_to_repo(path)?;
+        let blame = repo.blame_file(&relative_path, None)?;
+
+        let mut lines_by_author: HashMap<String, usize> = HashMap::new();
+        let mut total_lines = 0;
+        let mut last_commit: Option<(i64, String)> = None;
+        for hunk in blame.iter() {
+            let signature = hunk.final_signature();
+            let author = signature.name().unwrap_or("Unknown").to_string();
+            let lines = hunk.lines_in_hunk();
+            total_lines += lines;
+            *lines_by_author.entry(author).or_insert(0) += lines;
+
+            let when = signature.when().seconds();
+            if last_commit.as_ref().map_or(true, |(t, _)| when > *t) {
+                let sha = hunk.final_commit_id().to_string()[..12].to_string();
+                last_commit = Some((when, sha));
+            }
+        }
+
+        let mut authors: Vec<(String, usize)> =
+            lines_by_author.into_iter().collect();
+        authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(BlameSummary {
+            total_lines,
+            authors,
+            last_commit: last_commit.map(|(_, sha)| sha),
+        })
+    }
+
+    /// Read a file's current worktree content and its content at HEAD, both
+    /// decoded to UTF-8. Shared by [`Self::get_semantic_diff`] and
+    /// [`Self::get_structural_diff`], which differ only in how they turn
+    /// those two strings into a diff.
+    ///
+    /// A HEAD blob can reference a path that's absent from the worktree of a
+    /// sparse checkout, so that case is surfaced distinctly rather than
+    /// letting a generic "No such file" bubble up.
+    ///
+    /// The bytes are decoded with `decode_source_bytes` rather than
+    /// `read_to_string`, so a UTF-16 source (detected by its BOM) is
+    /// transcoded to UTF-8 instead of failing as invalid UTF-8.
+    fn head_and_worktree_content(
+        &self,
+        path: &Path,
+    ) -> Result<(String, String), Box<dyn std::error::Error>> {
+        let current_bytes = std::fs::read(path).map_err(|err| {
+            if self.is_sparse_checkout() && !path.exists() {
+                format!(
+                    "'{}' is outside the sparse-checkout cone \
+                     (not present in the worktree)",
+                    path.display()
+                )
+                .into()
+            } else {
+                Box::<dyn std::error::Error>::from(err)
+            }
+        })?;
+        let (current_content, _current_encoding) =
+            crate::diagnostics::encoding::decode_source_bytes(&current_bytes)
+                .ok_or_else(|| -> Box<dyn std::error::Error> {
+                    format!(
+                        "'{}' is not valid UTF-8 or UTF-16 text",
+                        path.display()
+                    )
+                    .into()
+                })?;
+
         // Get HEAD content - need to handle path resolution properly
-        let head_content = match self.get_file_at_head(path) {
-            Ok(content) => content,
+        let head_bytes = match self.get_file_bytes_at_head(path) {
+            Ok(bytes) => bytes,
             Err(_) => {
                 // If direct path fails, try to resolve relative to current working directory
                 let cwd = std::env::current_dir()?;
@@ -314,19 +501,79 @@ impl GitAnalyzer {
                 } else {
                     cwd.join(path)
                 };
-                self.get_file_at_head(&absolute_path)?
+                self.get_file_bytes_at_head(&absolute_path)?
             }
         };
-        
+        let (head_content, _head_encoding) =
+            crate::diagnostics::encoding::decode_source_bytes(&head_bytes)
+                .unwrap_or_else(|| {
+                    (
+                        String::from_utf8_lossy(&head_bytes).to_string(),
+                        crate::diagnostics::encoding::TextEncoding::Utf8,
+                    )
+                });
+
+        Ok((head_content, current_content))
+    }
+
+    /// Get semantic diff for a file using diffsitter
+    pub fn get_semantic_diff(
+        &self,
+        path: &Path,
+        options: &DiffOptions,
+    ) -> Result<SemanticDiffOutcome, Box<dyn std::error::Error>> {
+        let (head_content, current_content) =
+            self.head_and_worktree_content(path)?;
+
         // Try to get file extension for language detection
         let language = path.extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("txt");
-        
+
+        let suppressed_hunks =
+            options.count_suppressed_hunks(&head_content, &current_content);
+        let (head_content, current_content) = if options.is_enabled() {
+            (
+                options.normalize(&head_content),
+                options.normalize(&current_content),
+            )
+        } else {
+            (head_content, current_content)
+        };
+
         // Use diffsitter to generate the diff
-        let diff_output = self.run_diffsitter(&head_content, &current_content, language)?;
-        
-        Ok(diff_output)
+        let diff =
+            self.run_diffsitter(&head_content, &current_content, language)?;
+
+        Ok(SemanticDiffOutcome { diff, suppressed_hunks })
+    }
+
+    /// Get a structural (symbol-level) diff for a file between HEAD and the
+    /// worktree, without shelling out to an external tool.
+    ///
+    /// Returns `Ok(None)` for files whose language isn't supported by the
+    /// bundled tree-sitter parsers, or whose content fails to parse -- this
+    /// mirrors [`crate::diagnostics::extract_ast_structure`]'s own "nothing
+    /// to report" semantics rather than treating either case as an error.
+    pub fn get_structural_diff(
+        &self,
+        path: &Path,
+    ) -> Result<
+        Option<crate::diagnostics::StructuralDiff>,
+        Box<dyn std::error::Error>,
+    > {
+        let Some(language) =
+            outgrep_ast_language::SupportLang::from_path(path)
+        else {
+            return Ok(None);
+        };
+        let (head_content, current_content) =
+            self.head_and_worktree_content(path)?;
+        Ok(crate::diagnostics::structural_diff(
+            &head_content,
+            &current_content,
+            language,
+        ))
     }
 
     /// Run diffsitter to generate semantic diff
@@ -367,14 +614,22 @@ impl GitAnalyzer {
         }
     }
 
-    /// Fallback to simple diff if diffsitter is not available
-    fn fallback_diff(&self, old_content: &str, new_content: &str) -> Result<String, Box<dyn std::error::Error>> {
+    /// Fallback to simple diff if diffsitter is not available. Renders the
+    /// structured records from [`diff_hunks`] as an ANSI-colored string,
+    /// grouped into hunks with 3 lines of context.
+    fn fallback_diff(
+        &self,
+        old_content: &str,
+        new_content: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
         use similar::{ChangeTag, TextDiff};
-        
+
+        let hunks = diff_hunks(old_content, new_content);
         let diff = TextDiff::from_lines(old_content, new_content);
         let mut output = String::new();
         let mut has_changes = false;
-        
+        let mut hunk_idx = 0;
+
         // Group changes into hunks with context
         for group in diff.grouped_ops(3) {
             if !has_changes {
@@ -382,33 +637,165 @@ impl GitAnalyzer {
             } else {
                 output.push_str("\x1b[90m...\x1b[0m\n"); // Gray separator
             }
-            
-            for op in &group {
-                for change in diff.iter_changes(op) {
-                    let (sign, color) = match change.tag() {
-                        ChangeTag::Delete => ("-", "\x1b[31m"), // Red for deletions
-                        ChangeTag::Insert => ("+", "\x1b[32m"), // Green for insertions
-                        ChangeTag::Equal => (" ", "\x1b[90m"),  // Gray for context
-                    };
-                    
-                    // Only show context lines (Equal) around changes, not all of them
-                    match change.tag() {
-                        ChangeTag::Delete | ChangeTag::Insert => {
-                            output.push_str(&format!("{}{}{}\x1b[0m", color, sign, change));
-                        }
-                        ChangeTag::Equal => {
-                            // Only show context lines, not all equal lines  
-                            output.push_str(&format!("{}{}{}\x1b[0m", color, sign, change));
-                        }
-                    }
-                }
+
+            let group_len: usize =
+                group.iter().map(|op| diff.iter_changes(op).count()).sum();
+            // Groups skip unchanged lines far from any change, so find
+            // where this group starts in the full, ungrouped record list.
+            let old_start = group[0].old_range().start as u32 + 1;
+            let new_start = group[0].new_range().start as u32 + 1;
+            hunk_idx = hunks
+                .iter()
+                .position(|h| {
+                    h.old_line == Some(old_start)
+                        || h.new_line == Some(new_start)
+                })
+                .unwrap_or(hunk_idx);
+            for hunk in &hunks[hunk_idx..hunk_idx + group_len] {
+                let (sign, color) = match hunk.kind {
+                    DiffLineKind::Removed => ("-", "\x1b[31m"), // Red for deletions
+                    DiffLineKind::Added => ("+", "\x1b[32m"), // Green for insertions
+                    DiffLineKind::Context => (" ", "\x1b[90m"), // Gray for context
+                };
+                output.push_str(&format!(
+                    "{}{}{}\x1b[0m\n",
+                    color, sign, hunk.content
+                ));
             }
+            hunk_idx += group_len;
         }
-        
+
         Ok(output)
     }
 }
 
+/// Line-ending and whitespace normalization for [`GitAnalyzer::get_semantic_diff`].
+///
+/// Both `--diff-ignore-eol` and `--diff-ignore-whitespace` can be enabled
+/// independently; they compose, since normalization for each is applied to
+/// both sides of the diff before it's generated.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiffOptions {
+    /// Treat CRLF and LF line endings as equivalent.
+    pub ignore_eol: bool,
+    /// Ignore leading and trailing whitespace on each line.
+    pub ignore_whitespace: bool,
+}
+
+impl DiffOptions {
+    /// Whether any normalization is requested at all.
+    fn is_enabled(&self) -> bool {
+        self.ignore_eol || self.ignore_whitespace
+    }
+
+    /// Apply the requested normalizations to `content`.
+    pub fn normalize(&self, content: &str) -> String {
+        let content = if self.ignore_eol {
+            content.replace("\r\n", "\n")
+        } else {
+            content.to_string()
+        };
+        if self.ignore_whitespace {
+            content
+                .lines()
+                .map(|line| line.trim())
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            content
+        }
+    }
+
+    /// Count how many change hunks between `old` and `new` disappear once
+    /// this options' normalization is applied, i.e. how many hunks were
+    /// pure line-ending or whitespace noise.
+    pub fn count_suppressed_hunks(&self, old: &str, new: &str) -> usize {
+        if !self.is_enabled() {
+            return 0;
+        }
+        let raw = count_hunks(old, new);
+        let normalized =
+            count_hunks(&self.normalize(old), &self.normalize(new));
+        raw.saturating_sub(normalized)
+    }
+}
+
+/// Count contiguous runs of changed lines between `old` and `new`.
+fn count_hunks(old: &str, new: &str) -> usize {
+    use similar::{DiffTag, TextDiff};
+
+    TextDiff::from_lines(old, new)
+        .ops()
+        .iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .count()
+}
+
+/// The result of [`GitAnalyzer::get_semantic_diff`]: the diff text itself,
+/// plus how many hunks `options` suppressed as pure formatting noise.
+#[derive(Debug, Clone)]
+pub struct SemanticDiffOutcome {
+    pub diff: String,
+    pub suppressed_hunks: usize,
+}
+
+/// Whether a [`DiffHunk`] line was added, removed, or is unchanged context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// One line of a [`GitAnalyzer::get_diff_hunks`] result, with enough
+/// information for a caller to render or re-diff it without going back to
+/// `similar` itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub kind: DiffLineKind,
+    /// 1-based line number on the base side, absent for added lines.
+    pub old_line: Option<u32>,
+    /// 1-based line number on the current side, absent for removed lines.
+    pub new_line: Option<u32>,
+    pub content: String,
+}
+
+/// The result of [`GitAnalyzer::blame_summary`]: how a file's current lines
+/// are distributed across authors, and its most recently touched commit.
+#[derive(Debug, Clone)]
+pub struct BlameSummary {
+    pub total_lines: usize,
+    /// Author name paired with how many lines they currently own, sorted by
+    /// line count descending.
+    pub authors: Vec<(String, usize)>,
+    /// Short hex sha of the commit that most recently touched the file.
+    pub last_commit: Option<String>,
+}
+
+/// Build the structured line-by-line diff between `old` and `new`. Shared
+/// by [`GitAnalyzer::get_diff_hunks`] and [`GitAnalyzer::fallback_diff`], so
+/// the terminal renderer and the programmatic API describe the same diff.
+fn diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    use similar::{ChangeTag, TextDiff};
+
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| {
+            let kind = match change.tag() {
+                ChangeTag::Delete => DiffLineKind::Removed,
+                ChangeTag::Insert => DiffLineKind::Added,
+                ChangeTag::Equal => DiffLineKind::Context,
+            };
+            DiffHunk {
+                kind,
+                old_line: change.old_index().map(|i| i as u32 + 1),
+                new_line: change.new_index().map(|i| i as u32 + 1),
+                content: change.value().trim_end_matches('\n').to_string(),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,23 +815,47 @@ mod tests {
         assert_eq!(diagnostics.total_commits, 0);
     }
 
+    #[cfg(not(feature = "test-support"))]
     #[test]
     fn test_git_analyzer_creation() {
         // Test with current directory (should be a git repo)
         let analyzer = GitAnalyzer::new(".");
-        
+
         // This should be true since we're in a git repo
         assert!(analyzer.is_git_repo());
-        
+
         // Should have a current branch
         assert!(analyzer.current_branch().is_some());
-        
+
         // Should be able to get diagnostics
         let diagnostics = analyzer.get_diagnostics().unwrap();
         assert!(diagnostics.is_repo);
         assert!(diagnostics.total_commits > 0);
     }
 
+    // With `test-support` enabled, prefer a `GitFixture` over the ambient
+    // checkout above: it doesn't depend on this crate's own repo having
+    // commits/a branch at test time, and it pins down exactly how many
+    // commits `total_commits` should report.
+    #[cfg(feature = "test-support")]
+    #[test]
+    fn test_git_analyzer_creation() {
+        let fixture = crate::test_support::GitFixture::init()
+            .expect("init should not fail");
+        fixture
+            .commit_file("a.txt", "one\n", "initial")
+            .expect("commit should not fail");
+
+        let analyzer = GitAnalyzer::new(fixture.path());
+
+        assert!(analyzer.is_git_repo());
+        assert!(analyzer.current_branch().is_some());
+
+        let diagnostics = analyzer.get_diagnostics().unwrap();
+        assert!(diagnostics.is_repo);
+        assert_eq!(diagnostics.total_commits, 1);
+    }
+
     #[test]
     fn test_diagnostics_summary() {
         let analyzer = GitAnalyzer::new(".");
@@ -468,4 +879,83 @@ mod tests {
         assert!(summary.contains("3 staged"));
         assert!(summary.contains("+2 -1"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_diff_hunks() {
+        let old = "a\nb\nc\n";
+        let new = "a\nx\nc\n";
+
+        let hunks = diff_hunks(old, new);
+        assert_eq!(
+            hunks,
+            vec![
+                DiffHunk {
+                    kind: DiffLineKind::Context,
+                    old_line: Some(1),
+                    new_line: Some(1),
+                    content: "a".to_string(),
+                },
+                DiffHunk {
+                    kind: DiffLineKind::Removed,
+                    old_line: Some(2),
+                    new_line: None,
+                    content: "b".to_string(),
+                },
+                DiffHunk {
+                    kind: DiffLineKind::Added,
+                    old_line: None,
+                    new_line: Some(2),
+                    content: "x".to_string(),
+                },
+                DiffHunk {
+                    kind: DiffLineKind::Context,
+                    old_line: Some(3),
+                    new_line: Some(3),
+                    content: "c".to_string(),
+                },
+            ]
+        );
+    }
+
+    proptest::proptest! {
+        /// Every `old`/`new` line, in order, should be recoverable by
+        /// filtering `diff_hunks`' output down to the side it came from --
+        /// regardless of how `similar` chose to group the changes.
+        #[test]
+        fn diff_hunks_reconstructs_both_sides(
+            old_lines in proptest::collection::vec("[a-z]{0,4}", 0..12),
+            new_lines in proptest::collection::vec("[a-z]{0,4}", 0..12),
+        ) {
+            let old = old_lines.iter().map(|l| format!("{}\n", l)).collect::<String>();
+            let new = new_lines.iter().map(|l| format!("{}\n", l)).collect::<String>();
+
+            let hunks = diff_hunks(&old, &new);
+
+            let reconstructed_old: Vec<&str> = hunks
+                .iter()
+                .filter(|h| h.kind != DiffLineKind::Added)
+                .map(|h| h.content.as_str())
+                .collect();
+            let reconstructed_new: Vec<&str> = hunks
+                .iter()
+                .filter(|h| h.kind != DiffLineKind::Removed)
+                .map(|h| h.content.as_str())
+                .collect();
+
+            proptest::prop_assert_eq!(&reconstructed_old, &old_lines);
+            proptest::prop_assert_eq!(&reconstructed_new, &new_lines);
+
+            // Line numbers on each side are 1-based and strictly increasing.
+            let old_line_numbers: Vec<u32> =
+                hunks.iter().filter_map(|h| h.old_line).collect();
+            let new_line_numbers: Vec<u32> =
+                hunks.iter().filter_map(|h| h.new_line).collect();
+            proptest::prop_assert!(
+                old_line_numbers.windows(2).all(|w| w[0] < w[1])
+            );
+            proptest::prop_assert!(
+                new_line_numbers.windows(2).all(|w| w[0] < w[1])
+            );
+        }
+    }
+}
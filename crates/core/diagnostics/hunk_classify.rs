@@ -0,0 +1,121 @@
+//! Classifies each diff hunk between two versions of a file as code,
+//! comment-only, or whitespace-only, so callers like `--diff-hide-trivial`
+//! can filter down to the hunks actually worth a reviewer's attention.
+//!
+//! A hunk here is one non-`Equal` op from `similar::TextDiff::from_lines`,
+//! matching the granularity `DiffOptions::count_suppressed_hunks` already
+//! uses to report how many hunks whitespace normalization would remove.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+use similar::{DiffTag, TextDiff};
+
+use crate::diagnostics::DiffOptions;
+
+/// How a diff hunk's content differs, from the AST's perspective.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HunkClassification {
+    /// At least one changed line affects non-comment code.
+    Code,
+    /// Every changed line lies inside a comment in its respective version.
+    CommentOnly,
+    /// The hunk disappears once whitespace and line-ending differences are
+    /// normalized away.
+    WhitespaceOnly,
+}
+
+/// Classify every hunk between `old` and `new`, in the same order
+/// `DiffOptions::count_suppressed_hunks` walks them.
+///
+/// Falls back to classifying every hunk as [`HunkClassification::Code`]
+/// when `path`'s language isn't supported, since comment membership can't
+/// be determined without a parser.
+pub fn classify_hunks(
+    path: &Path,
+    old: &str,
+    new: &str,
+) -> Vec<HunkClassification> {
+    let old_comment_lines = comment_lines(path, old);
+    let new_comment_lines = comment_lines(path, new);
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let whitespace = DiffOptions { ignore_eol: true, ignore_whitespace: true };
+
+    TextDiff::from_lines(old, new)
+        .ops()
+        .iter()
+        .filter(|op| op.tag() != DiffTag::Equal)
+        .map(|op| {
+            let old_range = op.old_range();
+            let new_range = op.new_range();
+            let old_is_comment =
+                old_range.clone().all(|i| old_comment_lines.contains(&i));
+            let new_is_comment =
+                new_range.clone().all(|i| new_comment_lines.contains(&i));
+            if old_is_comment && new_is_comment {
+                return HunkClassification::CommentOnly;
+            }
+
+            let old_text = old_lines[old_range].join("\n");
+            let new_text = new_lines[new_range].join("\n");
+            if whitespace.normalize(&old_text)
+                == whitespace.normalize(&new_text)
+            {
+                return HunkClassification::WhitespaceOnly;
+            }
+
+            HunkClassification::Code
+        })
+        .collect()
+}
+
+/// Line indices (0-based, matching `similar`'s line-diff ranges) covered by
+/// a comment node in `content`.
+fn comment_lines(path: &Path, content: &str) -> BTreeSet<usize> {
+    let mut lines = BTreeSet::new();
+    let Some(lang) = SupportLang::from_path(path) else { return lines };
+
+    macro_rules! walk_with {
+        ($lang_impl:expr) => {{
+            let ast_grep = $lang_impl.ast_grep(content);
+            let root = ast_grep.root();
+            for node in root.dfs() {
+                if is_comment_kind(node.kind().as_ref()) {
+                    let start = node.start_pos().line();
+                    let end = node.end_pos().line();
+                    lines.extend(start..=end);
+                }
+            }
+        }};
+    }
+
+    use SupportLang::*;
+    match lang {
+        Rust => walk_with!(outgrep_ast_language::Rust),
+        JavaScript => walk_with!(outgrep_ast_language::JavaScript),
+        TypeScript => walk_with!(outgrep_ast_language::TypeScript),
+        Tsx => walk_with!(outgrep_ast_language::Tsx),
+        Python => walk_with!(outgrep_ast_language::Python),
+        Java => walk_with!(outgrep_ast_language::Java),
+        Go => walk_with!(outgrep_ast_language::Go),
+        C => walk_with!(outgrep_ast_language::C),
+        Cpp => walk_with!(outgrep_ast_language::Cpp),
+        CSharp => walk_with!(outgrep_ast_language::CSharp),
+        Ruby => walk_with!(outgrep_ast_language::Ruby),
+        Php => walk_with!(outgrep_ast_language::Php),
+        _ => {}
+    }
+    lines
+}
+
+/// Check if a node kind represents a comment, across the languages we
+/// support. Mirrors `ast_extractor::is_comment_kind`.
+fn is_comment_kind(kind: &str) -> bool {
+    matches!(kind, "comment" | "line_comment" | "block_comment")
+}
@@ -0,0 +1,166 @@
+use std::path::Path;
+
+/// A single import/use statement found in a source file, resolved (when
+/// possible) to the module or file it refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStatement {
+    /// The raw module path as written in the source, e.g. `crate::config`
+    /// or `./widgets/button`.
+    pub module_path: String,
+    /// Line number the import appears on (1-based).
+    pub line: u32,
+}
+
+/// Extract the import/use statements from a source file, using a per-language
+/// heuristic rather than a full parse.
+///
+/// `--references` (see `crate::references`) uses this to constrain matches
+/// to files that actually import a symbol's module, rather than walking
+/// every file in the tree. This only extracts *what* is imported; matching
+/// those paths back to specific files on disk is handled by
+/// [`resolve_import`].
+pub fn extract_imports(path: &Path, content: &str) -> Vec<ImportStatement> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => extract_rust_imports(content),
+        Some("js") | Some("jsx") | Some("ts") | Some("tsx") => {
+            extract_js_imports(content)
+        }
+        Some("py") => extract_python_imports(content),
+        _ => Vec::new(),
+    }
+}
+
+fn extract_rust_imports(content: &str) -> Vec<ImportStatement> {
+    let mut imports = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("use ") {
+            let module_path = rest.trim_end_matches(';').trim();
+            imports.push(ImportStatement {
+                module_path: module_path.to_string(),
+                line: (idx + 1) as u32,
+            });
+        }
+    }
+    imports
+}
+
+fn extract_js_imports(content: &str) -> Vec<ImportStatement> {
+    let mut imports = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        let from = trimmed
+            .strip_prefix("import ")
+            .and_then(|rest| rest.rsplit_once("from"))
+            .map(|(_, module)| module)
+            .or_else(|| {
+                trimmed
+                    .strip_prefix("const ")
+                    .or_else(|| trimmed.strip_prefix("let "))
+                    .and_then(|rest| rest.split_once("require("))
+                    .map(|(_, module)| module)
+            });
+        let Some(module) = from else { continue };
+        let module_path = module
+            .trim()
+            .trim_start_matches(['(', ')'])
+            .trim_matches(|c| c == ';' || c == ')' || c == '(')
+            .trim_matches(['\'', '"']);
+        if !module_path.is_empty() {
+            imports.push(ImportStatement {
+                module_path: module_path.to_string(),
+                line: (idx + 1) as u32,
+            });
+        }
+    }
+    imports
+}
+
+fn extract_python_imports(content: &str) -> Vec<ImportStatement> {
+    let mut imports = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        let module_path = if let Some(rest) = trimmed.strip_prefix("from ") {
+            rest.split_whitespace().next()
+        } else {
+            trimmed
+                .strip_prefix("import ")
+                .and_then(|rest| rest.split(',').next().map(|m| m.trim()))
+        };
+        if let Some(module_path) = module_path {
+            imports.push(ImportStatement {
+                module_path: module_path.to_string(),
+                line: (idx + 1) as u32,
+            });
+        }
+    }
+    imports
+}
+
+/// Resolve a module path found by [`extract_imports`] to the module or
+/// relative path it is most likely referring to, so a reference finder can
+/// check it against a symbol's owning module.
+///
+/// For relative imports (`./foo`, `../bar`) this strips the prefix and
+/// returns the path relative to the importing file's directory. For
+/// absolute/crate-level imports (`crate::foo`, `foo.bar.baz`) it returns the
+/// module path unchanged, since resolving those fully requires knowing the
+/// project's module tree.
+///
+/// TODO: resolve `crate::`/package-relative imports against the actual
+/// module tree; for now this only normalizes relative imports, and callers
+/// that need to match an absolute import against a file compare the last
+/// path segment instead (see `crate::references::imported_stems`).
+pub fn resolve_import(importing_file: &Path, module_path: &str) -> String {
+    if let Some(relative) = module_path
+        .strip_prefix("./")
+        .or_else(|| module_path.strip_prefix("../"))
+    {
+        return importing_file
+            .parent()
+            .map(|dir| dir.join(relative).to_string_lossy().into_owned())
+            .unwrap_or_else(|| relative.to_string());
+    }
+    module_path.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_extract_rust_imports() {
+        let content =
+            "use std::fmt;\nuse crate::config::Config;\nfn main() {}";
+        let imports = extract_imports(&PathBuf::from("src/main.rs"), content);
+        assert_eq!(2, imports.len());
+        assert_eq!("std::fmt", imports[0].module_path);
+        assert_eq!("crate::config::Config", imports[1].module_path);
+    }
+
+    #[test]
+    fn test_extract_js_imports() {
+        let content = "import { Button } from './widgets/button';\nconst fs = require('fs');";
+        let imports = extract_imports(&PathBuf::from("src/app.js"), content);
+        assert_eq!(2, imports.len());
+        assert_eq!("./widgets/button", imports[0].module_path);
+        assert_eq!("fs", imports[1].module_path);
+    }
+
+    #[test]
+    fn test_extract_python_imports() {
+        let content = "from outgrep.config import Config\nimport os, sys";
+        let imports = extract_imports(&PathBuf::from("main.py"), content);
+        assert_eq!(2, imports.len());
+        assert_eq!("outgrep.config", imports[0].module_path);
+        assert_eq!("os", imports[1].module_path);
+    }
+
+    #[test]
+    fn test_resolve_relative_import() {
+        let resolved =
+            resolve_import(&PathBuf::from("src/app.js"), "./widgets/button");
+        assert_eq!("src/widgets/button", resolved);
+    }
+}
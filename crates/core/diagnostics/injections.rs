@@ -0,0 +1,63 @@
+/*!
+Language injection: locate embedded-language regions inside a parsed file
+(e.g. `<script>`/`<style>` in HTML) and parse each one with its own
+grammar, so AST context, syntax highlighting, and symbol extraction cover
+the embedded code too, not just the host document.
+
+This builds directly on `LanguageExt::extract_injections` and
+`Root::get_injections`, which already know how to find `<script>`/`<style>`
+regions (and honor an explicit `lang=` attribute) and hand back
+already-reparsed roots via tree-sitter's included ranges -- we only need to
+map the embedded region's name (e.g. `"js"`, `"scss"`) onto a [`SupportLang`]
+and run it back through [`build_ast_structure`].
+
+Injecting SQL out of string literals is a different problem: no host
+language's grammar marks a string literal as "this one is SQL", so it
+needs a content heuristic rather than a structural one. That's tracked as
+follow-on work rather than faked here.
+*/
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+
+use super::ast_extractor::build_ast_structure;
+use crate::diagnostics::types::LanguageInjection;
+
+/// Find embedded-language regions in `content`, parsed as `language`, and
+/// extract each one's symbols using its own grammar.
+///
+/// Returns an empty vector for host languages with no known injections
+/// (i.e. anything other than HTML today).
+pub(crate) fn find_injections(
+    language: SupportLang,
+    content: &str,
+) -> Vec<LanguageInjection> {
+    if language.injectable_languages().is_none() {
+        return Vec::new();
+    }
+    let root = language.ast_grep(content);
+    root.get_injections(injection_lang)
+        .into_iter()
+        .filter_map(|embedded| {
+            let embedded_lang = *embedded.lang();
+            let node = embedded.root();
+            let structure = build_ast_structure(embedded_lang, &node)?;
+            Some(LanguageInjection {
+                language: format!("{:?}", embedded_lang),
+                range: node.range(),
+                symbols: structure.symbols,
+            })
+        })
+        .collect()
+}
+
+/// Map an embedded region's name (from a tree-sitter injection, or an
+/// explicit `lang=` attribute) onto the [`SupportLang`] that parses it.
+fn injection_lang(name: &str) -> Option<SupportLang> {
+    match name {
+        "js" | "javascript" | "jsx" => Some(SupportLang::JavaScript),
+        "ts" | "typescript" | "tsx" => Some(SupportLang::TypeScript),
+        "css" | "scss" | "less" => Some(SupportLang::Css),
+        _ => None,
+    }
+}
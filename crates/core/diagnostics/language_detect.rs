@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use crate::diagnostics::types::{
+    CompilerDiagnostic, DiagnosticLocation, DiagnosticSeverity,
+};
+
+/// Guess a file's language from its extension, using the same naming
+/// convention as `TreeBuilder::detect_language_from_extension` ("Rust",
+/// "JavaScript", etc.) so the two can be compared directly.
+fn language_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?;
+    match extension.to_lowercase().as_str() {
+        "rs" => Some("Rust"),
+        "js" | "jsx" | "mjs" | "cjs" => Some("JavaScript"),
+        "ts" | "tsx" => Some("TypeScript"),
+        "py" => Some("Python"),
+        "sh" | "bash" | "zsh" => Some("Shell"),
+        "txt" => Some("Text"),
+        _ => None,
+    }
+}
+
+/// Guess a file's language from its content, using cheap textual signals
+/// rather than a full parse. Returns `None` when nothing distinctive is
+/// found, which should not be treated as "unknown language" - just as "no
+/// opinion", so callers only compare when this returns `Some`.
+fn language_from_content(content: &str) -> Option<&'static str> {
+    if let Some(first_line) = content.lines().next() {
+        if first_line.starts_with("#!") {
+            if first_line.contains("bash")
+                || first_line.contains("zsh")
+                || first_line.ends_with("/sh")
+            {
+                return Some("Shell");
+            }
+            if first_line.contains("python") {
+                return Some("Python");
+            }
+            if first_line.contains("node") {
+                return Some("JavaScript");
+            }
+        }
+    }
+
+    // TypeScript-only syntax that is a parse error in plain JavaScript.
+    if content.contains("interface ")
+        || content.contains(": string")
+        || content.contains(": number")
+        || content.contains(": boolean")
+        || content.contains("): void")
+    {
+        return Some("TypeScript");
+    }
+
+    if content.contains("fn main(") || content.contains("impl ") {
+        return Some("Rust");
+    }
+
+    if content.contains("def ") && content.contains(':') {
+        return Some("Python");
+    }
+
+    None
+}
+
+/// Flag files where the extension and a cheap content-based guess disagree,
+/// e.g. TypeScript syntax saved as `.js`, or a shell script saved as `.txt`.
+///
+/// Returns `None` when the file has no extension-based guess, no
+/// content-based guess, or the two agree - a mismatch is only reported when
+/// both heuristics have an opinion and those opinions conflict.
+pub fn check_language_mismatch(
+    path: &Path,
+    content: &str,
+) -> Option<CompilerDiagnostic> {
+    let from_extension = language_from_extension(path)?;
+    let from_content = language_from_content(content)?;
+    if from_extension == from_content {
+        return None;
+    }
+
+    Some(CompilerDiagnostic {
+        severity: DiagnosticSeverity::Hint,
+        message: format!(
+            "File extension suggests {} but content looks like {}",
+            from_extension, from_content
+        ),
+        code: Some("mixed-language".to_string()),
+        location: DiagnosticLocation { line: 1, column: 1, length: None },
+        file_path: path.to_path_buf(),
+        suggestions: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_typescript_in_js_file_is_flagged() {
+        let content = "interface Foo { bar: string }\n";
+        let hint = check_language_mismatch(&PathBuf::from("widget.js"), content)
+            .expect("mismatch should be detected");
+        assert_eq!(hint.code.as_deref(), Some("mixed-language"));
+        assert!(hint.message.contains("JavaScript"));
+        assert!(hint.message.contains("TypeScript"));
+    }
+
+    #[test]
+    fn test_shell_shebang_in_txt_file_is_flagged() {
+        let content = "#!/bin/bash\necho hello\n";
+        let hint = check_language_mismatch(&PathBuf::from("notes.txt"), content)
+            .expect("mismatch should be detected");
+        assert!(hint.message.contains("Text"));
+        assert!(hint.message.contains("Shell"));
+    }
+
+    #[test]
+    fn test_matching_extension_and_content_is_not_flagged() {
+        let content = "fn main() {\n    println!(\"hi\");\n}\n";
+        let hint = check_language_mismatch(&PathBuf::from("main.rs"), content);
+        assert!(hint.is_none());
+    }
+
+    #[test]
+    fn test_plain_javascript_is_not_flagged() {
+        let content = "function add(a, b) {\n    return a + b;\n}\n";
+        let hint = check_language_mismatch(&PathBuf::from("add.js"), content);
+        assert!(hint.is_none());
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Read;
+use std::path::Path;
+
+/// Identify a binary file's type from its leading bytes ("magic numbers"),
+/// for files whose extension is missing or doesn't map to a known
+/// language in [`crate::diagnostics::tree::TreeBuilder::detect_language`].
+///
+/// This only looks at a small signature at the start of the file - it's
+/// meant to turn tree/analysis output's generic "Other" bucket into
+/// something a human can act on (e.g. spotting a stray `.sqlite3` database
+/// or an accidentally-committed executable), not to be a full file-type
+/// database.
+pub fn detect_by_magic_bytes(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    // 262 bytes covers every signature below, including tar's "ustar"
+    // marker at offset 257.
+    let mut header = [0u8; 262];
+    let n = file.read(&mut header).ok()?;
+    let header = &header[..n];
+
+    classify_magic_bytes(header)
+}
+
+/// Match a byte signature against known magic numbers. Split out from
+/// [`detect_by_magic_bytes`] so the classification logic can be tested
+/// without touching the filesystem.
+fn classify_magic_bytes(header: &[u8]) -> Option<String> {
+    let kind = if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "PNG image"
+    } else if header.starts_with(b"\xff\xd8\xff") {
+        "JPEG image"
+    } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        "GIF image"
+    } else if header.starts_with(b"\x00\x00\x01\x00") {
+        "ICO image"
+    } else if header.starts_with(b"BM") {
+        "BMP image"
+    } else if header.starts_with(b"RIFF")
+        && header.len() >= 12
+        && &header[8..12] == b"WEBP"
+    {
+        "WebP image"
+    } else if header.starts_with(b"%PDF-") {
+        "PDF document"
+    } else if header.starts_with(b"SQLite format 3\x00") {
+        "SQLite database"
+    } else if header.starts_with(b"PK\x03\x04")
+        || header.starts_with(b"PK\x05\x06")
+    {
+        "ZIP archive"
+    } else if header.starts_with(b"\x1f\x8b") {
+        "Gzip archive"
+    } else if header.starts_with(b"BZh") {
+        "Bzip2 archive"
+    } else if header.starts_with(b"\xfd7zXZ\x00") {
+        "XZ archive"
+    } else if header.starts_with(b"7z\xbc\xaf\x27\x1c") {
+        "7-Zip archive"
+    } else if header.len() >= 262 && &header[257..262] == b"ustar" {
+        "Tar archive"
+    } else if header.starts_with(b"\x7fELF") {
+        "ELF executable"
+    } else if header.starts_with(b"MZ") {
+        "Windows executable"
+    } else if header.starts_with(b"\xca\xfe\xba\xbe")
+        || header.starts_with(b"\xfe\xed\xfa\xce")
+        || header.starts_with(b"\xfe\xed\xfa\xcf")
+        || header.starts_with(b"\xce\xfa\xed\xfe")
+        || header.starts_with(b"\xcf\xfa\xed\xfe")
+    {
+        "Mach-O executable"
+    } else {
+        return None;
+    };
+    Some(kind.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_png_signature() {
+        let header = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+        assert_eq!(
+            classify_magic_bytes(header),
+            Some("PNG image".to_string())
+        );
+    }
+
+    #[test]
+    fn detects_sqlite_signature() {
+        let header = b"SQLite format 3\x00";
+        assert_eq!(
+            classify_magic_bytes(header),
+            Some("SQLite database".to_string())
+        );
+    }
+
+    #[test]
+    fn detects_elf_signature() {
+        let header =
+            b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+        assert_eq!(
+            classify_magic_bytes(header),
+            Some("ELF executable".to_string())
+        );
+    }
+
+    #[test]
+    fn unrecognized_bytes_return_none() {
+        assert_eq!(classify_magic_bytes(b"not a known signature"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::diagnostics::complexity;
+use crate::diagnostics::test_detection::TestDetector;
 use crate::diagnostics::types::CodeMetrics;
 use std::path::Path;
 
@@ -15,6 +17,11 @@ struct ComplexityMetrics {
     cyclomatic_complexity: u32,
     cognitive_complexity: u32,
     function_count: u32,
+    /// 0 for the line-based fallback heuristics below, which don't track
+    /// nesting or per-function line spans.
+    max_nesting_depth: u32,
+    max_function_length: u32,
+    avg_function_length: f64,
 }
 
 #[derive(Debug, PartialEq)]
@@ -34,6 +41,28 @@ enum Language {
     Unknown,
 }
 
+impl Language {
+    /// Display name used to group files by language, e.g. in
+    /// `--filetype-stats`.
+    fn name(&self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::JavaScript => "JavaScript",
+            Language::TypeScript => "TypeScript",
+            Language::Python => "Python",
+            Language::Java => "Java",
+            Language::Go => "Go",
+            Language::Cpp => "C++",
+            Language::C => "C",
+            Language::Php => "PHP",
+            Language::Ruby => "Ruby",
+            Language::CSharp => "C#",
+            Language::Swift => "Swift",
+            Language::Unknown => "Other",
+        }
+    }
+}
+
 impl MetricsCalculator {
     /// Calculate comprehensive code metrics for a file
     pub fn calculate_metrics(path: &Path, content: &str) -> Result<CodeMetrics, Box<dyn std::error::Error>> {
@@ -50,9 +79,28 @@ impl MetricsCalculator {
             cyclomatic_complexity: complexity_metrics.cyclomatic_complexity,
             cognitive_complexity: complexity_metrics.cognitive_complexity,
             function_count: complexity_metrics.function_count,
+            max_nesting_depth: complexity_metrics.max_nesting_depth,
+            max_function_length: complexity_metrics.max_function_length,
+            avg_function_length: complexity_metrics.avg_function_length,
+            is_test: TestDetector::is_test_file(path, content),
         })
     }
     
+    /// Count files, lines of code, comments, and blanks for `content`,
+    /// grouped by the language its extension implies.
+    ///
+    /// This is the fast path used by `--filetype-stats`: unlike
+    /// [`Self::calculate_metrics`], it never runs AST parsing or complexity
+    /// analysis, so it stays cheap enough to run over an entire large repo.
+    pub fn calculate_filetype_stats(
+        path: &Path,
+        content: &str,
+    ) -> (&'static str, u64, u64, u64) {
+        let basic = Self::calculate_basic_metrics(path, content);
+        let language = Self::detect_language_from_extension(path);
+        (language.name(), basic.code, basic.comments, basic.blanks)
+    }
+
     /// Calculate basic line metrics
     fn calculate_basic_metrics(path: &Path, content: &str) -> BasicMetrics {
         let language = Self::detect_language_from_extension(path);
@@ -100,10 +148,27 @@ impl MetricsCalculator {
         }
     }
     
-    /// Calculate complexity and function metrics
+    /// Calculate complexity and function metrics.
+    ///
+    /// This walks the real AST for languages `complexity::calculate` has
+    /// rules for (see `ComplexityRules`), since that's the only way to
+    /// count constructs like match arms or `&&`/`||` chains correctly. For
+    /// anything else -- an unsupported language, or a file the AST parser
+    /// rejects -- this falls back to the coarser line-based heuristics
+    /// below.
     fn calculate_complexity_metrics(path: &Path, content: &str) -> ComplexityMetrics {
+        if let Some(ast) = complexity::calculate(path, content) {
+            return ComplexityMetrics {
+                cyclomatic_complexity: ast.cyclomatic_complexity,
+                cognitive_complexity: ast.cognitive_complexity,
+                function_count: ast.function_count,
+                max_nesting_depth: ast.max_nesting_depth,
+                max_function_length: ast.max_function_length,
+                avg_function_length: ast.avg_function_length,
+            };
+        }
+
         let language = Self::detect_language_from_extension(path);
-        
         match language {
             Language::Rust => Self::calculate_rust_metrics(content),
             Language::JavaScript | Language::TypeScript => Self::calculate_js_metrics(content),
@@ -169,6 +234,9 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: (cyclomatic_complexity as f32 * 0.8) as u32, // Approximation
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
@@ -204,6 +272,9 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: (cyclomatic_complexity as f32 * 0.9) as u32,
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
@@ -236,6 +307,9 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: cyclomatic_complexity,
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
@@ -269,6 +343,9 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: (cyclomatic_complexity as f32 * 1.1) as u32,
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
@@ -301,6 +378,9 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: cyclomatic_complexity,
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
@@ -329,18 +409,41 @@ impl MetricsCalculator {
             cyclomatic_complexity,
             cognitive_complexity: cyclomatic_complexity,
             function_count,
+            max_nesting_depth: 0,
+            max_function_length: 0,
+            avg_function_length: 0.0,
         }
     }
     
     /// Get a summary string of the metrics
     pub fn metrics_summary(metrics: &CodeMetrics) -> String {
         format!(
-            "LOC: {}, Comments: {}, Blank: {}, Functions: {}, Complexity: {}",
+            "LOC: {}, Comments: {}, Blank: {}, Functions: {}, Complexity: {}, \
+             Max Nesting: {}, Max Fn Length: {}, Avg Fn Length: {:.1}",
             metrics.lines_of_code,
             metrics.comment_lines,
             metrics.blank_lines,
             metrics.function_count,
-            metrics.cyclomatic_complexity
+            metrics.cyclomatic_complexity,
+            metrics.max_nesting_depth,
+            metrics.max_function_length,
+            metrics.avg_function_length
+        )
+    }
+
+    /// Format how `current` differs from `previous`, in the same style as
+    /// [`Self::metrics_summary`], for `--watch`'s per-file delta reporting
+    /// on a `Modified` event.
+    pub fn metrics_delta_summary(
+        previous: &CodeMetrics,
+        current: &CodeMetrics,
+    ) -> String {
+        format!(
+            "LOC {:+}, Functions {:+}, Complexity {:+}",
+            current.lines_of_code as i64 - previous.lines_of_code as i64,
+            current.function_count as i64 - previous.function_count as i64,
+            current.cyclomatic_complexity as i64
+                - previous.cyclomatic_complexity as i64,
         )
     }
 }
@@ -426,6 +529,10 @@ const arrow = () => {
             cyclomatic_complexity: 15,
             cognitive_complexity: 12,
             function_count: 8,
+            max_nesting_depth: 3,
+            max_function_length: 25,
+            avg_function_length: 12.5,
+            is_test: false,
         };
         
         let summary = MetricsCalculator::metrics_summary(&metrics);
@@ -434,4 +541,35 @@ const arrow = () => {
         assert!(summary.contains("Functions: 8"));
         assert!(summary.contains("Complexity: 15"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_metrics_delta_summary() {
+        let previous = CodeMetrics {
+            lines_of_code: 100,
+            comment_lines: 20,
+            blank_lines: 10,
+            cyclomatic_complexity: 15,
+            cognitive_complexity: 12,
+            function_count: 8,
+            max_nesting_depth: 3,
+            max_function_length: 25,
+            avg_function_length: 12.5,
+            is_test: false,
+        };
+        let mut grew = previous.clone();
+        grew.lines_of_code = 112;
+        grew.function_count = 9;
+        grew.cyclomatic_complexity = 18;
+
+        let delta = MetricsCalculator::metrics_delta_summary(&previous, &grew);
+        assert!(delta.contains("LOC +12"));
+        assert!(delta.contains("Functions +1"));
+        assert!(delta.contains("Complexity +3"));
+
+        let mut shrank = previous.clone();
+        shrank.lines_of_code = 90;
+        let delta =
+            MetricsCalculator::metrics_delta_summary(&previous, &shrank);
+        assert!(delta.contains("LOC -10"));
+    }
+}
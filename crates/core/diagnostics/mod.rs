@@ -1,18 +1,63 @@
+#[cfg(feature = "watch")]
 pub mod watcher;
+#[cfg(feature = "watch")]
+pub mod change_batch;
 pub mod types;
 pub mod metrics;
 pub mod git;
 pub mod tree;
 pub mod compiler;
 pub mod ast_extractor;
+pub mod document_symbols;
+pub mod injections;
+pub mod parse_cache;
+pub mod complexity;
+pub mod encoding;
+pub mod hunk_classify;
+pub mod references;
+pub mod signature;
+pub mod symbol_db;
+pub mod structural_diff;
+pub mod imports;
+pub mod language_detect;
+pub mod magic;
+pub mod pathresolve;
+pub mod test_detection;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "watch"))]
 mod test_watcher;
+#[cfg(all(test, feature = "watch"))]
+mod test_change_batch;
 
+#[cfg(feature = "watch")]
 pub use watcher::FileWatcher;
+#[cfg(feature = "watch")]
+pub use change_batch::{ChangeBatcher, ChangeSet};
 pub use types::*;
 pub use metrics::MetricsCalculator;
-pub use git::GitAnalyzer;
+pub use git::{
+    BlameSummary, DiffHunk, DiffLineKind, DiffOptions, GitAnalyzer,
+    SemanticDiffOutcome,
+};
 pub use tree::{TreeBuilder, TreeDisplay, TreeDisplayOptions};
 // CompilerDiagnosticsRunner is used internally by TreeBuilder
-pub use ast_extractor::extract_ast_structure;
\ No newline at end of file
+pub use ast_extractor::extract_ast_structure;
+pub use document_symbols::{
+    document_symbols, nest_summary as nest_document_symbols, DocumentSymbol,
+};
+pub use parse_cache::ParseCache;
+pub use complexity::{ComplexityRules, calculate as calculate_ast_complexity};
+pub use encoding::{decode_source_bytes, read_source_file, TextEncoding};
+pub use hunk_classify::{classify_hunks, HunkClassification};
+pub use references::find_references;
+pub use signature::SignatureQuery;
+pub use symbol_db::SymbolDatabase;
+pub use structural_diff::{
+    format_lines as format_structural_diff_lines, structural_diff,
+    ModifiedSymbol, StructuralDiff,
+};
+pub use imports::{extract_imports, resolve_import, ImportStatement};
+pub use language_detect::check_language_mismatch;
+pub use magic::detect_by_magic_bytes;
+pub use pathresolve::PathResolver;
+pub use test_detection::TestDetector;
\ No newline at end of file
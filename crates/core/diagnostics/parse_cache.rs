@@ -0,0 +1,155 @@
+/*!
+Incremental re-parsing cache for `--watch` mode.
+
+[`extract_ast_structure`](crate::diagnostics::extract_ast_structure) parses
+a file from scratch on every call, which is fine for a one-off `analyze`
+but wastes work when `--watch` re-analyzes the same file over and over as
+it's edited. [`ParseCache`] instead keeps the previous
+[`AstGrep`](outgrep_ast_core::AstGrep) tree for each watched path around
+and, on the next modification, hands tree-sitter the old tree plus a byte
+diff of what changed so it can reuse unaffected subtrees instead of
+re-parsing the whole file.
+*/
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use outgrep_ast_core::source::Edit;
+use outgrep_ast_core::tree_sitter::{LanguageExt, StrDoc};
+use outgrep_ast_core::{AstGrep, Language};
+use outgrep_ast_language::SupportLang;
+
+use crate::diagnostics::ast_extractor::build_ast_structure;
+use crate::diagnostics::types::AstStructure;
+
+/// Per-path cache of parsed [`AstGrep`] trees, used to re-parse a
+/// `--watch`ed file incrementally instead of from scratch.
+pub struct ParseCache {
+    trees: HashMap<PathBuf, AstGrep<StrDoc<SupportLang>>>,
+}
+
+impl ParseCache {
+    pub fn new() -> ParseCache {
+        ParseCache { trees: HashMap::new() }
+    }
+
+    /// Re-parse `path` given its full new content, reusing and editing
+    /// the previously cached tree for `path` when one exists and is for
+    /// the same language. Returns `None` if `path`'s extension isn't a
+    /// supported language.
+    pub fn update(
+        &mut self,
+        path: &Path,
+        new_content: &str,
+    ) -> Option<AstStructure> {
+        let lang = SupportLang::from_path(path)?;
+
+        if let Some(cached) = self.trees.get_mut(path) {
+            if *cached.lang() == lang {
+                let edit = diff_edit(&cached.root().text(), new_content);
+                match edit {
+                    None => return build_ast_structure(lang, &cached.root()),
+                    Some(edit) => {
+                        if cached.edit(edit).is_ok() {
+                            return build_ast_structure(lang, &cached.root());
+                        }
+                        // The edit was rejected (e.g. an out-of-range
+                        // offset); fall through and reparse from scratch.
+                    }
+                }
+            }
+        }
+
+        let fresh = lang.ast_grep(new_content);
+        let structure = build_ast_structure(lang, &fresh.root());
+        self.trees.insert(path.to_path_buf(), fresh);
+        structure
+    }
+
+    /// Drop `path`'s cached tree, e.g. because the file was deleted.
+    pub fn remove(&mut self, path: &Path) {
+        self.trees.remove(path);
+    }
+}
+
+impl Default for ParseCache {
+    fn default() -> ParseCache {
+        ParseCache::new()
+    }
+}
+
+/// Compute the minimal tree-sitter [`Edit`] that turns `old` into `new`,
+/// by trimming their common prefix and suffix. Returns `None` if the two
+/// are identical, since there's nothing to feed to `AstGrep::edit`.
+fn diff_edit(old: &str, new: &str) -> Option<Edit<String>> {
+    if old == new {
+        return None;
+    }
+    let old = old.as_bytes();
+    let new = new.as_bytes();
+
+    let prefix_len = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let max_suffix_len = old.len().min(new.len()) - prefix_len;
+    let suffix_len = old[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new[prefix_len..].iter().rev())
+        .take(max_suffix_len)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    Some(Edit {
+        position: prefix_len,
+        deleted_length: old.len() - prefix_len - suffix_len,
+        inserted_text: new[prefix_len..new.len() - suffix_len].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_the_cached_tree_across_a_small_edit() {
+        let mut cache = ParseCache::new();
+        let path = Path::new("example.rs");
+
+        let first =
+            cache.update(path, "fn add(a: i32, b: i32) -> i32 { a + b }");
+        assert!(first.is_some());
+        assert!(cache.trees.contains_key(path));
+
+        let second = cache
+            .update(path, "fn add(a: i32, b: i32) -> i32 { a - b }")
+            .expect("re-parse should still succeed");
+        assert!(second.symbols.functions.iter().any(|f| f.name == "add"));
+    }
+
+    #[test]
+    fn identical_content_reuses_the_tree_without_editing() {
+        let mut cache = ParseCache::new();
+        let path = Path::new("example.rs");
+        let src = "fn main() {}";
+
+        cache.update(path, src);
+        let second =
+            cache.update(path, src).expect("identical content should parse");
+        assert!(second.symbols.functions.iter().any(|f| f.name == "main"));
+    }
+
+    #[test]
+    fn unsupported_extension_returns_none() {
+        let mut cache = ParseCache::new();
+        let result = cache.update(Path::new("notes.txt"), "hello");
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn diff_edit_trims_common_prefix_and_suffix() {
+        let edit =
+            diff_edit("fn add(a, b) { a + b }", "fn add(a, b) { a - b }")
+                .expect("differing strings should produce an edit");
+        assert_eq!(edit.deleted_length, 1);
+        assert_eq!(edit.inserted_text, b"-".to_vec());
+    }
+}
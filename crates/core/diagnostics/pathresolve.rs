@@ -0,0 +1,92 @@
+//! Resolves file paths to one consistent key so lookups into git-status and
+//! diagnostics maps are exact, instead of the ad hoc canonicalize/relative
+//! path/filename-suffix fallback chains previously scattered across `tree`
+//! and `git`.
+//!
+//! Every path a caller sees -- an absolute path from a directory walk, a
+//! path relative to the repo root from `git2`, or a path relative to the
+//! current directory typed on the command line -- gets resolved down to a
+//! canonical absolute path here, so the same file always produces the same
+//! key no matter which of those forms it started as.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Normalizes paths against a repository root and the current working
+/// directory, caching canonicalization results since the same paths are
+/// often resolved repeatedly while building a tree.
+pub struct PathResolver {
+    repo_root: Option<PathBuf>,
+    cwd: PathBuf,
+    cache: Mutex<HashMap<PathBuf, PathBuf>>,
+}
+
+impl std::fmt::Debug for PathResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PathResolver")
+            .field("repo_root", &self.repo_root)
+            .field("cwd", &self.cwd)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PathResolver {
+    /// Create a resolver rooted at `repo_root` (if the tree being walked is
+    /// inside a Git repository) using the process's current directory.
+    pub fn new(repo_root: Option<PathBuf>) -> Self {
+        let cwd =
+            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        PathResolver { repo_root, cwd, cache: Mutex::new(HashMap::new()) }
+    }
+
+    /// Resolve `path` to a canonical absolute path.
+    ///
+    /// Relative paths are joined against the current working directory
+    /// first. Falls back to the joined-but-uncanonicalized path when
+    /// canonicalization fails, e.g. because the file has since been
+    /// deleted -- a stale key is still one exact, consistent key, rather
+    /// than no key at all.
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        if let Some(hit) = self.cache.lock().unwrap().get(path) {
+            return hit.clone();
+        }
+
+        let absolute = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.cwd.join(path)
+        };
+        let resolved = absolute.canonicalize().unwrap_or(absolute);
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), resolved.clone());
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_is_stable_across_absolute_and_relative_forms() {
+        let cwd = std::env::current_dir().unwrap();
+        let resolver = PathResolver::new(None);
+
+        let absolute = cwd.join("Cargo.toml");
+        let relative = Path::new("Cargo.toml");
+
+        assert_eq!(resolver.resolve(&absolute), resolver.resolve(relative));
+    }
+
+    #[test]
+    fn resolve_falls_back_for_missing_paths() {
+        let resolver = PathResolver::new(None);
+        let missing = Path::new("this-file-does-not-exist-anywhere.rs");
+        // Doesn't panic, and is stable across repeated lookups.
+        assert_eq!(resolver.resolve(missing), resolver.resolve(missing));
+    }
+}
@@ -0,0 +1,168 @@
+//! AST-based reference finding.
+//!
+//! Unlike a plain-text search for an identifier, this walks the real AST
+//! (via `outgrep-ast-core`, the same engine `complexity.rs` uses) and only
+//! reports occurrences that are actually a *use* of the identifier: the
+//! callee of a call expression, or a type reference. Because comments and
+//! string contents never parse as `identifier`/`type_identifier` nodes,
+//! restricting matches to those node kinds also excludes them for free,
+//! without any separate string/comment filtering.
+//!
+//! Node kinds vary by language, so both the call-expression kinds and the
+//! candidate field names used to find a call's callee are tried in order
+//! rather than hard-coded to one grammar.
+
+use std::path::Path;
+
+use outgrep_ast_core::{tree_sitter::LanguageExt, Doc, Node};
+use outgrep_ast_language::SupportLang;
+
+use crate::diagnostics::types::{ReferenceKind, ReferenceOccurrence};
+
+/// Node kinds, across the languages `SupportLang` covers, whose callee
+/// counts as a call-expression reference.
+const CALL_KINDS: &[&str] = &[
+    "call_expression",
+    "call",
+    "method_invocation",
+    "function_call_expression",
+];
+
+/// Field names tried, in order, to find a call node's callee.
+const CALLEE_FIELDS: &[&str] = &["function", "method", "name"];
+
+/// Field names tried, in order, to find the accessed member of a
+/// method-call chain like `receiver.ident()`, so the rightmost identifier
+/// (`ident`) is compared rather than the receiver.
+const MEMBER_FIELDS: &[&str] = &["field", "property", "attribute", "method"];
+
+/// Node kinds that represent a reference to a type.
+const TYPE_REFERENCE_KINDS: &[&str] =
+    &["type_identifier", "generic_type", "scoped_type_identifier"];
+
+/// Node kinds that mark a named definition, used to find the enclosing
+/// symbol for grouping. Mirrors `ast_extractor::is_named_entity`.
+const ENCLOSING_KINDS: &[&str] = &[
+    "function_declaration",
+    "function_definition",
+    "function_item",
+    "method_definition",
+    "class_declaration",
+    "class_definition",
+    "struct_item",
+    "impl_item",
+    "trait_item",
+    "interface_declaration",
+    "type_alias",
+    "typedef",
+    "type_definition",
+    "enum_declaration",
+    "union_declaration",
+    "type_item",
+    "module",
+    "namespace",
+    "mod_item",
+];
+
+/// Find every call-expression and type-reference occurrence of `ident` in
+/// `content`, grouped by their enclosing symbol.
+///
+/// Returns `None` if `path`'s extension isn't a supported language.
+pub fn find_references(
+    path: &Path,
+    content: &str,
+    ident: &str,
+) -> Option<Vec<ReferenceOccurrence>> {
+    let lang = SupportLang::from_path(path)?;
+
+    macro_rules! walk_with {
+        ($lang_impl:expr) => {{
+            let ast_grep = $lang_impl.ast_grep(content);
+            let root = ast_grep.root();
+            if root.range().start == 0
+                && root.range().end == 0
+                && !content.is_empty()
+            {
+                return None;
+            }
+            root.dfs()
+                .filter_map(|node| occurrence_for(&node, ident))
+                .collect()
+        }};
+    }
+
+    use SupportLang::*;
+    let occurrences = match lang {
+        Rust => walk_with!(outgrep_ast_language::Rust),
+        JavaScript => walk_with!(outgrep_ast_language::JavaScript),
+        TypeScript => walk_with!(outgrep_ast_language::TypeScript),
+        Tsx => walk_with!(outgrep_ast_language::Tsx),
+        Python => walk_with!(outgrep_ast_language::Python),
+        Java => walk_with!(outgrep_ast_language::Java),
+        Go => walk_with!(outgrep_ast_language::Go),
+        C => walk_with!(outgrep_ast_language::C),
+        Cpp => walk_with!(outgrep_ast_language::Cpp),
+        CSharp => walk_with!(outgrep_ast_language::CSharp),
+        Ruby => walk_with!(outgrep_ast_language::Ruby),
+        Php => walk_with!(outgrep_ast_language::Php),
+        _ => return None,
+    };
+    Some(occurrences)
+}
+
+/// If `node` is a reference to `ident`, return the occurrence it describes.
+fn occurrence_for<D: Doc>(
+    node: &Node<D>,
+    ident: &str,
+) -> Option<ReferenceOccurrence> {
+    let kind = node.kind();
+    let reference_kind = if CALL_KINDS.contains(&kind.as_ref()) {
+        let callee = call_callee(node)?;
+        if callee.text().as_ref() != ident {
+            return None;
+        }
+        ReferenceKind::Call
+    } else if TYPE_REFERENCE_KINDS.contains(&kind.as_ref()) {
+        if node.text().as_ref() != ident {
+            return None;
+        }
+        ReferenceKind::TypeReference
+    } else {
+        return None;
+    };
+
+    let start_pos = node.start_pos();
+    Some(ReferenceOccurrence {
+        kind: reference_kind,
+        line: (start_pos.line() + 1) as u32,
+        column: (start_pos.column(node) + 1) as u32,
+        enclosing_symbol: enclosing_symbol_name(node),
+    })
+}
+
+/// Resolve a call node's callee identifier, following one level of member
+/// access (`receiver.ident()`) so the accessed name is returned rather than
+/// the receiver.
+fn call_callee<'r, D: Doc>(node: &Node<'r, D>) -> Option<Node<'r, D>> {
+    let callee = CALLEE_FIELDS.iter().find_map(|field| node.field(field))?;
+    if callee.is_named_leaf() {
+        return Some(callee);
+    }
+    MEMBER_FIELDS.iter().find_map(|field| callee.field(field))
+}
+
+/// Walk up from `node` to find the nearest enclosing named definition.
+fn enclosing_symbol_name<D: Doc>(node: &Node<D>) -> Option<String> {
+    node.ancestors().find_map(|ancestor| {
+        if !ENCLOSING_KINDS.contains(&ancestor.kind().as_ref()) {
+            return None;
+        }
+        ancestor.children().find_map(|child| {
+            matches!(
+                child.kind().as_ref(),
+                "identifier" | "name" | "type_identifier"
+            )
+            .then(|| child.text().to_string())
+        })
+    })
+}
@@ -0,0 +1,212 @@
+//! Function signature extraction, backing `--signature`.
+//!
+//! Unlike `complexity.rs`'s `ComplexityRules`, which are data tables driving
+//! a single generic walker, typed-parameter grammars vary enough
+//! (containing nodes, field names, how a receiver like `&self` is
+//! represented) that a shared walker isn't worth it yet for the one
+//! language this module currently understands. `for_language` returns
+//! `None` for everything else, the same convention `ComplexityRules` uses,
+//! so callers fall back to treating the symbol as having no signature.
+//!
+//! TODO: add rules for TypeScript, Go, and Java once their grammars' exact
+//! field names for typed parameters and return types are verified against
+//! real parses.
+
+use outgrep_ast_core::{Doc, Node};
+use outgrep_ast_language::SupportLang;
+
+use crate::diagnostics::types::FunctionSignature;
+
+/// Node kinds, across the languages this module supports, that represent a
+/// function-like definition with typed parameters.
+const FUNCTION_KINDS: &[&str] = &["function_item"];
+
+/// Returns whether `kind` is one this module knows how to extract a
+/// signature from.
+pub fn is_signature_kind(lang: SupportLang, kind: &str) -> bool {
+    match lang {
+        SupportLang::Rust => FUNCTION_KINDS.contains(&kind),
+        _ => false,
+    }
+}
+
+/// Extract `node`'s parameter and return types, if `lang` and `node`'s kind
+/// are supported.
+///
+/// Rust's `self`/`&self`/`&mut self` receiver, when present, is reported as
+/// the first parameter using its own text rather than a type, since it has
+/// no separate type annotation to extract.
+pub fn extract_signature<D: Doc>(
+    lang: SupportLang,
+    node: &Node<D>,
+) -> Option<FunctionSignature> {
+    if !is_signature_kind(lang, &node.kind()) {
+        return None;
+    }
+    let params_node = node.field("parameters")?;
+    let params = params_node
+        .children()
+        .filter(|child| child.is_named())
+        .map(|child| match child.kind().as_ref() {
+            "self_parameter" => child.text().trim().to_string(),
+            _ => child
+                .field("type")
+                .map(|t| t.text().trim().to_string())
+                .unwrap_or_else(|| child.text().trim().to_string()),
+        })
+        .collect();
+    let return_type =
+        node.field("return_type").map(|t| t.text().trim().to_string());
+    Some(FunctionSignature { params, return_type })
+}
+
+/// A parsed `--signature` query, e.g. `(Path, &str) -> Result`.
+///
+/// Matching is intentionally loose: a query parameter matches if it's a
+/// substring of the candidate's parameter type (so `Path` matches `&Path`
+/// and `PathBuf`), and the return type likewise, so a query can name just
+/// the outer type (`Result`) without spelling out its generic arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureQuery {
+    params: Vec<String>,
+    return_type: Option<String>,
+}
+
+impl SignatureQuery {
+    /// Parse a query of the form `(Type, Type, ...) -> Type` or just
+    /// `(Type, Type, ...)` if the return type shouldn't be constrained.
+    /// Whitespace around parameters and the return type is trimmed; an
+    /// empty parameter list (`()`) matches only functions with no
+    /// parameters.
+    pub fn parse(query: &str) -> SignatureQuery {
+        let query = query.trim();
+        let (params_part, return_type) = match query.split_once("->") {
+            Some((params, ret)) => {
+                (params.trim(), Some(ret.trim().to_string()))
+            }
+            None => (query, None),
+        };
+        let params_part = params_part
+            .trim()
+            .strip_prefix('(')
+            .unwrap_or(params_part)
+            .strip_suffix(')')
+            .unwrap_or(params_part);
+        let params = if params_part.trim().is_empty() {
+            Vec::new()
+        } else {
+            params_part.split(',').map(|p| p.trim().to_string()).collect()
+        };
+        SignatureQuery { params, return_type }
+    }
+
+    /// Returns whether `signature` matches this query: the same number of
+    /// parameters, each containing its corresponding query parameter as a
+    /// substring, and (if the query names one) a return type containing the
+    /// query's return type as a substring.
+    pub fn matches(&self, signature: &FunctionSignature) -> bool {
+        if signature.params.len() != self.params.len() {
+            return false;
+        }
+        let params_match = self
+            .params
+            .iter()
+            .zip(&signature.params)
+            .all(|(want, got)| got.contains(want.as_str()));
+        if !params_match {
+            return false;
+        }
+        match &self.return_type {
+            None => true,
+            Some(want) => signature
+                .return_type
+                .as_deref()
+                .is_some_and(|got| got.contains(want.as_str())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use outgrep_ast_core::tree_sitter::LanguageExt;
+
+    fn sig(params: &[&str], return_type: Option<&str>) -> FunctionSignature {
+        FunctionSignature {
+            params: params.iter().map(|s| s.to_string()).collect(),
+            return_type: return_type.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn extracts_rust_function_signature() {
+        let code =
+            "fn read(path: &Path, mode: &str) -> Result<Vec<u8>> { todo!() }";
+        let ast_grep = outgrep_ast_language::Rust.ast_grep(code);
+        let root = ast_grep.root();
+        let func = root
+            .dfs()
+            .find(|n| n.kind() == "function_item")
+            .expect("function_item should be found");
+        let signature = extract_signature(SupportLang::Rust, &func)
+            .expect("rust function should have a signature");
+        assert_eq!(signature.params, vec!["&Path", "&str"]);
+        assert_eq!(signature.return_type.as_deref(), Some("Result<Vec<u8>>"));
+    }
+
+    #[test]
+    fn extracts_self_receiver_as_first_param() {
+        let code = "impl Foo { fn bar(&self, n: i32) {} }";
+        let ast_grep = outgrep_ast_language::Rust.ast_grep(code);
+        let root = ast_grep.root();
+        let func = root
+            .dfs()
+            .find(|n| n.kind() == "function_item")
+            .expect("function_item should be found");
+        let signature = extract_signature(SupportLang::Rust, &func)
+            .expect("method should have a signature");
+        assert_eq!(signature.params, vec!["&self", "i32"]);
+        assert_eq!(signature.return_type, None);
+    }
+
+    #[test]
+    fn unsupported_language_returns_none() {
+        let code = "def read(path, mode): pass";
+        let ast_grep = outgrep_ast_language::Python.ast_grep(code);
+        let root = ast_grep.root();
+        let func = root
+            .dfs()
+            .find(|n| n.kind() == "function_definition")
+            .expect("function_definition should be found");
+        assert!(extract_signature(SupportLang::Python, &func).is_none());
+    }
+
+    #[test]
+    fn query_matches_by_substring_ignoring_generics() {
+        let query = SignatureQuery::parse("(Path, &str) -> Result");
+        assert!(query.matches(&sig(
+            &["&Path", "&str"],
+            Some("Result<Vec<u8>, io::Error>")
+        )));
+    }
+
+    #[test]
+    fn query_rejects_wrong_arity() {
+        let query = SignatureQuery::parse("(Path) -> Result");
+        assert!(!query.matches(&sig(&["&Path", "&str"], Some("Result<()>"))));
+    }
+
+    #[test]
+    fn query_with_no_return_type_ignores_it() {
+        let query = SignatureQuery::parse("(i32)");
+        assert!(query.matches(&sig(&["i32"], None)));
+        assert!(query.matches(&sig(&["i32"], Some("bool"))));
+    }
+
+    #[test]
+    fn empty_parameter_list_matches_only_no_params() {
+        let query = SignatureQuery::parse("()");
+        assert!(query.matches(&sig(&[], None)));
+        assert!(!query.matches(&sig(&["i32"], None)));
+    }
+}
@@ -0,0 +1,217 @@
+/*!
+Native structural (symbol-level) diffing.
+
+[`GitAnalyzer::get_semantic_diff`](crate::diagnostics::GitAnalyzer::get_semantic_diff)
+shells out to the external `diffsitter` binary and still reports the result
+as colored line-oriented text. This module instead reuses the same
+tree-sitter-backed symbol extraction that powers `--syntax`
+([`extract_ast_info_for_language`](crate::diagnostics::ast_extractor::extract_ast_info_for_language))
+to compare two versions of a file symbol-by-symbol, reporting which
+functions, classes, types, and modules were added, removed, or edited --
+without needing an external tool on `$PATH`.
+*/
+
+use crate::diagnostics::ast_extractor::extract_ast_info_for_language;
+use crate::diagnostics::types::SymbolInfo;
+use outgrep_ast_language::SupportLang;
+
+/// A symbol present in both versions of a file whose source text changed.
+#[derive(Debug, Clone)]
+pub struct ModifiedSymbol {
+    pub before: SymbolInfo,
+    pub after: SymbolInfo,
+}
+
+/// The result of comparing two versions of a file symbol-by-symbol.
+///
+/// Symbols are matched by `(symbol_type, name)`, so renaming a function is
+/// reported as one removal and one addition rather than a modification --
+/// the same convention line-based diffs use for a deleted-then-recreated
+/// block.
+#[derive(Debug, Clone, Default)]
+pub struct StructuralDiff {
+    pub added: Vec<SymbolInfo>,
+    pub removed: Vec<SymbolInfo>,
+    pub modified: Vec<ModifiedSymbol>,
+}
+
+impl StructuralDiff {
+    /// Whether any symbol differs between the two versions compared.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+    }
+}
+
+/// Flatten an [`AstSymbolSummary`](crate::diagnostics::types::AstSymbolSummary)'s
+/// four buckets into a single list, since a symbol's bucket is already
+/// recorded on it via `symbol_type` and the diff doesn't need to treat the
+/// buckets separately.
+fn flatten_symbols(
+    summary: crate::diagnostics::types::AstSymbolSummary,
+) -> Vec<SymbolInfo> {
+    let mut symbols = summary.functions;
+    symbols.extend(summary.classes);
+    symbols.extend(summary.types);
+    symbols.extend(summary.modules);
+    symbols
+}
+
+/// Compute the [`StructuralDiff`] between `old_content` and `new_content`,
+/// both parsed as `language`.
+///
+/// Returns `None` if either version fails to parse (e.g. because it's
+/// empty or has a syntax error severe enough that tree-sitter can't
+/// recover a root node) -- there's no meaningful symbol comparison to make
+/// in that case.
+pub fn structural_diff(
+    old_content: &str,
+    new_content: &str,
+    language: SupportLang,
+) -> Option<StructuralDiff> {
+    let old_symbols = flatten_symbols(
+        extract_ast_info_for_language(language, old_content)?.symbols,
+    );
+    let new_symbols = flatten_symbols(
+        extract_ast_info_for_language(language, new_content)?.symbols,
+    );
+
+    let key = |s: &SymbolInfo| (s.symbol_type.clone(), s.name.clone());
+    let old_text = |s: &SymbolInfo| {
+        old_content.get(s.range.clone()).unwrap_or_default().to_string()
+    };
+    let new_text = |s: &SymbolInfo| {
+        new_content.get(s.range.clone()).unwrap_or_default().to_string()
+    };
+
+    let mut diff = StructuralDiff::default();
+    for new_symbol in &new_symbols {
+        match old_symbols.iter().find(|old| key(old) == key(new_symbol)) {
+            Some(old_symbol) => {
+                if old_text(old_symbol) != new_text(new_symbol) {
+                    diff.modified.push(ModifiedSymbol {
+                        before: old_symbol.clone(),
+                        after: new_symbol.clone(),
+                    });
+                }
+            }
+            None => diff.added.push(new_symbol.clone()),
+        }
+    }
+    for old_symbol in &old_symbols {
+        if !new_symbols.iter().any(|new| key(new) == key(old_symbol)) {
+            diff.removed.push(old_symbol.clone());
+        }
+    }
+
+    Some(diff)
+}
+
+/// Render a [`StructuralDiff`] as the `+`/`-`/`~` prefixed lines printed
+/// under the "Structural diff:" header in `--structural-diff` output.
+///
+/// Pulled out as a pure function (rather than left inline in the CLI's
+/// analyze loop) so the rendering itself has golden-file test coverage
+/// independent of git plumbing.
+pub fn format_lines(diff: &StructuralDiff) -> Vec<String> {
+    let mut lines = Vec::new();
+    for symbol in &diff.added {
+        lines.push(format!("+ {} {}", symbol.symbol_type, symbol.name));
+    }
+    for symbol in &diff.removed {
+        lines.push(format!("- {} {}", symbol.symbol_type, symbol.name));
+    }
+    for modified in &diff.modified {
+        lines.push(format!(
+            "~ {} {}",
+            modified.after.symbol_type, modified.after.name
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(symbol_type: &str, name: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            symbol_type: symbol_type.to_string(),
+            range: 0..0,
+            line: 1,
+            column: 1,
+            doc_comment: None,
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn format_lines_golden() {
+        let diff = StructuralDiff {
+            added: vec![symbol("function", "new_fn")],
+            removed: vec![symbol("struct", "OldStruct")],
+            modified: vec![ModifiedSymbol {
+                before: symbol("function", "changed_fn"),
+                after: symbol("function", "changed_fn"),
+            }],
+        };
+
+        insta::assert_debug_snapshot!(format_lines(&diff), @r###"
+        [
+            "+ function new_fn",
+            "- struct OldStruct",
+            "~ function changed_fn",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn format_lines_empty_diff_is_empty() {
+        insta::assert_debug_snapshot!(
+            format_lines(&StructuralDiff::default()),
+            @"[]"
+        );
+    }
+
+    #[test]
+    fn detects_added_and_removed_functions() {
+        let old = "fn a() {}\nfn b() {}\n";
+        let new = "fn a() {}\nfn c() {}\n";
+
+        let diff = structural_diff(old, new, SupportLang::Rust)
+            .expect("should parse");
+        assert_eq!(
+            diff.added.iter().map(|s| &s.name).collect::<Vec<_>>(),
+            vec!["c"]
+        );
+        assert_eq!(
+            diff.removed.iter().map(|s| &s.name).collect::<Vec<_>>(),
+            vec!["b"]
+        );
+        assert!(diff.modified.is_empty());
+    }
+
+    #[test]
+    fn detects_modified_function_body() {
+        let old = "fn a() { 1 }\n";
+        let new = "fn a() { 2 }\n";
+
+        let diff = structural_diff(old, new, SupportLang::Rust)
+            .expect("should parse");
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].before.name, "a");
+    }
+
+    #[test]
+    fn unchanged_file_has_no_diff() {
+        let content = "fn a() {}\nstruct S;\n";
+
+        let diff = structural_diff(content, content, SupportLang::Rust)
+            .expect("should parse");
+        assert!(diff.is_empty());
+    }
+}
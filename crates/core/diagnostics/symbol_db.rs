@@ -0,0 +1,407 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::diagnostics::types::{
+    AstSymbolSummary, FunctionSignature, SymbolInfo,
+};
+
+/// Persistent store of extracted symbols, backed by SQLite.
+///
+/// `--symbols` uses this as a parse cache, keyed by each file's mtime: see
+/// [`SymbolDatabase::cached_summary`] and [`SymbolDatabase::upsert_file`].
+/// A row's `mtime` no longer matching the file on disk means it's stale, so
+/// callers re-run the AST extractor and overwrite it rather than trusting
+/// it blindly.
+///
+/// TODO: `--find-references`, outline and rename still re-parse the world
+/// on every invocation; wiring them to read from this database too is
+/// tracked separately.
+pub struct SymbolDatabase {
+    conn: Connection,
+}
+
+/// Which [`AstSymbolSummary`] bucket a stored symbol came from, so a cache
+/// hit can rebuild the summary's shape instead of just a flat list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolBucket {
+    Function,
+    Class,
+    Type,
+    Module,
+}
+
+impl SymbolBucket {
+    fn as_str(self) -> &'static str {
+        match self {
+            SymbolBucket::Function => "function",
+            SymbolBucket::Class => "class",
+            SymbolBucket::Type => "type",
+            SymbolBucket::Module => "module",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<SymbolBucket> {
+        Some(match s {
+            "function" => SymbolBucket::Function,
+            "class" => SymbolBucket::Class,
+            "type" => SymbolBucket::Type,
+            "module" => SymbolBucket::Module,
+            _ => return None,
+        })
+    }
+}
+
+/// A symbol as stored in the database, with its owning file attached.
+#[derive(Debug, Clone)]
+pub struct StoredSymbol {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub start_byte: i64,
+    pub end_byte: i64,
+    pub line: i64,
+    pub column: i64,
+    pub signature: Option<String>,
+}
+
+impl SymbolDatabase {
+    /// Open (creating if necessary) a symbol database at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SymbolDatabase> {
+        let conn = Connection::open(path.as_ref()).with_context(|| {
+            format!(
+                "failed to open symbol database at {}",
+                path.as_ref().display()
+            )
+        })?;
+        let db = SymbolDatabase { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    /// Open an in-memory database, primarily useful for tests.
+    pub fn open_in_memory() -> Result<SymbolDatabase> {
+        let conn = Connection::open_in_memory()
+            .context("failed to open in-memory symbol database")?;
+        let db = SymbolDatabase { conn };
+        db.init_schema()?;
+        Ok(db)
+    }
+
+    fn init_schema(&self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                r"
+                CREATE TABLE IF NOT EXISTS symbols (
+                    id INTEGER PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    kind TEXT NOT NULL,
+                    bucket TEXT NOT NULL,
+                    path TEXT NOT NULL,
+                    start_byte INTEGER NOT NULL,
+                    end_byte INTEGER NOT NULL,
+                    line INTEGER NOT NULL,
+                    column INTEGER NOT NULL,
+                    signature TEXT
+                );
+                CREATE INDEX IF NOT EXISTS symbols_name ON symbols(name);
+                CREATE INDEX IF NOT EXISTS symbols_path ON symbols(path);
+
+                CREATE TABLE IF NOT EXISTS files (
+                    path TEXT PRIMARY KEY,
+                    mtime INTEGER NOT NULL
+                );
+                ",
+            )
+            .context("failed to initialize symbol database schema")
+    }
+
+    /// Replace all symbols recorded for `path` with `symbols`, and record
+    /// `mtime` as the point in time they were extracted from.
+    ///
+    /// `mtime` is later compared against the file's on-disk modification
+    /// time by [`SymbolDatabase::cached_summary`] to decide whether the
+    /// stored symbols are still trustworthy, so callers should pass the
+    /// exact mtime they read the file's contents at.
+    pub fn upsert_file(
+        &mut self,
+        path: &str,
+        mtime: i64,
+        symbols: &[(SymbolBucket, &SymbolInfo)],
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM symbols WHERE path = ?1", params![path])?;
+        tx.execute(
+            "INSERT INTO files (path, mtime) VALUES (?1, ?2)
+             ON CONFLICT(path) DO UPDATE SET mtime = excluded.mtime",
+            params![path, mtime],
+        )?;
+        for (bucket, symbol) in symbols {
+            let signature = symbol
+                .signature
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .context("failed to serialize symbol signature")?;
+            tx.execute(
+                "INSERT INTO symbols
+                    (name, kind, bucket, path, start_byte, end_byte, line, column, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    symbol.name,
+                    symbol.symbol_type,
+                    bucket.as_str(),
+                    path,
+                    symbol.range.start as i64,
+                    symbol.range.end as i64,
+                    symbol.line as i64,
+                    symbol.column as i64,
+                    signature,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replace all symbols recorded for `path` with the buckets in
+    /// `summary`, recording `mtime` as their extraction time.
+    ///
+    /// Convenience wrapper around [`SymbolDatabase::upsert_file`] for
+    /// callers that already have an [`AstSymbolSummary`] in hand, such as
+    /// `--symbols`.
+    pub fn upsert_summary(
+        &mut self,
+        path: &str,
+        mtime: i64,
+        summary: &AstSymbolSummary,
+    ) -> Result<()> {
+        let mut rows = Vec::new();
+        rows.extend(
+            summary.functions.iter().map(|s| (SymbolBucket::Function, s)),
+        );
+        rows.extend(summary.classes.iter().map(|s| (SymbolBucket::Class, s)));
+        rows.extend(summary.types.iter().map(|s| (SymbolBucket::Type, s)));
+        rows.extend(summary.modules.iter().map(|s| (SymbolBucket::Module, s)));
+        self.upsert_file(path, mtime, &rows)
+    }
+
+    /// Return the symbols recorded for `path` as an [`AstSymbolSummary`],
+    /// provided they were extracted at exactly `mtime`.
+    ///
+    /// Returns `Ok(None)` on a cache miss, i.e. `path` has never been
+    /// indexed or was last indexed at a different mtime, which callers
+    /// should treat as "re-run the AST extractor and call
+    /// [`SymbolDatabase::upsert_summary`] to refresh the cache".
+    pub fn cached_summary(
+        &self,
+        path: &str,
+        mtime: i64,
+    ) -> Result<Option<AstSymbolSummary>> {
+        let stored_mtime: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT mtime FROM files WHERE path = ?1",
+                params![path],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if stored_mtime != Some(mtime) {
+            return Ok(None);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT name, kind, bucket, start_byte, end_byte, line, column, signature
+             FROM symbols WHERE path = ?1 ORDER BY start_byte",
+        )?;
+        let mut summary = AstSymbolSummary::default();
+        let rows = stmt.query_map(params![path], |row| {
+            let bucket: String = row.get(2)?;
+            let signature: Option<String> = row.get(7)?;
+            Ok((bucket, Self::row_to_symbol_info(row)?, signature))
+        })?;
+        for row in rows {
+            let (bucket, symbol, signature) = row?;
+            let mut symbol = symbol;
+            symbol.signature = signature
+                .map(|s| serde_json::from_str::<FunctionSignature>(&s))
+                .transpose()
+                .context("failed to deserialize symbol signature")?;
+            match SymbolBucket::from_str(&bucket) {
+                Some(SymbolBucket::Function) => summary.functions.push(symbol),
+                Some(SymbolBucket::Class) => summary.classes.push(symbol),
+                Some(SymbolBucket::Type) => summary.types.push(symbol),
+                Some(SymbolBucket::Module) => summary.modules.push(symbol),
+                None => continue,
+            }
+        }
+        Ok(Some(summary))
+    }
+
+    fn row_to_symbol_info(
+        row: &rusqlite::Row<'_>,
+    ) -> rusqlite::Result<SymbolInfo> {
+        let start_byte: i64 = row.get(3)?;
+        let end_byte: i64 = row.get(4)?;
+        Ok(SymbolInfo {
+            name: row.get(0)?,
+            symbol_type: row.get(1)?,
+            range: start_byte as usize..end_byte as usize,
+            line: row.get(5)?,
+            column: row.get(6)?,
+            doc_comment: None,
+            signature: None,
+        })
+    }
+
+    /// Remove every symbol recorded for `path`, e.g. when a file is deleted.
+    pub fn remove_file(&self, path: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM symbols WHERE path = ?1", params![path])?;
+        Ok(())
+    }
+
+    /// Find all symbols with the given exact name, across every indexed
+    /// file.
+    pub fn find_by_name(&self, name: &str) -> Result<Vec<StoredSymbol>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, kind, path, start_byte, end_byte, line, column, signature
+             FROM symbols WHERE name = ?1 ORDER BY path, start_byte",
+        )?;
+        let rows = stmt.query_map(params![name], Self::row_to_symbol)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    /// List every symbol recorded for `path`, in source order.
+    pub fn symbols_in_file(&self, path: &str) -> Result<Vec<StoredSymbol>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, kind, path, start_byte, end_byte, line, column, signature
+             FROM symbols WHERE path = ?1 ORDER BY start_byte",
+        )?;
+        let rows = stmt.query_map(params![path], Self::row_to_symbol)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(Into::into)
+    }
+
+    fn row_to_symbol(
+        row: &rusqlite::Row<'_>,
+    ) -> rusqlite::Result<StoredSymbol> {
+        Ok(StoredSymbol {
+            name: row.get(0)?,
+            kind: row.get(1)?,
+            path: row.get(2)?,
+            start_byte: row.get(3)?,
+            end_byte: row.get(4)?,
+            line: row.get(5)?,
+            column: row.get(6)?,
+            signature: row.get(7)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sym(
+        name: &str,
+        symbol_type: &str,
+        start: usize,
+        end: usize,
+    ) -> SymbolInfo {
+        SymbolInfo {
+            name: name.to_string(),
+            symbol_type: symbol_type.to_string(),
+            range: start..end,
+            line: 1,
+            column: 1,
+            doc_comment: None,
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_find_by_name() {
+        let mut db = SymbolDatabase::open_in_memory().unwrap();
+        let parse = sym("parse", "function", 0, 10);
+        let config = sym("Config", "struct", 20, 40);
+        db.upsert_file(
+            "src/lib.rs",
+            1,
+            &[
+                (SymbolBucket::Function, &parse),
+                (SymbolBucket::Class, &config),
+            ],
+        )
+        .unwrap();
+
+        let found = db.find_by_name("parse").unwrap();
+        assert_eq!(1, found.len());
+        assert_eq!("src/lib.rs", found[0].path);
+        assert_eq!("function", found[0].kind);
+    }
+
+    #[test]
+    fn test_upsert_replaces_previous_symbols() {
+        let mut db = SymbolDatabase::open_in_memory().unwrap();
+        let old = sym("old", "function", 0, 5);
+        let new = sym("new", "function", 0, 5);
+        db.upsert_file("src/lib.rs", 1, &[(SymbolBucket::Function, &old)])
+            .unwrap();
+        db.upsert_file("src/lib.rs", 2, &[(SymbolBucket::Function, &new)])
+            .unwrap();
+
+        let symbols = db.symbols_in_file("src/lib.rs").unwrap();
+        assert_eq!(1, symbols.len());
+        assert_eq!("new", symbols[0].name);
+    }
+
+    #[test]
+    fn test_remove_file() {
+        let mut db = SymbolDatabase::open_in_memory().unwrap();
+        let parse = sym("parse", "function", 0, 10);
+        db.upsert_file("src/lib.rs", 1, &[(SymbolBucket::Function, &parse)])
+            .unwrap();
+        db.remove_file("src/lib.rs").unwrap();
+        assert!(db.symbols_in_file("src/lib.rs").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cached_summary_hit_and_miss() {
+        let mut db = SymbolDatabase::open_in_memory().unwrap();
+        let mut summary = AstSymbolSummary::default();
+        summary.functions.push(sym("parse", "function", 0, 10));
+        summary.classes.push(sym("Config", "struct", 20, 40));
+        db.upsert_summary("src/lib.rs", 100, &summary).unwrap();
+
+        let hit = db.cached_summary("src/lib.rs", 100).unwrap().unwrap();
+        assert_eq!(1, hit.functions.len());
+        assert_eq!(1, hit.classes.len());
+        assert_eq!("parse", hit.functions[0].name);
+
+        assert!(db.cached_summary("src/lib.rs", 200).unwrap().is_none());
+        assert!(db.cached_summary("src/other.rs", 100).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cached_summary_roundtrips_signature() {
+        let mut db = SymbolDatabase::open_in_memory().unwrap();
+        let mut summary = AstSymbolSummary::default();
+        let mut parse = sym("parse", "function", 0, 10);
+        parse.signature = Some(FunctionSignature {
+            params: vec!["&Path".to_string()],
+            return_type: Some("Result<()>".to_string()),
+        });
+        summary.functions.push(parse);
+        db.upsert_summary("src/lib.rs", 1, &summary).unwrap();
+
+        let hit = db.cached_summary("src/lib.rs", 1).unwrap().unwrap();
+        assert_eq!(
+            Some(FunctionSignature {
+                params: vec!["&Path".to_string()],
+                return_type: Some("Result<()>".to_string()),
+            }),
+            hit.functions[0].signature
+        );
+    }
+}
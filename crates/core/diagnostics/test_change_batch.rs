@@ -0,0 +1,38 @@
+use crate::diagnostics::{ChangeBatcher, FileWatcher};
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_change_batch_debounces_and_dedupes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+
+    let mut watcher =
+        FileWatcher::new().expect("Failed to create file watcher");
+    watcher.watch(temp_path).expect("Failed to start watching");
+    let mut batcher = ChangeBatcher::new(watcher, Duration::from_millis(200));
+
+    let test_file = temp_path.join("test.txt");
+    fs::write(&test_file, "one").expect("Failed to write test file");
+    fs::write(&test_file, "two").expect("Failed to modify test file");
+    fs::write(&test_file, "three").expect("Failed to modify test file");
+
+    let batch =
+        tokio::time::timeout(Duration::from_secs(2), batcher.next_batch())
+            .await
+            .expect("Timed out waiting for a change batch")
+            .expect("Watcher channel closed unexpectedly");
+
+    assert_eq!(batch.generation, 1);
+    // Three writes to the same path within the debounce window collapse
+    // into a single event for that path.
+    assert_eq!(batch.events.len(), 1);
+    let canonical_event_path = batch.events[0]
+        .path()
+        .canonicalize()
+        .unwrap_or_else(|_| batch.events[0].path().to_path_buf());
+    let canonical_test_path =
+        test_file.canonicalize().unwrap_or(test_file.clone());
+    assert_eq!(canonical_event_path, canonical_test_path);
+}
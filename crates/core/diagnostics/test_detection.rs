@@ -0,0 +1,94 @@
+//! Heuristics for detecting whether a file is test code or production code,
+//! based on its path and, where available, its contents.
+
+use std::path::Path;
+
+/// Detects test files using per-language path and content heuristics.
+pub struct TestDetector;
+
+impl TestDetector {
+    /// Returns true if `path` looks like a test file based on its location
+    /// or name, without reading its contents.
+    ///
+    /// This is the only check available before a file has been read (for
+    /// example, while deciding which files to search at all), so it leans
+    /// on conventions that are common across languages: a `tests/`-style
+    /// directory, a `test_`/`_test` name, or a `.spec`/`.test` suffix.
+    pub fn is_test_path(path: &Path) -> bool {
+        let in_test_dir = path.components().any(|c| {
+            matches!(
+                c.as_os_str().to_str(),
+                Some("tests") | Some("test") | Some("__tests__") | Some("spec")
+            )
+        });
+        if in_test_dir {
+            return true;
+        }
+
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str())
+        else {
+            return false;
+        };
+        let stem =
+            file_name.rsplit_once('.').map(|(s, _)| s).unwrap_or(file_name);
+
+        stem.starts_with("test_")
+            || stem.ends_with("_test")
+            || stem.ends_with("_tests")
+            || stem.ends_with(".test")
+            || stem.ends_with(".spec")
+    }
+
+    /// Returns true if `content` contains markers commonly used to declare
+    /// tests in one of our supported languages.
+    ///
+    /// This complements `is_test_path` for files whose name alone doesn't
+    /// give it away, such as a Rust module with production code and a
+    /// trailing `#[cfg(test)] mod tests` block.
+    pub fn is_test_content(content: &str) -> bool {
+        content.contains("#[test]")
+            || content.contains("#[cfg(test)]")
+            || content.contains("def test_")
+            || content.contains("func Test")
+            || content.contains("describe(")
+            || content.contains("it(\"")
+            || content.contains("it('")
+    }
+
+    /// Returns true if the file should be considered a test file, checking
+    /// both its path and its contents.
+    pub fn is_test_file(path: &Path, content: &str) -> bool {
+        Self::is_test_path(path) || Self::is_test_content(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_detects_test_directory() {
+        assert!(TestDetector::is_test_path(&PathBuf::from(
+            "src/tests/foo.rs"
+        )));
+    }
+
+    #[test]
+    fn test_detects_spec_file() {
+        assert!(TestDetector::is_test_path(&PathBuf::from(
+            "src/foo.spec.ts"
+        )));
+    }
+
+    #[test]
+    fn test_detects_rust_test_attribute() {
+        assert!(TestDetector::is_test_content("#[test]\nfn it_works() {}"));
+    }
+
+    #[test]
+    fn test_production_file_is_not_a_test() {
+        assert!(!TestDetector::is_test_path(&PathBuf::from("src/main.rs")));
+        assert!(!TestDetector::is_test_content("fn main() {}"));
+    }
+}
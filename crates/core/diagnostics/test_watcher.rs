@@ -62,6 +62,47 @@ async fn test_file_watcher_basic() {
     }
 }
 
+#[tokio::test]
+async fn test_file_watcher_rename() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let temp_path = temp_dir.path();
+
+    let mut watcher =
+        FileWatcher::new().expect("Failed to create file watcher");
+    watcher.watch(temp_path).expect("Failed to start watching");
+
+    let original = temp_path.join("original.txt");
+    fs::write(&original, "content").expect("Failed to write test file");
+
+    // Drain the create event for the initial write before renaming, so it
+    // isn't mistaken for part of the rename below.
+    watcher
+        .next_event_timeout(Duration::from_secs(2))
+        .await
+        .expect("No create event received within timeout");
+
+    let renamed = temp_path.join("renamed.txt");
+    fs::rename(&original, &renamed).expect("Failed to rename test file");
+
+    if let Some(event) =
+        watcher.next_event_timeout(Duration::from_secs(2)).await
+    {
+        match event {
+            FileChangeEvent::Renamed { from, to } => {
+                let canonical_to = to.canonicalize().unwrap_or(to.clone());
+                let canonical_renamed =
+                    renamed.canonicalize().unwrap_or(renamed.clone());
+                assert_eq!(canonical_to, canonical_renamed);
+                assert_eq!(from.file_name(), original.file_name());
+                println!("File rename detected: {:?} -> {:?}", from, to);
+            }
+            _ => panic!("Expected rename event, got: {:?}", event),
+        }
+    } else {
+        panic!("No file rename event received within timeout");
+    }
+}
+
 // #[test]
 // fn test_should_ignore_file() {
 //     assert!(FileWatcher::should_ignore_file(Path::new(".gitignore")));
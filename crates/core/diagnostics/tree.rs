@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use termcolor::WriteColor;
+
 use crate::diagnostics::types::{TreeNode, DirectoryNode, FileNode, GitFileStatus, FileDiagnostics};
-use crate::diagnostics::{MetricsCalculator, GitAnalyzer};
+use crate::diagnostics::{MetricsCalculator, GitAnalyzer, PathResolver};
 use crate::diagnostics::compiler::CompilerDiagnosticsRunner;
 
 /// Builder for constructing directory trees with metrics and git information
@@ -10,6 +12,7 @@ pub struct TreeBuilder {
     git_analyzer: GitAnalyzer,
     git_status: HashMap<PathBuf, GitFileStatus>,
     workspace_diagnostics: HashMap<PathBuf, FileDiagnostics>,
+    path_resolver: std::sync::Arc<PathResolver>,
     options: TreeDisplayOptions,
 }
 
@@ -22,19 +25,39 @@ impl TreeBuilder {
     /// Create a new tree builder with specific display options
     pub fn with_options<P: AsRef<Path>>(path: P, options: TreeDisplayOptions) -> Self {
         let git_analyzer = GitAnalyzer::new(&path);
-        let git_status = git_analyzer.get_status_for_cwd().unwrap_or_default();
-        
+
+        // Reuse the caller's resolver when one was already built alongside
+        // `options.git_status` (see `tree_only`/`tree_with_diff` in
+        // `main.rs`), so both sides of every git-status lookup agree on
+        // what key a given path resolves to.
+        let path_resolver = options.path_resolver.clone().unwrap_or_else(|| {
+            std::sync::Arc::new(PathResolver::new(
+                git_analyzer.get_repo_root().map(|root| root.to_path_buf()),
+            ))
+        });
+
+        let git_status = git_analyzer
+            .get_status_for_cwd()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(path, status)| (path_resolver.resolve(&path), status))
+            .collect();
+
         // Run workspace-wide diagnostics once if diagnostics are enabled
         let workspace_diagnostics = if options.show_diagnostics {
             Self::run_workspace_diagnostics(&path)
+                .into_iter()
+                .map(|(path, diag)| (path_resolver.resolve(&path), diag))
+                .collect()
         } else {
             HashMap::new()
         };
-        
+
         Self {
             git_analyzer,
             git_status,
             workspace_diagnostics,
+            path_resolver,
             options,
         }
     }
@@ -58,11 +81,20 @@ impl TreeBuilder {
             .git_exclude(true)
             .ignore(true)
             .parents(true)
+            .follow_links(self.options.follow_symlinks)
             .build();
-        
-        let mut all_entries: Vec<_> = walker
-            .filter_map(|result| result.ok())
-            .collect();
+
+        // Unlike a plain `.filter_map(Result::ok)`, report walk errors
+        // instead of swallowing them. With `--follow` enabled this is how a
+        // symlink cycle becomes visible: the underlying walker detects the
+        // loop and returns an `Err` for it rather than recursing forever.
+        let mut all_entries = Vec::new();
+        for result in walker {
+            match result {
+                Ok(entry) => all_entries.push(entry),
+                Err(err) => eprintln!("og: tree: {}", err),
+            }
+        }
         
         // Sort entries to ensure consistent tree building
         all_entries.sort_by(|a, b| a.path().cmp(b.path()));
@@ -123,7 +155,10 @@ impl TreeBuilder {
                 let mut dir_node = DirectoryNode::new(name.clone(), dir_path.clone());
                 
                 // Set git status for this directory if available
-                dir_node.git_status = self.git_status.get(&dir_path).cloned();
+                dir_node.git_status = self
+                    .git_status
+                    .get(&self.path_resolver.resolve(&dir_path))
+                    .cloned();
                 
                 current.children.insert(name.clone(), TreeNode::Directory(dir_node));
             }
@@ -182,42 +217,124 @@ impl TreeBuilder {
         
         // Create the file node
         let mut file_node = FileNode::new(file_name.clone(), full_path.to_path_buf());
-        
+
+        // Classify the entry with `symlink_metadata`, which does not follow
+        // symlinks, so sockets/fifos/devices are never mistaken for regular
+        // files and handed to a reader.
+        file_node.kind = Self::classify_file_kind(full_path);
+        let is_regular = file_node.kind == crate::diagnostics::types::FileKind::Regular;
+
         // Set git status
-        file_node.git_status = self.git_status.get(relative_path).cloned();
-        
-        // Detect language from extension
+        file_node.git_status = self
+            .git_status
+            .get(&self.path_resolver.resolve(relative_path))
+            .cloned();
+
+        // Detect language from extension, falling back to magic-byte
+        // sniffing for regular files whose extension is missing or unknown
+        // ("Other") so binary files get a real type in tree/analysis output
+        // instead of a dead end.
         file_node.language = self.detect_language(full_path);
-        
-        // Calculate metrics for source files if analysis is enabled
-        if self.options.show_analysis && self.is_source_file(full_path) {
-            if let Ok(content) = std::fs::read_to_string(full_path) {
-                if let Ok(metrics) = MetricsCalculator::calculate_metrics(full_path, &content) {
+        if is_regular
+            && matches!(file_node.language.as_deref(), None | Some("Other"))
+        {
+            if let Some(kind) =
+                crate::diagnostics::detect_by_magic_bytes(full_path)
+            {
+                file_node.language = Some(kind);
+            }
+        }
+
+        // Calculate metrics for source files if analysis is enabled. Content
+        // is transcoded from its detected encoding first, so UTF-16 sources
+        // (which `read_to_string` would reject as invalid UTF-8) are
+        // measured instead of silently skipped.
+        if is_regular
+            && self.options.show_analysis
+            && self.is_source_file(full_path)
+        {
+            if let Some((content, encoding)) =
+                crate::diagnostics::read_source_file(full_path)
+            {
+                if let Ok(metrics) =
+                    MetricsCalculator::calculate_metrics(full_path, &content)
+                {
                     file_node.metrics = Some(metrics);
+                    file_node.encoding = Some(encoding);
                 }
             }
         }
-        
+
         // Run compiler diagnostics for this file if diagnostics are enabled
-        if self.options.show_diagnostics && self.is_source_file(full_path) {
+        if is_regular && self.options.show_diagnostics && self.is_source_file(full_path) {
             file_node.diagnostics = self.run_diagnostics_for_file(full_path);
         }
-        
+
         // Extract AST structure for supported files if syntax analysis is enabled
-        if self.options.show_syntax && self.is_source_file(full_path) {
+        if is_regular && self.options.show_syntax && self.is_source_file(full_path) {
             file_node.ast_structure = crate::diagnostics::extract_ast_structure(full_path);
         }
-        
+
         // Set last modified time
-        if let Ok(metadata) = std::fs::metadata(full_path) {
+        if let Ok(metadata) = std::fs::symlink_metadata(full_path) {
             file_node.last_modified = metadata.modified().ok();
         }
-        
+
         current.children.insert(file_name, TreeNode::File(file_node));
         
         Ok(())
     }
     
+    /// Classify a filesystem entry without following symlinks.
+    ///
+    /// Sockets, FIFOs, and device files are reported as their own kind
+    /// instead of being treated as regular files, since reading them can
+    /// block forever or return garbage rather than file content.
+    fn classify_file_kind(path: &Path) -> crate::diagnostics::types::FileKind {
+        use crate::diagnostics::types::FileKind;
+
+        let metadata = match std::fs::symlink_metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return FileKind::Regular,
+        };
+        let file_type = metadata.file_type();
+
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(path).ok();
+            return FileKind::Symlink { target };
+        }
+
+        Self::classify_special_file(&file_type).unwrap_or(FileKind::Regular)
+    }
+
+    #[cfg(unix)]
+    fn classify_special_file(
+        file_type: &std::fs::FileType,
+    ) -> Option<crate::diagnostics::types::FileKind> {
+        use std::os::unix::fs::FileTypeExt;
+
+        use crate::diagnostics::types::FileKind;
+
+        if file_type.is_socket() {
+            Some(FileKind::Socket)
+        } else if file_type.is_fifo() {
+            Some(FileKind::Fifo)
+        } else if file_type.is_char_device() {
+            Some(FileKind::CharDevice)
+        } else if file_type.is_block_device() {
+            Some(FileKind::BlockDevice)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn classify_special_file(
+        _file_type: &std::fs::FileType,
+    ) -> Option<crate::diagnostics::types::FileKind> {
+        None
+    }
+
     /// Check if a file should be skipped (lock files, etc.)
     fn should_skip_file(&self, path: &Path) -> bool {
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
@@ -427,33 +544,45 @@ impl TreeBuilder {
     /// Run compiler diagnostics for a file
     fn run_diagnostics_for_file(&self, file_path: &Path) -> Option<FileDiagnostics> {
         // First check cached diagnostics
-        if let Some(diagnostics) = self.get_diagnostics_for_file(file_path) {
+        if let Some(mut diagnostics) = self.get_diagnostics_for_file(file_path) {
+            self.add_language_mismatch_hint(file_path, &mut diagnostics);
             return Some(diagnostics);
         }
-        
+
         // Run fresh diagnostics using CompilerDiagnosticsRunner
         let language_str = Self::detect_language_from_extension(file_path);
-        CompilerDiagnosticsRunner::run_diagnostics(file_path, language_str)
+        let mut diagnostics = CompilerDiagnosticsRunner::run_diagnostics(file_path, language_str);
+        match &mut diagnostics {
+            Some(diagnostics) => self.add_language_mismatch_hint(file_path, diagnostics),
+            None => {
+                let mut fresh = FileDiagnostics::default();
+                self.add_language_mismatch_hint(file_path, &mut fresh);
+                if fresh.total_count() > 0 {
+                    diagnostics = Some(fresh);
+                }
+            }
+        }
+        diagnostics
     }
 
-    /// Get diagnostics for a file with robust path matching
-    fn get_diagnostics_for_file(&self, file_path: &Path) -> Option<FileDiagnostics> {
-        // Try exact path match first
-        if let Some(diagnostics) = self.workspace_diagnostics.get(file_path) {
-            return Some(diagnostics.clone());
+    /// Append a `mixed-language` hint to `diagnostics` when the file's
+    /// extension and its content disagree about what language it's written
+    /// in, e.g. TypeScript syntax saved with a `.js` extension.
+    fn add_language_mismatch_hint(&self, file_path: &Path, diagnostics: &mut FileDiagnostics) {
+        let Ok(content) = std::fs::read_to_string(file_path) else { return };
+        if let Some(hint) = crate::diagnostics::check_language_mismatch(file_path, &content) {
+            diagnostics.add_diagnostic(hint);
         }
-        
-        // Try all stored paths to find a match
-        for (stored_path, diagnostics) in &self.workspace_diagnostics {
-            // Check if paths point to the same file
-            if Self::paths_match(file_path, stored_path) {
-                return Some(diagnostics.clone());
-            }
-        }
-        
-        None
     }
-    
+
+    /// Get diagnostics for a file, keyed by `path_resolver` the same way
+    /// `workspace_diagnostics` itself was populated.
+    fn get_diagnostics_for_file(&self, file_path: &Path) -> Option<FileDiagnostics> {
+        self.workspace_diagnostics
+            .get(&self.path_resolver.resolve(file_path))
+            .cloned()
+    }
+
     /// Detect language from file extension
     fn detect_language_from_extension(path: &Path) -> Option<&'static str> {
         if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
@@ -471,32 +600,6 @@ impl TreeBuilder {
         }
     }
     
-    /// Check if two paths refer to the same file
-    fn paths_match(path1: &Path, path2: &Path) -> bool {
-        // Try exact match
-        if path1 == path2 {
-            return true;
-        }
-        
-        // Try canonicalized paths
-        if let (Ok(canon1), Ok(canon2)) = (path1.canonicalize(), path2.canonicalize()) {
-            if canon1 == canon2 {
-                return true;
-            }
-        }
-        
-        // Try file name match (last resort)
-        if let (Some(name1), Some(name2)) = (path1.file_name(), path2.file_name()) {
-            if name1 == name2 {
-                // Check if the path endings match (same directory structure)
-                let components1: Vec<_> = path1.components().rev().take(3).collect();
-                let components2: Vec<_> = path2.components().rev().take(3).collect();
-                return components1 == components2;
-            }
-        }
-        
-        false
-    }
 }
 
 /// Display a tree structure with proper formatting
@@ -510,12 +613,47 @@ pub struct TreeDisplayOptions {
     pub show_analysis: bool,
     pub show_diagnostics: bool,
     pub show_syntax: bool,
+    pub symbol_kinds: Vec<String>,
+    pub ast_depth: Option<usize>,
+    pub ast_max_nodes: Option<usize>,
+    pub ast_summary: bool,
+    pub with_docs: bool,
     pub truncate_diffs: bool,
     pub output_json: bool,
     pub git_status: std::collections::HashMap<std::path::PathBuf, crate::diagnostics::GitFileStatus>,
+    /// Resolves paths to the same key `git_status` (and, inside
+    /// `TreeBuilder`, `workspace_diagnostics`) is keyed by, replacing the
+    /// ad hoc canonicalize/relative-path/filename fallback chains lookups
+    /// used to need. `None` (e.g. `TreeDisplayOptions::default()`) falls
+    /// back to matching `git_status` keys exactly, unresolved.
+    pub path_resolver: Option<std::sync::Arc<crate::diagnostics::PathResolver>>,
+    /// Follow symlinks while walking, mirroring the `--follow` flag used by
+    /// search. Symlinked directories are only descended into when this is
+    /// set, and then appear as ordinary directory nodes with no symlink
+    /// marking. Every other symlink entry is reported as `FileKind::Symlink`
+    /// rather than being read as if it were the target file.
+    pub follow_symlinks: bool,
+    /// Whether diff lines and summary headings/numbers should be colored,
+    /// mirroring the resolved `--color` choice used by search. `false` means
+    /// plain text, matching `--color=never` or output that isn't a tty.
+    pub use_color: bool,
 }
 
 impl TreeDisplay {
+    /// Look up `path`'s git status in `options.git_status`, resolving it
+    /// through `options.path_resolver` first when one is set so the lookup
+    /// doesn't depend on `path` already being in exactly the form the map
+    /// was built with.
+    fn lookup_git_status<'a>(
+        options: &'a TreeDisplayOptions,
+        path: &Path,
+    ) -> Option<&'a GitFileStatus> {
+        match &options.path_resolver {
+            Some(resolver) => options.git_status.get(&resolver.resolve(path)),
+            None => options.git_status.get(path),
+        }
+    }
+
     /// Display a tree node with proper indentation and formatting (legacy method)
     pub fn display_tree(node: &TreeNode, show_metrics: bool) {
         let options = TreeDisplayOptions {
@@ -616,6 +754,16 @@ impl TreeDisplay {
                     ));
                 }
                 
+                // Add file kind, and symlink target when applicable
+                file_obj.insert("kind".to_string(), serde_json::Value::String(
+                    Self::file_kind_to_string(&file.kind).to_string()
+                ));
+                if let crate::diagnostics::types::FileKind::Symlink { target: Some(target) } = &file.kind {
+                    file_obj.insert("symlink_target".to_string(), serde_json::Value::String(
+                        target.to_string_lossy().to_string()
+                    ));
+                }
+
                 // Add language if available
                 if let Some(language) = &file.language {
                     file_obj.insert("language".to_string(), serde_json::Value::String(language.clone()));
@@ -642,31 +790,19 @@ impl TreeDisplay {
                         metrics_obj.insert("blank_lines".to_string(), serde_json::Value::Number(metrics.blank_lines.into()));
                         metrics_obj.insert("function_count".to_string(), serde_json::Value::Number(metrics.function_count.into()));
                         metrics_obj.insert("cyclomatic_complexity".to_string(), serde_json::Value::Number(metrics.cyclomatic_complexity.into()));
+                        metrics_obj.insert("cognitive_complexity".to_string(), serde_json::Value::Number(metrics.cognitive_complexity.into()));
+                        metrics_obj.insert("max_nesting_depth".to_string(), serde_json::Value::Number(metrics.max_nesting_depth.into()));
+                        metrics_obj.insert("max_function_length".to_string(), serde_json::Value::Number(metrics.max_function_length.into()));
+                        if let Some(avg_function_length) = serde_json::Number::from_f64(metrics.avg_function_length) {
+                            metrics_obj.insert("avg_function_length".to_string(), serde_json::Value::Number(avg_function_length));
+                        }
                         file_obj.insert("metrics".to_string(), serde_json::Value::Object(metrics_obj));
                     }
                 }
                 
                 // Add diff information if enabled and file has changes
                 if options.show_diffs {
-                    // Enhanced path matching for git status lookup
-                    let git_status = options.git_status.get(&file.path)
-                        .or_else(|| {
-                            // Try looking up by relative path
-                            if let Ok(current_dir) = std::env::current_dir() {
-                                if let Ok(relative) = file.path.strip_prefix(&current_dir) {
-                                    return options.git_status.get(relative);
-                                }
-                            }
-                            None
-                        })
-                        .or_else(|| {
-                            // Try stripping ./ prefix if present
-                            if let Some(stripped) = file.path.to_string_lossy().strip_prefix("./") {
-                                let path_without_prefix = std::path::Path::new(stripped);
-                                return options.git_status.get(path_without_prefix);
-                            }
-                            None
-                        });
+                    let git_status = Self::lookup_git_status(options, &file.path);
 
                     if let Some(status) = git_status {
                         if matches!(status, crate::diagnostics::GitFileStatus::Modified | crate::diagnostics::GitFileStatus::Staged) {
@@ -731,8 +867,36 @@ impl TreeDisplay {
                 // Add AST structure if available and syntax analysis is enabled
                 if options.show_syntax {
                     if let Some(ast_structure) = &file.ast_structure {
-                        if let Ok(ast_json) = serde_json::to_value(ast_structure) {
-                            file_obj.insert("ast_structure".to_string(), ast_json);
+                        let mut ast_structure = ast_structure.clone();
+                        ast_structure.symbols =
+                            ast_structure.symbols.filtered(&options.symbol_kinds);
+                        if !options.with_docs {
+                            ast_structure.symbols = ast_structure.symbols.without_docs();
+                        }
+
+                        if options.ast_summary {
+                            let node_counts = ast_structure.node_counts();
+                            ast_structure.root_nodes = Vec::new();
+                            ast_structure.syntax_tokens = Vec::new();
+                            if let Ok(serde_json::Value::Object(mut ast_obj)) =
+                                serde_json::to_value(&ast_structure)
+                            {
+                                if let Ok(node_counts_json) = serde_json::to_value(&node_counts) {
+                                    ast_obj.insert("node_counts".to_string(), node_counts_json);
+                                }
+                                file_obj.insert("ast_structure".to_string(), serde_json::Value::Object(ast_obj));
+                            }
+                        } else {
+                            let (ast_structure, truncated) =
+                                ast_structure.limited(options.ast_depth, options.ast_max_nodes);
+                            if let Ok(serde_json::Value::Object(mut ast_obj)) =
+                                serde_json::to_value(&ast_structure)
+                            {
+                                if truncated {
+                                    ast_obj.insert("truncated".to_string(), serde_json::Value::Bool(true));
+                                }
+                                file_obj.insert("ast_structure".to_string(), serde_json::Value::Object(ast_obj));
+                            }
                         }
                     }
                 }
@@ -782,7 +946,21 @@ impl TreeDisplay {
             crate::diagnostics::GitFileStatus::Conflicted => "conflicted".to_string(),
         }
     }
-    
+
+    /// Convert a file kind to string for JSON
+    fn file_kind_to_string(kind: &crate::diagnostics::types::FileKind) -> &'static str {
+        use crate::diagnostics::types::FileKind;
+
+        match kind {
+            FileKind::Regular => "file",
+            FileKind::Symlink { .. } => "symlink",
+            FileKind::Socket => "socket",
+            FileKind::Fifo => "fifo",
+            FileKind::CharDevice => "char_device",
+            FileKind::BlockDevice => "block_device",
+        }
+    }
+
     /// Recursively display a tree node
     fn display_node(node: &TreeNode, prefix: &str, is_last: bool, show_metrics: bool) {
         let options = TreeDisplayOptions {
@@ -854,9 +1032,11 @@ impl TreeDisplay {
             String::new()
         };
         
+        let kind_info = Self::file_kind_suffix(&file.kind);
+
         let git_icon = Self::get_git_icon(&file.git_status);
-        println!("{}{}{}{}{}{}{}", 
-            prefix, connector, git_icon, icon, name, language_info, metrics_info);
+        println!("{}{}{}{}{}{}{}{}",
+            prefix, connector, git_icon, icon, name, kind_info, language_info, metrics_info);
         
         // Show additional file-centric information with proper indentation
         let file_prefix = format!("{}    ", prefix);
@@ -865,18 +1045,12 @@ impl TreeDisplay {
         if options.show_diffs {
             let file_path = &file.path;
             
-            // Try to get status from file or from options map
-            let status = file.git_status.as_ref()
-                .or_else(|| options.git_status.get(file_path))
-                .or_else(|| {
-                    // Try looking up by relative path from current directory
-                    if let Ok(current_dir) = std::env::current_dir() {
-                        if let Ok(relative) = file_path.strip_prefix(&current_dir) {
-                            return options.git_status.get(relative);
-                        }
-                    }
-                    None
-                });
+            // Prefer the status already resolved onto the node itself,
+            // falling back to a fresh lookup for nodes built without one.
+            let status = file
+                .git_status
+                .as_ref()
+                .or_else(|| Self::lookup_git_status(options, file_path));
             
             if let Some(status) = status {
                 match status {
@@ -907,6 +1081,9 @@ impl TreeDisplay {
                 println!("{}│  • Comment lines: {}", file_prefix, metrics.comment_lines);
                 println!("{}│  • Functions: {}", file_prefix, metrics.function_count);
                 println!("{}│  • Complexity: {}", file_prefix, metrics.cyclomatic_complexity);
+                println!("{}│  • Max nesting depth: {}", file_prefix, metrics.max_nesting_depth);
+                println!("{}│  • Max function length: {} lines", file_prefix, metrics.max_function_length);
+                println!("{}│  • Avg function length: {:.1} lines", file_prefix, metrics.avg_function_length);
             }
         }
         
@@ -964,60 +1141,111 @@ impl TreeDisplay {
                 let connector = if has_other_sections { "├─" } else { "└─" };
                 
                 println!("{}{} AST Structure:", file_prefix, connector);
-                Self::display_ast_structure(ast_structure, &format!("{}│  ", file_prefix));
+                Self::display_ast_structure(
+                    ast_structure,
+                    &format!("{}│  ", file_prefix),
+                    &options.symbol_kinds,
+                    options.ast_depth,
+                    options.ast_max_nodes,
+                    options.ast_summary,
+                    options.with_docs,
+                );
             }
         }
     }
-    
-    /// Display AST structure in a readable tree format
-    fn display_ast_structure(ast: &crate::diagnostics::types::AstStructure, prefix: &str) {
+
+    /// Display AST structure in a readable tree format, restricting symbol
+    /// output to `symbol_kinds` (empty means show every kind) and the node
+    /// listing to `ast_depth`/`ast_max_nodes` (or to per-kind counts, if
+    /// `ast_summary` is set).
+    fn display_ast_structure(
+        ast: &crate::diagnostics::types::AstStructure,
+        prefix: &str,
+        symbol_kinds: &[String],
+        ast_depth: Option<usize>,
+        ast_max_nodes: Option<usize>,
+        ast_summary: bool,
+        with_docs: bool,
+    ) {
         println!("{}Language: {}", prefix, ast.language);
-        
-        if !ast.root_nodes.is_empty() {
-            println!("{}Root nodes: {}", prefix, ast.root_nodes.len());
-            for (i, root) in ast.root_nodes.iter().enumerate().take(3) {
-                println!("{}  {}. {} ({}..{})", prefix, i + 1, root.node_type, root.range.start, root.range.end);
+
+        if ast_summary {
+            let counts = ast.node_counts();
+            println!("{}Total nodes: {}", prefix, counts.total);
+            for (kind, count) in &counts.by_kind {
+                println!("{}  {}: {}", prefix, kind, count);
+            }
+        } else {
+            let (ast, truncated) = ast.limited(ast_depth, ast_max_nodes);
+            if !ast.root_nodes.is_empty() {
+                println!("{}Root nodes: {}", prefix, ast.root_nodes.len());
+                for (i, root) in ast.root_nodes.iter().enumerate().take(3) {
+                    println!("{}  {}. {} ({}..{})", prefix, i + 1, root.node_type, root.range.start, root.range.end);
+                }
+                if ast.root_nodes.len() > 3 {
+                    println!("{}  ... and {} more", prefix, ast.root_nodes.len() - 3);
+                }
             }
-            if ast.root_nodes.len() > 3 {
-                println!("{}  ... and {} more", prefix, ast.root_nodes.len() - 3);
+            if truncated {
+                println!("{}  (truncated by --ast-depth/--ast-max-nodes)", prefix);
             }
         }
-        
-        if !ast.symbols.functions.is_empty() {
+
+        let symbols = ast.symbols.filtered(symbol_kinds);
+
+        let print_doc = |prefix: &str, doc: &Option<String>| {
+            if with_docs {
+                if let Some(doc) = doc {
+                    for line in doc.lines() {
+                        println!("{}      {}", prefix, line);
+                    }
+                }
+            }
+        };
+
+        if !symbols.functions.is_empty() {
             println!("{}Functions:", prefix);
-            for func in &ast.symbols.functions {
+            for func in &symbols.functions {
                 println!("{}  • {} (line {})", prefix, func.name, func.line);
+                print_doc(prefix, &func.doc_comment);
             }
         }
-        
-        if !ast.symbols.classes.is_empty() {
+
+        if !symbols.classes.is_empty() {
             println!("{}Classes/Structs:", prefix);
-            for class in &ast.symbols.classes {
+            for class in &symbols.classes {
                 println!("{}  • {} (line {})", prefix, class.name, class.line);
+                print_doc(prefix, &class.doc_comment);
             }
         }
-        
-        if !ast.symbols.types.is_empty() {
+
+        if !symbols.types.is_empty() {
             println!("{}Types:", prefix);
-            for type_def in &ast.symbols.types {
+            for type_def in &symbols.types {
                 println!("{}  • {} (line {})", prefix, type_def.name, type_def.line);
+                print_doc(prefix, &type_def.doc_comment);
             }
         }
-        
-        if !ast.symbols.modules.is_empty() {
+
+        if !symbols.modules.is_empty() {
             println!("{}Modules:", prefix);
-            for module in &ast.symbols.modules {
+            for module in &symbols.modules {
                 println!("{}  • {} (line {})", prefix, module.name, module.line);
+                print_doc(prefix, &module.doc_comment);
             }
         }
-        
+
         if !ast.syntax_tokens.is_empty() {
             println!("{}Syntax tokens: {} total", prefix, ast.syntax_tokens.len());
         }
     }
     
     /// Display diff information for a file with original formatting and optional truncation
-    fn display_file_diff_with_options(file_path: &std::path::Path, prefix: &str, options: &TreeDisplayOptions) {
+    fn display_file_diff_with_options(
+        file_path: &std::path::Path,
+        prefix: &str,
+        options: &TreeDisplayOptions,
+    ) {
         // Try regular git diff for tracked files first
         if let Ok(output) = std::process::Command::new("git")
             .args(&["diff", "HEAD", "--"])
@@ -1026,11 +1254,16 @@ impl TreeDisplay {
         {
             if !output.stdout.is_empty() {
                 let diff_content = String::from_utf8_lossy(&output.stdout);
-                Self::print_diff_content(&diff_content, prefix, options.truncate_diffs);
+                Self::print_diff_content(
+                    &diff_content,
+                    prefix,
+                    options.truncate_diffs,
+                    options.use_color,
+                );
                 return;
             }
         }
-        
+
         // Fall back to diff against /dev/null for untracked files
         if let Ok(output) = std::process::Command::new("git")
             .args(&["diff", "--no-index", "/dev/null"])
@@ -1039,39 +1272,51 @@ impl TreeDisplay {
         {
             if !output.stdout.is_empty() {
                 let diff_content = String::from_utf8_lossy(&output.stdout);
-                Self::print_diff_content(&diff_content, prefix, options.truncate_diffs);
+                Self::print_diff_content(
+                    &diff_content,
+                    prefix,
+                    options.truncate_diffs,
+                    options.use_color,
+                );
             }
         }
     }
-    
+
     /// Print diff content with syntax highlighting and optional truncation
-    fn print_diff_content(diff_content: &str, prefix: &str, truncate: bool) {
+    fn print_diff_content(
+        diff_content: &str,
+        prefix: &str,
+        truncate: bool,
+        use_color: bool,
+    ) {
         let lines: Vec<&str> = diff_content.lines().collect();
-        
+
         let lines_to_show = if truncate && lines.len() > 15 {
             &lines[..15]
         } else {
             &lines
         };
-        
+
         // Print lines with syntax highlighting
         for line in lines_to_show {
-            let highlighted_line = Self::highlight_diff_line(line);
+            let highlighted_line = Self::highlight_diff_line(line, use_color);
             println!("{}{}", prefix, highlighted_line);
         }
-        
+
         // Show truncation message if needed
         if truncate && lines.len() > 15 {
             println!("{}... (truncated, showing first 15 lines of {} total)", prefix, lines.len());
         }
     }
-    
-    /// Apply syntax highlighting to a diff line based on its prefix
-    fn highlight_diff_line(line: &str) -> String {
-        if line.is_empty() {
+
+    /// Apply syntax highlighting to a diff line based on its prefix, unless
+    /// `use_color` is false (matching `--color=never` or non-tty output), in
+    /// which case the line is returned unmodified.
+    fn highlight_diff_line(line: &str, use_color: bool) -> String {
+        if line.is_empty() || !use_color {
             return line.to_string();
         }
-        
+
         let first_char = line.chars().next().unwrap();
         match first_char {
             '+' => {
@@ -1133,36 +1378,143 @@ impl TreeDisplay {
             None => "",
         }
     }
-    
+
+    /// Render a suffix describing a non-regular file's kind, e.g.
+    /// ` -> target` for a symlink or ` [socket]` for a Unix domain socket.
+    /// Regular files get no suffix.
+    fn file_kind_suffix(kind: &crate::diagnostics::types::FileKind) -> String {
+        use crate::diagnostics::types::FileKind;
+
+        match kind {
+            FileKind::Regular => String::new(),
+            FileKind::Symlink { target: Some(target) } => {
+                format!(" -> {}", target.display())
+            }
+            FileKind::Symlink { target: None } => " -> ?".to_string(),
+            FileKind::Socket => " [socket]".to_string(),
+            FileKind::Fifo => " [fifo]".to_string(),
+            FileKind::CharDevice => " [char device]".to_string(),
+            FileKind::BlockDevice => " [block device]".to_string(),
+        }
+    }
+
     /// Display directory statistics summary
-    pub fn display_summary(node: &TreeNode) {
+    pub fn display_summary(
+        node: &TreeNode,
+        colors: &grep::printer::ColorSpecs,
+        use_color: bool,
+    ) {
         if let TreeNode::Directory(dir) = node {
             println!();
-            println!("Directory Summary:");
-            println!("  Total files: {}", dir.stats.total_files);
-            println!("  Total directories: {}", dir.stats.total_directories);
-            println!("  Total lines of code: {}", dir.stats.total_loc);
-            println!("  Total comment lines: {}", dir.stats.total_comments);
-            println!("  Total functions: {}", dir.stats.total_functions);
-            println!("  Average complexity: {:.1}", 
-                if dir.stats.total_functions > 0 { 
-                    dir.stats.total_complexity as f64 / dir.stats.total_functions as f64 
-                } else { 
-                    0.0 
-                }
+            println!(
+                "{}",
+                Self::style(colors.heading(), use_color, "Directory Summary:")
             );
-            
+            println!(
+                "  Total files: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &dir.stats.total_files.to_string()
+                )
+            );
+            println!(
+                "  Total directories: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &dir.stats.total_directories.to_string()
+                )
+            );
+            println!(
+                "  Total lines of code: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &dir.stats.total_loc.to_string()
+                )
+            );
+            println!(
+                "  Total comment lines: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &dir.stats.total_comments.to_string()
+                )
+            );
+            println!(
+                "  Total functions: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &dir.stats.total_functions.to_string()
+                )
+            );
+            let avg_complexity = if dir.stats.total_functions > 0 {
+                dir.stats.total_complexity as f64
+                    / dir.stats.total_functions as f64
+            } else {
+                0.0
+            };
+            println!(
+                "  Average complexity: {}",
+                Self::style(
+                    colors.number(),
+                    use_color,
+                    &format!("{:.1}", avg_complexity)
+                )
+            );
+
             if !dir.stats.languages.is_empty() {
                 println!();
-                println!("Languages:");
-                let mut lang_vec: Vec<_> = dir.stats.languages.iter().collect();
+                println!(
+                    "{}",
+                    Self::style(colors.heading(), use_color, "Languages:")
+                );
+                let mut lang_vec: Vec<_> =
+                    dir.stats.languages.iter().collect();
                 lang_vec.sort_by(|a, b| b.1.cmp(a.1)); // Sort by count descending
-                
+
                 for (language, count) in lang_vec {
-                    let percentage = (*count as f64 / dir.stats.total_files as f64) * 100.0;
-                    println!("  {}: {} files ({:.1}%)", language, count, percentage);
+                    let percentage =
+                        (*count as f64 / dir.stats.total_files as f64) * 100.0;
+                    println!(
+                        "  {}: {} files ({:.1}%)",
+                        language,
+                        Self::style(
+                            colors.number(),
+                            use_color,
+                            &count.to_string()
+                        ),
+                        percentage
+                    );
                 }
             }
         }
     }
+
+    /// Wrap `text` in the ANSI escapes for `spec`, or return it unmodified
+    /// when `use_color` is false (matching `--color=never` or non-tty
+    /// output).
+    fn style(
+        spec: &termcolor::ColorSpec,
+        use_color: bool,
+        text: &str,
+    ) -> String {
+        if !use_color {
+            return text.to_string();
+        }
+        let mut buf = termcolor::Ansi::new(Vec::new());
+        if buf.set_color(spec).is_err() {
+            return text.to_string();
+        }
+        if std::io::Write::write_all(&mut buf, text.as_bytes()).is_err() {
+            return text.to_string();
+        }
+        if buf.reset().is_err() {
+            return text.to_string();
+        }
+        String::from_utf8(buf.into_inner())
+            .unwrap_or_else(|_| text.to_string())
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::time::SystemTime;
 use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 /// AST-related types for syntax tree structure
 
@@ -40,6 +40,105 @@ pub struct AstStructure {
     pub syntax_tokens: Vec<SyntaxHighlightToken>,
     /// Symbol summary - quick access to important symbols
     pub symbols: AstSymbolSummary,
+    /// Embedded-language regions found inside this file (e.g. `<script>`
+    /// bodies in HTML) and the symbols extracted from each using its own
+    /// grammar; see `crate::diagnostics::injections`.
+    pub injections: Vec<LanguageInjection>,
+}
+
+/// One embedded-language region found inside a host file, with the
+/// symbols extracted from it using its own language's grammar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageInjection {
+    /// The embedded language, e.g. `"JavaScript"` for a `<script>` body.
+    pub language: String,
+    /// Byte range of the embedded region within the host file.
+    pub range: std::ops::Range<usize>,
+    /// Symbols extracted from the embedded region.
+    pub symbols: AstSymbolSummary,
+}
+
+/// A count of AST nodes, broken down by node type.
+///
+/// This is what `--ast-summary` substitutes for the full `root_nodes` tree
+/// when a caller wants to know the shape of a file (how many nodes of
+/// each kind, how many nodes overall) without paying for the entire
+/// node-by-node payload. It carries no nesting-depth information.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AstNodeCounts {
+    /// Total number of nodes in the tree
+    pub total: usize,
+    /// Number of nodes of each node type, e.g. "function_declaration" => 4
+    pub by_kind: BTreeMap<String, usize>,
+}
+
+impl AstStructure {
+    /// Return a copy of this structure with `root_nodes` limited to at most
+    /// `max_depth` levels and `max_nodes` total nodes. `None` in either
+    /// leaves that dimension unlimited. Returns the limited structure along
+    /// with whether anything was actually cut.
+    ///
+    /// This exists because `root_nodes` can be enormous for large files;
+    /// `--ast-depth` and `--ast-max-nodes` let callers cap the payload
+    /// instead of receiving (and having to transmit or store) the whole
+    /// tree.
+    pub fn limited(
+        &self,
+        max_depth: Option<usize>,
+        max_nodes: Option<usize>,
+    ) -> (AstStructure, bool) {
+        if max_depth.is_none() && max_nodes.is_none() {
+            return (self.clone(), false);
+        }
+        let mut budget = max_nodes.unwrap_or(usize::MAX);
+        let mut truncated = false;
+        let root_nodes =
+            truncate_nodes(&self.root_nodes, max_depth, &mut budget, &mut truncated);
+        (AstStructure { root_nodes, ..self.clone() }, truncated)
+    }
+
+    /// Summarize this structure's `root_nodes` as per-kind node counts,
+    /// without materializing the full tree.
+    pub fn node_counts(&self) -> AstNodeCounts {
+        let mut by_kind = BTreeMap::new();
+        count_nodes(&self.root_nodes, &mut by_kind);
+        let total = by_kind.values().sum();
+        AstNodeCounts { total, by_kind }
+    }
+}
+
+fn truncate_nodes(
+    nodes: &[AstNodeInfo],
+    depth_remaining: Option<usize>,
+    budget: &mut usize,
+    truncated: &mut bool,
+) -> Vec<AstNodeInfo> {
+    let mut out = Vec::new();
+    for node in nodes {
+        if *budget == 0 {
+            *truncated = true;
+            break;
+        }
+        *budget -= 1;
+        let children = if depth_remaining == Some(0) {
+            if !node.children.is_empty() {
+                *truncated = true;
+            }
+            Vec::new()
+        } else {
+            let next_depth = depth_remaining.map(|d| d - 1);
+            truncate_nodes(&node.children, next_depth, budget, truncated)
+        };
+        out.push(AstNodeInfo { children, ..node.clone() });
+    }
+    out
+}
+
+fn count_nodes(nodes: &[AstNodeInfo], counts: &mut BTreeMap<String, usize>) {
+    for node in nodes {
+        *counts.entry(node.node_type.clone()).or_insert(0) += 1;
+        count_nodes(&node.children, counts);
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -50,10 +149,95 @@ pub struct AstSymbolSummary {
     pub classes: Vec<SymbolInfo>,
     /// Type definitions
     pub types: Vec<SymbolInfo>,
-    /// Module/namespace definitions  
+    /// Module/namespace definitions
     pub modules: Vec<SymbolInfo>,
 }
 
+impl AstSymbolSummary {
+    /// Return a copy of this summary with every kind removed except those
+    /// named in `kinds` (matched against "functions", "classes", "types",
+    /// "modules"). An empty `kinds` list means "no filter" and the summary
+    /// is returned unchanged.
+    ///
+    /// This is how `--symbol-kinds` scopes the outline/tree syntax view:
+    /// each language populates the same four buckets, so filtering here
+    /// applies uniformly regardless of which language a file is written in.
+    pub fn filtered(&self, kinds: &[String]) -> AstSymbolSummary {
+        if kinds.is_empty() {
+            return self.clone();
+        }
+        let wants = |kind: &str| kinds.iter().any(|k| k.eq_ignore_ascii_case(kind));
+        AstSymbolSummary {
+            functions: if wants("functions") { self.functions.clone() } else { Vec::new() },
+            classes: if wants("classes") { self.classes.clone() } else { Vec::new() },
+            types: if wants("types") { self.types.clone() } else { Vec::new() },
+            modules: if wants("modules") { self.modules.clone() } else { Vec::new() },
+        }
+    }
+
+    /// Return a copy of this summary with every symbol's `doc_comment`
+    /// cleared. Used to honor `--with-docs` being absent: doc comments are
+    /// always extracted, but only surfaced in output when asked for.
+    pub fn without_docs(&self) -> AstSymbolSummary {
+        let clear = |symbols: &[SymbolInfo]| {
+            symbols
+                .iter()
+                .cloned()
+                .map(|mut s| {
+                    s.doc_comment = None;
+                    s
+                })
+                .collect()
+        };
+        AstSymbolSummary {
+            functions: clear(&self.functions),
+            classes: clear(&self.classes),
+            types: clear(&self.types),
+            modules: clear(&self.modules),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol_with_doc(doc: &str) -> SymbolInfo {
+        SymbolInfo {
+            name: "documented".to_string(),
+            symbol_type: "function".to_string(),
+            range: 0..0,
+            line: 1,
+            column: 1,
+            doc_comment: Some(doc.to_string()),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn without_docs_clears_every_bucket() {
+        let summary = AstSymbolSummary {
+            functions: vec![symbol_with_doc("a function")],
+            classes: vec![symbol_with_doc("a class")],
+            types: vec![symbol_with_doc("a type")],
+            modules: vec![symbol_with_doc("a module")],
+        };
+
+        let stripped = summary.without_docs();
+        assert!(stripped.functions[0].doc_comment.is_none());
+        assert!(stripped.classes[0].doc_comment.is_none());
+        assert!(stripped.types[0].doc_comment.is_none());
+        assert!(stripped.modules[0].doc_comment.is_none());
+    }
+
+    #[test]
+    fn doc_comment_round_trips_through_json() {
+        let symbol = symbol_with_doc("computes the answer");
+        let value = serde_json::to_value(&symbol).expect("should serialize");
+        assert_eq!(value["doc_comment"], "computes the answer");
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolInfo {
     /// Name of the symbol
@@ -64,8 +248,64 @@ pub struct SymbolInfo {
     pub range: std::ops::Range<usize>,
     /// Line number (1-based)
     pub line: u32,
-    /// Column number (1-based) 
+    /// Column number (1-based)
+    pub column: u32,
+    /// Leading doc comment block immediately preceding this symbol, if any
+    /// (e.g. a Rust `///` block or a JSDoc `/** */` comment). Populated
+    /// during extraction regardless of `--with-docs`; that flag only
+    /// controls whether it's surfaced in output.
+    pub doc_comment: Option<String>,
+    /// Parameter and return types, for symbols that are function-like
+    /// definitions in a language `diagnostics::signature` has rules for.
+    /// `None` for non-function symbols and for languages without signature
+    /// extraction support yet. See [`crate::diagnostics::signature`].
+    pub signature: Option<FunctionSignature>,
+}
+
+/// A function or method's parameter and return types, as written in the
+/// source -- not resolved or normalized beyond trimming whitespace.
+///
+/// This is what `--signature` matches against: it lets a query like
+/// `(Path, &str) -> Result` find every function with a particular shape
+/// without caring about parameter names or exact generic arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionSignature {
+    /// Each parameter's type, in declaration order. For a method's
+    /// receiver (e.g. Rust's `&self`), this is the receiver's own text
+    /// (`"&self"`, `"&mut self"`, `"self"`) rather than a type.
+    pub params: Vec<String>,
+    /// The return type, or `None` if the function has no explicit return
+    /// type (e.g. Rust's implicit `()`).
+    pub return_type: Option<String>,
+}
+
+/// Which kind of AST construct a [`ReferenceOccurrence`] was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// The identifier is the callee of a call expression, e.g. `ident()` or
+    /// `receiver.ident()`.
+    Call,
+    /// The identifier is used as a type, e.g. a variable annotation, return
+    /// type, or generic argument.
+    TypeReference,
+}
+
+/// A single usage of an identifier found by `--references`, scoped to call
+/// expressions and type references so that unrelated identifiers sharing the
+/// same spelling (or occurrences inside strings and comments, which never
+/// parse as identifier nodes) are excluded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceOccurrence {
+    /// How this occurrence uses the identifier.
+    pub kind: ReferenceKind,
+    /// Line number (1-based).
+    pub line: u32,
+    /// Column number (1-based).
     pub column: u32,
+    /// Name of the function/method/type enclosing this occurrence, if any.
+    /// `None` when the reference sits at module/file scope.
+    pub enclosing_symbol: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,6 +316,19 @@ pub struct CodeMetrics {
     pub cyclomatic_complexity: u32,
     pub cognitive_complexity: u32,
     pub function_count: u32,
+    /// Deepest nesting of decision/branch constructs anywhere in the file.
+    /// 0 for languages without AST-based complexity rules (see
+    /// `complexity::ComplexityRules`).
+    pub max_nesting_depth: u32,
+    /// Line count of the file's longest function/method body, or 0 if it has
+    /// none or its language lacks AST-based complexity rules.
+    pub max_function_length: u32,
+    /// Mean line count across the file's function/method bodies, or 0.0 if
+    /// it has none or its language lacks AST-based complexity rules.
+    pub avg_function_length: f64,
+    /// Whether this file was detected as a test file rather than production
+    /// code. See `TestDetector` for the detection heuristics.
+    pub is_test: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -96,7 +349,7 @@ pub struct GitDiagnostics {
     pub file_stats: DiffStats,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GitFileStatus {
     Modified,
     Staged,
@@ -120,6 +373,22 @@ pub enum FileChangeEvent {
     Renamed { from: PathBuf, to: PathBuf },
 }
 
+impl FileChangeEvent {
+    /// The path this event is primarily about.
+    ///
+    /// For `Renamed`, this is the destination path: that's the path that
+    /// still exists on disk afterward, and so the one a consumer tracking
+    /// current file state (e.g. an index) needs to key on.
+    pub fn path(&self) -> &Path {
+        match self {
+            FileChangeEvent::Created(path) => path,
+            FileChangeEvent::Modified(path) => path,
+            FileChangeEvent::Deleted(path) => path,
+            FileChangeEvent::Renamed { to, .. } => to,
+        }
+    }
+}
+
 /// Tree structure types for directory representation
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -223,16 +492,51 @@ impl DirectoryNode {
     }
 }
 
+/// What kind of filesystem entry a [`FileNode`] represents.
+///
+/// Tree mode classifies entries with `symlink_metadata` (which does not
+/// follow symlinks) so that symlinks and Unix special files are reported
+/// as themselves instead of being silently treated as regular files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FileKind {
+    /// A regular file, safe to read for metrics, diagnostics, and AST
+    /// extraction.
+    Regular,
+    /// A symlink, along with its target if it could be read. The target
+    /// is not followed for content analysis.
+    Symlink { target: Option<PathBuf> },
+    /// A Unix domain socket.
+    Socket,
+    /// A named pipe (FIFO).
+    Fifo,
+    /// A Unix character device, e.g. `/dev/null`.
+    CharDevice,
+    /// A Unix block device.
+    BlockDevice,
+}
+
+impl Default for FileKind {
+    fn default() -> Self {
+        FileKind::Regular
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileNode {
     pub name: String,
     pub path: PathBuf,
+    pub kind: FileKind,
     pub language: Option<String>,
     pub git_status: Option<GitFileStatus>,
     pub metrics: Option<CodeMetrics>,
     pub last_modified: Option<SystemTime>,
     pub diagnostics: Option<FileDiagnostics>,
     pub ast_structure: Option<AstStructure>,
+    /// The text encoding `metrics` was computed from, detected from the
+    /// file's byte-order mark. `None` for files metrics weren't computed
+    /// for, since only [`crate::diagnostics::tree::TreeBuilder`]'s analysis
+    /// path reads and tags file content.
+    pub encoding: Option<crate::diagnostics::encoding::TextEncoding>,
 }
 
 impl FileNode {
@@ -241,12 +545,14 @@ impl FileNode {
         Self {
             name,
             path,
+            kind: FileKind::Regular,
             language: None,
             git_status: None,
             metrics: None,
             last_modified: None,
             diagnostics: None,
             ast_structure: None,
+            encoding: None,
         }
     }
 }
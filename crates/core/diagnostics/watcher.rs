@@ -1,9 +1,28 @@
 use crate::diagnostics::types::FileChangeEvent;
-use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Result, Watcher};
-use std::path::Path;
-use std::time::Duration;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{
+    Event, EventKind, RecommendedWatcher, RecursiveMode, Result, Watcher,
+};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// How long a lone rename half is held while waiting for its other half to
+/// arrive, before it's given up on and reported as a plain delete instead.
+///
+/// This is needed on backends that don't correlate rename halves themselves:
+/// a move across the boundary of the watched tree, or a rename observed
+/// without cookie support, never gets a matching half at all.
+const RENAME_CORRELATION_WINDOW: Duration = Duration::from_millis(500);
+
+/// One half of a rename seen without enough information yet to know whether
+/// it will turn out to be a real rename or a plain create/delete.
+struct PendingRename {
+    path: PathBuf,
+    seen_at: Instant,
+}
+
 pub struct FileWatcher {
     _watcher: RecommendedWatcher,
     receiver: mpsc::Receiver<FileChangeEvent>,
@@ -12,13 +31,23 @@ pub struct FileWatcher {
 impl FileWatcher {
     pub fn new() -> Result<Self> {
         let (tx, rx) = mpsc::channel(1000);
-        
+
         let watcher = notify::recommended_watcher({
             let tx = tx.clone();
+            // Rename halves seen on platforms/backends that don't correlate
+            // them for us: inotify's `MOVED_FROM`/`MOVED_TO` pair via a
+            // cookie (`pending_by_tracker`), while backends without cookie
+            // support fall back to matching arrival order (`pending_fifo`).
+            let mut pending_by_tracker: HashMap<usize, PendingRename> =
+                HashMap::new();
+            let mut pending_fifo: VecDeque<PendingRename> = VecDeque::new();
             move |res: notify::Result<Event>| {
                 if let Ok(event) = res {
-                    let change_event = Self::convert_event(event);
-                    if let Some(change) = change_event {
+                    for change in Self::convert_event(
+                        event,
+                        &mut pending_by_tracker,
+                        &mut pending_fifo,
+                    ) {
                         // Use blocking send since we're in a sync callback
                         if let Err(_) = tx.blocking_send(change) {
                             eprintln!("Failed to send file change event");
@@ -27,86 +56,181 @@ impl FileWatcher {
                 }
             }
         })?;
-        
-        Ok(Self {
-            _watcher: watcher,
-            receiver: rx,
-        })
+
+        Ok(Self { _watcher: watcher, receiver: rx })
     }
-    
+
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self._watcher.watch(path.as_ref(), RecursiveMode::Recursive)
     }
-    
+
     pub async fn next_event(&mut self) -> Option<FileChangeEvent> {
         self.receiver.recv().await
     }
-    
-    pub async fn next_event_timeout(&mut self, timeout: Duration) -> Option<FileChangeEvent> {
-        tokio::time::timeout(timeout, self.receiver.recv()).await.ok().flatten()
+
+    pub async fn next_event_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Option<FileChangeEvent> {
+        tokio::time::timeout(timeout, self.receiver.recv())
+            .await
+            .ok()
+            .flatten()
     }
-    
-    fn convert_event(event: Event) -> Option<FileChangeEvent> {
+
+    /// Convert a raw `notify` event into zero or more `FileChangeEvent`s,
+    /// synthesizing `Renamed { from, to }` out of whatever rename
+    /// information the platform's backend actually provides.
+    ///
+    /// `notify`'s inotify backend already pairs a `MOVED_FROM`/`MOVED_TO`
+    /// with a matching cookie into a single `RenameMode::Both` event, which
+    /// is handled directly below. Other backends (FSEvents, Windows,
+    /// kqueue) report the two halves as separate events, sometimes with a
+    /// tracker to correlate them and sometimes without one at all, so those
+    /// halves are held in `pending_by_tracker`/`pending_fifo` until either a
+    /// matching half arrives or `RENAME_CORRELATION_WINDOW` elapses.
+    fn convert_event(
+        event: Event,
+        pending_by_tracker: &mut HashMap<usize, PendingRename>,
+        pending_fifo: &mut VecDeque<PendingRename>,
+    ) -> Vec<FileChangeEvent> {
+        let mut out = Vec::new();
+        Self::expire_pending_renames(
+            pending_by_tracker,
+            pending_fifo,
+            &mut out,
+        );
+
         match event.kind {
             EventKind::Create(_) => {
                 if let Some(path) = event.paths.first() {
-                    Some(FileChangeEvent::Created(path.clone()))
-                } else {
-                    None
+                    out.push(FileChangeEvent::Created(path.clone()));
+                }
+            }
+            EventKind::Modify(ModifyKind::Name(rename_mode)) => {
+                match rename_mode {
+                    RenameMode::Both => {
+                        if let [from, to] = event.paths.as_slice() {
+                            out.push(FileChangeEvent::Renamed {
+                                from: from.clone(),
+                                to: to.clone(),
+                            });
+                        }
+                    }
+                    RenameMode::From => {
+                        if let Some(path) = event.paths.first() {
+                            let pending = PendingRename {
+                                path: path.clone(),
+                                seen_at: Instant::now(),
+                            };
+                            match event.attrs.tracker() {
+                                Some(tracker) => {
+                                    pending_by_tracker
+                                        .insert(tracker, pending);
+                                }
+                                None => pending_fifo.push_back(pending),
+                            }
+                        }
+                    }
+                    RenameMode::To => {
+                        if let Some(path) = event.paths.first() {
+                            let from = event
+                                .attrs
+                                .tracker()
+                                .and_then(|t| pending_by_tracker.remove(&t))
+                                .or_else(|| pending_fifo.pop_front());
+                            out.push(match from {
+                                Some(pending) => FileChangeEvent::Renamed {
+                                    from: pending.path,
+                                    to: path.clone(),
+                                },
+                                // No half to pair with, e.g. a file moved in
+                                // from outside the watched tree.
+                                None => FileChangeEvent::Created(path.clone()),
+                            });
+                        }
+                    }
+                    // The backend (e.g. kqueue) knows only that a rename
+                    // happened, not what either path was, so there's
+                    // nothing more specific to synthesize than a plain
+                    // modification.
+                    RenameMode::Any | RenameMode::Other => {
+                        if let Some(path) = event.paths.first() {
+                            out.push(FileChangeEvent::Modified(path.clone()));
+                        }
+                    }
                 }
             }
             EventKind::Modify(_) => {
                 if let Some(path) = event.paths.first() {
-                    Some(FileChangeEvent::Modified(path.clone()))
-                } else {
-                    None
+                    out.push(FileChangeEvent::Modified(path.clone()));
                 }
             }
             EventKind::Remove(_) => {
                 if let Some(path) = event.paths.first() {
-                    Some(FileChangeEvent::Deleted(path.clone()))
-                } else {
-                    None
+                    out.push(FileChangeEvent::Deleted(path.clone()));
                 }
             }
-            EventKind::Other => {
-                // Handle rename events
-                if event.paths.len() == 2 {
-                    Some(FileChangeEvent::Renamed {
-                        from: event.paths[0].clone(),
-                        to: event.paths[1].clone(),
-                    })
-                } else {
-                    None
-                }
+            _ => {}
+        }
+
+        out
+    }
+
+    /// Flush rename halves that have waited longer than
+    /// `RENAME_CORRELATION_WINDOW` for their other half, reporting each as a
+    /// plain delete since no matching half ever arrived to pair it with.
+    fn expire_pending_renames(
+        pending_by_tracker: &mut HashMap<usize, PendingRename>,
+        pending_fifo: &mut VecDeque<PendingRename>,
+        out: &mut Vec<FileChangeEvent>,
+    ) {
+        let now = Instant::now();
+        pending_by_tracker.retain(|_, pending| {
+            let expired = now.duration_since(pending.seen_at)
+                > RENAME_CORRELATION_WINDOW;
+            if expired {
+                out.push(FileChangeEvent::Deleted(pending.path.clone()));
             }
-            _ => None,
+            !expired
+        });
+        while let Some(pending) = pending_fifo.front() {
+            if now.duration_since(pending.seen_at) <= RENAME_CORRELATION_WINDOW
+            {
+                break;
+            }
+            let pending =
+                pending_fifo.pop_front().expect("front just checked");
+            out.push(FileChangeEvent::Deleted(pending.path));
         }
     }
-    
+
     pub fn should_ignore_file(path: &Path) -> bool {
         if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
             // Ignore common temporary and build files
-            if name.starts_with('.') 
-                || name.ends_with('~') 
-                || name.ends_with(".tmp") 
-                || name.ends_with(".swp") {
+            if name.starts_with('.')
+                || name.ends_with('~')
+                || name.ends_with(".tmp")
+                || name.ends_with(".swp")
+            {
                 return true;
             }
         }
-        
+
         // Ignore common build directories
         if let Some(parent) = path.parent() {
-            if let Some(dir_name) = parent.file_name().and_then(|n| n.to_str()) {
-                if dir_name == "target" 
-                    || dir_name == "node_modules" 
-                    || dir_name == ".git" 
-                    || dir_name == "build" {
+            if let Some(dir_name) = parent.file_name().and_then(|n| n.to_str())
+            {
+                if dir_name == "target"
+                    || dir_name == "node_modules"
+                    || dir_name == ".git"
+                    || dir_name == "build"
+                {
                     return true;
                 }
             }
         }
-        
+
         false
     }
 }
@@ -115,4 +239,4 @@ impl Default for FileWatcher {
     fn default() -> Self {
         Self::new().expect("Failed to create file watcher")
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,319 @@
+/*!
+Implements `og --doctor`, a first-run diagnostics report.
+
+Outgrep touches a lot of external state: PATH tools it shells out to
+(`git` for `--remote` and the git-aware diagnostics, `cargo`/node-based
+linters for the compiler-diagnostics integration), the terminal it prints
+color and hyperlinks to, the hierarchical config files it merges on every
+invocation, and the on-disk cache of downloaded embedding models. When one
+of those is missing or misconfigured, the failure usually shows up as a
+confusing error several layers away from the actual cause. `--doctor`
+checks all of it up front and prints one line per check with a concrete
+fix, so new users (and new machines) don't have to reverse-engineer which
+piece is missing.
+*/
+
+use std::io::IsTerminal;
+
+use crate::catalog::Message;
+use crate::flags::hierarchy::ConfigHierarchy;
+
+/// The outcome of a single diagnostic check.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// One row of the doctor report: a name, its status, a short detail line,
+/// and (for anything that isn't `Ok`) a suggested fix.
+#[derive(Debug, Clone)]
+pub(crate) struct DoctorCheck {
+    pub(crate) name: String,
+    pub(crate) status: CheckStatus,
+    pub(crate) detail: String,
+    pub(crate) fix: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> DoctorCheck {
+        DoctorCheck {
+            name: name.into(),
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> DoctorCheck {
+        DoctorCheck {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        fix: impl Into<String>,
+    ) -> DoctorCheck {
+        DoctorCheck {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every diagnostic check and return the full report, in the order it
+/// should be printed.
+pub(crate) fn run_checks() -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+    checks.extend(check_path_tools());
+    checks.push(check_terminal());
+    checks.push(check_config());
+    checks.push(check_model_cache());
+    checks.push(check_model_availability());
+    checks
+}
+
+/// Checks for external tools that outgrep shells out to: `git` (used by
+/// `--remote` and the git-aware diagnostics), `cargo` (used by the Rust
+/// compiler-diagnostics integration), and a handful of common Node-based
+/// linters (used by the JS/TS compiler-diagnostics integration).
+///
+/// TODO: the linter shortlist below is hardcoded; once `.outgrep/config`
+/// grows a way to declare per-project tool requirements, this should read
+/// from that instead of guessing at `eslint`.
+fn check_path_tools() -> Vec<DoctorCheck> {
+    let tools: &[(&str, &str)] = &[
+        ("git", "required for --remote and git-aware diagnostics"),
+        ("cargo", "required for Rust compiler diagnostics"),
+        ("node", "required for JavaScript/TypeScript linter diagnostics"),
+        ("eslint", "used for JavaScript/TypeScript linter diagnostics"),
+    ];
+    tools
+        .iter()
+        .map(|&(tool, purpose)| match which::which(tool) {
+            Ok(path) => DoctorCheck::ok(
+                format!("PATH: {tool}"),
+                format!("found at {}", path.display()),
+            ),
+            Err(_) => DoctorCheck::warn(
+                format!("PATH: {tool}"),
+                format!("not found on PATH ({purpose})"),
+                format!("install {tool} and ensure it is on PATH"),
+            ),
+        })
+        .collect()
+}
+
+/// Checks whether stdout is a terminal that supports color, since outgrep
+/// silently downgrades to plain output otherwise and users sometimes
+/// mistake that for a bug.
+fn check_terminal() -> DoctorCheck {
+    let is_tty = std::io::stdout().is_terminal();
+    if !is_tty {
+        return DoctorCheck::ok(
+            "Terminal",
+            "stdout is not a TTY (output is being piped or redirected)",
+        );
+    }
+    if std::env::var_os("NO_COLOR").is_some() {
+        return DoctorCheck::warn(
+            "Terminal",
+            "NO_COLOR is set, so color output is disabled",
+            "unset NO_COLOR to re-enable colored output",
+        );
+    }
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" {
+        return DoctorCheck::warn(
+            "Terminal",
+            "TERM is unset or \"dumb\"",
+            "set TERM to a color-capable value (e.g. xterm-256color)",
+        );
+    }
+    DoctorCheck::ok("Terminal", format!("TERM={term}, color output available"))
+}
+
+/// Checks that the hierarchical config files outgrep would load (global and
+/// local) exist and parse without error.
+fn check_config() -> DoctorCheck {
+    let hierarchy = match ConfigHierarchy::load() {
+        Ok(hierarchy) => hierarchy,
+        Err(e) => {
+            return DoctorCheck::fail(
+                "Config",
+                format!("failed to load configuration: {e}"),
+                "run `og --config-dump` for a per-file breakdown",
+            )
+        }
+    };
+    let mut parts = Vec::new();
+    match &hierarchy.global_config {
+        Some(c) => {
+            parts.push(format!("global: {} (loaded)", c.path.display()))
+        }
+        None => parts.push("global: not found".to_string()),
+    }
+    match &hierarchy.local_config {
+        Some(c) => parts.push(format!("local: {} (loaded)", c.path.display())),
+        None => parts.push("local: not found".to_string()),
+    }
+    DoctorCheck::ok("Config", parts.join(", "))
+}
+
+/// Checks that the semantic model cache directory exists and is writable,
+/// since `--semantic` silently fails to download models without it.
+fn check_model_cache() -> DoctorCheck {
+    let cache_dir =
+        match grep::searcher::semantic::ModelManager::default_storage_path() {
+            Ok(path) => path,
+            Err(e) => return DoctorCheck::fail(
+                "Model cache",
+                format!("could not determine cache directory: {e}"),
+                "set HOME (or --semantic-model-path) to a writable directory",
+            ),
+        };
+    if !cache_dir.exists() {
+        return DoctorCheck::warn(
+            "Model cache",
+            format!("{} does not exist yet", cache_dir.display()),
+            "run `og --semantic-download-model <name>` to create it",
+        );
+    }
+    match std::fs::metadata(&cache_dir) {
+        Ok(meta) if meta.permissions().readonly() => DoctorCheck::fail(
+            "Model cache",
+            format!("{} is read-only", cache_dir.display()),
+            "make the cache directory writable or set --semantic-model-path",
+        ),
+        Ok(_) => DoctorCheck::ok(
+            "Model cache",
+            format!("{} exists and is writable", cache_dir.display()),
+        ),
+        Err(e) => DoctorCheck::fail(
+            "Model cache",
+            format!("could not stat {}: {e}", cache_dir.display()),
+            "check permissions on the cache directory",
+        ),
+    }
+}
+
+/// Checks whether at least one embedding model has already been downloaded,
+/// since `--semantic` on a fresh machine otherwise fails on first use with
+/// no prior warning.
+fn check_model_availability() -> DoctorCheck {
+    let downloader =
+        match grep::searcher::semantic::ModelManager::create_downloader(None) {
+            Ok(downloader) => downloader,
+            Err(e) => return DoctorCheck::fail(
+                "Model availability",
+                format!("could not initialize model registry: {e}"),
+                "check network access and `--semantic-model-path` permissions",
+            ),
+        };
+    match downloader.list_downloaded_models() {
+        Ok(models) if models.is_empty() => DoctorCheck::warn(
+            "Model availability",
+            "no embedding models downloaded yet",
+            "run `og --semantic-download-model <name>` before using --semantic",
+        ),
+        Ok(models) => DoctorCheck::ok(
+            "Model availability",
+            format!("{} model(s) downloaded: {}", models.len(), models.join(", ")),
+        ),
+        Err(e) => DoctorCheck::fail(
+            "Model availability",
+            format!("failed to inspect downloaded models: {e}"),
+            "check permissions on the model cache directory",
+        ),
+    }
+}
+
+/// Prints the doctor report in the console and returns whether every check
+/// passed (used to decide the process exit code).
+pub(crate) fn print_report(checks: &[DoctorCheck]) -> bool {
+    println!("{}", Message::DoctorReportTitle.text());
+    println!("==============");
+    let mut all_ok = true;
+    for check in checks {
+        if check.status != CheckStatus::Ok {
+            all_ok = false;
+        }
+        println!(
+            "[{}] {}: {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+        if let Some(fix) = &check.fix {
+            println!("       fix: {fix}");
+        }
+    }
+    println!();
+    if all_ok {
+        println!("{}", Message::DoctorAllOk.text());
+    } else {
+        println!("{}", Message::DoctorNeedsAttention.text());
+    }
+    all_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ok_check_has_no_fix() {
+        let check = DoctorCheck::ok("Example", "detail");
+        assert_eq!(check.status, CheckStatus::Ok);
+        assert!(check.fix.is_none());
+    }
+
+    #[test]
+    fn warn_and_fail_checks_carry_a_fix() {
+        let warn = DoctorCheck::warn("Example", "detail", "do this");
+        assert_eq!(warn.status, CheckStatus::Warn);
+        assert_eq!(warn.fix.as_deref(), Some("do this"));
+
+        let fail = DoctorCheck::fail("Example", "detail", "do that");
+        assert_eq!(fail.status, CheckStatus::Fail);
+        assert_eq!(fail.fix.as_deref(), Some("do that"));
+    }
+
+    #[test]
+    fn print_report_reports_failure_when_any_check_is_not_ok() {
+        let checks = vec![
+            DoctorCheck::ok("A", "fine"),
+            DoctorCheck::warn("B", "meh", "fix it"),
+        ];
+        assert!(!print_report(&checks));
+    }
+
+    #[test]
+    fn print_report_reports_success_when_all_checks_pass() {
+        let checks =
+            vec![DoctorCheck::ok("A", "fine"), DoctorCheck::ok("B", "fine")];
+        assert!(print_report(&checks));
+    }
+}
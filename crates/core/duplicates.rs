@@ -0,0 +1,244 @@
+/*!
+Implements `og --find-duplicates`, near-duplicate symbol detection.
+
+Every function/class/module-level symbol under the search paths is chunked
+out with the same AST-based splitting `--semantic` uses by default (one
+chunk per symbol), embedded with the same embedding infrastructure, and
+grouped into clusters whenever two symbols' embeddings meet a similarity
+threshold. This surfaces copy-pasted or re-implemented logic that a purely
+textual search would miss, since it compares meaning rather than exact
+text.
+
+TODO: clustering is a single connected-components pass over every pairwise
+similarity (see `cluster`), which is O(n^2) in the number of symbols found.
+Fine for a single crate; a workspace with tens of thousands of functions
+would benefit from bucketing by an ANN index (the same `SemanticIndex` used
+by `--semantic`) before falling back to exact pairwise comparison.
+*/
+
+use std::path::{Path, PathBuf};
+
+use grep::searcher::semantic::{
+    chunk_content, cosine_similarity, generate_embeddings_parallel,
+};
+use grep::searcher::{
+    create_ast_calculator_for_file, default_context_types, ChunkingStrategy,
+    Embedding, SemanticConfig,
+};
+
+use crate::flags::HiArgs;
+use crate::search::byte_to_line;
+
+/// A single embedded symbol found under the search paths.
+#[derive(Debug, Clone)]
+struct Symbol {
+    path: PathBuf,
+    line_start: usize,
+    line_end: usize,
+    /// The symbol's first line, used as a human-readable label in the
+    /// report since chunks aren't tagged with a real symbol name.
+    signature: String,
+}
+
+/// Extract one `Symbol` (and its full text, to be embedded separately) per
+/// AST symbol found in `content`.
+///
+/// Returns an empty vector, rather than an error, for files the AST
+/// calculator can't parse (e.g. unsupported languages) -- `--find-duplicates`
+/// is a best-effort sweep over everything it can understand, not a hard
+/// requirement that every file parse.
+fn extract_symbols(path: &Path, content: &str) -> Vec<(Symbol, String)> {
+    let ast_calculator = match create_ast_calculator_for_file(
+        path,
+        content,
+        Some(default_context_types()),
+    ) {
+        Ok(calculator) => calculator,
+        Err(_) => return Vec::new(),
+    };
+    let chunks = chunk_content(
+        content,
+        Some(&ast_calculator),
+        ChunkingStrategy::Symbol,
+        0,
+        0,
+    );
+    chunks
+        .into_iter()
+        .filter(|chunk| !chunk.content.trim().is_empty())
+        .map(|chunk| {
+            let symbol = Symbol {
+                path: path.to_path_buf(),
+                line_start: byte_to_line(content, chunk.range.start),
+                line_end: byte_to_line(
+                    content,
+                    chunk.range.end.saturating_sub(1).max(chunk.range.start),
+                ),
+                signature: chunk
+                    .content
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+            };
+            (symbol, chunk.content)
+        })
+        .collect()
+}
+
+/// Group symbols into clusters using union-find: any two symbols whose
+/// embeddings meet `threshold` end up in the same cluster, even if they were
+/// only compared transitively through a third symbol.
+///
+/// Returns each cluster as a list of indices into `embeddings`, in no
+/// particular order; the caller decides how to sort clusters and their
+/// members for display. Clusters of size one (a symbol with no near
+/// duplicate) are omitted.
+fn cluster(embeddings: &[Embedding], threshold: f32) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..embeddings.len()).collect();
+
+    fn find(parent: &mut [usize], x: usize) -> usize {
+        if parent[x] != x {
+            parent[x] = find(parent, parent[x]);
+        }
+        parent[x]
+    }
+
+    for i in 0..embeddings.len() {
+        for j in (i + 1)..embeddings.len() {
+            if cosine_similarity(&embeddings[i], &embeddings[j]) >= threshold {
+                let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                if ri != rj {
+                    parent[ri] = rj;
+                }
+            }
+        }
+    }
+
+    let mut groups: std::collections::HashMap<usize, Vec<usize>> =
+        std::collections::HashMap::new();
+    for i in 0..embeddings.len() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+    groups.into_values().filter(|group| group.len() > 1).collect()
+}
+
+/// The top-level entry point for `--find-duplicates`.
+///
+/// Walks the search paths on a single thread (near-duplicate detection
+/// needs every symbol gathered before it can cluster anything, so there's
+/// no per-file result to stream), embeds every symbol found, and prints
+/// clusters largest first.
+pub(crate) fn run(args: &HiArgs) -> anyhow::Result<bool> {
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut symbols = Vec::new();
+    let mut snippets = Vec::new();
+    for haystack in haystacks {
+        let path = haystack.path();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            // Binary or unreadable files are silently skipped, same as
+            // `--semantic`'s per-file support check.
+            Err(_) => continue,
+        };
+        for (symbol, snippet) in extract_symbols(path, &content) {
+            symbols.push(symbol);
+            snippets.push(snippet);
+        }
+    }
+
+    if symbols.is_empty() {
+        println!("No symbols found under the search paths.");
+        return Ok(false);
+    }
+
+    let config = SemanticConfig::default();
+    let embeddings = generate_embeddings_parallel(&snippets, &config);
+    let mut clusters = cluster(&embeddings, args.find_duplicates_threshold());
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()));
+
+    if clusters.is_empty() {
+        println!(
+            "No near-duplicate symbols found (threshold: {:.2}).",
+            args.find_duplicates_threshold()
+        );
+        return Ok(false);
+    }
+
+    println!(
+        "Found {} cluster(s) of near-duplicate symbols (threshold: {:.2}):",
+        clusters.len(),
+        args.find_duplicates_threshold()
+    );
+    for (n, cluster) in clusters.iter().enumerate() {
+        println!();
+        println!("Cluster {} ({} symbols):", n + 1, cluster.len());
+        for &i in cluster {
+            let symbol = &symbols[i];
+            println!(
+                "  {}:{}-{}: {}",
+                symbol.path.display(),
+                symbol.line_start,
+                symbol.line_end,
+                symbol.signature
+            );
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn embedding(vector: Vec<f32>) -> Embedding {
+        let dimensions = vector.len();
+        Embedding { vector, dimensions }
+    }
+
+    #[test]
+    fn cluster_groups_similar_embeddings() {
+        let embeddings = vec![
+            embedding(vec![1.0, 0.0]),
+            embedding(vec![0.99, 0.01]),
+            embedding(vec![0.0, 1.0]),
+        ];
+        let clusters = cluster(&embeddings, 0.9);
+        assert_eq!(1, clusters.len());
+        let mut members = clusters[0].clone();
+        members.sort();
+        assert_eq!(vec![0, 1], members);
+    }
+
+    #[test]
+    fn cluster_omits_singletons() {
+        let embeddings = vec![
+            embedding(vec![1.0, 0.0]),
+            embedding(vec![0.0, 1.0]),
+            embedding(vec![0.0, -1.0]),
+        ];
+        let clusters = cluster(&embeddings, 0.99);
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn cluster_is_transitive_across_a_shared_neighbor() {
+        // b is similar to both a and c, but a and c alone would fall below
+        // threshold -- union-find should still merge all three.
+        let embeddings = vec![
+            embedding(vec![1.0, 0.2]),
+            embedding(vec![0.9, 0.4]),
+            embedding(vec![0.7, 0.9]),
+        ];
+        let clusters = cluster(&embeddings, 0.85);
+        assert_eq!(1, clusters.len());
+        assert_eq!(3, clusters[0].len());
+    }
+}
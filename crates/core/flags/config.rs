@@ -29,7 +29,11 @@ use crate::flags::hierarchy::ConfigHierarchy;
 ///
 /// The returned arguments should be merged with CLI arguments, with CLI taking
 /// the highest priority.
-pub fn args() -> Vec<OsString> {
+///
+/// `extra` is the list of `--config-extra` files given on the command line.
+/// They're loaded after the global and local config files, but still before
+/// CLI arguments, matching the priority documented on `--config-extra`.
+pub fn args(extra: &[PathBuf]) -> Vec<OsString> {
     let mut all_args = Vec::new();
 
     // Load hierarchical configuration (global + local)
@@ -60,6 +64,28 @@ pub fn args() -> Vec<OsString> {
         }
     }
 
+    // Add any --config-extra files next (higher priority than global/local,
+    // but still lower priority than CLI arguments).
+    for path in extra {
+        match ConfigHierarchy::parse_config_file_with_provenance(path) {
+            Ok(parsed) => {
+                log::debug!(
+                    "{}: arguments loaded from --config-extra: {:?}",
+                    path.display(),
+                    parsed
+                );
+                all_args.extend(parsed.into_iter().map(|(_, arg)| arg));
+            }
+            Err(err) => {
+                log::debug!(
+                    "failed to load --config-extra file {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+        }
+    }
+
     // For backward compatibility, also check RIPGREP_CONFIG_PATH
     // This has the lowest priority, so it goes first
     let legacy_args = load_legacy_config();
@@ -23,10 +23,10 @@ use {anyhow::Context as AnyhowContext, bstr::ByteVec};
 
 use crate::flags::{
     lowargs::{
-        BinaryMode, BoundaryMode, BufferMode, CaseMode, ColorChoice,
-        ContextMode, EncodingMode, EngineChoice, GenerateMode, LoggingMode,
-        LowArgs, MmapMode, Mode, PatternSource, SearchMode, SortMode,
-        SortModeKind, SpecialMode, TypeChange,
+        AnalyzeSortField, BinaryMode, BoundaryMode, BufferMode, CaseMode,
+        ColorChoice, ContextMode, EncodingMode, EngineChoice, GenerateMode,
+        LoggingMode, LowArgs, MmapMode, Mode, PatternSource, SearchMode,
+        SortMode, SortModeKind, SpecialMode, TestScope, TypeChange,
     },
     Category, Flag, FlagValue,
 };
@@ -58,15 +58,35 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &Context,
     &ContextSeparator,
     &EnclosingSymbol,
+    &ContextKind,
     &Count,
     &CountMatches,
     &Crlf,
     &Debug,
+    &Doctor,
+    &UsageSummary,
     &Analyze,
     &Watch,
+    &WatchEvents,
+    &WatchGlob,
+    &VscodeIpc,
+    &Tail,
     &Diff,
+    &StructuralDiff,
+    &Deterministic,
     &Diagnostics,
     &Syntax,
+    &Symbols,
+    &Definition,
+    &References,
+    &Signature,
+    &FindDuplicates,
+    &FindDuplicatesThreshold,
+    &SymbolKinds,
+    &AstDepth,
+    &AstMaxNodes,
+    &AstSummary,
+    &WithDocs,
     &DfaSizeLimit,
     &Encoding,
     &Engine,
@@ -75,6 +95,7 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &Files,
     &FilesWithMatches,
     &FilesWithoutMatch,
+    &FiletypeStats,
     &FixedStrings,
     &Follow,
     &Generate,
@@ -82,6 +103,8 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &GlobCaseInsensitive,
     &Heading,
     &Help,
+    &HexDump,
+    &HexDumpContext,
     &Hidden,
     &HostnameBin,
     &HyperlinkFormat,
@@ -97,6 +120,7 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &LineNumber,
     &LineNumberNo,
     &LineRegexp,
+    &MaxBufferSize,
     &MaxColumns,
     &MaxColumnsPreview,
     &MaxCount,
@@ -130,26 +154,79 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &Pretty,
     &Quiet,
     &RegexSizeLimit,
+    &Remote,
+    &RemoteRef,
     &Replace,
     &SearchZip,
     &SmartCase,
     &Sort,
     &Sortr,
+    &SortParallel,
     &Stats,
     &StopOnNonmatch,
     &NoSyntaxHighlight,
+    &TestsOnly,
+    &NoTests,
+    &AnalyzeSort,
+    &AnalyzeMin,
     &Semantic,
     &SemanticModelPath,
     &SemanticModel,
     &SemanticDimensions,
+    &SemanticDimensionMode,
     &SemanticSimilarityThreshold,
     &SemanticMaxResults,
+    &SemanticTopK,
+    &SemanticClusterFlag,
+    &SemanticEfSearch,
+    &SemanticChunking,
+    &SemanticGranularity,
+    &SemanticChunkSize,
+    &SemanticChunkOverlap,
+    &SemanticBackendFlag,
+    &SemanticQuantizeFlag,
+    &SemanticRerank,
+    &SemanticRerankModel,
+    &SemanticHistory,
+    &SemanticExport,
+    &SemanticImport,
+    &SemanticQueryFlag,
+    &SemanticQueryFusion,
+    &SemanticDownloadModel,
+    &SemanticListModels,
+    &SemanticIndexStats,
+    &SemanticGc,
+    &SimilarTo,
+    &Hybrid,
+    &Since,
+    &Until,
+    &JsonPath,
+    &YamlPath,
+    &CsvColumn,
+    &CsvRow,
+    &AstPattern,
+    &AstPatternLang,
+    &TsQuery,
+    &OnlyIn,
+    &NotIn,
+    &AstRewrite,
+    &AstRewriteWrite,
+    &AstRewriteDryRun,
+    &Rules,
+    &PluginsDir,
+    &WasmPlugin,
+    &Symbol,
+    &AstMultiline,
     &Text,
     &Threads,
+    &Throttle,
     &Trace,
     &Tree,
     &Trim,
     &TruncateDiffs,
+    &DiffIgnoreEol,
+    &DiffIgnoreWhitespace,
+    &DiffHideTrivial,
     &Type,
     &TypeNot,
     &TypeAdd,
@@ -162,9 +239,13 @@ pub(super) const FLAGS: &[&dyn Flag] = &[
     &WithFilenameNo,
     &WordRegexp,
     // Config management flags
+    &ConfigDump,
+    &ConfigExtra,
     &ConfigStatus,
+    &Editor,
     &InitGlobalConfig,
     &InitLocalConfig,
+    &Merge,
     &OpenGlobalConfig,
     &OpenLocalConfig,
     // DEPRECATED (make them show up last in their respective categories)
@@ -1149,13 +1230,29 @@ match instead of a fixed number of context lines. Uses AST parsing to identify
 symbol boundaries for supported languages. Falls back to showing just the match
 line for unsupported file types.
 .sp
-This overrides any \flag{before-context}, \flag{after-context}, and \flag{context}
-flags.
+\flag{before-context}, \flag{after-context}, and \flag{context} combine with
+this flag instead of overriding it, regardless of the order they're given in:
+they pad the printed symbol with extra lines before/after it, which is useful
+for catching things like attributes or doc comments sitting just outside the
+symbol's own AST node.
 "
     }
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
         assert!(v.unwrap_switch(), "--enclosing-symbol has no negation");
-        args.context = ContextMode::EnclosingSymbol;
+        // Carry over any padding a preceding --context/--before-context/
+        // --after-context flag already set, rather than resetting it, so
+        // this flag combines with them regardless of order.
+        args.context = match std::mem::take(&mut args.context) {
+            ContextMode::Limited(limited) => {
+                ContextMode::EnclosingSymbol(limited)
+            }
+            ContextMode::EnclosingSymbol(limited) => {
+                ContextMode::EnclosingSymbol(limited)
+            }
+            ContextMode::Passthru => {
+                ContextMode::EnclosingSymbol(Default::default())
+            }
+        };
         Ok(())
     }
 }
@@ -1166,14 +1263,83 @@ fn test_enclosing_symbol() {
     let args = parse_low_raw(None::<&str>).unwrap();
     assert_eq!(ContextMode::default(), args.context);
     let args = parse_low_raw(["--enclosing-symbol"]).unwrap();
-    assert_eq!(ContextMode::EnclosingSymbol, args.context);
-    // Test that enclosing-symbol overrides other context flags
+    assert_eq!(0, args.context.enclosing_symbol_padding().unwrap().0);
+    assert_eq!(0, args.context.enclosing_symbol_padding().unwrap().1);
+
+    // --context combines with --enclosing-symbol as padding, regardless of
+    // which flag comes first.
     let args = parse_low_raw(["-C5", "--enclosing-symbol"]).unwrap();
-    assert_eq!(ContextMode::EnclosingSymbol, args.context);
+    assert_eq!((5, 5), args.context.enclosing_symbol_padding().unwrap());
     let args = parse_low_raw(["--enclosing-symbol", "-C5"]).unwrap();
-    let mut mode = ContextMode::default();
-    mode.set_both(5);
-    assert_eq!(mode, args.context);
+    assert_eq!((5, 5), args.context.enclosing_symbol_padding().unwrap());
+
+    // --before-context/--after-context pad only their respective side.
+    let args = parse_low_raw(["--enclosing-symbol", "-B2", "-A4"]).unwrap();
+    assert_eq!((2, 4), args.context.enclosing_symbol_padding().unwrap());
+}
+
+/// --context-kind
+#[derive(Debug)]
+struct ContextKind;
+
+impl Flag for ContextKind {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "context-kind"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("KIND")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Restrict \flag{enclosing-symbol} to the given context kinds."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Restrict the AST context shown by \flag{enclosing-symbol} to the given kinds.
+\fIKIND\fP must be one of \fBfunction\fP, \fBclass\fP, \fBmodule\fP or
+\fBblock\fP. This flag can be given multiple times to allow more than one
+kind.
+.sp
+\fBfunction\fP also matches methods, since both are function-shaped symbols
+from the caller's point of view.
+.sp
+If this flag is never given, \flag{enclosing-symbol} falls back to its
+default set of context kinds (functions, classes, methods and modules).
+.sp
+Example: --enclosing-symbol --context-kind function --context-kind class
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.context_kinds.push(convert::string(v.unwrap_value())?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_context_kind() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.context_kinds);
+
+    let args = parse_low_raw(["--context-kind", "function"]).unwrap();
+    assert_eq!(vec!["function".to_string()], args.context_kinds);
+
+    let args = parse_low_raw([
+        "--context-kind",
+        "function",
+        "--context-kind",
+        "class",
+    ])
+    .unwrap();
+    assert_eq!(
+        vec!["function".to_string(), "class".to_string()],
+        args.context_kinds
+    );
 }
 
 /// --context-separator
@@ -1535,6 +1701,105 @@ fn test_debug() {
     assert_eq!(Some(LoggingMode::Debug), args.logging);
 }
 
+/// --doctor
+#[derive(Debug)]
+struct Doctor;
+
+impl Flag for Doctor {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "doctor"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Diagnose common setup problems and exit."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Run a first-run diagnostics report and exit without searching.
+.sp
+This checks for the external tools outgrep shells out to (\fBgit\fP,
+\fBcargo\fP, and common Node-based linters), whether the terminal supports
+color output, whether the configuration files outgrep would load are valid,
+and whether the semantic search model cache is present and populated. Each
+check prints a status and, for anything that isn't healthy, a suggested
+fix.
+.sp
+This is meant to reduce setup friction on a new machine or for a new
+contributor, especially around the many external tools and services that
+outgrep's AST, semantic search, and LSP integrations touch.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--doctor can only be enabled");
+        args.special = Some(SpecialMode::Doctor);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_doctor() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.special);
+
+    let args = parse_low_raw(["--doctor"]).unwrap();
+    assert_eq!(Some(SpecialMode::Doctor), args.special);
+}
+
+/// --usage-summary
+#[derive(Debug)]
+struct UsageSummary;
+
+impl Flag for UsageSummary {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "usage-summary"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print a summary of your local search history and exit."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print a summary of your own local search history and exit without
+searching: your top patterns, most-searched directories, and average query
+time.
+.sp
+This is meant to help you notice your own repeated searches so they can be
+promoted into config presets or shell aliases. The history it reads from is
+built up by every search you run, and is kept entirely on your machine
+under \fB~/.config/outgrep/history.jsonl\fP; none of it is ever
+transmitted anywhere.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--usage-summary can only be enabled");
+        args.special = Some(SpecialMode::UsageSummary);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_usage_summary() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.special);
+
+    let args = parse_low_raw(["--usage-summary"]).unwrap();
+    assert_eq!(Some(SpecialMode::UsageSummary), args.special);
+}
+
 /// --analyze
 #[derive(Debug)]
 struct Analyze;
@@ -1644,5550 +1909,9607 @@ fn test_watch() {
     assert_eq!(true, args.watch);
 }
 
-/// --diff
+/// --watch-events
 #[derive(Debug)]
-struct Diff;
-impl Flag for Diff {
+struct WatchEvents;
+
+impl Flag for WatchEvents {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "diff"
+        "watch-events"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("EVENTS")
     }
     fn doc_category(&self) -> Category {
         Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Show semantic diffs for changed files during analysis."
+        r"Restrict \flag{watch} to the given event kinds."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Show semantic diffs for changed files during analysis.
-.sp
-The \flag{diff} flag enables outgrep's semantic diff capabilities,
-displaying detailed, colorized diffs for files that have been
-modified according to Git status.
-.sp
-This flag is typically used in combination with \flag{analyze} to
-provide detailed diff information for changed files during code
-analysis. The diff output shows line-by-line changes with syntax
-highlighting and contextual information.
-.sp
-Features include:
+Restrict \flag{watch} to only report the given comma-separated event kinds.
+\fIEVENTS\fP is a comma-separated list where each item is one of \fBcreate\fP,
+\fBmodify\fP, \fBdelete\fP or \fBrename\fP. This flag can be given multiple
+times, and all of its values accumulate.
 .sp
-- Colorized diff output with red for deletions and green for additions
-- Line-by-line comparison with context
-- Support for all file types analyzed by outgrep
-- Integration with Git to compare against HEAD
+If this flag is never given, all event kinds are reported.
 .sp
-This mode is useful for reviewing changes during development and
-understanding the impact of modifications on the codebase.
+Example: --watch --watch-events create,modify
 "
     }
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--diff can only be enabled");
-        args.diff = true;
+        let value = convert::string(v.unwrap_value())?;
+        for kind in value.split(',') {
+            let kind = kind.trim();
+            if !kind.is_empty() {
+                args.watch_events.push(kind.to_string());
+            }
+        }
         Ok(())
     }
 }
+
 #[cfg(test)]
 #[test]
-fn test_diff() {
+fn test_watch_events() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.diff);
-    let args = parse_low_raw(["--diff"]).unwrap();
-    assert_eq!(true, args.diff);
+    assert_eq!(Vec::<String>::new(), args.watch_events);
+
+    let args = parse_low_raw(["--watch-events", "create,modify"]).unwrap();
+    assert_eq!(
+        vec!["create".to_string(), "modify".to_string()],
+        args.watch_events
+    );
+
+    let args = parse_low_raw([
+        "--watch-events",
+        "create",
+        "--watch-events",
+        "delete",
+    ])
+    .unwrap();
+    assert_eq!(
+        vec!["create".to_string(), "delete".to_string()],
+        args.watch_events
+    );
 }
 
-/// --diagnostics
+/// --watch-glob
 #[derive(Debug)]
-struct Diagnostics;
-impl Flag for Diagnostics {
+struct WatchGlob;
+
+impl Flag for WatchGlob {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "diagnostics"
+        "watch-glob"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("GLOB")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Show compiler diagnostics for source files."
+        r"Only report \flag{watch} events for paths matching GLOB."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Show compiler diagnostics for source files including errors, warnings,
-and hints from language-specific tools.
-
-This flag enables compiler and linter integration to show diagnostic
-information for each source file in the tree. Supported tools include:
-
-• Rust: cargo check
-• TypeScript/JavaScript: tsc, eslint
-• Python: mypy, flake8
-• Go: go vet
-• Java: javac
-
-Diagnostics are displayed with appropriate severity indicators and
-include line numbers, error codes, and detailed messages.
+Only report \flag{watch} events for paths matching the given glob. Multiple
+\flag{watch-glob} flags may be given, in which case an event is reported if
+its path matches any of them. Globbing rules match \fB.gitignore\fP globs, the
+same as \flag{glob}.
+.sp
+If this flag is never given, events for every watched path are reported.
+.sp
+Example: --watch --watch-glob '*.rs'
 "
     }
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--diagnostics has no negation");
-        args.diagnostics = true;
+        let glob = convert::string(v.unwrap_value())?;
+        args.watch_globs.push(glob);
         Ok(())
     }
 }
 
-/// --syntax
+#[cfg(test)]
+#[test]
+fn test_watch_glob() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.watch_globs);
+
+    let args = parse_low_raw(["--watch-glob", "*.rs"]).unwrap();
+    assert_eq!(vec!["*.rs".to_string()], args.watch_globs);
+
+    let args = parse_low_raw(["--watch-glob", "*.rs", "--watch-glob", "*.ts"])
+        .unwrap();
+    assert_eq!(vec!["*.rs".to_string(), "*.ts".to_string()], args.watch_globs);
+}
+
+/// --vscode-ipc
 #[derive(Debug)]
-struct Syntax;
-impl Flag for Syntax {
+struct VscodeIpc;
+impl Flag for VscodeIpc {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "syntax"
+        "vscode-ipc"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Show AST structure and symbol information for source files."
+        r"Run a long-lived JSON protocol on stdin/stdout for editor extensions."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Show Abstract Syntax Tree (AST) structure and symbol information for source files.
-
-The \flag{syntax} flag enables outgrep's syntax analysis capabilities,
-extracting and displaying AST structure, syntax highlighting tokens,
-and symbol information (functions, classes, types, modules) from source files.
-
-Features include:
-• Language detection and AST parsing for 21+ programming languages
-• Hierarchical AST node structure with type and position information
-• Syntax highlighting token extraction (keywords, strings, comments)
-• Symbol extraction and categorization (functions, classes, types, modules)
-• JSON output compatible with editors and analysis tools
-
-Supported languages include: Rust, JavaScript, TypeScript, Python, Go, Java,
-C, C++, C#, Ruby, PHP, Swift, Kotlin, Scala, Haskell, Elixir, Lua, Bash,
-HTML, CSS, JSON, YAML, and TSX.
-
-This mode is useful for code analysis tools, editors, and understanding
-the syntactic structure of source files.
+Run a long-lived, line-delimited JSON protocol on stdin/stdout, tailored for
+the VS Code extension: one process handles many searches over its lifetime
+instead of the extension spawning \fBog\fP per keystroke.
+.sp
+Each line of input is a request object, e.g.
+\fB{\"id\": 1, \"method\": \"search\", \"params\": {\"pattern\": \"TODO\"}}\fP.
+Matches for a search stream back as \fBmatch\fP notifications as they're
+found (each decorated with the file's git status and compiler/linter
+diagnostic count), followed by a \fB{\"id\": 1, \"result\": ...}\fP response
+once the search finishes. A search can be stopped early by sending
+\fB{\"id\": 2, \"method\": \"cancel\", \"params\": {\"id\": 1}}\fP.
+.sp
+This flag can't be combined with a search pattern or paths on the command
+line; those are given per request instead. outgrep runs until stdin closes.
 "
     }
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--syntax can only be enabled");
-        args.syntax = true;
+        assert!(v.unwrap_switch(), "--vscode-ipc can only be enabled");
+        args.vscode_ipc = true;
         Ok(())
     }
 }
 #[cfg(test)]
 #[test]
-fn test_syntax() {
+fn test_vscode_ipc() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.syntax);
-    let args = parse_low_raw(["--syntax"]).unwrap();
-    assert_eq!(true, args.syntax);
+    assert_eq!(false, args.vscode_ipc);
+    let args = parse_low_raw(["--vscode-ipc"]).unwrap();
+    assert_eq!(true, args.vscode_ipc);
 }
 
-/// --dfa-size-limit
+/// --tail
 #[derive(Debug)]
-struct DfaSizeLimit;
-
-impl Flag for DfaSizeLimit {
+struct Tail;
+impl Flag for Tail {
     fn is_switch(&self) -> bool {
-        false
+        true
     }
     fn name_long(&self) -> &'static str {
-        "dfa-size-limit"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM+SUFFIX?")
+        "tail"
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"The upper size limit of the regex DFA."
+        r"Follow a single file and search data as it's appended."
     }
     fn doc_long(&self) -> &'static str {
         r"
-The upper size limit of the regex DFA. The default limit is something generous
-for any single pattern or for many smallish patterns. This should only be
-changed on very large regex inputs where the (slower) fallback regex engine may
-otherwise be used if the limit is reached.
-.sp
-The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
-correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
-provided the input is treated as bytes.
+Follow a single file and search only the data appended to it, the way
+\flag{watch} follows a directory but scoped to one file's contents instead
+of its metadata.
+.sp
+This requires exactly one file path on the command line (not stdin, not a
+directory, and not multiple paths). outgrep opens the file, seeks to its
+current end, and then waits for it to grow, searching each newly appended
+chunk as it arrives and printing matches as they're found. Content that was
+already in the file before outgrep started is never searched or printed,
+the same way \flag{-}\flag{-}follow \flag{-n} \flag{0} works for \fBtail\fP.
+.sp
+This is meant as a structured alternative to \fBtail -f file | grep\fP:
+because matches are found by outgrep's own searcher and printer, flags like
+\flag{json}, \flag{count}, and the usual context flags all keep working
+against the streamed output.
+.sp
+outgrep runs until interrupted (Ctrl+C).
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let v = v.unwrap_value();
-        args.dfa_size_limit = Some(convert::human_readable_usize(&v)?);
+        assert!(v.unwrap_switch(), "--tail can only be enabled");
+        args.tail = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_dfa_size_limit() {
+fn test_tail() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.dfa_size_limit);
-
-    #[cfg(target_pointer_width = "64")]
-    {
-        let args = parse_low_raw(["--dfa-size-limit", "9G"]).unwrap();
-        assert_eq!(Some(9 * (1 << 30)), args.dfa_size_limit);
+    assert_eq!(false, args.tail);
+    let args = parse_low_raw(["--tail"]).unwrap();
+    assert_eq!(true, args.tail);
+}
 
-        let args = parse_low_raw(["--dfa-size-limit=9G"]).unwrap();
-        assert_eq!(Some(9 * (1 << 30)), args.dfa_size_limit);
-
-        let args =
-            parse_low_raw(["--dfa-size-limit=9G", "--dfa-size-limit=0"])
-                .unwrap();
-        assert_eq!(Some(0), args.dfa_size_limit);
-    }
-
-    let args = parse_low_raw(["--dfa-size-limit=0K"]).unwrap();
-    assert_eq!(Some(0), args.dfa_size_limit);
-
-    let args = parse_low_raw(["--dfa-size-limit=0M"]).unwrap();
-    assert_eq!(Some(0), args.dfa_size_limit);
-
-    let args = parse_low_raw(["--dfa-size-limit=0G"]).unwrap();
-    assert_eq!(Some(0), args.dfa_size_limit);
-
-    let result = parse_low_raw(["--dfa-size-limit", "9999999999999999999999"]);
-    assert!(result.is_err(), "{result:?}");
-
-    let result = parse_low_raw(["--dfa-size-limit", "9999999999999999G"]);
-    assert!(result.is_err(), "{result:?}");
-}
-
-/// -E/--encoding
+/// --diff
 #[derive(Debug)]
-struct Encoding;
-
-impl Flag for Encoding {
+struct Diff;
+impl Flag for Diff {
     fn is_switch(&self) -> bool {
-        false
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'E')
+        true
     }
     fn name_long(&self) -> &'static str {
-        "encoding"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-encoding")
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("ENCODING")
+        "diff"
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Specify the text encoding of files to search."
+        r"Show semantic diffs for changed files during analysis."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specify the text encoding that ripgrep will use on all files searched. The
-default value is \fBauto\fP, which will cause ripgrep to do a best effort
-automatic detection of encoding on a per-file basis. Automatic detection in
-this case only applies to files that begin with a UTF-8 or UTF-16 byte-order
-mark (BOM). No other automatic detection is performed. One can also specify
-\fBnone\fP which will then completely disable BOM sniffing and always result
-in searching the raw bytes, including a BOM if it's present, regardless of its
-encoding.
+Show semantic diffs for changed files during analysis.
 .sp
-Other supported values can be found in the list of labels here:
-\fIhttps://encoding.spec.whatwg.org/#concept-encoding-get\fP.
+The \flag{diff} flag enables outgrep's semantic diff capabilities,
+displaying detailed, colorized diffs for files that have been
+modified according to Git status.
 .sp
-For more details on encoding and how ripgrep deals with it, see \fBGUIDE.md\fP.
+This flag is typically used in combination with \flag{analyze} to
+provide detailed diff information for changed files during code
+analysis. The diff output shows line-by-line changes with syntax
+highlighting and contextual information.
 .sp
-The encoding detection that ripgrep uses can be reverted to its automatic mode
-via the \flag-negate{encoding} flag.
+Features include:
+.sp
+- Colorized diff output with red for deletions and green for additions
+- Line-by-line comparison with context
+- Support for all file types analyzed by outgrep
+- Integration with Git to compare against HEAD
+.sp
+This mode is useful for reviewing changes during development and
+understanding the impact of modifications on the codebase.
 "
     }
-    fn completion_type(&self) -> CompletionType {
-        CompletionType::Encoding
-    }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let value = match v {
-            FlagValue::Value(v) => v,
-            FlagValue::Switch(true) => {
-                unreachable!("--encoding must accept a value")
-            }
-            FlagValue::Switch(false) => {
-                args.encoding = EncodingMode::Auto;
-                return Ok(());
-            }
-        };
-        let label = convert::str(&value)?;
-        args.encoding = match label {
-            "auto" => EncodingMode::Auto,
-            "none" => EncodingMode::Disabled,
-            _ => EncodingMode::Some(grep::searcher::Encoding::new(label)?),
-        };
+        assert!(v.unwrap_switch(), "--diff can only be enabled");
+        args.diff = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_encoding() {
+fn test_diff() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(EncodingMode::Auto, args.encoding);
-
-    let args = parse_low_raw(["--encoding", "auto"]).unwrap();
-    assert_eq!(EncodingMode::Auto, args.encoding);
-
-    let args = parse_low_raw(["--encoding", "none"]).unwrap();
-    assert_eq!(EncodingMode::Disabled, args.encoding);
-
-    let args = parse_low_raw(["--encoding=none"]).unwrap();
-    assert_eq!(EncodingMode::Disabled, args.encoding);
-
-    let args = parse_low_raw(["-E", "none"]).unwrap();
-    assert_eq!(EncodingMode::Disabled, args.encoding);
-
-    let args = parse_low_raw(["-Enone"]).unwrap();
-    assert_eq!(EncodingMode::Disabled, args.encoding);
-
-    let args = parse_low_raw(["-E", "none", "--no-encoding"]).unwrap();
-    assert_eq!(EncodingMode::Auto, args.encoding);
-
-    let args = parse_low_raw(["--no-encoding", "-E", "none"]).unwrap();
-    assert_eq!(EncodingMode::Disabled, args.encoding);
-
-    let args = parse_low_raw(["-E", "utf-16"]).unwrap();
-    let enc = grep::searcher::Encoding::new("utf-16").unwrap();
-    assert_eq!(EncodingMode::Some(enc), args.encoding);
-
-    let args = parse_low_raw(["-E", "utf-16", "--no-encoding"]).unwrap();
-    assert_eq!(EncodingMode::Auto, args.encoding);
-
-    let result = parse_low_raw(["-E", "foo"]);
-    assert!(result.is_err(), "{result:?}");
+    assert_eq!(false, args.diff);
+    let args = parse_low_raw(["--diff"]).unwrap();
+    assert_eq!(true, args.diff);
 }
 
-/// --engine
+/// --structural-diff
 #[derive(Debug)]
-struct Engine;
-
-impl Flag for Engine {
+struct StructuralDiff;
+impl Flag for StructuralDiff {
     fn is_switch(&self) -> bool {
-        false
+        true
     }
     fn name_long(&self) -> &'static str {
-        "engine"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("ENGINE")
+        "structural-diff"
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Specify which regex engine to use."
+        r"Show symbol-level diffs for changed files during analysis."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specify which regular expression engine to use. When you choose a regex engine,
-it applies that choice for every regex provided to ripgrep (e.g., via multiple
-\flag{regexp} or \flag{file} flags).
-.sp
-Accepted values are \fBdefault\fP, \fBpcre2\fP, or \fBauto\fP.
-.sp
-The default value is \fBdefault\fP, which is usually the fastest and should be
-good for most use cases. The \fBpcre2\fP engine is generally useful when you
-want to use features such as look-around or backreferences. \fBauto\fP will
-dynamically choose between supported regex engines depending on the features
-used in a pattern on a best effort basis.
+Show symbol-level diffs for changed files during analysis.
 .sp
-Note that the \fBpcre2\fP engine is an optional ripgrep feature. If PCRE2
-wasn't included in your build of ripgrep, then using this flag will result in
-ripgrep printing an error message and exiting.
+Unlike \flag{diff}, which renders a line-oriented diff (via an external
+\fBdiffsitter\fP process when available), \flag{structural-diff} parses
+both the HEAD and worktree versions of a file with outgrep's bundled
+tree-sitter parsers and reports which functions, classes, types, and
+modules were added, removed, or edited.
 .sp
-This overrides previous uses of the \flag{pcre2} and \flag{auto-hybrid-regex}
-flags.
+This flag is typically used in combination with \flag{analyze} to review
+what actually changed in a file's structure, ignoring formatting-only or
+whitespace-only edits that don't move a symbol boundary.
 "
     }
-    fn doc_choices(&self) -> &'static [&'static str] {
-        &["default", "pcre2", "auto"]
-    }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let v = v.unwrap_value();
-        let string = convert::str(&v)?;
-        args.engine = match string {
-            "default" => EngineChoice::Default,
-            "pcre2" => EngineChoice::PCRE2,
-            "auto" => EngineChoice::Auto,
-            _ => anyhow::bail!("unrecognized regex engine '{string}'"),
-        };
+        assert!(v.unwrap_switch(), "--structural-diff can only be enabled");
+        args.structural_diff = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_engine() {
+fn test_structural_diff() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
-
-    let args = parse_low_raw(["--engine", "pcre2"]).unwrap();
-    assert_eq!(EngineChoice::PCRE2, args.engine);
-
-    let args = parse_low_raw(["--engine=pcre2"]).unwrap();
-    assert_eq!(EngineChoice::PCRE2, args.engine);
-
-    let args =
-        parse_low_raw(["--auto-hybrid-regex", "--engine=pcre2"]).unwrap();
-    assert_eq!(EngineChoice::PCRE2, args.engine);
-
-    let args =
-        parse_low_raw(["--engine=pcre2", "--auto-hybrid-regex"]).unwrap();
-    assert_eq!(EngineChoice::Auto, args.engine);
-
-    let args =
-        parse_low_raw(["--auto-hybrid-regex", "--engine=auto"]).unwrap();
-    assert_eq!(EngineChoice::Auto, args.engine);
-
-    let args =
-        parse_low_raw(["--auto-hybrid-regex", "--engine=default"]).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
-
-    let args =
-        parse_low_raw(["--engine=pcre2", "--no-auto-hybrid-regex"]).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
+    assert_eq!(false, args.structural_diff);
+    let args = parse_low_raw(["--structural-diff"]).unwrap();
+    assert_eq!(true, args.structural_diff);
 }
 
-/// --field-context-separator
+/// --deterministic
 #[derive(Debug)]
-struct FieldContextSeparator;
-
-impl Flag for FieldContextSeparator {
+struct Deterministic;
+impl Flag for Deterministic {
     fn is_switch(&self) -> bool {
-        false
+        true
     }
     fn name_long(&self) -> &'static str {
-        "field-context-separator"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("SEPARATOR")
+        "deterministic"
     }
     fn doc_category(&self) -> Category {
         Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Set the field context separator."
+        r"Suppress wall-clock timing so output is reproducible."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Set the field context separator. This separator is only used when printing
-contextual lines. It is used to delimit file paths, line numbers, columns and
-the contextual line itself. The separator may be any number of bytes, including
-zero. Escape sequences like \fB\\x7F\fP or \fB\\t\fP may be used.
+Suppress wall-clock timing so output is reproducible across runs.
 .sp
-The \fB-\fP character is the default value.
+Currently this zeroes out the timing fields in \flag{stats} output (both
+the human-readable and JSON summaries). It exists mainly as a hook for
+golden-file tests, which otherwise can't assert on \flag{stats} output
+without first stripping timing lines.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        use crate::flags::lowargs::FieldContextSeparator as Separator;
-
-        args.field_context_separator = Separator::new(&v.unwrap_value())?;
+        assert!(v.unwrap_switch(), "--deterministic can only be enabled");
+        args.deterministic = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_field_context_separator() {
-    use bstr::BString;
-
+fn test_deterministic() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(BString::from("-"), args.field_context_separator.into_bytes());
-
-    let args = parse_low_raw(["--field-context-separator", "XYZ"]).unwrap();
-    assert_eq!(
-        BString::from("XYZ"),
-        args.field_context_separator.into_bytes()
-    );
-
-    let args = parse_low_raw(["--field-context-separator=XYZ"]).unwrap();
-    assert_eq!(
-        BString::from("XYZ"),
-        args.field_context_separator.into_bytes()
-    );
+    assert_eq!(false, args.deterministic);
+    let args = parse_low_raw(["--deterministic"]).unwrap();
+    assert_eq!(true, args.deterministic);
+}
 
-    let args = parse_low_raw([
-        "--field-context-separator",
-        "XYZ",
-        "--field-context-separator",
-        "ABC",
-    ])
-    .unwrap();
-    assert_eq!(
-        BString::from("ABC"),
-        args.field_context_separator.into_bytes()
-    );
+/// --diagnostics
+#[derive(Debug)]
+struct Diagnostics;
+impl Flag for Diagnostics {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "diagnostics"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show compiler diagnostics for source files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Show compiler diagnostics for source files including errors, warnings,
+and hints from language-specific tools.
 
-    let args = parse_low_raw(["--field-context-separator", r"\t"]).unwrap();
-    assert_eq!(BString::from("\t"), args.field_context_separator.into_bytes());
+This flag enables compiler and linter integration to show diagnostic
+information for each source file in the tree. Supported tools include:
 
-    let args = parse_low_raw(["--field-context-separator", r"\x00"]).unwrap();
-    assert_eq!(
-        BString::from("\x00"),
-        args.field_context_separator.into_bytes()
-    );
+• Rust: cargo check
+• TypeScript/JavaScript: tsc, eslint
+• Python: mypy, flake8
+• Go: go vet
+• Java: javac
 
-    // This checks that invalid UTF-8 can be used. This case isn't too tricky
-    // to handle, because it passes the invalid UTF-8 as an escape sequence
-    // that is itself valid UTF-8. It doesn't become invalid UTF-8 until after
-    // the argument is parsed and then unescaped.
-    let args = parse_low_raw(["--field-context-separator", r"\xFF"]).unwrap();
-    assert_eq!(
-        BString::from(b"\xFF"),
-        args.field_context_separator.into_bytes()
-    );
+Diagnostics are displayed with appropriate severity indicators and
+include line numbers, error codes, and detailed messages.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--diagnostics has no negation");
+        args.diagnostics = true;
+        Ok(())
+    }
+}
 
-    // In this case, we specifically try to pass an invalid UTF-8 argument to
-    // the flag. In theory we might be able to support this, but because we do
-    // unescaping and because unescaping wants valid UTF-8, we do a UTF-8 check
-    // on the value. Since we pass invalid UTF-8, it fails. This demonstrates
-    // that the only way to use an invalid UTF-8 separator is by specifying an
-    // escape sequence that is itself valid UTF-8.
-    #[cfg(unix)]
-    {
-        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+/// --syntax
+#[derive(Debug)]
+struct Syntax;
+impl Flag for Syntax {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "syntax"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show AST structure and symbol information for source files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Show Abstract Syntax Tree (AST) structure and symbol information for source files.
 
-        let result = parse_low_raw([
-            OsStr::from_bytes(b"--field-context-separator"),
-            OsStr::from_bytes(&[0xFF]),
-        ]);
-        assert!(result.is_err(), "{result:?}");
+The \flag{syntax} flag enables outgrep's syntax analysis capabilities,
+extracting and displaying AST structure, syntax highlighting tokens,
+and symbol information (functions, classes, types, modules) from source files.
+
+Features include:
+• Language detection and AST parsing for 24+ programming languages
+• Hierarchical AST node structure with type and position information
+• Syntax highlighting token extraction (keywords, strings, comments)
+• Symbol extraction and categorization (functions, classes, types, modules)
+• JSON output compatible with editors and analysis tools
+
+Supported languages include: Rust, JavaScript, TypeScript, Python, Go, Java,
+C, C++, C#, Ruby, PHP, Swift, Kotlin, Scala, Haskell, Elixir, Lua, Bash,
+HTML, CSS, JSON, YAML, TSX, Zig, Dart, and Nim.
+
+This mode is useful for code analysis tools, editors, and understanding
+the syntactic structure of source files.
+"
     }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--syntax can only be enabled");
+        args.syntax = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_syntax() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.syntax);
+    let args = parse_low_raw(["--syntax"]).unwrap();
+    assert_eq!(true, args.syntax);
 }
 
-/// --field-match-separator
+/// --symbols
 #[derive(Debug)]
-struct FieldMatchSeparator;
+struct Symbols;
+impl Flag for Symbols {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "symbols"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print a ctags-like outline of symbols under the search paths."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print a ctags-like outline of every function, class, type, and module
+defined under the search paths, one file at a time, with the line and
+column each symbol's name starts at.
+
+This uses the same AST extraction as \flag{syntax}, but only prints the
+symbol outline rather than the full AST structure and syntax highlighting
+tokens, and walks the given paths (or the current directory, by default)
+instead of requiring \flag{tree}. Combine with \fB--json\fP to produce
+output an editor can parse into a symbol picker.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--symbols can only be enabled");
+        args.symbols = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_symbols() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.symbols);
+    let args = parse_low_raw(["--symbols"]).unwrap();
+    assert_eq!(true, args.symbols);
+}
 
-impl Flag for FieldMatchSeparator {
+/// --definition
+#[derive(Debug)]
+struct Definition;
+impl Flag for Definition {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "field-match-separator"
+        "definition"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("SEPARATOR")
+        Some("IDENT")
     }
     fn doc_category(&self) -> Category {
         Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Set the field match separator."
+        r"Print where IDENT is defined under the search paths."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Set the field match separator. This separator is only used when printing
-matching lines. It is used to delimit file paths, line numbers, columns and the
-matching line itself. The separator may be any number of bytes, including zero.
-Escape sequences like \fB\\x7F\fP or \fB\\t\fP may be used.
-.sp
-The \fB:\fP character is the default value.
+Print every definition of \fIIDENT\fP found under the search paths, using
+the same AST-based symbol extraction as \flag{symbols}, with the path,
+line, and column of each definition site.
+
+Because that extraction only records symbol *definitions* -- functions,
+classes, types, and modules -- and not the places a name is merely called
+or mentioned, this finds where \fIIDENT\fP is defined without the false
+positives an \fBog IDENT\fP text search would turn up from its call sites
+and comments. Combine with \fB--json\fP for editor \(lqgo to definition\(rq
+integrations.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        use crate::flags::lowargs::FieldMatchSeparator as Separator;
-
-        args.field_match_separator = Separator::new(&v.unwrap_value())?;
+        args.definition = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_field_match_separator() {
-    use bstr::BString;
-
+fn test_definition() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(BString::from(":"), args.field_match_separator.into_bytes());
-
-    let args = parse_low_raw(["--field-match-separator", "XYZ"]).unwrap();
-    assert_eq!(BString::from("XYZ"), args.field_match_separator.into_bytes());
-
-    let args = parse_low_raw(["--field-match-separator=XYZ"]).unwrap();
-    assert_eq!(BString::from("XYZ"), args.field_match_separator.into_bytes());
-
-    let args = parse_low_raw([
-        "--field-match-separator",
-        "XYZ",
-        "--field-match-separator",
-        "ABC",
-    ])
-    .unwrap();
-    assert_eq!(BString::from("ABC"), args.field_match_separator.into_bytes());
-
-    let args = parse_low_raw(["--field-match-separator", r"\t"]).unwrap();
-    assert_eq!(BString::from("\t"), args.field_match_separator.into_bytes());
-
-    let args = parse_low_raw(["--field-match-separator", r"\x00"]).unwrap();
-    assert_eq!(BString::from("\x00"), args.field_match_separator.into_bytes());
-
-    // This checks that invalid UTF-8 can be used. This case isn't too tricky
-    // to handle, because it passes the invalid UTF-8 as an escape sequence
-    // that is itself valid UTF-8. It doesn't become invalid UTF-8 until after
-    // the argument is parsed and then unescaped.
-    let args = parse_low_raw(["--field-match-separator", r"\xFF"]).unwrap();
-    assert_eq!(
-        BString::from(b"\xFF"),
-        args.field_match_separator.into_bytes()
-    );
-
-    // In this case, we specifically try to pass an invalid UTF-8 argument to
-    // the flag. In theory we might be able to support this, but because we do
-    // unescaping and because unescaping wants valid UTF-8, we do a UTF-8 check
-    // on the value. Since we pass invalid UTF-8, it fails. This demonstrates
-    // that the only way to use an invalid UTF-8 separator is by specifying an
-    // escape sequence that is itself valid UTF-8.
-    #[cfg(unix)]
-    {
-        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
-
-        let result = parse_low_raw([
-            OsStr::from_bytes(b"--field-match-separator"),
-            OsStr::from_bytes(&[0xFF]),
-        ]);
-        assert!(result.is_err(), "{result:?}");
-    }
+    assert_eq!(None, args.definition);
+    let args = parse_low_raw(["--definition", "parse_config"]).unwrap();
+    assert_eq!(Some("parse_config".to_string()), args.definition);
 }
 
-/// -f/--file
+/// --references
 #[derive(Debug)]
-struct File;
-
-impl Flag for File {
+struct References;
+impl Flag for References {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'f')
-    }
     fn name_long(&self) -> &'static str {
-        "file"
+        "references"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("PATTERNFILE")
+        Some("IDENT")
     }
     fn doc_category(&self) -> Category {
-        Category::Input
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Search for patterns from the given file."
+        r"Print where IDENT is used under the search paths."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Search for patterns from the given file, with one pattern per line. When this
-flag is used multiple times or in combination with the \flag{regexp} flag, then
-all patterns provided are searched. Empty pattern lines will match all input
-lines, and the newline is not counted as part of the pattern.
-.sp
-A line is printed if and only if it matches at least one of the patterns.
-.sp
-When \fIPATTERNFILE\fP is \fB-\fP, then \fBstdin\fP will be read for the
-patterns.
-.sp
-When \flag{file} or \flag{regexp} is used, then ripgrep treats all positional
-arguments as files or directories to search.
+Print every usage of \fIIDENT\fP found under the search paths: call
+expressions where \fIIDENT\fP is the callee, and type references where
+\fIIDENT\fP names a type. Each occurrence is grouped under its enclosing
+function, method, or type.
+.sp
+Because matches are scoped to those AST node kinds, occurrences inside
+strings and comments are excluded, along with unrelated identifiers that
+merely share \fIIDENT\fP's spelling in a different context. Combine with
+\fB--json\fP for editor \(lqfind references\(rq integrations. See also
+\flag{definition}, which finds where \fIIDENT\fP is defined instead.
 "
     }
-    fn completion_type(&self) -> CompletionType {
-        CompletionType::Filename
-    }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let path = PathBuf::from(v.unwrap_value());
-        args.patterns.push(PatternSource::File(path));
+        args.references = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_file() {
+fn test_references() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<PatternSource>::new(), args.patterns);
-
-    let args = parse_low_raw(["--file", "foo"]).unwrap();
-    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
-
-    let args = parse_low_raw(["--file=foo"]).unwrap();
-    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
-
-    let args = parse_low_raw(["-f", "foo"]).unwrap();
-    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
-
-    let args = parse_low_raw(["-ffoo"]).unwrap();
-    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
+    assert_eq!(None, args.references);
+    let args = parse_low_raw(["--references", "parse_config"]).unwrap();
+    assert_eq!(Some("parse_config".to_string()), args.references);
+}
 
-    let args = parse_low_raw(["--file", "-foo"]).unwrap();
-    assert_eq!(
-        vec![PatternSource::File(PathBuf::from("-foo"))],
-        args.patterns
-    );
-
-    let args = parse_low_raw(["--file=-foo"]).unwrap();
-    assert_eq!(
-        vec![PatternSource::File(PathBuf::from("-foo"))],
-        args.patterns
-    );
-
-    let args = parse_low_raw(["-f", "-foo"]).unwrap();
-    assert_eq!(
-        vec![PatternSource::File(PathBuf::from("-foo"))],
-        args.patterns
-    );
-
-    let args = parse_low_raw(["-f-foo"]).unwrap();
-    assert_eq!(
-        vec![PatternSource::File(PathBuf::from("-foo"))],
-        args.patterns
-    );
-
-    let args = parse_low_raw(["--file=foo", "--file", "bar"]).unwrap();
-    assert_eq!(
-        vec![
-            PatternSource::File(PathBuf::from("foo")),
-            PatternSource::File(PathBuf::from("bar"))
-        ],
-        args.patterns
-    );
-
-    // We permit path arguments to be invalid UTF-8. So test that. Some of
-    // these cases are tricky and depend on lexopt doing the right thing.
-    //
-    // We probably should add tests for this handling on Windows too, but paths
-    // that are invalid UTF-16 appear incredibly rare in the Windows world.
-    #[cfg(unix)]
-    {
-        use std::{
-            ffi::{OsStr, OsString},
-            os::unix::ffi::{OsStrExt, OsStringExt},
-        };
-
-        let bytes = &[b'A', 0xFF, b'Z'][..];
-        let path = PathBuf::from(OsString::from_vec(bytes.to_vec()));
-
-        let args = parse_low_raw([
-            OsStr::from_bytes(b"--file"),
-            OsStr::from_bytes(bytes),
-        ])
-        .unwrap();
-        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
-
-        let args = parse_low_raw([
-            OsStr::from_bytes(b"-f"),
-            OsStr::from_bytes(bytes),
-        ])
-        .unwrap();
-        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
-
-        let mut bytes = b"--file=A".to_vec();
-        bytes.push(0xFF);
-        bytes.push(b'Z');
-        let args = parse_low_raw([OsStr::from_bytes(&bytes)]).unwrap();
-        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
-
-        let mut bytes = b"-fA".to_vec();
-        bytes.push(0xFF);
-        bytes.push(b'Z');
-        let args = parse_low_raw([OsStr::from_bytes(&bytes)]).unwrap();
-        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
-    }
-}
-
-/// --files
+/// --signature
 #[derive(Debug)]
-struct Files;
-
-impl Flag for Files {
+struct Signature;
+impl Flag for Signature {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "files"
+        "signature"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("QUERY")
     }
     fn doc_category(&self) -> Category {
-        Category::OtherBehaviors
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Print each file that would be searched."
+        r"Find functions matching a parameter/return type shape."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Print each file that would be searched without actually performing the search.
-This is useful to determine whether a particular file is being searched or not.
-.sp
-This overrides \flag{type-list}.
+Find every function definition under the search paths whose parameter and
+return types match \fIQUERY\fP, a signature written like
+\fB(Path, &str) -> Result\fP. The number of parameters must match exactly,
+but each parameter and the return type match if \fIQUERY\fP's is a
+substring of the actual type, so \fBPath\fP matches \fB&Path\fP and
+\fBPathBuf\fP alike, and \fBResult\fP matches \fBResult<Vec<u8>, Error>\fP
+without spelling out its generic arguments. Omit the \fB-> Type\fP suffix
+to match any return type; use \fB()\fP to match functions with no
+parameters.
+.sp
+Only languages with typed-parameter extraction support report signatures;
+currently this is Rust. Combine with \fB--json\fP for editor integrations.
+See also \flag{symbol}, which matches by name instead of by shape.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch());
-        args.mode.update(Mode::Files);
+        args.signature = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_files() {
+fn test_signature() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
-
-    let args = parse_low_raw(["--files"]).unwrap();
-    assert_eq!(Mode::Files, args.mode);
+    assert_eq!(None, args.signature);
+    let args =
+        parse_low_raw(["--signature", "(Path, &str) -> Result"]).unwrap();
+    assert_eq!(Some("(Path, &str) -> Result".to_string()), args.signature);
 }
 
-/// -l/--files-with-matches
+/// --find-duplicates
 #[derive(Debug)]
-struct FilesWithMatches;
+struct FindDuplicates;
 
-impl Flag for FilesWithMatches {
+impl Flag for FindDuplicates {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'l')
-    }
     fn name_long(&self) -> &'static str {
-        "files-with-matches"
+        "find-duplicates"
     }
     fn doc_category(&self) -> Category {
-        Category::OutputModes
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Print the paths with at least one match."
+        r"Find near-duplicate functions using semantic embeddings."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Print only the paths with at least one match and suppress match contents.
+Find near-duplicate code by embedding every function-like symbol under the
+search paths and clustering the ones whose embeddings are similar, using
+the same embedding infrastructure as \flag{semantic}.
 .sp
-This overrides \flag{files-without-match}.
+Symbols are extracted with the same AST-based, one-chunk-per-symbol
+splitting that \flag{semantic-chunking} defaults to, so a duplicate is a
+function, method, or class whose meaning is similar, not necessarily its
+exact text (unlike literal copy-paste detection).
+.sp
+Two symbols are placed in the same cluster when their cosine similarity
+meets \flag{find-duplicates-threshold}; clusters are printed largest first,
+each entry showing the file, line range, and the symbol's first line.
+.sp
+This mode does not take a search pattern and does not print ordinary search
+results.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--files-with-matches can only be enabled");
-        args.mode.update(Mode::Search(SearchMode::FilesWithMatches));
+        assert!(v.unwrap_switch(), "--find-duplicates can only be enabled");
+        args.find_duplicates = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_files_with_matches() {
+fn test_find_duplicates() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
-
-    let args = parse_low_raw(["--files-with-matches"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
-
-    let args = parse_low_raw(["-l"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+    assert_eq!(false, args.find_duplicates);
+    let args = parse_low_raw(["--find-duplicates"]).unwrap();
+    assert_eq!(true, args.find_duplicates);
 }
 
-/// -l/--files-without-match
+/// --find-duplicates-threshold
 #[derive(Debug)]
-struct FilesWithoutMatch;
+struct FindDuplicatesThreshold;
 
-impl Flag for FilesWithoutMatch {
+impl Flag for FindDuplicatesThreshold {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "files-without-match"
+        "find-duplicates-threshold"
     }
     fn doc_category(&self) -> Category {
-        Category::OutputModes
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Print the paths that contain zero matches."
+        r"Minimum similarity for --find-duplicates clustering."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Print the paths that contain zero matches and suppress match contents.
+Specify the minimum cosine similarity (between 0.0 and 1.0) two symbols
+must share to be grouped into the same \flag{find-duplicates} cluster.
 .sp
-This overrides \flag{files-with-matches}.
+A higher threshold only groups near-identical symbols; a lower threshold
+also catches symbols that are merely similar in structure.
+.sp
+Default: 0.85
+.sp
+Example: --find-duplicates-threshold 0.9
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(
-            v.unwrap_switch(),
-            "--files-without-match can only be enabled"
+        let threshold = convert::str(&v.unwrap_value())?
+            .parse::<f32>()
+            .context(
+            "--find-duplicates-threshold must be a number between 0.0 and 1.0",
+        )?;
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&threshold),
+            "--find-duplicates-threshold must be between 0.0 and 1.0"
         );
-        args.mode.update(Mode::Search(SearchMode::FilesWithoutMatch));
+        args.find_duplicates_threshold = threshold;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_files_without_match() {
+fn test_find_duplicates_threshold() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
-
-    let args = parse_low_raw(["--files-without-match"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithoutMatch), args.mode);
+    assert_eq!(0.85, args.find_duplicates_threshold);
 
-    let args =
-        parse_low_raw(["--files-with-matches", "--files-without-match"])
-            .unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithoutMatch), args.mode);
+    let args = parse_low_raw(["--find-duplicates-threshold", "0.5"]).unwrap();
+    assert_eq!(0.5, args.find_duplicates_threshold);
 
-    let args =
-        parse_low_raw(["--files-without-match", "--files-with-matches"])
-            .unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+    let args = parse_low_raw(["--find-duplicates-threshold", "1.5"]);
+    assert!(args.is_err());
 }
 
-/// -F/--fixed-strings
+/// --symbol-kinds
 #[derive(Debug)]
-struct FixedStrings;
+struct SymbolKinds;
 
-impl Flag for FixedStrings {
+impl Flag for SymbolKinds {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'F')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "fixed-strings"
+        "symbol-kinds"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-fixed-strings")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("KIND")
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Treat all patterns as literals."
+        r"Restrict \flag{syntax} output to the given symbol kinds."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Treat all patterns as literals instead of as regular expressions. When this
-flag is used, special regular expression meta characters such as \fB.(){}*+\fP
-should not need be escaped.
+Restrict the symbol information shown by \flag{syntax} to the given kinds.
+\fIKIND\fP must be one of \fBfunctions\fP, \fBclasses\fP, \fBtypes\fP or
+\fBmodules\fP. This flag can be given multiple times to allow more than one
+kind.
+.sp
+This applies uniformly across languages: every language's AST extractor
+categorizes its symbols into the same four kinds, so \fB--symbol-kinds
+functions\fP shows only functions whether the file is Rust, Python, or
+JavaScript.
+.sp
+If this flag is never given, all kinds are shown.
+.sp
+Example: --syntax --symbol-kinds functions --symbol-kinds classes
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.fixed_strings = v.unwrap_switch();
+        args.symbol_kinds.push(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_fixed_strings() {
+fn test_symbol_kinds() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.fixed_strings);
-
-    let args = parse_low_raw(["--fixed-strings"]).unwrap();
-    assert_eq!(true, args.fixed_strings);
-
-    let args = parse_low_raw(["-F"]).unwrap();
-    assert_eq!(true, args.fixed_strings);
+    assert_eq!(Vec::<String>::new(), args.symbol_kinds);
 
-    let args = parse_low_raw(["-F", "--no-fixed-strings"]).unwrap();
-    assert_eq!(false, args.fixed_strings);
+    let args = parse_low_raw(["--symbol-kinds", "functions"]).unwrap();
+    assert_eq!(vec!["functions".to_string()], args.symbol_kinds);
 
-    let args = parse_low_raw(["--no-fixed-strings", "-F"]).unwrap();
-    assert_eq!(true, args.fixed_strings);
+    let args = parse_low_raw([
+        "--symbol-kinds",
+        "functions",
+        "--symbol-kinds",
+        "classes",
+    ])
+    .unwrap();
+    assert_eq!(
+        vec!["functions".to_string(), "classes".to_string()],
+        args.symbol_kinds
+    );
 }
 
-/// -L/--follow
+/// --ast-depth
 #[derive(Debug)]
-struct Follow;
+struct AstDepth;
 
-impl Flag for Follow {
+impl Flag for AstDepth {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'L')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "follow"
+        "ast-depth"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-follow")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Follow symbolic links."
+        r"Limit \flag{syntax} output to NUM levels of AST nesting."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag instructs ripgrep to follow symbolic links while traversing
-directories. This behavior is disabled by default. Note that ripgrep will
-check for symbolic link loops and report errors if it finds one. ripgrep will
-also report errors for broken links. To suppress error messages, use the
-\flag{no-messages} flag.
+Limit the AST structure shown by \flag{syntax} to at most \fINUM\fP levels
+of nesting, dropping any deeper descendants. Root nodes count as depth 0.
+.sp
+This is useful for large files where the full AST is impractically large
+to transmit or store, particularly with \fB--tree --syntax --json\fP.
+.sp
+If this flag is never given, the full tree is shown.
+.sp
+Example: --syntax --ast-depth 3
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.follow = v.unwrap_switch();
+        let depth = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("ast depth must be a non-negative integer")?;
+        args.ast_depth = Some(depth);
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_follow() {
+fn test_ast_depth() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.follow);
-
-    let args = parse_low_raw(["--follow"]).unwrap();
-    assert_eq!(true, args.follow);
-
-    let args = parse_low_raw(["-L"]).unwrap();
-    assert_eq!(true, args.follow);
-
-    let args = parse_low_raw(["-L", "--no-follow"]).unwrap();
-    assert_eq!(false, args.follow);
+    assert_eq!(None, args.ast_depth);
 
-    let args = parse_low_raw(["--no-follow", "-L"]).unwrap();
-    assert_eq!(true, args.follow);
+    let args = parse_low_raw(["--ast-depth", "3"]).unwrap();
+    assert_eq!(Some(3), args.ast_depth);
 }
 
-/// --generate
+/// --ast-max-nodes
 #[derive(Debug)]
-struct Generate;
+struct AstMaxNodes;
 
-impl Flag for Generate {
+impl Flag for AstMaxNodes {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "generate"
+        "ast-max-nodes"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("KIND")
+        Some("NUM")
     }
     fn doc_category(&self) -> Category {
-        Category::OtherBehaviors
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Generate man pages and completion scripts."
+        r"Limit \flag{syntax} output to at most NUM AST nodes."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag instructs ripgrep to generate some special kind of output identified
-by \fIKIND\fP and then quit without searching. \fIKIND\fP can be one of the
-following values:
+Limit the AST structure shown by \flag{syntax} to at most \fINUM\fP nodes
+in total, across the whole tree. Nodes beyond the limit (and their
+descendants) are dropped.
 .sp
-.TP 15
-\fBman\fP
-Generates a manual page for ripgrep in the \fBroff\fP format.
-.TP 15
-\fBcomplete\-bash\fP
-Generates a completion script for the \fBbash\fP shell.
-.TP 15
-\fBcomplete\-zsh\fP
-Generates a completion script for the \fBzsh\fP shell.
-.TP 15
-\fBcomplete\-fish\fP
-Generates a completion script for the \fBfish\fP shell.
-.TP 15
-\fBcomplete\-powershell\fP
-Generates a completion script for PowerShell.
-.PP
-The output is written to \fBstdout\fP. The list above may expand over time.
+This bounds the size of the AST payload independently of \flag{ast-depth},
+which bounds it by nesting level instead of by count. The two can be
+combined.
+.sp
+If this flag is never given, the node count is unbounded.
+.sp
+Example: --syntax --ast-max-nodes 500
 "
     }
-    fn doc_choices(&self) -> &'static [&'static str] {
-        &[
-            "man",
-            "complete-bash",
-            "complete-zsh",
-            "complete-fish",
-            "complete-powershell",
-        ]
-    }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let genmode = match convert::str(&v.unwrap_value())? {
-            "man" => GenerateMode::Man,
-            "complete-bash" => GenerateMode::CompleteBash,
-            "complete-zsh" => GenerateMode::CompleteZsh,
-            "complete-fish" => GenerateMode::CompleteFish,
-            "complete-powershell" => GenerateMode::CompletePowerShell,
-            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
-        };
-        args.mode.update(Mode::Generate(genmode));
+        let max_nodes = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("ast max nodes must be a non-negative integer")?;
+        args.ast_max_nodes = Some(max_nodes);
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_generate() {
+fn test_ast_max_nodes() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
-
-    let args = parse_low_raw(["--generate", "man"]).unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::Man), args.mode);
-
-    let args = parse_low_raw(["--generate", "complete-bash"]).unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::CompleteBash), args.mode);
-
-    let args = parse_low_raw(["--generate", "complete-zsh"]).unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::CompleteZsh), args.mode);
-
-    let args = parse_low_raw(["--generate", "complete-fish"]).unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::CompleteFish), args.mode);
-
-    let args = parse_low_raw(["--generate", "complete-powershell"]).unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::CompletePowerShell), args.mode);
-
-    let args =
-        parse_low_raw(["--generate", "complete-bash", "--generate=man"])
-            .unwrap();
-    assert_eq!(Mode::Generate(GenerateMode::Man), args.mode);
+    assert_eq!(None, args.ast_max_nodes);
 
-    let args = parse_low_raw(["--generate", "man", "-l"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
-
-    // An interesting quirk of how the modes override each other that lets
-    // you get back to the "default" mode of searching.
-    let args =
-        parse_low_raw(["--generate", "man", "--json", "--no-json"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+    let args = parse_low_raw(["--ast-max-nodes", "500"]).unwrap();
+    assert_eq!(Some(500), args.ast_max_nodes);
 }
 
-/// -g/--glob
+/// --ast-summary
 #[derive(Debug)]
-struct Glob;
-
-impl Flag for Glob {
+struct AstSummary;
+impl Flag for AstSummary {
     fn is_switch(&self) -> bool {
-        false
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'g')
+        true
     }
     fn name_long(&self) -> &'static str {
-        "glob"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("GLOB")
+        "ast-summary"
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Include or exclude file paths."
+        r"Replace \flag{syntax} node output with per-kind node counts."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-Include or exclude files and directories for searching that match the given
-glob. This always overrides any other ignore logic. Multiple glob flags may
-be used. Globbing rules match \fB.gitignore\fP globs. Precede a glob with a
-\fB!\fP to exclude it. If multiple globs match a file or directory, the glob
-given later in the command line takes precedence.
+        r"
+Instead of emitting the full AST node tree, summarize it as a count of
+nodes per node type (e.g. how many \fBfunction_declaration\fP nodes, how
+many \fBidentifier\fP nodes) plus a total node count.
 .sp
-As an extension, globs support specifying alternatives:
-.BI "\-g '" ab{c,d}* '
-is equivalent to
-.BI "\-g " "abc " "\-g " abd.
-Empty alternatives like
-.BI "\-g '" ab{,c} '
-are not currently supported. Note that this syntax extension is also currently
-enabled in \fBgitignore\fP files, even though this syntax isn't supported by
-git itself. ripgrep may disable this syntax extension in gitignore files, but
-it will always remain available via the \flag{glob} flag.
+This is the most compact way to get a sense of a file's shape through
+\flag{syntax} without paying for the full tree, and composes with
+\flag{symbol-kinds}, which still restricts the separate, always-present
+symbol summary.
 .sp
-When this flag is set, every file and directory is applied to it to test for
-a match. For example, if you only want to search in a particular directory
-\fIfoo\fP, then
-.BI "\-g " foo
-is incorrect because \fIfoo/bar\fP does not match
-the glob \fIfoo\fP. Instead, you should use
-.BI "\-g '" foo/** '.
-"#
+Example: --syntax --ast-summary --json
+"
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let glob = convert::string(v.unwrap_value())?;
-        args.globs.push(glob);
+        assert!(v.unwrap_switch(), "--ast-summary can only be enabled");
+        args.ast_summary = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_glob() {
+fn test_ast_summary() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<String>::new(), args.globs);
-
-    let args = parse_low_raw(["--glob", "foo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["--glob=foo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["-g", "foo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["-gfoo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["--glob", "-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["--glob=-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["-g", "-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.globs);
-
-    let args = parse_low_raw(["-g-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.globs);
+    assert_eq!(false, args.ast_summary);
+    let args = parse_low_raw(["--ast-summary"]).unwrap();
+    assert_eq!(true, args.ast_summary);
 }
 
-/// --glob-case-insensitive
+/// --with-docs
 #[derive(Debug)]
-struct GlobCaseInsensitive;
-
-impl Flag for GlobCaseInsensitive {
+struct WithDocs;
+impl Flag for WithDocs {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "glob-case-insensitive"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-glob-case-insensitive")
+        "with-docs"
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Process all glob patterns case insensitively."
+        r"Include leading doc comments with \flag{syntax} symbols."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Process all glob patterns given with the \flag{glob} flag case insensitively.
-This effectively treats \flag{glob} as \flag{iglob}.
+Include each symbol's leading doc comment block (a Rust \fB///\fP block, a
+JSDoc \fB/**\fP comment, a Python \fB#\fP comment, and so on) alongside its
+entry in the \flag{syntax} symbol summary and in enclosing-symbol context
+output (\flag{enclosing-symbol}).
+.sp
+Comment markers are stripped so the text reads the same regardless of
+language. Symbols without a leading comment are unaffected.
+.sp
+Example: --syntax --with-docs
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.glob_case_insensitive = v.unwrap_switch();
+        assert!(v.unwrap_switch(), "--with-docs can only be enabled");
+        args.with_docs = true;
         Ok(())
     }
 }
-
 #[cfg(test)]
 #[test]
-fn test_glob_case_insensitive() {
+fn test_with_docs() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.glob_case_insensitive);
-
-    let args = parse_low_raw(["--glob-case-insensitive"]).unwrap();
-    assert_eq!(true, args.glob_case_insensitive);
-
-    let args = parse_low_raw([
-        "--glob-case-insensitive",
-        "--no-glob-case-insensitive",
-    ])
-    .unwrap();
-    assert_eq!(false, args.glob_case_insensitive);
-
-    let args = parse_low_raw([
-        "--no-glob-case-insensitive",
-        "--glob-case-insensitive",
-    ])
-    .unwrap();
-    assert_eq!(true, args.glob_case_insensitive);
+    assert_eq!(false, args.with_docs);
+    let args = parse_low_raw(["--with-docs"]).unwrap();
+    assert_eq!(true, args.with_docs);
 }
 
-/// --heading
+/// --dfa-size-limit
 #[derive(Debug)]
-struct Heading;
+struct DfaSizeLimit;
 
-impl Flag for Heading {
+impl Flag for DfaSizeLimit {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "heading"
+        "dfa-size-limit"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-heading")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM+SUFFIX?")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Print matches grouped by each file."
+        r"The upper size limit of the regex DFA."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag prints the file path above clusters of matches from each file instead
-of printing the file path as a prefix for each matched line.
-.sp
-This is the default mode when printing to a tty.
+The upper size limit of the regex DFA. The default limit is something generous
+for any single pattern or for many smallish patterns. This should only be
+changed on very large regex inputs where the (slower) fallback regex engine may
+otherwise be used if the limit is reached.
 .sp
-When \fBstdout\fP is not a tty, then ripgrep will default to the standard
-grep-like format. One can force this format in Unix-like environments by
-piping the output of ripgrep to \fBcat\fP. For example, \fBrg\fP \fIfoo\fP \fB|
-cat\fP.
+The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
+correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
+provided the input is treated as bytes.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.heading = Some(v.unwrap_switch());
+        let v = v.unwrap_value();
+        args.dfa_size_limit = Some(convert::human_readable_usize(&v)?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_heading() {
+fn test_dfa_size_limit() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.heading);
+    assert_eq!(None, args.dfa_size_limit);
 
-    let args = parse_low_raw(["--heading"]).unwrap();
-    assert_eq!(Some(true), args.heading);
+    #[cfg(target_pointer_width = "64")]
+    {
+        let args = parse_low_raw(["--dfa-size-limit", "9G"]).unwrap();
+        assert_eq!(Some(9 * (1 << 30)), args.dfa_size_limit);
 
-    let args = parse_low_raw(["--no-heading"]).unwrap();
-    assert_eq!(Some(false), args.heading);
+        let args = parse_low_raw(["--dfa-size-limit=9G"]).unwrap();
+        assert_eq!(Some(9 * (1 << 30)), args.dfa_size_limit);
 
-    let args = parse_low_raw(["--heading", "--no-heading"]).unwrap();
-    assert_eq!(Some(false), args.heading);
+        let args =
+            parse_low_raw(["--dfa-size-limit=9G", "--dfa-size-limit=0"])
+                .unwrap();
+        assert_eq!(Some(0), args.dfa_size_limit);
+    }
 
-    let args = parse_low_raw(["--no-heading", "--heading"]).unwrap();
-    assert_eq!(Some(true), args.heading);
+    let args = parse_low_raw(["--dfa-size-limit=0K"]).unwrap();
+    assert_eq!(Some(0), args.dfa_size_limit);
+
+    let args = parse_low_raw(["--dfa-size-limit=0M"]).unwrap();
+    assert_eq!(Some(0), args.dfa_size_limit);
+
+    let args = parse_low_raw(["--dfa-size-limit=0G"]).unwrap();
+    assert_eq!(Some(0), args.dfa_size_limit);
+
+    let result = parse_low_raw(["--dfa-size-limit", "9999999999999999999999"]);
+    assert!(result.is_err(), "{result:?}");
+
+    let result = parse_low_raw(["--dfa-size-limit", "9999999999999999G"]);
+    assert!(result.is_err(), "{result:?}");
 }
 
-/// -h/--help
+/// -E/--encoding
 #[derive(Debug)]
-struct Help;
+struct Encoding;
 
-impl Flag for Help {
+impl Flag for Encoding {
     fn is_switch(&self) -> bool {
-        true
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'E')
     }
     fn name_long(&self) -> &'static str {
-        "help"
+        "encoding"
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'h')
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-encoding")
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("ENCODING")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Show help output."
+        r"Specify the text encoding of files to search."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag prints the help output for ripgrep.
+Specify the text encoding that ripgrep will use on all files searched. The
+default value is \fBauto\fP, which will cause ripgrep to do a best effort
+automatic detection of encoding on a per-file basis. Automatic detection in
+this case only applies to files that begin with a UTF-8 or UTF-16 byte-order
+mark (BOM). No other automatic detection is performed. One can also specify
+\fBnone\fP which will then completely disable BOM sniffing and always result
+in searching the raw bytes, including a BOM if it's present, regardless of its
+encoding.
 .sp
-Unlike most other flags, the behavior of the short flag, \fB\-h\fP, and the
-long flag, \fB\-\-help\fP, is different. The short flag will show a condensed
-help output while the long flag will show a verbose help output. The verbose
-help output has complete documentation, where as the condensed help output will
-show only a single line for every flag.
+Other supported values can be found in the list of labels here:
+\fIhttps://encoding.spec.whatwg.org/#concept-encoding-get\fP.
+.sp
+For more details on encoding and how ripgrep deals with it, see \fBGUIDE.md\fP.
+.sp
+The encoding detection that ripgrep uses can be reverted to its automatic mode
+via the \flag-negate{encoding} flag.
 "
     }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Encoding
+    }
 
-    fn update(&self, v: FlagValue, _: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--help has no negation");
-        // Since this flag has different semantics for -h and --help and the
-        // Flag trait doesn't support encoding this sort of thing, we handle it
-        // as a special case in the parser.
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let value = match v {
+            FlagValue::Value(v) => v,
+            FlagValue::Switch(true) => {
+                unreachable!("--encoding must accept a value")
+            }
+            FlagValue::Switch(false) => {
+                args.encoding = EncodingMode::Auto;
+                return Ok(());
+            }
+        };
+        let label = convert::str(&value)?;
+        args.encoding = match label {
+            "auto" => EncodingMode::Auto,
+            "none" => EncodingMode::Disabled,
+            _ => EncodingMode::Some(grep::searcher::Encoding::new(label)?),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_help() {
+fn test_encoding() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.special);
+    assert_eq!(EncodingMode::Auto, args.encoding);
 
-    let args = parse_low_raw(["-h"]).unwrap();
-    assert_eq!(Some(SpecialMode::HelpShort), args.special);
+    let args = parse_low_raw(["--encoding", "auto"]).unwrap();
+    assert_eq!(EncodingMode::Auto, args.encoding);
 
-    let args = parse_low_raw(["--help"]).unwrap();
-    assert_eq!(Some(SpecialMode::HelpLong), args.special);
+    let args = parse_low_raw(["--encoding", "none"]).unwrap();
+    assert_eq!(EncodingMode::Disabled, args.encoding);
 
-    let args = parse_low_raw(["-h", "--help"]).unwrap();
-    assert_eq!(Some(SpecialMode::HelpLong), args.special);
+    let args = parse_low_raw(["--encoding=none"]).unwrap();
+    assert_eq!(EncodingMode::Disabled, args.encoding);
 
-    let args = parse_low_raw(["--help", "-h"]).unwrap();
-    assert_eq!(Some(SpecialMode::HelpShort), args.special);
+    let args = parse_low_raw(["-E", "none"]).unwrap();
+    assert_eq!(EncodingMode::Disabled, args.encoding);
+
+    let args = parse_low_raw(["-Enone"]).unwrap();
+    assert_eq!(EncodingMode::Disabled, args.encoding);
+
+    let args = parse_low_raw(["-E", "none", "--no-encoding"]).unwrap();
+    assert_eq!(EncodingMode::Auto, args.encoding);
+
+    let args = parse_low_raw(["--no-encoding", "-E", "none"]).unwrap();
+    assert_eq!(EncodingMode::Disabled, args.encoding);
+
+    let args = parse_low_raw(["-E", "utf-16"]).unwrap();
+    let enc = grep::searcher::Encoding::new("utf-16").unwrap();
+    assert_eq!(EncodingMode::Some(enc), args.encoding);
+
+    let args = parse_low_raw(["-E", "utf-16", "--no-encoding"]).unwrap();
+    assert_eq!(EncodingMode::Auto, args.encoding);
+
+    let result = parse_low_raw(["-E", "foo"]);
+    assert!(result.is_err(), "{result:?}");
 }
 
-/// -./--hidden
+/// --engine
 #[derive(Debug)]
-struct Hidden;
+struct Engine;
 
-impl Flag for Hidden {
+impl Flag for Engine {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'.')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "hidden"
+        "engine"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-hidden")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("ENGINE")
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Search hidden files and directories."
+        r"Specify which regex engine to use."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-Search hidden files and directories. By default, hidden files and directories
-are skipped. Note that if a hidden file or a directory is whitelisted in
-an ignore file, then it will be searched even if this flag isn't provided.
-Similarly if a hidden file or directory is given explicitly as an argument to
-ripgrep.
+        r"
+Specify which regular expression engine to use. When you choose a regex engine,
+it applies that choice for every regex provided to ripgrep (e.g., via multiple
+\flag{regexp} or \flag{file} flags).
 .sp
-A file or directory is considered hidden if its base name starts with a dot
-character (\fB.\fP). On operating systems which support a "hidden" file
-attribute, like Windows, files with this attribute are also considered hidden.
-"#
+Accepted values are \fBdefault\fP, \fBpcre2\fP, or \fBauto\fP.
+.sp
+The default value is \fBdefault\fP, which is usually the fastest and should be
+good for most use cases. The \fBpcre2\fP engine is generally useful when you
+want to use features such as look-around or backreferences. \fBauto\fP will
+dynamically choose between supported regex engines depending on the features
+used in a pattern on a best effort basis.
+.sp
+Note that the \fBpcre2\fP engine is an optional ripgrep feature. If PCRE2
+wasn't included in your build of ripgrep, then using this flag will result in
+ripgrep printing an error message and exiting.
+.sp
+This overrides previous uses of the \flag{pcre2} and \flag{auto-hybrid-regex}
+flags.
+"
+    }
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["default", "pcre2", "auto"]
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.hidden = v.unwrap_switch();
+        let v = v.unwrap_value();
+        let string = convert::str(&v)?;
+        args.engine = match string {
+            "default" => EngineChoice::Default,
+            "pcre2" => EngineChoice::PCRE2,
+            "auto" => EngineChoice::Auto,
+            _ => anyhow::bail!("unrecognized regex engine '{string}'"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_hidden() {
+fn test_engine() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.hidden);
+    assert_eq!(EngineChoice::Default, args.engine);
 
-    let args = parse_low_raw(["--hidden"]).unwrap();
-    assert_eq!(true, args.hidden);
+    let args = parse_low_raw(["--engine", "pcre2"]).unwrap();
+    assert_eq!(EngineChoice::PCRE2, args.engine);
 
-    let args = parse_low_raw(["-."]).unwrap();
-    assert_eq!(true, args.hidden);
+    let args = parse_low_raw(["--engine=pcre2"]).unwrap();
+    assert_eq!(EngineChoice::PCRE2, args.engine);
 
-    let args = parse_low_raw(["-.", "--no-hidden"]).unwrap();
-    assert_eq!(false, args.hidden);
+    let args =
+        parse_low_raw(["--auto-hybrid-regex", "--engine=pcre2"]).unwrap();
+    assert_eq!(EngineChoice::PCRE2, args.engine);
 
-    let args = parse_low_raw(["--no-hidden", "-."]).unwrap();
-    assert_eq!(true, args.hidden);
+    let args =
+        parse_low_raw(["--engine=pcre2", "--auto-hybrid-regex"]).unwrap();
+    assert_eq!(EngineChoice::Auto, args.engine);
+
+    let args =
+        parse_low_raw(["--auto-hybrid-regex", "--engine=auto"]).unwrap();
+    assert_eq!(EngineChoice::Auto, args.engine);
+
+    let args =
+        parse_low_raw(["--auto-hybrid-regex", "--engine=default"]).unwrap();
+    assert_eq!(EngineChoice::Default, args.engine);
+
+    let args =
+        parse_low_raw(["--engine=pcre2", "--no-auto-hybrid-regex"]).unwrap();
+    assert_eq!(EngineChoice::Default, args.engine);
 }
 
-/// --hostname-bin
+/// --field-context-separator
 #[derive(Debug)]
-struct HostnameBin;
+struct FieldContextSeparator;
 
-impl Flag for HostnameBin {
+impl Flag for FieldContextSeparator {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "hostname-bin"
+        "field-context-separator"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("COMMAND")
+        Some("SEPARATOR")
     }
     fn doc_category(&self) -> Category {
         Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Run a program to get this system's hostname."
+        r"Set the field context separator."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-This flag controls how ripgrep determines this system's hostname. The flag's
-value should correspond to an executable (either a path or something that can
-be found via your system's \fBPATH\fP environment variable). When set, ripgrep
-will run this executable, with no arguments, and treat its output (with leading
-and trailing whitespace stripped) as your system's hostname.
-.sp
-When not set (the default, or the empty string), ripgrep will try to
-automatically detect your system's hostname. On Unix, this corresponds
-to calling \fBgethostname\fP. On Windows, this corresponds to calling
-\fBGetComputerNameExW\fP to fetch the system's "physical DNS hostname."
+        r"
+Set the field context separator. This separator is only used when printing
+contextual lines. It is used to delimit file paths, line numbers, columns and
+the contextual line itself. The separator may be any number of bytes, including
+zero. Escape sequences like \fB\\x7F\fP or \fB\\t\fP may be used.
 .sp
-ripgrep uses your system's hostname for producing hyperlinks.
-"#
-    }
-    fn completion_type(&self) -> CompletionType {
-        CompletionType::Executable
+The \fB-\fP character is the default value.
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let path = PathBuf::from(v.unwrap_value());
-        args.hostname_bin =
-            if path.as_os_str().is_empty() { None } else { Some(path) };
+        use crate::flags::lowargs::FieldContextSeparator as Separator;
+
+        args.field_context_separator = Separator::new(&v.unwrap_value())?;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_hostname_bin() {
+fn test_field_context_separator() {
+    use bstr::BString;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.hostname_bin);
+    assert_eq!(BString::from("-"), args.field_context_separator.into_bytes());
 
-    let args = parse_low_raw(["--hostname-bin", "foo"]).unwrap();
-    assert_eq!(Some(PathBuf::from("foo")), args.hostname_bin);
+    let args = parse_low_raw(["--field-context-separator", "XYZ"]).unwrap();
+    assert_eq!(
+        BString::from("XYZ"),
+        args.field_context_separator.into_bytes()
+    );
 
-    let args = parse_low_raw(["--hostname-bin=foo"]).unwrap();
-    assert_eq!(Some(PathBuf::from("foo")), args.hostname_bin);
-}
+    let args = parse_low_raw(["--field-context-separator=XYZ"]).unwrap();
+    assert_eq!(
+        BString::from("XYZ"),
+        args.field_context_separator.into_bytes()
+    );
+
+    let args = parse_low_raw([
+        "--field-context-separator",
+        "XYZ",
+        "--field-context-separator",
+        "ABC",
+    ])
+    .unwrap();
+    assert_eq!(
+        BString::from("ABC"),
+        args.field_context_separator.into_bytes()
+    );
+
+    let args = parse_low_raw(["--field-context-separator", r"\t"]).unwrap();
+    assert_eq!(BString::from("\t"), args.field_context_separator.into_bytes());
+
+    let args = parse_low_raw(["--field-context-separator", r"\x00"]).unwrap();
+    assert_eq!(
+        BString::from("\x00"),
+        args.field_context_separator.into_bytes()
+    );
+
+    // This checks that invalid UTF-8 can be used. This case isn't too tricky
+    // to handle, because it passes the invalid UTF-8 as an escape sequence
+    // that is itself valid UTF-8. It doesn't become invalid UTF-8 until after
+    // the argument is parsed and then unescaped.
+    let args = parse_low_raw(["--field-context-separator", r"\xFF"]).unwrap();
+    assert_eq!(
+        BString::from(b"\xFF"),
+        args.field_context_separator.into_bytes()
+    );
+
+    // In this case, we specifically try to pass an invalid UTF-8 argument to
+    // the flag. In theory we might be able to support this, but because we do
+    // unescaping and because unescaping wants valid UTF-8, we do a UTF-8 check
+    // on the value. Since we pass invalid UTF-8, it fails. This demonstrates
+    // that the only way to use an invalid UTF-8 separator is by specifying an
+    // escape sequence that is itself valid UTF-8.
+    #[cfg(unix)]
+    {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let result = parse_low_raw([
+            OsStr::from_bytes(b"--field-context-separator"),
+            OsStr::from_bytes(&[0xFF]),
+        ]);
+        assert!(result.is_err(), "{result:?}");
+    }
+}
+
+/// --field-match-separator
+#[derive(Debug)]
+struct FieldMatchSeparator;
+
+impl Flag for FieldMatchSeparator {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "field-match-separator"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("SEPARATOR")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Set the field match separator."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Set the field match separator. This separator is only used when printing
+matching lines. It is used to delimit file paths, line numbers, columns and the
+matching line itself. The separator may be any number of bytes, including zero.
+Escape sequences like \fB\\x7F\fP or \fB\\t\fP may be used.
+.sp
+The \fB:\fP character is the default value.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        use crate::flags::lowargs::FieldMatchSeparator as Separator;
+
+        args.field_match_separator = Separator::new(&v.unwrap_value())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_field_match_separator() {
+    use bstr::BString;
+
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(BString::from(":"), args.field_match_separator.into_bytes());
+
+    let args = parse_low_raw(["--field-match-separator", "XYZ"]).unwrap();
+    assert_eq!(BString::from("XYZ"), args.field_match_separator.into_bytes());
+
+    let args = parse_low_raw(["--field-match-separator=XYZ"]).unwrap();
+    assert_eq!(BString::from("XYZ"), args.field_match_separator.into_bytes());
+
+    let args = parse_low_raw([
+        "--field-match-separator",
+        "XYZ",
+        "--field-match-separator",
+        "ABC",
+    ])
+    .unwrap();
+    assert_eq!(BString::from("ABC"), args.field_match_separator.into_bytes());
+
+    let args = parse_low_raw(["--field-match-separator", r"\t"]).unwrap();
+    assert_eq!(BString::from("\t"), args.field_match_separator.into_bytes());
+
+    let args = parse_low_raw(["--field-match-separator", r"\x00"]).unwrap();
+    assert_eq!(BString::from("\x00"), args.field_match_separator.into_bytes());
+
+    // This checks that invalid UTF-8 can be used. This case isn't too tricky
+    // to handle, because it passes the invalid UTF-8 as an escape sequence
+    // that is itself valid UTF-8. It doesn't become invalid UTF-8 until after
+    // the argument is parsed and then unescaped.
+    let args = parse_low_raw(["--field-match-separator", r"\xFF"]).unwrap();
+    assert_eq!(
+        BString::from(b"\xFF"),
+        args.field_match_separator.into_bytes()
+    );
+
+    // In this case, we specifically try to pass an invalid UTF-8 argument to
+    // the flag. In theory we might be able to support this, but because we do
+    // unescaping and because unescaping wants valid UTF-8, we do a UTF-8 check
+    // on the value. Since we pass invalid UTF-8, it fails. This demonstrates
+    // that the only way to use an invalid UTF-8 separator is by specifying an
+    // escape sequence that is itself valid UTF-8.
+    #[cfg(unix)]
+    {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let result = parse_low_raw([
+            OsStr::from_bytes(b"--field-match-separator"),
+            OsStr::from_bytes(&[0xFF]),
+        ]);
+        assert!(result.is_err(), "{result:?}");
+    }
+}
+
+/// -f/--file
+#[derive(Debug)]
+struct File;
+
+impl Flag for File {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'f')
+    }
+    fn name_long(&self) -> &'static str {
+        "file"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATTERNFILE")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Input
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Search for patterns from the given file."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Search for patterns from the given file, with one pattern per line. When this
+flag is used multiple times or in combination with the \flag{regexp} flag, then
+all patterns provided are searched. Empty pattern lines will match all input
+lines, and the newline is not counted as part of the pattern.
+.sp
+A line is printed if and only if it matches at least one of the patterns.
+.sp
+When \fIPATTERNFILE\fP is \fB-\fP, then \fBstdin\fP will be read for the
+patterns.
+.sp
+When \flag{file} or \flag{regexp} is used, then ripgrep treats all positional
+arguments as files or directories to search.
+"
+    }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Filename
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let path = PathBuf::from(v.unwrap_value());
+        args.patterns.push(PatternSource::File(path));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_file() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<PatternSource>::new(), args.patterns);
+
+    let args = parse_low_raw(["--file", "foo"]).unwrap();
+    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
+
+    let args = parse_low_raw(["--file=foo"]).unwrap();
+    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
+
+    let args = parse_low_raw(["-f", "foo"]).unwrap();
+    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
+
+    let args = parse_low_raw(["-ffoo"]).unwrap();
+    assert_eq!(vec![PatternSource::File(PathBuf::from("foo"))], args.patterns);
+
+    let args = parse_low_raw(["--file", "-foo"]).unwrap();
+    assert_eq!(
+        vec![PatternSource::File(PathBuf::from("-foo"))],
+        args.patterns
+    );
+
+    let args = parse_low_raw(["--file=-foo"]).unwrap();
+    assert_eq!(
+        vec![PatternSource::File(PathBuf::from("-foo"))],
+        args.patterns
+    );
+
+    let args = parse_low_raw(["-f", "-foo"]).unwrap();
+    assert_eq!(
+        vec![PatternSource::File(PathBuf::from("-foo"))],
+        args.patterns
+    );
+
+    let args = parse_low_raw(["-f-foo"]).unwrap();
+    assert_eq!(
+        vec![PatternSource::File(PathBuf::from("-foo"))],
+        args.patterns
+    );
+
+    let args = parse_low_raw(["--file=foo", "--file", "bar"]).unwrap();
+    assert_eq!(
+        vec![
+            PatternSource::File(PathBuf::from("foo")),
+            PatternSource::File(PathBuf::from("bar"))
+        ],
+        args.patterns
+    );
+
+    // We permit path arguments to be invalid UTF-8. So test that. Some of
+    // these cases are tricky and depend on lexopt doing the right thing.
+    //
+    // We probably should add tests for this handling on Windows too, but paths
+    // that are invalid UTF-16 appear incredibly rare in the Windows world.
+    #[cfg(unix)]
+    {
+        use std::{
+            ffi::{OsStr, OsString},
+            os::unix::ffi::{OsStrExt, OsStringExt},
+        };
+
+        let bytes = &[b'A', 0xFF, b'Z'][..];
+        let path = PathBuf::from(OsString::from_vec(bytes.to_vec()));
+
+        let args = parse_low_raw([
+            OsStr::from_bytes(b"--file"),
+            OsStr::from_bytes(bytes),
+        ])
+        .unwrap();
+        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
+
+        let args = parse_low_raw([
+            OsStr::from_bytes(b"-f"),
+            OsStr::from_bytes(bytes),
+        ])
+        .unwrap();
+        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
+
+        let mut bytes = b"--file=A".to_vec();
+        bytes.push(0xFF);
+        bytes.push(b'Z');
+        let args = parse_low_raw([OsStr::from_bytes(&bytes)]).unwrap();
+        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
+
+        let mut bytes = b"-fA".to_vec();
+        bytes.push(0xFF);
+        bytes.push(b'Z');
+        let args = parse_low_raw([OsStr::from_bytes(&bytes)]).unwrap();
+        assert_eq!(vec![PatternSource::File(path.clone())], args.patterns);
+    }
+}
+
+/// --files
+#[derive(Debug)]
+struct Files;
+
+impl Flag for Files {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "files"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print each file that would be searched."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print each file that would be searched without actually performing the search.
+This is useful to determine whether a particular file is being searched or not.
+.sp
+This overrides \flag{type-list}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch());
+        args.mode.update(Mode::Files);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_files() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--files"]).unwrap();
+    assert_eq!(Mode::Files, args.mode);
+}
+
+/// -l/--files-with-matches
+#[derive(Debug)]
+struct FilesWithMatches;
+
+impl Flag for FilesWithMatches {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'l')
+    }
+    fn name_long(&self) -> &'static str {
+        "files-with-matches"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OutputModes
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print the paths with at least one match."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print only the paths with at least one match and suppress match contents.
+.sp
+This overrides \flag{files-without-match}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--files-with-matches can only be enabled");
+        args.mode.update(Mode::Search(SearchMode::FilesWithMatches));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_files_with_matches() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--files-with-matches"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+
+    let args = parse_low_raw(["-l"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+}
+
+/// -l/--files-without-match
+#[derive(Debug)]
+struct FilesWithoutMatch;
+
+impl Flag for FilesWithoutMatch {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "files-without-match"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OutputModes
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print the paths that contain zero matches."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print the paths that contain zero matches and suppress match contents.
+.sp
+This overrides \flag{files-with-matches}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(
+            v.unwrap_switch(),
+            "--files-without-match can only be enabled"
+        );
+        args.mode.update(Mode::Search(SearchMode::FilesWithoutMatch));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_files_without_match() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--files-without-match"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithoutMatch), args.mode);
+
+    let args =
+        parse_low_raw(["--files-with-matches", "--files-without-match"])
+            .unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithoutMatch), args.mode);
+
+    let args =
+        parse_low_raw(["--files-without-match", "--files-with-matches"])
+            .unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+}
+
+/// -F/--fixed-strings
+#[derive(Debug)]
+struct FixedStrings;
+
+impl Flag for FixedStrings {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'F')
+    }
+    fn name_long(&self) -> &'static str {
+        "fixed-strings"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-fixed-strings")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Treat all patterns as literals."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Treat all patterns as literals instead of as regular expressions. When this
+flag is used, special regular expression meta characters such as \fB.(){}*+\fP
+should not need be escaped.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.fixed_strings = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_fixed_strings() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.fixed_strings);
+
+    let args = parse_low_raw(["--fixed-strings"]).unwrap();
+    assert_eq!(true, args.fixed_strings);
+
+    let args = parse_low_raw(["-F"]).unwrap();
+    assert_eq!(true, args.fixed_strings);
+
+    let args = parse_low_raw(["-F", "--no-fixed-strings"]).unwrap();
+    assert_eq!(false, args.fixed_strings);
+
+    let args = parse_low_raw(["--no-fixed-strings", "-F"]).unwrap();
+    assert_eq!(true, args.fixed_strings);
+}
+
+/// -L/--follow
+#[derive(Debug)]
+struct Follow;
+
+impl Flag for Follow {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'L')
+    }
+    fn name_long(&self) -> &'static str {
+        "follow"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-follow")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Follow symbolic links."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag instructs ripgrep to follow symbolic links while traversing
+directories. This behavior is disabled by default. Note that ripgrep will
+check for symbolic link loops and report errors if it finds one. ripgrep will
+also report errors for broken links. To suppress error messages, use the
+\flag{no-messages} flag.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.follow = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_follow() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.follow);
+
+    let args = parse_low_raw(["--follow"]).unwrap();
+    assert_eq!(true, args.follow);
+
+    let args = parse_low_raw(["-L"]).unwrap();
+    assert_eq!(true, args.follow);
+
+    let args = parse_low_raw(["-L", "--no-follow"]).unwrap();
+    assert_eq!(false, args.follow);
+
+    let args = parse_low_raw(["--no-follow", "-L"]).unwrap();
+    assert_eq!(true, args.follow);
+}
+
+/// --generate
+#[derive(Debug)]
+struct Generate;
+
+impl Flag for Generate {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "generate"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("KIND")
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Generate man pages and completion scripts."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag instructs ripgrep to generate some special kind of output identified
+by \fIKIND\fP and then quit without searching. \fIKIND\fP can be one of the
+following values:
+.sp
+.TP 15
+\fBman\fP
+Generates a manual page for ripgrep in the \fBroff\fP format.
+.TP 15
+\fBcomplete\-bash\fP
+Generates a completion script for the \fBbash\fP shell.
+.TP 15
+\fBcomplete\-zsh\fP
+Generates a completion script for the \fBzsh\fP shell.
+.TP 15
+\fBcomplete\-fish\fP
+Generates a completion script for the \fBfish\fP shell.
+.TP 15
+\fBcomplete\-powershell\fP
+Generates a completion script for PowerShell.
+.PP
+The output is written to \fBstdout\fP. The list above may expand over time.
+"
+    }
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &[
+            "man",
+            "complete-bash",
+            "complete-zsh",
+            "complete-fish",
+            "complete-powershell",
+        ]
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let genmode = match convert::str(&v.unwrap_value())? {
+            "man" => GenerateMode::Man,
+            "complete-bash" => GenerateMode::CompleteBash,
+            "complete-zsh" => GenerateMode::CompleteZsh,
+            "complete-fish" => GenerateMode::CompleteFish,
+            "complete-powershell" => GenerateMode::CompletePowerShell,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
+        args.mode.update(Mode::Generate(genmode));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_generate() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--generate", "man"]).unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::Man), args.mode);
+
+    let args = parse_low_raw(["--generate", "complete-bash"]).unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::CompleteBash), args.mode);
+
+    let args = parse_low_raw(["--generate", "complete-zsh"]).unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::CompleteZsh), args.mode);
+
+    let args = parse_low_raw(["--generate", "complete-fish"]).unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::CompleteFish), args.mode);
+
+    let args = parse_low_raw(["--generate", "complete-powershell"]).unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::CompletePowerShell), args.mode);
+
+    let args =
+        parse_low_raw(["--generate", "complete-bash", "--generate=man"])
+            .unwrap();
+    assert_eq!(Mode::Generate(GenerateMode::Man), args.mode);
+
+    let args = parse_low_raw(["--generate", "man", "-l"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+
+    // An interesting quirk of how the modes override each other that lets
+    // you get back to the "default" mode of searching.
+    let args =
+        parse_low_raw(["--generate", "man", "--json", "--no-json"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+}
+
+/// -g/--glob
+#[derive(Debug)]
+struct Glob;
+
+impl Flag for Glob {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'g')
+    }
+    fn name_long(&self) -> &'static str {
+        "glob"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("GLOB")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Include or exclude file paths."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+Include or exclude files and directories for searching that match the given
+glob. This always overrides any other ignore logic. Multiple glob flags may
+be used. Globbing rules match \fB.gitignore\fP globs. Precede a glob with a
+\fB!\fP to exclude it. If multiple globs match a file or directory, the glob
+given later in the command line takes precedence.
+.sp
+As an extension, globs support specifying alternatives:
+.BI "\-g '" ab{c,d}* '
+is equivalent to
+.BI "\-g " "abc " "\-g " abd.
+Empty alternatives like
+.BI "\-g '" ab{,c} '
+are not currently supported. Note that this syntax extension is also currently
+enabled in \fBgitignore\fP files, even though this syntax isn't supported by
+git itself. ripgrep may disable this syntax extension in gitignore files, but
+it will always remain available via the \flag{glob} flag.
+.sp
+When this flag is set, every file and directory is applied to it to test for
+a match. For example, if you only want to search in a particular directory
+\fIfoo\fP, then
+.BI "\-g " foo
+is incorrect because \fIfoo/bar\fP does not match
+the glob \fIfoo\fP. Instead, you should use
+.BI "\-g '" foo/** '.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let glob = convert::string(v.unwrap_value())?;
+        args.globs.push(glob);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_glob() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.globs);
+
+    let args = parse_low_raw(["--glob", "foo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["--glob=foo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["-g", "foo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["-gfoo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["--glob", "-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["--glob=-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["-g", "-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.globs);
+
+    let args = parse_low_raw(["-g-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.globs);
+}
+
+/// --glob-case-insensitive
+#[derive(Debug)]
+struct GlobCaseInsensitive;
+
+impl Flag for GlobCaseInsensitive {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "glob-case-insensitive"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-glob-case-insensitive")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Process all glob patterns case insensitively."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Process all glob patterns given with the \flag{glob} flag case insensitively.
+This effectively treats \flag{glob} as \flag{iglob}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.glob_case_insensitive = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_glob_case_insensitive() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.glob_case_insensitive);
+
+    let args = parse_low_raw(["--glob-case-insensitive"]).unwrap();
+    assert_eq!(true, args.glob_case_insensitive);
+
+    let args = parse_low_raw([
+        "--glob-case-insensitive",
+        "--no-glob-case-insensitive",
+    ])
+    .unwrap();
+    assert_eq!(false, args.glob_case_insensitive);
+
+    let args = parse_low_raw([
+        "--no-glob-case-insensitive",
+        "--glob-case-insensitive",
+    ])
+    .unwrap();
+    assert_eq!(true, args.glob_case_insensitive);
+}
+
+/// --heading
+#[derive(Debug)]
+struct Heading;
+
+impl Flag for Heading {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "heading"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-heading")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print matches grouped by each file."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag prints the file path above clusters of matches from each file instead
+of printing the file path as a prefix for each matched line.
+.sp
+This is the default mode when printing to a tty.
+.sp
+When \fBstdout\fP is not a tty, then ripgrep will default to the standard
+grep-like format. One can force this format in Unix-like environments by
+piping the output of ripgrep to \fBcat\fP. For example, \fBrg\fP \fIfoo\fP \fB|
+cat\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.heading = Some(v.unwrap_switch());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_heading() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.heading);
+
+    let args = parse_low_raw(["--heading"]).unwrap();
+    assert_eq!(Some(true), args.heading);
+
+    let args = parse_low_raw(["--no-heading"]).unwrap();
+    assert_eq!(Some(false), args.heading);
+
+    let args = parse_low_raw(["--heading", "--no-heading"]).unwrap();
+    assert_eq!(Some(false), args.heading);
+
+    let args = parse_low_raw(["--no-heading", "--heading"]).unwrap();
+    assert_eq!(Some(true), args.heading);
+}
+
+/// -h/--help
+#[derive(Debug)]
+struct Help;
+
+impl Flag for Help {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "help"
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'h')
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show help output."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag prints the help output for ripgrep.
+.sp
+Unlike most other flags, the behavior of the short flag, \fB\-h\fP, and the
+long flag, \fB\-\-help\fP, is different. The short flag will show a condensed
+help output while the long flag will show a verbose help output. The verbose
+help output has complete documentation, where as the condensed help output will
+show only a single line for every flag.
+"
+    }
+
+    fn update(&self, v: FlagValue, _: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--help has no negation");
+        // Since this flag has different semantics for -h and --help and the
+        // Flag trait doesn't support encoding this sort of thing, we handle it
+        // as a special case in the parser.
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_help() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.special);
+
+    let args = parse_low_raw(["-h"]).unwrap();
+    assert_eq!(Some(SpecialMode::HelpShort), args.special);
+
+    let args = parse_low_raw(["--help"]).unwrap();
+    assert_eq!(Some(SpecialMode::HelpLong), args.special);
+
+    let args = parse_low_raw(["-h", "--help"]).unwrap();
+    assert_eq!(Some(SpecialMode::HelpLong), args.special);
+
+    let args = parse_low_raw(["--help", "-h"]).unwrap();
+    assert_eq!(Some(SpecialMode::HelpShort), args.special);
+}
+
+/// --hex
+#[derive(Debug)]
+struct HexDump;
+
+impl Flag for HexDump {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "hex"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-hex")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        "Render binary matches as a hex+ASCII dump."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When a match is found in a binary file (see \flag{binary}), render a
+hex+ASCII dump of the bytes around the match instead of the raw, usually
+unprintable, bytes on the line it falls in.
+.sp
+The size of the window shown around each match is controlled by
+\flag{hex-context}.
+.sp
+This flag has no effect on matches found in files that ripgrep considers
+text.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.hex = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_hex() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.hex);
+
+    let args = parse_low_raw(["--hex"]).unwrap();
+    assert_eq!(true, args.hex);
+
+    let args = parse_low_raw(["--hex", "--no-hex"]).unwrap();
+    assert_eq!(false, args.hex);
+}
+
+/// --hex-context
+#[derive(Debug)]
+struct HexDumpContext;
+
+impl Flag for HexDumpContext {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "hex-context"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        "Set the number of context bytes shown in a --hex dump."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Set the number of bytes of context shown before and after each match in a
+\flag{hex} dump. The window is rounded outward to whole 16-byte rows.
+.sp
+This defaults to 32.
+.sp
+This flag has no effect unless \flag{hex} is also given.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.hex_context = convert::usize(&v.unwrap_value())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_hex_context() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(32, args.hex_context);
+
+    let args = parse_low_raw(["--hex-context", "8"]).unwrap();
+    assert_eq!(8, args.hex_context);
+
+    let args = parse_low_raw(["--hex-context=64"]).unwrap();
+    assert_eq!(64, args.hex_context);
+}
+
+/// -./--hidden
+#[derive(Debug)]
+struct Hidden;
+
+impl Flag for Hidden {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'.')
+    }
+    fn name_long(&self) -> &'static str {
+        "hidden"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-hidden")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Search hidden files and directories."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+Search hidden files and directories. By default, hidden files and directories
+are skipped. Note that if a hidden file or a directory is whitelisted in
+an ignore file, then it will be searched even if this flag isn't provided.
+Similarly if a hidden file or directory is given explicitly as an argument to
+ripgrep.
+.sp
+A file or directory is considered hidden if its base name starts with a dot
+character (\fB.\fP). On operating systems which support a "hidden" file
+attribute, like Windows, files with this attribute are also considered hidden.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.hidden = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_hidden() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.hidden);
+
+    let args = parse_low_raw(["--hidden"]).unwrap();
+    assert_eq!(true, args.hidden);
+
+    let args = parse_low_raw(["-."]).unwrap();
+    assert_eq!(true, args.hidden);
+
+    let args = parse_low_raw(["-.", "--no-hidden"]).unwrap();
+    assert_eq!(false, args.hidden);
+
+    let args = parse_low_raw(["--no-hidden", "-."]).unwrap();
+    assert_eq!(true, args.hidden);
+}
+
+/// --hostname-bin
+#[derive(Debug)]
+struct HostnameBin;
+
+impl Flag for HostnameBin {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "hostname-bin"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("COMMAND")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Run a program to get this system's hostname."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+This flag controls how ripgrep determines this system's hostname. The flag's
+value should correspond to an executable (either a path or something that can
+be found via your system's \fBPATH\fP environment variable). When set, ripgrep
+will run this executable, with no arguments, and treat its output (with leading
+and trailing whitespace stripped) as your system's hostname.
+.sp
+When not set (the default, or the empty string), ripgrep will try to
+automatically detect your system's hostname. On Unix, this corresponds
+to calling \fBgethostname\fP. On Windows, this corresponds to calling
+\fBGetComputerNameExW\fP to fetch the system's "physical DNS hostname."
+.sp
+ripgrep uses your system's hostname for producing hyperlinks.
+"#
+    }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Executable
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let path = PathBuf::from(v.unwrap_value());
+        args.hostname_bin =
+            if path.as_os_str().is_empty() { None } else { Some(path) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_hostname_bin() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.hostname_bin);
+
+    let args = parse_low_raw(["--hostname-bin", "foo"]).unwrap();
+    assert_eq!(Some(PathBuf::from("foo")), args.hostname_bin);
+
+    let args = parse_low_raw(["--hostname-bin=foo"]).unwrap();
+    assert_eq!(Some(PathBuf::from("foo")), args.hostname_bin);
+}
+
+/// --hyperlink-format
+#[derive(Debug)]
+struct HyperlinkFormat;
+
+impl Flag for HyperlinkFormat {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "hyperlink-format"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("FORMAT")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Set the format of hyperlinks."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+Set the format of hyperlinks to use when printing results. Hyperlinks make
+certain elements of ripgrep's output, such as file paths, clickable. This
+generally only works in terminal emulators that support OSC-8 hyperlinks. For
+example, the format \fBfile://{host}{path}\fP will emit an RFC 8089 hyperlink.
+To see the format that ripgrep is using, pass the \flag{debug} flag.
+.sp
+Alternatively, a format string may correspond to one of the following aliases:
+\fBdefault\fP, \fBnone\fP, \fBfile\fP, \fBgrep+\fP, \fBkitty\fP, \fBmacvim\fP,
+\fBtextmate\fP, \fBvscode\fP, \fBvscode-insiders\fP, \fBvscodium\fP. The
+alias will be replaced with a format string that is intended to work for the
+corresponding application.
+.sp
+The following variables are available in the format string:
+.sp
+.TP 12
+\fB{path}\fP
+Required. This is replaced with a path to a matching file. The path is
+guaranteed to be absolute and percent encoded such that it is valid to put into
+a URI. Note that a path is guaranteed to start with a /.
+.TP 12
+\fB{host}\fP
+Optional. This is replaced with your system's hostname. On Unix, this
+corresponds to calling \fBgethostname\fP. On Windows, this corresponds to
+calling \fBGetComputerNameExW\fP to fetch the system's "physical DNS hostname."
+Alternatively, if \flag{hostname-bin} was provided, then the hostname returned
+from the output of that program will be returned. If no hostname could be
+found, then this variable is replaced with the empty string.
+.TP 12
+\fB{line}\fP
+Optional. If appropriate, this is replaced with the line number of a match. If
+no line number is available (for example, if \fB\-\-no\-line\-number\fP was
+given), then it is automatically replaced with the value 1.
+.TP 12
+\fB{column}\fP
+Optional, but requires the presence of \fB{line}\fP. If appropriate, this is
+replaced with the column number of a match. If no column number is available
+(for example, if \fB\-\-no\-column\fP was given), then it is automatically
+replaced with the value 1.
+.TP 12
+\fB{wslprefix}\fP
+Optional. This is a special value that is set to
+\fBwsl$/\fP\fIWSL_DISTRO_NAME\fP, where \fIWSL_DISTRO_NAME\fP corresponds to
+the value of the equivalent environment variable. If the system is not Unix
+or if the \fIWSL_DISTRO_NAME\fP environment variable is not set, then this is
+replaced with the empty string.
+.PP
+A format string may be empty. An empty format string is equivalent to the
+\fBnone\fP alias. In this case, hyperlinks will be disabled.
+.sp
+At present, ripgrep does not enable hyperlinks by default. Users must opt into
+them. If you aren't sure what format to use, try \fBdefault\fP.
+.sp
+Like colors, when ripgrep detects that stdout is not connected to a tty, then
+hyperlinks are automatically disabled, regardless of the value of this flag.
+Users can pass \fB\-\-color=always\fP to forcefully emit hyperlinks.
+.sp
+Note that hyperlinks are only written when a path is also in the output
+and colors are enabled. To write hyperlinks without colors, you'll need to
+configure ripgrep to not colorize anything without actually disabling all ANSI
+escape codes completely:
+.sp
+.EX
+    \-\-colors 'path:none' \\
+    \-\-colors 'line:none' \\
+    \-\-colors 'column:none' \\
+    \-\-colors 'match:none'
+.EE
+.sp
+ripgrep works this way because it treats the \flag{color} flag as a proxy for
+whether ANSI escape codes should be used at all. This means that environment
+variables like \fBNO_COLOR=1\fP and \fBTERM=dumb\fP not only disable colors,
+but hyperlinks as well. Similarly, colors and hyperlinks are disabled when
+ripgrep is not writing to a tty. (Unless one forces the issue by setting
+\fB\-\-color=always\fP.)
+.sp
+If you're searching a file directly, for example:
+.sp
+.EX
+    rg foo path/to/file
+.EE
+.sp
+then hyperlinks will not be emitted since the path given does not appear
+in the output. To make the path appear, and thus also a hyperlink, use the
+\flag{with-filename} flag.
+.sp
+For more information on hyperlinks in terminal emulators, see:
+https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let v = v.unwrap_value();
+        let string = convert::str(&v)?;
+        let format = string.parse().context("invalid hyperlink format")?;
+        args.hyperlink_format = format;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_hyperlink_format() {
+    let parseformat = |format: &str| {
+        format.parse::<grep::printer::HyperlinkFormat>().unwrap()
+    };
+
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(parseformat("none"), args.hyperlink_format);
+
+    let args = parse_low_raw(["--hyperlink-format", "default"]).unwrap();
+    #[cfg(windows)]
+    assert_eq!(parseformat("file://{path}"), args.hyperlink_format);
+    #[cfg(not(windows))]
+    assert_eq!(parseformat("file://{host}{path}"), args.hyperlink_format);
+
+    let args = parse_low_raw(["--hyperlink-format", "file"]).unwrap();
+    assert_eq!(parseformat("file://{host}{path}"), args.hyperlink_format);
+
+    let args = parse_low_raw([
+        "--hyperlink-format",
+        "file",
+        "--hyperlink-format=grep+",
+    ])
+    .unwrap();
+    assert_eq!(parseformat("grep+://{path}:{line}"), args.hyperlink_format);
+
+    let args =
+        parse_low_raw(["--hyperlink-format", "file://{host}{path}#{line}"])
+            .unwrap();
+    assert_eq!(
+        parseformat("file://{host}{path}#{line}"),
+        args.hyperlink_format
+    );
+
+    let result = parse_low_raw(["--hyperlink-format", "file://heythere"]);
+    assert!(result.is_err(), "{result:?}");
+}
+
+/// --iglob
+#[derive(Debug)]
+struct IGlob;
+
+impl Flag for IGlob {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "iglob"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("GLOB")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Include/exclude paths case insensitively."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Include or exclude files and directories for searching that match the given
+glob. This always overrides any other ignore logic. Multiple glob flags may
+be used. Globbing rules match \fB.gitignore\fP globs. Precede a glob with a
+\fB!\fP to exclude it. If multiple globs match a file or directory, the glob
+given later in the command line takes precedence. Globs used via this flag are
+matched case insensitively.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let glob = convert::string(v.unwrap_value())?;
+        args.iglobs.push(glob);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_iglob() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.iglobs);
+
+    let args = parse_low_raw(["--iglob", "foo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.iglobs);
+
+    let args = parse_low_raw(["--iglob=foo"]).unwrap();
+    assert_eq!(vec!["foo".to_string()], args.iglobs);
+
+    let args = parse_low_raw(["--iglob", "-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.iglobs);
+
+    let args = parse_low_raw(["--iglob=-foo"]).unwrap();
+    assert_eq!(vec!["-foo".to_string()], args.iglobs);
+}
+
+/// -i/--ignore-case
+#[derive(Debug)]
+struct IgnoreCase;
+
+impl Flag for IgnoreCase {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'i')
+    }
+    fn name_long(&self) -> &'static str {
+        "ignore-case"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Case insensitive search."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+When this flag is provided, all patterns will be searched case insensitively.
+The case insensitivity rules used by ripgrep's default regex engine conform to
+Unicode's "simple" case folding rules.
+.sp
+This is a global option that applies to all patterns given to ripgrep.
+Individual patterns can still be matched case sensitively by using
+inline regex flags. For example, \fB(?\-i)abc\fP will match \fBabc\fP
+case sensitively even when this flag is used.
+.sp
+This flag overrides \flag{case-sensitive} and \flag{smart-case}.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "flag has no negation");
+        args.case = CaseMode::Insensitive;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_ignore_case() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(CaseMode::Sensitive, args.case);
+
+    let args = parse_low_raw(["--ignore-case"]).unwrap();
+    assert_eq!(CaseMode::Insensitive, args.case);
+
+    let args = parse_low_raw(["-i"]).unwrap();
+    assert_eq!(CaseMode::Insensitive, args.case);
+
+    let args = parse_low_raw(["-i", "-s"]).unwrap();
+    assert_eq!(CaseMode::Sensitive, args.case);
+
+    let args = parse_low_raw(["-s", "-i"]).unwrap();
+    assert_eq!(CaseMode::Insensitive, args.case);
+}
+
+/// --ignore-file
+#[derive(Debug)]
+struct IgnoreFile;
+
+impl Flag for IgnoreFile {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "ignore-file"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Specify additional ignore files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Specifies a path to one or more \fBgitignore\fP formatted rules files.
+These patterns are applied after the patterns found in \fB.gitignore\fP,
+\fB.rgignore\fP and \fB.ignore\fP are applied and are matched relative to the
+current working directory. Multiple additional ignore files can be specified
+by using this flag repeatedly. When specifying multiple ignore files, earlier
+files have lower precedence than later files.
+.sp
+If you are looking for a way to include or exclude files and directories
+directly on the command line, then use \flag{glob} instead.
+"
+    }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Filename
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let path = PathBuf::from(v.unwrap_value());
+        args.ignore_file.push(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_ignore_file() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<PathBuf>::new(), args.ignore_file);
+
+    let args = parse_low_raw(["--ignore-file", "foo"]).unwrap();
+    assert_eq!(vec![PathBuf::from("foo")], args.ignore_file);
+
+    let args = parse_low_raw(["--ignore-file", "foo", "--ignore-file", "bar"])
+        .unwrap();
+    assert_eq!(
+        vec![PathBuf::from("foo"), PathBuf::from("bar")],
+        args.ignore_file
+    );
+}
+
+/// --ignore-file-case-insensitive
+#[derive(Debug)]
+struct IgnoreFileCaseInsensitive;
+
+impl Flag for IgnoreFileCaseInsensitive {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "ignore-file-case-insensitive"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-ignore-file-case-insensitive")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Process ignore files case insensitively."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Process ignore files (\fB.gitignore\fP, \fB.ignore\fP, etc.) case
+insensitively. Note that this comes with a performance penalty and is most
+useful on case insensitive file systems (such as Windows).
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.ignore_file_case_insensitive = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_ignore_file_case_insensitive() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.ignore_file_case_insensitive);
+
+    let args = parse_low_raw(["--ignore-file-case-insensitive"]).unwrap();
+    assert_eq!(true, args.ignore_file_case_insensitive);
+
+    let args = parse_low_raw([
+        "--ignore-file-case-insensitive",
+        "--no-ignore-file-case-insensitive",
+    ])
+    .unwrap();
+    assert_eq!(false, args.ignore_file_case_insensitive);
+
+    let args = parse_low_raw([
+        "--no-ignore-file-case-insensitive",
+        "--ignore-file-case-insensitive",
+    ])
+    .unwrap();
+    assert_eq!(true, args.ignore_file_case_insensitive);
+}
+
+/// --include-zero
+#[derive(Debug)]
+struct IncludeZero;
+
+impl Flag for IncludeZero {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "include-zero"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-include-zero")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Include zero matches in summary output."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When used with \flag{count} or \flag{count-matches}, this causes ripgrep to
+print the number of matches for each file even if there were zero matches. This
+is disabled by default but can be enabled to make ripgrep behave more like
+grep.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.include_zero = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_include_zero() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.include_zero);
+
+    let args = parse_low_raw(["--include-zero"]).unwrap();
+    assert_eq!(true, args.include_zero);
+
+    let args = parse_low_raw(["--include-zero", "--no-include-zero"]).unwrap();
+    assert_eq!(false, args.include_zero);
+}
+
+/// -v/--invert-match
+#[derive(Debug)]
+struct InvertMatch;
+
+impl Flag for InvertMatch {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'v')
+    }
+    fn name_long(&self) -> &'static str {
+        "invert-match"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-invert-match")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Invert matching."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag inverts matching. That is, instead of printing lines that match,
+ripgrep will print lines that don't match.
+.sp
+Note that this only inverts line-by-line matching. For example, combining this
+flag with \flag{files-with-matches} will emit files that contain any lines
+that do not match the patterns given. That's not the same as, for example,
+\flag{files-without-match}, which will emit files that do not contain any
+matching lines.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.invert_match = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_invert_match() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.invert_match);
+
+    let args = parse_low_raw(["--invert-match"]).unwrap();
+    assert_eq!(true, args.invert_match);
+
+    let args = parse_low_raw(["-v"]).unwrap();
+    assert_eq!(true, args.invert_match);
+
+    let args = parse_low_raw(["-v", "--no-invert-match"]).unwrap();
+    assert_eq!(false, args.invert_match);
+}
+
+/// --json
+#[derive(Debug)]
+struct JSON;
+
+impl Flag for JSON {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "json"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-json")
+    }
+    fn doc_category(&self) -> Category {
+        Category::OutputModes
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show search results in a JSON Lines format."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Enable printing results in a JSON Lines format.
+.sp
+When this flag is provided, ripgrep will emit a sequence of messages, each
+encoded as a JSON object, where there are five different message types:
+.sp
+.TP 12
+\fBbegin\fP
+A message that indicates a file is being searched and contains at least one
+match.
+.TP 12
+\fBend\fP
+A message the indicates a file is done being searched. This message also
+include summary statistics about the search for a particular file.
+.TP 12
+\fBmatch\fP
+A message that indicates a match was found. This includes the text and offsets
+of the match.
+.TP 12
+\fBcontext\fP
+A message that indicates a contextual line was found. This includes the text of
+the line, along with any match information if the search was inverted.
+.TP 12
+\fBsummary\fP
+The final message emitted by ripgrep that contains summary statistics about the
+search across all files.
+.PP
+Since file paths or the contents of files are not guaranteed to be valid
+UTF-8 and JSON itself must be representable by a Unicode encoding, ripgrep
+will emit all data elements as objects with one of two keys: \fBtext\fP or
+\fBbytes\fP. \fBtext\fP is a normal JSON string when the data is valid UTF-8
+while \fBbytes\fP is the base64 encoded contents of the data.
+.sp
+The JSON Lines format is only supported for showing search results. It cannot
+be used with other flags that emit other types of output, such as \flag{files},
+\flag{files-with-matches}, \flag{files-without-match}, \flag{count} or
+\flag{count-matches}. ripgrep will report an error if any of the aforementioned
+flags are used in concert with \flag{json}.
+.sp
+Other flags that control aspects of the standard output such as
+\flag{only-matching}, \flag{heading}, \flag{replace}, \flag{max-columns}, etc.,
+have no effect when \flag{json} is set. However, enabling JSON output will
+always implicitly and unconditionally enable \flag{stats}.
+.sp
+A more complete description of the JSON format used can be found here:
+\fIhttps://docs.rs/grep-printer/*/grep_printer/struct.JSON.html\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        if v.unwrap_switch() {
+            args.mode.update(Mode::Search(SearchMode::JSON));
+        } else if matches!(args.mode, Mode::Search(SearchMode::JSON)) {
+            // --no-json only reverts to the default mode if the mode is
+            // JSON, otherwise it's a no-op.
+            args.mode.update(Mode::Search(SearchMode::Standard));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_json() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--json"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::JSON), args.mode);
+
+    let args = parse_low_raw(["--json", "--no-json"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+
+    let args = parse_low_raw(["--json", "--files", "--no-json"]).unwrap();
+    assert_eq!(Mode::Files, args.mode);
+
+    let args = parse_low_raw(["--json", "-l", "--no-json"]).unwrap();
+    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+}
+
+/// --line-buffered
+#[derive(Debug)]
+struct LineBuffered;
+
+impl Flag for LineBuffered {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "line-buffered"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-line-buffered")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Force line buffering."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When enabled, ripgrep will always use line buffering. That is, whenever a
+matching line is found, it will be flushed to stdout immediately. This is the
+default when ripgrep's stdout is connected to a tty, but otherwise, ripgrep
+will use block buffering, which is typically faster. This flag forces ripgrep
+to use line buffering even if it would otherwise use block buffering. This is
+typically useful in shell pipelines, for example:
+.sp
+.EX
+    tail -f something.log | rg foo --line-buffered | rg bar
+.EE
+.sp
+This overrides the \flag{block-buffered} flag.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.buffer = if v.unwrap_switch() {
+            BufferMode::Line
+        } else {
+            BufferMode::Auto
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_line_buffered() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(BufferMode::Auto, args.buffer);
+
+    let args = parse_low_raw(["--line-buffered"]).unwrap();
+    assert_eq!(BufferMode::Line, args.buffer);
+
+    let args =
+        parse_low_raw(["--line-buffered", "--no-line-buffered"]).unwrap();
+    assert_eq!(BufferMode::Auto, args.buffer);
+
+    let args = parse_low_raw(["--line-buffered", "--block-buffered"]).unwrap();
+    assert_eq!(BufferMode::Block, args.buffer);
+}
+
+/// -n/--line-number
+#[derive(Debug)]
+struct LineNumber;
+
+impl Flag for LineNumber {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'n')
+    }
+    fn name_long(&self) -> &'static str {
+        "line-number"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show line numbers."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Show line numbers (1-based).
+.sp
+This is enabled by default when stdout is connected to a tty.
+.sp
+This flag can be disabled by \flag{no-line-number}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--line-number has no automatic negation");
+        args.line_number = Some(true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_line_number() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.line_number);
+
+    let args = parse_low_raw(["--line-number"]).unwrap();
+    assert_eq!(Some(true), args.line_number);
+
+    let args = parse_low_raw(["-n"]).unwrap();
+    assert_eq!(Some(true), args.line_number);
+
+    let args = parse_low_raw(["-n", "--no-line-number"]).unwrap();
+    assert_eq!(Some(false), args.line_number);
+}
+
+/// -N/--no-line-number
+#[derive(Debug)]
+struct LineNumberNo;
+
+impl Flag for LineNumberNo {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'N')
+    }
+    fn name_long(&self) -> &'static str {
+        "no-line-number"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Suppress line numbers."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Suppress line numbers.
+.sp
+Line numbers are off by default when stdout is not connected to a tty.
+.sp
+Line numbers can be forcefully turned on by \flag{line-number}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(
+            v.unwrap_switch(),
+            "--no-line-number has no automatic negation"
+        );
+        args.line_number = Some(false);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_line_number() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.line_number);
+
+    let args = parse_low_raw(["--no-line-number"]).unwrap();
+    assert_eq!(Some(false), args.line_number);
+
+    let args = parse_low_raw(["-N"]).unwrap();
+    assert_eq!(Some(false), args.line_number);
+
+    let args = parse_low_raw(["-N", "--line-number"]).unwrap();
+    assert_eq!(Some(true), args.line_number);
+}
+
+/// -x/--line-regexp
+#[derive(Debug)]
+struct LineRegexp;
+
+impl Flag for LineRegexp {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'x')
+    }
+    fn name_long(&self) -> &'static str {
+        "line-regexp"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show matches surrounded by line boundaries."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When enabled, ripgrep will only show matches surrounded by line boundaries.
+This is equivalent to surrounding every pattern with \fB^\fP and \fB$\fP. In
+other words, this only prints lines where the entire line participates in a
+match.
+.sp
+This overrides the \flag{word-regexp} flag.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--line-regexp has no negation");
+        args.boundary = Some(BoundaryMode::Line);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_line_regexp() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.boundary);
+
+    let args = parse_low_raw(["--line-regexp"]).unwrap();
+    assert_eq!(Some(BoundaryMode::Line), args.boundary);
+
+    let args = parse_low_raw(["-x"]).unwrap();
+    assert_eq!(Some(BoundaryMode::Line), args.boundary);
+}
+
+/// --max-buffer-size
+#[derive(Debug)]
+struct MaxBufferSize;
+
+impl Flag for MaxBufferSize {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "max-buffer-size"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM+SUFFIX?")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Cap per-file output buffering during parallel search."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When searching with multiple threads, each worker accumulates a whole file's
+output in memory before handing it to the buffer writer, which prints it in
+one piece so concurrent threads don't interleave their output. A file with
+an enormous number of matches (e.g. minified JS searched with
+\flag{passthru}) can therefore balloon memory well beyond the size of the
+file itself.
+.sp
+This flag caps how much a worker buffers before flushing what it has so far,
+in \fINUM\fP bytes. The input format accepts suffixes of \fBK\fP, \fBM\fP or
+\fBG\fP, the same as \flag{max-filesize}. When this flag is omitted or set to
+\fB0\fP, buffering is unbounded, as if this flag weren't given.
+.sp
+This flag has no effect when ripgrep's output already depends on knowing
+whether anything has been printed yet, namely \flag{heading} mode or when
+context lines (\flag{context} and friends) are active: splitting a single
+file's output across multiple flushes would incorrectly duplicate or
+misplace the separator those modes print between files.
+.sp
+Example: --max-buffer-size 10M
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let v = v.unwrap_value();
+        let bytes = convert::human_readable_u64(&v)?;
+        args.max_buffer_size = if bytes == 0 { None } else { Some(bytes) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_buffer_size() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.max_buffer_size);
+
+    let args = parse_low_raw(["--max-buffer-size", "1024"]).unwrap();
+    assert_eq!(Some(1024), args.max_buffer_size);
+
+    let args = parse_low_raw(["--max-buffer-size", "10M"]).unwrap();
+    assert_eq!(Some(10 * (1 << 20)), args.max_buffer_size);
+
+    let args = parse_low_raw(["--max-buffer-size", "0"]).unwrap();
+    assert_eq!(None, args.max_buffer_size);
+}
+
+/// -M/--max-columns
+#[derive(Debug)]
+struct MaxColumns;
+
+impl Flag for MaxColumns {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'M')
+    }
+    fn name_long(&self) -> &'static str {
+        "max-columns"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Omit lines longer than this limit."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When given, ripgrep will omit lines longer than this limit in bytes. Instead of
+printing long lines, only the number of matches in that line is printed.
+.sp
+When this flag is omitted or is set to \fB0\fP, then it has no effect.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let max = convert::u64(&v.unwrap_value())?;
+        args.max_columns = if max == 0 { None } else { Some(max) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_columns() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.max_columns);
+
+    let args = parse_low_raw(["--max-columns", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_columns);
+
+    let args = parse_low_raw(["-M", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_columns);
+
+    let args = parse_low_raw(["-M5"]).unwrap();
+    assert_eq!(Some(5), args.max_columns);
+
+    let args = parse_low_raw(["--max-columns", "5", "-M0"]).unwrap();
+    assert_eq!(None, args.max_columns);
+}
+
+/// --max-columns-preview
+#[derive(Debug)]
+struct MaxColumnsPreview;
+
+impl Flag for MaxColumnsPreview {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "max-columns-preview"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-max-columns-preview")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show preview for lines exceeding the limit."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Prints a preview for lines exceeding the configured max column limit.
+.sp
+When the \flag{max-columns} flag is used, ripgrep will by default completely
+replace any line that is too long with a message indicating that a matching
+line was removed. When this flag is combined with \flag{max-columns}, a preview
+of the line (corresponding to the limit size) is shown instead, where the part
+of the line exceeding the limit is not shown.
+.sp
+If the \flag{max-columns} flag is not set, then this has no effect.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.max_columns_preview = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_columns_preview() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.max_columns_preview);
+
+    let args = parse_low_raw(["--max-columns-preview"]).unwrap();
+    assert_eq!(true, args.max_columns_preview);
+
+    let args =
+        parse_low_raw(["--max-columns-preview", "--no-max-columns-preview"])
+            .unwrap();
+    assert_eq!(false, args.max_columns_preview);
+}
+
+/// -m/--max-count
+#[derive(Debug)]
+struct MaxCount;
+
+impl Flag for MaxCount {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'm')
+    }
+    fn name_long(&self) -> &'static str {
+        "max-count"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Limit the number of matching lines."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Limit the number of matching lines per file searched to \fINUM\fP.
+.sp
+Note that \fB0\fP is a legal value but not likely to be useful. When used,
+ripgrep won't search anything.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.max_count = Some(convert::u64(&v.unwrap_value())?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_count() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.max_count);
+
+    let args = parse_low_raw(["--max-count", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_count);
+
+    let args = parse_low_raw(["-m", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_count);
+
+    let args = parse_low_raw(["-m", "5", "--max-count=10"]).unwrap();
+    assert_eq!(Some(10), args.max_count);
+    let args = parse_low_raw(["-m0"]).unwrap();
+    assert_eq!(Some(0), args.max_count);
+}
+
+/// --max-depth
+#[derive(Debug)]
+struct MaxDepth;
+
+impl Flag for MaxDepth {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'd')
+    }
+    fn name_long(&self) -> &'static str {
+        "max-depth"
+    }
+    fn aliases(&self) -> &'static [&'static str] {
+        &["maxdepth"]
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Descend at most NUM directories."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag limits the depth of directory traversal to \fINUM\fP levels beyond
+the paths given. A value of \fB0\fP only searches the explicitly given paths
+themselves.
+.sp
+For example, \fBrg --max-depth 0 \fP\fIdir/\fP is a no-op because \fIdir/\fP
+will not be descended into. \fBrg --max-depth 1 \fP\fIdir/\fP will search only
+the direct children of \fIdir\fP.
+.sp
+An alternative spelling for this flag is \fB\-\-maxdepth\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.max_depth = Some(convert::usize(&v.unwrap_value())?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_depth() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.max_depth);
+
+    let args = parse_low_raw(["--max-depth", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_depth);
+
+    let args = parse_low_raw(["-d", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_depth);
+
+    let args = parse_low_raw(["--max-depth", "5", "--max-depth=10"]).unwrap();
+    assert_eq!(Some(10), args.max_depth);
+
+    let args = parse_low_raw(["--max-depth", "0"]).unwrap();
+    assert_eq!(Some(0), args.max_depth);
+
+    let args = parse_low_raw(["--maxdepth", "5"]).unwrap();
+    assert_eq!(Some(5), args.max_depth);
+}
+
+/// --max-filesize
+#[derive(Debug)]
+struct MaxFilesize;
+
+impl Flag for MaxFilesize {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "max-filesize"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM+SUFFIX?")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Ignore files larger than NUM in size."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Ignore files larger than \fINUM\fP in size. This does not apply to directories.
+.sp
+The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
+correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
+provided the input is treated as bytes.
+.sp
+Examples: \fB\-\-max-filesize 50K\fP or \fB\-\-max\-filesize 80M\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let v = v.unwrap_value();
+        args.max_filesize = Some(convert::human_readable_u64(&v)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_max_filesize() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.max_filesize);
+
+    let args = parse_low_raw(["--max-filesize", "1024"]).unwrap();
+    assert_eq!(Some(1024), args.max_filesize);
+
+    let args = parse_low_raw(["--max-filesize", "1K"]).unwrap();
+    assert_eq!(Some(1024), args.max_filesize);
+
+    let args =
+        parse_low_raw(["--max-filesize", "1K", "--max-filesize=1M"]).unwrap();
+    assert_eq!(Some(1024 * 1024), args.max_filesize);
+}
+
+/// --mmap
+#[derive(Debug)]
+struct Mmap;
+
+impl Flag for Mmap {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "mmap"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-mmap")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Search with memory maps when possible."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When enabled, ripgrep will search using memory maps when possible. This is
+enabled by default when ripgrep thinks it will be faster.
+.sp
+Memory map searching cannot be used in all circumstances. For example, when
+searching virtual files or streams likes \fBstdin\fP. In such cases, memory
+maps will not be used even when this flag is enabled.
+.sp
+Note that ripgrep may abort unexpectedly when memory maps are used if it
+searches a file that is simultaneously truncated. Users can opt out of this
+possibility by disabling memory maps.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.mmap = if v.unwrap_switch() {
+            MmapMode::AlwaysTryMmap
+        } else {
+            MmapMode::Never
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_mmap() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(MmapMode::Auto, args.mmap);
+
+    let args = parse_low_raw(["--mmap"]).unwrap();
+    assert_eq!(MmapMode::AlwaysTryMmap, args.mmap);
+
+    let args = parse_low_raw(["--no-mmap"]).unwrap();
+    assert_eq!(MmapMode::Never, args.mmap);
+
+    let args = parse_low_raw(["--mmap", "--no-mmap"]).unwrap();
+    assert_eq!(MmapMode::Never, args.mmap);
+
+    let args = parse_low_raw(["--no-mmap", "--mmap"]).unwrap();
+    assert_eq!(MmapMode::AlwaysTryMmap, args.mmap);
+}
+
+/// -U/--multiline
+#[derive(Debug)]
+struct Multiline;
+
+impl Flag for Multiline {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'U')
+    }
+    fn name_long(&self) -> &'static str {
+        "multiline"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-multiline")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Enable searching across multiple lines."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+This flag enable searching across multiple lines.
+.sp
+When multiline mode is enabled, ripgrep will lift the restriction that a
+match cannot include a line terminator. For example, when multiline mode
+is not enabled (the default), then the regex \fB\\p{any}\fP will match any
+Unicode codepoint other than \fB\\n\fP. Similarly, the regex \fB\\n\fP is
+explicitly forbidden, and if you try to use it, ripgrep will return an error.
+However, when multiline mode is enabled, \fB\\p{any}\fP will match any Unicode
+codepoint, including \fB\\n\fP, and regexes like \fB\\n\fP are permitted.
+.sp
+An important caveat is that multiline mode does not change the match semantics
+of \fB.\fP. Namely, in most regex matchers, a \fB.\fP will by default match any
+character other than \fB\\n\fP, and this is true in ripgrep as well. In order
+to make \fB.\fP match \fB\\n\fP, you must enable the "dot all" flag inside the
+regex. For example, both \fB(?s).\fP and \fB(?s:.)\fP have the same semantics,
+where \fB.\fP will match any character, including \fB\\n\fP. Alternatively, the
+\flag{multiline-dotall} flag may be passed to make the "dot all" behavior the
+default. This flag only applies when multiline search is enabled.
+.sp
+There is no limit on the number of the lines that a single match can span.
+.sp
+\fBWARNING\fP: Because of how the underlying regex engine works, multiline
+searches may be slower than normal line-oriented searches, and they may also
+use more memory. In particular, when multiline mode is enabled, ripgrep
+requires that each file it searches is laid out contiguously in memory (either
+by reading it onto the heap or by memory-mapping it). Things that cannot be
+memory-mapped (such as \fBstdin\fP) will be consumed until EOF before searching
+can begin. In general, ripgrep will only do these things when necessary.
+Specifically, if the \flag{multiline} flag is provided but the regex does
+not contain patterns that would match \fB\\n\fP characters, then ripgrep
+will automatically avoid reading each file into memory before searching it.
+Nevertheless, if you only care about matches spanning at most one line, then it
+is always better to disable multiline mode.
+.sp
+This overrides the \flag{stop-on-nonmatch} flag.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.multiline = v.unwrap_switch();
+        if args.multiline {
+            args.stop_on_nonmatch = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_multiline() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.multiline);
+
+    let args = parse_low_raw(["--multiline"]).unwrap();
+    assert_eq!(true, args.multiline);
+
+    let args = parse_low_raw(["-U"]).unwrap();
+    assert_eq!(true, args.multiline);
+
+    let args = parse_low_raw(["-U", "--no-multiline"]).unwrap();
+    assert_eq!(false, args.multiline);
+}
+
+/// --multiline-dotall
+#[derive(Debug)]
+struct MultilineDotall;
+
+impl Flag for MultilineDotall {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "multiline-dotall"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-multiline-dotall")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Make '.' match line terminators."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+This flag enables "dot all" mode in all regex patterns. This causes \fB.\fP to
+match line terminators when multiline searching is enabled. This flag has no
+effect if multiline searching isn't enabled with the \flag{multiline} flag.
+.sp
+Normally, a \fB.\fP will match any character except line terminators. While
+this behavior typically isn't relevant for line-oriented matching (since
+matches can span at most one line), this can be useful when searching with the
+\flag{multiline} flag. By default, multiline mode runs without "dot all" mode
+enabled.
+.sp
+This flag is generally intended to be used in an alias or your ripgrep config
+file if you prefer "dot all" semantics by default. Note that regardless of
+whether this flag is used, "dot all" semantics can still be controlled via
+inline flags in the regex pattern itself, e.g., \fB(?s:.)\fP always enables
+"dot all" whereas \fB(?-s:.)\fP always disables "dot all". Moreover, you
+can use character classes like \fB\\p{any}\fP to match any Unicode codepoint
+regardless of whether "dot all" mode is enabled or not.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.multiline_dotall = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_multiline_dotall() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.multiline_dotall);
+
+    let args = parse_low_raw(["--multiline-dotall"]).unwrap();
+    assert_eq!(true, args.multiline_dotall);
+
+    let args = parse_low_raw(["--multiline-dotall", "--no-multiline-dotall"])
+        .unwrap();
+    assert_eq!(false, args.multiline_dotall);
+}
+
+/// --no-config
+#[derive(Debug)]
+struct NoConfig;
+
+impl Flag for NoConfig {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-config"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Never read configuration files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When set, ripgrep will never read configuration files. When this flag is
+present, ripgrep will not respect the \fBRIPGREP_CONFIG_PATH\fP environment
+variable.
+.sp
+If ripgrep ever grows a feature to automatically read configuration files in
+pre-defined locations, then this flag will also disable that behavior as well.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--no-config has no negation");
+        args.no_config = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_config() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_config);
+
+    let args = parse_low_raw(["--no-config"]).unwrap();
+    assert_eq!(true, args.no_config);
+}
+
+/// --no-ignore
+#[derive(Debug)]
+struct NoIgnore;
+
+impl Flag for NoIgnore {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use ignore files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When set, ignore files such as \fB.gitignore\fP, \fB.ignore\fP and
+\fB.rgignore\fP will not be respected. This implies \flag{no-ignore-dot},
+\flag{no-ignore-exclude}, \flag{no-ignore-global}, \flag{no-ignore-parent} and
+\flag{no-ignore-vcs}.
+.sp
+This does not imply \flag{no-ignore-files}, since \flag{ignore-file} is
+specified explicitly as a command line argument.
+.sp
+When given only once, the \flag{unrestricted} flag is identical in
+behavior to this flag and can be considered an alias. However, subsequent
+\flag{unrestricted} flags have additional effects.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let yes = v.unwrap_switch();
+        args.no_ignore_dot = yes;
+        args.no_ignore_exclude = yes;
+        args.no_ignore_global = yes;
+        args.no_ignore_parent = yes;
+        args.no_ignore_vcs = yes;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_dot);
+    assert_eq!(false, args.no_ignore_exclude);
+    assert_eq!(false, args.no_ignore_global);
+    assert_eq!(false, args.no_ignore_parent);
+    assert_eq!(false, args.no_ignore_vcs);
+
+    let args = parse_low_raw(["--no-ignore"]).unwrap();
+    assert_eq!(true, args.no_ignore_dot);
+    assert_eq!(true, args.no_ignore_exclude);
+    assert_eq!(true, args.no_ignore_global);
+    assert_eq!(true, args.no_ignore_parent);
+    assert_eq!(true, args.no_ignore_vcs);
+
+    let args = parse_low_raw(["--no-ignore", "--ignore"]).unwrap();
+    assert_eq!(false, args.no_ignore_dot);
+    assert_eq!(false, args.no_ignore_exclude);
+    assert_eq!(false, args.no_ignore_global);
+    assert_eq!(false, args.no_ignore_parent);
+    assert_eq!(false, args.no_ignore_vcs);
+}
+
+/// --no-ignore-dot
+#[derive(Debug)]
+struct NoIgnoreDot;
+
+impl Flag for NoIgnoreDot {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-dot"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-dot")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use .ignore or .rgignore files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Don't respect filter rules from \fB.ignore\fP or \fB.rgignore\fP files.
+.sp
+This does not impact whether ripgrep will ignore files and directories whose
+names begin with a dot. For that, see the \flag{hidden} flag. This flag also
+does not impact whether filter rules from \fB.gitignore\fP files are respected.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_dot = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_dot() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_dot);
+
+    let args = parse_low_raw(["--no-ignore-dot"]).unwrap();
+    assert_eq!(true, args.no_ignore_dot);
+
+    let args = parse_low_raw(["--no-ignore-dot", "--ignore-dot"]).unwrap();
+    assert_eq!(false, args.no_ignore_dot);
+}
+
+/// --no-ignore-exclude
+#[derive(Debug)]
+struct NoIgnoreExclude;
+
+impl Flag for NoIgnoreExclude {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-exclude"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-exclude")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use local exclusion files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Don't respect filter rules from files that are manually configured for the repository.
+For example, this includes \fBgit\fP's \fB.git/info/exclude\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_exclude = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_exclude() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_exclude);
+
+    let args = parse_low_raw(["--no-ignore-exclude"]).unwrap();
+    assert_eq!(true, args.no_ignore_exclude);
+
+    let args =
+        parse_low_raw(["--no-ignore-exclude", "--ignore-exclude"]).unwrap();
+    assert_eq!(false, args.no_ignore_exclude);
+}
+
+/// --no-ignore-files
+#[derive(Debug)]
+struct NoIgnoreFiles;
+
+impl Flag for NoIgnoreFiles {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-files"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-files")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use --ignore-file arguments."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When set, any \flag{ignore-file} flags, even ones that come after this flag,
+are ignored.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_files = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_files() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_files);
+
+    let args = parse_low_raw(["--no-ignore-files"]).unwrap();
+    assert_eq!(true, args.no_ignore_files);
+
+    let args = parse_low_raw(["--no-ignore-files", "--ignore-files"]).unwrap();
+    assert_eq!(false, args.no_ignore_files);
+}
+
+/// --no-ignore-global
+#[derive(Debug)]
+struct NoIgnoreGlobal;
+
+impl Flag for NoIgnoreGlobal {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-global"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-global")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use global ignore files."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+Don't respect filter rules from ignore files that come from "global" sources
+such as \fBgit\fP's \fBcore.excludesFile\fP configuration option (which
+defaults to \fB$HOME/.config/git/ignore\fP).
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_global = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_global() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_global);
+
+    let args = parse_low_raw(["--no-ignore-global"]).unwrap();
+    assert_eq!(true, args.no_ignore_global);
+
+    let args =
+        parse_low_raw(["--no-ignore-global", "--ignore-global"]).unwrap();
+    assert_eq!(false, args.no_ignore_global);
+}
+
+/// --no-ignore-messages
+#[derive(Debug)]
+struct NoIgnoreMessages;
+
+impl Flag for NoIgnoreMessages {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-messages"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-messages")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Logging
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Suppress gitignore parse error messages."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When this flag is enabled, all error messages related to parsing ignore files
+are suppressed. By default, error messages are printed to stderr. In cases
+where these errors are expected, this flag can be used to avoid seeing the
+noise produced by the messages.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_messages = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_messages() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_messages);
+
+    let args = parse_low_raw(["--no-ignore-messages"]).unwrap();
+    assert_eq!(true, args.no_ignore_messages);
+
+    let args =
+        parse_low_raw(["--no-ignore-messages", "--ignore-messages"]).unwrap();
+    assert_eq!(false, args.no_ignore_messages);
+}
+
+/// --no-ignore-parent
+#[derive(Debug)]
+struct NoIgnoreParent;
+
+impl Flag for NoIgnoreParent {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-parent"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-parent")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use ignore files in parent directories."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When this flag is set, filter rules from ignore files found in parent
+directories are not respected. By default, ripgrep will ascend the parent
+directories of the current working directory to look for any applicable ignore
+files that should be applied. In some cases this may not be desirable.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_parent = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_parent() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_parent);
+
+    let args = parse_low_raw(["--no-ignore-parent"]).unwrap();
+    assert_eq!(true, args.no_ignore_parent);
+
+    let args =
+        parse_low_raw(["--no-ignore-parent", "--ignore-parent"]).unwrap();
+    assert_eq!(false, args.no_ignore_parent);
+}
+
+/// --no-ignore-vcs
+#[derive(Debug)]
+struct NoIgnoreVcs;
+
+impl Flag for NoIgnoreVcs {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-ignore-vcs"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("ignore-vcs")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Don't use ignore files from source control."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When given, filter rules from source control ignore files (e.g., \fB.gitignore\fP)
+are not respected. By default, ripgrep respects \fBgit\fP's ignore rules for
+automatic filtering. In some cases, it may not be desirable to respect the
+source control's ignore rules and instead only respect rules in \fB.ignore\fP
+or \fB.rgignore\fP.
+.sp
+This flag implies \flag{no-ignore-parent} for source control ignore files as
+well.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_ignore_vcs = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_ignore_vcs() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_ignore_vcs);
+
+    let args = parse_low_raw(["--no-ignore-vcs"]).unwrap();
+    assert_eq!(true, args.no_ignore_vcs);
+
+    let args = parse_low_raw(["--no-ignore-vcs", "--ignore-vcs"]).unwrap();
+    assert_eq!(false, args.no_ignore_vcs);
+}
+
+/// --no-messages
+#[derive(Debug)]
+struct NoMessages;
+
+impl Flag for NoMessages {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-messages"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("messages")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Logging
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Suppress some error messages."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag suppresses some error messages. Specifically, messages related to
+the failed opening and reading of files. Error messages related to the syntax
+of the pattern are still shown.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_messages = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_messages() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_messages);
+
+    let args = parse_low_raw(["--no-messages"]).unwrap();
+    assert_eq!(true, args.no_messages);
+
+    let args = parse_low_raw(["--no-messages", "--messages"]).unwrap();
+    assert_eq!(false, args.no_messages);
+}
+
+/// --no-pcre2-unicode
+#[derive(Debug)]
+struct NoPcre2Unicode;
+
+impl Flag for NoPcre2Unicode {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-pcre2-unicode"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("pcre2-unicode")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"(DEPRECATED) Disable Unicode mode for PCRE2."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+DEPRECATED. Use \flag{no-unicode} instead.
+.sp
+Note that Unicode mode is enabled by default.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_unicode = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_pcre2_unicode() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_unicode);
+
+    let args = parse_low_raw(["--no-pcre2-unicode"]).unwrap();
+    assert_eq!(true, args.no_unicode);
+
+    let args =
+        parse_low_raw(["--no-pcre2-unicode", "--pcre2-unicode"]).unwrap();
+    assert_eq!(false, args.no_unicode);
+}
+
+/// --no-require-git
+#[derive(Debug)]
+struct NoRequireGit;
+
+impl Flag for NoRequireGit {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-require-git"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("require-git")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Use .gitignore outside of git repositories."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When this flag is given, source control ignore files such as \fB.gitignore\fP
+are respected even if no \fBgit\fP repository is present.
+.sp
+By default, ripgrep will only respect filter rules from source control ignore
+files when ripgrep detects that the search is executed inside a source control
+repository. For example, when a \fB.git\fP directory is observed.
+.sp
+This flag relaxes the default restriction. For example, it might be useful when
+the contents of a \fBgit\fP repository are stored or copied somewhere, but
+where the repository state is absent.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_require_git = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_require_git() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_require_git);
+
+    let args = parse_low_raw(["--no-require-git"]).unwrap();
+    assert_eq!(true, args.no_require_git);
+
+    let args = parse_low_raw(["--no-require-git", "--require-git"]).unwrap();
+    assert_eq!(false, args.no_require_git);
+}
+
+/// --no-unicode
+#[derive(Debug)]
+struct NoUnicode;
+
+impl Flag for NoUnicode {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "no-unicode"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("unicode")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Disable Unicode mode."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+This flag disables Unicode mode for all patterns given to ripgrep.
+.sp
+By default, ripgrep will enable "Unicode mode" in all of its regexes. This has
+a number of consequences:
+.sp
+.IP \(bu 3n
+\fB.\fP will only match valid UTF-8 encoded Unicode scalar values.
+.sp
+.IP \(bu 3n
+Classes like \fB\\w\fP, \fB\\s\fP, \fB\\d\fP are all Unicode aware and much
+bigger than their ASCII only versions.
+.sp
+.IP \(bu 3n
+Case insensitive matching will use Unicode case folding.
+.sp
+.IP \(bu 3n
+A large array of classes like \fB\\p{Emoji}\fP are available. (Although the
+specific set of classes available varies based on the regex engine. In general,
+the default regex engine has more classes available to it.)
+.sp
+.IP \(bu 3n
+Word boundaries (\fB\\b\fP and \fB\\B\fP) use the Unicode definition of a word
+character.
+.PP
+In some cases it can be desirable to turn these things off. This flag will do
+exactly that. For example, Unicode mode can sometimes have a negative impact
+on performance, especially when things like \fB\\w\fP are used frequently
+(including via bounded repetitions like \fB\\w{100}\fP) when only their ASCII
+interpretation is needed.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.no_unicode = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_no_unicode() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.no_unicode);
+
+    let args = parse_low_raw(["--no-unicode"]).unwrap();
+    assert_eq!(true, args.no_unicode);
+
+    let args = parse_low_raw(["--no-unicode", "--unicode"]).unwrap();
+    assert_eq!(false, args.no_unicode);
+
+    let args = parse_low_raw(["--no-unicode", "--pcre2-unicode"]).unwrap();
+    assert_eq!(false, args.no_unicode);
+
+    let args = parse_low_raw(["--no-pcre2-unicode", "--unicode"]).unwrap();
+    assert_eq!(false, args.no_unicode);
+}
+
+/// -0/--null
+#[derive(Debug)]
+struct Null;
+
+impl Flag for Null {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'0')
+    }
+    fn name_long(&self) -> &'static str {
+        "null"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print a NUL byte after file paths."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Whenever a file path is printed, follow it with a \fBNUL\fP byte. This includes
+printing file paths before matches, and when printing a list of matching files
+such as with \flag{count}, \flag{files-with-matches} and \flag{files}. This
+option is useful for use with \fBxargs\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--null has no negation");
+        args.null = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_null() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.null);
+
+    let args = parse_low_raw(["--null"]).unwrap();
+    assert_eq!(true, args.null);
+
+    let args = parse_low_raw(["-0"]).unwrap();
+    assert_eq!(true, args.null);
+}
+
+/// --null-data
+#[derive(Debug)]
+struct NullData;
+
+impl Flag for NullData {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "null-data"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Use NUL as a line terminator."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Enabling this flag causes ripgrep to use \fBNUL\fP as a line terminator instead
+of the default of \fP\\n\fP.
+.sp
+This is useful when searching large binary files that would otherwise have
+very long lines if \fB\\n\fP were used as the line terminator. In particular,
+ripgrep requires that, at a minimum, each line must fit into memory. Using
+\fBNUL\fP instead can be a useful stopgap to keep memory requirements low and
+avoid OOM (out of memory) conditions.
+.sp
+This is also useful for processing NUL delimited data, such as that emitted
+when using ripgrep's \flag{null} flag or \fBfind\fP's \fB\-\-print0\fP flag.
+.sp
+Using this flag implies \flag{text}. It also overrides \flag{crlf}.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--null-data has no negation");
+        args.crlf = false;
+        args.null_data = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_null_data() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.null_data);
+
+    let args = parse_low_raw(["--null-data"]).unwrap();
+    assert_eq!(true, args.null_data);
+
+    let args = parse_low_raw(["--null-data", "--crlf"]).unwrap();
+    assert_eq!(false, args.null_data);
+    assert_eq!(true, args.crlf);
+
+    let args = parse_low_raw(["--crlf", "--null-data"]).unwrap();
+    assert_eq!(true, args.null_data);
+    assert_eq!(false, args.crlf);
+
+    let args = parse_low_raw(["--null-data", "--no-crlf"]).unwrap();
+    assert_eq!(true, args.null_data);
+    assert_eq!(false, args.crlf);
+}
+
+/// --one-file-system
+#[derive(Debug)]
+struct OneFileSystem;
+
+impl Flag for OneFileSystem {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "one-file-system"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-one-file-system")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Skip directories on other file systems."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When enabled, ripgrep will not cross file system boundaries relative to where
+the search started from.
+.sp
+Note that this applies to each path argument given to ripgrep. For example, in
+the command
+.sp
+.EX
+    rg \-\-one\-file\-system /foo/bar /quux/baz
+.EE
+.sp
+ripgrep will search both \fI/foo/bar\fP and \fI/quux/baz\fP even if they are
+on different file systems, but will not cross a file system boundary when
+traversing each path's directory tree.
+.sp
+This is similar to \fBfind\fP's \fB\-xdev\fP or \fB\-mount\fP flag.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.one_file_system = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_one_file_system() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.one_file_system);
+
+    let args = parse_low_raw(["--one-file-system"]).unwrap();
+    assert_eq!(true, args.one_file_system);
+
+    let args =
+        parse_low_raw(["--one-file-system", "--no-one-file-system"]).unwrap();
+    assert_eq!(false, args.one_file_system);
+}
+
+/// -o/--only-matching
+#[derive(Debug)]
+struct OnlyMatching;
+
+impl Flag for OnlyMatching {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'o')
+    }
+    fn name_long(&self) -> &'static str {
+        "only-matching"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print only matched parts of a line."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Print only the matched (non-empty) parts of a matching line, with each such
+part on a separate output line.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--only-matching does not have a negation");
+        args.only_matching = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_only_matching() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.only_matching);
+
+    let args = parse_low_raw(["--only-matching"]).unwrap();
+    assert_eq!(true, args.only_matching);
+
+    let args = parse_low_raw(["-o"]).unwrap();
+    assert_eq!(true, args.only_matching);
+}
+
+/// --path-separator
+#[derive(Debug)]
+struct PathSeparator;
+
+impl Flag for PathSeparator {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "path-separator"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("SEPARATOR")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Set the path separator for printing paths."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Set the path separator to use when printing file paths. This defaults to your
+platform's path separator, which is \fB/\fP on Unix and \fB\\\fP on Windows.
+This flag is intended for overriding the default when the environment demands
+it (e.g., cygwin). A path separator is limited to a single byte.
+.sp
+Setting this flag to an empty string reverts it to its default behavior. That
+is, the path separator is automatically chosen based on the environment.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let s = convert::string(v.unwrap_value())?;
+        let raw = Vec::unescape_bytes(&s);
+        args.path_separator = if raw.is_empty() {
+            None
+        } else if raw.len() == 1 {
+            Some(raw[0])
+        } else {
+            anyhow::bail!(
+                "A path separator must be exactly one byte, but \
+                 the given separator is {len} bytes: {sep}\n\
+                 In some shells on Windows '/' is automatically \
+                 expanded. Use '//' instead.",
+                len = raw.len(),
+                sep = s,
+            )
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_path_separator() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", "/"]).unwrap();
+    assert_eq!(Some(b'/'), args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", r"\"]).unwrap();
+    assert_eq!(Some(b'\\'), args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", r"\x00"]).unwrap();
+    assert_eq!(Some(0), args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", r"\0"]).unwrap();
+    assert_eq!(Some(0), args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", "\x00"]).unwrap();
+    assert_eq!(Some(0), args.path_separator);
+
+    let args = parse_low_raw(["--path-separator", "\0"]).unwrap();
+    assert_eq!(Some(0), args.path_separator);
+
+    let args =
+        parse_low_raw(["--path-separator", r"\x00", "--path-separator=/"])
+            .unwrap();
+    assert_eq!(Some(b'/'), args.path_separator);
+
+    let result = parse_low_raw(["--path-separator", "foo"]);
+    assert!(result.is_err(), "{result:?}");
+
+    let result = parse_low_raw(["--path-separator", r"\\x00"]);
+    assert!(result.is_err(), "{result:?}");
+}
+
+/// --passthru
+#[derive(Debug)]
+struct Passthru;
+
+impl Flag for Passthru {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "passthru"
+    }
+    fn aliases(&self) -> &'static [&'static str] {
+        &["passthrough"]
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print both matching and non-matching lines."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+Print both matching and non-matching lines.
+.sp
+Another way to achieve a similar effect is by modifying your pattern to match
+the empty string. For example, if you are searching using \fBrg\fP \fIfoo\fP,
+then using \fBrg\fP \fB'^|\fP\fIfoo\fP\fB'\fP instead will emit every line in
+every file searched, but only occurrences of \fIfoo\fP will be highlighted.
+This flag enables the same behavior without needing to modify the pattern.
+.sp
+An alternative spelling for this flag is \fB\-\-passthrough\fP.
+.sp
+This overrides the \flag{context}, \flag{after-context} and
+\flag{before-context} flags.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--passthru has no negation");
+        args.context = ContextMode::Passthru;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_passthru() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(ContextMode::default(), args.context);
+
+    let args = parse_low_raw(["--passthru"]).unwrap();
+    assert_eq!(ContextMode::Passthru, args.context);
+
+    let args = parse_low_raw(["--passthrough"]).unwrap();
+    assert_eq!(ContextMode::Passthru, args.context);
+}
+
+/// -P/--pcre2
+#[derive(Debug)]
+struct PCRE2;
+
+impl Flag for PCRE2 {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'P')
+    }
+    fn name_long(&self) -> &'static str {
+        "pcre2"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-pcre2")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Enable PCRE2 matching."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When this flag is present, ripgrep will use the PCRE2 regex engine instead of
+its default regex engine.
+.sp
+This is generally useful when you want to use features such as look-around
+or backreferences.
+.sp
+Using this flag is the same as passing \fB\-\-engine=pcre2\fP. Users may
+instead elect to use \fB\-\-engine=auto\fP to ask ripgrep to automatically
+select the right regex engine based on the patterns given. This flag and the
+\flag{engine} flag override one another.
+.sp
+Note that PCRE2 is an optional ripgrep feature. If PCRE2 wasn't included in
+your build of ripgrep, then using this flag will result in ripgrep printing
+an error message and exiting. PCRE2 may also have worse user experience in
+some cases, since it has fewer introspection APIs than ripgrep's default
+regex engine. For example, if you use a \fB\\n\fP in a PCRE2 regex without
+the \flag{multiline} flag, then ripgrep will silently fail to match anything
+instead of reporting an error immediately (like it does with the default regex
+engine).
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.engine = if v.unwrap_switch() {
+            EngineChoice::PCRE2
+        } else {
+            EngineChoice::Default
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_pcre2() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(EngineChoice::Default, args.engine);
+
+    let args = parse_low_raw(["--pcre2"]).unwrap();
+    assert_eq!(EngineChoice::PCRE2, args.engine);
+
+    let args = parse_low_raw(["-P"]).unwrap();
+    assert_eq!(EngineChoice::PCRE2, args.engine);
+
+    let args = parse_low_raw(["-P", "--no-pcre2"]).unwrap();
+    assert_eq!(EngineChoice::Default, args.engine);
+
+    let args = parse_low_raw(["--engine=auto", "-P", "--no-pcre2"]).unwrap();
+    assert_eq!(EngineChoice::Default, args.engine);
+
+    let args = parse_low_raw(["-P", "--engine=auto"]).unwrap();
+    assert_eq!(EngineChoice::Auto, args.engine);
+}
+
+/// --pcre2-version
+#[derive(Debug)]
+struct PCRE2Version;
+
+impl Flag for PCRE2Version {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "pcre2-version"
+    }
+    fn doc_category(&self) -> Category {
+        Category::OtherBehaviors
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Print the version of PCRE2 that ripgrep uses."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When this flag is present, ripgrep will print the version of PCRE2 in use,
+along with other information, and then exit. If PCRE2 is not available, then
+ripgrep will print an error message and exit with an error code.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--pcre2-version has no negation");
+        args.special = Some(SpecialMode::VersionPCRE2);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_pcre2_version() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.special);
+
+    let args = parse_low_raw(["--pcre2-version"]).unwrap();
+    assert_eq!(Some(SpecialMode::VersionPCRE2), args.special);
+}
+
+/// --pre
+#[derive(Debug)]
+struct Pre;
+
+impl Flag for Pre {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "pre"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-pre")
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("COMMAND")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Input
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Search output of COMMAND for each PATH."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+For each input \fIPATH\fP, this flag causes ripgrep to search the standard
+output of \fICOMMAND\fP \fIPATH\fP instead of the contents of \fIPATH\fP.
+This option expects the \fICOMMAND\fP program to either be a path or to be
+available in your \fBPATH\fP. Either an empty string \fICOMMAND\fP or the
+\fB\-\-no\-pre\fP flag will disable this behavior.
+.sp
+.TP 12
+\fBWARNING\fP
+When this flag is set, ripgrep will unconditionally spawn a process for every
+file that is searched. Therefore, this can incur an unnecessarily large
+performance penalty if you don't otherwise need the flexibility offered by this
+flag. One possible mitigation to this is to use the \flag{pre-glob} flag to
+limit which files a preprocessor is run with.
+.PP
+A preprocessor is not run when ripgrep is searching stdin.
+.sp
+When searching over sets of files that may require one of several
+preprocessors, \fICOMMAND\fP should be a wrapper program which first classifies
+\fIPATH\fP based on magic numbers/content or based on the \fIPATH\fP name and
+then dispatches to an appropriate preprocessor. Each \fICOMMAND\fP also has its
+standard input connected to \fIPATH\fP for convenience.
+.sp
+For example, a shell script for \fICOMMAND\fP might look like:
+.sp
+.EX
+    case "$1" in
+    *.pdf)
+        exec pdftotext "$1" -
+        ;;
+    *)
+        case $(file "$1") in
+        *Zstandard*)
+            exec pzstd -cdq
+            ;;
+        *)
+            exec cat
+            ;;
+        esac
+        ;;
+    esac
+.EE
+.sp
+The above script uses \fBpdftotext\fP to convert a PDF file to plain text. For
+all other files, the script uses the \fBfile\fP utility to sniff the type of
+the file based on its contents. If it is a compressed file in the Zstandard
+format, then \fBpzstd\fP is used to decompress the contents to stdout.
+.sp
+This overrides the \flag{search-zip} flag.
+"#
+    }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Executable
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let path = match v {
+            FlagValue::Value(v) => PathBuf::from(v),
+            FlagValue::Switch(yes) => {
+                assert!(!yes, "there is no affirmative switch for --pre");
+                args.pre = None;
+                return Ok(());
+            }
+        };
+        args.pre = if path.as_os_str().is_empty() { None } else { Some(path) };
+        if args.pre.is_some() {
+            args.search_zip = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_pre() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.pre);
+
+    let args = parse_low_raw(["--pre", "foo/bar"]).unwrap();
+    assert_eq!(Some(PathBuf::from("foo/bar")), args.pre);
+
+    let args = parse_low_raw(["--pre", ""]).unwrap();
+    assert_eq!(None, args.pre);
+
+    let args = parse_low_raw(["--pre", "foo/bar", "--pre", ""]).unwrap();
+    assert_eq!(None, args.pre);
+
+    let args = parse_low_raw(["--pre", "foo/bar", "--pre="]).unwrap();
+    assert_eq!(None, args.pre);
+
+    let args = parse_low_raw(["--pre", "foo/bar", "--no-pre"]).unwrap();
+    assert_eq!(None, args.pre);
+}
+
+/// --pre-glob
+#[derive(Debug)]
+struct PreGlob;
+
+impl Flag for PreGlob {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "pre-glob"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("GLOB")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Input
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Include or exclude files from a preprocessor."
+    }
+    fn doc_long(&self) -> &'static str {
+        r#"
+This flag works in conjunction with the \flag{pre} flag. Namely, when one or
+more \flag{pre-glob} flags are given, then only files that match the given set
+of globs will be handed to the command specified by the \flag{pre} flag. Any
+non-matching files will be searched without using the preprocessor command.
+.sp
+This flag is useful when searching many files with the \flag{pre} flag.
+Namely, it provides the ability to avoid process overhead for files that
+don't need preprocessing. For example, given the following shell script,
+\fIpre-pdftotext\fP:
+.sp
+.EX
+    #!/bin/sh
+    pdftotext "$1" -
+.EE
+.sp
+then it is possible to use \fB\-\-pre\fP \fIpre-pdftotext\fP \fB--pre-glob
+'\fP\fI*.pdf\fP\fB'\fP to make it so ripgrep only executes the
+\fIpre-pdftotext\fP command on files with a \fI.pdf\fP extension.
+.sp
+Multiple \flag{pre-glob} flags may be used. Globbing rules match
+\fBgitignore\fP globs. Precede a glob with a \fB!\fP to exclude it.
+.sp
+This flag has no effect if the \flag{pre} flag is not used.
+"#
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let glob = convert::string(v.unwrap_value())?;
+        args.pre_glob.push(glob);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_pre_glob() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.pre_glob);
+
+    let args = parse_low_raw(["--pre-glob", "*.pdf"]).unwrap();
+    assert_eq!(vec!["*.pdf".to_string()], args.pre_glob);
+
+    let args =
+        parse_low_raw(["--pre-glob", "*.pdf", "--pre-glob=foo"]).unwrap();
+    assert_eq!(vec!["*.pdf".to_string(), "foo".to_string()], args.pre_glob);
+}
+
+/// -p/--pretty
+#[derive(Debug)]
+struct Pretty;
+
+impl Flag for Pretty {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'p')
+    }
+    fn name_long(&self) -> &'static str {
+        "pretty"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Alias for colors, headings and line numbers."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This is a convenience alias for \fB\-\-color=always \-\-heading
+\-\-line\-number\fP. This flag is useful when you still want pretty output even
+if you're piping ripgrep to another program or file. For example: \fBrg -p
+\fP\fIfoo\fP \fB| less -R\fP.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--pretty has no negation");
+        args.color = ColorChoice::Always;
+        args.heading = Some(true);
+        args.line_number = Some(true);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_pretty() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(ColorChoice::Auto, args.color);
+    assert_eq!(None, args.heading);
+    assert_eq!(None, args.line_number);
+
+    let args = parse_low_raw(["--pretty"]).unwrap();
+    assert_eq!(ColorChoice::Always, args.color);
+    assert_eq!(Some(true), args.heading);
+    assert_eq!(Some(true), args.line_number);
+
+    let args = parse_low_raw(["-p"]).unwrap();
+    assert_eq!(ColorChoice::Always, args.color);
+    assert_eq!(Some(true), args.heading);
+    assert_eq!(Some(true), args.line_number);
+}
+
+/// -q/--quiet
+#[derive(Debug)]
+struct Quiet;
+
+impl Flag for Quiet {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'q')
+    }
+    fn name_long(&self) -> &'static str {
+        "quiet"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Do not print anything to stdout."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Do not print anything to stdout. If a match is found in a file, then ripgrep
+will stop searching. This is useful when ripgrep is used only for its exit code
+(which will be an error code if no matches are found).
+.sp
+When \flag{files} is used, ripgrep will stop finding files after finding the
+first file that does not match any ignore rules.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--quiet has no negation");
+        args.quiet = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_quiet() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.quiet);
+
+    let args = parse_low_raw(["--quiet"]).unwrap();
+    assert_eq!(true, args.quiet);
+
+    let args = parse_low_raw(["-q"]).unwrap();
+    assert_eq!(true, args.quiet);
+
+    // flags like -l and --json cannot override -q, regardless of order
+    let args = parse_low_raw(["-q", "--json"]).unwrap();
+    assert_eq!(true, args.quiet);
+
+    let args = parse_low_raw(["-q", "--files-with-matches"]).unwrap();
+    assert_eq!(true, args.quiet);
+
+    let args = parse_low_raw(["-q", "--files-without-match"]).unwrap();
+    assert_eq!(true, args.quiet);
 
-/// --hyperlink-format
+    let args = parse_low_raw(["-q", "--count"]).unwrap();
+    assert_eq!(true, args.quiet);
+
+    let args = parse_low_raw(["-q", "--count-matches"]).unwrap();
+    assert_eq!(true, args.quiet);
+}
+
+/// --regex-size-limit
 #[derive(Debug)]
-struct HyperlinkFormat;
+struct RegexSizeLimit;
 
-impl Flag for HyperlinkFormat {
+impl Flag for RegexSizeLimit {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "hyperlink-format"
+        "regex-size-limit"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("FORMAT")
+        Some("NUM+SUFFIX?")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Set the format of hyperlinks."
+        r"The size limit of the compiled regex."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-Set the format of hyperlinks to use when printing results. Hyperlinks make
-certain elements of ripgrep's output, such as file paths, clickable. This
-generally only works in terminal emulators that support OSC-8 hyperlinks. For
-example, the format \fBfile://{host}{path}\fP will emit an RFC 8089 hyperlink.
-To see the format that ripgrep is using, pass the \flag{debug} flag.
-.sp
-Alternatively, a format string may correspond to one of the following aliases:
-\fBdefault\fP, \fBnone\fP, \fBfile\fP, \fBgrep+\fP, \fBkitty\fP, \fBmacvim\fP,
-\fBtextmate\fP, \fBvscode\fP, \fBvscode-insiders\fP, \fBvscodium\fP. The
-alias will be replaced with a format string that is intended to work for the
-corresponding application.
+        r"
+The size limit of the compiled regex, where the compiled regex generally
+corresponds to a single object in memory that can match all of the patterns
+provided to ripgrep. The default limit is generous enough that most reasonable
+patterns (or even a small number of them) should fit.
 .sp
-The following variables are available in the format string:
+This useful to change when you explicitly want to let ripgrep spend potentially
+much more time and/or memory building a regex matcher.
 .sp
-.TP 12
-\fB{path}\fP
-Required. This is replaced with a path to a matching file. The path is
-guaranteed to be absolute and percent encoded such that it is valid to put into
-a URI. Note that a path is guaranteed to start with a /.
-.TP 12
-\fB{host}\fP
-Optional. This is replaced with your system's hostname. On Unix, this
-corresponds to calling \fBgethostname\fP. On Windows, this corresponds to
-calling \fBGetComputerNameExW\fP to fetch the system's "physical DNS hostname."
-Alternatively, if \flag{hostname-bin} was provided, then the hostname returned
-from the output of that program will be returned. If no hostname could be
-found, then this variable is replaced with the empty string.
-.TP 12
-\fB{line}\fP
-Optional. If appropriate, this is replaced with the line number of a match. If
-no line number is available (for example, if \fB\-\-no\-line\-number\fP was
-given), then it is automatically replaced with the value 1.
-.TP 12
-\fB{column}\fP
-Optional, but requires the presence of \fB{line}\fP. If appropriate, this is
-replaced with the column number of a match. If no column number is available
-(for example, if \fB\-\-no\-column\fP was given), then it is automatically
-replaced with the value 1.
-.TP 12
-\fB{wslprefix}\fP
-Optional. This is a special value that is set to
-\fBwsl$/\fP\fIWSL_DISTRO_NAME\fP, where \fIWSL_DISTRO_NAME\fP corresponds to
-the value of the equivalent environment variable. If the system is not Unix
-or if the \fIWSL_DISTRO_NAME\fP environment variable is not set, then this is
-replaced with the empty string.
-.PP
-A format string may be empty. An empty format string is equivalent to the
-\fBnone\fP alias. In this case, hyperlinks will be disabled.
+The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
+correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
+provided the input is treated as bytes.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let v = v.unwrap_value();
+        args.regex_size_limit = Some(convert::human_readable_usize(&v)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_regex_size_limit() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.regex_size_limit);
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        let args = parse_low_raw(["--regex-size-limit", "9G"]).unwrap();
+        assert_eq!(Some(9 * (1 << 30)), args.regex_size_limit);
+
+        let args = parse_low_raw(["--regex-size-limit=9G"]).unwrap();
+        assert_eq!(Some(9 * (1 << 30)), args.regex_size_limit);
+
+        let args =
+            parse_low_raw(["--regex-size-limit=9G", "--regex-size-limit=0"])
+                .unwrap();
+        assert_eq!(Some(0), args.regex_size_limit);
+    }
+
+    let args = parse_low_raw(["--regex-size-limit=0K"]).unwrap();
+    assert_eq!(Some(0), args.regex_size_limit);
+
+    let args = parse_low_raw(["--regex-size-limit=0M"]).unwrap();
+    assert_eq!(Some(0), args.regex_size_limit);
+
+    let args = parse_low_raw(["--regex-size-limit=0G"]).unwrap();
+    assert_eq!(Some(0), args.regex_size_limit);
+
+    let result =
+        parse_low_raw(["--regex-size-limit", "9999999999999999999999"]);
+    assert!(result.is_err(), "{result:?}");
+
+    let result = parse_low_raw(["--regex-size-limit", "9999999999999999G"]);
+    assert!(result.is_err(), "{result:?}");
+}
+
+/// -e/--regexp
+#[derive(Debug)]
+struct Regexp;
+
+impl Flag for Regexp {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'e')
+    }
+    fn name_long(&self) -> &'static str {
+        "regexp"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATTERN")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Input
+    }
+    fn doc_short(&self) -> &'static str {
+        r"A pattern to search for."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+A pattern to search for. This option can be provided multiple times, where
+all patterns given are searched, in addition to any patterns provided by
+\flag{file}. Lines matching at least one of the provided patterns are printed.
+This flag can also be used when searching for patterns that start with a dash.
 .sp
-At present, ripgrep does not enable hyperlinks by default. Users must opt into
-them. If you aren't sure what format to use, try \fBdefault\fP.
+For example, to search for the literal \fB\-foo\fP:
 .sp
-Like colors, when ripgrep detects that stdout is not connected to a tty, then
-hyperlinks are automatically disabled, regardless of the value of this flag.
-Users can pass \fB\-\-color=always\fP to forcefully emit hyperlinks.
+.EX
+    rg \-e \-foo
+.EE
 .sp
-Note that hyperlinks are only written when a path is also in the output
-and colors are enabled. To write hyperlinks without colors, you'll need to
-configure ripgrep to not colorize anything without actually disabling all ANSI
-escape codes completely:
+You can also use the special \fB\-\-\fP delimiter to indicate that no more
+flags will be provided. Namely, the following is equivalent to the above:
 .sp
 .EX
-    \-\-colors 'path:none' \\
-    \-\-colors 'line:none' \\
-    \-\-colors 'column:none' \\
-    \-\-colors 'match:none'
+    rg \-\- \-foo
 .EE
 .sp
-ripgrep works this way because it treats the \flag{color} flag as a proxy for
-whether ANSI escape codes should be used at all. This means that environment
-variables like \fBNO_COLOR=1\fP and \fBTERM=dumb\fP not only disable colors,
-but hyperlinks as well. Similarly, colors and hyperlinks are disabled when
-ripgrep is not writing to a tty. (Unless one forces the issue by setting
-\fB\-\-color=always\fP.)
-.sp
-If you're searching a file directly, for example:
+When \flag{file} or \flag{regexp} is used, then ripgrep treats all positional
+arguments as files or directories to search.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let regexp = convert::string(v.unwrap_value())?;
+        args.patterns.push(PatternSource::Regexp(regexp));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_regexp() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<PatternSource>::new(), args.patterns);
+
+    let args = parse_low_raw(["--regexp", "foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["--regexp=foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["-e", "foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["-efoo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["--regexp", "-foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["--regexp=-foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["-e", "-foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["-e-foo"]).unwrap();
+    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
+
+    let args = parse_low_raw(["--regexp=foo", "--regexp", "bar"]).unwrap();
+    assert_eq!(
+        vec![
+            PatternSource::Regexp("foo".to_string()),
+            PatternSource::Regexp("bar".to_string())
+        ],
+        args.patterns
+    );
+
+    // While we support invalid UTF-8 arguments in general, patterns must be
+    // valid UTF-8.
+    #[cfg(unix)]
+    {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let bytes = &[b'A', 0xFF, b'Z'][..];
+        let result = parse_low_raw([
+            OsStr::from_bytes(b"-e"),
+            OsStr::from_bytes(bytes),
+        ]);
+        assert!(result.is_err(), "{result:?}");
+    }
+
+    // Check that combining -e/--regexp and -f/--file works as expected.
+    let args = parse_low_raw(["-efoo", "-fbar"]).unwrap();
+    assert_eq!(
+        vec![
+            PatternSource::Regexp("foo".to_string()),
+            PatternSource::File(PathBuf::from("bar"))
+        ],
+        args.patterns
+    );
+
+    let args = parse_low_raw(["-efoo", "-fbar", "-equux"]).unwrap();
+    assert_eq!(
+        vec![
+            PatternSource::Regexp("foo".to_string()),
+            PatternSource::File(PathBuf::from("bar")),
+            PatternSource::Regexp("quux".to_string()),
+        ],
+        args.patterns
+    );
+}
+
+/// --remote
+#[derive(Debug)]
+struct Remote;
+
+impl Flag for Remote {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "remote"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("URL")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        "Run against a shallow clone of a remote Git repository."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Instead of searching a local path, shallow-clone \fIURL\fP (or fetch and
+check it out if it was already cloned by an earlier \flag{remote} run) into
+a cache directory, and run the command against that clone. This works with
+plain search as well as \flag{tree} and \fB\-\-analyze\fP, and is intended
+for evaluating a third-party dependency without cloning it yourself first.
 .sp
-.EX
-    rg foo path/to/file
-.EE
+The clone is cached under \fB~/.cache/outgrep/remotes\fP, keyed by URL, and
+reused (via \fBgit fetch\fP) on subsequent runs against the same URL rather
+than re-cloned from scratch.
 .sp
-then hyperlinks will not be emitted since the path given does not appear
-in the output. To make the path appear, and thus also a hyperlink, use the
-\flag{with-filename} flag.
+Use \flag{remote-ref} to select a branch, tag, or commit other than the
+repository's default branch.
 .sp
-For more information on hyperlinks in terminal emulators, see:
-https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda
-"#
+Example: --remote https://github.com/BurntSushi/ripgrep --analyze
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let v = v.unwrap_value();
-        let string = convert::str(&v)?;
-        let format = string.parse().context("invalid hyperlink format")?;
-        args.hyperlink_format = format;
+        args.remote = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_hyperlink_format() {
-    let parseformat = |format: &str| {
-        format.parse::<grep::printer::HyperlinkFormat>().unwrap()
-    };
-
+fn test_remote() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(parseformat("none"), args.hyperlink_format);
-
-    let args = parse_low_raw(["--hyperlink-format", "default"]).unwrap();
-    #[cfg(windows)]
-    assert_eq!(parseformat("file://{path}"), args.hyperlink_format);
-    #[cfg(not(windows))]
-    assert_eq!(parseformat("file://{host}{path}"), args.hyperlink_format);
-
-    let args = parse_low_raw(["--hyperlink-format", "file"]).unwrap();
-    assert_eq!(parseformat("file://{host}{path}"), args.hyperlink_format);
-
-    let args = parse_low_raw([
-        "--hyperlink-format",
-        "file",
-        "--hyperlink-format=grep+",
-    ])
-    .unwrap();
-    assert_eq!(parseformat("grep+://{path}:{line}"), args.hyperlink_format);
+    assert_eq!(None, args.remote);
 
     let args =
-        parse_low_raw(["--hyperlink-format", "file://{host}{path}#{line}"])
+        parse_low_raw(["--remote", "https://github.com/BurntSushi/ripgrep"])
             .unwrap();
     assert_eq!(
-        parseformat("file://{host}{path}#{line}"),
-        args.hyperlink_format
+        Some("https://github.com/BurntSushi/ripgrep".to_string()),
+        args.remote
     );
-
-    let result = parse_low_raw(["--hyperlink-format", "file://heythere"]);
-    assert!(result.is_err(), "{result:?}");
 }
 
-/// --iglob
+/// --remote-ref
 #[derive(Debug)]
-struct IGlob;
+struct RemoteRef;
 
-impl Flag for IGlob {
+impl Flag for RemoteRef {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "iglob"
+        "remote-ref"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("GLOB")
+        Some("REF")
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Include/exclude paths case insensitively."
+        "Branch, tag, or commit to check out with --remote."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Include or exclude files and directories for searching that match the given
-glob. This always overrides any other ignore logic. Multiple glob flags may
-be used. Globbing rules match \fB.gitignore\fP globs. Precede a glob with a
-\fB!\fP to exclude it. If multiple globs match a file or directory, the glob
-given later in the command line takes precedence. Globs used via this flag are
-matched case insensitively.
+Select which branch, tag, or commit \flag{remote} checks out, instead of
+the repository's default branch. Has no effect without \flag{remote}.
+.sp
+Example: --remote https://github.com/BurntSushi/ripgrep --remote-ref 14.1.1
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let glob = convert::string(v.unwrap_value())?;
-        args.iglobs.push(glob);
+        args.remote_ref = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_iglob() {
+fn test_remote_ref() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<String>::new(), args.iglobs);
-
-    let args = parse_low_raw(["--iglob", "foo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.iglobs);
-
-    let args = parse_low_raw(["--iglob=foo"]).unwrap();
-    assert_eq!(vec!["foo".to_string()], args.iglobs);
-
-    let args = parse_low_raw(["--iglob", "-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.iglobs);
+    assert_eq!(None, args.remote_ref);
 
-    let args = parse_low_raw(["--iglob=-foo"]).unwrap();
-    assert_eq!(vec!["-foo".to_string()], args.iglobs);
+    let args = parse_low_raw(["--remote-ref", "14.1.1"]).unwrap();
+    assert_eq!(Some("14.1.1".to_string()), args.remote_ref);
 }
 
-/// -i/--ignore-case
+/// -r/--replace
 #[derive(Debug)]
-struct IgnoreCase;
+struct Replace;
 
-impl Flag for IgnoreCase {
+impl Flag for Replace {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_short(&self) -> Option<u8> {
-        Some(b'i')
+        Some(b'r')
     }
     fn name_long(&self) -> &'static str {
-        "ignore-case"
+        "replace"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("REPLACEMENT")
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Case insensitive search."
+        r"Replace matches with the given text."
     }
     fn doc_long(&self) -> &'static str {
         r#"
-When this flag is provided, all patterns will be searched case insensitively.
-The case insensitivity rules used by ripgrep's default regex engine conform to
-Unicode's "simple" case folding rules.
+Replaces every match with the text given when printing results. Neither this
+flag nor any other ripgrep flag will modify your files.
 .sp
-This is a global option that applies to all patterns given to ripgrep.
-Individual patterns can still be matched case sensitively by using
-inline regex flags. For example, \fB(?\-i)abc\fP will match \fBabc\fP
-case sensitively even when this flag is used.
+Capture group indices (e.g., \fB$\fP\fI5\fP) and names (e.g., \fB$\fP\fIfoo\fP)
+are supported in the replacement string. Capture group indices are numbered
+based on the position of the opening parenthesis of the group, where the
+leftmost such group is \fB$\fP\fI1\fP. The special \fB$\fP\fI0\fP group
+corresponds to the entire match.
 .sp
-This flag overrides \flag{case-sensitive} and \flag{smart-case}.
+The name of a group is formed by taking the longest string of letters, numbers
+and underscores (i.e. \fB[_0-9A-Za-z]\fP) after the \fB$\fP. For example,
+\fB$\fP\fI1a\fP will be replaced with the group named \fI1a\fP, not the
+group at index \fI1\fP. If the group's name contains characters that aren't
+letters, numbers or underscores, or you want to immediately follow the group
+with another string, the name should be put inside braces. For example,
+\fB${\fP\fI1\fP\fB}\fP\fIa\fP will take the content of the group at index
+\fI1\fP and append \fIa\fP to the end of it.
+.sp
+If an index or name does not refer to a valid capture group, it will be
+replaced with an empty string.
+.sp
+In shells such as Bash and zsh, you should wrap the pattern in single quotes
+instead of double quotes. Otherwise, capture group indices will be replaced by
+expanded shell variables which will most likely be empty.
+.sp
+To write a literal \fB$\fP, use \fB$$\fP.
+.sp
+Note that the replacement by default replaces each match, and not the entire
+line. To replace the entire line, you should match the entire line.
+.sp
+This flag can be used with the \flag{only-matching} flag.
 "#
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "flag has no negation");
-        args.case = CaseMode::Insensitive;
+        args.replace = Some(convert::string(v.unwrap_value())?.into());
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_ignore_case() {
+fn test_replace() {
+    use bstr::BString;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(CaseMode::Sensitive, args.case);
+    assert_eq!(None, args.replace);
 
-    let args = parse_low_raw(["--ignore-case"]).unwrap();
-    assert_eq!(CaseMode::Insensitive, args.case);
+    let args = parse_low_raw(["--replace", "foo"]).unwrap();
+    assert_eq!(Some(BString::from("foo")), args.replace);
 
-    let args = parse_low_raw(["-i"]).unwrap();
-    assert_eq!(CaseMode::Insensitive, args.case);
+    let args = parse_low_raw(["--replace", "-foo"]).unwrap();
+    assert_eq!(Some(BString::from("-foo")), args.replace);
 
-    let args = parse_low_raw(["-i", "-s"]).unwrap();
-    assert_eq!(CaseMode::Sensitive, args.case);
+    let args = parse_low_raw(["-r", "foo"]).unwrap();
+    assert_eq!(Some(BString::from("foo")), args.replace);
 
-    let args = parse_low_raw(["-s", "-i"]).unwrap();
-    assert_eq!(CaseMode::Insensitive, args.case);
+    let args = parse_low_raw(["-r", "foo", "-rbar"]).unwrap();
+    assert_eq!(Some(BString::from("bar")), args.replace);
+
+    let args = parse_low_raw(["-r", "foo", "-r", ""]).unwrap();
+    assert_eq!(Some(BString::from("")), args.replace);
 }
 
-/// --ignore-file
+/// -z/--search-zip
 #[derive(Debug)]
-struct IgnoreFile;
+struct SearchZip;
 
-impl Flag for IgnoreFile {
+impl Flag for SearchZip {
     fn is_switch(&self) -> bool {
-        false
+        true
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'z')
     }
     fn name_long(&self) -> &'static str {
-        "ignore-file"
+        "search-zip"
     }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("PATH")
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-search-zip")
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Input
     }
     fn doc_short(&self) -> &'static str {
-        r"Specify additional ignore files."
+        r"Search in compressed files."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specifies a path to one or more \fBgitignore\fP formatted rules files.
-These patterns are applied after the patterns found in \fB.gitignore\fP,
-\fB.rgignore\fP and \fB.ignore\fP are applied and are matched relative to the
-current working directory. Multiple additional ignore files can be specified
-by using this flag repeatedly. When specifying multiple ignore files, earlier
-files have lower precedence than later files.
+This flag instructs ripgrep to search in compressed files. Currently gzip,
+bzip2, xz, LZ4, LZMA, Brotli and Zstd files are supported. This option expects
+the decompression binaries (such as \fBgzip\fP) to be available in your
+\fBPATH\fP. If the required binaries are not found, then ripgrep will not
+emit an error messages by default. Use the \flag{debug} flag to see more
+information.
 .sp
-If you are looking for a way to include or exclude files and directories
-directly on the command line, then use \flag{glob} instead.
+Note that this flag does not make ripgrep search archive formats as directory
+trees. It only makes ripgrep detect compressed files and then decompress them
+before searching their contents as it would any other file.
+.sp
+This overrides the \flag{pre} flag.
 "
     }
-    fn completion_type(&self) -> CompletionType {
-        CompletionType::Filename
-    }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let path = PathBuf::from(v.unwrap_value());
-        args.ignore_file.push(path);
+        args.search_zip = if v.unwrap_switch() {
+            args.pre = None;
+            true
+        } else {
+            false
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_ignore_file() {
+fn test_search_zip() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<PathBuf>::new(), args.ignore_file);
+    assert_eq!(false, args.search_zip);
 
-    let args = parse_low_raw(["--ignore-file", "foo"]).unwrap();
-    assert_eq!(vec![PathBuf::from("foo")], args.ignore_file);
+    let args = parse_low_raw(["--search-zip"]).unwrap();
+    assert_eq!(true, args.search_zip);
+
+    let args = parse_low_raw(["-z"]).unwrap();
+    assert_eq!(true, args.search_zip);
+
+    let args = parse_low_raw(["-z", "--no-search-zip"]).unwrap();
+    assert_eq!(false, args.search_zip);
+
+    let args = parse_low_raw(["--pre=foo", "--no-search-zip"]).unwrap();
+    assert_eq!(Some(PathBuf::from("foo")), args.pre);
+    assert_eq!(false, args.search_zip);
+
+    let args = parse_low_raw(["--pre=foo", "--search-zip"]).unwrap();
+    assert_eq!(None, args.pre);
+    assert_eq!(true, args.search_zip);
 
-    let args = parse_low_raw(["--ignore-file", "foo", "--ignore-file", "bar"])
-        .unwrap();
-    assert_eq!(
-        vec![PathBuf::from("foo"), PathBuf::from("bar")],
-        args.ignore_file
-    );
+    let args = parse_low_raw(["--pre=foo", "-z", "--no-search-zip"]).unwrap();
+    assert_eq!(None, args.pre);
+    assert_eq!(false, args.search_zip);
 }
 
-/// --ignore-file-case-insensitive
+/// -S/--smart-case
 #[derive(Debug)]
-struct IgnoreFileCaseInsensitive;
+struct SmartCase;
 
-impl Flag for IgnoreFileCaseInsensitive {
+impl Flag for SmartCase {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_long(&self) -> &'static str {
-        "ignore-file-case-insensitive"
+    fn name_short(&self) -> Option<u8> {
+        Some(b'S')
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-ignore-file-case-insensitive")
+    fn name_long(&self) -> &'static str {
+        "smart-case"
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Process ignore files case insensitively."
+        r"Smart case search."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Process ignore files (\fB.gitignore\fP, \fB.ignore\fP, etc.) case
-insensitively. Note that this comes with a performance penalty and is most
-useful on case insensitive file systems (such as Windows).
+This flag instructs ripgrep to searches case insensitively if the pattern is
+all lowercase. Otherwise, ripgrep will search case sensitively.
+.sp
+A pattern is considered all lowercase if both of the following rules hold:
+.sp
+.IP \(bu 3n
+First, the pattern contains at least one literal character. For example,
+\fBa\\w\fP contains a literal (\fBa\fP) but just \fB\\w\fP does not.
+.sp
+.IP \(bu 3n
+Second, of the literals in the pattern, none of them are considered to be
+uppercase according to Unicode. For example, \fBfoo\\pL\fP has no uppercase
+literals but \fBFoo\\pL\fP does.
+.PP
+This overrides the \flag{case-sensitive} and \flag{ignore-case} flags.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.ignore_file_case_insensitive = v.unwrap_switch();
+        assert!(v.unwrap_switch(), "--smart-case flag has no negation");
+        args.case = CaseMode::Smart;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_ignore_file_case_insensitive() {
+fn test_smart_case() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.ignore_file_case_insensitive);
+    assert_eq!(CaseMode::Sensitive, args.case);
 
-    let args = parse_low_raw(["--ignore-file-case-insensitive"]).unwrap();
-    assert_eq!(true, args.ignore_file_case_insensitive);
+    let args = parse_low_raw(["--smart-case"]).unwrap();
+    assert_eq!(CaseMode::Smart, args.case);
 
-    let args = parse_low_raw([
-        "--ignore-file-case-insensitive",
-        "--no-ignore-file-case-insensitive",
-    ])
-    .unwrap();
-    assert_eq!(false, args.ignore_file_case_insensitive);
+    let args = parse_low_raw(["-S"]).unwrap();
+    assert_eq!(CaseMode::Smart, args.case);
 
-    let args = parse_low_raw([
-        "--no-ignore-file-case-insensitive",
-        "--ignore-file-case-insensitive",
-    ])
-    .unwrap();
-    assert_eq!(true, args.ignore_file_case_insensitive);
+    let args = parse_low_raw(["-S", "-s"]).unwrap();
+    assert_eq!(CaseMode::Sensitive, args.case);
+
+    let args = parse_low_raw(["-S", "-i"]).unwrap();
+    assert_eq!(CaseMode::Insensitive, args.case);
+
+    let args = parse_low_raw(["-s", "-S"]).unwrap();
+    assert_eq!(CaseMode::Smart, args.case);
+
+    let args = parse_low_raw(["-i", "-S"]).unwrap();
+    assert_eq!(CaseMode::Smart, args.case);
 }
 
-/// --include-zero
+/// --sort-files
 #[derive(Debug)]
-struct IncludeZero;
+struct SortFiles;
 
-impl Flag for IncludeZero {
+impl Flag for SortFiles {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "include-zero"
+        "sort-files"
     }
     fn name_negated(&self) -> Option<&'static str> {
-        Some("no-include-zero")
+        Some("no-sort-files")
     }
     fn doc_category(&self) -> Category {
         Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Include zero matches in summary output."
+        r"(DEPRECATED) Sort results by file path."
     }
     fn doc_long(&self) -> &'static str {
         r"
-When used with \flag{count} or \flag{count-matches}, this causes ripgrep to
-print the number of matches for each file even if there were zero matches. This
-is disabled by default but can be enabled to make ripgrep behave more like
-grep.
+DEPRECATED. Use \fB\-\-sort=path\fP instead.
+.sp
+This flag instructs ripgrep to sort search results by file path
+lexicographically in ascending order. Note that this currently disables all
+parallelism and runs search in a single thread.
+.sp
+This flag overrides \flag{sort} and \flag{sortr}.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.include_zero = v.unwrap_switch();
+        args.sort = if v.unwrap_switch() {
+            Some(SortMode { reverse: false, kind: SortModeKind::Path })
+        } else {
+            None
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_include_zero() {
+fn test_sort_files() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.include_zero);
+    assert_eq!(None, args.sort);
 
-    let args = parse_low_raw(["--include-zero"]).unwrap();
-    assert_eq!(true, args.include_zero);
+    let args = parse_low_raw(["--sort-files"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
+        args.sort
+    );
 
-    let args = parse_low_raw(["--include-zero", "--no-include-zero"]).unwrap();
-    assert_eq!(false, args.include_zero);
+    let args = parse_low_raw(["--sort-files", "--no-sort-files"]).unwrap();
+    assert_eq!(None, args.sort);
+
+    let args = parse_low_raw(["--sort", "created", "--sort-files"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
+        args.sort
+    );
+
+    let args = parse_low_raw(["--sort-files", "--sort", "created"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Created }),
+        args.sort
+    );
+
+    let args = parse_low_raw(["--sortr", "created", "--sort-files"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
+        args.sort
+    );
+
+    let args = parse_low_raw(["--sort-files", "--sortr", "created"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: true, kind: SortModeKind::Created }),
+        args.sort
+    );
+
+    let args = parse_low_raw(["--sort=path", "--no-sort-files"]).unwrap();
+    assert_eq!(None, args.sort);
+
+    let args = parse_low_raw(["--sortr=path", "--no-sort-files"]).unwrap();
+    assert_eq!(None, args.sort);
 }
 
-/// -v/--invert-match
+/// --sort
 #[derive(Debug)]
-struct InvertMatch;
+struct Sort;
 
-impl Flag for InvertMatch {
+impl Flag for Sort {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'v')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "invert-match"
+        "sort"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-invert-match")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("SORTBY")
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Invert matching."
+        r"Sort results in ascending order."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag inverts matching. That is, instead of printing lines that match,
-ripgrep will print lines that don't match.
+This flag enables sorting of results in ascending order. The possible values
+for this flag are:
 .sp
-Note that this only inverts line-by-line matching. For example, combining this
-flag with \flag{files-with-matches} will emit files that contain any lines
-that do not match the patterns given. That's not the same as, for example,
-\flag{files-without-match}, which will emit files that do not contain any
-matching lines.
+.TP 12
+\fBnone\fP
+(Default) Do not sort results. Fastest. Can be multi-threaded.
+.TP 12
+\fBpath\fP
+Sort by file path. Always single-threaded. The order is determined by sorting
+files in each directory entry during traversal. This means that given the files
+\fBa/b\fP and \fBa+\fP, the latter will sort after the former even though
+\fB+\fP would normally sort before \fB/\fP.
+.TP 12
+\fBmodified\fP
+Sort by the last modified time on a file. Always single-threaded.
+.TP 12
+\fBaccessed\fP
+Sort by the last accessed time on a file. Always single-threaded.
+.TP 12
+\fBcreated\fP
+Sort by the creation time on a file. Always single-threaded.
+.PP
+If the chosen (manually or by-default) sorting criteria isn't available on your
+system (for example, creation time is not available on ext4 file systems), then
+ripgrep will attempt to detect this, print an error and exit without searching.
+.sp
+To sort results in reverse or descending order, use the \flag{sortr} flag.
+Also, this flag overrides \flag{sortr}.
+.sp
+Note that sorting results currently always forces ripgrep to abandon
+parallelism and run in a single thread.
 "
     }
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["none", "path", "modified", "accessed", "created"]
+    }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.invert_match = v.unwrap_switch();
+        let kind = match convert::str(&v.unwrap_value())? {
+            "none" => {
+                args.sort = None;
+                return Ok(());
+            }
+            "path" => SortModeKind::Path,
+            "modified" => SortModeKind::LastModified,
+            "accessed" => SortModeKind::LastAccessed,
+            "created" => SortModeKind::Created,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
+        args.sort = Some(SortMode { reverse: false, kind });
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_invert_match() {
+fn test_sort() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.invert_match);
+    assert_eq!(None, args.sort);
 
-    let args = parse_low_raw(["--invert-match"]).unwrap();
-    assert_eq!(true, args.invert_match);
+    let args = parse_low_raw(["--sort", "path"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
+        args.sort
+    );
 
-    let args = parse_low_raw(["-v"]).unwrap();
-    assert_eq!(true, args.invert_match);
+    let args = parse_low_raw(["--sort", "path", "--sort=created"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Created }),
+        args.sort
+    );
 
-    let args = parse_low_raw(["-v", "--no-invert-match"]).unwrap();
-    assert_eq!(false, args.invert_match);
+    let args = parse_low_raw(["--sort=none"]).unwrap();
+    assert_eq!(None, args.sort);
+
+    let args = parse_low_raw(["--sort", "path", "--sort=none"]).unwrap();
+    assert_eq!(None, args.sort);
 }
 
-/// --json
+/// --sortr
 #[derive(Debug)]
-struct JSON;
+struct Sortr;
 
-impl Flag for JSON {
+impl Flag for Sortr {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "json"
+        "sortr"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-json")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("SORTBY")
     }
     fn doc_category(&self) -> Category {
-        Category::OutputModes
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Show search results in a JSON Lines format."
+        r"Sort results in descending order."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Enable printing results in a JSON Lines format.
-.sp
-When this flag is provided, ripgrep will emit a sequence of messages, each
-encoded as a JSON object, where there are five different message types:
+This flag enables sorting of results in descending order. The possible values
+for this flag are:
 .sp
 .TP 12
-\fBbegin\fP
-A message that indicates a file is being searched and contains at least one
-match.
+\fBnone\fP
+(Default) Do not sort results. Fastest. Can be multi-threaded.
 .TP 12
-\fBend\fP
-A message the indicates a file is done being searched. This message also
-include summary statistics about the search for a particular file.
+\fBpath\fP
+Sort by file path. Always single-threaded. The order is determined by sorting
+files in each directory entry during traversal. This means that given the files
+\fBa/b\fP and \fBa+\fP, the latter will sort before the former even though
+\fB+\fP would normally sort after \fB/\fP when doing a reverse lexicographic
+sort.
 .TP 12
-\fBmatch\fP
-A message that indicates a match was found. This includes the text and offsets
-of the match.
+\fBmodified\fP
+Sort by the last modified time on a file. Always single-threaded.
 .TP 12
-\fBcontext\fP
-A message that indicates a contextual line was found. This includes the text of
-the line, along with any match information if the search was inverted.
+\fBaccessed\fP
+Sort by the last accessed time on a file. Always single-threaded.
 .TP 12
-\fBsummary\fP
-The final message emitted by ripgrep that contains summary statistics about the
-search across all files.
+\fBcreated\fP
+Sort by the creation time on a file. Always single-threaded.
 .PP
-Since file paths or the contents of files are not guaranteed to be valid
-UTF-8 and JSON itself must be representable by a Unicode encoding, ripgrep
-will emit all data elements as objects with one of two keys: \fBtext\fP or
-\fBbytes\fP. \fBtext\fP is a normal JSON string when the data is valid UTF-8
-while \fBbytes\fP is the base64 encoded contents of the data.
-.sp
-The JSON Lines format is only supported for showing search results. It cannot
-be used with other flags that emit other types of output, such as \flag{files},
-\flag{files-with-matches}, \flag{files-without-match}, \flag{count} or
-\flag{count-matches}. ripgrep will report an error if any of the aforementioned
-flags are used in concert with \flag{json}.
+If the chosen (manually or by-default) sorting criteria isn't available on your
+system (for example, creation time is not available on ext4 file systems), then
+ripgrep will attempt to detect this, print an error and exit without searching.
 .sp
-Other flags that control aspects of the standard output such as
-\flag{only-matching}, \flag{heading}, \flag{replace}, \flag{max-columns}, etc.,
-have no effect when \flag{json} is set. However, enabling JSON output will
-always implicitly and unconditionally enable \flag{stats}.
+To sort results in ascending order, use the \flag{sort} flag. Also, this flag
+overrides \flag{sort}.
 .sp
-A more complete description of the JSON format used can be found here:
-\fIhttps://docs.rs/grep-printer/*/grep_printer/struct.JSON.html\fP.
+Note that sorting results currently always forces ripgrep to abandon
+parallelism and run in a single thread.
 "
     }
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["none", "path", "modified", "accessed", "created"]
+    }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        if v.unwrap_switch() {
-            args.mode.update(Mode::Search(SearchMode::JSON));
-        } else if matches!(args.mode, Mode::Search(SearchMode::JSON)) {
-            // --no-json only reverts to the default mode if the mode is
-            // JSON, otherwise it's a no-op.
-            args.mode.update(Mode::Search(SearchMode::Standard));
-        }
+        let kind = match convert::str(&v.unwrap_value())? {
+            "none" => {
+                args.sort = None;
+                return Ok(());
+            }
+            "path" => SortModeKind::Path,
+            "modified" => SortModeKind::LastModified,
+            "accessed" => SortModeKind::LastAccessed,
+            "created" => SortModeKind::Created,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
+        args.sort = Some(SortMode { reverse: true, kind });
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_json() {
+fn test_sortr() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+    assert_eq!(None, args.sort);
 
-    let args = parse_low_raw(["--json"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::JSON), args.mode);
+    let args = parse_low_raw(["--sortr", "path"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: true, kind: SortModeKind::Path }),
+        args.sort
+    );
 
-    let args = parse_low_raw(["--json", "--no-json"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::Standard), args.mode);
+    let args = parse_low_raw(["--sortr", "path", "--sortr=created"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: true, kind: SortModeKind::Created }),
+        args.sort
+    );
 
-    let args = parse_low_raw(["--json", "--files", "--no-json"]).unwrap();
-    assert_eq!(Mode::Files, args.mode);
+    let args = parse_low_raw(["--sortr=none"]).unwrap();
+    assert_eq!(None, args.sort);
 
-    let args = parse_low_raw(["--json", "-l", "--no-json"]).unwrap();
-    assert_eq!(Mode::Search(SearchMode::FilesWithMatches), args.mode);
+    let args = parse_low_raw(["--sortr", "path", "--sortr=none"]).unwrap();
+    assert_eq!(None, args.sort);
+
+    let args = parse_low_raw(["--sort=path", "--sortr=path"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: true, kind: SortModeKind::Path }),
+        args.sort
+    );
+
+    let args = parse_low_raw(["--sortr=path", "--sort=path"]).unwrap();
+    assert_eq!(
+        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
+        args.sort
+    );
 }
 
-/// --line-buffered
+/// --sort-parallel
 #[derive(Debug)]
-struct LineBuffered;
+struct SortParallel;
 
-impl Flag for LineBuffered {
+impl Flag for SortParallel {
     fn is_switch(&self) -> bool {
         true
     }
+
     fn name_long(&self) -> &'static str {
-        "line-buffered"
+        "sort-parallel"
     }
+
     fn name_negated(&self) -> Option<&'static str> {
-        Some("no-line-buffered")
+        Some("no-sort-parallel")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::OtherBehaviors
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Force line buffering."
+        r"Keep most parallelism when combined with --sort path."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When enabled, ripgrep will always use line buffering. That is, whenever a
-matching line is found, it will be flushed to stdout immediately. This is the
-default when ripgrep's stdout is connected to a tty, but otherwise, ripgrep
-will use block buffering, which is typically faster. This flag forces ripgrep
-to use line buffering even if it would otherwise use block buffering. This is
-typically useful in shell pipelines, for example:
-.sp
-.EX
-    tail -f something.log | rg foo --line-buffered | rg bar
-.EE
-.sp
-This overrides the \flag{block-buffered} flag.
+By default, \flag{sort} forces single-threaded search, since parallel search
+can't otherwise promise any particular output order. When this flag is
+combined with \flag{sort}\fB=path\fP (ascending, not \flag{sortr}), outgrep
+instead walks and sorts the file list up front on a single thread, the same
+as it always has, but then searches that sorted list in parallel and uses a
+reorder buffer to print each file's output only once every file before it
+has also finished. This keeps most of the parallel speedup while producing
+output identical to single-threaded \flag{sort}\fB=path\fP.
+.sp
+This flag has no effect unless combined with \flag{sort}\fB=path\fP; it is
+silently ignored for \flag{sortr} and for sorting by modification, access,
+or creation time, which all still force single-threaded search.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.buffer = if v.unwrap_switch() {
-            BufferMode::Line
-        } else {
-            BufferMode::Auto
-        };
+        args.sort_parallel = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_line_buffered() {
+fn test_sort_parallel() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(BufferMode::Auto, args.buffer);
+    assert_eq!(false, args.sort_parallel);
 
-    let args = parse_low_raw(["--line-buffered"]).unwrap();
-    assert_eq!(BufferMode::Line, args.buffer);
+    let args = parse_low_raw(["--sort-parallel"]).unwrap();
+    assert_eq!(true, args.sort_parallel);
 
     let args =
-        parse_low_raw(["--line-buffered", "--no-line-buffered"]).unwrap();
-    assert_eq!(BufferMode::Auto, args.buffer);
-
-    let args = parse_low_raw(["--line-buffered", "--block-buffered"]).unwrap();
-    assert_eq!(BufferMode::Block, args.buffer);
+        parse_low_raw(["--sort-parallel", "--no-sort-parallel"]).unwrap();
+    assert_eq!(false, args.sort_parallel);
 }
 
-/// -n/--line-number
+/// --stats
 #[derive(Debug)]
-struct LineNumber;
+struct Stats;
 
-impl Flag for LineNumber {
+impl Flag for Stats {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'n')
-    }
     fn name_long(&self) -> &'static str {
-        "line-number"
+        "stats"
+    }
+    fn name_negated(&self) -> Option<&'static str> {
+        Some("no-stats")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Logging
     }
     fn doc_short(&self) -> &'static str {
-        r"Show line numbers."
+        r"Print statistics about the search."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Show line numbers (1-based).
+When enabled, ripgrep will print aggregate statistics about the search. When
+this flag is present, ripgrep will print at least the following stats to
+stdout at the end of the search: number of matched lines, number of files with
+matches, number of files searched, and the time taken for the entire search to
+complete.
 .sp
-This is enabled by default when stdout is connected to a tty.
+This set of aggregate statistics may expand over time.
 .sp
-This flag can be disabled by \flag{no-line-number}.
+This flag is always and implicitly enabled when \flag{json} is used.
+.sp
+Note that this flag has no effect if \flag{files}, \flag{files-with-matches} or
+\flag{files-without-match} is passed.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--line-number has no automatic negation");
-        args.line_number = Some(true);
+        args.stats = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_line_number() {
+fn test_stats() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.line_number);
-
-    let args = parse_low_raw(["--line-number"]).unwrap();
-    assert_eq!(Some(true), args.line_number);
+    assert_eq!(false, args.stats);
 
-    let args = parse_low_raw(["-n"]).unwrap();
-    assert_eq!(Some(true), args.line_number);
+    let args = parse_low_raw(["--stats"]).unwrap();
+    assert_eq!(true, args.stats);
 
-    let args = parse_low_raw(["-n", "--no-line-number"]).unwrap();
-    assert_eq!(Some(false), args.line_number);
+    let args = parse_low_raw(["--stats", "--no-stats"]).unwrap();
+    assert_eq!(false, args.stats);
 }
 
-/// -N/--no-line-number
+/// --stop-on-nonmatch
 #[derive(Debug)]
-struct LineNumberNo;
+struct StopOnNonmatch;
 
-impl Flag for LineNumberNo {
+impl Flag for StopOnNonmatch {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'N')
-    }
     fn name_long(&self) -> &'static str {
-        "no-line-number"
+        "stop-on-nonmatch"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Suppress line numbers."
+        r"Stop searching after a non-match."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Suppress line numbers.
-.sp
-Line numbers are off by default when stdout is not connected to a tty.
+Enabling this option will cause ripgrep to stop reading a file once it
+encounters a non-matching line after it has encountered a matching line.
+This is useful if it is expected that all matches in a given file will be on
+sequential lines, for example due to the lines being sorted.
 .sp
-Line numbers can be forcefully turned on by \flag{line-number}.
+This overrides the \flag{multiline} flag.
 "
     }
 
-    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(
-            v.unwrap_switch(),
-            "--no-line-number has no automatic negation"
-        );
-        args.line_number = Some(false);
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--stop-on-nonmatch has no negation");
+        args.stop_on_nonmatch = true;
+        args.multiline = false;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_line_number() {
+fn test_stop_on_nonmatch() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.line_number);
+    assert_eq!(false, args.stop_on_nonmatch);
 
-    let args = parse_low_raw(["--no-line-number"]).unwrap();
-    assert_eq!(Some(false), args.line_number);
+    let args = parse_low_raw(["--stop-on-nonmatch"]).unwrap();
+    assert_eq!(true, args.stop_on_nonmatch);
 
-    let args = parse_low_raw(["-N"]).unwrap();
-    assert_eq!(Some(false), args.line_number);
+    let args = parse_low_raw(["--stop-on-nonmatch", "-U"]).unwrap();
+    assert_eq!(true, args.multiline);
+    assert_eq!(false, args.stop_on_nonmatch);
 
-    let args = parse_low_raw(["-N", "--line-number"]).unwrap();
-    assert_eq!(Some(true), args.line_number);
+    let args = parse_low_raw(["-U", "--stop-on-nonmatch"]).unwrap();
+    assert_eq!(false, args.multiline);
+    assert_eq!(true, args.stop_on_nonmatch);
+
+    let args =
+        parse_low_raw(["--stop-on-nonmatch", "--no-multiline"]).unwrap();
+    assert_eq!(false, args.multiline);
+    assert_eq!(true, args.stop_on_nonmatch);
 }
 
-/// -x/--line-regexp
+/// --no-syntax-highlight
 #[derive(Debug)]
-struct LineRegexp;
+struct NoSyntaxHighlight;
 
-impl Flag for LineRegexp {
+impl Flag for NoSyntaxHighlight {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'x')
-    }
     fn name_long(&self) -> &'static str {
-        "line-regexp"
+        "no-syntax-highlight"
     }
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Output
     }
     fn doc_short(&self) -> &'static str {
-        r"Show matches surrounded by line boundaries."
+        "Disable the symbol summary line in AST context mode."
     }
     fn doc_long(&self) -> &'static str {
         r"
-When enabled, ripgrep will only show matches surrounded by line boundaries.
-This is equivalent to surrounding every pattern with \fB^\fP and \fB$\fP. In
-other words, this only prints lines where the entire line participates in a
-match.
+Disable the \fB--\fP \fIkind name\fP\fB, ... --\fP summary line printed
+ahead of each file's matches when using --enclosing-symbol (AST context
+mode), naming the symbols shown below it.
 .sp
-This overrides the \flag{word-regexp} flag.
+This flag has no effect outside of --enclosing-symbol mode, or when writing
+JSON output, which has no place for a summary line outside its structured
+messages.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--line-regexp has no negation");
-        args.boundary = Some(BoundaryMode::Line);
+        // Since this is --no-syntax-highlight, we invert the switch
+        args.syntax_highlighting = !v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_line_regexp() {
+fn test_no_syntax_highlight() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.boundary);
-
-    let args = parse_low_raw(["--line-regexp"]).unwrap();
-    assert_eq!(Some(BoundaryMode::Line), args.boundary);
+    assert_eq!(true, args.syntax_highlighting); // Default is now true
 
-    let args = parse_low_raw(["-x"]).unwrap();
-    assert_eq!(Some(BoundaryMode::Line), args.boundary);
+    let args = parse_low_raw(["--no-syntax-highlight"]).unwrap();
+    assert_eq!(false, args.syntax_highlighting); // Disabled with flag
 }
 
-/// -M/--max-columns
+/// --tests-only
 #[derive(Debug)]
-struct MaxColumns;
+struct TestsOnly;
 
-impl Flag for MaxColumns {
+impl Flag for TestsOnly {
     fn is_switch(&self) -> bool {
-        false
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'M')
+        true
     }
     fn name_long(&self) -> &'static str {
-        "max-columns"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM")
+        "tests-only"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Omit lines longer than this limit."
+        "Scope search, analysis, and semantic indexing to test code."
     }
     fn doc_long(&self) -> &'static str {
         r"
-When given, ripgrep will omit lines longer than this limit in bytes. Instead of
-printing long lines, only the number of matches in that line is printed.
+Only consider files detected as tests: files under a \fBtests/\fP or
+\fBtest/\fP directory, files matching common test naming conventions (such
+as \fB*_test.rs\fP, \fBtest_*.py\fP, or \fB*.spec.ts\fP), and files whose
+contents contain test markers such as Rust's \fB#[test]\fP, Python's
+\fBdef test_\fP, or Go's \fBfunc Test\fP.
 .sp
-When this flag is omitted or is set to \fB0\fP, then it has no effect.
+This scoping applies to regular search, \flag{analyze}'s production vs. test
+line-of-code split, and semantic indexing.
+.sp
+This flag overrides \flag{no-tests} if both are given.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let max = convert::u64(&v.unwrap_value())?;
-        args.max_columns = if max == 0 { None } else { Some(max) };
+        assert!(v.unwrap_switch(), "flag has no negation");
+        args.test_scope = TestScope::TestsOnly;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_max_columns() {
+fn test_tests_only() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.max_columns);
-
-    let args = parse_low_raw(["--max-columns", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_columns);
+    assert_eq!(TestScope::All, args.test_scope);
 
-    let args = parse_low_raw(["-M", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_columns);
-
-    let args = parse_low_raw(["-M5"]).unwrap();
-    assert_eq!(Some(5), args.max_columns);
-
-    let args = parse_low_raw(["--max-columns", "5", "-M0"]).unwrap();
-    assert_eq!(None, args.max_columns);
+    let args = parse_low_raw(["--tests-only"]).unwrap();
+    assert_eq!(TestScope::TestsOnly, args.test_scope);
 }
 
-/// --max-columns-preview
+/// --no-tests
 #[derive(Debug)]
-struct MaxColumnsPreview;
+struct NoTests;
 
-impl Flag for MaxColumnsPreview {
+impl Flag for NoTests {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "max-columns-preview"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-max-columns-preview")
+        "no-tests"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Show preview for lines exceeding the limit."
+        "Exclude test code from search, analysis, and semantic indexing."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Prints a preview for lines exceeding the configured max column limit.
-.sp
-When the \flag{max-columns} flag is used, ripgrep will by default completely
-replace any line that is too long with a message indicating that a matching
-line was removed. When this flag is combined with \flag{max-columns}, a preview
-of the line (corresponding to the limit size) is shown instead, where the part
-of the line exceeding the limit is not shown.
+Exclude files detected as tests, using the same detection rules as
+\flag{tests-only}.
 .sp
-If the \flag{max-columns} flag is not set, then this has no effect.
+This flag overrides \flag{tests-only} if both are given.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.max_columns_preview = v.unwrap_switch();
+        assert!(v.unwrap_switch(), "flag has no negation");
+        args.test_scope = TestScope::NoTests;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_max_columns_preview() {
+fn test_no_tests() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.max_columns_preview);
+    assert_eq!(TestScope::All, args.test_scope);
 
-    let args = parse_low_raw(["--max-columns-preview"]).unwrap();
-    assert_eq!(true, args.max_columns_preview);
+    let args = parse_low_raw(["--no-tests"]).unwrap();
+    assert_eq!(TestScope::NoTests, args.test_scope);
 
     let args =
-        parse_low_raw(["--max-columns-preview", "--no-max-columns-preview"])
-            .unwrap();
-    assert_eq!(false, args.max_columns_preview);
+        parse_low_raw(["--tests-only", "--no-tests"]).unwrap();
+    assert_eq!(TestScope::NoTests, args.test_scope);
 }
 
-/// -m/--max-count
+/// --analyze-sort
 #[derive(Debug)]
-struct MaxCount;
+struct AnalyzeSort;
 
-impl Flag for MaxCount {
+impl Flag for AnalyzeSort {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'm')
-    }
+
     fn name_long(&self) -> &'static str {
-        "max-count"
+        "analyze-sort"
     }
+
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM")
+        Some("FIELD")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Search
+        Category::Filter
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Limit the number of matching lines."
+        "Sort --analyze's per-file output by a CodeMetrics field."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Limit the number of matching lines per file searched to \fINUM\fP.
+Sort the per-file lines printed by \flag{analyze} by \fIFIELD\fP instead of
+file path. \fIFIELD\fP can be one of: \fBpath\fP (the default), \fBloc\fP,
+\fBcomplexity\fP (cyclomatic), \fBcognitive\fP (cognitive complexity),
+\fBnesting-depth\fP, or \fBfunction-length\fP.
 .sp
-Note that \fB0\fP is a legal value but not likely to be useful. When used,
-ripgrep won't search anything.
+Combine with \flag{analyze-min} to gate output on the same field, e.g. to
+see only the most complex files first.
+.sp
+Example: --analyze-sort complexity
 "
     }
 
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &[
+            "path",
+            "loc",
+            "complexity",
+            "cognitive",
+            "nesting-depth",
+            "function-length",
+        ]
+    }
+
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.max_count = Some(convert::u64(&v.unwrap_value())?);
+        args.analyze_sort = match convert::str(&v.unwrap_value())? {
+            "path" => AnalyzeSortField::Path,
+            "loc" => AnalyzeSortField::Loc,
+            "complexity" => AnalyzeSortField::Complexity,
+            "cognitive" => AnalyzeSortField::CognitiveComplexity,
+            "nesting-depth" => AnalyzeSortField::NestingDepth,
+            "function-length" => AnalyzeSortField::FunctionLength,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_max_count() {
+fn test_analyze_sort() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.max_count);
+    assert_eq!(AnalyzeSortField::Path, args.analyze_sort);
 
-    let args = parse_low_raw(["--max-count", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_count);
+    let args = parse_low_raw(["--analyze-sort", "complexity"]).unwrap();
+    assert_eq!(AnalyzeSortField::Complexity, args.analyze_sort);
 
-    let args = parse_low_raw(["-m", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_count);
+    let args = parse_low_raw(["--analyze-sort", "nesting-depth"]).unwrap();
+    assert_eq!(AnalyzeSortField::NestingDepth, args.analyze_sort);
 
-    let args = parse_low_raw(["-m", "5", "--max-count=10"]).unwrap();
-    assert_eq!(Some(10), args.max_count);
-    let args = parse_low_raw(["-m0"]).unwrap();
-    assert_eq!(Some(0), args.max_count);
+    let args = parse_low_raw(["--analyze-sort", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// --max-depth
+/// --analyze-min
 #[derive(Debug)]
-struct MaxDepth;
+struct AnalyzeMin;
 
-impl Flag for MaxDepth {
+impl Flag for AnalyzeMin {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'd')
-    }
+
     fn name_long(&self) -> &'static str {
-        "max-depth"
-    }
-    fn aliases(&self) -> &'static [&'static str] {
-        &["maxdepth"]
+        "analyze-min"
     }
+
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM")
+        Some("N")
     }
+
     fn doc_category(&self) -> Category {
         Category::Filter
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Descend at most NUM directories."
+        "Only show --analyze files meeting a --analyze-sort threshold."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-This flag limits the depth of directory traversal to \fINUM\fP levels beyond
-the paths given. A value of \fB0\fP only searches the explicitly given paths
-themselves.
-.sp
-For example, \fBrg --max-depth 0 \fP\fIdir/\fP is a no-op because \fIdir/\fP
-will not be descended into. \fBrg --max-depth 1 \fP\fIdir/\fP will search only
-the direct children of \fIdir\fP.
+Exclude files from \flag{analyze}'s per-file output whose
+\flag{analyze-sort} field is below \fIN\fP. Has no effect when
+\flag{analyze-sort} is \fBpath\fP, since path has no numeric ordering.
 .sp
-An alternative spelling for this flag is \fB\-\-maxdepth\fP.
+Example: --analyze-sort nesting-depth --analyze-min 4
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.max_depth = Some(convert::usize(&v.unwrap_value())?);
+        let min = convert::str(&v.unwrap_value())?
+            .parse::<f64>()
+            .context("analyze minimum must be a number")?;
+        args.analyze_min = Some(min);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_max_depth() {
+fn test_analyze_min() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.max_depth);
-
-    let args = parse_low_raw(["--max-depth", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_depth);
+    assert_eq!(None, args.analyze_min);
 
-    let args = parse_low_raw(["-d", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_depth);
-
-    let args = parse_low_raw(["--max-depth", "5", "--max-depth=10"]).unwrap();
-    assert_eq!(Some(10), args.max_depth);
-
-    let args = parse_low_raw(["--max-depth", "0"]).unwrap();
-    assert_eq!(Some(0), args.max_depth);
-
-    let args = parse_low_raw(["--maxdepth", "5"]).unwrap();
-    assert_eq!(Some(5), args.max_depth);
+    let args = parse_low_raw(["--analyze-min", "4"]).unwrap();
+    assert_eq!(Some(4.0), args.analyze_min);
 }
 
-/// --max-filesize
+/// --semantic
 #[derive(Debug)]
-struct MaxFilesize;
+struct Semantic;
 
-impl Flag for MaxFilesize {
+impl Flag for Semantic {
     fn is_switch(&self) -> bool {
-        false
+        true
     }
     fn name_long(&self) -> &'static str {
-        "max-filesize"
-    }
-    fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM+SUFFIX?")
+        "semantic"
     }
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Ignore files larger than NUM in size."
+        "Enable semantic code search using vector embeddings."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Ignore files larger than \fINUM\fP in size. This does not apply to directories.
+Enable semantic code search using vector embeddings. This allows searching
+for code with similar meaning rather than just exact text matches.
 .sp
-The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
-correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
-provided the input is treated as bytes.
+When enabled, outgrep will generate vector embeddings for code functions
+and symbols, and search for semantically similar content based on the query.
+This is particularly useful for finding code patterns, similar functions,
+or conceptually related code blocks.
 .sp
-Examples: \fB\-\-max-filesize 50K\fP or \fB\-\-max\-filesize 80M\fP.
+Note: This feature requires additional processing time for embedding generation
+and is currently experimental.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let v = v.unwrap_value();
-        args.max_filesize = Some(convert::human_readable_u64(&v)?);
+        args.semantic = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_max_filesize() {
+fn test_semantic() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.max_filesize);
-
-    let args = parse_low_raw(["--max-filesize", "1024"]).unwrap();
-    assert_eq!(Some(1024), args.max_filesize);
-
-    let args = parse_low_raw(["--max-filesize", "1K"]).unwrap();
-    assert_eq!(Some(1024), args.max_filesize);
+    assert_eq!(false, args.semantic);
 
-    let args =
-        parse_low_raw(["--max-filesize", "1K", "--max-filesize=1M"]).unwrap();
-    assert_eq!(Some(1024 * 1024), args.max_filesize);
+    let args = parse_low_raw(["--semantic"]).unwrap();
+    assert_eq!(true, args.semantic);
 }
 
-/// --mmap
+/// --semantic-model-path
 #[derive(Debug)]
-struct Mmap;
+struct SemanticModelPath;
 
-impl Flag for Mmap {
+impl Flag for SemanticModelPath {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "mmap"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-mmap")
+        "semantic-model-path"
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Search with memory maps when possible."
+        "Directory path where semantic embedding models are stored."
     }
     fn doc_long(&self) -> &'static str {
         r"
-When enabled, ripgrep will search using memory maps when possible. This is
-enabled by default when ripgrep thinks it will be faster.
+Specify the directory path where semantic embedding models are stored.
+This directory should contain the model.onnx and tokenizer.json files
+required for semantic code search.
 .sp
-Memory map searching cannot be used in all circumstances. For example, when
-searching virtual files or streams likes \fBstdin\fP. In such cases, memory
-maps will not be used even when this flag is enabled.
+By default, models are automatically downloaded to '~/.cache/outgrep/models'.
+Use this flag to specify a different location such as a custom model cache
+directory.
 .sp
-Note that ripgrep may abort unexpectedly when memory maps are used if it
-searches a file that is simultaneously truncated. Users can opt out of this
-possibility by disabling memory maps.
+Example: --semantic-model-path ~/.cache/outgrep/models
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.mmap = if v.unwrap_switch() {
-            MmapMode::AlwaysTryMmap
-        } else {
-            MmapMode::Never
-        };
+        args.semantic_model_path = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
-#[cfg(test)]
-#[test]
-fn test_mmap() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(MmapMode::Auto, args.mmap);
-
-    let args = parse_low_raw(["--mmap"]).unwrap();
-    assert_eq!(MmapMode::AlwaysTryMmap, args.mmap);
-
-    let args = parse_low_raw(["--no-mmap"]).unwrap();
-    assert_eq!(MmapMode::Never, args.mmap);
-
-    let args = parse_low_raw(["--mmap", "--no-mmap"]).unwrap();
-    assert_eq!(MmapMode::Never, args.mmap);
-
-    let args = parse_low_raw(["--no-mmap", "--mmap"]).unwrap();
-    assert_eq!(MmapMode::AlwaysTryMmap, args.mmap);
-}
-
-/// -U/--multiline
+/// --semantic-model
 #[derive(Debug)]
-struct Multiline;
+struct SemanticModel;
 
-impl Flag for Multiline {
+impl Flag for SemanticModel {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'U')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "multiline"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-multiline")
+        "semantic-model"
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Enable searching across multiple lines."
+        "Specify which embedding model to use for semantic search."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-This flag enable searching across multiple lines.
-.sp
-When multiline mode is enabled, ripgrep will lift the restriction that a
-match cannot include a line terminator. For example, when multiline mode
-is not enabled (the default), then the regex \fB\\p{any}\fP will match any
-Unicode codepoint other than \fB\\n\fP. Similarly, the regex \fB\\n\fP is
-explicitly forbidden, and if you try to use it, ripgrep will return an error.
-However, when multiline mode is enabled, \fB\\p{any}\fP will match any Unicode
-codepoint, including \fB\\n\fP, and regexes like \fB\\n\fP are permitted.
-.sp
-An important caveat is that multiline mode does not change the match semantics
-of \fB.\fP. Namely, in most regex matchers, a \fB.\fP will by default match any
-character other than \fB\\n\fP, and this is true in ripgrep as well. In order
-to make \fB.\fP match \fB\\n\fP, you must enable the "dot all" flag inside the
-regex. For example, both \fB(?s).\fP and \fB(?s:.)\fP have the same semantics,
-where \fB.\fP will match any character, including \fB\\n\fP. Alternatively, the
-\flag{multiline-dotall} flag may be passed to make the "dot all" behavior the
-default. This flag only applies when multiline search is enabled.
+        r"
+Specify which embedding model to use for semantic code search.
+The model name should correspond to a supported embedding model.
 .sp
-There is no limit on the number of the lines that a single match can span.
+Models are auto-downloaded from the model registry. See the registry for
+current available models and their specifications. Common models include
+compact 384-dimension models for speed and larger 768-dimension models
+for better quality.
 .sp
-\fBWARNING\fP: Because of how the underlying regex engine works, multiline
-searches may be slower than normal line-oriented searches, and they may also
-use more memory. In particular, when multiline mode is enabled, ripgrep
-requires that each file it searches is laid out contiguously in memory (either
-by reading it onto the heap or by memory-mapping it). Things that cannot be
-memory-mapped (such as \fBstdin\fP) will be consumed until EOF before searching
-can begin. In general, ripgrep will only do these things when necessary.
-Specifically, if the \flag{multiline} flag is provided but the regex does
-not contain patterns that would match \fB\\n\fP characters, then ripgrep
-will automatically avoid reading each file into memory before searching it.
-Nevertheless, if you only care about matches spanning at most one line, then it
-is always better to disable multiline mode.
+The model files (model.onnx and tokenizer.json) should be available
+in the model storage directory for the specified model.
 .sp
-This overrides the \flag{stop-on-nonmatch} flag.
-"#
+Example: --semantic-model all-mpnet-base-v2
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.multiline = v.unwrap_switch();
-        if args.multiline {
-            args.stop_on_nonmatch = false;
-        }
+        args.semantic_model = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
-#[cfg(test)]
-#[test]
-fn test_multiline() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.multiline);
-
-    let args = parse_low_raw(["--multiline"]).unwrap();
-    assert_eq!(true, args.multiline);
-
-    let args = parse_low_raw(["-U"]).unwrap();
-    assert_eq!(true, args.multiline);
-
-    let args = parse_low_raw(["-U", "--no-multiline"]).unwrap();
-    assert_eq!(false, args.multiline);
-}
-
-/// --multiline-dotall
+/// --semantic-dimensions
 #[derive(Debug)]
-struct MultilineDotall;
+struct SemanticDimensions;
 
-impl Flag for MultilineDotall {
+impl Flag for SemanticDimensions {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "multiline-dotall"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-multiline-dotall")
+        "semantic-dimensions"
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Make '.' match line terminators."
+        "Number of dimensions for semantic embedding vectors."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-This flag enables "dot all" mode in all regex patterns. This causes \fB.\fP to
-match line terminators when multiline searching is enabled. This flag has no
-effect if multiline searching isn't enabled with the \flag{multiline} flag.
+        r"
+Specify the number of dimensions for semantic embedding vectors.
+This must match the dimensions of the embedding model being used.
 .sp
-Normally, a \fB.\fP will match any character except line terminators. While
-this behavior typically isn't relevant for line-oriented matching (since
-matches can span at most one line), this can be useful when searching with the
-\flag{multiline} flag. By default, multiline mode runs without "dot all" mode
-enabled.
+Common dimension sizes are 384 (compact models), 768 (balanced models),
+and 1024 (high-quality models).
 .sp
-This flag is generally intended to be used in an alias or your ripgrep config
-file if you prefer "dot all" semantics by default. Note that regardless of
-whether this flag is used, "dot all" semantics can still be controlled via
-inline flags in the regex pattern itself, e.g., \fB(?s:.)\fP always enables
-"dot all" whereas \fB(?-s:.)\fP always disables "dot all". Moreover, you
-can use character classes like \fB\\p{any}\fP to match any Unicode codepoint
-regardless of whether "dot all" mode is enabled or not.
-"#
+If not specified, defaults to the dimension size of the selected model. The dimension size
+affects memory usage and search performance.
+.sp
+Example: --semantic-dimensions 768
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.multiline_dotall = v.unwrap_switch();
+        let dims = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic dimensions must be a positive integer")?;
+        args.semantic_dimensions = Some(dims);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_multiline_dotall() {
+fn test_semantic_dimensions() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.multiline_dotall);
-
-    let args = parse_low_raw(["--multiline-dotall"]).unwrap();
-    assert_eq!(true, args.multiline_dotall);
+    assert_eq!(None, args.semantic_dimensions);
 
-    let args = parse_low_raw(["--multiline-dotall", "--no-multiline-dotall"])
-        .unwrap();
-    assert_eq!(false, args.multiline_dotall);
+    let args = parse_low_raw(["--semantic-dimensions", "768"]).unwrap();
+    assert_eq!(Some(768), args.semantic_dimensions);
 }
 
-/// --no-config
+/// --semantic-dimension-mode
 #[derive(Debug)]
-struct NoConfig;
+struct SemanticDimensionMode;
 
-impl Flag for NoConfig {
+impl Flag for SemanticDimensionMode {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-config"
+        "semantic-dimension-mode"
     }
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("MODE")
+    }
+
     fn doc_category(&self) -> Category {
-        Category::OtherBehaviors
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Never read configuration files."
+        "How to handle a --semantic-import index built with a different model."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When set, ripgrep will never read configuration files. When this flag is
-present, ripgrep will not respect the \fBRIPGREP_CONFIG_PATH\fP environment
-variable.
+Controls what \flag{semantic-import} does when the index file's embedding
+dimensionality doesn't match the currently configured model -- i.e. the
+index was built with a different \flag{semantic-model}. \fIMODE\fP can be
+one of the following values:
 .sp
-If ripgrep ever grows a feature to automatically read configuration files in
-pre-defined locations, then this flag will also disable that behavior as well.
+.TP 15
+\fBreject\fP
+Refuse to import, with an error naming both dimensionalities. This is the
+default: silently mixing incompatible embeddings produces similarity scores
+that look valid but are meaningless.
+.TP 15
+\fBproject\fP
+Import anyway, linearly truncating or zero-padding each embedding to the
+configured dimensionality. This is not a fitted cross-model projection, so
+similarity scores involving projected vectors are a rough approximation, not
+a precise comparison.
+.sp
+Example: --semantic-import index.ogsx --semantic-dimension-mode project
 "
     }
 
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["reject", "project"]
+    }
+
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--no-config has no negation");
-        args.no_config = true;
+        use grep::searcher::DimensionMismatchPolicy;
+
+        args.semantic_dimension_mismatch =
+            match convert::str(&v.unwrap_value())? {
+                "reject" => DimensionMismatchPolicy::Reject,
+                "project" => DimensionMismatchPolicy::Project,
+                unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+            };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_config() {
+fn test_semantic_dimension_mode() {
+    use grep::searcher::DimensionMismatchPolicy;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_config);
+    assert_eq!(
+        DimensionMismatchPolicy::Reject,
+        args.semantic_dimension_mismatch
+    );
 
-    let args = parse_low_raw(["--no-config"]).unwrap();
-    assert_eq!(true, args.no_config);
+    let args =
+        parse_low_raw(["--semantic-dimension-mode", "project"]).unwrap();
+    assert_eq!(
+        DimensionMismatchPolicy::Project,
+        args.semantic_dimension_mismatch
+    );
+
+    let args = parse_low_raw(["--semantic-dimension-mode", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore
+/// --semantic-similarity-threshold
 #[derive(Debug)]
-struct NoIgnore;
+struct SemanticSimilarityThreshold;
 
-impl Flag for NoIgnore {
+impl Flag for SemanticSimilarityThreshold {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore"
+        "semantic-similarity-threshold"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore")
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["semantic-min-score"]
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use ignore files."
+        "Minimum similarity score for semantic search results."
     }
-    fn doc_long(&self) -> &'static str {
-        r"
-When set, ignore files such as \fB.gitignore\fP, \fB.ignore\fP and
-\fB.rgignore\fP will not be respected. This implies \flag{no-ignore-dot},
-\flag{no-ignore-exclude}, \flag{no-ignore-global}, \flag{no-ignore-parent} and
-\flag{no-ignore-vcs}.
+
+    fn doc_long(&self) -> &'static str {
+        r"
+Specify the minimum similarity score (between 0.0 and 1.0) for including
+results in semantic search output. Results with similarity scores below
+this threshold will be filtered out.
 .sp
-This does not imply \flag{no-ignore-files}, since \flag{ignore-file} is
-specified explicitly as a command line argument.
+A higher threshold means more selective results with stronger semantic
+similarity, while a lower threshold includes more loosely related matches.
 .sp
-When given only once, the \flag{unrestricted} flag is identical in
-behavior to this flag and can be considered an alias. However, subsequent
-\flag{unrestricted} flags have additional effects.
+Default: 0.2 (20% similarity)
+.sp
+Example: --semantic-similarity-threshold 0.5
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let yes = v.unwrap_switch();
-        args.no_ignore_dot = yes;
-        args.no_ignore_exclude = yes;
-        args.no_ignore_global = yes;
-        args.no_ignore_parent = yes;
-        args.no_ignore_vcs = yes;
+        let threshold = convert::str(&v.unwrap_value())?.parse::<f32>()
+            .context("semantic similarity threshold must be a number between 0.0 and 1.0")?;
+
+        if threshold < 0.0 || threshold > 1.0 {
+            return Err(anyhow::anyhow!(
+                "semantic similarity threshold must be between 0.0 and 1.0"
+            ));
+        }
+
+        args.semantic_similarity_threshold = Some(threshold);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore() {
+fn test_semantic_similarity_threshold() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_dot);
-    assert_eq!(false, args.no_ignore_exclude);
-    assert_eq!(false, args.no_ignore_global);
-    assert_eq!(false, args.no_ignore_parent);
-    assert_eq!(false, args.no_ignore_vcs);
+    assert_eq!(None, args.semantic_similarity_threshold);
 
-    let args = parse_low_raw(["--no-ignore"]).unwrap();
-    assert_eq!(true, args.no_ignore_dot);
-    assert_eq!(true, args.no_ignore_exclude);
-    assert_eq!(true, args.no_ignore_global);
-    assert_eq!(true, args.no_ignore_parent);
-    assert_eq!(true, args.no_ignore_vcs);
+    let args =
+        parse_low_raw(["--semantic-similarity-threshold", "0.5"]).unwrap();
+    assert_eq!(Some(0.5), args.semantic_similarity_threshold);
 
-    let args = parse_low_raw(["--no-ignore", "--ignore"]).unwrap();
-    assert_eq!(false, args.no_ignore_dot);
-    assert_eq!(false, args.no_ignore_exclude);
-    assert_eq!(false, args.no_ignore_global);
-    assert_eq!(false, args.no_ignore_parent);
-    assert_eq!(false, args.no_ignore_vcs);
+    let args = parse_low_raw(["--semantic-min-score", "0.7"]).unwrap();
+    assert_eq!(Some(0.7), args.semantic_similarity_threshold);
+
+    let args = parse_low_raw(["--semantic-similarity-threshold", "1.5"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore-dot
+/// --semantic-max-results
 #[derive(Debug)]
-struct NoIgnoreDot;
+struct SemanticMaxResults;
 
-impl Flag for NoIgnoreDot {
+impl Flag for SemanticMaxResults {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-dot"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-dot")
+        "semantic-max-results"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use .ignore or .rgignore files."
+        "Maximum number of semantic search results to return."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Don't respect filter rules from \fB.ignore\fP or \fB.rgignore\fP files.
+Specify the maximum number of semantic search results to return.
+This limits the output to the top N most similar matches.
 .sp
-This does not impact whether ripgrep will ignore files and directories whose
-names begin with a dot. For that, see the \flag{hidden} flag. This flag also
-does not impact whether filter rules from \fB.gitignore\fP files are respected.
+Lowering this value can improve performance and reduce noise in results,
+while increasing it provides more comprehensive coverage of similar content.
+.sp
+Default: 10 results
+.sp
+Example: --semantic-max-results 20
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_dot = v.unwrap_switch();
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-#[test]
-fn test_no_ignore_dot() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_dot);
+        let max_results = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic max results must be a positive integer")?;
 
-    let args = parse_low_raw(["--no-ignore-dot"]).unwrap();
-    assert_eq!(true, args.no_ignore_dot);
+        if max_results == 0 {
+            return Err(anyhow::anyhow!(
+                "semantic max results must be greater than 0"
+            ));
+        }
 
-    let args = parse_low_raw(["--no-ignore-dot", "--ignore-dot"]).unwrap();
-    assert_eq!(false, args.no_ignore_dot);
+        args.semantic_max_results = Some(max_results);
+        Ok(())
+    }
 }
 
-/// --no-ignore-exclude
+/// --semantic-top-k
 #[derive(Debug)]
-struct NoIgnoreExclude;
+struct SemanticTopK;
 
-impl Flag for NoIgnoreExclude {
+impl Flag for SemanticTopK {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-exclude"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-exclude")
+        "semantic-top-k"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use local exclusion files."
+        "Limit semantic results to the top N across the whole run."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Don't respect filter rules from files that are manually configured for the repository.
-For example, this includes \fBgit\fP's \fB.git/info/exclude\fP.
+Limit semantic search output to the N highest-scoring results across the
+entire run, rather than the top results of each file searched.
+.sp
+\flag{semantic-max-results} caps how many matches come out of each file,
+so a run over many files can still print far more than N results overall,
+and results are printed in the streaming, per-file order that the walker
+finds them in. \flag{semantic-top-k} instead collects every match from
+every searched file, ranks them by similarity score, and prints only the
+overall top N, ordered from most to least similar.
+.sp
+Because every match has to be collected before any of them can be ranked,
+this flag forces single-threaded search, the same way \flag{sort} does.
+.sp
+Example: --semantic-top-k 20
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_exclude = v.unwrap_switch();
+        let top_k = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic top-k must be a positive integer")?;
+
+        if top_k == 0 {
+            return Err(anyhow::anyhow!(
+                "semantic top-k must be greater than 0"
+            ));
+        }
+
+        args.semantic_top_k = Some(top_k);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore_exclude() {
+fn test_semantic_top_k() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_exclude);
+    assert_eq!(None, args.semantic_top_k);
 
-    let args = parse_low_raw(["--no-ignore-exclude"]).unwrap();
-    assert_eq!(true, args.no_ignore_exclude);
+    let args = parse_low_raw(["--semantic-top-k", "20"]).unwrap();
+    assert_eq!(Some(20), args.semantic_top_k);
 
-    let args =
-        parse_low_raw(["--no-ignore-exclude", "--ignore-exclude"]).unwrap();
-    assert_eq!(false, args.no_ignore_exclude);
+    let args = parse_low_raw(["--semantic-top-k", "0"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore-files
+/// --semantic-cluster
 #[derive(Debug)]
-struct NoIgnoreFiles;
+struct SemanticClusterFlag;
 
-impl Flag for NoIgnoreFiles {
+impl Flag for SemanticClusterFlag {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-files"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-files")
+        "semantic-cluster"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use --ignore-file arguments."
+        "Group semantic results into N clusters by embedding similarity."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When set, any \flag{ignore-file} flags, even ones that come after this flag,
-are ignored.
+Group each file's semantic results into at most N clusters by embedding
+similarity, and print only one representative match per cluster, alongside
+how many matches it stands in for.
+.sp
+This is meant to help with large result sets full of near-identical hits,
+e.g. many call sites of the same helper, where the individual matches
+aren't interesting on their own but scrolling through all of them is
+tedious.
+.sp
+Clustering runs per file, the same as semantic search itself; it does not
+collect results across the whole run the way \flag{semantic-top-k} does.
+.sp
+Example: --semantic-cluster 5
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_files = v.unwrap_switch();
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-#[test]
-fn test_no_ignore_files() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_files);
+        let k = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic cluster count must be a positive integer")?;
 
-    let args = parse_low_raw(["--no-ignore-files"]).unwrap();
-    assert_eq!(true, args.no_ignore_files);
+        if k == 0 {
+            return Err(anyhow::anyhow!(
+                "semantic cluster count must be greater than 0"
+            ));
+        }
 
-    let args = parse_low_raw(["--no-ignore-files", "--ignore-files"]).unwrap();
-    assert_eq!(false, args.no_ignore_files);
+        args.semantic_cluster = Some(k);
+        Ok(())
+    }
 }
 
-/// --no-ignore-global
+/// --semantic-stream
 #[derive(Debug)]
-struct NoIgnoreGlobal;
+struct SemanticStream;
 
-impl Flag for NoIgnoreGlobal {
+impl Flag for SemanticStream {
     fn is_switch(&self) -> bool {
         true
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-global"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-global")
+        "semantic-stream"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use global ignore files."
+        "Print semantic matches as soon as each one is scored."
     }
+
     fn doc_long(&self) -> &'static str {
-        r#"
-Don't respect filter rules from ignore files that come from "global" sources
-such as \fBgit\fP's \fBcore.excludesFile\fP configuration option (which
-defaults to \fB$HOME/.config/git/ignore\fP).
-"#
+        r"
+Print (or emit as JSON) each semantic match the instant it clears
+\flag{semantic-similarity-threshold}, instead of scoring every chunk in the
+file, sorting the results by similarity, and only then printing the top
+\flag{semantic-max-results}.
+.sp
+This means matches are reported in whatever order the file's chunks were
+embedded in rather than by descending similarity, and \flag{semantic-rerank}
+(which needs the whole candidate list to rescore) is not applied. It also
+scores every chunk with an exact linear scan instead of the approximate
+nearest-neighbor index \flag{semantic} normally searches, since building that
+index's graph only pays for itself when ranking many chunks against one
+query, not when the first match that clears the threshold is good enough.
+.sp
+\flag{-}\flag{-}semantic-stream is incompatible with \flag{semantic-top-k},
+\flag{semantic-cluster} and multiple \flag{semantic-query} flags, all of
+which need the full candidate set before they can do anything; when any of
+those are also given, this flag is ignored.
+.sp
+Like a normal search, a broken output pipe (e.g. piping into \fBhead\fP)
+stops the scan, and \flag{quit-after-match} stops it after the first match.
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_global = v.unwrap_switch();
+        assert!(v.unwrap_switch(), "--semantic-stream can only be enabled");
+        args.semantic_stream = true;
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore_global() {
+fn test_semantic_stream() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_global);
+    assert_eq!(false, args.semantic_stream);
+    let args = parse_low_raw(["--semantic-stream"]).unwrap();
+    assert_eq!(true, args.semantic_stream);
+}
 
-    let args = parse_low_raw(["--no-ignore-global"]).unwrap();
-    assert_eq!(true, args.no_ignore_global);
+#[cfg(test)]
+#[test]
+fn test_semantic_cluster() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.semantic_cluster);
 
-    let args =
-        parse_low_raw(["--no-ignore-global", "--ignore-global"]).unwrap();
-    assert_eq!(false, args.no_ignore_global);
+    let args = parse_low_raw(["--semantic-cluster", "5"]).unwrap();
+    assert_eq!(Some(5), args.semantic_cluster);
+
+    let args = parse_low_raw(["--semantic-cluster", "0"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore-messages
+/// --semantic-ef-search
 #[derive(Debug)]
-struct NoIgnoreMessages;
+struct SemanticEfSearch;
 
-impl Flag for NoIgnoreMessages {
+impl Flag for SemanticEfSearch {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-messages"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-messages")
+        "semantic-ef-search"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Logging
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Suppress gitignore parse error messages."
+        "Tune the HNSW search effort for semantic search."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When this flag is enabled, all error messages related to parsing ignore files
-are suppressed. By default, error messages are printed to stderr. In cases
-where these errors are expected, this flag can be used to avoid seeing the
-noise produced by the messages.
+Set the \fIef\fP parameter used when searching the semantic index's HNSW
+graph. A higher value visits more candidate neighbors per query, which
+tends to improve recall at the cost of search speed; a lower value is
+faster but may miss some relevant matches.
+.sp
+By default, the underlying index library picks a reasonable value. This
+flag is useful when the default doesn't give enough recall for a large
+index, or when search latency matters more than exhaustiveness.
+.sp
+Example: --semantic-ef-search 200
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_messages = v.unwrap_switch();
+        let ef_search = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic ef-search must be a positive integer")?;
+
+        if ef_search == 0 {
+            return Err(anyhow::anyhow!(
+                "semantic ef-search must be greater than 0"
+            ));
+        }
+
+        args.semantic_ef_search = Some(ef_search);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore_messages() {
+fn test_semantic_ef_search() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_messages);
+    assert_eq!(None, args.semantic_ef_search);
 
-    let args = parse_low_raw(["--no-ignore-messages"]).unwrap();
-    assert_eq!(true, args.no_ignore_messages);
+    let args = parse_low_raw(["--semantic-ef-search", "200"]).unwrap();
+    assert_eq!(Some(200), args.semantic_ef_search);
 
-    let args =
-        parse_low_raw(["--no-ignore-messages", "--ignore-messages"]).unwrap();
-    assert_eq!(false, args.no_ignore_messages);
+    let args = parse_low_raw(["--semantic-ef-search", "0"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore-parent
+/// --semantic-chunking
 #[derive(Debug)]
-struct NoIgnoreParent;
+struct SemanticChunking;
 
-impl Flag for NoIgnoreParent {
+impl Flag for SemanticChunking {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-parent"
+        "semantic-chunking"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-parent")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("STRATEGY")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use ignore files in parent directories."
+        "Choose how file content is split into chunks for embedding."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When this flag is set, filter rules from ignore files found in parent
-directories are not respected. By default, ripgrep will ascend the parent
-directories of the current working directory to look for any applicable ignore
-files that should be applied. In some cases this may not be desirable.
+Controls how a file's content is divided into chunks before each chunk is
+embedded for semantic search. \fISTRATEGY\fP can be one of the following
+values:
+.sp
+.TP 15
+\fBsymbol\fP
+Walk the file's AST once and emit one chunk per function, class, or module.
+Falls back to a single whole-file chunk if no symbols are found, e.g. for
+unsupported languages. This is the default.
+.TP 15
+\fBsliding\-window\fP
+Split the raw text into fixed-size, overlapping windows, ignoring AST
+structure. Useful for languages outgrep can't parse. Window size and overlap
+are controlled by \flag{semantic-chunk-size} and
+\flag{semantic-chunk-overlap}.
+.TP 15
+\fBfile\fP
+Treat the entire file as a single chunk.
+.sp
+Example: --semantic-chunking sliding-window
 "
     }
 
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["symbol", "sliding-window", "file"]
+    }
+
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_parent = v.unwrap_switch();
+        use grep::searcher::ChunkingStrategy;
+
+        args.semantic_chunking = match convert::str(&v.unwrap_value())? {
+            "symbol" => ChunkingStrategy::Symbol,
+            "sliding-window" => ChunkingStrategy::SlidingWindow,
+            "file" => ChunkingStrategy::File,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore_parent() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_parent);
+fn test_semantic_chunking() {
+    use grep::searcher::ChunkingStrategy;
 
-    let args = parse_low_raw(["--no-ignore-parent"]).unwrap();
-    assert_eq!(true, args.no_ignore_parent);
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(ChunkingStrategy::Symbol, args.semantic_chunking);
 
     let args =
-        parse_low_raw(["--no-ignore-parent", "--ignore-parent"]).unwrap();
-    assert_eq!(false, args.no_ignore_parent);
+        parse_low_raw(["--semantic-chunking", "sliding-window"]).unwrap();
+    assert_eq!(ChunkingStrategy::SlidingWindow, args.semantic_chunking);
+
+    let args = parse_low_raw(["--semantic-chunking", "file"]).unwrap();
+    assert_eq!(ChunkingStrategy::File, args.semantic_chunking);
+
+    let args = parse_low_raw(["--semantic-chunking", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// --no-ignore-vcs
+/// --semantic-granularity
 #[derive(Debug)]
-struct NoIgnoreVcs;
+struct SemanticGranularity;
 
-impl Flag for NoIgnoreVcs {
+impl Flag for SemanticGranularity {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-ignore-vcs"
+        "semantic-granularity"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("ignore-vcs")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("GRANULARITY")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Don't use ignore files from source control."
+        "An alternate spelling of --semantic-chunking, in symbol/file/block terms."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When given, filter rules from source control ignore files (e.g., \fB.gitignore\fP)
-are not respected. By default, ripgrep respects \fBgit\fP's ignore rules for
-automatic filtering. In some cases, it may not be desirable to respect the
-source control's ignore rules and instead only respect rules in \fB.ignore\fP
-or \fB.rgignore\fP.
+An alternate spelling of \flag{semantic-chunking} for users who think in
+terms of indexing granularity rather than chunking strategy.
+\fIGRANULARITY\fP can be one of the following values:
 .sp
-This flag implies \flag{no-ignore-parent} for source control ignore files as
-well.
+.TP 15
+\fBsymbol\fP
+Same as \flag{semantic-chunking}=\fBsymbol\fP: one chunk per function,
+class, or module. This is the default, and gives the most precise results.
+.TP 15
+\fBblock\fP
+Same as \flag{semantic-chunking}=\fBsliding\-window\fP: fixed-size,
+overlapping windows of raw text, ignoring AST structure.
+.TP 15
+\fBfile\fP
+Same as \flag{semantic-chunking}=\fBfile\fP: treat the entire file as a
+single chunk. The fastest option, best suited to indexing huge repositories
+where per-symbol precision matters less than coverage.
+.sp
+\flag{semantic-granularity} and \flag{semantic-chunking} set the same
+underlying option; whichever is given last on the command line wins.
+.sp
+Example: --semantic-granularity block
 "
     }
 
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["symbol", "file", "block"]
+    }
+
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_ignore_vcs = v.unwrap_switch();
+        use grep::searcher::ChunkingStrategy;
+
+        args.semantic_chunking = match convert::str(&v.unwrap_value())? {
+            "symbol" => ChunkingStrategy::Symbol,
+            "block" => ChunkingStrategy::SlidingWindow,
+            "file" => ChunkingStrategy::File,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_ignore_vcs() {
+fn test_semantic_granularity() {
+    use grep::searcher::ChunkingStrategy;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_ignore_vcs);
+    assert_eq!(ChunkingStrategy::Symbol, args.semantic_chunking);
 
-    let args = parse_low_raw(["--no-ignore-vcs"]).unwrap();
-    assert_eq!(true, args.no_ignore_vcs);
+    let args = parse_low_raw(["--semantic-granularity", "block"]).unwrap();
+    assert_eq!(ChunkingStrategy::SlidingWindow, args.semantic_chunking);
 
-    let args = parse_low_raw(["--no-ignore-vcs", "--ignore-vcs"]).unwrap();
-    assert_eq!(false, args.no_ignore_vcs);
+    let args = parse_low_raw(["--semantic-granularity", "file"]).unwrap();
+    assert_eq!(ChunkingStrategy::File, args.semantic_chunking);
+
+    let args = parse_low_raw(["--semantic-granularity", "bogus"]);
+    assert!(args.is_err());
+
+    // Whichever flag comes last wins, since they share one underlying field.
+    let args = parse_low_raw([
+        "--semantic-granularity",
+        "block",
+        "--semantic-chunking",
+        "file",
+    ])
+    .unwrap();
+    assert_eq!(ChunkingStrategy::File, args.semantic_chunking);
 }
 
-/// --no-messages
+/// --semantic-chunk-size
 #[derive(Debug)]
-struct NoMessages;
+struct SemanticChunkSize;
 
-impl Flag for NoMessages {
+impl Flag for SemanticChunkSize {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-messages"
+        "semantic-chunk-size"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("messages")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("BYTES")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Logging
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Suppress some error messages."
+        "Set the window size for --semantic-chunking sliding-window."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-This flag suppresses some error messages. Specifically, messages related to
-the failed opening and reading of files. Error messages related to the syntax
-of the pattern are still shown.
+Set the target chunk size, in bytes, used by
+\flag{semantic-chunking}=\fBsliding\-window\fP. Ignored by the \fBsymbol\fP
+and \fBfile\fP chunking strategies.
+.sp
+Example: --semantic-chunking sliding-window --semantic-chunk-size 1000
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_messages = v.unwrap_switch();
+        let size = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic chunk size must be a positive integer")?;
+
+        if size == 0 {
+            return Err(anyhow::anyhow!(
+                "semantic chunk size must be greater than 0"
+            ));
+        }
+
+        args.semantic_chunk_size = Some(size);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_messages() {
+fn test_semantic_chunk_size() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_messages);
+    assert_eq!(None, args.semantic_chunk_size);
 
-    let args = parse_low_raw(["--no-messages"]).unwrap();
-    assert_eq!(true, args.no_messages);
+    let args = parse_low_raw(["--semantic-chunk-size", "1000"]).unwrap();
+    assert_eq!(Some(1000), args.semantic_chunk_size);
 
-    let args = parse_low_raw(["--no-messages", "--messages"]).unwrap();
-    assert_eq!(false, args.no_messages);
+    let args = parse_low_raw(["--semantic-chunk-size", "0"]);
+    assert!(args.is_err());
 }
 
-/// --no-pcre2-unicode
+/// --semantic-chunk-overlap
 #[derive(Debug)]
-struct NoPcre2Unicode;
+struct SemanticChunkOverlap;
 
-impl Flag for NoPcre2Unicode {
+impl Flag for SemanticChunkOverlap {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-pcre2-unicode"
+        "semantic-chunk-overlap"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("pcre2-unicode")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("BYTES")
     }
+
     fn doc_category(&self) -> Category {
         Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"(DEPRECATED) Disable Unicode mode for PCRE2."
+        "Set the window overlap for --semantic-chunking sliding-window."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-DEPRECATED. Use \flag{no-unicode} instead.
+Set the number of bytes consecutive windows overlap by, used by
+\flag{semantic-chunking}=\fBsliding\-window\fP. Ignored by the \fBsymbol\fP
+and \fBfile\fP chunking strategies.
 .sp
-Note that Unicode mode is enabled by default.
+Example: --semantic-chunking sliding-window --semantic-chunk-overlap 100
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_unicode = v.unwrap_switch();
+        let overlap = convert::str(&v.unwrap_value())?
+            .parse::<usize>()
+            .context("semantic chunk overlap must be a non-negative integer")?;
+
+        args.semantic_chunk_overlap = Some(overlap);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_pcre2_unicode() {
+fn test_semantic_chunk_overlap() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_unicode);
+    assert_eq!(None, args.semantic_chunk_overlap);
 
-    let args = parse_low_raw(["--no-pcre2-unicode"]).unwrap();
-    assert_eq!(true, args.no_unicode);
-
-    let args =
-        parse_low_raw(["--no-pcre2-unicode", "--pcre2-unicode"]).unwrap();
-    assert_eq!(false, args.no_unicode);
+    let args = parse_low_raw(["--semantic-chunk-overlap", "100"]).unwrap();
+    assert_eq!(Some(100), args.semantic_chunk_overlap);
 }
 
-/// --no-require-git
+/// --semantic-backend
 #[derive(Debug)]
-struct NoRequireGit;
+struct SemanticBackendFlag;
 
-impl Flag for NoRequireGit {
+impl Flag for SemanticBackendFlag {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-require-git"
+        "semantic-backend"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("require-git")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("BACKEND")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Use .gitignore outside of git repositories."
+        "Choose the execution backend for embedding generation."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When this flag is given, source control ignore files such as \fB.gitignore\fP
-are respected even if no \fBgit\fP repository is present.
-.sp
-By default, ripgrep will only respect filter rules from source control ignore
-files when ripgrep detects that the search is executed inside a source control
-repository. For example, when a \fB.git\fP directory is observed.
+Controls which execution backend ONNX Runtime uses to generate embeddings
+for semantic search. \fIBACKEND\fP can be one of the following values:
 .sp
-This flag relaxes the default restriction. For example, it might be useful when
-the contents of a \fBgit\fP repository are stored or copied somewhere, but
-where the repository state is absent.
+.TP 15
+\fBcpu\fP
+Run on CPU. Always available. This is the default.
+.TP 15
+\fBcuda\fP
+Run on an NVIDIA GPU via CUDA. Requires outgrep to have been built with the
+\fBcuda\fP Cargo feature.
+.TP 15
+\fBmetal\fP
+Run on Apple GPUs via ONNX Runtime's CoreML provider. Requires outgrep to
+have been built with the \fBcoreml\fP Cargo feature.
+.TP 15
+\fBremote\fP
+Call a remote OpenAI-compatible \fB/embeddings\fP HTTP endpoint instead of
+running inference locally. Configure the endpoint with
+\fBOUTGREP_EMBEDDING_API_URL\fP (default: the public OpenAI API) and
+\fBOUTGREP_EMBEDDING_API_KEY\fP. Useful when local model support isn't
+available.
+.sp
+If the requested local backend isn't usable on this machine (the matching
+Cargo feature wasn't built in, missing drivers, no compatible device,
+etc.), outgrep falls back to the CPU backend automatically.
+.sp
+Example: --semantic-backend cuda
 "
     }
 
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["cpu", "cuda", "metal", "remote"]
+    }
+
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_require_git = v.unwrap_switch();
+        use grep::searcher::SemanticBackend;
+
+        args.semantic_backend = match convert::str(&v.unwrap_value())? {
+            "cpu" => SemanticBackend::Cpu,
+            "cuda" => SemanticBackend::Cuda,
+            "metal" => SemanticBackend::Metal,
+            "remote" => SemanticBackend::Remote,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_require_git() {
+fn test_semantic_backend() {
+    use grep::searcher::SemanticBackend;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_require_git);
+    assert_eq!(SemanticBackend::Cpu, args.semantic_backend);
 
-    let args = parse_low_raw(["--no-require-git"]).unwrap();
-    assert_eq!(true, args.no_require_git);
+    let args = parse_low_raw(["--semantic-backend", "cuda"]).unwrap();
+    assert_eq!(SemanticBackend::Cuda, args.semantic_backend);
 
-    let args = parse_low_raw(["--no-require-git", "--require-git"]).unwrap();
-    assert_eq!(false, args.no_require_git);
+    let args = parse_low_raw(["--semantic-backend", "metal"]).unwrap();
+    assert_eq!(SemanticBackend::Metal, args.semantic_backend);
+
+    let args = parse_low_raw(["--semantic-backend", "remote"]).unwrap();
+    assert_eq!(SemanticBackend::Remote, args.semantic_backend);
+
+    let args = parse_low_raw(["--semantic-backend", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// --no-unicode
+/// --semantic-quantize
 #[derive(Debug)]
-struct NoUnicode;
+struct SemanticQuantizeFlag;
 
-impl Flag for NoUnicode {
+impl Flag for SemanticQuantizeFlag {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "no-unicode"
+        "semantic-quantize"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("unicode")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("MODE")
     }
+
     fn doc_category(&self) -> Category {
         Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Disable Unicode mode."
+        "Quantize stored embeddings to reduce semantic index memory usage."
     }
+
     fn doc_long(&self) -> &'static str {
-        r#"
-This flag disables Unicode mode for all patterns given to ripgrep.
-.sp
-By default, ripgrep will enable "Unicode mode" in all of its regexes. This has
-a number of consequences:
-.sp
-.IP \(bu 3n
-\fB.\fP will only match valid UTF-8 encoded Unicode scalar values.
-.sp
-.IP \(bu 3n
-Classes like \fB\\w\fP, \fB\\s\fP, \fB\\d\fP are all Unicode aware and much
-bigger than their ASCII only versions.
+        r"
+Controls how embeddings are stored in the semantic index. \fIMODE\fP can be
+one of the following values:
 .sp
-.IP \(bu 3n
-Case insensitive matching will use Unicode case folding.
+.TP 15
+\fBnone\fP
+Store embeddings at full \fBf32\fP precision. This is the default.
+.TP 15
+\fBint8\fP
+Scalar-quantize each embedding to signed bytes, cutting memory and disk
+usage roughly 4x.
+.TP 15
+\fBf16\fP
+Store each embedding as half-precision floats, cutting memory and disk
+usage roughly 2x.
 .sp
-.IP \(bu 3n
-A large array of classes like \fB\\p{Emoji}\fP are available. (Although the
-specific set of classes available varies based on the regex engine. In general,
-the default regex engine has more classes available to it.)
+Quantized embeddings are transparently dequantized to \fBf32\fP before
+scoring, so this only affects memory usage, not which flags are accepted.
 .sp
-.IP \(bu 3n
-Word boundaries (\fB\\b\fP and \fB\\B\fP) use the Unicode definition of a word
-character.
-.PP
-In some cases it can be desirable to turn these things off. This flag will do
-exactly that. For example, Unicode mode can sometimes have a negative impact
-on performance, especially when things like \fB\\w\fP are used frequently
-(including via bounded repetitions like \fB\\w{100}\fP) when only their ASCII
-interpretation is needed.
-"#
+Example: --semantic-quantize int8
+"
+    }
+
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["none", "int8", "f16"]
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.no_unicode = v.unwrap_switch();
+        use grep::searcher::SemanticQuantize;
+
+        args.semantic_quantize = match convert::str(&v.unwrap_value())? {
+            "none" => SemanticQuantize::None,
+            "int8" => SemanticQuantize::Int8,
+            "f16" => SemanticQuantize::F16,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_unicode() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.no_unicode);
+fn test_semantic_quantize() {
+    use grep::searcher::SemanticQuantize;
 
-    let args = parse_low_raw(["--no-unicode"]).unwrap();
-    assert_eq!(true, args.no_unicode);
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(SemanticQuantize::None, args.semantic_quantize);
 
-    let args = parse_low_raw(["--no-unicode", "--unicode"]).unwrap();
-    assert_eq!(false, args.no_unicode);
+    let args = parse_low_raw(["--semantic-quantize", "int8"]).unwrap();
+    assert_eq!(SemanticQuantize::Int8, args.semantic_quantize);
 
-    let args = parse_low_raw(["--no-unicode", "--pcre2-unicode"]).unwrap();
-    assert_eq!(false, args.no_unicode);
+    let args = parse_low_raw(["--semantic-quantize", "f16"]).unwrap();
+    assert_eq!(SemanticQuantize::F16, args.semantic_quantize);
 
-    let args = parse_low_raw(["--no-pcre2-unicode", "--unicode"]).unwrap();
-    assert_eq!(false, args.no_unicode);
+    let args = parse_low_raw(["--semantic-quantize", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// -0/--null
+/// --semantic-rerank
 #[derive(Debug)]
-struct Null;
+struct SemanticRerank;
 
-impl Flag for Null {
+impl Flag for SemanticRerank {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'0')
-    }
+
     fn name_long(&self) -> &'static str {
-        "null"
+        "semantic-rerank"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Print a NUL byte after file paths."
+        "Rerank top semantic results for better precision."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Whenever a file path is printed, follow it with a \fBNUL\fP byte. This includes
-printing file paths before matches, and when printing a list of matching files
-such as with \flag{count}, \flag{files-with-matches} and \flag{files}. This
-option is useful for use with \fBxargs\fP.
+After the approximate nearest-neighbor search shortlists candidates for
+\flag{semantic}, rescore that shortlist with a second, more precise pass
+before applying \flag{semantic-max-results} and
+\flag{semantic-similarity-threshold}.
+.sp
+This trades a small amount of extra latency, proportional to the shortlist
+size rather than the whole index, for better precision at the top of the
+results. Use \flag{semantic-rerank-model} to select which model the
+reranking pass uses.
+.sp
+Example: --semantic 'parse configuration' --semantic-rerank
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--null has no negation");
-        args.null = true;
+        args.semantic_rerank = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_null() {
+fn test_semantic_rerank() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.null);
+    assert_eq!(false, args.semantic_rerank);
 
-    let args = parse_low_raw(["--null"]).unwrap();
-    assert_eq!(true, args.null);
-
-    let args = parse_low_raw(["-0"]).unwrap();
-    assert_eq!(true, args.null);
+    let args = parse_low_raw(["--semantic-rerank"]).unwrap();
+    assert_eq!(true, args.semantic_rerank);
 }
 
-/// --null-data
+/// --semantic-rerank-model
 #[derive(Debug)]
-struct NullData;
+struct SemanticRerankModel;
 
-impl Flag for NullData {
+impl Flag for SemanticRerankModel {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "null-data"
+        "semantic-rerank-model"
     }
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NAME")
+    }
+
     fn doc_category(&self) -> Category {
         Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Use NUL as a line terminator."
+        "Model to use for the --semantic-rerank pass."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Enabling this flag causes ripgrep to use \fBNUL\fP as a line terminator instead
-of the default of \fP\\n\fP.
-.sp
-This is useful when searching large binary files that would otherwise have
-very long lines if \fB\\n\fP were used as the line terminator. In particular,
-ripgrep requires that, at a minimum, each line must fit into memory. Using
-\fBNUL\fP instead can be a useful stopgap to keep memory requirements low and
-avoid OOM (out of memory) conditions.
-.sp
-This is also useful for processing NUL delimited data, such as that emitted
-when using ripgrep's \flag{null} flag or \fBfind\fP's \fB\-\-print0\fP flag.
+Specify which model, looked up in the model registry, the
+\flag{semantic-rerank} pass should use. Implies \flag{semantic-rerank}.
+If not given, the reranking pass uses its own built-in default.
 .sp
-Using this flag implies \flag{text}. It also overrides \flag{crlf}.
+Example: --semantic-rerank-model ms-marco-MiniLM-L-6-v2
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--null-data has no negation");
-        args.crlf = false;
-        args.null_data = true;
+        args.semantic_rerank = true;
+        args.semantic_rerank_model = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_null_data() {
+fn test_semantic_rerank_model() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.null_data);
-
-    let args = parse_low_raw(["--null-data"]).unwrap();
-    assert_eq!(true, args.null_data);
-
-    let args = parse_low_raw(["--null-data", "--crlf"]).unwrap();
-    assert_eq!(false, args.null_data);
-    assert_eq!(true, args.crlf);
-
-    let args = parse_low_raw(["--crlf", "--null-data"]).unwrap();
-    assert_eq!(true, args.null_data);
-    assert_eq!(false, args.crlf);
+    assert_eq!(None, args.semantic_rerank_model);
 
-    let args = parse_low_raw(["--null-data", "--no-crlf"]).unwrap();
-    assert_eq!(true, args.null_data);
-    assert_eq!(false, args.crlf);
+    let args = parse_low_raw(["--semantic-rerank-model", "ms-marco-MiniLM-L-6-v2"])
+        .unwrap();
+    assert_eq!(true, args.semantic_rerank);
+    assert_eq!(
+        Some("ms-marco-MiniLM-L-6-v2".to_string()),
+        args.semantic_rerank_model
+    );
 }
 
-/// --one-file-system
+/// --semantic-history
 #[derive(Debug)]
-struct OneFileSystem;
+struct SemanticHistory;
 
-impl Flag for OneFileSystem {
+impl Flag for SemanticHistory {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "one-file-system"
+        "semantic-history"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-one-file-system")
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("RANGE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Filter
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Skip directories on other file systems."
+        "Run --semantic search over a range of Git history."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When enabled, ripgrep will not cross file system boundaries relative to where
-the search started from.
-.sp
-Note that this applies to each path argument given to ripgrep. For example, in
-the command
-.sp
-.EX
-    rg \-\-one\-file\-system /foo/bar /quux/baz
-.EE
+Instead of searching only the current contents of each file, also embed and
+search the version of each file at every commit in \fIRANGE\fP, a revspec
+understood by \fBgit-rev-list\fP(1) such as \fIHEAD~50..HEAD\fP or
+\fImain..feature\fP.
 .sp
-ripgrep will search both \fI/foo/bar\fP and \fI/quux/baz\fP even if they are
-on different file systems, but will not cross a file system boundary when
-traversing each path's directory tree.
+This requires the searched path to be inside a Git repository and implies
+\flag{semantic}. Each result is labeled with the commit it was found at, so
+you can see when a concept was introduced or removed, not just where it
+lives in the working tree today.
 .sp
-This is similar to \fBfind\fP's \fB\-xdev\fP or \fB\-mount\fP flag.
+Example: --semantic 'retry backoff' --semantic-history HEAD~50..HEAD
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.one_file_system = v.unwrap_switch();
+        args.semantic_history = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_one_file_system() {
+fn test_semantic_history() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.one_file_system);
-
-    let args = parse_low_raw(["--one-file-system"]).unwrap();
-    assert_eq!(true, args.one_file_system);
+    assert_eq!(None, args.semantic_history);
 
     let args =
-        parse_low_raw(["--one-file-system", "--no-one-file-system"]).unwrap();
-    assert_eq!(false, args.one_file_system);
+        parse_low_raw(["--semantic-history", "HEAD~50..HEAD"]).unwrap();
+    assert_eq!(Some("HEAD~50..HEAD".to_string()), args.semantic_history);
 }
 
-/// -o/--only-matching
+/// --semantic-export
 #[derive(Debug)]
-struct OnlyMatching;
+struct SemanticExport;
 
-impl Flag for OnlyMatching {
+impl Flag for SemanticExport {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'o')
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "only-matching"
+        "semantic-export"
+    }
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("FILE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Print only matched parts of a line."
+        "Write the built semantic index to FILE."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Print only the matched (non-empty) parts of a matching line, with each such
-part on a separate output line.
+After building the semantic index for a search, write it to \fIFILE\fP in
+outgrep's versioned binary index format. A file built this way can later be
+loaded with \flag{semantic-import} to skip re-embedding, which is useful for
+letting CI build the index once and having developers download it instead of
+running the embedding model locally.
+.sp
+Only the index built for the most recently searched file is written; this
+does not yet aggregate an index across multiple files.
+.sp
+Example: --semantic 'retry backoff' --semantic-export index.ogsx
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--only-matching does not have a negation");
-        args.only_matching = true;
+        args.semantic_export = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_only_matching() {
+fn test_semantic_export() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.only_matching);
-
-    let args = parse_low_raw(["--only-matching"]).unwrap();
-    assert_eq!(true, args.only_matching);
+    assert_eq!(None, args.semantic_export);
 
-    let args = parse_low_raw(["-o"]).unwrap();
-    assert_eq!(true, args.only_matching);
+    let args = parse_low_raw(["--semantic-export", "index.ogsx"]).unwrap();
+    assert_eq!(Some(PathBuf::from("index.ogsx")), args.semantic_export);
 }
 
-/// --path-separator
+/// --semantic-import
 #[derive(Debug)]
-struct PathSeparator;
+struct SemanticImport;
 
-impl Flag for PathSeparator {
+impl Flag for SemanticImport {
     fn is_switch(&self) -> bool {
         false
     }
+
     fn name_long(&self) -> &'static str {
-        "path-separator"
+        "semantic-import"
     }
+
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("SEPARATOR")
+        Some("FILE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Set the path separator for printing paths."
+        "Load a previously exported semantic index from FILE."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Set the path separator to use when printing file paths. This defaults to your
-platform's path separator, which is \fB/\fP on Unix and \fB\\\fP on Windows.
-This flag is intended for overriding the default when the environment demands
-it (e.g., cygwin). A path separator is limited to a single byte.
+Load a semantic index previously written by \flag{semantic-export} from
+\fIFILE\fP instead of embedding the searched file locally. This implies
+\flag{semantic}.
 .sp
-Setting this flag to an empty string reverts it to its default behavior. That
-is, the path separator is automatically chosen based on the environment.
+Because the loaded index's snippets were not necessarily extracted from the
+file being searched on this machine, matches are reported with byte offsets
+only; line numbers are not resolved in this mode.
+.sp
+Example: --semantic-import index.ogsx 'retry backoff'
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let s = convert::string(v.unwrap_value())?;
-        let raw = Vec::unescape_bytes(&s);
-        args.path_separator = if raw.is_empty() {
-            None
-        } else if raw.len() == 1 {
-            Some(raw[0])
-        } else {
-            anyhow::bail!(
-                "A path separator must be exactly one byte, but \
-                 the given separator is {len} bytes: {sep}\n\
-                 In some shells on Windows '/' is automatically \
-                 expanded. Use '//' instead.",
-                len = raw.len(),
-                sep = s,
-            )
-        };
+        args.semantic_import = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_path_separator() {
+fn test_semantic_import() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.path_separator);
+    assert_eq!(None, args.semantic_import);
 
-    let args = parse_low_raw(["--path-separator", "/"]).unwrap();
-    assert_eq!(Some(b'/'), args.path_separator);
+    let args = parse_low_raw(["--semantic-import", "index.ogsx"]).unwrap();
+    assert_eq!(Some(PathBuf::from("index.ogsx")), args.semantic_import);
+}
 
-    let args = parse_low_raw(["--path-separator", r"\"]).unwrap();
-    assert_eq!(Some(b'\\'), args.path_separator);
+/// --semantic-query
+#[derive(Debug)]
+struct SemanticQueryFlag;
 
-    let args = parse_low_raw(["--path-separator", r"\x00"]).unwrap();
-    assert_eq!(Some(0), args.path_separator);
+impl Flag for SemanticQueryFlag {
+    fn is_switch(&self) -> bool {
+        false
+    }
 
-    let args = parse_low_raw(["--path-separator", r"\0"]).unwrap();
-    assert_eq!(Some(0), args.path_separator);
+    fn name_long(&self) -> &'static str {
+        "semantic-query"
+    }
 
-    let args = parse_low_raw(["--path-separator", "\x00"]).unwrap();
-    assert_eq!(Some(0), args.path_separator);
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("QUERY")
+    }
 
-    let args = parse_low_raw(["--path-separator", "\0"]).unwrap();
-    assert_eq!(Some(0), args.path_separator);
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
 
-    let args =
-        parse_low_raw(["--path-separator", r"\x00", "--path-separator=/"])
-            .unwrap();
-    assert_eq!(Some(b'/'), args.path_separator);
+    fn doc_short(&self) -> &'static str {
+        r"Add a query for multi-query semantic search."
+    }
 
-    let result = parse_low_raw(["--path-separator", "foo"]);
-    assert!(result.is_err(), "{result:?}");
+    fn doc_long(&self) -> &'static str {
+        r"
+Search semantically for \fIQUERY\fP instead of (or in addition to) the
+pattern given on the command line. This implies \flag{semantic}. May be
+given multiple times; when it is, each chunk's similarity score against
+every query is combined according to \flag{semantic-query-fusion} instead of
+ranking against a single query.
+.sp
+This is useful for queries like \-\-semantic\-query 'http client'
+\-\-semantic\-query 'retry logic', which (with the default OR fusion)
+surfaces chunks that look like either concept, or (with AND fusion) only
+chunks that look like both at once.
+.sp
+Example: --semantic-query 'http client' --semantic-query 'retry logic'
+"
+    }
 
-    let result = parse_low_raw(["--path-separator", r"\\x00"]);
-    assert!(result.is_err(), "{result:?}");
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.semantic_query.push(convert::string(v.unwrap_value())?);
+        Ok(())
+    }
 }
 
-/// --passthru
+#[cfg(test)]
+#[test]
+fn test_semantic_query() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.semantic_query);
+
+    let args = parse_low_raw(["--semantic-query", "http client"]).unwrap();
+    assert_eq!(vec!["http client".to_string()], args.semantic_query);
+
+    let args = parse_low_raw([
+        "--semantic-query",
+        "http client",
+        "--semantic-query",
+        "retry logic",
+    ])
+    .unwrap();
+    assert_eq!(
+        vec!["http client".to_string(), "retry logic".to_string()],
+        args.semantic_query
+    );
+}
+
+/// --semantic-query-fusion
 #[derive(Debug)]
-struct Passthru;
+struct SemanticQueryFusion;
 
-impl Flag for Passthru {
+impl Flag for SemanticQueryFusion {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "passthru"
+        "semantic-query-fusion"
     }
-    fn aliases(&self) -> &'static [&'static str] {
-        &["passthrough"]
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("MODE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Print both matching and non-matching lines."
+        r"How multiple --semantic-query scores are combined."
     }
+
     fn doc_long(&self) -> &'static str {
-        r#"
-Print both matching and non-matching lines.
+        r"
+Controls how a chunk's per-query similarity scores are combined when
+multiple \flag{semantic-query} flags are given. \fIMODE\fP can be one of the
+following values:
 .sp
-Another way to achieve a similar effect is by modifying your pattern to match
-the empty string. For example, if you are searching using \fBrg\fP \fIfoo\fP,
-then using \fBrg\fP \fB'^|\fP\fIfoo\fP\fB'\fP instead will emit every line in
-every file searched, but only occurrences of \fIfoo\fP will be highlighted.
-This flag enables the same behavior without needing to modify the pattern.
+.TP 15
+\fBor\fP
+Keep each chunk's highest score across all queries. This is the default.
+.TP 15
+\fBand\fP
+Keep each chunk's lowest score across all queries, so a chunk only ranks
+highly if it looks relevant to every query.
 .sp
-An alternative spelling for this flag is \fB\-\-passthrough\fP.
+Has no effect unless \flag{semantic-query} is given more than once.
 .sp
-This overrides the \flag{context}, \flag{after-context} and
-\flag{before-context} flags.
-"#
+Example: --semantic-query-fusion and
+"
+    }
+
+    fn doc_choices(&self) -> &'static [&'static str] {
+        &["or", "and"]
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--passthru has no negation");
-        args.context = ContextMode::Passthru;
+        use grep::searcher::QueryFusion;
+
+        args.semantic_query_fusion = match convert::str(&v.unwrap_value())? {
+            "or" => QueryFusion::Or,
+            "and" => QueryFusion::And,
+            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
+        };
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_passthru() {
+fn test_semantic_query_fusion() {
+    use grep::searcher::QueryFusion;
+
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(ContextMode::default(), args.context);
+    assert_eq!(QueryFusion::Or, args.semantic_query_fusion);
 
-    let args = parse_low_raw(["--passthru"]).unwrap();
-    assert_eq!(ContextMode::Passthru, args.context);
+    let args = parse_low_raw(["--semantic-query-fusion", "and"]).unwrap();
+    assert_eq!(QueryFusion::And, args.semantic_query_fusion);
 
-    let args = parse_low_raw(["--passthrough"]).unwrap();
-    assert_eq!(ContextMode::Passthru, args.context);
+    let args = parse_low_raw(["--semantic-query-fusion", "bogus"]);
+    assert!(args.is_err());
 }
 
-/// -P/--pcre2
+/// --semantic-download-model
 #[derive(Debug)]
-struct PCRE2;
+struct SemanticDownloadModel;
 
-impl Flag for PCRE2 {
+impl Flag for SemanticDownloadModel {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'P')
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "pcre2"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-pcre2")
+        "semantic-download-model"
     }
+
     fn doc_category(&self) -> Category {
         Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Enable PCRE2 matching."
+        "Download a semantic search model and exit."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When this flag is present, ripgrep will use the PCRE2 regex engine instead of
-its default regex engine.
-.sp
-This is generally useful when you want to use features such as look-around
-or backreferences.
+Download the named model from the model registry into the semantic model
+storage directory (\flag{semantic-model-path}, or the default cache location
+if that flag isn't given), verify it against the registry's recorded SHA256
+checksums, and then exit without searching.
 .sp
-Using this flag is the same as passing \fB\-\-engine=pcre2\fP. Users may
-instead elect to use \fB\-\-engine=auto\fP to ask ripgrep to automatically
-select the right regex engine based on the patterns given. This flag and the
-\flag{engine} flag override one another.
+A download that is interrupted leaves a partial file in place. Running the
+same command again resumes from where it left off instead of starting over.
 .sp
-Note that PCRE2 is an optional ripgrep feature. If PCRE2 wasn't included in
-your build of ripgrep, then using this flag will result in ripgrep printing
-an error message and exiting. PCRE2 may also have worse user experience in
-some cases, since it has fewer introspection APIs than ripgrep's default
-regex engine. For example, if you use a \fB\\n\fP in a PCRE2 regex without
-the \flag{multiline} flag, then ripgrep will silently fail to match anything
-instead of reporting an error immediately (like it does with the default regex
-engine).
+Example: --semantic-download-model all-MiniLM-L6-v2
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.engine = if v.unwrap_switch() {
-            EngineChoice::PCRE2
-        } else {
-            EngineChoice::Default
-        };
+        let name = convert::string(v.unwrap_value())?;
+        args.special = Some(SpecialMode::DownloadModel(name));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_pcre2() {
+fn test_semantic_download_model() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
-
-    let args = parse_low_raw(["--pcre2"]).unwrap();
-    assert_eq!(EngineChoice::PCRE2, args.engine);
-
-    let args = parse_low_raw(["-P"]).unwrap();
-    assert_eq!(EngineChoice::PCRE2, args.engine);
-
-    let args = parse_low_raw(["-P", "--no-pcre2"]).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
-
-    let args = parse_low_raw(["--engine=auto", "-P", "--no-pcre2"]).unwrap();
-    assert_eq!(EngineChoice::Default, args.engine);
+    assert_eq!(None, args.special);
 
-    let args = parse_low_raw(["-P", "--engine=auto"]).unwrap();
-    assert_eq!(EngineChoice::Auto, args.engine);
+    let args =
+        parse_low_raw(["--semantic-download-model", "all-MiniLM-L6-v2"])
+            .unwrap();
+    assert_eq!(
+        Some(SpecialMode::DownloadModel("all-MiniLM-L6-v2".to_string())),
+        args.special
+    );
 }
 
-/// --pcre2-version
+/// --semantic-list-models
 #[derive(Debug)]
-struct PCRE2Version;
+struct SemanticListModels;
 
-impl Flag for PCRE2Version {
+impl Flag for SemanticListModels {
     fn is_switch(&self) -> bool {
         true
     }
+
     fn name_long(&self) -> &'static str {
-        "pcre2-version"
+        "semantic-list-models"
     }
+
     fn doc_category(&self) -> Category {
-        Category::OtherBehaviors
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Print the version of PCRE2 that ripgrep uses."
+        r"List the semantic model registry and exit."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-When this flag is present, ripgrep will print the version of PCRE2 in use,
-along with other information, and then exit. If PCRE2 is not available, then
-ripgrep will print an error message and exit with an error code.
+List every model in the semantic model registry (\flag{semantic-model-path},
+or the embedded default registry if that flag isn't given) and exit without
+searching.
+.sp
+For each model, this prints its name, embedding dimensions, declared and
+on-disk size, whether it has already been downloaded into the local model
+cache, and whether it is the registry's default recommendation.
+.sp
+When \flag{json-output} is also given, the same information is printed as a
+JSON array instead of a table.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--pcre2-version has no negation");
-        args.special = Some(SpecialMode::VersionPCRE2);
+        assert!(
+            v.unwrap_switch(),
+            "--semantic-list-models can only be enabled"
+        );
+        args.special = Some(SpecialMode::ListModels(false));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_pcre2_version() {
+fn test_semantic_list_models() {
     let args = parse_low_raw(None::<&str>).unwrap();
     assert_eq!(None, args.special);
 
-    let args = parse_low_raw(["--pcre2-version"]).unwrap();
-    assert_eq!(Some(SpecialMode::VersionPCRE2), args.special);
+    let args = parse_low_raw(["--semantic-list-models"]).unwrap();
+    assert_eq!(Some(SpecialMode::ListModels(false)), args.special);
 }
 
-/// --pre
+/// --semantic-index-stats
 #[derive(Debug)]
-struct Pre;
+struct SemanticIndexStats;
 
-impl Flag for Pre {
+impl Flag for SemanticIndexStats {
     fn is_switch(&self) -> bool {
         false
     }
+
     fn name_long(&self) -> &'static str {
-        "pre"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-pre")
+        "semantic-index-stats"
     }
+
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("COMMAND")
+        Some("FILE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Input
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Search output of COMMAND for each PATH."
+        r"Report statistics about a semantic index file and exit."
     }
+
     fn doc_long(&self) -> &'static str {
-        r#"
-For each input \fIPATH\fP, this flag causes ripgrep to search the standard
-output of \fICOMMAND\fP \fIPATH\fP instead of the contents of \fIPATH\fP.
-This option expects the \fICOMMAND\fP program to either be a path or to be
-available in your \fBPATH\fP. Either an empty string \fICOMMAND\fP or the
-\fB\-\-no\-pre\fP flag will disable this behavior.
-.sp
-.TP 12
-\fBWARNING\fP
-When this flag is set, ripgrep will unconditionally spawn a process for every
-file that is searched. Therefore, this can incur an unnecessarily large
-performance penalty if you don't otherwise need the flexibility offered by this
-flag. One possible mitigation to this is to use the \flag{pre-glob} flag to
-limit which files a preprocessor is run with.
-.PP
-A preprocessor is not run when ripgrep is searching stdin.
-.sp
-When searching over sets of files that may require one of several
-preprocessors, \fICOMMAND\fP should be a wrapper program which first classifies
-\fIPATH\fP based on magic numbers/content or based on the \fIPATH\fP name and
-then dispatches to an appropriate preprocessor. Each \fICOMMAND\fP also has its
-standard input connected to \fIPATH\fP for convenience.
-.sp
-For example, a shell script for \fICOMMAND\fP might look like:
+        r"
+Read \fIFILE\fP, a semantic index previously written by \flag{semantic-export},
+and print its format version, number of indexed chunks, embedding
+dimensions, and size on disk, then exit without searching.
 .sp
-.EX
-    case "$1" in
-    *.pdf)
-        exec pdftotext "$1" -
-        ;;
-    *)
-        case $(file "$1") in
-        *Zstandard*)
-            exec pzstd -cdq
-            ;;
-        *)
-            exec cat
-            ;;
-        esac
-        ;;
-    esac
-.EE
+The index format does not currently record which source files a chunk came
+from, the embedding model used to build it, or when it was built, so this
+cannot yet report per-file counts, staleness relative to the files on disk,
+or the model used. The report notes this explicitly rather than guessing.
 .sp
-The above script uses \fBpdftotext\fP to convert a PDF file to plain text. For
-all other files, the script uses the \fBfile\fP utility to sniff the type of
-the file based on its contents. If it is a compressed file in the Zstandard
-format, then \fBpzstd\fP is used to decompress the contents to stdout.
+When \flag{json-output} is also given, the same information is printed as
+JSON instead of a plain-text report.
 .sp
-This overrides the \flag{search-zip} flag.
-"#
-    }
-    fn completion_type(&self) -> CompletionType {
-        CompletionType::Executable
+Example: --semantic-index-stats index.ogsx
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let path = match v {
-            FlagValue::Value(v) => PathBuf::from(v),
-            FlagValue::Switch(yes) => {
-                assert!(!yes, "there is no affirmative switch for --pre");
-                args.pre = None;
-                return Ok(());
-            }
-        };
-        args.pre = if path.as_os_str().is_empty() { None } else { Some(path) };
-        if args.pre.is_some() {
-            args.search_zip = false;
-        }
+        let path = PathBuf::from(v.unwrap_value());
+        args.special = Some(SpecialMode::SemanticIndexStats(path, false));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_pre() {
+fn test_semantic_index_stats() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.pre);
-
-    let args = parse_low_raw(["--pre", "foo/bar"]).unwrap();
-    assert_eq!(Some(PathBuf::from("foo/bar")), args.pre);
-
-    let args = parse_low_raw(["--pre", ""]).unwrap();
-    assert_eq!(None, args.pre);
-
-    let args = parse_low_raw(["--pre", "foo/bar", "--pre", ""]).unwrap();
-    assert_eq!(None, args.pre);
-
-    let args = parse_low_raw(["--pre", "foo/bar", "--pre="]).unwrap();
-    assert_eq!(None, args.pre);
+    assert_eq!(None, args.special);
 
-    let args = parse_low_raw(["--pre", "foo/bar", "--no-pre"]).unwrap();
-    assert_eq!(None, args.pre);
+    let args =
+        parse_low_raw(["--semantic-index-stats", "index.ogsx"]).unwrap();
+    assert_eq!(
+        Some(SpecialMode::SemanticIndexStats(
+            PathBuf::from("index.ogsx"),
+            false
+        )),
+        args.special
+    );
 }
 
-/// --pre-glob
+/// --semantic-gc
 #[derive(Debug)]
-struct PreGlob;
+struct SemanticGc;
 
-impl Flag for PreGlob {
+impl Flag for SemanticGc {
     fn is_switch(&self) -> bool {
         false
     }
+
     fn name_long(&self) -> &'static str {
-        "pre-glob"
+        "semantic-gc"
     }
+
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("GLOB")
+        Some("FILE")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Input
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Include or exclude files from a preprocessor."
+        r"Compact a semantic index file and reclaim space, then exit."
     }
-    fn doc_long(&self) -> &'static str {
-        r#"
-This flag works in conjunction with the \flag{pre} flag. Namely, when one or
-more \flag{pre-glob} flags are given, then only files that match the given set
-of globs will be handed to the command specified by the \flag{pre} flag. Any
-non-matching files will be searched without using the preprocessor command.
-.sp
-This flag is useful when searching many files with the \flag{pre} flag.
-Namely, it provides the ability to avoid process overhead for files that
-don't need preprocessing. For example, given the following shell script,
-\fIpre-pdftotext\fP:
-.sp
-.EX
-    #!/bin/sh
-    pdftotext "$1" -
-.EE
+
+    fn doc_long(&self) -> &'static str {
+        r"
+Read \fIFILE\fP, a semantic index previously written by \flag{semantic-export},
+drop every indexed chunk whose source file no longer exists on disk, rewrite
+the file with the survivors, and print how many chunks and bytes were
+reclaimed, then exit without searching.
 .sp
-then it is possible to use \fB\-\-pre\fP \fIpre-pdftotext\fP \fB--pre-glob
-'\fP\fI*.pdf\fP\fB'\fP to make it so ripgrep only executes the
-\fIpre-pdftotext\fP command on files with a \fI.pdf\fP extension.
+A chunk with no recorded source path (written by a pre-v2 index, or indexed
+from content with no associated file, e.g. \flag{semantic-history}) is kept
+rather than guessed at. A renamed file looks identical to a deleted one from
+here, so a rename is tombstoned the same way a deletion is; re-indexing under
+the new name is the only way to recover those chunks.
 .sp
-Multiple \flag{pre-glob} flags may be used. Globbing rules match
-\fBgitignore\fP globs. Precede a glob with a \fB!\fP to exclude it.
+When \flag{json-output} is also given, the reclaimed-space report is printed
+as JSON instead of a plain-text summary.
 .sp
-This flag has no effect if the \flag{pre} flag is not used.
-"#
+Example: --semantic-gc index.ogsx
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let glob = convert::string(v.unwrap_value())?;
-        args.pre_glob.push(glob);
+        let path = PathBuf::from(v.unwrap_value());
+        args.special = Some(SpecialMode::SemanticGc(path, false));
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_pre_glob() {
+fn test_semantic_gc() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<String>::new(), args.pre_glob);
-
-    let args = parse_low_raw(["--pre-glob", "*.pdf"]).unwrap();
-    assert_eq!(vec!["*.pdf".to_string()], args.pre_glob);
+    assert_eq!(None, args.special);
 
-    let args =
-        parse_low_raw(["--pre-glob", "*.pdf", "--pre-glob=foo"]).unwrap();
-    assert_eq!(vec!["*.pdf".to_string(), "foo".to_string()], args.pre_glob);
+    let args = parse_low_raw(["--semantic-gc", "index.ogsx"]).unwrap();
+    assert_eq!(
+        Some(SpecialMode::SemanticGc(PathBuf::from("index.ogsx"), false)),
+        args.special
+    );
 }
 
-/// -p/--pretty
+/// --similar-to
 #[derive(Debug)]
-struct Pretty;
+struct SimilarTo;
 
-impl Flag for Pretty {
+impl Flag for SimilarTo {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'p')
+        false
     }
+
     fn name_long(&self) -> &'static str {
-        "pretty"
+        "similar-to"
+    }
+
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("FILE[:RANGE]")
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Alias for colors, headings and line numbers."
+        "Find code semantically similar to a snippet or file."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-This is a convenience alias for \fB\-\-color=always \-\-heading
-\-\-line\-number\fP. This flag is useful when you still want pretty output even
-if you're piping ripgrep to another program or file. For example: \fBrg -p
-\fP\fIfoo\fP \fB| less -R\fP.
+Find code that is semantically similar to a given snippet, embedding the
+snippet and ranking the rest of the semantic index by similarity to it.
+This is query-by-example: instead of typing a description of the code you
+want, you point at code that already looks like it.
+.sp
+\fIFILE\fP is the path to a file containing the example snippet. An optional
+\fIRANGE\fP of the form \fISTART\fP-\fIEND\fP (1-based, inclusive line
+numbers) may be appended after a colon to select a slice of that file rather
+than embedding the whole thing.
+.sp
+This flag implies \flag{semantic}, and the snippet is used as the query in
+place of the search pattern.
+.sp
+Example: --similar-to src/parser.rs:120-160
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--pretty has no negation");
-        args.color = ColorChoice::Always;
-        args.heading = Some(true);
-        args.line_number = Some(true);
+        args.similar_to = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_pretty() {
+fn test_similar_to() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(ColorChoice::Auto, args.color);
-    assert_eq!(None, args.heading);
-    assert_eq!(None, args.line_number);
-
-    let args = parse_low_raw(["--pretty"]).unwrap();
-    assert_eq!(ColorChoice::Always, args.color);
-    assert_eq!(Some(true), args.heading);
-    assert_eq!(Some(true), args.line_number);
+    assert_eq!(None, args.similar_to);
 
-    let args = parse_low_raw(["-p"]).unwrap();
-    assert_eq!(ColorChoice::Always, args.color);
-    assert_eq!(Some(true), args.heading);
-    assert_eq!(Some(true), args.line_number);
+    let args = parse_low_raw(["--similar-to", "src/lib.rs:10-20"]).unwrap();
+    assert_eq!(Some("src/lib.rs:10-20".to_string()), args.similar_to);
 }
 
-/// -q/--quiet
+/// --hybrid
 #[derive(Debug)]
-struct Quiet;
+struct Hybrid;
 
-impl Flag for Quiet {
+impl Flag for Hybrid {
     fn is_switch(&self) -> bool {
         true
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'q')
-    }
+
     fn name_long(&self) -> &'static str {
-        "quiet"
+        "hybrid"
     }
+
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
+
     fn doc_short(&self) -> &'static str {
-        r"Do not print anything to stdout."
+        "Re-rank regex matches by semantic similarity to the query."
     }
+
     fn doc_long(&self) -> &'static str {
         r"
-Do not print anything to stdout. If a match is found in a file, then ripgrep
-will stop searching. This is useful when ripgrep is used only for its exit code
-(which will be an error code if no matches are found).
+Run the normal regex search, then re-rank the resulting matches by embedding
+similarity to the search pattern, instead of leaving them in file order.
+This combines the precision of literal matching with the ranking of
+\flag{semantic}: you still only see lines that actually match the pattern,
+but the most semantically relevant ones are surfaced first.
 .sp
-When \flag{files} is used, ripgrep will stop finding files after finding the
-first file that does not match any ignore rules.
+Each result is printed with both the literal match and its similarity score,
+so you can see why it was ranked where it was.
+.sp
+Example: --hybrid 'fn parse'
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--quiet has no negation");
-        args.quiet = true;
+        args.hybrid = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_quiet() {
+fn test_hybrid() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.quiet);
-
-    let args = parse_low_raw(["--quiet"]).unwrap();
-    assert_eq!(true, args.quiet);
-
-    let args = parse_low_raw(["-q"]).unwrap();
-    assert_eq!(true, args.quiet);
-
-    // flags like -l and --json cannot override -q, regardless of order
-    let args = parse_low_raw(["-q", "--json"]).unwrap();
-    assert_eq!(true, args.quiet);
-
-    let args = parse_low_raw(["-q", "--files-with-matches"]).unwrap();
-    assert_eq!(true, args.quiet);
-
-    let args = parse_low_raw(["-q", "--files-without-match"]).unwrap();
-    assert_eq!(true, args.quiet);
+    assert_eq!(false, args.hybrid);
 
-    let args = parse_low_raw(["-q", "--count"]).unwrap();
-    assert_eq!(true, args.quiet);
-
-    let args = parse_low_raw(["-q", "--count-matches"]).unwrap();
-    assert_eq!(true, args.quiet);
+    let args = parse_low_raw(["--hybrid"]).unwrap();
+    assert_eq!(true, args.hybrid);
 }
 
-/// --regex-size-limit
+/// --since
 #[derive(Debug)]
-struct RegexSizeLimit;
+struct Since;
 
-impl Flag for RegexSizeLimit {
+impl Flag for Since {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "regex-size-limit"
+        "since"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM+SUFFIX?")
+        Some("TIMESTAMP")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"The size limit of the compiled regex."
+        r"Only show matches on or after TIMESTAMP."
     }
     fn doc_long(&self) -> &'static str {
         r"
-The size limit of the compiled regex, where the compiled regex generally
-corresponds to a single object in memory that can match all of the patterns
-provided to ripgrep. The default limit is generous enough that most reasonable
-patterns (or even a small number of them) should fit.
-.sp
-This useful to change when you explicitly want to let ripgrep spend potentially
-much more time and/or memory building a regex matcher.
-.sp
-The input format accepts suffixes of \fBK\fP, \fBM\fP or \fBG\fP which
-correspond to kilobytes, megabytes and gigabytes, respectively. If no suffix is
-provided the input is treated as bytes.
+Only show matches on lines timestamped at or after \fITIMESTAMP\fP. A line's
+timestamp is parsed from its own leading bytes, so this is meant for log
+files: ISO 8601 (\fB2024\-01\-02T15:04:05Z\fP, with or without fractional
+seconds or a timezone offset, and \fBT\fP or a space before the time) and
+syslog (\fBJan  2 15:04:05\fP) timestamps are both recognized. Lines that
+don't start with a recognized timestamp are always shown, since only some
+lines in a log (continuation lines in a multi-line stack trace, for example)
+are expected to carry one.
+.sp
+\fITIMESTAMP\fP accepts the same formats, plus a bare date
+(\fB2024\-01\-02\fP), which is treated as midnight UTC. A syslog timestamp has
+no year, so both it and a year-less \fITIMESTAMP\fP are resolved against the
+current year.
+.sp
+Can be combined with \flag{until} to bound a window on both ends.
+.sp
+Example: --since 2024-01-02T00:00:00Z
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let v = v.unwrap_value();
-        args.regex_size_limit = Some(convert::human_readable_usize(&v)?);
+        let v = convert::str(&v.unwrap_value())?.to_string();
+        args.since = Some(
+            crate::logtime::LogTimestamp::parse_flag_value(&v)
+                .map_err(|e| anyhow::anyhow!(e))?,
+        );
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_regex_size_limit() {
+fn test_since() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.regex_size_limit);
-
-    #[cfg(target_pointer_width = "64")]
-    {
-        let args = parse_low_raw(["--regex-size-limit", "9G"]).unwrap();
-        assert_eq!(Some(9 * (1 << 30)), args.regex_size_limit);
-
-        let args = parse_low_raw(["--regex-size-limit=9G"]).unwrap();
-        assert_eq!(Some(9 * (1 << 30)), args.regex_size_limit);
-
-        let args =
-            parse_low_raw(["--regex-size-limit=9G", "--regex-size-limit=0"])
-                .unwrap();
-        assert_eq!(Some(0), args.regex_size_limit);
-    }
-
-    let args = parse_low_raw(["--regex-size-limit=0K"]).unwrap();
-    assert_eq!(Some(0), args.regex_size_limit);
+    assert_eq!(None, args.since);
 
-    let args = parse_low_raw(["--regex-size-limit=0M"]).unwrap();
-    assert_eq!(Some(0), args.regex_size_limit);
-
-    let args = parse_low_raw(["--regex-size-limit=0G"]).unwrap();
-    assert_eq!(Some(0), args.regex_size_limit);
-
-    let result =
-        parse_low_raw(["--regex-size-limit", "9999999999999999999999"]);
-    assert!(result.is_err(), "{result:?}");
+    let args = parse_low_raw(["--since", "2024-01-02"]).unwrap();
+    assert!(args.since.is_some());
 
-    let result = parse_low_raw(["--regex-size-limit", "9999999999999999G"]);
-    assert!(result.is_err(), "{result:?}");
+    assert!(parse_low_raw(["--since", "not-a-date"]).is_err());
 }
 
-/// -e/--regexp
+/// --until
 #[derive(Debug)]
-struct Regexp;
+struct Until;
 
-impl Flag for Regexp {
+impl Flag for Until {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'e')
-    }
     fn name_long(&self) -> &'static str {
-        "regexp"
+        "until"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("PATTERN")
+        Some("TIMESTAMP")
     }
     fn doc_category(&self) -> Category {
-        Category::Input
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"A pattern to search for."
+        r"Only show matches on or before TIMESTAMP."
     }
     fn doc_long(&self) -> &'static str {
         r"
-A pattern to search for. This option can be provided multiple times, where
-all patterns given are searched, in addition to any patterns provided by
-\flag{file}. Lines matching at least one of the provided patterns are printed.
-This flag can also be used when searching for patterns that start with a dash.
-.sp
-For example, to search for the literal \fB\-foo\fP:
-.sp
-.EX
-    rg \-e \-foo
-.EE
+Only show matches on lines timestamped at or before \fITIMESTAMP\fP. See
+\flag{since} for the accepted timestamp formats and how lines with no
+recognized timestamp are handled; the two flags share one parser and can be
+combined to bound a window on both ends.
 .sp
-You can also use the special \fB\-\-\fP delimiter to indicate that no more
-flags will be provided. Namely, the following is equivalent to the above:
-.sp
-.EX
-    rg \-\- \-foo
-.EE
-.sp
-When \flag{file} or \flag{regexp} is used, then ripgrep treats all positional
-arguments as files or directories to search.
+Example: --since 2024-01-02 --until 2024-01-03
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let regexp = convert::string(v.unwrap_value())?;
-        args.patterns.push(PatternSource::Regexp(regexp));
+        let v = convert::str(&v.unwrap_value())?.to_string();
+        args.until = Some(
+            crate::logtime::LogTimestamp::parse_flag_value(&v)
+                .map_err(|e| anyhow::anyhow!(e))?,
+        );
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_regexp() {
-    let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(Vec::<PatternSource>::new(), args.patterns);
-
-    let args = parse_low_raw(["--regexp", "foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["--regexp=foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["-e", "foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["-efoo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["--regexp", "-foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["--regexp=-foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["-e", "-foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["-e-foo"]).unwrap();
-    assert_eq!(vec![PatternSource::Regexp("-foo".to_string())], args.patterns);
-
-    let args = parse_low_raw(["--regexp=foo", "--regexp", "bar"]).unwrap();
-    assert_eq!(
-        vec![
-            PatternSource::Regexp("foo".to_string()),
-            PatternSource::Regexp("bar".to_string())
-        ],
-        args.patterns
-    );
-
-    // While we support invalid UTF-8 arguments in general, patterns must be
-    // valid UTF-8.
-    #[cfg(unix)]
-    {
-        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
-
-        let bytes = &[b'A', 0xFF, b'Z'][..];
-        let result = parse_low_raw([
-            OsStr::from_bytes(b"-e"),
-            OsStr::from_bytes(bytes),
-        ]);
-        assert!(result.is_err(), "{result:?}");
-    }
-
-    // Check that combining -e/--regexp and -f/--file works as expected.
-    let args = parse_low_raw(["-efoo", "-fbar"]).unwrap();
-    assert_eq!(
-        vec![
-            PatternSource::Regexp("foo".to_string()),
-            PatternSource::File(PathBuf::from("bar"))
-        ],
-        args.patterns
-    );
+fn test_until() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.until);
 
-    let args = parse_low_raw(["-efoo", "-fbar", "-equux"]).unwrap();
-    assert_eq!(
-        vec![
-            PatternSource::Regexp("foo".to_string()),
-            PatternSource::File(PathBuf::from("bar")),
-            PatternSource::Regexp("quux".to_string()),
-        ],
-        args.patterns
-    );
+    let args = parse_low_raw(["--until", "2024-01-02"]).unwrap();
+    assert!(args.until.is_some());
+
+    assert!(parse_low_raw(["--until", "not-a-date"]).is_err());
 }
 
-/// -r/--replace
+/// --jsonpath
 #[derive(Debug)]
-struct Replace;
+struct JsonPath;
 
-impl Flag for Replace {
+impl Flag for JsonPath {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'r')
-    }
     fn name_long(&self) -> &'static str {
-        "replace"
+        "jsonpath"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("REPLACEMENT")
+        Some("PATH")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Replace matches with the given text."
+        r"Match the pattern against values selected from JSON files."
     }
     fn doc_long(&self) -> &'static str {
-        r#"
-Replaces every match with the text given when printing results. Neither this
-flag nor any other ripgrep flag will modify your files.
-.sp
-Capture group indices (e.g., \fB$\fP\fI5\fP) and names (e.g., \fB$\fP\fIfoo\fP)
-are supported in the replacement string. Capture group indices are numbered
-based on the position of the opening parenthesis of the group, where the
-leftmost such group is \fB$\fP\fI1\fP. The special \fB$\fP\fI0\fP group
-corresponds to the entire match.
-.sp
-The name of a group is formed by taking the longest string of letters, numbers
-and underscores (i.e. \fB[_0-9A-Za-z]\fP) after the \fB$\fP. For example,
-\fB$\fP\fI1a\fP will be replaced with the group named \fI1a\fP, not the
-group at index \fI1\fP. If the group's name contains characters that aren't
-letters, numbers or underscores, or you want to immediately follow the group
-with another string, the name should be put inside braces. For example,
-\fB${\fP\fI1\fP\fB}\fP\fIa\fP will take the content of the group at index
-\fI1\fP and append \fIa\fP to the end of it.
-.sp
-If an index or name does not refer to a valid capture group, it will be
-replaced with an empty string.
-.sp
-In shells such as Bash and zsh, you should wrap the pattern in single quotes
-instead of double quotes. Otherwise, capture group indices will be replaced by
-expanded shell variables which will most likely be empty.
+        r"
+Parse each searched file as JSON and match the pattern against the text of
+every value selected by the dotted key \fIPATH\fP, instead of matching lines
+of raw text. A \fIPATH\fP segment of \fB*\fP matches any object key or array
+index at that depth, so \fBdependencies.*.version\fP selects the
+\fBversion\fP field of every entry under \fBdependencies\fP. Numeric segments
+select an array element by position.
 .sp
-To write a literal \fB$\fP, use \fB$$\fP.
+Results are reported at key-path granularity: one match per selected value
+whose text matches the pattern, labeled with the dotted path it was found
+at rather than a line number.
 .sp
-Note that the replacement by default replaces each match, and not the entire
-line. To replace the entire line, you should match the entire line.
+This flag conflicts with \flag{yamlpath}.
 .sp
-This flag can be used with the \flag{only-matching} flag.
-"#
+Example: --jsonpath dependencies.*.version '^0\.'
+"
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.replace = Some(convert::string(v.unwrap_value())?.into());
+        args.jsonpath = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_replace() {
-    use bstr::BString;
-
+fn test_jsonpath() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.replace);
-
-    let args = parse_low_raw(["--replace", "foo"]).unwrap();
-    assert_eq!(Some(BString::from("foo")), args.replace);
-
-    let args = parse_low_raw(["--replace", "-foo"]).unwrap();
-    assert_eq!(Some(BString::from("-foo")), args.replace);
-
-    let args = parse_low_raw(["-r", "foo"]).unwrap();
-    assert_eq!(Some(BString::from("foo")), args.replace);
-
-    let args = parse_low_raw(["-r", "foo", "-rbar"]).unwrap();
-    assert_eq!(Some(BString::from("bar")), args.replace);
+    assert_eq!(None, args.jsonpath);
 
-    let args = parse_low_raw(["-r", "foo", "-r", ""]).unwrap();
-    assert_eq!(Some(BString::from("")), args.replace);
+    let args =
+        parse_low_raw(["--jsonpath", "dependencies.*.version"]).unwrap();
+    assert_eq!(Some("dependencies.*.version".to_string()), args.jsonpath);
 }
 
-/// -z/--search-zip
+/// --yamlpath
 #[derive(Debug)]
-struct SearchZip;
+struct YamlPath;
 
-impl Flag for SearchZip {
+impl Flag for YamlPath {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'z')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "search-zip"
+        "yamlpath"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-search-zip")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
     }
     fn doc_category(&self) -> Category {
-        Category::Input
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Search in compressed files."
+        r"Match the pattern against values selected from YAML files."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag instructs ripgrep to search in compressed files. Currently gzip,
-bzip2, xz, LZ4, LZMA, Brotli and Zstd files are supported. This option expects
-the decompression binaries (such as \fBgzip\fP) to be available in your
-\fBPATH\fP. If the required binaries are not found, then ripgrep will not
-emit an error messages by default. Use the \flag{debug} flag to see more
-information.
+Same as \flag{jsonpath}, except each searched file is parsed as YAML instead
+of JSON. See \flag{jsonpath} for the \fIPATH\fP syntax and how results are
+reported.
 .sp
-Note that this flag does not make ripgrep search archive formats as directory
-trees. It only makes ripgrep detect compressed files and then decompress them
-before searching their contents as it would any other file.
+This flag conflicts with \flag{jsonpath}.
 .sp
-This overrides the \flag{pre} flag.
+Example: --yamlpath dependencies.*.version '^0\.'
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.search_zip = if v.unwrap_switch() {
-            args.pre = None;
-            true
-        } else {
-            false
-        };
+        args.yamlpath = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_search_zip() {
+fn test_yamlpath() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.search_zip);
-
-    let args = parse_low_raw(["--search-zip"]).unwrap();
-    assert_eq!(true, args.search_zip);
+    assert_eq!(None, args.yamlpath);
 
-    let args = parse_low_raw(["-z"]).unwrap();
-    assert_eq!(true, args.search_zip);
-
-    let args = parse_low_raw(["-z", "--no-search-zip"]).unwrap();
-    assert_eq!(false, args.search_zip);
-
-    let args = parse_low_raw(["--pre=foo", "--no-search-zip"]).unwrap();
-    assert_eq!(Some(PathBuf::from("foo")), args.pre);
-    assert_eq!(false, args.search_zip);
-
-    let args = parse_low_raw(["--pre=foo", "--search-zip"]).unwrap();
-    assert_eq!(None, args.pre);
-    assert_eq!(true, args.search_zip);
-
-    let args = parse_low_raw(["--pre=foo", "-z", "--no-search-zip"]).unwrap();
-    assert_eq!(None, args.pre);
-    assert_eq!(false, args.search_zip);
+    let args =
+        parse_low_raw(["--yamlpath", "dependencies.*.version"]).unwrap();
+    assert_eq!(Some("dependencies.*.version".to_string()), args.yamlpath);
 }
 
-/// -S/--smart-case
+/// --csv-column
 #[derive(Debug)]
-struct SmartCase;
+struct CsvColumn;
 
-impl Flag for SmartCase {
+impl Flag for CsvColumn {
     fn is_switch(&self) -> bool {
-        true
-    }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'S')
+        false
     }
     fn name_long(&self) -> &'static str {
-        "smart-case"
+        "csv-column"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("COLUMN")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Smart case search."
+        r"Match the pattern against a column of a CSV/TSV file."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag instructs ripgrep to searches case insensitively if the pattern is
-all lowercase. Otherwise, ripgrep will search case sensitively.
-.sp
-A pattern is considered all lowercase if both of the following rules hold:
-.sp
-.IP \(bu 3n
-First, the pattern contains at least one literal character. For example,
-\fBa\\w\fP contains a literal (\fBa\fP) but just \fB\\w\fP does not.
-.sp
-.IP \(bu 3n
-Second, of the literals in the pattern, none of them are considered to be
-uppercase according to Unicode. For example, \fBfoo\\pL\fP has no uppercase
-literals but \fBFoo\\pL\fP does.
-.PP
-This overrides the \flag{case-sensitive} and \flag{ignore-case} flags.
+Parse each searched file as delimited records (sniffing whether the
+delimiter is a comma, tab or semicolon, and whether the first row is a
+header) and match the pattern against the text of \fICOLUMN\fP only,
+instead of matching whole lines of raw text. \fICOLUMN\fP is either a
+header name, resolved against the first row when it looks like a header, or
+a 0-based column index, which works with or without a header.
+.sp
+Results are reported one per matching row, labeled with the row's 1-based
+data row number (not counting a header row) rather than a line number. Use
+\flag{csv-row} to print the whole matching row instead of just the column
+value.
+.sp
+Example: --csv-column email 'example\.com$'
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--smart-case flag has no negation");
-        args.case = CaseMode::Smart;
+        args.csv_column = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_smart_case() {
+fn test_csv_column() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(CaseMode::Sensitive, args.case);
-
-    let args = parse_low_raw(["--smart-case"]).unwrap();
-    assert_eq!(CaseMode::Smart, args.case);
-
-    let args = parse_low_raw(["-S"]).unwrap();
-    assert_eq!(CaseMode::Smart, args.case);
-
-    let args = parse_low_raw(["-S", "-s"]).unwrap();
-    assert_eq!(CaseMode::Sensitive, args.case);
-
-    let args = parse_low_raw(["-S", "-i"]).unwrap();
-    assert_eq!(CaseMode::Insensitive, args.case);
-
-    let args = parse_low_raw(["-s", "-S"]).unwrap();
-    assert_eq!(CaseMode::Smart, args.case);
+    assert_eq!(None, args.csv_column);
 
-    let args = parse_low_raw(["-i", "-S"]).unwrap();
-    assert_eq!(CaseMode::Smart, args.case);
+    let args = parse_low_raw(["--csv-column", "email"]).unwrap();
+    assert_eq!(Some("email".to_string()), args.csv_column);
 }
 
-/// --sort-files
+/// --csv-row
 #[derive(Debug)]
-struct SortFiles;
+struct CsvRow;
 
-impl Flag for SortFiles {
+impl Flag for CsvRow {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "sort-files"
-    }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-sort-files")
+        "csv-row"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"(DEPRECATED) Sort results by file path."
+        r"Print the whole row for --csv-column matches."
     }
     fn doc_long(&self) -> &'static str {
         r"
-DEPRECATED. Use \fB\-\-sort=path\fP instead.
-.sp
-This flag instructs ripgrep to sort search results by file path
-lexicographically in ascending order. Note that this currently disables all
-parallelism and runs search in a single thread.
-.sp
-This flag overrides \flag{sort} and \flag{sortr}.
+When used with \flag{csv-column}, print the full matching row instead of
+just the value of the selected column. Has no effect without
+\flag{csv-column}.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.sort = if v.unwrap_switch() {
-            Some(SortMode { reverse: false, kind: SortModeKind::Path })
-        } else {
-            None
-        };
+        args.csv_row = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_sort_files() {
+fn test_csv_row() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.sort);
-
-    let args = parse_low_raw(["--sort-files"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
-        args.sort
-    );
+    assert_eq!(false, args.csv_row);
 
-    let args = parse_low_raw(["--sort-files", "--no-sort-files"]).unwrap();
-    assert_eq!(None, args.sort);
-
-    let args = parse_low_raw(["--sort", "created", "--sort-files"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
-        args.sort
-    );
+    let args = parse_low_raw(["--csv-row"]).unwrap();
+    assert_eq!(true, args.csv_row);
+}
 
-    let args = parse_low_raw(["--sort-files", "--sort", "created"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Created }),
-        args.sort
-    );
+/// --pattern
+#[derive(Debug)]
+struct AstPattern;
 
-    let args = parse_low_raw(["--sortr", "created", "--sort-files"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
-        args.sort
-    );
+impl Flag for AstPattern {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "pattern"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATTERN")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Match an ast-grep style structural pattern."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Parse each searched file with tree-sitter and match \fIPATTERN\fP, source
+code containing metavariables like \fB$COND\fP or \fB$X\fP, against nodes
+of the parsed tree, instead of matching a regular expression against lines
+of raw text. A metavariable matches any single AST node; the same
+metavariable used more than once must match identical text every time.
+Requires \flag{lang}.
+.sp
+Results are reported one per matched node, labeled with the line and
+column the match starts at rather than a byte offset into a line.
+.sp
+Example: --pattern 'if ($COND) { return $X; }' --lang rust
+"
+    }
 
-    let args = parse_low_raw(["--sort-files", "--sortr", "created"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: true, kind: SortModeKind::Created }),
-        args.sort
-    );
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.ast_pattern = Some(convert::string(v.unwrap_value())?);
+        Ok(())
+    }
+}
 
-    let args = parse_low_raw(["--sort=path", "--no-sort-files"]).unwrap();
-    assert_eq!(None, args.sort);
+#[cfg(test)]
+#[test]
+fn test_ast_pattern() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.ast_pattern);
 
-    let args = parse_low_raw(["--sortr=path", "--no-sort-files"]).unwrap();
-    assert_eq!(None, args.sort);
+    let args = parse_low_raw(["--pattern", "if $C { $X }"]).unwrap();
+    assert_eq!(Some("if $C { $X }".to_string()), args.ast_pattern);
 }
 
-/// --sort
+/// --lang
 #[derive(Debug)]
-struct Sort;
+struct AstPatternLang;
 
-impl Flag for Sort {
+impl Flag for AstPatternLang {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "sort"
+        "lang"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("SORTBY")
+        Some("LANG")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Sort results in ascending order."
+        r"Set the language \flag{pattern} is parsed as."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag enables sorting of results in ascending order. The possible values
-for this flag are:
-.sp
-.TP 12
-\fBnone\fP
-(Default) Do not sort results. Fastest. Can be multi-threaded.
-.TP 12
-\fBpath\fP
-Sort by file path. Always single-threaded. The order is determined by sorting
-files in each directory entry during traversal. This means that given the files
-\fBa/b\fP and \fBa+\fP, the latter will sort after the former even though
-\fB+\fP would normally sort before \fB/\fP.
-.TP 12
-\fBmodified\fP
-Sort by the last modified time on a file. Always single-threaded.
-.TP 12
-\fBaccessed\fP
-Sort by the last accessed time on a file. Always single-threaded.
-.TP 12
-\fBcreated\fP
-Sort by the creation time on a file. Always single-threaded.
-.PP
-If the chosen (manually or by-default) sorting criteria isn't available on your
-system (for example, creation time is not available on ext4 file systems), then
-ripgrep will attempt to detect this, print an error and exit without searching.
-.sp
-To sort results in reverse or descending order, use the \flag{sortr} flag.
-Also, this flag overrides \flag{sortr}.
-.sp
-Note that sorting results currently always forces ripgrep to abandon
-parallelism and run in a single thread.
+Set the language that \flag{pattern} or \flag{ts-query} is written in and
+that every searched file is parsed as, e.g. \fBrust\fP, \fBpython\fP or
+\fBjavascript\fP. Has no effect without one of those two flags.
 "
     }
-    fn doc_choices(&self) -> &'static [&'static str] {
-        &["none", "path", "modified", "accessed", "created"]
-    }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let kind = match convert::str(&v.unwrap_value())? {
-            "none" => {
-                args.sort = None;
-                return Ok(());
-            }
-            "path" => SortModeKind::Path,
-            "modified" => SortModeKind::LastModified,
-            "accessed" => SortModeKind::LastAccessed,
-            "created" => SortModeKind::Created,
-            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
-        };
-        args.sort = Some(SortMode { reverse: false, kind });
+        args.ast_pattern_lang = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_sort() {
+fn test_ast_pattern_lang() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.sort);
-
-    let args = parse_low_raw(["--sort", "path"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
-        args.sort
-    );
-
-    let args = parse_low_raw(["--sort", "path", "--sort=created"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Created }),
-        args.sort
-    );
-
-    let args = parse_low_raw(["--sort=none"]).unwrap();
-    assert_eq!(None, args.sort);
+    assert_eq!(None, args.ast_pattern_lang);
 
-    let args = parse_low_raw(["--sort", "path", "--sort=none"]).unwrap();
-    assert_eq!(None, args.sort);
+    let args = parse_low_raw(["--lang", "rust"]).unwrap();
+    assert_eq!(Some("rust".to_string()), args.ast_pattern_lang);
 }
 
-/// --sortr
+/// --ts-query
 #[derive(Debug)]
-struct Sortr;
+struct TsQuery;
 
-impl Flag for Sortr {
+impl Flag for TsQuery {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "sortr"
+        "ts-query"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("SORTBY")
+        Some("QUERY")
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Sort results in descending order."
+        r"Match a raw tree-sitter S-expression query."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag enables sorting of results in descending order. The possible values
-for this flag are:
-.sp
-.TP 12
-\fBnone\fP
-(Default) Do not sort results. Fastest. Can be multi-threaded.
-.TP 12
-\fBpath\fP
-Sort by file path. Always single-threaded. The order is determined by sorting
-files in each directory entry during traversal. This means that given the files
-\fBa/b\fP and \fBa+\fP, the latter will sort before the former even though
-\fB+\fP would normally sort after \fB/\fP when doing a reverse lexicographic
-sort.
-.TP 12
-\fBmodified\fP
-Sort by the last modified time on a file. Always single-threaded.
-.TP 12
-\fBaccessed\fP
-Sort by the last accessed time on a file. Always single-threaded.
-.TP 12
-\fBcreated\fP
-Sort by the creation time on a file. Always single-threaded.
-.PP
-If the chosen (manually or by-default) sorting criteria isn't available on your
-system (for example, creation time is not available on ext4 file systems), then
-ripgrep will attempt to detect this, print an error and exit without searching.
+Parse each searched file with tree-sitter and run \fIQUERY\fP, a query
+written in tree-sitter's own S-expression query syntax, directly against
+the parsed tree via \fBtree_sitter::Query\fP, bypassing the
+metavariable-based matching engine that \flag{pattern} uses. Requires
+\flag{lang}.
 .sp
-To sort results in ascending order, use the \flag{sort} flag. Also, this flag
-overrides \flag{sort}.
+Results are reported one per capture, labeled with the capture's name and
+the line/column the captured node starts at.
 .sp
-Note that sorting results currently always forces ripgrep to abandon
-parallelism and run in a single thread.
+Example: --ts-query '(function_item name: (identifier) @name)' --lang rust
 "
     }
-    fn doc_choices(&self) -> &'static [&'static str] {
-        &["none", "path", "modified", "accessed", "created"]
-    }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let kind = match convert::str(&v.unwrap_value())? {
-            "none" => {
-                args.sort = None;
-                return Ok(());
-            }
-            "path" => SortModeKind::Path,
-            "modified" => SortModeKind::LastModified,
-            "accessed" => SortModeKind::LastAccessed,
-            "created" => SortModeKind::Created,
-            unk => anyhow::bail!("choice '{unk}' is unrecognized"),
-        };
-        args.sort = Some(SortMode { reverse: true, kind });
+        args.ts_query = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_sortr() {
+fn test_ts_query() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.sort);
-
-    let args = parse_low_raw(["--sortr", "path"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: true, kind: SortModeKind::Path }),
-        args.sort
-    );
+    assert_eq!(None, args.ts_query);
 
-    let args = parse_low_raw(["--sortr", "path", "--sortr=created"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: true, kind: SortModeKind::Created }),
-        args.sort
-    );
+    let args = parse_low_raw(["--ts-query", "(struct_item) @s"]).unwrap();
+    assert_eq!(Some("(struct_item) @s".to_string()), args.ts_query);
+}
 
-    let args = parse_low_raw(["--sortr=none"]).unwrap();
-    assert_eq!(None, args.sort);
+/// --only-in
+#[derive(Debug)]
+struct OnlyIn;
 
-    let args = parse_low_raw(["--sortr", "path", "--sortr=none"]).unwrap();
-    assert_eq!(None, args.sort);
+impl Flag for OnlyIn {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "only-in"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("KINDS")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Restrict matches to the given AST node kinds."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Parse each searched file with tree-sitter and only report matches that fall
+inside a node of one of the given comma-separated kinds. \fIKINDS\fP is a
+comma-separated list where each item is one of \fBcomments\fP or
+\fBstrings\fP. This flag can be given multiple times, and all of its values
+accumulate.
+.sp
+Files whose language isn't supported produce no matches at all, since kind
+membership can't be determined without a parser. Cannot be combined with
+\flag{not-in}.
+.sp
+Example: --only-in comments TODO
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let value = convert::string(v.unwrap_value())?;
+        for kind in value.split(',') {
+            let kind = kind.trim();
+            if !kind.is_empty() {
+                args.only_in.push(kind.to_string());
+            }
+        }
+        Ok(())
+    }
+}
 
-    let args = parse_low_raw(["--sort=path", "--sortr=path"]).unwrap();
-    assert_eq!(
-        Some(SortMode { reverse: true, kind: SortModeKind::Path }),
-        args.sort
-    );
+#[cfg(test)]
+#[test]
+fn test_only_in() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<String>::new(), args.only_in);
 
-    let args = parse_low_raw(["--sortr=path", "--sort=path"]).unwrap();
+    let args = parse_low_raw(["--only-in", "comments,strings"]).unwrap();
     assert_eq!(
-        Some(SortMode { reverse: false, kind: SortModeKind::Path }),
-        args.sort
+        vec!["comments".to_string(), "strings".to_string()],
+        args.only_in
     );
 }
 
-/// --stats
+/// --not-in
 #[derive(Debug)]
-struct Stats;
+struct NotIn;
 
-impl Flag for Stats {
+impl Flag for NotIn {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "stats"
+        "not-in"
     }
-    fn name_negated(&self) -> Option<&'static str> {
-        Some("no-stats")
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("KINDS")
     }
     fn doc_category(&self) -> Category {
-        Category::Logging
+        Category::Filter
     }
     fn doc_short(&self) -> &'static str {
-        r"Print statistics about the search."
+        r"Exclude matches inside the given AST node kinds."
     }
     fn doc_long(&self) -> &'static str {
         r"
-When enabled, ripgrep will print aggregate statistics about the search. When
-this flag is present, ripgrep will print at least the following stats to
-stdout at the end of the search: number of matched lines, number of files with
-matches, number of files searched, and the time taken for the entire search to
-complete.
+Parse each searched file with tree-sitter and drop any match that falls
+inside a node of one of the given comma-separated kinds. \fIKINDS\fP takes
+the same values as \flag{only-in}: \fBcomments\fP and \fBstrings\fP. This
+flag can be given multiple times, and all of its values accumulate.
 .sp
-This set of aggregate statistics may expand over time.
-.sp
-This flag is always and implicitly enabled when \flag{json} is used.
+Files whose language isn't supported are searched normally, since nothing
+can be confirmed to be inside an unrecognized kind. Cannot be combined with
+\flag{only-in}.
 .sp
-Note that this flag has no effect if \flag{files}, \flag{files-with-matches} or
-\flag{files-without-match} is passed.
+Example: --not-in comments,strings TODO
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.stats = v.unwrap_switch();
+        let value = convert::string(v.unwrap_value())?;
+        for kind in value.split(',') {
+            let kind = kind.trim();
+            if !kind.is_empty() {
+                args.not_in.push(kind.to_string());
+            }
+        }
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_stats() {
+fn test_not_in() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.stats);
-
-    let args = parse_low_raw(["--stats"]).unwrap();
-    assert_eq!(true, args.stats);
+    assert_eq!(Vec::<String>::new(), args.not_in);
 
-    let args = parse_low_raw(["--stats", "--no-stats"]).unwrap();
-    assert_eq!(false, args.stats);
+    let args = parse_low_raw(["--not-in", "strings"]).unwrap();
+    assert_eq!(vec!["strings".to_string()], args.not_in);
 }
 
-/// --stop-on-nonmatch
+/// --rewrite
 #[derive(Debug)]
-struct StopOnNonmatch;
+struct AstRewrite;
 
-impl Flag for StopOnNonmatch {
+impl Flag for AstRewrite {
     fn is_switch(&self) -> bool {
-        true
+        false
     }
     fn name_long(&self) -> &'static str {
-        "stop-on-nonmatch"
+        "rewrite"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("REPLACEMENT")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Stop searching after a non-match."
+        r"Rewrite each \flag{pattern} match to REPLACEMENT."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Enabling this option will cause ripgrep to stop reading a file once it
-encounters a non-matching line after it has encountered a matching line.
-This is useful if it is expected that all matches in a given file will be on
-sequential lines, for example due to the lines being sorted.
+Rewrite each node \flag{pattern} matches to \fIREPLACEMENT\fP, substituting
+any metavariables captured by the pattern (e.g. \fB$X\fP) with the text they
+matched. Requires \flag{pattern} and \flag{lang}.
 .sp
-This overrides the \flag{multiline} flag.
+By default (and always when \flag{dry-run} is given), this only previews the
+change: a unified diff is printed for each file with a match, and nothing on
+disk is touched. Pass \flag{write} to apply the rewrite in place.
+.sp
+Example: --pattern 'old_name($X)' --rewrite 'new_name($X)' --lang rust
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        assert!(v.unwrap_switch(), "--stop-on-nonmatch has no negation");
-        args.stop_on_nonmatch = true;
-        args.multiline = false;
+        args.rewrite = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_stop_on_nonmatch() {
+fn test_ast_rewrite() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.stop_on_nonmatch);
-
-    let args = parse_low_raw(["--stop-on-nonmatch"]).unwrap();
-    assert_eq!(true, args.stop_on_nonmatch);
-
-    let args = parse_low_raw(["--stop-on-nonmatch", "-U"]).unwrap();
-    assert_eq!(true, args.multiline);
-    assert_eq!(false, args.stop_on_nonmatch);
+    assert_eq!(None, args.rewrite);
 
-    let args = parse_low_raw(["-U", "--stop-on-nonmatch"]).unwrap();
-    assert_eq!(false, args.multiline);
-    assert_eq!(true, args.stop_on_nonmatch);
-
-    let args =
-        parse_low_raw(["--stop-on-nonmatch", "--no-multiline"]).unwrap();
-    assert_eq!(false, args.multiline);
-    assert_eq!(true, args.stop_on_nonmatch);
+    let args = parse_low_raw(["--rewrite", "new($X)"]).unwrap();
+    assert_eq!(Some("new($X)".to_string()), args.rewrite);
 }
 
-/// --no-syntax-highlight
+/// --write
 #[derive(Debug)]
-struct NoSyntaxHighlight;
+struct AstRewriteWrite;
 
-impl Flag for NoSyntaxHighlight {
+impl Flag for AstRewriteWrite {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "no-syntax-highlight"
+        "write"
     }
     fn doc_category(&self) -> Category {
-        Category::Output
+        Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        "Disable syntax highlighting in AST context mode."
+        r"Apply \flag{rewrite} changes in place."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Disable syntax highlighting when using --enclosing-symbol (AST context mode).
-By default, syntax highlighting is enabled when using AST context mode to
-colorize code elements like keywords, strings, comments, and functions based
-on the detected language.
-.sp
-Syntax highlighting is automatically disabled when:
-.sp
-.IP \(bu 3n
-Not using --enclosing-symbol mode.
-.sp
-.IP \(bu 3n
-Output is redirected to a file or pipe (unless --color=always is used).
-.sp
-.IP \(bu 3n
-The file type is not supported by tree-sitter.
-.sp
-.IP \(bu 3n
-Colors are disabled via --color=never.
+Apply every \flag{rewrite} edit directly to the files they're found in,
+instead of only previewing them as a diff. A one-line summary of the number
+of replacements written is printed for each file, followed by a total
+across every file once the search completes.
 .sp
-Note that this feature requires the language to be detected from the file
-extension. Currently supported languages include Rust, Python, JavaScript,
-TypeScript, Go, Java, C/C++, and many others.
+Has no effect without \flag{rewrite}, and is overridden by \flag{dry-run}.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        // Since this is --no-syntax-highlight, we invert the switch
-        args.syntax_highlighting = !v.unwrap_switch();
+        args.rewrite_write = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_no_syntax_highlight() {
+fn test_ast_rewrite_write() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(true, args.syntax_highlighting); // Default is now true
+    assert_eq!(false, args.rewrite_write);
 
-    let args = parse_low_raw(["--no-syntax-highlight"]).unwrap();
-    assert_eq!(false, args.syntax_highlighting); // Disabled with flag
+    let args = parse_low_raw(["--write"]).unwrap();
+    assert_eq!(true, args.rewrite_write);
 }
 
-/// --semantic
+/// --dry-run
 #[derive(Debug)]
-struct Semantic;
+struct AstRewriteDryRun;
 
-impl Flag for Semantic {
+impl Flag for AstRewriteDryRun {
     fn is_switch(&self) -> bool {
         true
     }
     fn name_long(&self) -> &'static str {
-        "semantic"
+        "dry-run"
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        "Enable semantic code search using vector embeddings."
+        r"Preview \flag{rewrite} changes even if \flag{write} is given."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Enable semantic code search using vector embeddings. This allows searching
-for code with similar meaning rather than just exact text matches.
-.sp
-When enabled, outgrep will generate vector embeddings for code functions
-and symbols, and search for semantically similar content based on the query.
-This is particularly useful for finding code patterns, similar functions,
-or conceptually related code blocks.
-.sp
-Note: This feature requires additional processing time for embedding generation
-and is currently experimental.
+Force \flag{rewrite} to only preview its changes as a diff, even if
+\flag{write} is also given. Since previewing is already the default without
+\flag{write}, this flag is mainly useful for overriding \flag{write} set
+elsewhere, e.g. in a config file.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.semantic = v.unwrap_switch();
+        args.rewrite_dry_run = v.unwrap_switch();
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_semantic() {
+fn test_ast_rewrite_dry_run() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(false, args.semantic);
+    assert_eq!(false, args.rewrite_dry_run);
 
-    let args = parse_low_raw(["--semantic"]).unwrap();
-    assert_eq!(true, args.semantic);
+    let args = parse_low_raw(["--dry-run"]).unwrap();
+    assert_eq!(true, args.rewrite_dry_run);
 }
 
-/// --semantic-model-path
+/// --rules
 #[derive(Debug)]
-struct SemanticModelPath;
+struct Rules;
 
-impl Flag for SemanticModelPath {
+impl Flag for Rules {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "semantic-model-path"
+        "rules"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        "Directory path where semantic embedding models are stored."
+        r"Check files against ast-grep style YAML lint rules at PATH."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specify the directory path where semantic embedding models are stored.
-This directory should contain the model.onnx and tokenizer.json files
-required for semantic code search.
+Load one or more ast-grep style YAML rule files from \fIPATH\fP (a single
+file, or a directory searched recursively for \fB*.yml\fP/\fB*.yaml\fP
+files) and report every structural match as a lint violation, using each
+rule's \fBid\fP, \fBmessage\fP, and \fBseverity\fP.
 .sp
-By default, models are automatically downloaded to '~/.cache/outgrep/models'.
-Use this flag to specify a different location such as a custom model cache
-directory.
+Rule files use the same schema as ast-grep's own rules: a \fBrule\fP tree of
+structural matchers (\fBpattern\fP, \fBkind\fP, \fBregex\fP, and so on), plus
+a \fBlanguage\fP naming which files the rule applies to. This lets a team
+encode project-specific lint rules as data instead of writing a bespoke
+linter.
 .sp
-Example: --semantic-model-path ~/.cache/outgrep/models
+Example rule file:
+.sp
+.EX
+    id: no-unwrap
+    language: Rust
+    message: avoid unwrap(), handle the error instead
+    severity: warning
+    rule:
+      pattern: $X.unwrap()
+.EE
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.semantic_model_path = Some(PathBuf::from(v.unwrap_value()));
+        args.rules = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
-/// --semantic-model
+#[cfg(test)]
+#[test]
+fn test_rules() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.rules);
+
+    let args = parse_low_raw(["--rules", "rules/"]).unwrap();
+    assert_eq!(Some(PathBuf::from("rules/")), args.rules);
+}
+
+/// --plugins-dir
 #[derive(Debug)]
-struct SemanticModel;
+struct PluginsDir;
 
-impl Flag for SemanticModel {
+impl Flag for PluginsDir {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "semantic-model"
+        "plugins-dir"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        "Specify which embedding model to use for semantic search."
+        r"Also look for plugin executables in PATH."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specify which embedding model to use for semantic code search.
-The model name should correspond to a supported embedding model.
-.sp
-Models are auto-downloaded from the model registry. See the registry for
-current available models and their specifications. Common models include
-compact 384-dimension models for speed and larger 768-dimension models
-for better quality.
-.sp
-The model files (model.onnx and tokenizer.json) should be available
-in the model storage directory for the specified model.
-.sp
-Example: --semantic-model all-mpnet-base-v2
+In addition to the usual \fBPATH\fP environment variable, look directly
+inside \fIPATH\fP (a directory) for third-party plugin executables named
+\fBog-plugin-*\fP.
+.sp
+Every discovered plugin is run once per \fB--tree\fP/\fB--analyze\fP
+invocation: outgrep writes a single-line JSON request naming the project
+root to the plugin's stdin, and reads a single-line JSON response with a
+\fBtitle\fP and arbitrary \fBdata\fP from its stdout. Each plugin's
+response is shown as its own section in the report, and included verbatim
+under its title in \fB--json\fP output. A plugin that fails to run,
+exits non-zero, or writes something that doesn't parse as that response
+shape is skipped, the same as a plugin that legitimately has nothing to
+report.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        args.semantic_model = Some(convert::string(v.unwrap_value())?);
+        args.plugins_dir = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
-/// --semantic-dimensions
+#[cfg(test)]
+#[test]
+fn test_plugins_dir() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.plugins_dir);
+
+    let args = parse_low_raw(["--plugins-dir", "plugins/"]).unwrap();
+    assert_eq!(Some(PathBuf::from("plugins/")), args.plugins_dir);
+}
+
+/// --wasm-plugin
 #[derive(Debug)]
-struct SemanticDimensions;
+struct WasmPlugin;
 
-impl Flag for SemanticDimensions {
+impl Flag for WasmPlugin {
     fn is_switch(&self) -> bool {
         false
     }
     fn name_long(&self) -> &'static str {
-        "semantic-dimensions"
+        "wasm-plugin"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        "Number of dimensions for semantic embedding vectors."
+        r"Filter or rewrite matched lines with a WASM module at PATH."
     }
     fn doc_long(&self) -> &'static str {
         r"
-Specify the number of dimensions for semantic embedding vectors.
-This must match the dimensions of the embedding model being used.
-.sp
-Common dimension sizes are 384 (compact models), 768 (balanced models),
-and 1024 (high-quality models).
-.sp
-If not specified, defaults to the dimension size of the selected model. The dimension size
-affects memory usage and search performance.
-.sp
-Example: --semantic-dimensions 768
+Load a WebAssembly module from \fIPATH\fP and call into it, in-process,
+for every line that matches the search pattern.
+.sp
+Unlike \fB--plugins-dir\fP, which spawns a subprocess once per invocation to
+produce a project-wide report, \fB--wasm-plugin\fP is meant to run per match:
+the module is compiled and instantiated once, then called for each matched
+line to decide whether to keep it, rewrite it, or drop it.
+.sp
+The module must export \fBmemory\fP, \fBoutgrep_alloc(len: i32) -> i32\fP,
+and \fBoutgrep_filter(ptr: i32, len: i32) -> i64\fP. \fBoutgrep_filter\fP is
+passed the matched line written into memory at \fBoutgrep_alloc\fP's
+returned offset, and returns a packed \fB(out_ptr << 32) | out_len\fP
+pointing at the line to keep, or \fB-1\fP to drop the match.
+.sp
+This flag requires outgrep to have been built with the \fBwasm-plugins\fP
+feature, which is on by default.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let dims = convert::str(&v.unwrap_value())?
-            .parse::<usize>()
-            .context("semantic dimensions must be a positive integer")?;
-        args.semantic_dimensions = Some(dims);
+        args.wasm_plugin = Some(PathBuf::from(v.unwrap_value()));
         Ok(())
     }
 }
 
-/// --semantic-similarity-threshold
+#[cfg(test)]
+#[test]
+fn test_wasm_plugin() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.wasm_plugin);
+
+    let args = parse_low_raw(["--wasm-plugin", "filter.wasm"]).unwrap();
+    assert_eq!(Some(PathBuf::from("filter.wasm")), args.wasm_plugin);
+}
+
+/// --symbol
 #[derive(Debug)]
-struct SemanticSimilarityThreshold;
+struct Symbol;
 
-impl Flag for SemanticSimilarityThreshold {
+impl Flag for Symbol {
     fn is_switch(&self) -> bool {
         false
     }
-
     fn name_long(&self) -> &'static str {
-        "semantic-similarity-threshold"
+        "symbol"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NAME")
     }
-
     fn doc_category(&self) -> Category {
         Category::Search
     }
-
     fn doc_short(&self) -> &'static str {
-        "Minimum similarity score for semantic search results."
+        r"Match only symbol definitions named NAME."
     }
-
     fn doc_long(&self) -> &'static str {
         r"
-Specify the minimum similarity score (between 0.0 and 1.0) for including
-results in semantic search output. Results with similarity scores below
-this threshold will be filtered out.
-.sp
-A higher threshold means more selective results with stronger semantic
-similarity, while a lower threshold includes more loosely related matches.
-.sp
-Default: 0.2 (20% similarity)
-.sp
-Example: --semantic-similarity-threshold 0.5
+Parse each searched file with tree-sitter and match \fINAME\fP only against
+the names of symbol definitions -- functions, classes, structs, types, and
+modules -- extracted by the same AST layer that backs \flag{analyze}'s
+symbol summary, instead of matching against lines of raw text.
+.sp
+This means \fB--symbol parse_config\fP finds where \fBparse_config\fP is
+defined without false positives from its call sites, its mentions in
+comments, or unrelated substring matches. The language of each file is
+detected from its extension, the same way \flag{pattern} would need
+\flag{lang} but this flag does not, since it runs over every file in a
+search rather than one fixed language.
+.sp
+Files whose language isn't recognized, or that fail to parse, are treated
+as having no matching symbols rather than as an error.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let threshold = convert::str(&v.unwrap_value())?.parse::<f32>()
-            .context("semantic similarity threshold must be a number between 0.0 and 1.0")?;
-
-        if threshold < 0.0 || threshold > 1.0 {
-            return Err(anyhow::anyhow!(
-                "semantic similarity threshold must be between 0.0 and 1.0"
-            ));
-        }
-
-        args.semantic_similarity_threshold = Some(threshold);
+        args.symbol = Some(convert::string(v.unwrap_value())?);
         Ok(())
     }
 }
 
-/// --semantic-max-results
+#[cfg(test)]
+#[test]
+fn test_symbol() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.symbol);
+
+    let args = parse_low_raw(["--symbol", "parse_config"]).unwrap();
+    assert_eq!(Some("parse_config".to_string()), args.symbol);
+}
+
+/// --ast-multiline
 #[derive(Debug)]
-struct SemanticMaxResults;
+struct AstMultiline;
 
-impl Flag for SemanticMaxResults {
+impl Flag for AstMultiline {
     fn is_switch(&self) -> bool {
-        false
+        true
     }
-
     fn name_long(&self) -> &'static str {
-        "semantic-max-results"
+        "ast-multiline"
     }
-
     fn doc_category(&self) -> Category {
         Category::Search
     }
-
     fn doc_short(&self) -> &'static str {
-        "Maximum number of semantic search results to return."
+        r"Match the pattern against whole AST symbols, not lines."
     }
-
     fn doc_long(&self) -> &'static str {
         r"
-Specify the maximum number of semantic search results to return.
-This limits the output to the top N most similar matches.
-.sp
-Lowering this value can improve performance and reduce noise in results,
-while increasing it provides more comprehensive coverage of similar content.
-.sp
-Default: 10 results
-.sp
-Example: --semantic-max-results 20
+Parse each searched file with tree-sitter and match the pattern against
+each function, class, type, and module definition's text joined onto a
+single line, with runs of whitespace collapsed to one space, instead of
+against the file's lines.
+.sp
+This lets a pattern spanning a formatted call chain or a multi-line
+function signature match reliably without a hand-written \fB(?s)\fP regex
+or \flag{multiline}, since the whole definition is always presented as one
+line regardless of how it's wrapped in the source.
+.sp
+Files whose language isn't recognized, or that fail to parse, are treated
+as having no matching symbols rather than as an error. See also
+\flag{symbol}, which matches symbol names instead of running the pattern
+over their text.
 "
     }
-
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let max_results = convert::str(&v.unwrap_value())?
-            .parse::<usize>()
-            .context("semantic max results must be a positive integer")?;
-
-        if max_results == 0 {
-            return Err(anyhow::anyhow!(
-                "semantic max results must be greater than 0"
-            ));
-        }
-
-        args.semantic_max_results = Some(max_results);
+        args.ast_multiline = v.unwrap_switch();
         Ok(())
     }
 }
 
+#[cfg(test)]
+#[test]
+fn test_ast_multiline() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.ast_multiline);
+
+    let args = parse_low_raw(["--ast-multiline"]).unwrap();
+    assert_eq!(true, args.ast_multiline);
+}
+
 /// -a/--text
 #[derive(Debug)]
 struct Text;
@@ -7272,60 +11594,131 @@ fn test_text() {
 #[derive(Debug)]
 struct Threads;
 
-impl Flag for Threads {
+impl Flag for Threads {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_short(&self) -> Option<u8> {
+        Some(b'j')
+    }
+    fn name_long(&self) -> &'static str {
+        "threads"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("NUM")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Search
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Set the approximate number of threads to use."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+This flag sets the approximate number of threads to use. A value of \fB0\fP
+(which is the default) causes ripgrep to choose the thread count using
+heuristics.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let threads = convert::usize(&v.unwrap_value())?;
+        args.threads = if threads == 0 { None } else { Some(threads) };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_threads() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.threads);
+
+    let args = parse_low_raw(["--threads", "5"]).unwrap();
+    assert_eq!(Some(5), args.threads);
+
+    let args = parse_low_raw(["-j", "5"]).unwrap();
+    assert_eq!(Some(5), args.threads);
+
+    let args = parse_low_raw(["-j5"]).unwrap();
+    assert_eq!(Some(5), args.threads);
+
+    let args = parse_low_raw(["-j5", "-j10"]).unwrap();
+    assert_eq!(Some(10), args.threads);
+
+    let args = parse_low_raw(["-j5", "-j0"]).unwrap();
+    assert_eq!(None, args.threads);
+}
+
+/// --throttle
+#[derive(Debug)]
+struct Throttle;
+
+impl Flag for Throttle {
     fn is_switch(&self) -> bool {
         false
     }
-    fn name_short(&self) -> Option<u8> {
-        Some(b'j')
-    }
     fn name_long(&self) -> &'static str {
-        "threads"
+        "throttle"
     }
     fn doc_variable(&self) -> Option<&'static str> {
-        Some("NUM")
+        Some("RATE")
     }
     fn doc_category(&self) -> Category {
         Category::Search
     }
     fn doc_short(&self) -> &'static str {
-        r"Set the approximate number of threads to use."
+        r"Pace file reads to at most RATE megabytes per second."
     }
     fn doc_long(&self) -> &'static str {
         r"
-This flag sets the approximate number of threads to use. A value of \fB0\fP
-(which is the default) causes ripgrep to choose the thread count using
-heuristics.
+Pace file reads so this search does not exceed \fBRATE\fP megabytes per
+second, and yield the thread between files. This is intended for background
+runs, such as a scheduled or \flag{watch}-triggered search over a large
+tree, where ripgrep should stay out of the way of a developer's foreground
+IO rather than search as fast as possible.
+.sp
+\fBRATE\fP must be a positive number, e.g. \fB--throttle 20\fP paces reads
+to roughly 20 MB/s. There is no default, so ripgrep searches unthrottled
+unless this flag is given.
+.sp
+This flag does not lower ripgrep's OS scheduling priority (its \fBnice\fP
+value on Unix). Doing so would require a new dependency this crate does not
+otherwise need, so for now \fB--throttle\fP only paces IO. If OS-level
+priority matters for your use case, wrap ripgrep with \fBnice\fP(1) or an
+equivalent yourself.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
-        let threads = convert::usize(&v.unwrap_value())?;
-        args.threads = if threads == 0 { None } else { Some(threads) };
+        let value = convert::str(&v.unwrap_value())?;
+        let rate: f64 =
+            value.parse().context("value is not a valid number")?;
+        anyhow::ensure!(
+            rate > 0.0,
+            "--throttle rate must be a positive number of megabytes \
+             per second"
+        );
+        args.throttle = Some(rate);
         Ok(())
     }
 }
 
 #[cfg(test)]
 #[test]
-fn test_threads() {
+fn test_throttle() {
     let args = parse_low_raw(None::<&str>).unwrap();
-    assert_eq!(None, args.threads);
+    assert_eq!(None, args.throttle);
 
-    let args = parse_low_raw(["--threads", "5"]).unwrap();
-    assert_eq!(Some(5), args.threads);
-
-    let args = parse_low_raw(["-j", "5"]).unwrap();
-    assert_eq!(Some(5), args.threads);
-
-    let args = parse_low_raw(["-j5"]).unwrap();
-    assert_eq!(Some(5), args.threads);
+    let args = parse_low_raw(["--throttle", "5"]).unwrap();
+    assert_eq!(Some(5.0), args.throttle);
 
-    let args = parse_low_raw(["-j5", "-j10"]).unwrap();
-    assert_eq!(Some(10), args.threads);
+    let args = parse_low_raw(["--throttle", "0.5"]).unwrap();
+    assert_eq!(Some(0.5), args.throttle);
 
-    let args = parse_low_raw(["-j5", "-j0"]).unwrap();
-    assert_eq!(None, args.threads);
+    assert!(parse_low_raw(["--throttle", "0"]).is_err());
+    assert!(parse_low_raw(["--throttle", "-1"]).is_err());
+    assert!(parse_low_raw(["--throttle", "nope"]).is_err());
 }
 
 /// --trace
@@ -7404,6 +11797,49 @@ individual files rather than showing separate sections."
     }
 }
 
+/// --filetype-stats
+#[derive(Debug)]
+struct FiletypeStats;
+impl Flag for FiletypeStats {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "filetype-stats"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Filter
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Show per-language file, line, comment, and blank counts."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Show per-language file, line, comment, and blank counts for the current
+directory, similar to tools like tokei or cloc.
+.sp
+Unlike \flag{analyze}, this mode never runs AST parsing or complexity
+analysis, so it stays fast even on very large repositories. It walks the
+tree the same way a search would (honoring .gitignore and \flag{hidden}),
+counts lines with the same per-language comment heuristics used elsewhere
+in outgrep, and prints one row per language plus a total.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--filetype-stats has no negation");
+        args.filetype_stats = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_filetype_stats() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.filetype_stats);
+    let args = parse_low_raw(["--filetype-stats"]).unwrap();
+    assert_eq!(true, args.filetype_stats);
+}
+
 /// --trim
 #[derive(Debug)]
 struct Trim;
@@ -7467,6 +11903,130 @@ is only effective when used in combination with both --tree and --diff flags.
     }
 }
 
+/// --diff-ignore-eol
+#[derive(Debug)]
+struct DiffIgnoreEol;
+impl Flag for DiffIgnoreEol {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "diff-ignore-eol"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Treat CRLF and LF line endings as equivalent in --diff output."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When used with \flag{diff}, normalize CRLF line endings to LF on both sides
+of the comparison before generating a diff, so a file that only changed its
+line endings doesn't show up as fully changed.
+.sp
+The number of hunks this suppresses is reported alongside the diff.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--diff-ignore-eol has no negation");
+        args.diff_ignore_eol = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_diff_ignore_eol() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.diff_ignore_eol);
+    let args = parse_low_raw(["--diff-ignore-eol"]).unwrap();
+    assert_eq!(true, args.diff_ignore_eol);
+}
+
+/// --diff-ignore-whitespace
+#[derive(Debug)]
+struct DiffIgnoreWhitespace;
+impl Flag for DiffIgnoreWhitespace {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "diff-ignore-whitespace"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Ignore leading and trailing line whitespace in --diff output."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When used with \flag{diff}, trim leading and trailing whitespace from each
+line on both sides of the comparison before generating a diff, so
+reindentation or trailing-whitespace cleanup doesn't show up as a change.
+.sp
+The number of hunks this suppresses is reported alongside the diff.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--diff-ignore-whitespace has no negation");
+        args.diff_ignore_whitespace = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_diff_ignore_whitespace() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.diff_ignore_whitespace);
+    let args = parse_low_raw(["--diff-ignore-whitespace"]).unwrap();
+    assert_eq!(true, args.diff_ignore_whitespace);
+}
+
+/// --diff-hide-trivial
+#[derive(Debug)]
+struct DiffHideTrivial;
+impl Flag for DiffHideTrivial {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "diff-hide-trivial"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Output
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Hide comment-only and whitespace-only hunks from --diff output."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+When used with \flag{diff}, classify each hunk using the AST as code,
+comment-only, or whitespace-only, and omit comment-only and
+whitespace-only hunks from the printed diff, leaving only hunks that
+change actual code.
+.sp
+Unlike \flag{diff-ignore-eol} and \flag{diff-ignore-whitespace}, which
+change what counts as a difference in the first place, this only changes
+what gets displayed: use \fB--json\fP to see every hunk's classification
+regardless of this flag.
+"
+    }
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch(), "--diff-hide-trivial has no negation");
+        args.diff_hide_trivial = true;
+        Ok(())
+    }
+}
+#[cfg(test)]
+#[test]
+fn test_diff_hide_trivial() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.diff_hide_trivial);
+    let args = parse_low_raw(["--diff-hide-trivial"]).unwrap();
+    assert_eq!(true, args.diff_hide_trivial);
+}
+
 #[cfg(test)]
 #[test]
 fn test_trim() {
@@ -8262,6 +12822,111 @@ precedence order of configuration sources.
     }
 }
 
+/// --config-extra
+#[derive(Debug)]
+struct ConfigExtra;
+
+impl Flag for ConfigExtra {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "config-extra"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("PATH")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Config
+    }
+    fn doc_short(&self) -> &'static str {
+        r"Load an additional shared config file."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Loads an additional configuration file, on top of any global and local
+configuration files that are otherwise discovered. This is the command line
+equivalent of an include directive inside a config file (see
+\flag{config-dump} for how to inspect where a setting came from), and is
+meant for organizations that maintain a shared team config outside of the
+per-project config hierarchy.
+.sp
+Multiple additional config files can be specified by using this flag
+repeatedly. They are applied after the global and local config files, but
+before any other CLI arguments, so CLI arguments still take precedence.
+"
+    }
+    fn completion_type(&self) -> CompletionType {
+        CompletionType::Filename
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        let path = PathBuf::from(v.unwrap_value());
+        args.config_extra.push(path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_extra() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(Vec::<PathBuf>::new(), args.config_extra);
+
+    let args = parse_low_raw(["--config-extra", "shared.conf"]).unwrap();
+    assert_eq!(vec![PathBuf::from("shared.conf")], args.config_extra);
+
+    let args = parse_low_raw([
+        "--config-extra",
+        "shared.conf",
+        "--config-extra",
+        "team.conf",
+    ])
+    .unwrap();
+    assert_eq!(
+        vec![PathBuf::from("shared.conf"), PathBuf::from("team.conf")],
+        args.config_extra
+    );
+}
+
+/// --config-dump
+#[derive(Debug)]
+struct ConfigDump;
+
+impl Flag for ConfigDump {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "config-dump"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Config
+    }
+    fn doc_short(&self) -> &'static str {
+        "Print the merged configuration with provenance."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Prints the fully merged configuration, in priority order, with each setting
+annotated by the file it came from. This follows \fBinclude\fP directives
+(and any \flag{config-extra} files) recursively, so a setting that arrived
+via a shared team config several includes deep is still attributed to the
+file that actually defined it.
+.sp
+This is primarily useful for debugging a config hierarchy that spans
+multiple files, for example when an organization maintains a central config
+that individual projects include and extend.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        assert!(v.unwrap_switch());
+        args.special = Some(SpecialMode::ConfigDump(args.config_extra.clone()));
+        Ok(())
+    }
+}
+
 /// --init-global-config
 #[derive(Debug)]
 struct InitGlobalConfig;
@@ -8292,13 +12957,13 @@ for your platform:
 \fBWindows:\fP \fB%APPDATA%\\outgrep\\config\fP
 .sp
 If a config file already exists, this command will fail unless used with
-the \fB--force\fP flag.
+the \fB--force\fP flag, or updated in place with \flag{merge}.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
         assert!(v.unwrap_switch());
-        args.special = Some(SpecialMode::InitGlobalConfig);
+        args.special = Some(SpecialMode::InitGlobalConfig(args.config_merge));
         Ok(())
     }
 }
@@ -8331,16 +12996,122 @@ Project root is detected by looking for version control directories
 .sp
 Local configuration files override global settings and are overridden by
 command-line flags.
+.sp
+If a config file already exists, this command will fail unless used with
+the \fB--force\fP flag, or updated in place with \flag{merge}.
 "
     }
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
         assert!(v.unwrap_switch());
-        args.special = Some(SpecialMode::InitLocalConfig);
+        args.special = Some(SpecialMode::InitLocalConfig(args.config_merge));
         Ok(())
     }
 }
 
+/// --merge
+#[derive(Debug)]
+struct Merge;
+
+impl Flag for Merge {
+    fn is_switch(&self) -> bool {
+        true
+    }
+    fn name_long(&self) -> &'static str {
+        "merge"
+    }
+    fn doc_category(&self) -> Category {
+        Category::Config
+    }
+    fn doc_short(&self) -> &'static str {
+        "Add new template sections to an existing config file."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Used with \flag{init-global-config} or \flag{init-local-config}: instead of
+failing (or, with \flag{force}, overwriting) an existing config file, append
+whichever \fB# ===== SECTION =====\fP template sections were added to
+outgrep's config template since the file was created.
+.sp
+The file's own content, including any settings you've uncommented or edited,
+is left untouched. Outgrep tracks which sections a config file already has
+with an \fBoutgrep-config-template-version\fP marker comment near the top
+of the file; re-running with \flag{merge} is a no-op once the file is
+already current.
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.config_merge = v.unwrap_switch();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_merge() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(false, args.config_merge);
+
+    let args = parse_low_raw(["--merge", "--init-local-config"]).unwrap();
+    assert_eq!(true, args.config_merge);
+    assert_eq!(
+        Some(SpecialMode::InitLocalConfig(true)),
+        args.special
+    );
+}
+
+/// --editor
+#[derive(Debug)]
+struct Editor;
+
+impl Flag for Editor {
+    fn is_switch(&self) -> bool {
+        false
+    }
+    fn name_long(&self) -> &'static str {
+        "editor"
+    }
+    fn doc_variable(&self) -> Option<&'static str> {
+        Some("COMMAND")
+    }
+    fn doc_category(&self) -> Category {
+        Category::Config
+    }
+    fn doc_short(&self) -> &'static str {
+        "Editor command used by --open-global-config/--open-local-config."
+    }
+    fn doc_long(&self) -> &'static str {
+        r"
+Overrides editor detection for \fB--open-global-config\fP and
+\fB--open-local-config\fP with \fICOMMAND\fP, which may include arguments,
+e.g. \fIcode --wait\fP. This takes priority over the \fBVISUAL\fP and
+\fBEDITOR\fP environment variables and the platform-specific fallback list.
+.sp
+This flag is most useful set in a config file, as a persistent editor
+override that doesn't depend on the shell environment outgrep happens to be
+invoked from.
+.sp
+Example: --editor 'idea64.exe --wait'
+"
+    }
+
+    fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
+        args.editor = Some(convert::string(v.unwrap_value())?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_editor() {
+    let args = parse_low_raw(None::<&str>).unwrap();
+    assert_eq!(None, args.editor);
+
+    let args = parse_low_raw(["--editor", "code --wait"]).unwrap();
+    assert_eq!(Some("code --wait".to_string()), args.editor);
+}
+
 /// --open-global-config
 #[derive(Debug)]
 struct OpenGlobalConfig;
@@ -8361,14 +13132,20 @@ impl Flag for OpenGlobalConfig {
     fn doc_long(&self) -> &'static str {
         r"
 Opens the global configuration file in your preferred text editor. The editor
-is determined by checking the \fBEDITOR\fP environment variable first, then
-falling back to platform-specific defaults:
+is determined by checking \fB--editor\fP, then the \fBVISUAL\fP and
+\fBEDITOR\fP environment variables (in that order), then falling back to
+platform-specific defaults:
+.sp
+\fBLinux/Unix:\fP \fBnano\fP, \fBvim\fP, \fBvi\fP, \fBcode\fP
 .sp
-\fBLinux/Unix:\fP \fBnano\fP, \fBvim\fP, \fBvi\fP
+\fBmacOS:\fP \fBnano\fP, \fBvim\fP, \fBcode\fP, \fBopen\fP (TextEdit)
 .sp
-\fBmacOS:\fP \fBnano\fP, \fBvim\fP, \fBopen\fP (TextEdit)
+\fBWindows:\fP \fBnotepad.exe\fP, \fBcode.exe\fP, \fBnotepad++.exe\fP
 .sp
-\fBWindows:\fP \fBnotepad.exe\fP, \fBcode.exe\fP
+Editors known to fork into the background (VS Code, JetBrains IDEs, Sublime
+Text) are automatically launched with their wait flag so outgrep blocks until
+you close the file, unless an explicit editor command already supplied its
+own arguments.
 .sp
 If no global config file exists, use \fB--init-global-config\fP first.
 "
@@ -8376,7 +13153,7 @@ If no global config file exists, use \fB--init-global-config\fP first.
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
         assert!(v.unwrap_switch());
-        args.special = Some(SpecialMode::OpenGlobalConfig);
+        args.special = Some(SpecialMode::OpenGlobalConfig(args.editor.clone()));
         Ok(())
     }
 }
@@ -8409,7 +13186,7 @@ If no local config file exists, use \fB--init-local-config\fP first.
 
     fn update(&self, v: FlagValue, args: &mut LowArgs) -> anyhow::Result<()> {
         assert!(v.unwrap_switch());
-        args.special = Some(SpecialMode::OpenLocalConfig);
+        args.special = Some(SpecialMode::OpenLocalConfig(args.editor.clone()));
         Ok(())
     }
 }
@@ -18,10 +18,10 @@ pub(crate) fn generate_digits() -> String {
     }
 }
 
-/// Generates a short version string of the form `ripgrep x.y.z`.
+/// Generates a short version string of the form `outgrep x.y.z`.
 pub(crate) fn generate_short() -> String {
     let digits = generate_digits();
-    format!("ripgrep {digits}")
+    format!("{} {digits}", crate::catalog::Message::ProductName.text())
 }
 
 /// Generates a longer multi-line version string.
@@ -69,8 +69,12 @@ pub(crate) fn generate_pcre2() -> (String, bool) {
 
     #[cfg(not(feature = "pcre2"))]
     {
-        writeln!(out, "PCRE2 is not available in this build of ripgrep.")
-            .unwrap();
+        writeln!(
+            out,
+            "PCRE2 is not available in this build of {}.",
+            crate::catalog::Message::ProductName.text()
+        )
+        .unwrap();
         (out, false)
     }
 }
@@ -14,10 +14,11 @@ use {
 
 use crate::{
     flags::lowargs::{
-        BinaryMode, BoundaryMode, BufferMode, CaseMode, ColorChoice,
-        ContextMode, ContextSeparator, EncodingMode, EngineChoice,
-        FieldContextSeparator, FieldMatchSeparator, LowArgs, MmapMode, Mode,
-        PatternSource, SearchMode, SortMode, SortModeKind, TypeChange,
+        AnalyzeSortField, BinaryMode, BoundaryMode, BufferMode, CaseMode,
+        ColorChoice, ContextMode, ContextSeparator, EncodingMode,
+        EngineChoice, FieldContextSeparator, FieldMatchSeparator, LowArgs,
+        MmapMode, Mode, PatternSource, SearchMode, SortMode, SortModeKind,
+        TestScope, TypeChange,
     },
     haystack::{Haystack, HaystackBuilder},
     search::{PatternMatcher, Printer, SearchWorker, SearchWorkerBuilder},
@@ -43,15 +44,37 @@ pub(crate) struct HiArgs {
     colors: grep::printer::ColorSpecs,
     column: bool,
     context: ContextMode,
+    context_kinds: Vec<String>,
     context_separator: ContextSeparator,
     crlf: bool,
     analyze: bool,
     watch: bool,
+    watch_events: Vec<String>,
+    watch_globs: ignore::overrides::Override,
+    vscode_ipc: bool,
+    tail: bool,
     diff: bool,
+    structural_diff: bool,
+    deterministic: bool,
     tree: bool,
+    filetype_stats: bool,
     truncate_diffs: bool,
+    diff_ignore_eol: bool,
+    diff_ignore_whitespace: bool,
+    diff_hide_trivial: bool,
     diagnostics: bool,
     syntax: bool,
+    symbols: bool,
+    definition: Option<String>,
+    references: Option<String>,
+    signature: Option<String>,
+    find_duplicates: bool,
+    find_duplicates_threshold: f32,
+    symbol_kinds: Vec<String>,
+    ast_depth: Option<usize>,
+    ast_max_nodes: Option<usize>,
+    ast_summary: bool,
+    with_docs: bool,
     json_output: bool,
     dfa_size_limit: Option<usize>,
     encoding: EncodingMode,
@@ -71,6 +94,7 @@ pub(crate) struct HiArgs {
     invert_match: bool,
     is_terminal_stdout: bool,
     line_number: bool,
+    max_buffer_size: Option<u64>,
     max_columns: Option<u64>,
     max_columns_preview: bool,
     max_count: Option<u64>,
@@ -108,11 +132,56 @@ pub(crate) struct HiArgs {
     semantic_dimensions: Option<usize>,
     semantic_similarity_threshold: Option<f32>,
     semantic_max_results: Option<usize>,
+    semantic_top_k: Option<usize>,
+    semantic_cluster: Option<usize>,
+    semantic_stream: bool,
+    semantic_ef_search: Option<usize>,
+    semantic_chunking: grep::searcher::ChunkingStrategy,
+    semantic_chunk_size: Option<usize>,
+    semantic_chunk_overlap: Option<usize>,
+    semantic_backend: grep::searcher::SemanticBackend,
+    semantic_quantize: grep::searcher::SemanticQuantize,
+    semantic_rerank: bool,
+    semantic_rerank_model: Option<String>,
+    semantic_dimension_mismatch: grep::searcher::DimensionMismatchPolicy,
+    semantic_history: Option<String>,
+    semantic_export: Option<PathBuf>,
+    semantic_import: Option<PathBuf>,
+    semantic_query: Vec<String>,
+    semantic_query_fusion: grep::searcher::QueryFusion,
+    similar_to: Option<String>,
+    hybrid: bool,
+    since: Option<crate::logtime::LogTimestamp>,
+    until: Option<crate::logtime::LogTimestamp>,
+    keypath: Option<crate::keypath::KeyPathQuery>,
+    csv_column: Option<crate::delimited::CsvColumnQuery>,
+    csv_row: bool,
+    ast_pattern: Option<crate::astpattern::AstPatternQuery>,
+    ts_query: Option<std::sync::Arc<crate::tsquery::TsQuery>>,
+    only_in: Vec<String>,
+    not_in: Vec<String>,
+    hex: bool,
+    hex_context: usize,
+    rewrite: Option<crate::rewrite::RewriteQuery>,
+    rewrite_write: bool,
+    rewrite_dry_run: bool,
+    rules: Option<std::sync::Arc<crate::lintrules::LintRuleSet>>,
+    plugins_dir: Option<PathBuf>,
+    wasm_filter: Option<
+        std::sync::Arc<std::sync::Mutex<crate::wasm_plugin::WasmFilter>>,
+    >,
+    symbol: Option<crate::symbolsearch::SymbolQuery>,
+    ast_multiline: bool,
     sort: Option<SortMode>,
+    sort_parallel: bool,
     stats: Option<grep::printer::Stats>,
     stop_on_nonmatch: bool,
     syntax_highlighting: bool,
+    test_scope: TestScope,
+    analyze_sort: AnalyzeSortField,
+    analyze_min: Option<f64>,
     threads: usize,
+    throttle: crate::throttle::Throttle,
     trim: bool,
     types: ignore::types::Types,
     vimgrep: bool,
@@ -135,6 +204,110 @@ impl HiArgs {
         if let Some(ref sort) = low.sort {
             sort.supported()?;
         }
+        if low.jsonpath.is_some() && low.yamlpath.is_some() {
+            anyhow::bail!(
+                "the --jsonpath and --yamlpath flags cannot be used together"
+            );
+        }
+        if !low.only_in.is_empty() && !low.not_in.is_empty() {
+            anyhow::bail!(
+                "the --only-in and --not-in flags cannot be used together"
+            );
+        }
+        let keypath = match (low.jsonpath.take(), low.yamlpath.take()) {
+            (Some(expr), None) => Some(crate::keypath::KeyPathQuery::parse(
+                crate::keypath::KeyPathFormat::Json,
+                &expr,
+            )?),
+            (None, Some(expr)) => Some(crate::keypath::KeyPathQuery::parse(
+                crate::keypath::KeyPathFormat::Yaml,
+                &expr,
+            )?),
+            (None, None) => None,
+            (Some(_), Some(_)) => unreachable!("checked above"),
+        };
+        let csv_column = low
+            .csv_column
+            .take()
+            .map(|expr| crate::delimited::CsvColumnQuery::parse(&expr))
+            .transpose()?;
+        // `--rewrite` reuses `--pattern`/`--lang` to find what to rewrite,
+        // so it's resolved before deciding whether `ast_pattern` itself
+        // should be set: when a rewrite is active, matches are reported
+        // through the rewrite path instead of the plain pattern-search one.
+        let ast_pattern_expr = low.ast_pattern.take();
+        let ast_pattern_lang_name = low.ast_pattern_lang.take();
+        let rewrite = match low.rewrite.take() {
+            None => None,
+            Some(replacement) => {
+                let expr = ast_pattern_expr.clone().ok_or_else(|| {
+                    anyhow::anyhow!("the --rewrite flag requires --pattern")
+                })?;
+                let lang_name =
+                    ast_pattern_lang_name.clone().ok_or_else(|| {
+                        anyhow::anyhow!("the --rewrite flag requires --lang")
+                    })?;
+                let lang: outgrep_ast_language::SupportLang =
+                    lang_name.parse().map_err(|e| anyhow::anyhow!("{}", e))?;
+                Some(crate::rewrite::RewriteQuery::parse(
+                    lang,
+                    &expr,
+                    &replacement,
+                )?)
+            }
+        };
+        let ast_pattern = if rewrite.is_some() {
+            None
+        } else {
+            match ast_pattern_expr {
+                None => None,
+                Some(expr) => {
+                    let lang_name =
+                        ast_pattern_lang_name.clone().ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "the --pattern flag requires --lang"
+                            )
+                        })?;
+                    let lang: outgrep_ast_language::SupportLang = lang_name
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                    Some(crate::astpattern::AstPatternQuery::parse(
+                        lang, &expr,
+                    )?)
+                }
+            }
+        };
+        let ts_query = match low.ts_query.take() {
+            None => None,
+            Some(expr) => {
+                let lang_name = ast_pattern_lang_name.ok_or_else(|| {
+                    anyhow::anyhow!("the --ts-query flag requires --lang")
+                })?;
+                let lang: outgrep_ast_language::SupportLang =
+                    lang_name.parse().map_err(|e| anyhow::anyhow!("{}", e))?;
+                Some(std::sync::Arc::new(crate::tsquery::TsQuery::parse(
+                    lang, &expr,
+                )?))
+            }
+        };
+        let only_in = std::mem::take(&mut low.only_in);
+        let not_in = std::mem::take(&mut low.not_in);
+        let rules = low
+            .rules
+            .take()
+            .map(|path| crate::lintrules::LintRuleSet::load(&path))
+            .transpose()?
+            .map(std::sync::Arc::new);
+        let wasm_filter = low
+            .wasm_plugin
+            .take()
+            .map(|path| crate::wasm_plugin::WasmFilter::load(&path))
+            .transpose()?
+            .map(std::sync::Mutex::new)
+            .map(std::sync::Arc::new);
+        let symbol =
+            low.symbol.take().map(crate::symbolsearch::SymbolQuery::new);
+        let throttle = crate::throttle::Throttle::new(low.throttle);
 
         // We modify the mode in-place on `low` so that subsequent conversions
         // see the correct mode.
@@ -153,6 +326,17 @@ impl HiArgs {
             _ => {}
         }
 
+        // `--remote` replaces whatever positional paths were given (or the
+        // implicit "search ./") with a local clone of the remote repo, so
+        // resolve it before paths are read from `low.positional`.
+        if let Some(ref url) = low.remote {
+            let workdir = crate::remote::resolve_remote_workdir(
+                url,
+                low.remote_ref.as_deref(),
+            )?;
+            low.positional = vec![workdir.into_os_string()];
+        }
+
         let mut state = State::new()?;
         let patterns = Patterns::from_low_args(&mut state, &mut low)?;
         let paths = Paths::from_low_args(&mut state, &patterns, &mut low)?;
@@ -164,6 +348,7 @@ impl HiArgs {
         let types = types(&low)?;
         let globs = globs(&state, &low)?;
         let pre_globs = preprocessor_globs(&state, &low)?;
+        let watch_globs = watch_globs(&state, &low)?;
 
         let color = match low.color {
             ColorChoice::Auto if !state.is_terminal_stdout => {
@@ -179,7 +364,32 @@ impl HiArgs {
         };
         let path_terminator = if low.null { Some(b'\x00') } else { None };
         let quit_after_match = stats.is_none() && low.quiet;
-        let threads = if low.sort.is_some() || paths.is_one_file {
+        // `--semantic-top-k` ranks matches across the whole run rather than
+        // per file, which means every match has to be collected before any
+        // of them can be printed. That's incompatible with the streaming,
+        // per-file output that parallel search relies on, so fall back to
+        // single-threaded search the same way `--sort` does.
+        //
+        // `--sort` itself also forces single-threaded search, since the
+        // directory walker can't otherwise promise any particular output
+        // order. `--sort-parallel` opts back into multiple threads for the
+        // one case that's worth the trouble: ascending `--sort=path`, where
+        // a sorted file list can be computed up front and then searched in
+        // parallel behind a reorder buffer (see `search_parallel_sorted` in
+        // `main.rs`).
+        let sort_forces_single_threaded = low.sort.as_ref().is_some_and(|s| {
+            !(low.sort_parallel
+                && !s.reverse
+                && matches!(s.kind, SortModeKind::Path))
+        });
+        // `--rewrite --write` also needs single-threaded search, since the
+        // end-of-run summary of replacements written totals them across
+        // every file and can't do that correctly with concurrent workers.
+        let threads = if sort_forces_single_threaded
+            || paths.is_one_file
+            || low.semantic_top_k.is_some()
+            || rewrite.is_some()
+        {
             1
         } else if let Some(threads) = low.threads {
             threads
@@ -202,7 +412,7 @@ impl HiArgs {
                     } else {
                         None
                     }
-                } else if let ContextMode::EnclosingSymbol = low.context {
+                } else if let ContextMode::EnclosingSymbol(_) = low.context {
                     // EnclosingSymbol mode will show context, so use separator
                     low.context_separator.clone().into_bytes()
                 } else {
@@ -278,15 +488,37 @@ impl HiArgs {
             colors,
             column,
             context: low.context,
+            context_kinds: low.context_kinds,
             context_separator: low.context_separator,
             crlf: low.crlf,
             analyze: low.analyze,
             watch: low.watch,
+            watch_events: low.watch_events,
+            watch_globs,
+            vscode_ipc: low.vscode_ipc,
+            tail: low.tail,
             diff: low.diff,
+            structural_diff: low.structural_diff,
+            deterministic: low.deterministic,
             tree: low.tree,
+            filetype_stats: low.filetype_stats,
             truncate_diffs: low.truncate_diffs,
+            diff_ignore_eol: low.diff_ignore_eol,
+            diff_ignore_whitespace: low.diff_ignore_whitespace,
+            diff_hide_trivial: low.diff_hide_trivial,
             diagnostics: low.diagnostics,
             syntax: low.syntax,
+            symbols: low.symbols,
+            definition: low.definition,
+            references: low.references,
+            signature: low.signature,
+            find_duplicates: low.find_duplicates,
+            find_duplicates_threshold: low.find_duplicates_threshold,
+            symbol_kinds: low.symbol_kinds,
+            ast_depth: low.ast_depth,
+            ast_max_nodes: low.ast_max_nodes,
+            ast_summary: low.ast_summary,
+            with_docs: low.with_docs,
             json_output: low.json_output,
             dfa_size_limit: low.dfa_size_limit,
             encoding: low.encoding,
@@ -305,6 +537,7 @@ impl HiArgs {
             invert_match: low.invert_match,
             is_terminal_stdout: state.is_terminal_stdout,
             line_number,
+            max_buffer_size: low.max_buffer_size,
             max_columns: low.max_columns,
             max_columns_preview: low.max_columns_preview,
             max_count: low.max_count,
@@ -340,11 +573,54 @@ impl HiArgs {
             semantic_dimensions: low.semantic_dimensions,
             semantic_similarity_threshold: low.semantic_similarity_threshold,
             semantic_max_results: low.semantic_max_results,
+            semantic_top_k: low.semantic_top_k,
+            semantic_cluster: low.semantic_cluster,
+            semantic_stream: low.semantic_stream,
+            semantic_ef_search: low.semantic_ef_search,
+            semantic_chunking: low.semantic_chunking,
+            semantic_chunk_size: low.semantic_chunk_size,
+            semantic_chunk_overlap: low.semantic_chunk_overlap,
+            semantic_backend: low.semantic_backend,
+            semantic_quantize: low.semantic_quantize,
+            semantic_rerank: low.semantic_rerank,
+            semantic_rerank_model: low.semantic_rerank_model,
+            semantic_dimension_mismatch: low.semantic_dimension_mismatch,
+            semantic_history: low.semantic_history,
+            semantic_export: low.semantic_export,
+            semantic_import: low.semantic_import,
+            semantic_query: low.semantic_query,
+            semantic_query_fusion: low.semantic_query_fusion,
+            similar_to: low.similar_to,
+            hybrid: low.hybrid,
+            since: low.since,
+            until: low.until,
+            keypath,
+            csv_column,
+            csv_row: low.csv_row,
+            ast_pattern,
+            ts_query,
+            only_in,
+            not_in,
+            hex: low.hex,
+            hex_context: low.hex_context,
+            rewrite,
+            rewrite_write: low.rewrite_write,
+            rewrite_dry_run: low.rewrite_dry_run,
+            rules,
+            plugins_dir: low.plugins_dir,
+            wasm_filter,
+            symbol,
+            ast_multiline: low.ast_multiline,
             sort: low.sort,
+            sort_parallel: low.sort_parallel,
             stats,
             stop_on_nonmatch: low.stop_on_nonmatch,
             syntax_highlighting: low.syntax_highlighting,
+            test_scope: low.test_scope,
+            analyze_sort: low.analyze_sort,
+            analyze_min: low.analyze_min,
             threads,
+            throttle,
             trim: low.trim,
             types,
             vimgrep: low.vimgrep,
@@ -366,6 +642,22 @@ impl HiArgs {
         wtr
     }
 
+    /// Returns the `--max-buffer-size` cap to apply to each worker's
+    /// per-file output buffer during parallel search, or `None` for
+    /// unbounded buffering.
+    ///
+    /// This returns `None` even when `--max-buffer-size` was given if a
+    /// file separator is configured (`--heading` or context lines), since
+    /// `BufferWriter` only knows to print that separator once per buffer it
+    /// prints; splitting a single file's output across multiple buffers
+    /// would incorrectly repeat or misplace it.
+    pub(crate) fn max_buffer_size(&self) -> Option<u64> {
+        if self.file_separator.is_some() {
+            return None;
+        }
+        self.max_buffer_size
+    }
+
     /// Returns the context mode being used.
     pub(crate) fn context_mode(&self) -> &ContextMode {
         &self.context
@@ -386,6 +678,11 @@ impl HiArgs {
         self.paths.has_implicit_path
     }
 
+    /// Return the file paths that will be searched.
+    pub(crate) fn search_paths(&self) -> &[std::path::PathBuf] {
+        &self.paths.paths
+    }
+
     /// Return a properly configured builder for constructing haystacks.
     ///
     /// The builder can be used to turn a directory entry (from the `ignore`
@@ -586,7 +883,8 @@ impl HiArgs {
             .color_specs(self.colors.clone())
             .hyperlink(self.hyperlink_config.clone())
             .separator(self.path_separator.clone())
-            .terminator(self.path_terminator.unwrap_or(b'\n'));
+            .terminator(self.path_terminator.unwrap_or(b'\n'))
+            .json(self.json_output);
         builder
     }
 
@@ -728,21 +1026,73 @@ impl HiArgs {
     ) -> anyhow::Result<SearchWorker<W>> {
         let mut builder = SearchWorkerBuilder::new();
         let use_ast_context =
-            matches!(self.context, ContextMode::EnclosingSymbol);
+            matches!(self.context, ContextMode::EnclosingSymbol(_));
+        let context_padding =
+            self.context.enclosing_symbol_padding().unwrap_or((0, 0));
         builder
             .preprocessor(self.pre.clone())?
             .preprocessor_globs(self.pre_globs.clone())
             .search_zip(self.search_zip)
             .binary_detection_explicit(self.binary.explicit.clone())
             .binary_detection_implicit(self.binary.implicit.clone())
+            .max_filesize(self.max_filesize)
             .ast_context(use_ast_context)
+            .context_kinds(self.context_kinds.clone())
+            .context_padding(context_padding)
             .syntax_highlighting(self.syntax_highlighting)
-            .semantic_search(self.semantic)
+            .with_docs(self.with_docs)
+            .semantic_search(
+                self.semantic
+                    || self.similar_to.is_some()
+                    || self.semantic_history.is_some()
+                    || self.semantic_import.is_some()
+                    || !self.semantic_query.is_empty(),
+            )
             .semantic_model_path(self.semantic_model_path.clone())
             .semantic_model(self.semantic_model.clone())
             .semantic_dimensions(self.semantic_dimensions)
             .semantic_similarity_threshold(self.semantic_similarity_threshold)
             .semantic_max_results(self.semantic_max_results)
+            .semantic_top_k(self.semantic_top_k)
+            .semantic_cluster(self.semantic_cluster)
+            .semantic_stream(self.semantic_stream)
+            .quit_after_match(self.quit_after_match)
+            .semantic_ef_search(self.semantic_ef_search)
+            .semantic_chunking(self.semantic_chunking)
+            .semantic_chunk_size(self.semantic_chunk_size)
+            .semantic_chunk_overlap(self.semantic_chunk_overlap)
+            .semantic_backend(self.semantic_backend)
+            .semantic_quantize(self.semantic_quantize)
+            .semantic_rerank(self.semantic_rerank)
+            .semantic_rerank_model(self.semantic_rerank_model.clone())
+            .semantic_dimension_mismatch(self.semantic_dimension_mismatch)
+            .semantic_history(self.semantic_history.clone())
+            .semantic_export(self.semantic_export.clone())
+            .semantic_import(self.semantic_import.clone())
+            .semantic_query(self.semantic_query.clone())
+            .semantic_query_fusion(self.semantic_query_fusion)
+            .similar_to(self.similar_to.clone())
+            .hybrid_search(self.hybrid)
+            .since(self.since)
+            .until(self.until)
+            .keypath(self.keypath.clone())
+            .csv_column(self.csv_column.clone())
+            .csv_row(self.csv_row)
+            .ast_pattern(self.ast_pattern.clone())
+            .ts_query(self.ts_query.clone())
+            .only_in(self.only_in.clone())
+            .not_in(self.not_in.clone())
+            .hex(self.hex && self.binary.is_convert())
+            .hex_context(self.hex_context)
+            .rewrite(self.rewrite.clone())
+            .rewrite_write(self.rewrite_write)
+            .rewrite_dry_run(self.rewrite_dry_run)
+            .rules(self.rules.clone())
+            .wasm_filter(self.wasm_filter.clone())
+            .symbol(self.symbol.clone())
+            .ast_multiline(self.ast_multiline)
+            .throttle(self.throttle)
+            .test_scope(self.test_scope)
             .pattern(self.first_pattern().map(|s| s.to_string()));
         Ok(builder.build(matcher, searcher, printer))
     }
@@ -773,11 +1123,11 @@ impl HiArgs {
                 builder.before_context(before);
                 builder.after_context(after);
             }
-            ContextMode::EnclosingSymbol => {
-                // For EnclosingSymbol mode, we'll need to implement
-                // context expansion based on AST parsing later.
-                // For now, treat it as passthrough until the searcher
-                // can handle enclosing symbol context.
+            ContextMode::EnclosingSymbol(_) => {
+                // The low-level searcher only ever sees whole lines, so it
+                // can't expand a match to its enclosing AST symbol. Padding
+                // around the symbol is applied later by `AstSymbolSink`,
+                // which reads `context_padding` off the search worker.
                 builder.passthru(true);
             }
         }
@@ -803,6 +1153,49 @@ impl HiArgs {
         self.semantic
     }
 
+    /// Return the configured `--semantic-top-k` limit, if any.
+    ///
+    /// When set, `HiArgs::threads` is forced to `1`, since ranking matches
+    /// across the whole run requires collecting all of them before any can
+    /// be printed.
+    pub(crate) fn semantic_top_k(&self) -> Option<usize> {
+        self.semantic_top_k
+    }
+
+    /// Return whether `--semantic-stream` was given.
+    ///
+    /// This only takes effect when `semantic_top_k`, `semantic_cluster` and
+    /// multi-query fusion are all absent, since each of those needs the full
+    /// candidate set before it can do anything; `search_path_semantic`
+    /// decides whether streaming actually applies.
+    pub(crate) fn semantic_stream(&self) -> bool {
+        self.semantic_stream
+    }
+
+    /// Return whether hybrid regex + semantic ranking is enabled.
+    pub(crate) fn hybrid(&self) -> bool {
+        self.hybrid
+    }
+
+    /// Return the configured test/production code scope, as set by
+    /// `--tests-only` or `--no-tests`.
+    pub(crate) fn test_scope(&self) -> TestScope {
+        self.test_scope
+    }
+
+    /// Return the `CodeMetrics` field `--analyze` sorts its per-file output
+    /// by, as set by `--analyze-sort`.
+    pub(crate) fn analyze_sort(&self) -> AnalyzeSortField {
+        self.analyze_sort
+    }
+
+    /// Return the minimum value of the `--analyze-sort` field a file's
+    /// metrics must meet to be included in `--analyze` output, as set by
+    /// `--analyze-min`. `None` means no filtering.
+    pub(crate) fn analyze_min(&self) -> Option<f64> {
+        self.analyze_min
+    }
+
     /// Return whether analyze mode is enabled.
     pub(crate) fn analyze(&self) -> bool {
         self.analyze
@@ -813,16 +1206,54 @@ impl HiArgs {
         self.watch
     }
 
+    /// Return the event kinds `--watch-events` restricts `--watch` to.
+    /// Empty means every event kind is reported.
+    pub(crate) fn watch_events(&self) -> &[String] {
+        &self.watch_events
+    }
+
+    /// Return the glob matcher built from `--watch-glob`. An empty
+    /// (unmatched-by-default) override means every watched path is
+    /// reported.
+    pub(crate) fn watch_globs(&self) -> &ignore::overrides::Override {
+        &self.watch_globs
+    }
+
+    /// Return whether `--vscode-ipc` mode is enabled.
+    pub(crate) fn vscode_ipc(&self) -> bool {
+        self.vscode_ipc
+    }
+
+    /// Return whether tail-follow mode is enabled.
+    pub(crate) fn tail(&self) -> bool {
+        self.tail
+    }
+
     /// Return whether diff mode is enabled.
     pub(crate) fn diff(&self) -> bool {
         self.diff
     }
 
+    /// Return whether structural (symbol-level) diff mode is enabled.
+    pub(crate) fn structural_diff(&self) -> bool {
+        self.structural_diff
+    }
+
+    /// Return whether deterministic output mode is enabled.
+    pub(crate) fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
     /// Return whether tree mode is enabled.
     pub(crate) fn tree(&self) -> bool {
         self.tree
     }
 
+    /// Return whether standalone filetype statistics mode is enabled.
+    pub(crate) fn filetype_stats(&self) -> bool {
+        self.filetype_stats
+    }
+
     /// Return whether diff truncation is enabled.
     pub(crate) fn truncate_diffs(&self) -> bool {
         self.truncate_diffs
@@ -833,11 +1264,118 @@ impl HiArgs {
         self.diagnostics
     }
 
+    /// Return the line-ending and whitespace normalization requested for
+    /// `--diff` output by `--diff-ignore-eol` and `--diff-ignore-whitespace`.
+    pub(crate) fn diff_options(&self) -> crate::diagnostics::DiffOptions {
+        crate::diagnostics::DiffOptions {
+            ignore_eol: self.diff_ignore_eol,
+            ignore_whitespace: self.diff_ignore_whitespace,
+        }
+    }
+
+    /// Return whether `--diff-hide-trivial` was given, hiding comment-only
+    /// and whitespace-only hunks from `--diff` output.
+    pub(crate) fn diff_hide_trivial(&self) -> bool {
+        self.diff_hide_trivial
+    }
+
+    /// Return whether symlinks should be followed, e.g. so that tree mode
+    /// can descend into symlinked directories the same way search does.
+    pub(crate) fn follow(&self) -> bool {
+        self.follow
+    }
+
     /// Return whether syntax analysis is enabled.
     pub(crate) fn syntax(&self) -> bool {
         self.syntax
     }
 
+    /// Return whether `--symbols` mode is enabled.
+    pub(crate) fn symbols(&self) -> bool {
+        self.symbols
+    }
+
+    /// Return the identifier `--definition` should look up, if given.
+    pub(crate) fn definition(&self) -> Option<&str> {
+        self.definition.as_deref()
+    }
+
+    /// Return the identifier `--references` should look up, if given.
+    pub(crate) fn references(&self) -> Option<&str> {
+        self.references.as_deref()
+    }
+
+    /// Return the `--signature` query to match functions against, if given.
+    pub(crate) fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    /// Return whether `--find-duplicates` is enabled.
+    pub(crate) fn find_duplicates(&self) -> bool {
+        self.find_duplicates
+    }
+
+    /// Return the minimum cosine similarity two symbols must share to be
+    /// grouped into the same `--find-duplicates` cluster, as set by
+    /// `--find-duplicates-threshold`.
+    pub(crate) fn find_duplicates_threshold(&self) -> f32 {
+        self.find_duplicates_threshold
+    }
+
+    /// Return the `--plugins-dir` directory to search for `og-plugin-*`
+    /// executables in, in addition to `PATH`, if given.
+    pub(crate) fn plugins_dir(&self) -> Option<&Path> {
+        self.plugins_dir.as_deref()
+    }
+
+    /// Return whether output should be colored, based on the resolved
+    /// `--color` choice (which already accounts for `--color=auto` and
+    /// whether stdout is a tty).
+    pub(crate) fn color_enabled(&self) -> bool {
+        self.color != ColorChoice::Never
+    }
+
+    /// Return the theme used to color search output, also reused by
+    /// `--tree`/`--analyze`'s summary and diff output so both respect the
+    /// same `--colors` configuration.
+    pub(crate) fn colors(&self) -> &grep::printer::ColorSpecs {
+        &self.colors
+    }
+
+    /// Return the symbol kinds that `--symbol-kinds` restricts the syntax
+    /// view to. Empty means no restriction.
+    pub(crate) fn symbol_kinds(&self) -> &[String] {
+        &self.symbol_kinds
+    }
+
+    /// Return the AST context kinds that `--context-kind` restricts
+    /// `--enclosing-symbol` to. Empty means use the default context types.
+    pub(crate) fn context_kinds(&self) -> &[String] {
+        &self.context_kinds
+    }
+
+    /// Return the maximum AST nesting depth that `--ast-depth` restricts
+    /// the syntax view to, or `None` if unrestricted.
+    pub(crate) fn ast_depth(&self) -> Option<usize> {
+        self.ast_depth
+    }
+
+    /// Return the maximum AST node count that `--ast-max-nodes` restricts
+    /// the syntax view to, or `None` if unrestricted.
+    pub(crate) fn ast_max_nodes(&self) -> Option<usize> {
+        self.ast_max_nodes
+    }
+
+    /// Return whether `--ast-summary` is enabled.
+    pub(crate) fn ast_summary(&self) -> bool {
+        self.ast_summary
+    }
+
+    /// Return whether `--with-docs` is enabled.
+    pub(crate) fn with_docs(&self) -> bool {
+        self.with_docs
+    }
+
     /// Return whether JSON output is enabled.
     pub(crate) fn json_output(&self) -> bool {
         self.json_output
@@ -959,6 +1497,28 @@ impl HiArgs {
         self.threads
     }
 
+    /// Returns whether `--throttle` was given, and thus whether this
+    /// process should lower its own scheduling priority for the duration
+    /// of the search.
+    pub(crate) fn throttled(&self) -> bool {
+        self.throttle.is_enabled()
+    }
+
+    /// Returns whether this search should use `search_parallel_sorted`:
+    /// multiple threads, combined with `--sort-parallel` and ascending
+    /// `--sort=path`.
+    ///
+    /// When this is true, `threads()` is guaranteed to be greater than 1,
+    /// since `--sort-parallel` is the only way `--sort` doesn't force
+    /// single-threaded search.
+    pub(crate) fn sort_parallel_enabled(&self) -> bool {
+        self.threads > 1
+            && self.sort_parallel
+            && self.sort.as_ref().is_some_and(|s| {
+                !s.reverse && matches!(s.kind, SortModeKind::Path)
+            })
+    }
+
     /// Returns the file type matcher that was built.
     ///
     /// The matcher includes both the default rules and any rules added by the
@@ -1013,8 +1573,14 @@ impl HiArgs {
         // Otherwise, sorting is done by collecting all paths, sorting them and
         // then searching them.
         if let Some(ref sort) = self.sort {
-            assert_eq!(1, self.threads, "sorting implies single threaded");
-            if !sort.reverse && matches!(sort.kind, SortModeKind::Path) {
+            let ascending_path =
+                !sort.reverse && matches!(sort.kind, SortModeKind::Path);
+            assert!(
+                self.threads == 1 || (self.sort_parallel && ascending_path),
+                "sorting implies single threaded unless --sort-parallel is \
+                 combined with ascending --sort=path",
+            );
+            if ascending_path {
                 builder.sort_by_file_name(|a, b| a.cmp(b));
             }
         }
@@ -1104,7 +1670,7 @@ impl Patterns {
         if low.patterns.is_empty() {
             anyhow::ensure!(
                 !low.positional.is_empty(),
-                "ripgrep requires at least one pattern to execute a search"
+                crate::catalog::Message::PatternRequired.text()
             );
             let ospat = low.positional.remove(0);
             let Ok(pat) = ospat.into_string() else {
@@ -1288,6 +1854,13 @@ impl BinaryDetection {
         let none = grep::searcher::BinaryDetection::none();
         self.explicit == none && self.implicit == none
     }
+
+    /// Returns true when binary files are searched rather than skipped or
+    /// truncated at the first NUL byte, i.e. when `--binary` was given.
+    pub(crate) fn is_convert(&self) -> bool {
+        let convert = grep::searcher::BinaryDetection::convert(b'\x00');
+        self.implicit == convert
+    }
 }
 
 /// Builds the file type matcher from low level arguments.
@@ -1338,6 +1911,22 @@ fn globs(
     Ok(builder.build()?)
 }
 
+/// Builds the glob matcher used to restrict `--watch` events (via
+/// `--watch-glob`).
+fn watch_globs(
+    state: &State,
+    low: &LowArgs,
+) -> anyhow::Result<ignore::overrides::Override> {
+    if low.watch_globs.is_empty() {
+        return Ok(ignore::overrides::Override::empty());
+    }
+    let mut builder = ignore::overrides::OverrideBuilder::new(&state.cwd);
+    for glob in low.watch_globs.iter() {
+        builder.add(glob)?;
+    }
+    Ok(builder.build()?)
+}
+
 /// Builds a glob matcher for all of the preprocessor globs (via `--pre-glob`).
 fn preprocessor_globs(
     state: &State,
@@ -164,29 +164,140 @@ impl ConfigHierarchy {
         None
     }
 
-    /// Parse a configuration file into command line arguments
+    /// Parse a configuration file into command line arguments, resolving
+    /// any `include = "path"` directives along the way.
     fn parse_config_file(path: &Path) -> Result<Vec<OsString>> {
+        let provenance = Self::parse_config_file_with_provenance(path)?;
+        Ok(provenance.into_iter().map(|(_, arg)| arg).collect())
+    }
+
+    /// Parse a configuration file the same way as `parse_config_file`, but
+    /// also return which file each resulting argument came from. Used by
+    /// `--config-dump` to show organizations where a shared team config
+    /// contributed a setting versus where a local override did.
+    pub fn parse_config_file_with_provenance(
+        path: &Path,
+    ) -> Result<Vec<(PathBuf, OsString)>> {
+        let mut visiting = Vec::new();
+        Self::parse_config_file_recursive(path, &mut visiting)
+    }
+
+    /// Recursive worker for `parse_config_file_with_provenance`.
+    ///
+    /// `visiting` holds the canonicalized paths of every config file
+    /// currently being resolved along the current include chain, so that an
+    /// `include` cycle (directly or through several files) is reported as an
+    /// error instead of recursing forever.
+    fn parse_config_file_recursive(
+        path: &Path,
+        visiting: &mut Vec<PathBuf>,
+    ) -> Result<Vec<(PathBuf, OsString)>> {
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if visiting.contains(&canonical) {
+            let mut chain: Vec<String> = visiting
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect();
+            chain.push(path.display().to_string());
+            anyhow::bail!(
+                "config include cycle detected: {}",
+                chain.join(" -> ")
+            );
+        }
+        visiting.push(canonical);
+
         let contents = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
         let mut args = Vec::new();
-
         for line in contents.lines() {
             let line = line.trim();
-            
+
             // Skip empty lines and comments
             if line.is_empty() || line.starts_with('#') {
                 continue;
             }
 
+            if let Some(include_value) = Self::parse_include_directive(line) {
+                let include_path =
+                    Self::resolve_include_path(path, &include_value);
+                let included = Self::parse_config_file_recursive(
+                    &include_path,
+                    visiting,
+                )
+                .with_context(|| {
+                    format!(
+                        "failed to include {} from {}",
+                        include_path.display(),
+                        path.display()
+                    )
+                })?;
+                args.extend(included);
+                continue;
+            }
+
+            // `editor = "COMMAND"` is the config-file form of `--editor`.
+            if let Some(rest) = line.strip_prefix("editor") {
+                if let Some(value) = rest.trim_start().strip_prefix('=') {
+                    let mut arg = OsString::from("--editor=");
+                    arg.push(Self::unquote(value));
+                    args.push((path.to_path_buf(), arg));
+                    continue;
+                }
+            }
+
             // For now, treat each line as a single argument
             // TODO: Add support for TOML format in the future
-            args.push(OsString::from(line));
+            args.push((path.to_path_buf(), OsString::from(line)));
         }
 
+        visiting.pop();
         Ok(args)
     }
 
+    /// If `line` is an `include` directive (`include = "path"`, `include
+    /// "path"`, or `--config-extra path`), return the included path as
+    /// written (not yet resolved relative to anything).
+    fn parse_include_directive(line: &str) -> Option<String> {
+        if let Some(rest) = line.strip_prefix("--config-extra") {
+            let rest = rest.trim();
+            if !rest.is_empty() {
+                return Some(Self::unquote(rest));
+            }
+        }
+
+        let rest = line.strip_prefix("include")?;
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix('=').unwrap_or(rest);
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return None;
+        }
+        Some(Self::unquote(rest))
+    }
+
+    /// Strip a single layer of matching double quotes from `s`, if present.
+    fn unquote(s: &str) -> String {
+        let s = s.trim();
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(inner) => inner.to_string(),
+            None => s.to_string(),
+        }
+    }
+
+    /// Resolve an `include` directive's path relative to the directory of
+    /// the config file that referenced it, unless it's already absolute.
+    fn resolve_include_path(including_file: &Path, include_value: &str) -> PathBuf {
+        let include_path = PathBuf::from(include_value);
+        if include_path.is_absolute() {
+            return include_path;
+        }
+        including_file
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(include_path)
+    }
+
     /// Get the path where a global config file should be created
     pub fn default_global_config_path() -> Result<PathBuf> {
         let home_dir = dirs::home_dir()
@@ -288,4 +399,81 @@ mod tests {
         assert_eq!(merged[3], "--line-number");   // local
         assert_eq!(merged[4], "--no-hidden");     // cli (overrides global --hidden)
     }
+
+    #[test]
+    fn test_config_file_include_directive() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared_path = temp_dir.path().join("shared");
+        fs::write(&shared_path, "--smart-case\n--hidden\n").unwrap();
+
+        let local_path = temp_dir.path().join("local");
+        fs::write(
+            &local_path,
+            format!("include = \"{}\"\n--line-number\n", shared_path.display()),
+        )
+        .unwrap();
+
+        let args = ConfigHierarchy::parse_config_file(&local_path).unwrap();
+        assert_eq!(args.len(), 3);
+        assert_eq!(args[0], "--smart-case");
+        assert_eq!(args[1], "--hidden");
+        assert_eq!(args[2], "--line-number");
+    }
+
+    #[test]
+    fn test_config_file_config_extra_directive() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared_path = temp_dir.path().join("shared");
+        fs::write(&shared_path, "--smart-case\n").unwrap();
+
+        let local_path = temp_dir.path().join("local");
+        fs::write(
+            &local_path,
+            format!("--config-extra {}\n--hidden\n", shared_path.display()),
+        )
+        .unwrap();
+
+        let args = ConfigHierarchy::parse_config_file(&local_path).unwrap();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0], "--smart-case");
+        assert_eq!(args[1], "--hidden");
+    }
+
+    #[test]
+    fn test_config_file_include_cycle_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let a_path = temp_dir.path().join("a");
+        let b_path = temp_dir.path().join("b");
+
+        fs::write(&a_path, format!("include = \"{}\"\n", b_path.display()))
+            .unwrap();
+        fs::write(&b_path, format!("include = \"{}\"\n", a_path.display()))
+            .unwrap();
+
+        let result = ConfigHierarchy::parse_config_file(&a_path);
+        assert!(result.is_err());
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("cycle") || message.contains("include"));
+    }
+
+    #[test]
+    fn test_config_file_with_provenance() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared_path = temp_dir.path().join("shared");
+        fs::write(&shared_path, "--smart-case\n").unwrap();
+
+        let local_path = temp_dir.path().join("local");
+        fs::write(
+            &local_path,
+            format!("include = \"{}\"\n--hidden\n", shared_path.display()),
+        )
+        .unwrap();
+
+        let args =
+            ConfigHierarchy::parse_config_file_with_provenance(&local_path)
+                .unwrap();
+        assert_eq!(args.len(), 2);
+        assert_eq!(args[0].0, shared_path);
+        assert_eq!(args[1].0, local_path);
+    }
 }
\ No newline at end of file
@@ -46,15 +46,37 @@ pub(crate) struct LowArgs {
     pub(crate) colors: Vec<UserColorSpec>,
     pub(crate) column: Option<bool>,
     pub(crate) context: ContextMode,
+    pub(crate) context_kinds: Vec<String>,
     pub(crate) context_separator: ContextSeparator,
     pub(crate) crlf: bool,
     pub(crate) analyze: bool,
     pub(crate) watch: bool,
+    pub(crate) watch_events: Vec<String>,
+    pub(crate) watch_globs: Vec<String>,
+    pub(crate) vscode_ipc: bool,
+    pub(crate) tail: bool,
     pub(crate) diff: bool,
+    pub(crate) structural_diff: bool,
+    pub(crate) deterministic: bool,
     pub(crate) tree: bool,
+    pub(crate) filetype_stats: bool,
     pub(crate) truncate_diffs: bool,
+    pub(crate) diff_ignore_eol: bool,
+    pub(crate) diff_ignore_whitespace: bool,
+    pub(crate) diff_hide_trivial: bool,
     pub(crate) diagnostics: bool,
     pub(crate) syntax: bool,
+    pub(crate) symbols: bool,
+    pub(crate) definition: Option<String>,
+    pub(crate) references: Option<String>,
+    pub(crate) signature: Option<String>,
+    pub(crate) find_duplicates: bool,
+    pub(crate) find_duplicates_threshold: f32,
+    pub(crate) symbol_kinds: Vec<String>,
+    pub(crate) ast_depth: Option<usize>,
+    pub(crate) ast_max_nodes: Option<usize>,
+    pub(crate) ast_summary: bool,
+    pub(crate) with_docs: bool,
     pub(crate) json_output: bool,
     pub(crate) dfa_size_limit: Option<usize>,
     pub(crate) encoding: EncodingMode,
@@ -76,6 +98,7 @@ pub(crate) struct LowArgs {
     pub(crate) invert_match: bool,
     pub(crate) line_number: Option<bool>,
     pub(crate) logging: Option<LoggingMode>,
+    pub(crate) max_buffer_size: Option<u64>,
     pub(crate) max_columns: Option<u64>,
     pub(crate) max_columns_preview: bool,
     pub(crate) max_count: Option<u64>,
@@ -84,6 +107,9 @@ pub(crate) struct LowArgs {
     pub(crate) mmap: MmapMode,
     pub(crate) multiline: bool,
     pub(crate) multiline_dotall: bool,
+    pub(crate) config_merge: bool,
+    pub(crate) config_extra: Vec<PathBuf>,
+    pub(crate) editor: Option<String>,
     pub(crate) no_config: bool,
     pub(crate) no_ignore_dot: bool,
     pub(crate) no_ignore_exclude: bool,
@@ -104,6 +130,8 @@ pub(crate) struct LowArgs {
     pub(crate) pre_glob: Vec<String>,
     pub(crate) quiet: bool,
     pub(crate) regex_size_limit: Option<usize>,
+    pub(crate) remote: Option<String>,
+    pub(crate) remote_ref: Option<String>,
     pub(crate) replace: Option<BString>,
     pub(crate) search_zip: bool,
     pub(crate) semantic: bool,
@@ -112,11 +140,57 @@ pub(crate) struct LowArgs {
     pub(crate) semantic_dimensions: Option<usize>,
     pub(crate) semantic_similarity_threshold: Option<f32>,
     pub(crate) semantic_max_results: Option<usize>,
+    pub(crate) semantic_top_k: Option<usize>,
+    pub(crate) semantic_cluster: Option<usize>,
+    pub(crate) semantic_stream: bool,
+    pub(crate) semantic_ef_search: Option<usize>,
+    pub(crate) semantic_chunking: grep::searcher::ChunkingStrategy,
+    pub(crate) semantic_chunk_size: Option<usize>,
+    pub(crate) semantic_chunk_overlap: Option<usize>,
+    pub(crate) semantic_backend: grep::searcher::SemanticBackend,
+    pub(crate) semantic_quantize: grep::searcher::SemanticQuantize,
+    pub(crate) semantic_rerank: bool,
+    pub(crate) semantic_rerank_model: Option<String>,
+    pub(crate) semantic_dimension_mismatch:
+        grep::searcher::DimensionMismatchPolicy,
+    pub(crate) semantic_history: Option<String>,
+    pub(crate) semantic_export: Option<PathBuf>,
+    pub(crate) semantic_import: Option<PathBuf>,
+    pub(crate) semantic_query: Vec<String>,
+    pub(crate) semantic_query_fusion: grep::searcher::QueryFusion,
+    pub(crate) similar_to: Option<String>,
+    pub(crate) hybrid: bool,
+    pub(crate) since: Option<crate::logtime::LogTimestamp>,
+    pub(crate) until: Option<crate::logtime::LogTimestamp>,
+    pub(crate) jsonpath: Option<String>,
+    pub(crate) yamlpath: Option<String>,
+    pub(crate) csv_column: Option<String>,
+    pub(crate) csv_row: bool,
+    pub(crate) ast_pattern: Option<String>,
+    pub(crate) ast_pattern_lang: Option<String>,
+    pub(crate) ts_query: Option<String>,
+    pub(crate) only_in: Vec<String>,
+    pub(crate) not_in: Vec<String>,
+    pub(crate) hex: bool,
+    pub(crate) hex_context: usize,
+    pub(crate) rewrite: Option<String>,
+    pub(crate) rewrite_write: bool,
+    pub(crate) rewrite_dry_run: bool,
+    pub(crate) rules: Option<PathBuf>,
+    pub(crate) plugins_dir: Option<PathBuf>,
+    pub(crate) wasm_plugin: Option<PathBuf>,
+    pub(crate) symbol: Option<String>,
+    pub(crate) ast_multiline: bool,
     pub(crate) sort: Option<SortMode>,
+    pub(crate) sort_parallel: bool,
     pub(crate) stats: bool,
     pub(crate) stop_on_nonmatch: bool,
     pub(crate) syntax_highlighting: bool,
+    pub(crate) test_scope: TestScope,
+    pub(crate) analyze_sort: AnalyzeSortField,
+    pub(crate) analyze_min: Option<f64>,
     pub(crate) threads: Option<usize>,
+    pub(crate) throttle: Option<f64>,
     pub(crate) trim: bool,
     pub(crate) type_changes: Vec<TypeChange>,
     pub(crate) unrestricted: usize,
@@ -140,15 +214,37 @@ impl Default for LowArgs {
             colors: Vec::new(),
             column: None,
             context: ContextMode::default(),
+            context_kinds: Vec::new(),
             context_separator: ContextSeparator::default(),
             crlf: false,
             analyze: false,
             watch: false,
+            watch_events: Vec::new(),
+            watch_globs: Vec::new(),
+            vscode_ipc: false,
+            tail: false,
             diff: false,
+            structural_diff: false,
+            deterministic: false,
             tree: false,
+            filetype_stats: false,
             truncate_diffs: false,
+            diff_ignore_eol: false,
+            diff_ignore_whitespace: false,
+            diff_hide_trivial: false,
             diagnostics: false,
             syntax: false,
+            symbols: false,
+            definition: None,
+            references: None,
+            signature: None,
+            find_duplicates: false,
+            find_duplicates_threshold: 0.85,
+            symbol_kinds: Vec::new(),
+            ast_depth: None,
+            ast_max_nodes: None,
+            ast_summary: false,
+            with_docs: false,
             json_output: false,
             dfa_size_limit: None,
             encoding: EncodingMode::default(),
@@ -170,6 +266,7 @@ impl Default for LowArgs {
             invert_match: false,
             line_number: None,
             logging: None,
+            max_buffer_size: None,
             max_columns: None,
             max_columns_preview: false,
             max_count: None,
@@ -178,6 +275,9 @@ impl Default for LowArgs {
             mmap: MmapMode::default(),
             multiline: false,
             multiline_dotall: false,
+            config_merge: false,
+            config_extra: Vec::new(),
+            editor: None,
             no_config: false,
             no_ignore_dot: false,
             no_ignore_exclude: false,
@@ -198,6 +298,8 @@ impl Default for LowArgs {
             pre_glob: Vec::new(),
             quiet: false,
             regex_size_limit: None,
+            remote: None,
+            remote_ref: None,
             replace: None,
             search_zip: false,
             semantic: false,
@@ -206,11 +308,57 @@ impl Default for LowArgs {
             semantic_dimensions: None,
             semantic_similarity_threshold: None,
             semantic_max_results: None,
+            semantic_top_k: None,
+            semantic_cluster: None,
+            semantic_stream: false,
+            semantic_ef_search: None,
+            semantic_chunking: grep::searcher::ChunkingStrategy::default(),
+            semantic_chunk_size: None,
+            semantic_chunk_overlap: None,
+            semantic_backend: grep::searcher::SemanticBackend::default(),
+            semantic_quantize: grep::searcher::SemanticQuantize::default(),
+            semantic_rerank: false,
+            semantic_rerank_model: None,
+            semantic_dimension_mismatch:
+                grep::searcher::DimensionMismatchPolicy::default(),
+            semantic_history: None,
+            semantic_export: None,
+            semantic_import: None,
+            semantic_query: Vec::new(),
+            semantic_query_fusion: grep::searcher::QueryFusion::default(),
+            similar_to: None,
+            hybrid: false,
+            since: None,
+            until: None,
+            jsonpath: None,
+            yamlpath: None,
+            csv_column: None,
+            csv_row: false,
+            ast_pattern: None,
+            ast_pattern_lang: None,
+            ts_query: None,
+            only_in: Vec::new(),
+            not_in: Vec::new(),
+            hex: false,
+            hex_context: 32,
+            rewrite: None,
+            rewrite_write: false,
+            rewrite_dry_run: false,
+            rules: None,
+            plugins_dir: None,
+            wasm_plugin: None,
+            symbol: None,
+            ast_multiline: false,
             sort: None,
+            sort_parallel: false,
             stats: false,
             stop_on_nonmatch: false,
             syntax_highlighting: true, // Default to true
+            test_scope: TestScope::default(),
+            analyze_sort: AnalyzeSortField::default(),
+            analyze_min: None,
             threads: None,
+            throttle: None,
             trim: false,
             type_changes: Vec::new(),
             unrestricted: 0,
@@ -229,7 +377,7 @@ impl Default for LowArgs {
 /// fail too, but usually not in a way that can't be worked around by removing
 /// the corresponding arguments from the CLI command.) This is overall a hedge
 /// to ensure that version and help information are basically always available.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) enum SpecialMode {
     /// Show a condensed version of "help" output. Generally speaking, this
     /// shows each flag and an extremely terse description of that flag on
@@ -246,16 +394,61 @@ pub(crate) enum SpecialMode {
     /// Show PCRE2's version information, or an error if this version of
     /// ripgrep wasn't compiled with PCRE2 support.
     VersionPCRE2,
-    /// Initialize a global configuration file with default settings.
-    InitGlobalConfig,
-    /// Initialize a local configuration file for the current project.
-    InitLocalConfig,
+    /// Initialize a global configuration file with default settings. The
+    /// `bool` is whether `--merge` was also given, resolved after the full
+    /// command line (including flags after `--init-global-config`) has been
+    /// parsed, so flag order doesn't matter.
+    InitGlobalConfig(bool),
+    /// Initialize a local configuration file for the current project. See
+    /// `InitGlobalConfig` for what the `bool` means.
+    InitLocalConfig(bool),
     /// Open the global configuration file in the user's preferred editor.
-    OpenGlobalConfig,
+    /// The `Option<String>` is an `--editor` override, resolved after the
+    /// full command line has been parsed, so flag order doesn't matter.
+    OpenGlobalConfig(Option<String>),
     /// Open the local configuration file in the user's preferred editor.
-    OpenLocalConfig,
+    /// See `OpenGlobalConfig` for what the payload means.
+    OpenLocalConfig(Option<String>),
     /// Show the status of configuration files (loaded/not found).
     ConfigStatus,
+    /// Print the fully merged configuration, annotating each argument with
+    /// the file (including any `include` chain) it came from. This
+    /// corresponds to the `--config-dump` flag. The `Vec<PathBuf>` is the
+    /// set of `--config-extra` files, resolved after the full command line
+    /// has been parsed, so flag order doesn't matter.
+    ConfigDump(Vec<PathBuf>),
+    /// Download the named semantic search model into the model storage
+    /// directory, with resume and checksum verification, and then exit.
+    /// This corresponds to the `--semantic-download-model NAME` flag.
+    DownloadModel(String),
+    /// Run the first-run diagnostics report: PATH tools, terminal
+    /// capabilities, config validity, model cache state, and model
+    /// availability. This corresponds to the `--doctor` flag.
+    Doctor,
+    /// Print a summary of the user's own local search history: top
+    /// patterns, most-searched directories, and average query time. This
+    /// corresponds to the `--usage-summary` flag.
+    UsageSummary,
+    /// List the semantic model registry's contents: model name, dimensions,
+    /// size on disk, download state, and default status. The `bool` is
+    /// whether `--json-output` was also given, resolved after the full
+    /// command line has been parsed, so flag order doesn't matter. This
+    /// corresponds to the `--semantic-list-models` flag.
+    ListModels(bool),
+    /// Print summary statistics (chunk count, embedding dimensions, file
+    /// size) about a semantic index previously written by
+    /// `--semantic-export`. The `bool` is whether `--json-output` was also
+    /// given, resolved after the full command line has been parsed, so
+    /// flag order doesn't matter. This corresponds to the
+    /// `--semantic-index-stats FILE` flag.
+    SemanticIndexStats(PathBuf, bool),
+    /// Compact a semantic index previously written by `--semantic-export`,
+    /// dropping chunks whose source file no longer exists on disk, and
+    /// report how many chunks and bytes were reclaimed. The `bool` is
+    /// whether `--json-output` was also given, resolved after the full
+    /// command line has been parsed, so flag order doesn't matter. This
+    /// corresponds to the `--semantic-gc FILE` flag.
+    SemanticGc(PathBuf, bool),
 }
 
 /// The overall mode that ripgrep should operate in.
@@ -435,6 +628,48 @@ impl Default for CaseMode {
     }
 }
 
+/// Whether a search, analysis, or semantic index should be scoped to test
+/// code, production code, or everything.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TestScope {
+    /// No scoping; both test and production files are included.
+    All,
+    /// Only files detected as tests are included.
+    TestsOnly,
+    /// Files detected as tests are excluded.
+    NoTests,
+}
+
+impl Default for TestScope {
+    fn default() -> TestScope {
+        TestScope::All
+    }
+}
+
+/// Which `CodeMetrics` field `--analyze` sorts its per-file output by, and
+/// (via `--analyze-min`) gates it on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AnalyzeSortField {
+    /// Sort by file path (the default, i.e. walk order).
+    Path,
+    /// Sort by lines of code.
+    Loc,
+    /// Sort by cyclomatic complexity.
+    Complexity,
+    /// Sort by cognitive complexity.
+    CognitiveComplexity,
+    /// Sort by maximum decision-construct nesting depth.
+    NestingDepth,
+    /// Sort by the longest function's line count.
+    FunctionLength,
+}
+
+impl Default for AnalyzeSortField {
+    fn default() -> AnalyzeSortField {
+        AnalyzeSortField::Path
+    }
+}
+
 /// Indicates whether ripgrep should include color/hyperlinks in its output.
 ///
 /// The default is `Auto`.
@@ -483,8 +718,10 @@ pub(crate) enum ContextMode {
     Passthru,
     /// Only show a certain number of lines before and after each match.
     Limited(ContextModeLimited),
-    /// Show the entire enclosing symbol (function, class, etc.) around each match.
-    EnclosingSymbol,
+    /// Show the entire enclosing symbol (function, class, etc.) around each
+    /// match, padded by the given number of lines before/after the symbol
+    /// (e.g. to capture attributes or doc comments sitting just outside it).
+    EnclosingSymbol(ContextModeLimited),
 }
 
 impl Default for ContextMode {
@@ -512,14 +749,13 @@ impl ContextMode {
                 ref mut before,
                 ..
             }) => *before = Some(lines),
-            ContextMode::EnclosingSymbol => {
-                // Convert to Limited mode when setting specific before context
-                *self = ContextMode::Limited(ContextModeLimited {
-                    before: Some(lines),
-                    after: None,
-                    both: None,
-                });
-            }
+            // `--before-context` combines with `--enclosing-symbol` as
+            // padding lines shown before the symbol, rather than switching
+            // back to plain limited context.
+            ContextMode::EnclosingSymbol(ContextModeLimited {
+                ref mut before,
+                ..
+            }) => *before = Some(lines),
         }
     }
 
@@ -540,14 +776,13 @@ impl ContextMode {
             ContextMode::Limited(ContextModeLimited {
                 ref mut after, ..
             }) => *after = Some(lines),
-            ContextMode::EnclosingSymbol => {
-                // Convert to Limited mode when setting specific after context
-                *self = ContextMode::Limited(ContextModeLimited {
-                    before: None,
-                    after: Some(lines),
-                    both: None,
-                });
-            }
+            // `--after-context` combines with `--enclosing-symbol` as
+            // padding lines shown after the symbol, rather than switching
+            // back to plain limited context.
+            ContextMode::EnclosingSymbol(ContextModeLimited {
+                ref mut after,
+                ..
+            }) => *after = Some(lines),
         }
     }
 
@@ -568,14 +803,16 @@ impl ContextMode {
             ContextMode::Limited(ContextModeLimited {
                 ref mut both, ..
             }) => *both = Some(lines),
-            ContextMode::EnclosingSymbol => {
-                // Convert to Limited mode when setting specific both context
-                *self = ContextMode::Limited(ContextModeLimited {
-                    before: None,
-                    after: None,
-                    both: Some(lines),
-                });
-            }
+            // `--context` combines with `--enclosing-symbol` as padding
+            // lines shown on both sides of the symbol, rather than
+            // switching back to plain limited context. This is what makes
+            // `--enclosing-symbol --context 3` work: it pads the printed
+            // symbol with 3 lines on each side to catch things like
+            // attributes or doc comments sitting just outside it.
+            ContextMode::EnclosingSymbol(ContextModeLimited {
+                ref mut both,
+                ..
+            }) => *both = Some(lines),
         }
     }
 
@@ -586,11 +823,21 @@ impl ContextMode {
         match *self {
             ContextMode::Passthru => unreachable!("context mode is passthru"),
             ContextMode::Limited(ref limited) => limited.get(),
-            ContextMode::EnclosingSymbol => {
+            ContextMode::EnclosingSymbol(_) => {
                 unreachable!("context mode is enclosing symbol")
             }
         }
     }
+
+    /// Returns the padding (lines before, lines after) that
+    /// `--enclosing-symbol` should add around each printed symbol. Returns
+    /// `None` if this mode isn't `EnclosingSymbol`.
+    pub(crate) fn enclosing_symbol_padding(&self) -> Option<(usize, usize)> {
+        match *self {
+            ContextMode::EnclosingSymbol(ref padding) => Some(padding.get()),
+            _ => None,
+        }
+    }
 }
 
 /// A context mode for a finite number of lines.
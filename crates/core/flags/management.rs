@@ -6,6 +6,7 @@ including global and local configurations with proper templates.
 */
 
 use std::env;
+use std::ffi::OsString;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -18,8 +19,18 @@ use crate::flags::hierarchy::ConfigHierarchy;
 pub struct ConfigTemplates;
 
 impl ConfigTemplates {
+    /// The current template version, bumped whenever a `# ===== ... =====`
+    /// section is added to [`Self::GLOBAL_TEMPLATE`] or
+    /// [`Self::LOCAL_TEMPLATE`].
+    ///
+    /// `ConfigManager::init_global_config`/`init_local_config` write this
+    /// into the `outgrep-config-template-version` marker comment so that a
+    /// later `--merge` can tell which sections a given config file predates.
+    pub const TEMPLATE_VERSION: u32 = 1;
+
     /// Global configuration template with examples and documentation
     pub const GLOBAL_TEMPLATE: &'static str = r#"# Global outgrep configuration
+# outgrep-config-template-version: 1
 # This file contains default settings for outgrep across all projects
 # Uncomment and modify any setting to customize your search behavior
 # Priority: CLI flags > Local config > Global config
@@ -242,6 +253,7 @@ impl ConfigTemplates {
 
     /// Local/project configuration template
     pub const LOCAL_TEMPLATE: &'static str = r#"# Project-specific outgrep configuration
+# outgrep-config-template-version: 1
 # These settings override global defaults for this project
 
 # Project-specific search settings
@@ -273,18 +285,88 @@ impl ConfigTemplates {
 "#;
 }
 
+/// The prefix of the marker comment templates use to record which
+/// `ConfigTemplates::TEMPLATE_VERSION` they were generated from.
+const TEMPLATE_VERSION_PREFIX: &str = "# outgrep-config-template-version:";
+
+/// Parse the `outgrep-config-template-version` marker out of `content`,
+/// defaulting to `0` for files written before the marker existed.
+fn template_version(content: &str) -> u32 {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix(TEMPLATE_VERSION_PREFIX))
+        .and_then(|rest| rest.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Replace (or insert, if absent) the `outgrep-config-template-version`
+/// marker line in `content` with `version`.
+fn set_template_version(content: &str, version: u32) -> String {
+    let marker = format!("{TEMPLATE_VERSION_PREFIX} {version}");
+    if content.lines().any(|line| line.starts_with(TEMPLATE_VERSION_PREFIX)) {
+        content
+            .lines()
+            .map(|line| {
+                if line.starts_with(TEMPLATE_VERSION_PREFIX) {
+                    marker.clone()
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        // Pre-marker files get the marker inserted right after the title
+        // comment on the first line.
+        let mut lines = content.lines();
+        let title = lines.next().unwrap_or_default();
+        let rest: Vec<&str> = lines.collect();
+        format!("{title}\n{marker}\n{}", rest.join("\n"))
+    }
+}
+
+/// Split `template` into `(heading, section)` pairs, one per
+/// `# ===== NAME =====` heading. The preamble before the first heading is
+/// not included, since `--merge` only ever appends whole sections.
+fn template_sections(template: &str) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in template.lines() {
+        if line.starts_with("# =====") && line.trim_end().ends_with("=====") {
+            if let Some(section) = current.take() {
+                sections.push(section);
+            }
+            current = Some((line.to_string(), String::new()));
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    if let Some(section) = current.take() {
+        sections.push(section);
+    }
+    sections
+        .into_iter()
+        .map(|(heading, body)| (heading.clone(), format!("{heading}\n{body}")))
+        .collect()
+}
+
 /// Configuration management operations
 pub struct ConfigManager;
 
 impl ConfigManager {
     /// Initialize global configuration file
-    pub fn init_global_config(force: bool) -> Result<PathBuf> {
+    pub fn init_global_config(force: bool, merge: bool) -> Result<PathBuf> {
         let config_path = ConfigHierarchy::default_global_config_path()?;
 
+        if merge && config_path.exists() {
+            return Self::merge_config(&config_path, ConfigTemplates::GLOBAL_TEMPLATE);
+        }
+
         // Check if config already exists
         if config_path.exists() && !force {
             anyhow::bail!(
-                "Global config file already exists at: {}\nUse --force to overwrite",
+                "Global config file already exists at: {}\nUse --force to overwrite or --merge to add new sections",
                 config_path.display()
             );
         }
@@ -312,13 +394,17 @@ impl ConfigManager {
     }
 
     /// Initialize local configuration file
-    pub fn init_local_config(force: bool) -> Result<PathBuf> {
+    pub fn init_local_config(force: bool, merge: bool) -> Result<PathBuf> {
         let config_path = ConfigHierarchy::default_local_config_path()?;
 
+        if merge && config_path.exists() {
+            return Self::merge_config(&config_path, ConfigTemplates::LOCAL_TEMPLATE);
+        }
+
         // Check if config already exists
         if config_path.exists() && !force {
             anyhow::bail!(
-                "Local config file already exists at: {}\nUse --force to overwrite",
+                "Local config file already exists at: {}\nUse --force to overwrite or --merge to add new sections",
                 config_path.display()
             );
         }
@@ -345,8 +431,51 @@ impl ConfigManager {
         Ok(config_path)
     }
 
-    /// Open global configuration file in editor
-    pub fn open_global_config() -> Result<()> {
+    /// Bring an existing config file up to date with `template` without
+    /// touching anything the user has already written.
+    ///
+    /// Any `# ===== NAME =====` section present in `template` but missing
+    /// from the file (identified by its heading, tracked since the file was
+    /// created via the `outgrep-config-template-version` marker) is appended
+    /// to the end of the file, and the marker is bumped to
+    /// `ConfigTemplates::TEMPLATE_VERSION`. Existing lines, including ones
+    /// the user has uncommented or edited, are never touched. If the file is
+    /// already at the current template version, it's returned unchanged.
+    fn merge_config(config_path: &Path, template: &str) -> Result<PathBuf> {
+        let existing = fs::read_to_string(config_path).with_context(|| {
+            format!("Failed to read config file: {}", config_path.display())
+        })?;
+
+        if template_version(&existing) >= ConfigTemplates::TEMPLATE_VERSION {
+            return Ok(config_path.to_path_buf());
+        }
+
+        let mut merged = existing.clone();
+        for (heading, section) in template_sections(template) {
+            if !existing.contains(&heading) {
+                if !merged.ends_with('\n') {
+                    merged.push('\n');
+                }
+                merged.push('\n');
+                merged.push_str(&section);
+            }
+        }
+        merged = set_template_version(&merged, ConfigTemplates::TEMPLATE_VERSION);
+
+        fs::write(config_path, merged).with_context(|| {
+            format!(
+                "Failed to write merged config file: {}",
+                config_path.display()
+            )
+        })?;
+
+        Ok(config_path.to_path_buf())
+    }
+
+    /// Open global configuration file in editor. `editor` is the
+    /// `--editor` override, if any; it takes priority over environment
+    /// detection.
+    pub fn open_global_config(editor: Option<&str>) -> Result<()> {
         let config_paths = ConfigHierarchy::global_config_paths();
 
         // Find existing config file
@@ -368,11 +497,12 @@ impl ConfigManager {
             );
         }
 
-        Self::open_file_in_editor(&config_path)
+        Self::open_file_in_editor(&config_path, editor)
     }
 
-    /// Open local configuration file in editor
-    pub fn open_local_config() -> Result<()> {
+    /// Open local configuration file in editor. See `open_global_config`
+    /// for what `editor` means.
+    pub fn open_local_config(editor: Option<&str>) -> Result<()> {
         let config_path = ConfigHierarchy::default_local_config_path()?;
 
         // If config doesn't exist, offer to create it
@@ -383,7 +513,7 @@ impl ConfigManager {
             );
         }
 
-        Self::open_file_in_editor(&config_path)
+        Self::open_file_in_editor(&config_path, editor)
     }
 
     /// Show current configuration status
@@ -437,9 +567,72 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Print the fully merged configuration, with each argument annotated
+    /// by the file it came from (following any `include` directives).
+    ///
+    /// This is useful for organizations that maintain a shared team config
+    /// via `include`/`--config-extra`: it makes it possible to see exactly
+    /// which file contributed which setting without manually tracing the
+    /// include chain by hand.
+    pub fn dump_config(extra: &[PathBuf]) -> Result<()> {
+        let hierarchy = ConfigHierarchy::load()?;
+        let mut sources: Vec<(PathBuf, OsString)> = Vec::new();
+
+        if let Some(global) = &hierarchy.global_config {
+            sources.extend(ConfigHierarchy::parse_config_file_with_provenance(
+                &global.path,
+            )?);
+        }
+        if let Some(local) = &hierarchy.local_config {
+            sources.extend(ConfigHierarchy::parse_config_file_with_provenance(
+                &local.path,
+            )?);
+        }
+        for path in extra {
+            sources.extend(
+                ConfigHierarchy::parse_config_file_with_provenance(path)
+                    .with_context(|| {
+                        format!(
+                            "Failed to parse --config-extra file: {}",
+                            path.display()
+                        )
+                    })?,
+            );
+        }
+
+        if sources.is_empty() {
+            println!("No configuration files found.");
+            return Ok(());
+        }
+
+        println!("Merged configuration (lowest to highest priority):");
+        println!("====================================================");
+        for (path, arg) in &sources {
+            println!("{:<40} {}", arg.to_string_lossy(), path.display());
+        }
+
+        Ok(())
+    }
+
     /// Detect and launch file in user's preferred editor
-    fn open_file_in_editor(file_path: &Path) -> Result<()> {
-        let (editor_cmd, editor_args) = Self::detect_editor()?;
+    fn open_file_in_editor(
+        file_path: &Path,
+        editor_override: Option<&str>,
+    ) -> Result<()> {
+        let (editor_cmd, mut editor_args) =
+            Self::detect_editor(editor_override)?;
+
+        // GUI editors (VS Code, JetBrains IDEs, Sublime Text) normally fork
+        // and return immediately, so `command.status()` below would report
+        // success before the user has actually edited anything. Append
+        // their wait flag automatically, but only when the editor command
+        // didn't already come with explicit arguments -- a user-supplied
+        // `--editor`/`EDITOR` value is assumed to already be correct.
+        if editor_args.is_empty() {
+            if let Some(wait_flag) = Self::editor_wait_flag(&editor_cmd) {
+                editor_args.push(wait_flag.to_string());
+            }
+        }
 
         let mut command = Command::new(&editor_cmd);
 
@@ -471,30 +664,44 @@ impl ConfigManager {
         Ok(())
     }
 
-    /// Detect user's preferred editor, returning (command, args)
-    fn detect_editor() -> Result<(PathBuf, Vec<String>)> {
-        // Check EDITOR environment variable first
-        if let Ok(editor) = env::var("EDITOR") {
-            // Parse the editor command which might include arguments
-            let parts: Vec<&str> = editor.split_whitespace().collect();
-            if parts.is_empty() {
-                anyhow::bail!("EDITOR environment variable is empty");
-            }
+    /// Detect user's preferred editor, returning (command, args).
+    ///
+    /// Resolution order: the `--editor`/config `editor = "..."` override (if
+    /// given), then the `VISUAL` environment variable, then `EDITOR`, then a
+    /// platform-specific fallback list.
+    fn detect_editor(
+        editor_override: Option<&str>,
+    ) -> Result<(PathBuf, Vec<String>)> {
+        if let Some(editor) = editor_override {
+            return Self::parse_editor_command(editor, "editor");
+        }
 
-            let cmd = PathBuf::from(parts[0]);
-            let args = parts[1..].iter().map(|s| s.to_string()).collect();
-            return Ok((cmd, args));
+        // `VISUAL` takes priority over `EDITOR` by long-standing Unix
+        // convention: `EDITOR` is meant for line editors usable from
+        // anywhere (e.g. inside `crontab -e`), `VISUAL` for full-screen
+        // ones that need a real terminal or GUI.
+        if let Ok(editor) = env::var("VISUAL") {
+            return Self::parse_editor_command(&editor, "VISUAL");
+        }
+        if let Ok(editor) = env::var("EDITOR") {
+            return Self::parse_editor_command(&editor, "EDITOR");
         }
 
         // Platform-specific fallbacks
         #[cfg(target_os = "windows")]
-        let candidates = &["notepad.exe", "code.exe", "notepad++.exe"];
+        let candidates = &[
+            "notepad.exe",
+            "code.exe",
+            "notepad++.exe",
+            "idea64.exe",
+            "pycharm64.exe",
+        ];
 
         #[cfg(target_os = "macos")]
-        let candidates = &["nano", "vim", "vi", "open"];
+        let candidates = &["nano", "vim", "vi", "code", "idea", "open"];
 
         #[cfg(all(unix, not(target_os = "macos")))]
-        let candidates = &["nano", "vim", "vi", "gedit"];
+        let candidates = &["nano", "vim", "vi", "code", "idea", "gedit"];
 
         for editor in candidates {
             if which::which(editor).is_ok() {
@@ -507,6 +714,35 @@ impl ConfigManager {
              Example: export EDITOR=nano"
         );
     }
+
+    /// Split an editor command string (from `--editor`, `VISUAL`, or
+    /// `EDITOR`) such as `"code --wait"` into a command and its arguments.
+    /// `source` names where the value came from, for the error message.
+    fn parse_editor_command(
+        editor: &str,
+        source: &str,
+    ) -> Result<(PathBuf, Vec<String>)> {
+        let parts: Vec<&str> = editor.split_whitespace().collect();
+        if parts.is_empty() {
+            anyhow::bail!("{} editor setting is empty", source);
+        }
+        let cmd = PathBuf::from(parts[0]);
+        let args = parts[1..].iter().map(|s| s.to_string()).collect();
+        Ok((cmd, args))
+    }
+
+    /// Return the flag that makes `editor_cmd` block until the file is
+    /// closed, for editors known to otherwise fork into the background.
+    fn editor_wait_flag(editor_cmd: &Path) -> Option<&'static str> {
+        let name = editor_cmd.file_stem().and_then(|s| s.to_str())?;
+        match name.to_ascii_lowercase().as_str() {
+            "code" | "code-insiders" | "codium" | "subl" | "sublime_text"
+            | "atom" | "idea" | "idea64" | "pycharm" | "pycharm64"
+            | "webstorm" | "clion" | "rustrover" | "goland" | "rider"
+            | "phpstorm" | "rubymine" | "appcode" => Some("--wait"),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -566,7 +802,7 @@ mod tests {
     fn test_editor_detection() {
         // This test might fail in CI environments without editors
         // So we just test that it either finds an editor or fails gracefully
-        match ConfigManager::detect_editor() {
+        match ConfigManager::detect_editor(None) {
             Ok((editor_path, _args)) => {
                 assert!(!editor_path.as_os_str().is_empty());
             }
@@ -575,4 +811,87 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_editor_override_takes_priority() {
+        let (cmd, args) =
+            ConfigManager::detect_editor(Some("code --wait")).unwrap();
+        assert_eq!(PathBuf::from("code"), cmd);
+        assert_eq!(vec!["--wait".to_string()], args);
+    }
+
+    #[test]
+    fn test_editor_wait_flag_known_editors() {
+        assert_eq!(
+            Some("--wait"),
+            ConfigManager::editor_wait_flag(&PathBuf::from("code"))
+        );
+        assert_eq!(
+            Some("--wait"),
+            ConfigManager::editor_wait_flag(&PathBuf::from("idea64.exe"))
+        );
+        assert_eq!(
+            None,
+            ConfigManager::editor_wait_flag(&PathBuf::from("vim"))
+        );
+    }
+
+    #[test]
+    fn test_template_version_defaults_to_zero_without_marker() {
+        assert_eq!(0, template_version("# Some old config\n--smart-case\n"));
+    }
+
+    #[test]
+    fn test_template_version_reads_marker() {
+        let content = "# Global outgrep configuration\n# outgrep-config-template-version: 3\n";
+        assert_eq!(3, template_version(content));
+    }
+
+    #[test]
+    fn test_set_template_version_inserts_marker_when_absent() {
+        let content = "# Title\n--smart-case\n";
+        let updated = set_template_version(content, 1);
+        assert_eq!(1, template_version(&updated));
+        assert!(updated.contains("--smart-case"));
+    }
+
+    #[test]
+    fn test_set_template_version_replaces_existing_marker() {
+        let content = "# Title\n# outgrep-config-template-version: 1\n--smart-case\n";
+        let updated = set_template_version(content, 2);
+        assert_eq!(2, template_version(&updated));
+        assert!(updated.contains("--smart-case"));
+    }
+
+    #[test]
+    fn test_merge_config_appends_missing_section_and_keeps_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config");
+
+        // Simulate a config written before the current template version,
+        // with a user edit (uncommented flag) that must survive the merge.
+        let stale = "# Global outgrep configuration\n# outgrep-config-template-version: 0\n\n# ===== SEARCH OPTIONS =====\n--smart-case\n";
+        fs::write(&config_path, stale).unwrap();
+
+        ConfigManager::merge_config(&config_path, ConfigTemplates::GLOBAL_TEMPLATE)
+            .unwrap();
+
+        let merged = fs::read_to_string(&config_path).unwrap();
+        assert!(merged.contains("--smart-case"));
+        assert!(merged.contains("# ===== SEMANTIC SEARCH ====="));
+        assert_eq!(ConfigTemplates::TEMPLATE_VERSION, template_version(&merged));
+    }
+
+    #[test]
+    fn test_merge_config_is_noop_when_already_current() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config");
+        fs::write(&config_path, ConfigTemplates::GLOBAL_TEMPLATE).unwrap();
+
+        ConfigManager::merge_config(&config_path, ConfigTemplates::GLOBAL_TEMPLATE)
+            .unwrap();
+
+        let merged = fs::read_to_string(&config_path).unwrap();
+        assert_eq!(ConfigTemplates::GLOBAL_TEMPLATE, merged);
+    }
 }
@@ -35,7 +35,10 @@ pub(crate) use crate::flags::{
         },
     },
     hiargs::HiArgs,
-    lowargs::{GenerateMode, Mode, SearchMode, SpecialMode},
+    lowargs::{
+        AnalyzeSortField, GenerateMode, Mode, SearchMode, SpecialMode,
+        TestScope,
+    },
     management::ConfigManager,
     parse::{parse, ParseResult},
 };
@@ -44,7 +47,7 @@ mod complete;
 mod config;
 mod defs;
 mod doc;
-mod hierarchy;
+pub(crate) mod hierarchy;
 mod hiargs;
 mod lowargs;
 mod management;
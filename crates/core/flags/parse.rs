@@ -48,9 +48,10 @@ impl<T> ParseResult<T> {
 /// Parse CLI arguments and convert then to their high level representation.
 pub(crate) fn parse() -> ParseResult<HiArgs> {
     parse_low().and_then(|low| {
-        // Special handling for analyze, watch, or diff mode to bypass pattern validation
-        if low.analyze || low.watch || low.diff {
-            // Create a minimal HiArgs for analyze/watch mode
+        // Special handling for analyze, watch, diff, or vscode-ipc mode to
+        // bypass pattern validation
+        if low.analyze || low.watch || low.diff || low.vscode_ipc {
+            // Create a minimal HiArgs for analyze/watch/vscode-ipc mode
             let low_with_pattern = LowArgs {
                 patterns: vec![crate::flags::lowargs::PatternSource::Regexp(".*".to_string())],
                 ..low
@@ -97,6 +98,47 @@ fn parse_low() -> ParseResult<LowArgs> {
     // if a special mode was enabled. This is basically only for version and
     // help output which shouldn't be impacted by extra configuration.
     if let Some(special) = low.special.take() {
+        // --merge can appear before or after --init-global-config/
+        // --init-local-config on the command line; resolve it here, now
+        // that the full command line has been parsed, so flag order
+        // doesn't matter.
+        let special = match special {
+            SpecialMode::InitGlobalConfig(_) => {
+                SpecialMode::InitGlobalConfig(low.config_merge)
+            }
+            SpecialMode::InitLocalConfig(_) => {
+                SpecialMode::InitLocalConfig(low.config_merge)
+            }
+            // --editor can likewise appear before or after
+            // --open-global-config/--open-local-config.
+            SpecialMode::OpenGlobalConfig(_) => {
+                SpecialMode::OpenGlobalConfig(low.editor.clone())
+            }
+            SpecialMode::OpenLocalConfig(_) => {
+                SpecialMode::OpenLocalConfig(low.editor.clone())
+            }
+            // --config-extra can likewise appear before or after
+            // --config-dump.
+            SpecialMode::ConfigDump(_) => {
+                SpecialMode::ConfigDump(low.config_extra.clone())
+            }
+            // --json-output can likewise appear before or after
+            // --semantic-list-models.
+            SpecialMode::ListModels(_) => {
+                SpecialMode::ListModels(low.json_output)
+            }
+            // --json-output can likewise appear before or after
+            // --semantic-index-stats.
+            SpecialMode::SemanticIndexStats(path, _) => {
+                SpecialMode::SemanticIndexStats(path, low.json_output)
+            }
+            // --json-output can likewise appear before or after
+            // --semantic-gc.
+            SpecialMode::SemanticGc(path, _) => {
+                SpecialMode::SemanticGc(path, low.json_output)
+            }
+            other => other,
+        };
         return ParseResult::Special(special);
     }
     // If the end user says no config, then respect it.
@@ -107,7 +149,7 @@ fn parse_low() -> ParseResult<LowArgs> {
     // Look for arguments from a config file. If we got nothing (whether the
     // file is empty or RIPGREP_CONFIG_PATH wasn't set), then we don't need
     // to re-parse.
-    let config_args = crate::flags::config::args();
+    let config_args = crate::flags::config::args(&low.config_extra);
     if config_args.is_empty() {
         log::debug!("no extra arguments found from configuration file");
         return ParseResult::Ok(low);
@@ -157,4 +157,22 @@ impl Haystack {
     fn is_file(&self) -> bool {
         self.dent.file_type().map_or(false, |ft| ft.is_file())
     }
+
+    /// Returns true if and only if this haystack is explicitly neither a
+    /// regular file, a directory nor a symlink -- e.g., a named pipe (FIFO)
+    /// created via shell process substitution or `mkfifo`, a Unix domain
+    /// socket, or a device file.
+    ///
+    /// Such haystacks are only ever searched when they're explicit (see
+    /// `is_explicit`), since non-regular files are filtered out of ordinary
+    /// directory walks. Once explicit, they still need to be streamed
+    /// through a plain reader rather than `search_path`'s file-based code
+    /// path, since they aren't seekable and can't be memory mapped.
+    pub(crate) fn is_non_regular_file(&self) -> bool {
+        let ft = match self.dent.file_type() {
+            None => return false,
+            Some(ft) => ft,
+        };
+        !ft.is_file() && !ft.is_dir() && !ft.is_symlink()
+    }
 }
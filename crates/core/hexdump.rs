@@ -0,0 +1,109 @@
+/*!
+Hex+ASCII dump rendering for binary matches, for `--hex`.
+
+Normally a match found in a binary file (with `--binary` enabled) is printed
+the same way a text match would be: the raw bytes of the "line" it falls in,
+which is usually mangled or unprintable garbage on a terminal. `--hex`
+instead renders a fixed-size window of bytes around each match as a classic
+hex+ASCII dump, so the surrounding binary structure is legible instead of
+raw bytes hitting the terminal.
+*/
+
+use std::fmt::Write as _;
+
+/// Number of bytes shown per row of a hex dump.
+const BYTES_PER_ROW: usize = 16;
+
+/// One match found directly in a file's raw bytes, in `--hex` mode.
+#[derive(Debug, Clone)]
+pub(crate) struct HexMatch {
+    /// Byte offset of the start of the match within the file.
+    pub(crate) start: usize,
+    /// Byte offset of the end of the match within the file.
+    pub(crate) end: usize,
+    /// The rendered hex+ASCII dump of the context window around the match.
+    pub(crate) dump: String,
+}
+
+/// Render a hex+ASCII dump of the bytes in `data` surrounding the byte range
+/// `[start, end)`, extended by `context` bytes on each side and rounded
+/// outward to whole rows so the dump always begins and ends on a
+/// `BYTES_PER_ROW`-byte boundary.
+pub(crate) fn dump_window(
+    data: &[u8],
+    start: usize,
+    end: usize,
+    context: usize,
+) -> String {
+    let window_start =
+        start.saturating_sub(context) / BYTES_PER_ROW * BYTES_PER_ROW;
+    let window_end = usize::min(
+        data.len(),
+        (end + context).div_ceil(BYTES_PER_ROW) * BYTES_PER_ROW,
+    );
+
+    let mut out = String::new();
+    for row_start in (window_start..window_end).step_by(BYTES_PER_ROW) {
+        let row_end = usize::min(row_start + BYTES_PER_ROW, window_end);
+        let row = &data[row_start..row_end];
+
+        write!(out, "{:08x}  ", row_start).unwrap();
+        for i in 0..BYTES_PER_ROW {
+            if i < row.len() {
+                let offset = row_start + i;
+                if offset >= start && offset < end {
+                    write!(out, "[{:02x}]", row[i]).unwrap();
+                } else {
+                    write!(out, " {:02x} ", row[i]).unwrap();
+                }
+            } else {
+                write!(out, "    ").unwrap();
+            }
+            if i == BYTES_PER_ROW / 2 - 1 {
+                out.push(' ');
+            }
+        }
+        out.push(' ');
+        for &byte in row {
+            let ch = if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            };
+            out.push(ch);
+        }
+        out.push('\n');
+    }
+    // Drop the trailing newline; callers print one line at a time.
+    out.pop();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_window_marks_the_match_bytes() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+        let dump = dump_window(data, 4, 9, 4);
+        assert!(dump.contains("[71]")); // 'q'
+        assert!(dump.contains("quick"));
+    }
+
+    #[test]
+    fn dump_window_clamps_to_data_bounds() {
+        let data = b"short";
+        let dump = dump_window(data, 0, 5, 100);
+        assert!(dump.starts_with("00000000"));
+        assert!(dump.contains("short"));
+    }
+
+    #[test]
+    fn dump_window_rounds_to_row_boundaries() {
+        let data = &[0u8; 40][..];
+        let dump = dump_window(data, 20, 21, 0);
+        let first_offset = &dump[..8];
+        assert_eq!(first_offset, "00000010");
+    }
+}
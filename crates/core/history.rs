@@ -0,0 +1,230 @@
+/*!
+Implements `og`'s local usage history and the `--usage-summary` report that
+reads it back.
+
+Every completed search appends one entry (pattern, search directories,
+duration) to a JSON-lines log under `~/.config/outgrep`. `--usage-summary`
+reads that log back and prints the user's own top patterns, most-searched
+directories, and average query time, so that frequent searches can be
+promoted into config presets or aliases. Nothing here is ever transmitted
+anywhere; the log never leaves the machine it was written on.
+*/
+
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::catalog::Message;
+use crate::flags::HiArgs;
+
+/// One completed search, as recorded in the local history log.
+///
+/// This is intentionally minimal: just enough to summarize usage patterns,
+/// without recording match contents or file names.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct HistoryEntry {
+    pattern: String,
+    dirs: Vec<String>,
+    duration_ms: u64,
+    timestamp_secs: u64,
+}
+
+/// Returns the path to the local history log, creating its parent directory
+/// if it doesn't already exist.
+///
+/// TODO: the log is never pruned or rotated, so a long-lived install will
+/// accumulate an ever-growing file. Revisit once there's a `--usage-clear`
+/// (or similar) to go with it.
+fn history_path() -> anyhow::Result<PathBuf> {
+    let home_dir =
+        dirs::home_dir().context("could not determine home directory")?;
+    let dir = home_dir.join(".config").join("outgrep");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+    Ok(dir.join("history.jsonl"))
+}
+
+/// Append one entry to the local history log.
+///
+/// Failures are logged and otherwise ignored: a failure to record usage
+/// history should never cause a search to fail.
+pub(crate) fn record(args: &HiArgs, elapsed: Duration) {
+    let Some(pattern) = args.first_pattern() else { return };
+    let entry = HistoryEntry {
+        pattern: pattern.to_string(),
+        dirs: args
+            .search_paths()
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect(),
+        duration_ms: elapsed.as_millis() as u64,
+        timestamp_secs: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    if let Err(err) = append(&entry) {
+        log::warn!("failed to record usage history: {err:#}");
+    }
+}
+
+fn append(entry: &HistoryEntry) -> anyhow::Result<()> {
+    let path = history_path()?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    serde_json::to_writer(&mut file, entry)
+        .context("failed to serialize history entry")?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Read every entry from the local history log.
+///
+/// Malformed lines are skipped rather than treated as a hard error, since a
+/// single corrupted entry (e.g. from a process killed mid-write) shouldn't
+/// make the rest of a user's history unreadable.
+fn load_all() -> anyhow::Result<Vec<HistoryEntry>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = std::fs::File::open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut entries = Vec::new();
+    for line in io::BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<HistoryEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => log::warn!("skipping malformed history entry: {err}"),
+        }
+    }
+    Ok(entries)
+}
+
+/// A summary of the user's own local search history.
+pub(crate) struct UsageSummary {
+    total_searches: usize,
+    top_patterns: Vec<(String, usize)>,
+    top_dirs: Vec<(String, usize)>,
+    average_duration_ms: f64,
+}
+
+/// The number of entries shown per ranked list in the report.
+const TOP_N: usize = 10;
+
+fn summarize(entries: &[HistoryEntry]) -> UsageSummary {
+    let mut pattern_counts: HashMap<&str, usize> = HashMap::new();
+    let mut dir_counts: HashMap<&str, usize> = HashMap::new();
+    let mut total_duration_ms: u64 = 0;
+
+    for entry in entries {
+        *pattern_counts.entry(entry.pattern.as_str()).or_insert(0) += 1;
+        for dir in &entry.dirs {
+            *dir_counts.entry(dir.as_str()).or_insert(0) += 1;
+        }
+        total_duration_ms += entry.duration_ms;
+    }
+
+    let rank = |counts: HashMap<&str, usize>| -> Vec<(String, usize)> {
+        let mut ranked: Vec<(String, usize)> =
+            counts.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(TOP_N);
+        ranked
+    };
+
+    let average_duration_ms = if entries.is_empty() {
+        0.0
+    } else {
+        total_duration_ms as f64 / entries.len() as f64
+    };
+
+    UsageSummary {
+        total_searches: entries.len(),
+        top_patterns: rank(pattern_counts),
+        top_dirs: rank(dir_counts),
+        average_duration_ms,
+    }
+}
+
+/// Load the local history log and summarize it.
+pub(crate) fn build_summary() -> anyhow::Result<UsageSummary> {
+    Ok(summarize(&load_all()?))
+}
+
+/// Print a usage summary report to stdout.
+pub(crate) fn print_summary(summary: &UsageSummary) {
+    println!("{}", Message::UsageSummaryTitle.text());
+    println!("======================");
+    println!("Total searches recorded: {}", summary.total_searches);
+    println!("Average search time: {:.1}ms", summary.average_duration_ms);
+    println!();
+    if summary.total_searches == 0 {
+        println!("{}", Message::UsageSummaryEmpty.text());
+        return;
+    }
+    println!("Top patterns:");
+    for (pattern, count) in &summary.top_patterns {
+        println!("  {count:>6}  {pattern}");
+    }
+    println!();
+    println!("Most searched directories:");
+    for (dir, count) in &summary.top_dirs {
+        println!("  {count:>6}  {dir}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pattern: &str, dirs: &[&str], duration_ms: u64) -> HistoryEntry {
+        HistoryEntry {
+            pattern: pattern.to_string(),
+            dirs: dirs.iter().map(|d| d.to_string()).collect(),
+            duration_ms,
+            timestamp_secs: 0,
+        }
+    }
+
+    #[test]
+    fn summarize_empty_history_has_no_averages() {
+        let summary = summarize(&[]);
+        assert_eq!(0, summary.total_searches);
+        assert_eq!(0.0, summary.average_duration_ms);
+        assert!(summary.top_patterns.is_empty());
+        assert!(summary.top_dirs.is_empty());
+    }
+
+    #[test]
+    fn summarize_ranks_patterns_and_dirs_by_frequency() {
+        let entries = vec![
+            entry("TODO", &["src"], 10),
+            entry("TODO", &["src"], 20),
+            entry("FIXME", &["tests"], 30),
+        ];
+        let summary = summarize(&entries);
+        assert_eq!(3, summary.total_searches);
+        assert_eq!(20.0, summary.average_duration_ms);
+        assert_eq!(
+            vec![("TODO".to_string(), 2), ("FIXME".to_string(), 1)],
+            summary.top_patterns
+        );
+        assert_eq!(
+            vec![("src".to_string(), 2), ("tests".to_string(), 1)],
+            summary.top_dirs
+        );
+    }
+}
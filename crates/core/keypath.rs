@@ -0,0 +1,232 @@
+/*!
+Structured key-path search over JSON and YAML documents, for `--jsonpath` and
+`--yamlpath`.
+
+Normally the search pattern is matched against lines of text. These two flags
+instead parse the whole file as structured data, walk it according to a
+dotted path expression (e.g. `dependencies.*.version`), and match the pattern
+against each selected leaf value on its own. Results are reported at
+key-path granularity (the dotted path a value was found at) rather than a
+line number, since a single line of pretty-printed JSON/YAML rarely
+corresponds to one logical value and a single logical value can span many
+lines.
+*/
+
+use std::fmt;
+
+/// Which structured format a [`KeyPathQuery`] parses its input as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyPathFormat {
+    Json,
+    Yaml,
+}
+
+impl fmt::Display for KeyPathFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyPathFormat::Json => write!(f, "JSON"),
+            KeyPathFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// One segment of a parsed `--jsonpath`/`--yamlpath` selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    /// A literal object key, or (if it parses as an integer) an array index.
+    Key(String),
+    /// `*`, matching any single object key or array index at this depth.
+    Wildcard,
+}
+
+/// A parsed `--jsonpath`/`--yamlpath` selector, e.g. `dependencies.*.version`.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyPathQuery {
+    format: KeyPathFormat,
+    segments: Vec<PathSegment>,
+}
+
+impl KeyPathQuery {
+    /// Parse `expr` as a dot-separated key path, where `*` matches any key
+    /// or index at that depth.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `expr` is empty.
+    pub(crate) fn parse(
+        format: KeyPathFormat,
+        expr: &str,
+    ) -> anyhow::Result<KeyPathQuery> {
+        if expr.is_empty() {
+            anyhow::bail!("key path expression cannot be empty");
+        }
+        let segments = expr
+            .split('.')
+            .map(|s| {
+                if s == "*" {
+                    PathSegment::Wildcard
+                } else {
+                    PathSegment::Key(s.to_string())
+                }
+            })
+            .collect();
+        Ok(KeyPathQuery { format, segments })
+    }
+
+    /// Parse `content` according to this query's format and return every
+    /// leaf value whose concrete key path matches the selector, alongside
+    /// the dotted path string it was found at.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` isn't valid JSON/YAML for the
+    /// configured format.
+    pub(crate) fn select(
+        &self,
+        content: &str,
+    ) -> anyhow::Result<Vec<(String, serde_json::Value)>> {
+        let root: serde_json::Value = match self.format {
+            KeyPathFormat::Json => serde_json::from_str(content)?,
+            KeyPathFormat::Yaml => serde_yaml::from_str(content)?,
+        };
+        let mut out = Vec::new();
+        walk(&root, &self.segments, String::new(), &mut out);
+        Ok(out)
+    }
+}
+
+/// Recursively descend `value` following `segments`, appending
+/// `(dotted_path, leaf_value)` to `out` once `segments` is exhausted.
+///
+/// A leaf reached with unconsumed segments remaining (e.g. the selector asks
+/// for `a.b` but `a` is a string, not an object) simply has nothing to
+/// recurse into and is dropped rather than reported.
+fn walk(
+    value: &serde_json::Value,
+    segments: &[PathSegment],
+    path: String,
+    out: &mut Vec<(String, serde_json::Value)>,
+) {
+    let Some((head, rest)) = segments.split_first() else {
+        out.push((path, value.clone()));
+        return;
+    };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let matched = match head {
+                    PathSegment::Wildcard => true,
+                    PathSegment::Key(k) => k == key,
+                };
+                if matched {
+                    walk(child, rest, join(&path, key), out);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                let matched = match head {
+                    PathSegment::Wildcard => true,
+                    PathSegment::Key(k) => {
+                        k.parse::<usize>().map_or(false, |idx| idx == i)
+                    }
+                };
+                if matched {
+                    walk(child, rest, join(&path, &i.to_string()), out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Append `segment` to the dotted path built up so far.
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+/// Render a JSON leaf value the way `--jsonpath`/`--yamlpath` match text
+/// against it: strings unquoted, everything else in its JSON text form.
+pub(crate) fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_wildcard_path_from_json() {
+        let query =
+            KeyPathQuery::parse(KeyPathFormat::Json, "dependencies.*.version")
+                .unwrap();
+        let content = r#"{
+            "dependencies": {
+                "foo": {"version": "0.1.0"},
+                "bar": {"version": "1.2.3"}
+            }
+        }"#;
+        let mut selected = query.select(content).unwrap();
+        selected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            selected,
+            vec![
+                (
+                    "dependencies.bar.version".to_string(),
+                    serde_json::json!("1.2.3")
+                ),
+                (
+                    "dependencies.foo.version".to_string(),
+                    serde_json::json!("0.1.0")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_from_yaml() {
+        let query =
+            KeyPathQuery::parse(KeyPathFormat::Yaml, "dependencies.*.version")
+                .unwrap();
+        let content = "dependencies:\n  foo:\n    version: 0.1.0\n";
+        let selected = query.select(content).unwrap();
+        assert_eq!(
+            selected,
+            vec![(
+                "dependencies.foo.version".to_string(),
+                serde_json::json!("0.1.0")
+            )]
+        );
+    }
+
+    #[test]
+    fn selects_array_index_by_position() {
+        let query =
+            KeyPathQuery::parse(KeyPathFormat::Json, "items.0.name").unwrap();
+        let content = r#"{"items": [{"name": "first"}, {"name": "second"}]}"#;
+        let selected = query.select(content).unwrap();
+        assert_eq!(
+            selected,
+            vec![("items.0.name".to_string(), serde_json::json!("first"))]
+        );
+    }
+
+    #[test]
+    fn empty_expression_is_rejected() {
+        assert!(KeyPathQuery::parse(KeyPathFormat::Json, "").is_err());
+    }
+
+    #[test]
+    fn value_text_unquotes_strings() {
+        assert_eq!(value_text(&serde_json::json!("0.1.0")), "0.1.0");
+        assert_eq!(value_text(&serde_json::json!(true)), "true");
+        assert_eq!(value_text(&serde_json::json!(42)), "42");
+    }
+}
@@ -8,11 +8,32 @@ Exposes all Outgrep functionality for integration with external applications lik
 #[macro_use]
 mod messages;
 
+mod astpattern;
+mod catalog;
+mod definition;
+mod delimited;
 pub mod diagnostics;
+mod doctor;
+mod duplicates;
 pub mod flags;
 pub mod haystack;
+mod hexdump;
+mod history;
+mod keypath;
+mod lintrules;
 mod logger;
+mod logtime;
+mod nodekind;
+mod remote;
+mod rewrite;
 pub mod search;
+mod symbols;
+mod symbolsearch;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+mod throttle;
+mod tsquery;
+pub mod vfs;
 
 // Re-export AST functionality from workspace crates
 pub use outgrep_ast_config as ast_config;
@@ -21,11 +42,16 @@ pub use outgrep_ast_language as ast_language;
 pub use outgrep_ast_lsp as ast_lsp;
 
 // Re-export diagnostics types
+#[cfg(feature = "watch")]
+pub use crate::diagnostics::FileWatcher;
 pub use crate::diagnostics::{
-    FileWatcher, GitAnalyzer, MetricsCalculator, TreeBuilder, TreeDisplay,
+    GitAnalyzer, MetricsCalculator, TreeBuilder, TreeDisplay,
     TreeDisplayOptions,
 };
 
+// Re-export the virtual filesystem abstraction
+pub use crate::vfs::{MemFs, RealFs, Vfs};
+
 // Note: Core search, flags, and haystack types are available through their modules
 // Individual types are pub(crate) and can't be re-exported, but modules provide full access
 
@@ -0,0 +1,197 @@
+/*!
+YAML rule files for structural lint rules, for `--rules`.
+
+Rule files use the same schema as ast-grep's own `sgconfig.yml` rules
+(a `rule` tree of structural matchers, plus `id`/`message`/`severity`), since
+the matching engine underneath is the vendored ast-grep core already used by
+`--pattern` and `--rewrite`. This lets teams encode project-specific lint
+rules as data instead of writing a bespoke linter, and report violations
+through the same output machinery as any other search mode.
+*/
+
+use std::path::{Path, PathBuf};
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_core::Language;
+use outgrep_ast_language::SupportLang;
+
+/// One structural lint rule loaded from a YAML rule file, ready to match
+/// against source parsed as its `language`.
+struct LintRule {
+    config: outgrep_ast_config::RuleConfig<SupportLang>,
+}
+
+/// A single violation of a [`LintRule`], reported at the AST node it
+/// matched.
+#[derive(Debug, Clone)]
+pub(crate) struct LintViolation {
+    pub(crate) rule_id: String,
+    pub(crate) severity: String,
+    pub(crate) message: String,
+    /// 1-based line the violation starts on.
+    pub(crate) line: usize,
+    /// 1-based, character-based column the violation starts on.
+    pub(crate) column: usize,
+    /// The exact source text of the matched node.
+    pub(crate) text: String,
+}
+
+/// A collection of [`LintRule`]s loaded from one or more YAML files, for
+/// `--rules`.
+pub(crate) struct LintRuleSet {
+    rules: Vec<LintRule>,
+}
+
+impl LintRuleSet {
+    /// Load every `*.yml`/`*.yaml` rule file under `path`, or `path` itself
+    /// if it names a single file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` can't be read, no rule files are found,
+    /// or any file's YAML doesn't parse as a valid ast-grep style rule.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<LintRuleSet> {
+        let mut files = Vec::new();
+        collect_yaml_files(path, &mut files)?;
+        if files.is_empty() {
+            anyhow::bail!(
+                "--rules: no YAML rule files found at {}",
+                path.display()
+            );
+        }
+
+        let globals = outgrep_ast_config::GlobalRules::default();
+        let mut rules = Vec::new();
+        for file in &files {
+            let yaml = std::fs::read_to_string(file)?;
+            let configs = outgrep_ast_config::from_yaml_string::<SupportLang>(
+                &yaml, &globals,
+            )
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "invalid --rules file {}: {}",
+                    file.display(),
+                    e
+                )
+            })?;
+            rules
+                .extend(configs.into_iter().map(|config| LintRule { config }));
+        }
+        Ok(LintRuleSet { rules })
+    }
+
+    /// Run every loaded rule whose `language` matches `path`'s extension
+    /// against `content`, in rule-file order.
+    pub(crate) fn check(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Vec<LintViolation> {
+        let mut violations = Vec::new();
+        let Some(lang) = SupportLang::from_path(path) else {
+            return violations;
+        };
+        let root = lang.ast_grep(content);
+        for rule in &self.rules {
+            if rule.config.language != lang {
+                continue;
+            }
+            for node_match in root.root().find_all(&rule.config.matcher) {
+                let message = rule.config.get_message(&node_match);
+                let pos = node_match.start_pos();
+                violations.push(LintViolation {
+                    rule_id: rule.config.id.clone(),
+                    severity: format!("{:?}", rule.config.severity)
+                        .to_lowercase(),
+                    message,
+                    line: pos.line() + 1,
+                    column: pos.column(&node_match) + 1,
+                    text: node_match.text().into_owned(),
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Recursively collect `.yml`/`.yaml` files under `path` into `out`, or add
+/// `path` itself if it's already a file.
+fn collect_yaml_files(
+    path: &Path,
+    out: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let metadata = std::fs::metadata(path)?;
+    if metadata.is_file() {
+        out.push(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        if entry_path.is_dir() {
+            collect_yaml_files(&entry_path, out)?;
+            continue;
+        }
+        let is_yaml = matches!(
+            entry_path.extension().and_then(|e| e.to_str()),
+            Some("yml") | Some("yaml")
+        );
+        if is_yaml {
+            out.push(entry_path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_rule_file(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_and_matches_a_single_rule() {
+        let dir = std::env::temp_dir()
+            .join(format!("outgrep-lintrules-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_rule_file(
+            &dir,
+            "no-unwrap.yml",
+            "id: no-unwrap\nlanguage: Rust\nmessage: avoid unwrap()\nseverity: warning\nrule:\n  pattern: $X.unwrap()\n",
+        );
+
+        let rules = LintRuleSet::load(&dir).unwrap();
+        let content = "fn f() {\n    let x = foo().unwrap();\n}\n";
+        let violations = rules.check(Path::new("f.rs"), content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "no-unwrap");
+        assert_eq!(violations[0].severity, "warning");
+        assert_eq!(violations[0].message, "avoid unwrap()");
+        assert_eq!(violations[0].line, 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn no_match_returns_no_violations() {
+        let dir = std::env::temp_dir().join(format!(
+            "outgrep-lintrules-test-nomatch-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        write_rule_file(
+            &dir,
+            "no-todo.yml",
+            "id: no-todo\nlanguage: Rust\nmessage: no TODOs\nrule:\n  kind: line_comment\n  regex: TODO\n",
+        );
+
+        let rules = LintRuleSet::load(&dir).unwrap();
+        let content = "fn f() {}\n";
+        assert!(rules.check(Path::new("f.rs"), content).is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,296 @@
+/*!
+Lightweight timestamp parsing for `--since`/`--until`.
+
+This intentionally doesn't pull in a calendar crate: `--since`/`--until` only
+need to parse a timestamp prefix off a log line and compare it against a
+flag value, so a small hand-rolled parser covering ISO 8601 and syslog-style
+timestamps is enough, and it keeps the dependency list unchanged.
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A moment in time, stored as seconds since the Unix epoch.
+///
+/// This is not a general-purpose calendar type. It supports exactly what
+/// `--since`/`--until` need: parsing a prefix off a line, parsing a flag
+/// value, and ordering the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct LogTimestamp(i64);
+
+impl LogTimestamp {
+    /// Parse a `--since`/`--until` flag value.
+    ///
+    /// Accepts anything [`LogTimestamp::parse_prefix`] recognizes (so the
+    /// same value that appears in the logs can be pasted in directly), plus
+    /// a bare `YYYY-MM-DD` date, which is treated as midnight UTC.
+    pub(crate) fn parse_flag_value(s: &str) -> Result<LogTimestamp, String> {
+        let bytes = s.trim().as_bytes();
+        if let Some((ts, rest)) = Self::parse_prefix(bytes) {
+            if rest.is_empty() {
+                return Ok(ts);
+            }
+        }
+        if let Some((y, m, d, rest)) = parse_ymd(bytes) {
+            if rest.is_empty() {
+                if let Some(epoch_day) = days_from_civil(y, m, d) {
+                    return Ok(LogTimestamp(epoch_day * 86_400));
+                }
+            }
+        }
+        Err(format!(
+            "invalid timestamp '{s}': expected ISO 8601 (e.g. \
+             2024-01-02T15:04:05Z), a bare date (e.g. 2024-01-02), or a \
+             syslog timestamp (e.g. 'Jan  2 15:04:05')"
+        ))
+    }
+
+    /// Try to parse a timestamp from the start of `line`, returning it along
+    /// with whatever bytes of `line` weren't consumed. Returns `None` if
+    /// `line` doesn't begin with a timestamp this function recognizes.
+    ///
+    /// Recognizes ISO 8601 (`2024-01-02T15:04:05`, optionally with
+    /// fractional seconds and a `Z` or `+HH:MM`/`-HH:MM` offset, and with
+    /// either `T` or a space separating the date and time) and syslog-style
+    /// timestamps (`Jan  2 15:04:05`). A syslog timestamp has no year, so
+    /// it's resolved against the current year; this can misattribute
+    /// entries logged right around a year boundary, the same ambiguity
+    /// tools like `journalctl` accept when reading year-less syslog output.
+    pub(crate) fn parse_prefix(line: &[u8]) -> Option<(LogTimestamp, &[u8])> {
+        parse_iso8601_prefix(line).or_else(|| parse_syslog_prefix(line))
+    }
+}
+
+fn parse_iso8601_prefix(b: &[u8]) -> Option<(LogTimestamp, &[u8])> {
+    let (year, month, day, rest) = parse_ymd(b)?;
+    let rest = rest.strip_prefix(b"T").or_else(|| rest.strip_prefix(b" "))?;
+    let (hour, rest) = read_fixed_digits(rest, 2)?;
+    let rest = rest.strip_prefix(b":")?;
+    let (minute, rest) = read_fixed_digits(rest, 2)?;
+    let rest = rest.strip_prefix(b":")?;
+    let (second, mut rest) = read_fixed_digits(rest, 2)?;
+
+    // Optional fractional seconds, e.g. `.123456`.
+    if let Some(after_dot) = rest.strip_prefix(b".") {
+        let digits =
+            after_dot.iter().take_while(|b| b.is_ascii_digit()).count();
+        rest = &after_dot[digits..];
+    }
+
+    // Optional offset: `Z`, or `+HH:MM`/`-HH:MM`.
+    let mut offset_secs: i64 = 0;
+    if let Some(after_z) = rest.strip_prefix(b"Z") {
+        rest = after_z;
+    } else if let Some(sign_byte) = rest.first().copied() {
+        if sign_byte == b'+' || sign_byte == b'-' {
+            let (off_hour, after_hour) = read_fixed_digits(&rest[1..], 2)?;
+            let after_colon = after_hour.strip_prefix(b":")?;
+            let (off_minute, after_minute) =
+                read_fixed_digits(after_colon, 2)?;
+            let sign: i64 = if sign_byte == b'+' { 1 } else { -1 };
+            offset_secs =
+                sign * (off_hour as i64 * 3600 + off_minute as i64 * 60);
+            rest = after_minute;
+        }
+    }
+
+    let epoch_day = days_from_civil(year, month, day)?;
+    let seconds = epoch_day * 86_400
+        + hour as i64 * 3600
+        + minute as i64 * 60
+        + second as i64
+        - offset_secs;
+    Some((LogTimestamp(seconds), rest))
+}
+
+const SYSLOG_MONTHS: [&[u8; 3]; 12] = [
+    b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep",
+    b"Oct", b"Nov", b"Dec",
+];
+
+fn parse_syslog_prefix(b: &[u8]) -> Option<(LogTimestamp, &[u8])> {
+    if b.len() < 6 {
+        return None;
+    }
+    let month = SYSLOG_MONTHS
+        .iter()
+        .position(|name| &b[..3] == *name)
+        .map(|i| (i + 1) as u32)?;
+    let rest = b[3..].strip_prefix(b" ")?;
+
+    // Day is space-padded (" 2") or zero-padded ("02"), always two bytes.
+    if rest.len() < 2 {
+        return None;
+    }
+    let day_bytes = &rest[..2];
+    let day = if day_bytes[0] == b' ' {
+        if !day_bytes[1].is_ascii_digit() {
+            return None;
+        }
+        (day_bytes[1] - b'0') as u32
+    } else {
+        if !day_bytes[0].is_ascii_digit() || !day_bytes[1].is_ascii_digit() {
+            return None;
+        }
+        (day_bytes[0] - b'0') as u32 * 10 + (day_bytes[1] - b'0') as u32
+    };
+
+    let rest = rest[2..].strip_prefix(b" ")?;
+    let (hour, rest) = read_fixed_digits(rest, 2)?;
+    let rest = rest.strip_prefix(b":")?;
+    let (minute, rest) = read_fixed_digits(rest, 2)?;
+    let rest = rest.strip_prefix(b":")?;
+    let (second, rest) = read_fixed_digits(rest, 2)?;
+
+    let year = current_year();
+    let epoch_day = days_from_civil(year, month, day)?;
+    let seconds = epoch_day * 86_400
+        + hour as i64 * 3600
+        + minute as i64 * 60
+        + second as i64;
+    Some((LogTimestamp(seconds), rest))
+}
+
+/// Parse a `YYYY-MM-DD` prefix, returning the year, month, day and the
+/// unconsumed remainder.
+fn parse_ymd(b: &[u8]) -> Option<(i64, u32, u32, &[u8])> {
+    let (year, rest) = read_fixed_digits(b, 4)?;
+    let rest = rest.strip_prefix(b"-")?;
+    let (month, rest) = read_fixed_digits(rest, 2)?;
+    let rest = rest.strip_prefix(b"-")?;
+    let (day, rest) = read_fixed_digits(rest, 2)?;
+    Some((year as i64, month, day, rest))
+}
+
+/// Read exactly `width` ASCII digits from the start of `b`, returning the
+/// parsed value and the remaining bytes.
+fn read_fixed_digits(b: &[u8], width: usize) -> Option<(u32, &[u8])> {
+    if b.len() < width || !b[..width].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let mut value = 0u32;
+    for &digit in &b[..width] {
+        value = value * 10 + (digit - b'0') as u32;
+    }
+    Some((value, &b[width..]))
+}
+
+/// Days since the Unix epoch (1970-01-01) for the given civil date.
+///
+/// This is Howard Hinnant's well-known `days_from_civil` algorithm, valid
+/// for all years representable in an `i64` (including proleptic Gregorian
+/// dates before 1970, which come out negative).
+fn days_from_civil(y: i64, m: u32, d: u32) -> Option<i64> {
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i64;
+    let mp = ((m as i64 + 9) % 12) as i64;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+/// The current UTC year, used to resolve year-less syslog timestamps.
+fn current_year() -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    civil_year_from_days(now.div_euclid(86_400))
+}
+
+/// Inverse of [`days_from_civil`]: the year containing the given day count
+/// since the Unix epoch. Only the year is needed here, so the month/day
+/// components of Hinnant's `civil_from_days` are left unused.
+fn civil_year_from_days(z: i64) -> i64 {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    if mp >= 10 {
+        y + 1
+    } else {
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso8601_with_t_separator_and_z() {
+        let (ts, rest) =
+            LogTimestamp::parse_prefix(b"2024-01-02T15:04:05Z rest").unwrap();
+        assert_eq!(rest, b" rest");
+        let (same, _) =
+            LogTimestamp::parse_prefix(b"2024-01-02T15:04:05Z").unwrap();
+        assert_eq!(ts, same);
+    }
+
+    #[test]
+    fn parses_iso8601_with_space_separator_and_offset() {
+        let (utc, _) =
+            LogTimestamp::parse_prefix(b"2024-01-02T16:04:05Z").unwrap();
+        let (offset, _) =
+            LogTimestamp::parse_prefix(b"2024-01-02 15:04:05-01:00 x")
+                .unwrap();
+        assert_eq!(utc, offset);
+    }
+
+    #[test]
+    fn parses_iso8601_with_fractional_seconds() {
+        let (a, rest) =
+            LogTimestamp::parse_prefix(b"2024-01-02T15:04:05.123456Z tail")
+                .unwrap();
+        let (b, _) =
+            LogTimestamp::parse_prefix(b"2024-01-02T15:04:05Z").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(rest, b" tail");
+    }
+
+    #[test]
+    fn parses_syslog_timestamp_with_space_padded_day() {
+        let (ts, rest) =
+            LogTimestamp::parse_prefix(b"Jan  2 15:04:05 host myapp: hi")
+                .unwrap();
+        assert_eq!(rest, b" host myapp: hi");
+        let year = current_year();
+        let expected =
+            LogTimestamp::parse_flag_value(&format!("{year}-01-02T15:04:05Z"))
+                .unwrap();
+        assert_eq!(ts, expected);
+    }
+
+    #[test]
+    fn rejects_lines_without_a_recognized_timestamp() {
+        assert_eq!(None, LogTimestamp::parse_prefix(b"not a timestamp"));
+    }
+
+    #[test]
+    fn flag_value_accepts_bare_date() {
+        let ts = LogTimestamp::parse_flag_value("2024-01-02").unwrap();
+        let midnight =
+            LogTimestamp::parse_flag_value("2024-01-02T00:00:00Z").unwrap();
+        assert_eq!(ts, midnight);
+    }
+
+    #[test]
+    fn flag_value_rejects_garbage() {
+        assert!(LogTimestamp::parse_flag_value("not-a-date").is_err());
+    }
+
+    #[test]
+    fn ordering_reflects_chronology() {
+        let earlier =
+            LogTimestamp::parse_flag_value("2024-01-01T00:00:00Z").unwrap();
+        let later =
+            LogTimestamp::parse_flag_value("2024-01-02T00:00:00Z").unwrap();
+        assert!(earlier < later);
+    }
+}
@@ -6,17 +6,42 @@ use std::{io::Write, process::ExitCode};
 
 use ignore::WalkState;
 
-use crate::flags::{HiArgs, SearchMode};
+use crate::flags::{AnalyzeSortField, HiArgs, SearchMode};
 
 #[macro_use]
 mod messages;
 
+mod catalog;
 mod flags;
 mod haystack;
 mod logger;
+mod remote;
 mod search;
+mod vfs;
 
+mod astmultiline;
+mod astpattern;
+mod definition;
+mod delimited;
 mod diagnostics;
+mod doctor;
+mod duplicates;
+mod hexdump;
+mod history;
+mod keypath;
+mod lintrules;
+mod logtime;
+mod nodekind;
+mod plugins;
+mod references;
+mod rewrite;
+mod signature_search;
+mod symbols;
+mod symbolsearch;
+mod throttle;
+mod tsquery;
+mod vscode_ipc;
+mod wasm_plugin;
 
 // Since Rust no longer uses jemalloc by default, ripgrep will, by default,
 // use the system allocator. On Linux, this would normally be glibc's
@@ -84,18 +109,58 @@ fn run(result: crate::flags::ParseResult<HiArgs>) -> anyhow::Result<ExitCode> {
         ParseResult::Special(mode) => return special(mode),
         ParseResult::Ok(args) => args,
     };
-    let matched = if args.analyze() && args.watch() {
+    if args.throttled() {
+        crate::throttle::Throttle::lower_process_priority();
+    }
+    let matched = if args.filetype_stats() {
+        return filetype_stats(&args);
+    } else if args.analyze() && args.watch() {
         return tokio::runtime::Runtime::new()?.block_on(analyze_and_watch(&args));
     } else if args.watch() {
         return tokio::runtime::Runtime::new()?.block_on(watch(&args));
+    } else if args.vscode_ipc() {
+        return tokio::runtime::Runtime::new()?
+            .block_on(vscode_ipc::run(&args));
+    } else if args.tail() {
+        let crate::flags::Mode::Search(mode) = args.mode() else {
+            anyhow::bail!("--tail can only be used with a search mode");
+        };
+        return tokio::runtime::Runtime::new()?
+            .block_on(tail_follow(&args, mode));
     } else if args.tree() || args.analyze() || args.diff() || args.diagnostics() || args.syntax() {
         // Unified tree backbone for all analysis modes
         return tokio::runtime::Runtime::new()?.block_on(unified_tree_mode(&args));
+    } else if args.symbols() {
+        symbols::run(&args)?
+    } else if let Some(ident) = args.definition() {
+        definition::run(&args, ident)?
+    } else if let Some(ident) = args.references() {
+        references::run(&args, ident)?
+    } else if let Some(query) = args.signature() {
+        signature_search::run(&args, query)?
+    } else if args.find_duplicates() {
+        duplicates::run(&args)?
     } else {
         match args.mode() {
             Mode::Search(_) if !args.matches_possible() => false,
-            Mode::Search(mode) if args.threads() == 1 => search(&args, mode)?,
-            Mode::Search(mode) => search_parallel(&args, mode)?,
+            Mode::Search(mode) if args.threads() == 1 => {
+                let started_at = std::time::Instant::now();
+                let matched = search(&args, mode)?;
+                history::record(&args, started_at.elapsed());
+                matched
+            }
+            Mode::Search(mode) if args.sort_parallel_enabled() => {
+                let started_at = std::time::Instant::now();
+                let matched = search_parallel_sorted(&args, mode)?;
+                history::record(&args, started_at.elapsed());
+                matched
+            }
+            Mode::Search(mode) => {
+                let started_at = std::time::Instant::now();
+                let matched = search_parallel(&args, mode)?;
+                history::record(&args, started_at.elapsed());
+                matched
+            }
             Mode::Files if args.threads() == 1 => files(&args)?,
             Mode::Files => files_parallel(&args)?,
             Mode::Types => return types(&args),
@@ -151,12 +216,26 @@ fn search(args: &HiArgs, mode: SearchMode) -> anyhow::Result<bool> {
             break;
         }
     }
+    // `--semantic-top-k` collects matches instead of printing them as each
+    // file is searched, so the ranked top `k` are only printed once every
+    // haystack has been visited.
+    searcher.finish_semantic_top_k()?;
+    // `--rewrite --write` likewise only prints its summary of files/edits
+    // written once every haystack has been visited.
+    searcher.finish_rewrite_summary()?;
     if args.has_implicit_path() && !searched {
         eprint_nothing_searched();
     }
     if let Some(ref stats) = stats {
         let wtr = searcher.printer().get_mut();
-        let _ = print_stats(mode, stats, started_at, wtr);
+        let _ = print_stats(
+            mode,
+            stats,
+            started_at,
+            args.colors(),
+            args.deterministic(),
+            wtr,
+        );
     }
     Ok(matched)
 }
@@ -174,6 +253,7 @@ fn search_parallel(args: &HiArgs, mode: SearchMode) -> anyhow::Result<bool> {
     let started_at = std::time::Instant::now();
     let haystack_builder = args.haystack_builder();
     let bufwtr = args.buffer_writer();
+    let max_buffer_size = args.max_buffer_size();
     let stats = args.stats().map(std::sync::Mutex::new);
     let matched = AtomicBool::new(false);
     let searched = AtomicBool::new(false);
@@ -181,7 +261,7 @@ fn search_parallel(args: &HiArgs, mode: SearchMode) -> anyhow::Result<bool> {
     let mut searcher = args.search_worker(
         args.matcher()?,
         args.searcher()?,
-        args.printer(mode, bufwtr.buffer()),
+        args.printer(mode, BoundedBuffer::new(&bufwtr, max_buffer_size)),
     )?;
     args.walk_builder()?.build_parallel().run(|| {
         let bufwtr = &bufwtr;
@@ -212,7 +292,8 @@ fn search_parallel(args: &HiArgs, mode: SearchMode) -> anyhow::Result<bool> {
                 let mut stats = locked_stats.lock().unwrap();
                 *stats += search_result.stats().unwrap();
             }
-            if let Err(err) = bufwtr.print(searcher.printer().get_mut()) {
+            let buf = searcher.printer().get_mut().remaining();
+            if let Err(err) = bufwtr.print(buf) {
                 // A broken pipe means graceful termination.
                 if err.kind() == std::io::ErrorKind::BrokenPipe {
                     return WalkState::Quit;
@@ -233,12 +314,355 @@ fn search_parallel(args: &HiArgs, mode: SearchMode) -> anyhow::Result<bool> {
     if let Some(ref locked_stats) = stats {
         let stats = locked_stats.lock().unwrap();
         let mut wtr = searcher.printer().get_mut();
-        let _ = print_stats(mode, &stats, started_at, &mut wtr);
-        let _ = bufwtr.print(&mut wtr);
+        let _ = print_stats(
+            mode,
+            &stats,
+            started_at,
+            args.colors(),
+            args.deterministic(),
+            &mut wtr,
+        );
+        let _ = bufwtr.print(wtr.remaining());
     }
     Ok(matched.load(Ordering::SeqCst))
 }
 
+/// A `termcolor::WriteColor` implementation that wraps a `Buffer` and
+/// flushes it through a shared `BufferWriter` as soon as it grows past
+/// `cap`, so `search_parallel` can't buffer an unbounded amount of memory
+/// for a single file with an enormous number of matches (e.g. minified JS
+/// searched with `--passthru`) before anything is printed.
+///
+/// Each flush goes through `BufferWriter::print`, the same call
+/// `search_parallel` already uses to print each file's buffer under the
+/// writer's internal lock, so overflowing a cap mid-file is indistinguishable
+/// from finishing several small files in a row. `cap` is `u64::MAX` when
+/// `--max-buffer-size` isn't in effect, which makes the size check always
+/// false and this behave identically to writing straight into `buf`.
+#[derive(Clone, Debug)]
+struct BoundedBuffer<'b> {
+    bufwtr: &'b termcolor::BufferWriter,
+    buf: termcolor::Buffer,
+    cap: u64,
+}
+
+impl<'b> BoundedBuffer<'b> {
+    fn new(
+        bufwtr: &'b termcolor::BufferWriter,
+        cap: Option<u64>,
+    ) -> BoundedBuffer<'b> {
+        BoundedBuffer {
+            buf: bufwtr.buffer(),
+            bufwtr,
+            cap: cap.unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Clears any buffered output, for reuse before searching the next file.
+    /// Output already flushed by a prior overflow is unaffected.
+    fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// The output accumulated since the last overflow flush (or since the
+    /// last `clear`, if no overflow happened), for the caller to print via
+    /// the normal per-file `BufferWriter::print` call.
+    fn remaining(&self) -> &termcolor::Buffer {
+        &self.buf
+    }
+
+    fn flush_if_over_cap(&mut self) -> std::io::Result<()> {
+        if (self.buf.len() as u64) < self.cap {
+            return Ok(());
+        }
+        self.bufwtr.print(&self.buf)?;
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+impl<'b> std::io::Write for BoundedBuffer<'b> {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        let n = self.buf.write(data)?;
+        self.flush_if_over_cap()?;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.buf.flush()
+    }
+}
+
+impl<'b> termcolor::WriteColor for BoundedBuffer<'b> {
+    fn supports_color(&self) -> bool {
+        self.buf.supports_color()
+    }
+
+    fn set_color(
+        &mut self,
+        spec: &termcolor::ColorSpec,
+    ) -> std::io::Result<()> {
+        self.buf.set_color(spec)
+    }
+
+    fn reset(&mut self) -> std::io::Result<()> {
+        self.buf.reset()
+    }
+
+    fn is_synchronous(&self) -> bool {
+        self.buf.is_synchronous()
+    }
+}
+
+/// The top-level entry point for multi-threaded search that also honors
+/// ascending `--sort=path`, via `--sort-parallel`.
+///
+/// Unlike `search_parallel`, which lets the directory walker itself drive
+/// parallelism and therefore can't promise any particular output order,
+/// this walks and sorts the full file list up front on a single thread --
+/// the same traversal `search` uses for `--sort=path` -- and then fans the
+/// actual searching out across a worker pool. Each worker tags its result
+/// with the position of the file it searched in the sorted list; the main
+/// thread holds finished results in a reorder buffer and prints them in
+/// order, only printing a file once every file before it has also
+/// finished. That buffer never holds more than `threads()` results at
+/// once, since that's the most files that can be searched ahead of the
+/// next one due to print.
+fn search_parallel_sorted(
+    args: &HiArgs,
+    mode: SearchMode,
+) -> anyhow::Result<bool> {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{mpsc, Mutex};
+
+    let started_at = std::time::Instant::now();
+    let haystack_builder = args.haystack_builder();
+    let bufwtr = args.buffer_writer();
+    let unsorted = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+    let haystacks: Vec<_> = args.sort(unsorted).collect();
+
+    if haystacks.is_empty() {
+        if args.has_implicit_path() {
+            eprint_nothing_searched();
+        }
+        return Ok(false);
+    }
+
+    let searcher = args.search_worker(
+        args.matcher()?,
+        args.searcher()?,
+        args.printer(mode, bufwtr.buffer()),
+    )?;
+
+    let next_index = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let matched = AtomicBool::new(false);
+    let stats = args.stats().map(Mutex::new);
+    let (results_tx, results_rx) =
+        mpsc::channel::<(usize, termcolor::Buffer)>();
+
+    std::thread::scope(|scope| {
+        for _ in 0..args.threads() {
+            let haystacks = &haystacks;
+            let next_index = &next_index;
+            let stop = &stop;
+            let matched = &matched;
+            let stats = &stats;
+            let results_tx = results_tx.clone();
+            let mut searcher = searcher.clone();
+            scope.spawn(move || loop {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(haystack) = haystacks.get(index) else {
+                    break;
+                };
+                searcher.printer().get_mut().clear();
+                let search_result = match searcher.search(haystack) {
+                    Ok(search_result) => search_result,
+                    Err(err) => {
+                        err_message!("{}: {}", haystack.path().display(), err);
+                        let _ = results_tx.send((
+                            index,
+                            searcher.printer().get_mut().clone(),
+                        ));
+                        continue;
+                    }
+                };
+                if search_result.has_match() {
+                    matched.store(true, Ordering::SeqCst);
+                    if args.quit_after_match() {
+                        stop.store(true, Ordering::SeqCst);
+                    }
+                }
+                if let (Some(locked_stats), Some(file_stats)) =
+                    (stats.as_ref(), search_result.stats())
+                {
+                    *locked_stats.lock().unwrap() += file_stats;
+                }
+                let _ = results_tx
+                    .send((index, searcher.printer().get_mut().clone()));
+            });
+        }
+        drop(results_tx);
+
+        let mut pending: HashMap<usize, termcolor::Buffer> = HashMap::new();
+        let mut next_to_print = 0usize;
+        while next_to_print < haystacks.len() {
+            let buf = match pending.remove(&next_to_print) {
+                Some(buf) => buf,
+                None => match results_rx.recv() {
+                    Ok((index, buf)) => {
+                        pending.insert(index, buf);
+                        continue;
+                    }
+                    // Every worker has finished without producing a result
+                    // for `next_to_print`, which only happens once `stop`
+                    // has cut off work early.
+                    Err(_) => break,
+                },
+            };
+            if !buf.as_slice().is_empty() {
+                let _ = bufwtr.print(&buf);
+            }
+            next_to_print += 1;
+        }
+    });
+
+    if let Some(stats) = stats.map(|s| s.into_inner().unwrap()) {
+        let mut wtr = bufwtr.buffer();
+        let _ = print_stats(
+            mode,
+            &stats,
+            started_at,
+            args.colors(),
+            args.deterministic(),
+            &mut wtr,
+        );
+        let _ = bufwtr.print(&wtr);
+    }
+    Ok(matched.load(Ordering::SeqCst))
+}
+
+/// Per-language totals accumulated by `--filetype-stats`.
+#[derive(Default, Clone, Copy)]
+struct FiletypeTotals {
+    files: u64,
+    lines_of_code: u64,
+    comment_lines: u64,
+    blank_lines: u64,
+}
+
+/// The top-level entry point for `--filetype-stats`.
+///
+/// Walks the tree with the same parallel walker search uses (honoring
+/// .gitignore, --hidden, and friends) and counts lines of code, comments,
+/// and blanks per language. Unlike `--analyze`, it never runs AST parsing
+/// or complexity analysis, so it stays fast enough to replace tools like
+/// tokei or cloc on large repositories.
+fn filetype_stats(args: &HiArgs) -> anyhow::Result<ExitCode> {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    let totals: Mutex<HashMap<&'static str, FiletypeTotals>> =
+        Mutex::new(HashMap::new());
+
+    args.walk_builder()?.build_parallel().run(|| {
+        let totals = &totals;
+        Box::new(move |result| {
+            let entry = match result {
+                Ok(entry) => entry,
+                Err(_) => return WalkState::Continue,
+            };
+            if entry.file_type().map_or(true, |ft| !ft.is_file()) {
+                return WalkState::Continue;
+            }
+            let path = entry.path();
+            let is_source = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map_or(false, |ext| {
+                    matches!(
+                        ext,
+                        "rs" | "js"
+                            | "jsx"
+                            | "ts"
+                            | "tsx"
+                            | "py"
+                            | "java"
+                            | "go"
+                            | "c"
+                            | "cpp"
+                            | "cc"
+                            | "cxx"
+                            | "h"
+                            | "hpp"
+                            | "php"
+                            | "rb"
+                            | "cs"
+                            | "swift"
+                    )
+                });
+            if !is_source {
+                return WalkState::Continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                return WalkState::Continue;
+            };
+            let (language, lines_of_code, comment_lines, blank_lines) =
+                crate::diagnostics::MetricsCalculator::calculate_filetype_stats(
+                    path, &content,
+                );
+            let mut totals = totals.lock().unwrap();
+            let stats = totals.entry(language).or_default();
+            stats.files += 1;
+            stats.lines_of_code += lines_of_code;
+            stats.comment_lines += comment_lines;
+            stats.blank_lines += blank_lines;
+            WalkState::Continue
+        })
+    });
+
+    let mut rows: Vec<(&'static str, FiletypeTotals)> =
+        totals.into_inner().unwrap().into_iter().collect();
+    rows.sort_by(|a, b| b.1.lines_of_code.cmp(&a.1.lines_of_code));
+
+    println!(
+        "{:<12} {:>8} {:>10} {:>10} {:>10}",
+        "Language", "Files", "Lines", "Comments", "Blanks"
+    );
+    let mut total = FiletypeTotals::default();
+    for (language, stats) in &rows {
+        println!(
+            "{:<12} {:>8} {:>10} {:>10} {:>10}",
+            language,
+            stats.files,
+            stats.lines_of_code,
+            stats.comment_lines,
+            stats.blank_lines
+        );
+        total.files += stats.files;
+        total.lines_of_code += stats.lines_of_code;
+        total.comment_lines += stats.comment_lines;
+        total.blank_lines += stats.blank_lines;
+    }
+    println!(
+        "{:<12} {:>8} {:>10} {:>10} {:>10}",
+        "Total",
+        total.files,
+        total.lines_of_code,
+        total.comment_lines,
+        total.blank_lines
+    );
+
+    Ok(ExitCode::from(0))
+}
+
 /// The top-level entry point for file listing without searching.
 ///
 /// This recursively steps through the file list (current directory by default)
@@ -291,15 +715,20 @@ fn files_parallel(args: &HiArgs) -> anyhow::Result<bool> {
     let haystack_builder = args.haystack_builder();
     let mut path_printer = args.path_printer_builder().build(args.stdout());
     let matched = AtomicBool::new(false);
-    let (tx, rx) = mpsc::channel::<crate::haystack::Haystack>();
+    // The print thread only ever calls `.path()` on what it receives, so we
+    // send just the path instead of the whole `Haystack` (which also carries
+    // an `ignore::DirEntry`'s file-type cache, depth and error metadata).
+    // That avoids cloning all of that per-entry state across the channel for
+    // data the print thread never looks at.
+    let (tx, rx) = mpsc::channel::<std::path::PathBuf>();
 
     // We spawn a single printing thread to make sure we don't tear writes.
     // We use a channel here under the presumption that it's probably faster
     // than using a mutex in the worker threads below, but this has never been
     // seriously litigated.
     let print_thread = thread::spawn(move || -> std::io::Result<()> {
-        for haystack in rx.iter() {
-            path_printer.write(haystack.path())?;
+        for path in rx.iter() {
+            path_printer.write(&path)?;
         }
         Ok(())
     });
@@ -317,7 +746,7 @@ fn files_parallel(args: &HiArgs) -> anyhow::Result<bool> {
             if args.quit_after_match() {
                 WalkState::Quit
             } else {
-                match tx.send(haystack) {
+                match tx.send(haystack.path().to_path_buf()) {
                     Ok(_) => WalkState::Continue,
                     Err(_) => WalkState::Quit,
                 }
@@ -419,8 +848,17 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
                 }
             }
         }
-        SpecialMode::InitGlobalConfig => {
-            match flags::ConfigManager::init_global_config(false) {
+        SpecialMode::ConfigDump(extra) => {
+            match flags::ConfigManager::dump_config(&extra) {
+                Ok(()) => return Ok(ExitCode::from(0)),
+                Err(e) => {
+                    writeln!(std::io::stderr(), "Error: {}", e)?;
+                    return Ok(ExitCode::from(1));
+                }
+            }
+        }
+        SpecialMode::InitGlobalConfig(merge) => {
+            match flags::ConfigManager::init_global_config(false, merge) {
                 Ok(path) => {
                     writeln!(std::io::stdout(), "Global config created at: {}", path.display())?;
                     return Ok(ExitCode::from(0));
@@ -431,8 +869,8 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
                 }
             }
         }
-        SpecialMode::InitLocalConfig => {
-            match flags::ConfigManager::init_local_config(false) {
+        SpecialMode::InitLocalConfig(merge) => {
+            match flags::ConfigManager::init_local_config(false, merge) {
                 Ok(path) => {
                     writeln!(std::io::stdout(), "Local config created at: {}", path.display())?;
                     return Ok(ExitCode::from(0));
@@ -443,8 +881,8 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
                 }
             }
         }
-        SpecialMode::OpenGlobalConfig => {
-            match flags::ConfigManager::open_global_config() {
+        SpecialMode::OpenGlobalConfig(editor) => {
+            match flags::ConfigManager::open_global_config(editor.as_deref()) {
                 Ok(()) => return Ok(ExitCode::from(0)),
                 Err(e) => {
                     writeln!(std::io::stderr(), "Error: {}", e)?;
@@ -452,8 +890,8 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
                 }
             }
         }
-        SpecialMode::OpenLocalConfig => {
-            match flags::ConfigManager::open_local_config() {
+        SpecialMode::OpenLocalConfig(editor) => {
+            match flags::ConfigManager::open_local_config(editor.as_deref()) {
                 Ok(()) => return Ok(ExitCode::from(0)),
                 Err(e) => {
                     writeln!(std::io::stderr(), "Error: {}", e)?;
@@ -461,6 +899,94 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
                 }
             }
         }
+        SpecialMode::DownloadModel(name) => {
+            use grep::searcher::semantic::ModelManager;
+
+            let result = ModelManager::create_downloader(None)
+                .and_then(|downloader| downloader.download_model(&name));
+            match result {
+                Ok(path) => {
+                    writeln!(
+                        std::io::stdout(),
+                        "Downloaded model {} to: {}",
+                        name,
+                        path.display()
+                    )?;
+                    return Ok(ExitCode::from(0));
+                }
+                Err(e) => {
+                    writeln!(std::io::stderr(), "Error: {}", e)?;
+                    return Ok(ExitCode::from(1));
+                }
+            }
+        }
+        SpecialMode::Doctor => {
+            let checks = doctor::run_checks();
+            let healthy = doctor::print_report(&checks);
+            return Ok(ExitCode::from(if healthy { 0 } else { 1 }));
+        }
+        SpecialMode::UsageSummary => {
+            let summary = history::build_summary()?;
+            history::print_summary(&summary);
+            return Ok(ExitCode::from(0));
+        }
+        SpecialMode::ListModels(json) => {
+            use grep::searcher::semantic::ModelManager;
+
+            let downloader = match ModelManager::create_downloader(None) {
+                Ok(downloader) => downloader,
+                Err(e) => {
+                    writeln!(std::io::stderr(), "Error: {}", e)?;
+                    return Ok(ExitCode::from(1));
+                }
+            };
+            let report = downloader.model_report();
+            if json {
+                serde_json::to_writer_pretty(std::io::stdout(), &report)?;
+                writeln!(std::io::stdout())?;
+            } else {
+                ModelManager::print_model_report(&report);
+            }
+            return Ok(ExitCode::from(0));
+        }
+        SpecialMode::SemanticIndexStats(path, json) => {
+            use grep::searcher::semantic::{index_stats, print_index_stats};
+
+            let stats = match index_stats(&path) {
+                Ok(stats) => stats,
+                Err(e) => {
+                    writeln!(std::io::stderr(), "Error: {}", e)?;
+                    return Ok(ExitCode::from(1));
+                }
+            };
+            if json {
+                serde_json::to_writer_pretty(std::io::stdout(), &stats)?;
+                writeln!(std::io::stdout())?;
+            } else {
+                print_index_stats(&path, &stats);
+            }
+            return Ok(ExitCode::from(0));
+        }
+        SpecialMode::SemanticGc(path, json) => {
+            use grep::searcher::semantic::{
+                gc_index, print_gc_report, SemanticConfig,
+            };
+
+            let report = match gc_index(&path, &SemanticConfig::default()) {
+                Ok(report) => report,
+                Err(e) => {
+                    writeln!(std::io::stderr(), "Error: {}", e)?;
+                    return Ok(ExitCode::from(1));
+                }
+            };
+            if json {
+                serde_json::to_writer_pretty(std::io::stdout(), &report)?;
+                writeln!(std::io::stdout())?;
+            } else {
+                print_gc_report(&path, &report);
+            }
+            return Ok(ExitCode::from(0));
+        }
     };
     writeln!(std::io::stdout(), "{}", output.trim_end())?;
     Ok(exit)
@@ -475,11 +1001,7 @@ fn special(mode: crate::flags::SpecialMode) -> anyhow::Result<ExitCode> {
 /// explicit path to search. This is because the message can otherwise be
 /// noisy, e.g., when it is intended that there is nothing to search.
 fn eprint_nothing_searched() {
-    err_message!(
-        "No files were searched, which means ripgrep probably \
-         applied a filter you didn't expect.\n\
-         Running with --debug will show why files are being skipped."
-    );
+    err_message!("{}", catalog::Message::NothingSearched.text());
 }
 
 /// Prints the statistics given to the writer given.
@@ -494,13 +1016,22 @@ fn eprint_nothing_searched() {
 /// whether stats fail to print or not generally shouldn't cause ripgrep to
 /// enter into an "error" state. And usually the only way for this to fail is
 /// if writing to stdout itself fails.
-fn print_stats<W: Write>(
+fn print_stats<W: termcolor::WriteColor>(
     mode: SearchMode,
     stats: &grep::printer::Stats,
     started: std::time::Instant,
+    colors: &grep::printer::ColorSpecs,
+    deterministic: bool,
     mut wtr: W,
 ) -> std::io::Result<()> {
-    let elapsed = std::time::Instant::now().duration_since(started);
+    // `--deterministic` zeroes out wall-clock timing so `--stats` output is
+    // reproducible across runs, e.g. for golden-file tests asserting on the
+    // whole summary rather than filtering timing lines out first.
+    let elapsed = if deterministic {
+        std::time::Duration::ZERO
+    } else {
+        std::time::Instant::now().duration_since(started)
+    };
     if matches!(mode, SearchMode::JSON) {
         // We specifically match the format laid out by the JSON printer in
         // the grep-printer crate. We simply "extend" it with the 'summary'
@@ -521,27 +1052,181 @@ fn print_stats<W: Write>(
         )?;
         write!(wtr, "\n")
     } else {
-        write!(
-            wtr,
-            "
-{matches} matches
-{lines} matched lines
-{searches_with_match} files contained matches
-{searches} files searched
-{bytes_printed} bytes printed
-{bytes_searched} bytes searched
-{search_time:0.6} seconds spent searching
-{process_time:0.6} seconds
-",
-            matches = stats.matches(),
-            lines = stats.matched_lines(),
-            searches_with_match = stats.searches_with_match(),
-            searches = stats.searches(),
-            bytes_printed = stats.bytes_printed(),
-            bytes_searched = stats.bytes_searched(),
-            search_time = stats.elapsed().as_secs_f64(),
-            process_time = elapsed.as_secs_f64(),
-        )
+        write_stat_line(&mut wtr, colors, stats.matches(), "matches")?;
+        write_stat_line(
+            &mut wtr,
+            colors,
+            stats.matched_lines(),
+            "matched lines",
+        )?;
+        write_stat_line(
+            &mut wtr,
+            colors,
+            stats.searches_with_match(),
+            "files contained matches",
+        )?;
+        write_stat_line(&mut wtr, colors, stats.searches(), "files searched")?;
+        write_stat_line(
+            &mut wtr,
+            colors,
+            stats.bytes_printed(),
+            "bytes printed",
+        )?;
+        write_stat_line(
+            &mut wtr,
+            colors,
+            stats.bytes_searched(),
+            "bytes searched",
+        )?;
+        write_stat_line_f64(
+            &mut wtr,
+            colors,
+            if deterministic { 0.0 } else { stats.elapsed().as_secs_f64() },
+            "seconds spent searching",
+        )?;
+        write_stat_line_f64(
+            &mut wtr,
+            colors,
+            elapsed.as_secs_f64(),
+            "seconds",
+        )?;
+        // Only printed when semantic search actually ran, since plain
+        // text/regex searches never generate embeddings.
+        if stats.embeddings_generated() > 0 {
+            let embedding_secs = stats.embedding_elapsed().as_secs_f64();
+            let throughput = if embedding_secs > 0.0 {
+                stats.embeddings_generated() as f64 / embedding_secs
+            } else {
+                0.0
+            };
+            write_stat_line(
+                &mut wtr,
+                colors,
+                stats.embeddings_generated(),
+                "embeddings generated",
+            )?;
+            write_stat_line_f64(
+                &mut wtr,
+                colors,
+                embedding_secs,
+                "seconds spent embedding",
+            )?;
+            write_stat_line_f64(
+                &mut wtr,
+                colors,
+                throughput,
+                "embeddings/sec",
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Write one `{number} {label}` stats line, with the number styled per
+/// `colors`'s `number` theme and the label per its `heading` theme.
+///
+/// `colors` has no effect when `wtr` isn't configured for color output
+/// (e.g. `--color=never` or a non-tty), since `termcolor` only emits escape
+/// codes for `WriteColor` implementations that have color enabled.
+fn write_stat_line<W: termcolor::WriteColor>(
+    wtr: &mut W,
+    colors: &grep::printer::ColorSpecs,
+    value: u64,
+    label: &str,
+) -> std::io::Result<()> {
+    wtr.set_color(colors.number())?;
+    write!(wtr, "{}", value)?;
+    wtr.reset()?;
+    write!(wtr, " ")?;
+    wtr.set_color(colors.heading())?;
+    write!(wtr, "{}", label)?;
+    wtr.reset()?;
+    writeln!(wtr)
+}
+
+/// Like `write_stat_line`, but for the floating point timing stats.
+fn write_stat_line_f64<W: termcolor::WriteColor>(
+    wtr: &mut W,
+    colors: &grep::printer::ColorSpecs,
+    value: f64,
+    label: &str,
+) -> std::io::Result<()> {
+    wtr.set_color(colors.number())?;
+    write!(wtr, "{:0.6}", value)?;
+    wtr.reset()?;
+    write!(wtr, " ")?;
+    wtr.set_color(colors.heading())?;
+    write!(wtr, "{}", label)?;
+    wtr.reset()?;
+    writeln!(wtr)
+}
+
+/// Wrap `text` in the ANSI escapes for `spec`, or return it unmodified when
+/// `use_color` is false (matching `--color=never` or non-tty output).
+///
+/// Used by plain `println!`-based reports (e.g. `diff_only`'s file diff
+/// count) that don't have a `WriteColor` writer to hand, unlike
+/// `write_stat_line`/`write_stat_line_f64` above.
+fn colorize(
+    spec: &termcolor::ColorSpec,
+    use_color: bool,
+    text: &str,
+) -> String {
+    if !use_color {
+        return text.to_string();
+    }
+    let mut buf = termcolor::Ansi::new(Vec::new());
+    if buf.set_color(spec).is_err() {
+        return text.to_string();
+    }
+    if std::io::Write::write_all(&mut buf, text.as_bytes()).is_err() {
+        return text.to_string();
+    }
+    if buf.reset().is_err() {
+        return text.to_string();
+    }
+    String::from_utf8(buf.into_inner()).unwrap_or_else(|_| text.to_string())
+}
+
+/// Extract the numeric value of `field` from `metrics`, or `None` if `field`
+/// has no numeric ordering (i.e. `AnalyzeSortField::Path`).
+///
+/// Shared by `--analyze-sort`'s sort comparator and `--analyze-min`'s
+/// threshold filter so both always agree on what a given field means.
+fn analyze_metrics_field(
+    field: AnalyzeSortField,
+    metrics: &crate::diagnostics::CodeMetrics,
+) -> Option<f64> {
+    match field {
+        AnalyzeSortField::Path => None,
+        AnalyzeSortField::Loc => Some(metrics.lines_of_code as f64),
+        AnalyzeSortField::Complexity => {
+            Some(metrics.cyclomatic_complexity as f64)
+        }
+        AnalyzeSortField::CognitiveComplexity => {
+            Some(metrics.cognitive_complexity as f64)
+        }
+        AnalyzeSortField::NestingDepth => {
+            Some(metrics.max_nesting_depth as f64)
+        }
+        AnalyzeSortField::FunctionLength => {
+            Some(metrics.max_function_length as f64)
+        }
+    }
+}
+
+/// Compare two analyze entries by `field`, descending for numeric fields
+/// (worst offenders first) and ascending by path for `AnalyzeSortField::Path`.
+fn analyze_entry_cmp(
+    field: AnalyzeSortField,
+    a: &(std::path::PathBuf, &'static str, crate::diagnostics::CodeMetrics),
+    b: &(std::path::PathBuf, &'static str, crate::diagnostics::CodeMetrics),
+) -> std::cmp::Ordering {
+    match (analyze_metrics_field(field, &a.2), analyze_metrics_field(field, &b.2)) {
+        (Some(x), Some(y)) => {
+            y.partial_cmp(&x).unwrap_or(std::cmp::Ordering::Equal)
+        }
+        _ => a.0.cmp(&b.0),
     }
 }
 
@@ -573,7 +1258,10 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
     let mut total_comments = 0;
     let mut total_functions = 0;
     let mut total_complexity = 0;
-    
+    let mut test_loc = 0;
+    let mut production_loc = 0;
+    let mut entries: Vec<(std::path::PathBuf, &'static str, crate::diagnostics::CodeMetrics)> = Vec::new();
+
     let walker = ignore::WalkBuilder::new(current_dir)
         .hidden(false)
         .git_ignore(true)
@@ -582,7 +1270,7 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
         .ignore(true)
         .parents(true)
         .build();
-    
+
     for result in walker {
         let entry = match result {
             Ok(entry) => entry,
@@ -591,30 +1279,30 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
                 continue;
             }
         };
-        
+
         // Skip directories
         if entry.file_type().map_or(false, |ft| ft.is_dir()) {
             continue;
         }
-        
+
         let path = entry.path();
-        
+
         // Skip common lock files and generated files
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
             match file_name {
-                "Cargo.lock" | "package-lock.json" | "yarn.lock" | "pnpm-lock.yaml" | 
+                "Cargo.lock" | "package-lock.json" | "yarn.lock" | "pnpm-lock.yaml" |
                 "composer.lock" | "Gemfile.lock" | "poetry.lock" | "Pipfile.lock" => {
                     continue;
                 }
                 _ => {}
             }
         }
-        
+
         // Only analyze source files
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
             match ext {
-                "rs" | "js" | "jsx" | "ts" | "tsx" | "py" | "java" | "go" | 
-                "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "php" | "rb" | 
+                "rs" | "js" | "jsx" | "ts" | "tsx" | "py" | "java" | "go" |
+                "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "php" | "rb" |
                 "cs" | "swift" => {
                     // Calculate metrics for this file
                     if let Ok(content) = std::fs::read_to_string(path) {
@@ -624,9 +1312,14 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
                             total_comments += metrics.comment_lines;
                             total_functions += metrics.function_count as u64;
                             total_complexity += metrics.cyclomatic_complexity as u64;
-                            
-                            let relative_path = path.strip_prefix(current_dir).unwrap_or(path);
-                            let status_icon = if let Some(git_status) = git_status.get(relative_path) {
+                            if metrics.is_test {
+                                test_loc += metrics.lines_of_code;
+                            } else {
+                                production_loc += metrics.lines_of_code;
+                            }
+
+                            let relative_path = path.strip_prefix(current_dir).unwrap_or(path).to_path_buf();
+                            let status_icon = if let Some(git_status) = git_status.get(&relative_path) {
                                 match git_status {
                                     crate::diagnostics::GitFileStatus::Modified => "M",
                                     crate::diagnostics::GitFileStatus::Staged => "S",
@@ -636,30 +1329,11 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
                             } else {
                                 ""
                             };
-                            
-                            println!("{} {}: {}", 
-                                status_icon,
-                                relative_path.display(),
-                                MetricsCalculator::metrics_summary(&metrics)
-                            );
-                            
-                            // Show inline diff if file has changes and diff flag is enabled
-                            if args.diff() && matches!(git_status.get(relative_path), Some(crate::diagnostics::GitFileStatus::Modified) | Some(crate::diagnostics::GitFileStatus::Staged)) {
-                                match git_analyzer.get_semantic_diff(path) {
-                                    Ok(diff) => {
-                                        if !diff.trim().is_empty() {
-                                            println!("    ┌─ Diff:");
-                                            for line in diff.lines() {
-                                                println!("    │ {}", line);
-                                            }
-                                            println!("    └─");
-                                        }
-                                    }
-                                    Err(e) => {
-                                        println!("    ┌─ Diff Error: {}", e);
-                                        println!("    └─");
-                                    }
-                                }
+
+                            if analyze_metrics_field(args.analyze_sort(), &metrics)
+                                .map_or(true, |v| v >= args.analyze_min().unwrap_or(f64::MIN))
+                            {
+                                entries.push((relative_path, status_icon, metrics));
                             }
                         }
                     }
@@ -668,11 +1342,85 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
             }
         }
     }
-    
+
+    entries.sort_by(|a, b| analyze_entry_cmp(args.analyze_sort(), a, b));
+
+    for (relative_path, status_icon, metrics) in &entries {
+        println!("{} {}: {}",
+            status_icon,
+            relative_path.display(),
+            MetricsCalculator::metrics_summary(metrics)
+        );
+
+        // Show inline diff if file has changes and diff flag is enabled
+        if args.diff()
+            && matches!(
+                git_status.get(relative_path),
+                Some(crate::diagnostics::GitFileStatus::Modified)
+                    | Some(crate::diagnostics::GitFileStatus::Staged)
+            )
+        {
+            match git_analyzer.get_semantic_diff(
+                &current_dir.join(relative_path),
+                &args.diff_options(),
+            ) {
+                Ok(outcome) => {
+                    if !outcome.diff.trim().is_empty() {
+                        println!("    ┌─ Diff:");
+                        for line in outcome.diff.lines() {
+                            println!("    │ {}", line);
+                        }
+                        if outcome.suppressed_hunks > 0 {
+                            println!(
+                                "    │ ({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                                outcome.suppressed_hunks
+                            );
+                        }
+                        println!("    └─");
+                    }
+                }
+                Err(e) => {
+                    println!("    ┌─ Diff Error: {}", e);
+                    println!("    └─");
+                }
+            }
+        }
+
+        // Show symbol-level diff if file has changes and structural-diff
+        // flag is enabled
+        if args.structural_diff()
+            && matches!(
+                git_status.get(relative_path),
+                Some(crate::diagnostics::GitFileStatus::Modified)
+                    | Some(crate::diagnostics::GitFileStatus::Staged)
+            )
+        {
+            match git_analyzer
+                .get_structural_diff(&current_dir.join(relative_path))
+            {
+                Ok(Some(diff)) if !diff.is_empty() => {
+                    println!("    ┌─ Structural diff:");
+                    for line in
+                        crate::diagnostics::format_structural_diff_lines(&diff)
+                    {
+                        println!("    │ {}", line);
+                    }
+                    println!("    └─");
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    println!("    ┌─ Structural Diff Error: {}", e);
+                    println!("    └─");
+                }
+            }
+        }
+    }
+
     println!();
     println!("Summary Statistics:");
     println!("  Files analyzed: {}", total_files);
     println!("  Total lines of code: {}", total_loc);
+    println!("    Production: {}, Tests: {}", production_loc, test_loc);
     println!("  Total comment lines: {}", total_comments);
     println!("  Total functions: {}", total_functions);
     println!("  Average complexity: {:.1}", 
@@ -702,20 +1450,30 @@ async fn analyze(args: &HiArgs) -> anyhow::Result<ExitCode> {
                     _ => {}
                 }
             }
-            
+
             match status {
-                crate::diagnostics::GitFileStatus::Modified | 
-                crate::diagnostics::GitFileStatus::Staged => {
+                crate::diagnostics::GitFileStatus::Modified
+                | crate::diagnostics::GitFileStatus::Staged => {
                     let full_path = current_dir.join(relative_path);
-                    if let Err(e) = show_semantic_diff(&full_path, &git_analyzer) {
-                        eprintln!("Warning: Could not show diff for {}: {}", relative_path.display(), e);
+                    if let Err(e) = show_semantic_diff(
+                        &full_path,
+                        &git_analyzer,
+                        &args.diff_options(),
+                        args.diff_hide_trivial(),
+                        args.json_output(),
+                    ) {
+                        eprintln!(
+                            "Warning: Could not show diff for {}: {}",
+                            relative_path.display(),
+                            e
+                        );
                     }
                 }
                 _ => {} // Skip untracked and conflicted files
             }
         }
     }
-    
+
     Ok(ExitCode::from(0))
 }
 
@@ -770,19 +1528,28 @@ async fn diff_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
                     crate::diagnostics::GitFileStatus::Staged => "S",
                     _ => "",
                 };
-                
+
                 println!("{} {}", status_icon, relative_path.display());
-                
+
                 // Convert relative path to absolute path for diff
-                let absolute_path = std::env::current_dir()?.join(relative_path);
-                
-                match git_analyzer.get_semantic_diff(&absolute_path) {
-                    Ok(diff) => {
-                        if !diff.trim().is_empty() {
+                let absolute_path =
+                    std::env::current_dir()?.join(relative_path);
+
+                match git_analyzer
+                    .get_semantic_diff(&absolute_path, &args.diff_options())
+                {
+                    Ok(outcome) => {
+                        if !outcome.diff.trim().is_empty() {
                             println!("┌─ Diff:");
-                            for line in diff.lines() {
+                            for line in outcome.diff.lines() {
                                 println!("│ {}", line);
                             }
+                            if outcome.suppressed_hunks > 0 {
+                                println!(
+                                    "│ ({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                                    outcome.suppressed_hunks
+                                );
+                            }
                             println!("└─");
                             diff_count += 1;
                         } else {
@@ -802,15 +1569,25 @@ async fn diff_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
     if diff_count == 0 {
         println!("No file diffs to display (files may be untracked or have no changes).");
     } else {
-        println!("Displayed {} file diff(s)", diff_count);
+        println!(
+            "Displayed {} file diff(s)",
+            colorize(
+                args.colors().number(),
+                args.color_enabled(),
+                &diff_count.to_string()
+            )
+        );
     }
-    
+
     Ok(ExitCode::from(0))
 }
 
 /// Entry point for standalone tree mode.
 async fn tree_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
-    use crate::diagnostics::{GitAnalyzer, TreeBuilder, TreeDisplay, TreeDisplayOptions};
+    use crate::diagnostics::{
+        GitAnalyzer, PathResolver, TreeBuilder, TreeDisplay,
+        TreeDisplayOptions,
+    };
     
     println!("Outgrep Tree View");
     println!("===================");
@@ -821,7 +1598,15 @@ async fn tree_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
     
     // Initialize Git analyzer for git status (optional)
     let git_analyzer = GitAnalyzer::new(&root_path_buf);
-    let git_status = git_analyzer.get_status_for_cwd().unwrap_or_default();
+    let path_resolver = std::sync::Arc::new(PathResolver::new(
+        git_analyzer.get_repo_root().map(|root| root.to_path_buf()),
+    ));
+    let git_status: std::collections::HashMap<_, _> = git_analyzer
+        .get_status_for_cwd()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, status)| (path_resolver.resolve(&path), status))
+        .collect();
     
     // Display git status summary if available
     if !git_status.is_empty() {
@@ -839,15 +1624,23 @@ async fn tree_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
         show_analysis: false,
         show_diagnostics: args.diagnostics(),
         show_syntax: args.syntax(),
+        symbol_kinds: args.symbol_kinds().to_vec(),
+        ast_depth: args.ast_depth(),
+        ast_max_nodes: args.ast_max_nodes(),
+        ast_summary: args.ast_summary(),
+        with_docs: args.with_docs(),
         truncate_diffs: args.truncate_diffs(),
         output_json: args.json_output(),
         git_status: git_status.clone(),
+        path_resolver: Some(path_resolver.clone()),
+        follow_symlinks: args.follow(),
+        use_color: args.color_enabled(),
     };
-    
+
     let tree_builder = TreeBuilder::with_options(&root_path_buf, options.clone());
     match tree_builder.build_tree(&root_path_buf) {
         Ok(tree) => {
-            
+
             if args.json_output() {
                 TreeDisplay::output_json(&tree, &options);
             } else {
@@ -865,7 +1658,10 @@ async fn tree_only(args: &HiArgs) -> anyhow::Result<ExitCode> {
 
 /// Entry point for tree mode with diff integration.
 async fn tree_with_diff(args: &HiArgs) -> anyhow::Result<ExitCode> {
-    use crate::diagnostics::{GitAnalyzer, TreeBuilder, TreeDisplay, TreeDisplayOptions};
+    use crate::diagnostics::{
+        GitAnalyzer, PathResolver, TreeBuilder, TreeDisplay,
+        TreeDisplayOptions,
+    };
     
     println!("Outgrep Git Diff Analysis");
     println!("============================");
@@ -886,7 +1682,15 @@ async fn tree_with_diff(args: &HiArgs) -> anyhow::Result<ExitCode> {
     
     // Initialize Git analyzer and tree builder
     let git_analyzer = GitAnalyzer::new(&root_path_buf);
-    let git_status = git_analyzer.get_status_for_cwd().unwrap_or_default();
+    let path_resolver = std::sync::Arc::new(PathResolver::new(
+        git_analyzer.get_repo_root().map(|root| root.to_path_buf()),
+    ));
+    let git_status: std::collections::HashMap<_, _> = git_analyzer
+        .get_status_for_cwd()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, status)| (path_resolver.resolve(&path), status))
+        .collect();
     let git_diagnostics = git_analyzer.get_diagnostics().ok();
     
     // Display git status summary
@@ -906,15 +1710,23 @@ async fn tree_with_diff(args: &HiArgs) -> anyhow::Result<ExitCode> {
         show_analysis: false,
         show_diagnostics: args.diagnostics(),
         show_syntax: args.syntax(),
+        symbol_kinds: args.symbol_kinds().to_vec(),
+        ast_depth: args.ast_depth(),
+        ast_max_nodes: args.ast_max_nodes(),
+        ast_summary: args.ast_summary(),
+        with_docs: args.with_docs(),
         truncate_diffs: args.truncate_diffs(),
         output_json: args.json_output(),
         git_status: git_status.clone(),
+        path_resolver: Some(path_resolver.clone()),
+        follow_symlinks: args.follow(),
+        use_color: args.color_enabled(),
     };
-    
+
     let tree_builder = TreeBuilder::with_options(&root_path_buf, options.clone());
     match tree_builder.build_tree(&root_path_buf) {
         Ok(tree) => {
-            
+
             TreeDisplay::display_tree_with_options(&tree, &options);
         }
         Err(e) => {
@@ -926,94 +1738,433 @@ async fn tree_with_diff(args: &HiArgs) -> anyhow::Result<ExitCode> {
     Ok(ExitCode::from(0))
 }
 
-/// Show semantic diff for a file using the similar crate
-fn show_semantic_diff(path: &std::path::Path, git_analyzer: &crate::diagnostics::GitAnalyzer) -> Result<(), Box<dyn std::error::Error>> {
-    use similar::{ChangeTag, TextDiff};
-    
+/// Show semantic diff for a file using the similar crate.
+///
+/// Each hunk is classified via [`crate::diagnostics::classify_hunks`] as
+/// code, comment-only, or whitespace-only. `hide_trivial` (`--diff-hide-
+/// trivial`) omits everything but code hunks from the printed diff;
+/// `json_output` (`--json`) instead emits one `diff_hunk` record per shown
+/// hunk with its classification, regardless of `hide_trivial`.
+fn show_semantic_diff(
+    path: &std::path::Path,
+    git_analyzer: &crate::diagnostics::GitAnalyzer,
+    diff_options: &crate::diagnostics::DiffOptions,
+    hide_trivial: bool,
+    json_output: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use similar::{ChangeTag, DiffTag, TextDiff};
+
     // Get the current content
     let current_content = std::fs::read_to_string(path)?;
-    
+
     // Get the Git HEAD content for comparison
     let head_content = git_analyzer.get_file_at_head(path)?;
-    
+
+    let suppressed_hunks =
+        diff_options.count_suppressed_hunks(&head_content, &current_content);
+    let current_content = diff_options.normalize(&current_content);
+    let head_content = diff_options.normalize(&head_content);
+
     // Create a diff
     let diff = TextDiff::from_lines(&head_content, &current_content);
-    
-    println!("\n{}", path.display());
-    println!("{}", "─".repeat(50));
-    
+    let classifications = crate::diagnostics::classify_hunks(
+        path,
+        &head_content,
+        &current_content,
+    );
+
+    if !json_output {
+        println!("\n{}", path.display());
+        println!("{}", "─".repeat(50));
+    }
+
     let mut has_changes = false;
-    for change in diff.iter_all_changes() {
+    let mut shown_hunks = false;
+    let mut hunk = 0;
+    for op in diff.ops() {
+        if op.tag() == DiffTag::Equal {
+            if !json_output {
+                for change in diff.iter_changes(op) {
+                    print!(" {}", change);
+                }
+            }
+            continue;
+        }
+
         has_changes = true;
-        let sign = match change.tag() {
-            ChangeTag::Delete => "-",
-            ChangeTag::Insert => "+",
-            ChangeTag::Equal => " ",
-        };
-        print!("{}{}", sign, change);
+        let classification = classifications[hunk];
+        hunk += 1;
+        if hide_trivial
+            && classification != crate::diagnostics::HunkClassification::Code
+        {
+            continue;
+        }
+        shown_hunks = true;
+
+        if json_output {
+            let lines: Vec<String> = diff
+                .iter_changes(op)
+                .map(|change| {
+                    let sign = match change.tag() {
+                        ChangeTag::Delete => "-",
+                        ChangeTag::Insert => "+",
+                        ChangeTag::Equal => " ",
+                    };
+                    format!(
+                        "{}{}",
+                        sign,
+                        change.value().trim_end_matches('\n')
+                    )
+                })
+                .collect();
+            let message = serde_json::json!({
+                "type": "diff_hunk",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "classification": classification,
+                    "lines": lines,
+                },
+            });
+            println!("{}", message);
+        } else {
+            for change in diff.iter_changes(op) {
+                let sign = match change.tag() {
+                    ChangeTag::Delete => "-",
+                    ChangeTag::Insert => "+",
+                    ChangeTag::Equal => " ",
+                };
+                print!("{}{}", sign, change);
+            }
+        }
     }
-    
-    if !has_changes {
-        println!("No changes detected");
+
+    if !json_output {
+        if !has_changes {
+            println!("No changes detected");
+        } else if hide_trivial && !shown_hunks {
+            println!(
+                "No non-trivial changes (all hunks were comment-only or whitespace-only)"
+            );
+        } else if suppressed_hunks > 0 {
+            println!(
+                "({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                suppressed_hunks
+            );
+        }
     }
-    
+
     Ok(())
 }
 
+/// A file's most recently seen metrics and diagnostics, kept around by
+/// [`watch`] so a later `Modified` event can report what changed instead of
+/// just the new absolute numbers.
+struct WatchSnapshot {
+    metrics: crate::diagnostics::CodeMetrics,
+    issues: std::collections::BTreeSet<String>,
+}
+
+/// Running totals across a `--watch` session, printed once on exit.
+#[derive(Default)]
+struct WatchSessionSummary {
+    files_modified: usize,
+    loc_delta: i64,
+    complexity_delta: i64,
+    issues_delta: i64,
+}
+
+/// Run diagnostics for `path`, scoped to its owning crate/project (see
+/// [`crate::diagnostics::compiler::CompilerDiagnosticsRunner`]), and render
+/// its errors and warnings as a set of stable strings so two runs can be
+/// diffed to find newly introduced/resolved issues. Returns an empty set if
+/// `path`'s language isn't one `CompilerDiagnosticsRunner` knows how to
+/// check.
+fn watch_diagnostic_issues(
+    path: &std::path::Path,
+) -> std::collections::BTreeSet<String> {
+    let Some(language) = single_file_diagnostics_language(path) else {
+        return std::collections::BTreeSet::new();
+    };
+    let Some(diagnostics) =
+        crate::diagnostics::compiler::CompilerDiagnosticsRunner::run_diagnostics(
+            path,
+            Some(language),
+        )
+    else {
+        return std::collections::BTreeSet::new();
+    };
+    diagnostics
+        .errors
+        .iter()
+        .chain(diagnostics.warnings.iter())
+        .map(|d| {
+            format!(
+                "{:?} {}:{}: {}",
+                d.severity, d.location.line, d.location.column, d.message
+            )
+        })
+        .collect()
+}
+
+/// Whether `--watch-events`/`--watch-glob` (when given) allow reporting an
+/// event of the given `kind` ("create", "modify", "delete" or "rename") for
+/// `path`. With neither flag given, every event is allowed.
+fn watch_event_allowed(
+    args: &HiArgs,
+    kind: &str,
+    path: &std::path::Path,
+) -> bool {
+    let events = args.watch_events();
+    if !events.is_empty()
+        && !events.iter().any(|e| e.eq_ignore_ascii_case(kind))
+    {
+        return false;
+    }
+    let globs = args.watch_globs();
+    if !globs.is_empty() && !globs.matched(path, false).is_whitelist() {
+        return false;
+    }
+    true
+}
+
 /// Entry point for watch mode.
 ///
-/// This function starts file watching for real-time monitoring of file changes.
+/// This function starts file watching for real-time monitoring of file
+/// changes. Each `Modified` event is reported as a delta (LOC, function
+/// count, complexity, and issue count changes) against the file's previous
+/// snapshot rather than its absolute metrics, and the deltas accumulate
+/// into a session summary printed when the user exits with Ctrl+C.
+/// Newly introduced and resolved diagnostics (scoped to the changed file's
+/// owning crate or project, not the whole workspace) are printed
+/// individually alongside the delta, for near-IDE feedback on save.
+/// `--watch-events`/`--watch-glob` restrict which events are reported at
+/// all.
+#[cfg(feature = "watch")]
 async fn watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     use crate::diagnostics::{FileWatcher, MetricsCalculator};
     use std::io::Write;
     use std::time::Duration;
-    
+
     let current_dir = std::path::Path::new(".");
-    
+
     println!("Outgrep File Watcher");
     println!("========================");
     println!("Watching for changes in: {}", current_dir.display());
     println!("Press Ctrl+C to exit...");
     println!();
-    
+
     let mut watcher = FileWatcher::new()?;
     watcher.watch(current_dir)?;
-    
-    // Watch for file changes
+
+    let mut snapshots: std::collections::HashMap<
+        std::path::PathBuf,
+        WatchSnapshot,
+    > = std::collections::HashMap::new();
+    let mut session = WatchSessionSummary::default();
+
+    // Watch for file changes until the user asks to stop, then report what
+    // changed across the whole session.
     loop {
-        if let Some(event) = watcher.next_event_timeout(Duration::from_secs(1)).await {
-            match event {
-                crate::diagnostics::FileChangeEvent::Created(path) => {
-                    println!("File created: {}", path.display());
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
-                            println!("   {}", MetricsCalculator::metrics_summary(&metrics));
+        tokio::select! {
+            event = watcher.next_event_timeout(Duration::from_secs(1)) => {
+                let Some(event) = event else { continue };
+                let (kind, filter_path): (&str, &std::path::Path) = match &event {
+                    crate::diagnostics::FileChangeEvent::Created(p) => ("create", p),
+                    crate::diagnostics::FileChangeEvent::Modified(p) => ("modify", p),
+                    crate::diagnostics::FileChangeEvent::Deleted(p) => ("delete", p),
+                    crate::diagnostics::FileChangeEvent::Renamed { to, .. } => ("rename", to),
+                };
+                if !watch_event_allowed(args, kind, filter_path) {
+                    continue;
+                }
+                match event {
+                    crate::diagnostics::FileChangeEvent::Created(path) => {
+                        println!("File created: {}", path.display());
+                        if let Ok(content) = std::fs::read_to_string(&path) {
+                            if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
+                                println!("   {}", MetricsCalculator::metrics_summary(&metrics));
+                                let issues = watch_diagnostic_issues(&path);
+                                for issue in &issues {
+                                    println!("   + {}", issue);
+                                }
+                                snapshots.insert(path, WatchSnapshot { metrics, issues });
+                            }
                         }
                     }
-                }
-                crate::diagnostics::FileChangeEvent::Modified(path) => {
-                    println!("File modified: {}", path.display());
-                    if let Ok(content) = std::fs::read_to_string(&path) {
-                        if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
-                            println!("   {}", MetricsCalculator::metrics_summary(&metrics));
+                    crate::diagnostics::FileChangeEvent::Modified(path) => {
+                        println!("File modified: {}", path.display());
+                        if let Ok(content) = std::fs::read_to_string(&path) {
+                            if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
+                                let issues = watch_diagnostic_issues(&path);
+                                match snapshots.get(&path) {
+                                    Some(previous) => {
+                                        println!(
+                                            "   {}, issues {:+}",
+                                            MetricsCalculator::metrics_delta_summary(&previous.metrics, &metrics),
+                                            issues.len() as i64 - previous.issues.len() as i64,
+                                        );
+                                        for new_issue in issues.difference(&previous.issues) {
+                                            println!("   + {}", new_issue);
+                                        }
+                                        for resolved_issue in previous.issues.difference(&issues) {
+                                            println!("   - {}", resolved_issue);
+                                        }
+                                        session.files_modified += 1;
+                                        session.loc_delta += metrics.lines_of_code as i64
+                                            - previous.metrics.lines_of_code as i64;
+                                        session.complexity_delta +=
+                                            metrics.cyclomatic_complexity as i64
+                                                - previous.metrics.cyclomatic_complexity as i64;
+                                        session.issues_delta +=
+                                            issues.len() as i64 - previous.issues.len() as i64;
+                                    }
+                                    None => {
+                                        println!("   {}", MetricsCalculator::metrics_summary(&metrics));
+                                        for issue in &issues {
+                                            println!("   + {}", issue);
+                                        }
+                                    }
+                                }
+                                snapshots.insert(path, WatchSnapshot { metrics, issues });
+                            }
+                        }
+                    }
+                    crate::diagnostics::FileChangeEvent::Deleted(path) => {
+                        println!("File deleted: {}", path.display());
+                        snapshots.remove(&path);
+                    }
+                    crate::diagnostics::FileChangeEvent::Renamed { from, to } => {
+                        println!("File renamed: {} -> {}", from.display(), to.display());
+                        if let Some(snapshot) = snapshots.remove(&from) {
+                            snapshots.insert(to, snapshot);
                         }
                     }
                 }
-                crate::diagnostics::FileChangeEvent::Deleted(path) => {
-                    println!("File deleted: {}", path.display());
-                }
-                crate::diagnostics::FileChangeEvent::Renamed { from, to } => {
-                    println!("File renamed: {} -> {}", from.display(), to.display());
-                }
+                std::io::stdout().flush().unwrap();
             }
-            std::io::stdout().flush().unwrap();
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                println!("Session summary:");
+                println!(
+                    "  Files modified: {}, LOC {:+}, Complexity {:+}, Issues {:+}",
+                    session.files_modified,
+                    session.loc_delta,
+                    session.complexity_delta,
+                    session.issues_delta,
+                );
+                return Ok(ExitCode::from(0));
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "watch"))]
+async fn watch(_args: &HiArgs) -> anyhow::Result<ExitCode> {
+    anyhow::bail!(
+        "outgrep was built without the `watch` feature; rebuild with \
+         `--features watch` to enable `--watch`"
+    )
+}
+
+/// Entry point for `--tail` follow mode.
+///
+/// Unlike `watch`, which monitors a whole directory tree for generic file
+/// change metrics, this follows exactly one file and searches only the bytes
+/// appended to it, the way `tail -f file | grep pattern` does -- except
+/// matches are found by outgrep's own matcher and printer, so `--json`,
+/// `--count` and the usual context flags keep working against the streamed
+/// output.
+#[cfg(feature = "watch")]
+async fn tail_follow(
+    args: &HiArgs,
+    mode: SearchMode,
+) -> anyhow::Result<ExitCode> {
+    use crate::diagnostics::{FileChangeEvent, FileWatcher};
+    use std::{
+        io::{Read, Seek, SeekFrom},
+        time::Duration,
+    };
+
+    let path = match args.search_paths() {
+        [path] if path != std::path::Path::new("-") => path.clone(),
+        _ => anyhow::bail!(
+            "--tail requires exactly one file path to follow \
+             (not stdin and not multiple paths)"
+        ),
+    };
+    if path.is_dir() {
+        anyhow::bail!(
+            "--tail requires a file to follow, but {} is a directory",
+            path.display()
+        );
+    }
+
+    // Only data appended after outgrep starts is searched -- the file's
+    // existing contents are never replayed, matching `tail -f -n 0`.
+    let mut offset = std::fs::metadata(&path)?.len();
+
+    eprintln!(
+        "Following {} for new matches (Ctrl+C to exit)...",
+        path.display()
+    );
+
+    let mut watcher = FileWatcher::new()?;
+    watcher.watch(&path)?;
+
+    let mut searcher = args.search_worker(
+        args.matcher()?,
+        args.searcher()?,
+        args.printer(mode, args.stdout()),
+    )?;
+
+    loop {
+        let event = watcher.next_event_timeout(Duration::from_secs(1)).await;
+        let changed = match event {
+            Some(FileChangeEvent::Modified(changed)) if changed == path => {
+                changed
+            }
+            Some(FileChangeEvent::Deleted(changed)) if changed == path => {
+                anyhow::bail!("{}: file was removed", changed.display());
+            }
+            _ => continue,
+        };
+
+        let mut file = std::fs::File::open(&changed)?;
+        let len = file.metadata()?.len();
+        if len < offset {
+            // The file shrank, e.g. due to log rotation or truncation.
+            // Restart from the beginning of whatever is there now.
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut chunk = Vec::with_capacity((len - offset) as usize);
+        file.read_to_end(&mut chunk)?;
+        offset = len;
+
+        if let Err(err) = searcher.search_tail_chunk(&path, &chunk) {
+            err_message!("{}: {}", path.display(), err);
         }
     }
 }
 
+#[cfg(not(feature = "watch"))]
+async fn tail_follow(
+    _args: &HiArgs,
+    _mode: SearchMode,
+) -> anyhow::Result<ExitCode> {
+    anyhow::bail!(
+        "outgrep was built without the `watch` feature; rebuild with \
+         `--features watch` to enable `--tail`"
+    )
+}
+
 /// Entry point for combined analyze and watch mode.
 ///
 /// This function performs initial analysis and then starts file watching.
+#[cfg(feature = "watch")]
 async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     use crate::diagnostics::{FileWatcher, MetricsCalculator, GitAnalyzer};
     use std::io::Write;
@@ -1039,7 +2190,9 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     let mut total_comments = 0;
     let mut total_functions = 0;
     let mut total_complexity = 0;
-    
+    let mut test_loc = 0;
+    let mut production_loc = 0;
+
     let walker = ignore::WalkBuilder::new(current_dir)
         .hidden(false)
         .git_ignore(true)
@@ -1048,7 +2201,7 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
         .ignore(true)
         .parents(true)
         .build();
-    
+
     for result in walker {
         let entry = match result {
             Ok(entry) => entry,
@@ -1057,30 +2210,30 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
                 continue;
             }
         };
-        
+
         // Skip directories
         if entry.file_type().map_or(false, |ft| ft.is_dir()) {
             continue;
         }
-        
+
         let path = entry.path();
-        
+
         // Skip common lock files and generated files
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
             match file_name {
-                "Cargo.lock" | "package-lock.json" | "yarn.lock" | "pnpm-lock.yaml" | 
+                "Cargo.lock" | "package-lock.json" | "yarn.lock" | "pnpm-lock.yaml" |
                 "composer.lock" | "Gemfile.lock" | "poetry.lock" | "Pipfile.lock" => {
                     continue;
                 }
                 _ => {}
             }
         }
-        
+
         // Only analyze source files
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
             match ext {
-                "rs" | "js" | "jsx" | "ts" | "tsx" | "py" | "java" | "go" | 
-                "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "php" | "rb" | 
+                "rs" | "js" | "jsx" | "ts" | "tsx" | "py" | "java" | "go" |
+                "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "php" | "rb" |
                 "cs" | "swift" => {
                     // Calculate metrics for this file
                     if let Ok(content) = std::fs::read_to_string(path) {
@@ -1090,7 +2243,12 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
                             total_comments += metrics.comment_lines;
                             total_functions += metrics.function_count as u64;
                             total_complexity += metrics.cyclomatic_complexity as u64;
-                            
+                            if metrics.is_test {
+                                test_loc += metrics.lines_of_code;
+                            } else {
+                                production_loc += metrics.lines_of_code;
+                            }
+
                             let relative_path = path.strip_prefix(current_dir).unwrap_or(path);
                             let status_icon = if let Some(git_status) = git_status.get(relative_path) {
                                 match git_status {
@@ -1102,22 +2260,37 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
                             } else {
                                 ""
                             };
-                            
-                            println!("{} {}: {}", 
+
+                            println!(
+                                "{} {}: {}",
                                 status_icon,
                                 relative_path.display(),
                                 MetricsCalculator::metrics_summary(&metrics)
                             );
-                            
+
                             // Show inline diff if file has changes and diff flag is enabled
-                            if args.diff() && matches!(git_status.get(relative_path), Some(crate::diagnostics::GitFileStatus::Modified) | Some(crate::diagnostics::GitFileStatus::Staged)) {
-                                match git_analyzer.get_semantic_diff(path) {
-                                    Ok(diff) => {
-                                        if !diff.trim().is_empty() {
+                            if args.diff()
+                                && matches!(
+                                    git_status.get(relative_path),
+                                    Some(crate::diagnostics::GitFileStatus::Modified)
+                                        | Some(crate::diagnostics::GitFileStatus::Staged)
+                                )
+                            {
+                                match git_analyzer
+                                    .get_semantic_diff(path, &args.diff_options())
+                                {
+                                    Ok(outcome) => {
+                                        if !outcome.diff.trim().is_empty() {
                                             println!("    ┌─ Diff:");
-                                            for line in diff.lines() {
+                                            for line in outcome.diff.lines() {
                                                 println!("    │ {}", line);
                                             }
+                                            if outcome.suppressed_hunks > 0 {
+                                                println!(
+                                                    "    │ ({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                                                    outcome.suppressed_hunks
+                                                );
+                                            }
                                             println!("    └─");
                                         }
                                     }
@@ -1139,6 +2312,7 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     println!("Summary Statistics:");
     println!("  Files analyzed: {}", total_files);
     println!("  Total lines of code: {}", total_loc);
+    println!("    Production: {}, Tests: {}", production_loc, test_loc);
     println!("  Total comment lines: {}", total_comments);
     println!("  Total functions: {}", total_functions);
     println!("  Average complexity: {:.1}", 
@@ -1159,7 +2333,12 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     
     let mut watcher = FileWatcher::new()?;
     watcher.watch(current_dir)?;
-    
+
+    // Tracks each watched file's previous AST so a `Modified` event can be
+    // re-parsed incrementally instead of from scratch; see
+    // `crate::diagnostics::ParseCache`.
+    let mut parse_cache = crate::diagnostics::ParseCache::new();
+
     // Watch for file changes
     loop {
         if let Some(event) = watcher.next_event_timeout(Duration::from_secs(1)).await {
@@ -1170,6 +2349,16 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
                         if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
                             println!("   {}", MetricsCalculator::metrics_summary(&metrics));
                         }
+                        let ast = parse_cache.update(&path, &content);
+                        if let Some(ast) = ast {
+                            println!(
+                                "   {} symbols",
+                                ast.symbols.functions.len()
+                                    + ast.symbols.classes.len()
+                                    + ast.symbols.types.len()
+                                    + ast.symbols.modules.len()
+                            );
+                        }
                     }
                 }
                 crate::diagnostics::FileChangeEvent::Modified(path) => {
@@ -1178,13 +2367,25 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
                         if let Ok(metrics) = MetricsCalculator::calculate_metrics(&path, &content) {
                             println!("   {}", MetricsCalculator::metrics_summary(&metrics));
                         }
+                        let ast = parse_cache.update(&path, &content);
+                        if let Some(ast) = ast {
+                            println!(
+                                "   {} symbols",
+                                ast.symbols.functions.len()
+                                    + ast.symbols.classes.len()
+                                    + ast.symbols.types.len()
+                                    + ast.symbols.modules.len()
+                            );
+                        }
                     }
                 }
                 crate::diagnostics::FileChangeEvent::Deleted(path) => {
                     println!("File deleted: {}", path.display());
+                    parse_cache.remove(&path);
                 }
                 crate::diagnostics::FileChangeEvent::Renamed { from, to } => {
                     println!("File renamed: {} -> {}", from.display(), to.display());
+                    parse_cache.remove(&from);
                 }
             }
             std::io::stdout().flush().unwrap();
@@ -1192,21 +2393,244 @@ async fn analyze_and_watch(args: &HiArgs) -> anyhow::Result<ExitCode> {
     }
 }
 
+#[cfg(not(feature = "watch"))]
+async fn analyze_and_watch(_args: &HiArgs) -> anyhow::Result<ExitCode> {
+    anyhow::bail!(
+        "outgrep was built without the `watch` feature; rebuild with \
+         `--features watch` to enable `--analyze --watch`"
+    )
+}
+
+/// Produce a focused report for a single file: metrics, AST outline,
+/// compiler diagnostics, a blame summary, and (if the file has changed) a
+/// diff against HEAD. This is the `og analyze path/to/file.rs` workflow --
+/// unlike [`unified_tree_mode`]'s directory walk, everything here is
+/// scoped to the one file the caller pointed at.
+fn analyze_single_file(
+    args: &HiArgs,
+    path: &std::path::Path,
+) -> anyhow::Result<ExitCode> {
+    use crate::diagnostics::{
+        extract_ast_structure, BlameSummary, GitAnalyzer, MetricsCalculator,
+    };
+
+    let content = std::fs::read_to_string(path)?;
+    let metrics = MetricsCalculator::calculate_metrics(path, &content).ok();
+
+    let ast = extract_ast_structure(path);
+    let symbols = ast.as_ref().map(|ast| {
+        let symbols = ast.symbols.filtered(args.symbol_kinds());
+        if args.with_docs() {
+            symbols
+        } else {
+            symbols.without_docs()
+        }
+    });
+
+    let language = single_file_diagnostics_language(path);
+    let diagnostics = language.and_then(|language| {
+        crate::diagnostics::compiler::CompilerDiagnosticsRunner::run_diagnostics(
+            path,
+            Some(language),
+        )
+    });
+
+    let git_analyzer = GitAnalyzer::new(path.parent().unwrap_or(path));
+    let blame = git_analyzer.blame_summary(path).ok();
+    let diff = if git_analyzer.is_git_repo() {
+        git_analyzer.get_semantic_diff(path, &args.diff_options()).ok()
+    } else {
+        None
+    };
+
+    if args.json_output() {
+        let message = serde_json::json!({
+            "type": "file_report",
+            "data": {
+                "path": {"text": path.display().to_string()},
+                "metrics": metrics,
+                "outline": symbols,
+                "diagnostics": diagnostics,
+                "blame_summary": blame.as_ref().map(|blame| serde_json::json!({
+                    "total_lines": blame.total_lines,
+                    "authors": blame.authors,
+                    "last_commit": blame.last_commit,
+                })),
+                "diff": diff.as_ref().map(|outcome| serde_json::json!({
+                    "diff": outcome.diff,
+                    "suppressed_hunks": outcome.suppressed_hunks,
+                })),
+            },
+        });
+        println!("{}", message);
+        return Ok(ExitCode::from(0));
+    }
+
+    println!("File Report: {}", path.display());
+    println!("{}", "=".repeat(13 + path.display().to_string().len()));
+    println!();
+
+    match &metrics {
+        Some(metrics) => {
+            println!(
+                "Metrics: {}",
+                MetricsCalculator::metrics_summary(metrics)
+            )
+        }
+        None => println!("Metrics: unavailable"),
+    }
+
+    println!();
+    match &symbols {
+        Some(symbols)
+            if !symbols.functions.is_empty()
+                || !symbols.classes.is_empty()
+                || !symbols.types.is_empty()
+                || !symbols.modules.is_empty() =>
+        {
+            println!("Outline:");
+            for (label, entries) in [
+                ("Functions", &symbols.functions),
+                ("Classes/Structs", &symbols.classes),
+                ("Types", &symbols.types),
+                ("Modules", &symbols.modules),
+            ] {
+                if entries.is_empty() {
+                    continue;
+                }
+                println!("  {}:", label);
+                for symbol in entries {
+                    println!("    - {} (line {})", symbol.name, symbol.line);
+                    if let Some(doc) = &symbol.doc_comment {
+                        for line in doc.lines() {
+                            println!("        {}", line);
+                        }
+                    }
+                }
+            }
+        }
+        Some(_) => println!("Outline: no symbols found"),
+        None => println!("Outline: unsupported language"),
+    }
+
+    println!();
+    match &diagnostics {
+        Some(diagnostics) if diagnostics.total_count() > 0 => {
+            println!("Diagnostics:");
+            for diagnostic in diagnostics
+                .errors
+                .iter()
+                .chain(&diagnostics.warnings)
+                .chain(&diagnostics.infos)
+                .chain(&diagnostics.hints)
+            {
+                println!(
+                    "  {:?} line {}: {}",
+                    diagnostic.severity,
+                    diagnostic.location.line,
+                    diagnostic.message
+                );
+            }
+        }
+        Some(_) => println!("Diagnostics: none"),
+        None => println!("Diagnostics: unavailable"),
+    }
+
+    println!();
+    match &blame {
+        Some(BlameSummary { total_lines, authors, last_commit }) => {
+            println!("Blame Summary ({} lines):", total_lines);
+            for (author, lines) in authors {
+                println!("  {} - {} lines", author, lines);
+            }
+            if let Some(sha) = last_commit {
+                println!("  Last touched by commit: {}", sha);
+            }
+        }
+        None => println!("Blame Summary: unavailable (not a Git repository)"),
+    }
+
+    if let Some(outcome) = &diff {
+        if !outcome.diff.trim().is_empty() {
+            println!();
+            println!("Diff since HEAD:");
+            for line in outcome.diff.lines() {
+                println!("  {}", line);
+            }
+            if outcome.suppressed_hunks > 0 {
+                println!(
+                    "  ({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                    outcome.suppressed_hunks
+                );
+            }
+        }
+    }
+
+    Ok(ExitCode::from(0))
+}
+
+/// Map a file extension to the language name [`crate::diagnostics::compiler::CompilerDiagnosticsRunner`]
+/// expects, for the subset of languages it knows how to run diagnostics for.
+fn single_file_diagnostics_language(
+    path: &std::path::Path,
+) -> Option<&'static str> {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())?
+        .to_lowercase()
+        .as_str()
+    {
+        "rs" => Some("Rust"),
+        "js" | "jsx" => Some("JavaScript"),
+        "ts" | "tsx" => Some("TypeScript"),
+        "py" => Some("Python"),
+        "java" => Some("Java"),
+        "go" => Some("Go"),
+        _ => None,
+    }
+}
+
 /// Entry point for unified tree mode that integrates all analysis types
 ///
 /// This function serves as the backbone for integrating tree, diff, analyze, and diagnostics
 /// into a single coherent view when any of these flags are enabled.
 async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
-    use crate::diagnostics::{GitAnalyzer, TreeBuilder, TreeDisplay, TreeDisplayOptions};
-    
+    use crate::diagnostics::{
+        GitAnalyzer, PathResolver, TreeBuilder, TreeDisplay, TreeDisplayOptions,
+    };
+
+    // A single explicit file argument (as opposed to a directory, or the
+    // implicit "." used when nothing was given) gets a focused report
+    // instead of being folded into the directory walk below, since that
+    // walk always starts from "." and would otherwise ignore the argument
+    // entirely.
+    if let [single_path] = args.search_paths() {
+        if single_path.is_file() {
+            return analyze_single_file(args, single_path);
+        }
+    }
+
     // Use current directory for analysis
     let root_path_buf = std::path::PathBuf::from(".");
     
     // Initialize Git analyzer and tree builder
     let git_analyzer = GitAnalyzer::new(&root_path_buf);
-    let git_status = git_analyzer.get_status_for_cwd().unwrap_or_default();
+    let path_resolver = std::sync::Arc::new(PathResolver::new(
+        git_analyzer.get_repo_root().map(|root| root.to_path_buf()),
+    ));
+    let git_status: std::collections::HashMap<_, _> = git_analyzer
+        .get_status_for_cwd()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, status)| (path_resolver.resolve(&path), status))
+        .collect();
     let git_diagnostics = git_analyzer.get_diagnostics().ok();
-    
+
+    // Run any discovered `og-plugin-*` executables once per invocation and
+    // fold their reported sections into the report below.
+    let plugins = crate::plugins::discover(args.plugins_dir());
+    let plugin_sections = crate::plugins::run_all(&plugins, &root_path_buf);
+
     // Only show headers and status when NOT in JSON output mode
     if !args.json_output() {
         // Determine header based on active flags - build dynamically
@@ -1243,8 +2667,19 @@ async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
                 println!();
             }
         }
+
+        // Show each plugin's reported section, in discovery order.
+        for section in &plugin_sections {
+            println!("Plugin: {}", section.title);
+            println!("{}", "-".repeat(section.title.len() + 8));
+            match serde_json::to_string_pretty(&section.data) {
+                Ok(pretty) => println!("{}", pretty),
+                Err(e) => println!("<failed to render plugin output: {}>", e),
+            }
+            println!();
+        }
     }
-    
+
     // Handle tree mode or file-centric mode
     if args.tree() || args.syntax() {
         // Tree backbone mode - integrate everything into tree structure
@@ -1261,17 +2696,32 @@ async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
             show_analysis: args.analyze(),
             show_diagnostics: args.diagnostics(),
             show_syntax: args.syntax(),
+            symbol_kinds: args.symbol_kinds().to_vec(),
+            ast_depth: args.ast_depth(),
+            ast_max_nodes: args.ast_max_nodes(),
+            ast_summary: args.ast_summary(),
+            with_docs: args.with_docs(),
             truncate_diffs: args.truncate_diffs(),
             output_json: args.json_output(),
             git_status: git_status.clone(),
+            path_resolver: Some(path_resolver.clone()),
+            follow_symlinks: args.follow(),
+            use_color: args.color_enabled(),
         };
-        
+
         let tree_builder = TreeBuilder::with_options(&root_path_buf, options.clone());
         match tree_builder.build_tree(&root_path_buf) {
             Ok(tree) => {
                 
                 if args.json_output() {
-                    output_unified_json(&tree, &options, args, &git_status).await;
+                    output_unified_json(
+                        &tree,
+                        &options,
+                        args,
+                        &git_status,
+                        &plugin_sections,
+                    )
+                    .await;
                 } else {
                     TreeDisplay::display_tree_with_options(&tree, &options);
                 }
@@ -1301,7 +2751,9 @@ async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
         let mut total_comments = 0;
         let mut total_functions = 0;
         let mut total_complexity = 0;
-        
+        let mut test_loc = 0;
+        let mut production_loc = 0;
+
         for result in walker {
             let entry = match result {
                 Ok(entry) => entry,
@@ -1385,33 +2837,48 @@ async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
                         total_comments += metrics.comment_lines;
                         total_functions += metrics.function_count as u64;
                         total_complexity += metrics.cyclomatic_complexity as u64;
+                        if metrics.is_test {
+                            test_loc += metrics.lines_of_code;
+                        } else {
+                            production_loc += metrics.lines_of_code;
+                        }
                         analyzed_files += 1;
                     }
                 }
             }
-            
+
             println!(); // End the file line
-            
+
             // Show diff if requested and file has changes
             if args.diff() && file_git_status.is_some() {
-                match git_analyzer.get_semantic_diff(path) {
-                    Ok(diff) => {
-                        if !diff.trim().is_empty() {
+                match git_analyzer
+                    .get_semantic_diff(path, &args.diff_options())
+                {
+                    Ok(outcome) => {
+                        if !outcome.diff.trim().is_empty() {
                             println!("  ┌─ Diff:");
-                            let lines: Vec<&str> = diff.lines().collect();
-                            let lines_to_show = if args.truncate_diffs() && lines.len() > 10 {
-                                &lines[..10]
-                            } else {
-                                &lines
-                            };
-                            
+                            let lines: Vec<&str> =
+                                outcome.diff.lines().collect();
+                            let lines_to_show =
+                                if args.truncate_diffs() && lines.len() > 10 {
+                                    &lines[..10]
+                                } else {
+                                    &lines
+                                };
+
                             for line in lines_to_show {
                                 println!("  │ {}", line);
                             }
-                            
+
                             if args.truncate_diffs() && lines.len() > 10 {
                                 println!("  │ ... (truncated, showing first 10 lines of {} total)", lines.len());
                             }
+                            if outcome.suppressed_hunks > 0 {
+                                println!(
+                                    "  │ ({} hunk(s) suppressed by --diff-ignore-eol/--diff-ignore-whitespace)",
+                                    outcome.suppressed_hunks
+                                );
+                            }
                             println!("  └─");
                         }
                     }
@@ -1435,6 +2902,8 @@ async fn unified_tree_mode(args: &HiArgs) -> anyhow::Result<ExitCode> {
             println!("Summary Statistics:");
             println!("  Files analyzed: {}", analyzed_files);
             println!("  Total lines of code: {}", total_loc);
+            println!("    Production: {}, Tests: {}", production_loc, test_loc);
+    println!("    Production: {}, Tests: {}", production_loc, test_loc);
             println!("  Total comment lines: {}", total_comments);
             println!("  Total functions: {}", total_functions);
             println!("  Average complexity: {:.1}", 
@@ -1451,7 +2920,8 @@ async fn output_unified_json(
     tree: &crate::diagnostics::types::TreeNode,
     options: &crate::diagnostics::TreeDisplayOptions,
     args: &HiArgs,
-    git_status: &std::collections::HashMap<std::path::PathBuf, crate::diagnostics::GitFileStatus>
+    git_status: &std::collections::HashMap<std::path::PathBuf, crate::diagnostics::GitFileStatus>,
+    plugin_sections: &[crate::plugins::PluginSection],
 ) {
     use crate::diagnostics::TreeDisplay;
     
@@ -1517,7 +2987,18 @@ async fn output_unified_json(
     // Get the enhanced tree data from TreeDisplay
     let tree_data = TreeDisplay::create_enhanced_json(tree, options);
     output.insert("tree".to_string(), tree_data);
-    
+
+    if !plugin_sections.is_empty() {
+        let plugins_json: Vec<serde_json::Value> = plugin_sections
+            .iter()
+            .map(|s| serde_json::json!({"title": s.title, "data": s.data}))
+            .collect();
+        output.insert(
+            "plugins".to_string(),
+            serde_json::Value::Array(plugins_json),
+        );
+    }
+
     // Output the complete JSON
     match serde_json::to_string_pretty(&output) {
         Ok(json) => println!("{}", json),
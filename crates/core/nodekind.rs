@@ -0,0 +1,223 @@
+/*!
+Restricts or excludes matches by the syntax node kind they fall in, for
+`--only-in`/`--not-in`.
+
+Both flags take a comma-separated list of node kind categories (currently
+`comments` and `strings`) and are resolved into a set of byte ranges within
+the searched file: `--only-in` narrows the regular search down to those
+ranges, `--not-in` excludes them, with the underlying regex or literal
+matcher running exactly as it would otherwise. This reuses the same
+`SymbolRangeFilterSink`/`search_slice` plumbing `--symbol` already relies on
+to restrict a whole-file search to a subset of it.
+*/
+
+use std::ops::Range;
+use std::path::Path;
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+
+/// A category of syntax node `--only-in`/`--not-in` can name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeKindCategory {
+    Comment,
+    String,
+}
+
+impl NodeKindCategory {
+    fn parse(name: &str) -> Option<NodeKindCategory> {
+        match name {
+            "comment" | "comments" => Some(NodeKindCategory::Comment),
+            "string" | "strings" => Some(NodeKindCategory::String),
+            _ => None,
+        }
+    }
+
+    fn matches_kind(self, kind: &str) -> bool {
+        match self {
+            NodeKindCategory::Comment => is_comment_kind(kind),
+            NodeKindCategory::String => is_string_kind(kind),
+        }
+    }
+}
+
+/// Resolve `--only-in`/`--not-in` into the byte ranges of `content` a
+/// search should be restricted to.
+///
+/// Unrecognized category names are ignored rather than rejected, the same
+/// as `--watch-events` -- there's no user-facing error path this deep into
+/// a per-file search. Returns the whole file (i.e. no restriction) when
+/// neither flag was given.
+pub(crate) fn visible_ranges(
+    path: &Path,
+    content: &str,
+    only_in: &[String],
+    not_in: &[String],
+) -> Vec<Range<usize>> {
+    if only_in.is_empty() && not_in.is_empty() {
+        return vec![0..content.len()];
+    }
+
+    if !only_in.is_empty() {
+        let categories = parse_categories(only_in);
+        return matching_node_ranges(path, content, &categories);
+    }
+
+    let categories = parse_categories(not_in);
+    let excluded = matching_node_ranges(path, content, &categories);
+    invert_ranges(&excluded, content.len())
+}
+
+fn parse_categories(names: &[String]) -> Vec<NodeKindCategory> {
+    names.iter().filter_map(|n| NodeKindCategory::parse(n)).collect()
+}
+
+/// The merged byte ranges of every node in `content` whose kind matches one
+/// of `categories`. Returns no ranges at all when `path`'s language isn't
+/// supported, since kind membership can't be determined without a parser.
+fn matching_node_ranges(
+    path: &Path,
+    content: &str,
+    categories: &[NodeKindCategory],
+) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let Some(lang) = SupportLang::from_path(path) else { return ranges };
+    if categories.is_empty() {
+        return ranges;
+    }
+
+    macro_rules! walk_with {
+        ($lang_impl:expr) => {{
+            let ast_grep = $lang_impl.ast_grep(content);
+            let root = ast_grep.root();
+            for node in root.dfs() {
+                let kind = node.kind();
+                if categories.iter().any(|c| c.matches_kind(kind.as_ref())) {
+                    ranges.push(node.range());
+                }
+            }
+        }};
+    }
+
+    use SupportLang::*;
+    match lang {
+        Rust => walk_with!(outgrep_ast_language::Rust),
+        JavaScript => walk_with!(outgrep_ast_language::JavaScript),
+        TypeScript => walk_with!(outgrep_ast_language::TypeScript),
+        Tsx => walk_with!(outgrep_ast_language::Tsx),
+        Python => walk_with!(outgrep_ast_language::Python),
+        Java => walk_with!(outgrep_ast_language::Java),
+        Go => walk_with!(outgrep_ast_language::Go),
+        C => walk_with!(outgrep_ast_language::C),
+        Cpp => walk_with!(outgrep_ast_language::Cpp),
+        CSharp => walk_with!(outgrep_ast_language::CSharp),
+        Ruby => walk_with!(outgrep_ast_language::Ruby),
+        Php => walk_with!(outgrep_ast_language::Php),
+        _ => {}
+    }
+
+    ranges.sort_by_key(|r| r.start);
+    merge_ranges(ranges)
+}
+
+/// Merge overlapping/adjacent ranges in `ranges`, which must already be
+/// sorted by start.
+fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(prev) if range.start <= prev.end => {
+                prev.end = prev.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// The byte ranges of `0..len` not covered by `ranges`, which must already
+/// be sorted and non-overlapping (as `merge_ranges` produces).
+fn invert_ranges(ranges: &[Range<usize>], len: usize) -> Vec<Range<usize>> {
+    let mut inverted = Vec::with_capacity(ranges.len() + 1);
+    let mut cursor = 0;
+    for range in ranges {
+        if cursor < range.start {
+            inverted.push(cursor..range.start);
+        }
+        cursor = range.end;
+    }
+    if cursor < len {
+        inverted.push(cursor..len);
+    }
+    inverted
+}
+
+/// Check if a node kind represents a comment, across the languages we
+/// support. Mirrors `ast_extractor::is_comment_kind`.
+fn is_comment_kind(kind: &str) -> bool {
+    matches!(kind, "comment" | "line_comment" | "block_comment")
+}
+
+/// Check if a node kind represents a string literal, across the languages
+/// we support.
+fn is_string_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "string_literal"
+            | "raw_string_literal"
+            | "interpreted_string_literal"
+            | "string"
+            | "string_content"
+            | "template_string"
+            | "encapsed_string"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_in_comments_restricts_to_comment_ranges() {
+        let content = "// hello\nfn f() {}\n";
+        let ranges = visible_ranges(
+            Path::new("f.rs"),
+            content,
+            &["comments".to_string()],
+            &[],
+        );
+        assert_eq!(ranges, vec![0..8]);
+    }
+
+    #[test]
+    fn not_in_strings_excludes_string_ranges() {
+        let content = r#"fn f() { "skip" }"#;
+        let ranges = visible_ranges(
+            Path::new("f.rs"),
+            content,
+            &[],
+            &["strings".to_string()],
+        );
+        // The string literal (including its quotes) is cut out of the
+        // otherwise-visible file.
+        assert_eq!(ranges, vec![0..9, 15..content.len()]);
+    }
+
+    #[test]
+    fn no_flags_returns_whole_file() {
+        let content = "anything at all";
+        let ranges = visible_ranges(Path::new("f.rs"), content, &[], &[]);
+        assert_eq!(ranges, vec![0..content.len()]);
+    }
+
+    #[test]
+    fn unsupported_language_only_in_matches_nothing() {
+        let ranges = visible_ranges(
+            Path::new("f.unknownext"),
+            "text",
+            &["comments".to_string()],
+            &[],
+        );
+        assert!(ranges.is_empty());
+    }
+}
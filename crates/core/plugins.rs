@@ -0,0 +1,203 @@
+/*!
+Runtime plugin system for custom analyzers, for `--plugins-dir`.
+
+Third parties can add per-file analyzers without recompiling outgrep by
+dropping an executable named `og-plugin-<name>` on `PATH`, or into a
+directory named by `--plugins-dir`. Each plugin speaks the same tiny
+subprocess protocol regardless of what it actually analyzes: outgrep
+writes a single-line JSON [`PluginRequest`] to its stdin, and the plugin
+writes a single-line JSON [`PluginSection`] to its stdout before exiting.
+That section is folded into `--tree`/`--analyze` output as its own
+titled block, and included verbatim under its title in `--json` output.
+*/
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use wait_timeout::ChildExt;
+
+/// How long a plugin gets to answer before it's treated as hung and
+/// killed. Plugins are meant to be quick, file-local analyzers, not
+/// long-running services, so this is generous but not unbounded.
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A discovered plugin executable.
+#[derive(Debug, Clone)]
+pub(crate) struct Plugin {
+    /// The plugin's name, taken from its filename with the `og-plugin-`
+    /// prefix (and a Windows `.exe` suffix, if present) stripped.
+    pub(crate) name: String,
+    path: PathBuf,
+}
+
+/// The request outgrep sends to a plugin's stdin, as a single line of
+/// JSON.
+#[derive(Debug, Serialize)]
+struct PluginRequest<'a> {
+    /// Absolute path to the project root being analyzed.
+    root: &'a Path,
+}
+
+/// The response a plugin writes to its stdout, as a single line of JSON.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct PluginSection {
+    /// A short heading for this plugin's output, shown above its data in
+    /// text mode and used as its `--json` key.
+    pub(crate) title: String,
+    /// Arbitrary plugin-defined data, included verbatim in `--json` output.
+    pub(crate) data: serde_json::Value,
+}
+
+/// Find every `og-plugin-*` executable on `PATH`, plus any found directly
+/// inside `plugins_dir` if given.
+///
+/// A name found in an earlier `PATH` entry wins over a later one, or over
+/// `plugins_dir`, mirroring how shells normally resolve `PATH` lookups.
+pub(crate) fn discover(plugins_dir: Option<&Path>) -> Vec<Plugin> {
+    let mut plugins = Vec::new();
+    if let Some(path_var) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path_var) {
+            collect_from_dir(&dir, &mut plugins);
+        }
+    }
+    if let Some(dir) = plugins_dir {
+        collect_from_dir(dir, &mut plugins);
+    }
+    plugins
+}
+
+/// Add every `og-plugin-*` executable directly inside `dir` to `plugins`,
+/// skipping any name already present.
+fn collect_from_dir(dir: &Path, plugins: &mut Vec<Plugin>) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let Some(name) = file_name.strip_prefix("og-plugin-") else {
+            continue;
+        };
+        let name = name.trim_end_matches(".exe").to_string();
+        if name.is_empty() || plugins.iter().any(|p: &Plugin| p.name == name) {
+            continue;
+        }
+        if !is_executable(&path) {
+            continue;
+        }
+        plugins.push(Plugin { name, path });
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Run `plugin` over `root` and return its reported section.
+///
+/// Any failure to spawn the process, write the request, or parse a valid
+/// [`PluginSection`] from its stdout is treated as the plugin having
+/// nothing to report, the same as a plugin that legitimately produces no
+/// output -- a broken or slow plugin should never fail the whole analysis.
+/// A plugin that doesn't answer within [`PLUGIN_TIMEOUT`] is killed and
+/// treated the same way, so a hung plugin can't block analysis forever.
+pub(crate) fn run(plugin: &Plugin, root: &Path) -> Option<PluginSection> {
+    let mut child = Command::new(&plugin.path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let request = serde_json::to_string(&PluginRequest { root }).ok()?;
+    child.stdin.take()?.write_all(request.as_bytes()).ok()?;
+
+    let status = match child.wait_timeout(PLUGIN_TIMEOUT).ok()? {
+        Some(status) => status,
+        None => {
+            // Still running after PLUGIN_TIMEOUT -- it's hung. Kill it and
+            // reap it so it doesn't linger as a zombie, then give up on it.
+            let _ = child.kill();
+            let _ = child.wait();
+            return None;
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+
+    let mut stdout = Vec::new();
+    child.stdout.take()?.read_to_end(&mut stdout).ok()?;
+    serde_json::from_slice(&stdout).ok()
+}
+
+/// Run every plugin in `plugins` over `root` and collect the sections
+/// reported by the ones that produced valid output, in discovery order.
+pub(crate) fn run_all(plugins: &[Plugin], root: &Path) -> Vec<PluginSection> {
+    plugins.iter().filter_map(|p| run(p, root)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovers_plugin_in_extra_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plugin_path = dir.path().join("og-plugin-hello");
+        std::fs::write(&plugin_path, "#!/bin/sh\necho hi\n")
+            .expect("write plugin");
+        make_executable(&plugin_path);
+
+        let plugins = discover(Some(dir.path()));
+        assert!(plugins.iter().any(|p| p.name == "hello"));
+    }
+
+    #[test]
+    fn ignores_non_executable_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plugin_path = dir.path().join("og-plugin-not-executable");
+        std::fs::write(&plugin_path, "not a script").expect("write file");
+
+        let plugins = discover(Some(dir.path()));
+        assert!(plugins.is_empty());
+    }
+
+    #[test]
+    fn runs_a_plugin_and_parses_its_section() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plugin_path = dir.path().join("og-plugin-echo");
+        std::fs::write(
+            &plugin_path,
+            "#!/bin/sh\ncat > /dev/null\necho '{\"title\": \"Echo\", \"data\": {\"ok\": true}}'\n",
+        )
+        .expect("write plugin");
+        make_executable(&plugin_path);
+
+        let plugin = Plugin { name: "echo".to_string(), path: plugin_path };
+        let section =
+            run(&plugin, dir.path()).expect("plugin should report a section");
+        assert_eq!(section.title, "Echo");
+        assert_eq!(section.data["ok"], serde_json::json!(true));
+    }
+
+    #[cfg(unix)]
+    fn make_executable(path: &Path) {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(path).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(path, perms).unwrap();
+    }
+}
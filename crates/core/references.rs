@@ -0,0 +1,165 @@
+/*!
+Implements `og --references IDENT`, a repo-wide find-references lookup.
+
+Like [`crate::definition`], this walks the search paths and parses each file
+with `outgrep-ast-core`, but where `--definition` only reports where `IDENT`
+is *defined*, this reports where it's *used*: as the callee of a call
+expression or as a type reference
+([`crate::diagnostics::find_references`]). Restricting matches to those AST
+node kinds excludes occurrences inside strings and comments for free, since
+neither ever parses as an identifier node, and groups each occurrence by its
+enclosing function/method/type.
+
+Before walking file contents, a first pass finds every file that actually
+*defines* `IDENT` (via [`crate::diagnostics::extract_ast_structure`], the
+same lookup `--definition` uses). If that turns up at least one definition,
+the second pass only parses files that either are one of those definition
+sites or whose imports ([`crate::diagnostics::extract_imports`],
+[`crate::diagnostics::resolve_import`]) plausibly point at one of them,
+which keeps an unrelated identically-named identifier in a file that never
+imports the symbol's module from showing up as a false positive. If `IDENT`
+isn't defined anywhere under the search paths (e.g. it comes from an
+external dependency), every file is scanned as before.
+*/
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{
+    extract_ast_structure, extract_imports, resolve_import,
+};
+use crate::flags::HiArgs;
+
+/// Every file under `args`'s search paths that defines a symbol named
+/// `ident`, per the same AST symbol extraction `--definition` uses.
+fn definition_sites(
+    args: &HiArgs,
+    ident: &str,
+) -> anyhow::Result<HashSet<PathBuf>> {
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut sites = HashSet::new();
+    for haystack in haystacks {
+        let path = haystack.path();
+        let Some(structure) = extract_ast_structure(path) else { continue };
+        let defines_ident = structure
+            .symbols
+            .functions
+            .iter()
+            .chain(&structure.symbols.classes)
+            .chain(&structure.symbols.types)
+            .chain(&structure.symbols.modules)
+            .any(|symbol| symbol.name == ident);
+        if defines_ident {
+            sites.insert(path.to_path_buf());
+        }
+    }
+    Ok(sites)
+}
+
+/// The last path/module segment of each import in `content`, e.g. `config`
+/// for both a Rust `use crate::config::Config;` and a Python
+/// `from app.config import Config`. Compared against `stems_of`'s output to
+/// decide whether a file plausibly imports one of the definition sites.
+fn imported_stems(path: &Path, content: &str) -> HashSet<String> {
+    extract_imports(path, content)
+        .iter()
+        .filter_map(|import| {
+            let resolved = resolve_import(path, &import.module_path);
+            resolved
+                .rsplit(['/', '\\', '.', ':'])
+                .find(|segment| !segment.is_empty())
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// The file-stem (name without extension) of each definition site, used as
+/// the other half of the [`imported_stems`] comparison.
+fn stems_of(sites: &HashSet<PathBuf>) -> HashSet<String> {
+    sites
+        .iter()
+        .filter_map(|path| path.file_stem())
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// Print every call-expression and type-reference occurrence of `ident`
+/// found under `args`'s search paths, respecting the walker's usual ignore
+/// rules. Returns whether any references were found.
+pub(crate) fn run(args: &HiArgs, ident: &str) -> anyhow::Result<bool> {
+    let sites = definition_sites(args, ident)?;
+    let site_stems = stems_of(&sites);
+
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut found_any = false;
+    for haystack in haystacks {
+        let path = haystack.path();
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        // If `ident` is defined somewhere under the search paths, restrict
+        // matches to its definition sites and files that import one of
+        // them; a same-named identifier in an unrelated file is not this
+        // symbol. If it isn't defined anywhere we know of (e.g. it comes
+        // from a dependency outside the search paths), fall back to
+        // scanning everything.
+        if !sites.is_empty()
+            && !sites.contains(path)
+            && imported_stems(path, &content).is_disjoint(&site_stems)
+        {
+            continue;
+        }
+        // Unsupported languages and files that fail to parse have no
+        // references to report, the same as a file with no matches.
+        let Some(references) =
+            crate::diagnostics::find_references(path, &content, ident)
+        else {
+            continue;
+        };
+        if references.is_empty() {
+            continue;
+        }
+        found_any = true;
+
+        if args.json_output() {
+            for reference in &references {
+                let message = serde_json::json!({
+                    "type": "reference",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "kind": reference.kind,
+                        "line_number": reference.line,
+                        "column": reference.column,
+                        "enclosing_symbol": reference.enclosing_symbol,
+                    },
+                });
+                println!("{}", message);
+            }
+        } else {
+            println!("{}", path.display());
+            for reference in &references {
+                let scope = reference
+                    .enclosing_symbol
+                    .as_deref()
+                    .unwrap_or("<module scope>");
+                println!(
+                    "  {}:{} {:?} in {}",
+                    reference.line, reference.column, reference.kind, scope
+                );
+            }
+        }
+    }
+
+    if !found_any && !args.json_output() {
+        println!("No references to '{}' found under the search paths.", ident);
+    }
+    Ok(found_any)
+}
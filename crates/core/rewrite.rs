@@ -0,0 +1,133 @@
+/*!
+AST-aware code rewriting, for `--rewrite`.
+
+Building on the structural matching engine behind `--pattern` (see
+[`crate::astpattern`]), this parses a replacement template containing the
+same metavariables as the pattern (e.g. `$X`, `$$$ARGS`) and, for every AST
+node the pattern matches, substitutes the metavariables it captured to
+produce a rewritten node. Edits are computed against the original source
+text rather than applied through tree-sitter's incremental reparsing, since
+all we need out of this is the final rewritten text, not a live tree.
+*/
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+
+/// One structural match rewritten by a [`RewriteQuery`], expressed as a
+/// plain byte-range edit against the original source.
+#[derive(Debug, Clone)]
+pub(crate) struct RewriteEdit {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) old_text: String,
+    pub(crate) new_text: String,
+}
+
+/// A parsed `--pattern`/`--lang`/`--rewrite` structural rewrite.
+#[derive(Debug, Clone)]
+pub(crate) struct RewriteQuery {
+    lang: SupportLang,
+    pattern: outgrep_ast_core::matcher::Pattern,
+    replacement: String,
+}
+
+impl RewriteQuery {
+    /// Parse `pattern_expr` as a structural pattern for `lang`, paired with
+    /// a `replacement` template that may reference the pattern's
+    /// metavariables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern_expr` doesn't parse as valid syntax for
+    /// `lang`.
+    pub(crate) fn parse(
+        lang: SupportLang,
+        pattern_expr: &str,
+        replacement: &str,
+    ) -> anyhow::Result<RewriteQuery> {
+        let pattern =
+            outgrep_ast_core::matcher::Pattern::try_new(pattern_expr, lang)
+                .map_err(|e| {
+                    anyhow::anyhow!("invalid --pattern for {}: {}", lang, e)
+                })?;
+        Ok(RewriteQuery {
+            lang,
+            pattern,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// Find every match of the pattern in `content` and compute the edit
+    /// that rewrites it, in source order.
+    pub(crate) fn edits(&self, content: &str) -> Vec<RewriteEdit> {
+        let root = self.lang.ast_grep(content);
+        root.root()
+            .find_all(&self.pattern)
+            .map(|node_match| {
+                let edit = node_match.replace_by(self.replacement.as_str());
+                let start = edit.position;
+                let end = edit.position + edit.deleted_length;
+                RewriteEdit {
+                    start,
+                    end,
+                    old_text: content[start..end].to_string(),
+                    new_text: String::from_utf8_lossy(&edit.inserted_text)
+                        .into_owned(),
+                }
+            })
+            .collect()
+    }
+
+    /// Apply every edit found in `content` and return the rewritten source.
+    pub(crate) fn apply(&self, content: &str) -> String {
+        let edits = self.edits(content);
+        let mut out = String::with_capacity(content.len());
+        let mut last = 0;
+        for edit in &edits {
+            out.push_str(&content[last..edit.start]);
+            out.push_str(&edit.new_text);
+            last = edit.end;
+        }
+        out.push_str(&content[last..]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_matched_calls_with_captured_metavar() {
+        let query = RewriteQuery::parse(
+            SupportLang::Rust,
+            "old_name($X)",
+            "new_name($X)",
+        )
+        .unwrap();
+        let content = "fn f() {\n    old_name(1 + 2);\n}\n";
+        let rewritten = query.apply(content);
+        assert_eq!(rewritten, "fn f() {\n    new_name(1 + 2);\n}\n");
+    }
+
+    #[test]
+    fn no_match_leaves_content_unchanged() {
+        let query =
+            RewriteQuery::parse(SupportLang::Rust, "while $C {}", "loop {}")
+                .unwrap();
+        let content = "fn f() {}\n";
+        assert_eq!(query.apply(content), content);
+        assert!(query.edits(content).is_empty());
+    }
+
+    #[test]
+    fn edits_report_matched_and_replacement_text() {
+        let query =
+            RewriteQuery::parse(SupportLang::Rust, "1 + 2", "3").unwrap();
+        let content = "let x = 1 + 2;\n";
+        let edits = query.edits(content);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].old_text, "1 + 2");
+        assert_eq!(edits[0].new_text, "3");
+    }
+}
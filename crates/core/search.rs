@@ -22,14 +22,60 @@ struct Config {
     search_zip: bool,
     binary_implicit: grep::searcher::BinaryDetection,
     binary_explicit: grep::searcher::BinaryDetection,
+    max_filesize: Option<u64>,
     use_ast_context: bool,
+    context_kinds: Vec<String>,
+    context_padding: (usize, usize),
     syntax_highlighting: bool,
+    with_docs: bool,
     semantic_search: bool,
     semantic_model_path: Option<std::path::PathBuf>,
     semantic_model: Option<String>,
     semantic_dimensions: Option<usize>,
     semantic_similarity_threshold: Option<f32>,
     semantic_max_results: Option<usize>,
+    semantic_top_k: Option<usize>,
+    semantic_cluster: Option<usize>,
+    semantic_stream: bool,
+    quit_after_match: bool,
+    semantic_ef_search: Option<usize>,
+    semantic_chunking: grep::searcher::ChunkingStrategy,
+    semantic_chunk_size: Option<usize>,
+    semantic_chunk_overlap: Option<usize>,
+    semantic_backend: grep::searcher::SemanticBackend,
+    semantic_quantize: grep::searcher::SemanticQuantize,
+    semantic_rerank: bool,
+    semantic_rerank_model: Option<String>,
+    semantic_dimension_mismatch: grep::searcher::DimensionMismatchPolicy,
+    semantic_history: Option<String>,
+    semantic_export: Option<std::path::PathBuf>,
+    semantic_import: Option<std::path::PathBuf>,
+    semantic_query: Vec<String>,
+    semantic_query_fusion: grep::searcher::QueryFusion,
+    similar_to: Option<String>,
+    hybrid_search: bool,
+    since: Option<crate::logtime::LogTimestamp>,
+    until: Option<crate::logtime::LogTimestamp>,
+    keypath: Option<crate::keypath::KeyPathQuery>,
+    csv_column: Option<crate::delimited::CsvColumnQuery>,
+    csv_row: bool,
+    ast_pattern: Option<crate::astpattern::AstPatternQuery>,
+    ts_query: Option<std::sync::Arc<crate::tsquery::TsQuery>>,
+    only_in: Vec<String>,
+    not_in: Vec<String>,
+    hex: bool,
+    hex_context: usize,
+    rewrite: Option<crate::rewrite::RewriteQuery>,
+    rewrite_write: bool,
+    rewrite_dry_run: bool,
+    rules: Option<std::sync::Arc<crate::lintrules::LintRuleSet>>,
+    wasm_filter: Option<
+        std::sync::Arc<std::sync::Mutex<crate::wasm_plugin::WasmFilter>>,
+    >,
+    symbol: Option<crate::symbolsearch::SymbolQuery>,
+    ast_multiline: bool,
+    throttle: crate::throttle::Throttle,
+    test_scope: crate::flags::TestScope,
 }
 
 impl Default for Config {
@@ -40,14 +86,59 @@ impl Default for Config {
             search_zip: false,
             binary_implicit: grep::searcher::BinaryDetection::none(),
             binary_explicit: grep::searcher::BinaryDetection::none(),
+            max_filesize: None,
             use_ast_context: false,
+            context_kinds: Vec::new(),
+            context_padding: (0, 0),
             syntax_highlighting: true, // Default to true
+            with_docs: false,
             semantic_search: false,
             semantic_model_path: None,
             semantic_model: None,
             semantic_dimensions: None,
             semantic_similarity_threshold: None,
             semantic_max_results: None,
+            semantic_top_k: None,
+            semantic_cluster: None,
+            semantic_stream: false,
+            quit_after_match: false,
+            semantic_ef_search: None,
+            semantic_chunking: grep::searcher::ChunkingStrategy::default(),
+            semantic_chunk_size: None,
+            semantic_chunk_overlap: None,
+            semantic_backend: grep::searcher::SemanticBackend::default(),
+            semantic_quantize: grep::searcher::SemanticQuantize::default(),
+            semantic_rerank: false,
+            semantic_rerank_model: None,
+            semantic_dimension_mismatch:
+                grep::searcher::DimensionMismatchPolicy::default(),
+            semantic_history: None,
+            semantic_export: None,
+            semantic_import: None,
+            semantic_query: Vec::new(),
+            semantic_query_fusion: grep::searcher::QueryFusion::default(),
+            similar_to: None,
+            hybrid_search: false,
+            since: None,
+            until: None,
+            keypath: None,
+            csv_column: None,
+            csv_row: false,
+            ast_pattern: None,
+            ts_query: None,
+            only_in: Vec::new(),
+            not_in: Vec::new(),
+            hex: false,
+            hex_context: 32,
+            rewrite: None,
+            rewrite_write: false,
+            rewrite_dry_run: false,
+            rules: None,
+            wasm_filter: None,
+            symbol: None,
+            ast_multiline: false,
+            throttle: crate::throttle::Throttle::new(None),
+            test_scope: crate::flags::TestScope::All,
         }
     }
 }
@@ -103,6 +194,9 @@ impl SearchWorkerBuilder {
             searcher,
             printer,
             pattern: self.pattern.clone(),
+            semantic_top_k_matches: Vec::new(),
+            rewrite_edits_written: 0,
+            rewrite_files_written: 0,
         }
     }
 
@@ -182,6 +276,21 @@ impl SearchWorkerBuilder {
         self
     }
 
+    /// Set the maximum file size that semantic search will read directly
+    /// from disk (i.e., outside of the recursive directory walk, which
+    /// already enforces `--max-filesize` on its own). `None` disables the
+    /// limit.
+    ///
+    /// This only affects `--semantic`; the walker's own `--max-filesize`
+    /// handling already covers every other search mode.
+    pub(crate) fn max_filesize(
+        &mut self,
+        size: Option<u64>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.max_filesize = size;
+        self
+    }
+
     /// Set whether to use AST-based enclosing symbol context.
     ///
     /// When enabled, the search worker will use AST parsing to find
@@ -197,6 +306,32 @@ impl SearchWorkerBuilder {
         self
     }
 
+    /// Restrict the AST context shown by `--enclosing-symbol` to the given
+    /// `--context-kind` values ("function", "class", "module", "block").
+    ///
+    /// An empty list (the default) falls back to `default_context_types()`.
+    pub(crate) fn context_kinds(
+        &mut self,
+        kinds: Vec<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.context_kinds = kinds;
+        self
+    }
+
+    /// Set the number of extra lines of padding to show before and after
+    /// each symbol printed by `--enclosing-symbol`, e.g. to capture
+    /// attributes or doc comments sitting just outside the symbol's own
+    /// AST node.
+    ///
+    /// Defaults to `(0, 0)`, i.e. no padding.
+    pub(crate) fn context_padding(
+        &mut self,
+        padding: (usize, usize),
+    ) -> &mut SearchWorkerBuilder {
+        self.config.context_padding = padding;
+        self
+    }
+
     /// Set whether to enable syntax highlighting.
     ///
     /// By default, syntax highlighting is disabled.
@@ -208,6 +343,15 @@ impl SearchWorkerBuilder {
         self
     }
 
+    /// Set whether to include leading doc comments alongside enclosing
+    /// symbols shown by `--enclosing-symbol`.
+    ///
+    /// By default, doc comments are omitted.
+    pub(crate) fn with_docs(&mut self, yes: bool) -> &mut SearchWorkerBuilder {
+        self.config.with_docs = yes;
+        self
+    }
+
     /// Set whether to enable semantic search using vector embeddings.
     ///
     /// By default, semantic search is disabled.
@@ -264,6 +408,437 @@ impl SearchWorkerBuilder {
         self
     }
 
+    /// Set the global `--semantic-top-k` limit.
+    ///
+    /// Unlike `semantic_max_results`, which caps matches within each file,
+    /// this ranks matches across every file searched in the run. When set,
+    /// `search_path_semantic` collects matches into
+    /// `SearchWorker::semantic_top_k_matches` instead of printing them
+    /// immediately; the caller must invoke
+    /// `SearchWorker::finish_semantic_top_k` once the run completes to sort,
+    /// truncate, and print the surviving matches.
+    pub(crate) fn semantic_top_k(
+        &mut self,
+        top_k: Option<usize>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_top_k = top_k;
+        self
+    }
+
+    /// Set the `--semantic-cluster` limit.
+    ///
+    /// When set, each file's semantic matches are grouped into at most this
+    /// many clusters by embedding similarity before printing, with one
+    /// representative match shown per cluster. See
+    /// `grep::searcher::semantic::cluster_matches`.
+    pub(crate) fn semantic_cluster(
+        &mut self,
+        cluster: Option<usize>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_cluster = cluster;
+        self
+    }
+
+    /// Set whether `--semantic-stream` was given.
+    ///
+    /// When true (and `semantic_top_k`, `semantic_cluster` and multi-query
+    /// fusion are all unset), `search_path_semantic` prints each semantic
+    /// match as soon as it's scored instead of collecting, sorting and
+    /// reranking the whole file's matches first.
+    pub(crate) fn semantic_stream(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_stream = yes;
+        self
+    }
+
+    /// Set whether to stop searching after the first match, mirroring
+    /// `--quit-after-match`'s effect on the regex search path.
+    pub(crate) fn quit_after_match(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.quit_after_match = yes;
+        self
+    }
+
+    /// Set the HNSW `ef` parameter used by the semantic index, trading
+    /// search speed for recall.
+    pub(crate) fn semantic_ef_search(
+        &mut self,
+        ef_search: Option<usize>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_ef_search = ef_search;
+        self
+    }
+
+    /// Set the strategy used to split a file's content into chunks before
+    /// each chunk is embedded for semantic search.
+    pub(crate) fn semantic_chunking(
+        &mut self,
+        strategy: grep::searcher::ChunkingStrategy,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_chunking = strategy;
+        self
+    }
+
+    /// Set the target chunk size, in bytes, used by the sliding-window
+    /// chunking strategy.
+    pub(crate) fn semantic_chunk_size(
+        &mut self,
+        size: Option<usize>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_chunk_size = size;
+        self
+    }
+
+    /// Set the overlap, in bytes, between consecutive sliding-window chunks.
+    pub(crate) fn semantic_chunk_overlap(
+        &mut self,
+        overlap: Option<usize>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_chunk_overlap = overlap;
+        self
+    }
+
+    /// Set which execution backend embedding inference runs on. Falls back
+    /// to CPU automatically if the requested backend is unavailable.
+    pub(crate) fn semantic_backend(
+        &mut self,
+        backend: grep::searcher::SemanticBackend,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_backend = backend;
+        self
+    }
+
+    /// Set how stored embeddings are quantized to reduce semantic index
+    /// memory usage. Scoring always dequantizes to `f32` first.
+    pub(crate) fn semantic_quantize(
+        &mut self,
+        quantize: grep::searcher::SemanticQuantize,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_quantize = quantize;
+        self
+    }
+
+    /// Set whether the top ANN candidates get a reranking pass before
+    /// `max_results`/`similarity_threshold` are applied.
+    pub(crate) fn semantic_rerank(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_rerank = yes;
+        self
+    }
+
+    /// Set which model the reranking pass should use, looked up in the
+    /// model registry. `None` uses the reranker's built-in default.
+    pub(crate) fn semantic_rerank_model(
+        &mut self,
+        model: Option<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_rerank_model = model;
+        self
+    }
+
+    /// Set how `--semantic-import` should handle an index whose embeddings
+    /// have a different dimensionality than the currently configured model.
+    pub(crate) fn semantic_dimension_mismatch(
+        &mut self,
+        policy: grep::searcher::DimensionMismatchPolicy,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_dimension_mismatch = policy;
+        self
+    }
+
+    /// Set the Git revision range `--semantic-history` should walk, e.g.
+    /// `HEAD~50..HEAD`. `None` searches only the current file contents.
+    pub(crate) fn semantic_history(
+        &mut self,
+        range: Option<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_history = range;
+        self
+    }
+
+    /// Set the file `--semantic-export` should write the built semantic
+    /// index to, in outgrep's versioned binary format. `None` disables
+    /// exporting.
+    pub(crate) fn semantic_export(
+        &mut self,
+        path: Option<std::path::PathBuf>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_export = path;
+        self
+    }
+
+    /// Set the file `--semantic-import` should load a previously exported
+    /// semantic index from, instead of building one from the searched
+    /// file's contents. `None` disables importing.
+    pub(crate) fn semantic_import(
+        &mut self,
+        path: Option<std::path::PathBuf>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_import = path;
+        self
+    }
+
+    /// Set the list of `--semantic-query` queries. When non-empty, these are
+    /// searched instead of the command-line pattern, with per-query scores
+    /// combined according to `semantic_query_fusion`.
+    pub(crate) fn semantic_query(
+        &mut self,
+        queries: Vec<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_query = queries;
+        self
+    }
+
+    /// Set how `--semantic-query` scores are combined when more than one is
+    /// given. See `--semantic-query-fusion`.
+    pub(crate) fn semantic_query_fusion(
+        &mut self,
+        fusion: grep::searcher::QueryFusion,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.semantic_query_fusion = fusion;
+        self
+    }
+
+    /// Set the query-by-example snippet spec for `--similar-to`, given as
+    /// `FILE` or `FILE:START-END`.
+    pub(crate) fn similar_to(
+        &mut self,
+        similar_to: Option<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.similar_to = similar_to;
+        self
+    }
+
+    /// Set whether to re-rank regex matches by semantic similarity to the
+    /// search pattern.
+    ///
+    /// By default, hybrid search is disabled.
+    pub(crate) fn hybrid_search(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.hybrid_search = yes;
+        self
+    }
+
+    /// Set the `--since` bound: matches on lines timestamped earlier than
+    /// this are dropped. `None` disables the lower bound.
+    pub(crate) fn since(
+        &mut self,
+        since: Option<crate::logtime::LogTimestamp>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.since = since;
+        self
+    }
+
+    /// Set the `--until` bound: matches on lines timestamped later than this
+    /// are dropped. `None` disables the upper bound.
+    pub(crate) fn until(
+        &mut self,
+        until: Option<crate::logtime::LogTimestamp>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.until = until;
+        self
+    }
+
+    /// Set the `--jsonpath`/`--yamlpath` query, if either flag was given.
+    /// When set, searches match the pattern against selected structured
+    /// values instead of lines of text; see [`crate::keypath`].
+    pub(crate) fn keypath(
+        &mut self,
+        keypath: Option<crate::keypath::KeyPathQuery>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.keypath = keypath;
+        self
+    }
+
+    /// Set the `--csv-column` query, if the flag was given. When set,
+    /// searches match the pattern against a single column of a delimited
+    /// file instead of lines of text; see [`crate::delimited`].
+    pub(crate) fn csv_column(
+        &mut self,
+        csv_column: Option<crate::delimited::CsvColumnQuery>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.csv_column = csv_column;
+        self
+    }
+
+    /// Set whether `--csv-column` matches print the whole matching row
+    /// instead of just the selected column's value. Has no effect unless
+    /// `--csv-column` is also set.
+    pub(crate) fn csv_row(&mut self, yes: bool) -> &mut SearchWorkerBuilder {
+        self.config.csv_row = yes;
+        self
+    }
+
+    /// Set the `--pattern`/`--lang` structural query, if `--pattern` was
+    /// given. When set, searches match the pattern against AST nodes
+    /// instead of lines of text; see [`crate::astpattern`].
+    pub(crate) fn ast_pattern(
+        &mut self,
+        ast_pattern: Option<crate::astpattern::AstPatternQuery>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.ast_pattern = ast_pattern;
+        self
+    }
+
+    /// Set the `--ts-query`/`--lang` raw tree-sitter query, if `--ts-query`
+    /// was given. When set, searches report every capture the query
+    /// produces against the parsed tree instead of matching lines of text
+    /// or an ast-grep pattern; see [`crate::tsquery`].
+    pub(crate) fn ts_query(
+        &mut self,
+        ts_query: Option<std::sync::Arc<crate::tsquery::TsQuery>>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.ts_query = ts_query;
+        self
+    }
+
+    /// Set the `--only-in` node kind categories, if any were given. When
+    /// non-empty, searches are restricted to the byte ranges of nodes
+    /// falling in one of these categories; see [`crate::nodekind`].
+    pub(crate) fn only_in(
+        &mut self,
+        only_in: Vec<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.only_in = only_in;
+        self
+    }
+
+    /// Set the `--not-in` node kind categories, if any were given. When
+    /// non-empty, searches exclude the byte ranges of nodes falling in one
+    /// of these categories; see [`crate::nodekind`].
+    pub(crate) fn not_in(
+        &mut self,
+        not_in: Vec<String>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.not_in = not_in;
+        self
+    }
+
+    /// Set whether `--hex` is enabled. When set, matches found in binary
+    /// files are rendered as a hex+ASCII dump instead of raw bytes; see
+    /// [`crate::hexdump`].
+    pub(crate) fn hex(&mut self, yes: bool) -> &mut SearchWorkerBuilder {
+        self.config.hex = yes;
+        self
+    }
+
+    /// Set the number of context bytes shown around each match in a
+    /// `--hex` dump. Has no effect unless `--hex` is also set.
+    pub(crate) fn hex_context(
+        &mut self,
+        bytes: usize,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.hex_context = bytes;
+        self
+    }
+
+    /// Set the `--rewrite` query, if the flag was given. When set, every
+    /// `--pattern` match is rewritten instead of printed; see
+    /// [`crate::rewrite`].
+    pub(crate) fn rewrite(
+        &mut self,
+        rewrite: Option<crate::rewrite::RewriteQuery>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.rewrite = rewrite;
+        self
+    }
+
+    /// Set whether `--rewrite` applies its edits in place instead of only
+    /// previewing them. Has no effect unless `--rewrite` is also set.
+    pub(crate) fn rewrite_write(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.rewrite_write = yes;
+        self
+    }
+
+    /// Set whether `--rewrite` is forced to only preview its edits, even if
+    /// `--write` was also given.
+    pub(crate) fn rewrite_dry_run(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.rewrite_dry_run = yes;
+        self
+    }
+
+    /// Set the `--rules` lint rule set, if the flag was given. When set,
+    /// every file is checked against these rules and violations are
+    /// reported instead of a normal pattern search; see
+    /// [`crate::lintrules`].
+    pub(crate) fn rules(
+        &mut self,
+        rules: Option<std::sync::Arc<crate::lintrules::LintRuleSet>>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.rules = rules;
+        self
+    }
+
+    /// Set the `--wasm-plugin` filter, if the flag was given. When set,
+    /// every match is additionally passed through the loaded WASM module,
+    /// which may keep, rewrite, or drop it; see [`crate::wasm_plugin`].
+    pub(crate) fn wasm_filter(
+        &mut self,
+        wasm_filter: Option<
+            std::sync::Arc<std::sync::Mutex<crate::wasm_plugin::WasmFilter>>,
+        >,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.wasm_filter = wasm_filter;
+        self
+    }
+
+    /// Set the `--symbol` query, if the flag was given. When set, every
+    /// file is matched against symbol definition names instead of a normal
+    /// pattern search; see [`crate::symbolsearch`].
+    pub(crate) fn symbol(
+        &mut self,
+        symbol: Option<crate::symbolsearch::SymbolQuery>,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.symbol = symbol;
+        self
+    }
+
+    /// Set whether `--ast-multiline` is enabled. When set, the pattern is
+    /// matched against each AST symbol's whitespace-normalized text instead
+    /// of `content`'s lines; see [`crate::astmultiline`].
+    pub(crate) fn ast_multiline(
+        &mut self,
+        yes: bool,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.ast_multiline = yes;
+        self
+    }
+
+    /// Set the `--throttle` IO pacing rate, if the flag was given. When
+    /// enabled, [`SearchWorker::search`] paces reads and yields between
+    /// files; see [`crate::throttle`].
+    pub(crate) fn throttle(
+        &mut self,
+        throttle: crate::throttle::Throttle,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.throttle = throttle;
+        self
+    }
+
+    /// Set whether to scope searches to test files, production files, or
+    /// everything (the default), as determined by `TestDetector`.
+    pub(crate) fn test_scope(
+        &mut self,
+        scope: crate::flags::TestScope,
+    ) -> &mut SearchWorkerBuilder {
+        self.config.test_scope = scope;
+        self
+    }
+
     /// Set the search pattern for semantic search operations.
     pub(crate) fn pattern(
         &mut self,
@@ -348,13 +923,54 @@ pub(crate) struct SearchWorker<W> {
     searcher: grep::searcher::Searcher,
     printer: Printer<W>,
     pattern: Option<String>,
+    /// Matches collected instead of printed immediately, when
+    /// `--semantic-top-k` is set. See `finish_semantic_top_k`.
+    semantic_top_k_matches: Vec<GlobalSemanticMatch>,
+    /// Running totals for the `--rewrite --write` end-of-run summary. See
+    /// `finish_rewrite_summary`.
+    rewrite_edits_written: usize,
+    rewrite_files_written: usize,
+}
+
+/// A single semantic match collected across the whole run, so that
+/// `--semantic-top-k` can rank matches globally instead of per file.
+#[derive(Clone, Debug)]
+struct GlobalSemanticMatch {
+    path: std::path::PathBuf,
+    similarity: f32,
+    byte_range: std::ops::Range<usize>,
+    content: String,
+    line_start: Option<usize>,
+    line_end: Option<usize>,
 }
 
 impl<W: WriteColor> SearchWorker<W> {
     /// Execute a search over the given haystack.
+    ///
+    /// When `--throttle` is set, this paces reads to the configured rate
+    /// and yields between files afterward; see [`crate::throttle`]. Every
+    /// dispatch branch in [`SearchWorker::search_dispatch`] shares this one
+    /// pacing step rather than each pacing itself.
     pub(crate) fn search(
         &mut self,
         haystack: &crate::haystack::Haystack,
+    ) -> io::Result<SearchResult> {
+        let result = self.search_dispatch(haystack);
+        if self.config.throttle.is_enabled() && !haystack.is_stdin() {
+            let bytes = std::fs::metadata(haystack.path())
+                .map(|m| m.len())
+                .unwrap_or(0);
+            self.config.throttle.pace(bytes);
+        }
+        result
+    }
+
+    /// The actual search dispatch logic for [`SearchWorker::search`],
+    /// separated out so `--throttle` pacing applies uniformly after any of
+    /// the branches below return, without duplicating it in each one.
+    fn search_dispatch(
+        &mut self,
+        haystack: &crate::haystack::Haystack,
     ) -> io::Result<SearchResult> {
         let bin = if haystack.is_explicit() {
             self.config.binary_explicit.clone()
@@ -364,369 +980,2880 @@ impl<W: WriteColor> SearchWorker<W> {
         let path = haystack.path();
         log::trace!("{}: binary detection: {:?}", path.display(), bin);
 
-        self.searcher.set_binary_detection(bin);
-        if haystack.is_stdin() {
-            self.search_reader(path, &mut io::stdin().lock())
-        } else if self.should_preprocess(path) {
-            self.search_preprocessor(path)
-        } else if self.should_decompress(path) {
-            self.search_decompress(path)
-        } else {
-            self.search_path(path)
+        if !haystack.is_stdin() && !self.path_in_test_scope(path) {
+            return Ok(SearchResult::default());
         }
-    }
 
-    /// Return a mutable reference to the underlying printer.
-    pub(crate) fn printer(&mut self) -> &mut Printer<W> {
-        &mut self.printer
+        self.searcher.set_binary_detection(bin.clone());
+        if let Some(query) = self.config.keypath.clone() {
+            return if haystack.is_stdin() {
+                self.search_keypath_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    query,
+                )
+            } else {
+                self.search_keypath(path, query)
+            };
+        }
+        if let Some(query) = self.config.csv_column.clone() {
+            return if haystack.is_stdin() {
+                self.search_csv_reader(path, &mut io::stdin().lock(), query)
+            } else {
+                self.search_csv(path, query)
+            };
+        }
+        if let Some(query) = self.config.ast_pattern.clone() {
+            return if haystack.is_stdin() {
+                self.search_ast_pattern_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    query,
+                )
+            } else {
+                self.search_ast_pattern(path, query)
+            };
+        }
+        if let Some(query) = self.config.ts_query.clone() {
+            return if haystack.is_stdin() {
+                self.search_ts_query_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    query,
+                )
+            } else {
+                self.search_ts_query(path, query)
+            };
+        }
+        if !self.config.only_in.is_empty() || !self.config.not_in.is_empty() {
+            return if haystack.is_stdin() {
+                self.search_node_filter_reader(path, &mut io::stdin().lock())
+            } else {
+                self.search_node_filter(path)
+            };
+        }
+        if self.config.hex {
+            return if haystack.is_stdin() {
+                self.search_hex_reader(path, &mut io::stdin().lock())
+            } else {
+                self.search_hex(path)
+            };
+        }
+        if let Some(query) = self.config.rewrite.clone() {
+            return if haystack.is_stdin() {
+                self.search_rewrite_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    query,
+                )
+            } else {
+                self.search_rewrite(path, query)
+            };
+        }
+        if let Some(rules) = self.config.rules.clone() {
+            return if haystack.is_stdin() {
+                self.search_lint_reader(path, &mut io::stdin().lock(), &rules)
+            } else {
+                self.search_lint(path, &rules)
+            };
+        }
+        if let Some(filter) = self.config.wasm_filter.clone() {
+            return if haystack.is_stdin() {
+                self.search_wasm_filter_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    &filter,
+                )
+            } else {
+                self.search_wasm_filter(path, &filter)
+            };
+        }
+        if let Some(query) = self.config.symbol.clone() {
+            return if haystack.is_stdin() {
+                self.search_symbol_reader(
+                    path,
+                    &mut io::stdin().lock(),
+                    &query,
+                )
+            } else {
+                self.search_symbol(path, &query)
+            };
+        }
+        if self.config.ast_multiline {
+            return if haystack.is_stdin() {
+                self.search_ast_symbols_reader(path, &mut io::stdin().lock())
+            } else {
+                self.search_ast_symbols(path)
+            };
+        }
+        // Semantic search used to always bypass the walker's ignore rules
+        // and size limits by reading the file straight off disk with its
+        // own `std::fs::read_to_string`, ignoring `--max-filesize`, binary
+        // detection, and preprocessors/decompression entirely. Route it
+        // through the same content-acquisition logic as everything else
+        // instead, unless `--hybrid` is also active, in which case the
+        // hybrid path below (which has its own, separate set of gaps) takes
+        // priority as it already did before this change.
+        if self.config.semantic_search && !self.config.hybrid_search {
+            return if haystack.is_stdin() {
+                self.search_semantic_reader(path, &mut io::stdin().lock(), &bin)
+            } else {
+                self.search_semantic(path, &bin)
+            };
+        }
+        if haystack.is_stdin() {
+            self.search_reader(path, &mut io::stdin().lock())
+        } else if haystack.is_non_regular_file() {
+            // Named pipes (e.g. from shell process substitution or
+            // `mkfifo`), sockets and device files aren't seekable and can't
+            // be memory mapped, so `search_path`'s file-based code path
+            // isn't appropriate for them. Stream them through a plain
+            // reader instead, the same way stdin is handled above.
+            let mut file = std::fs::File::open(path)?;
+            self.search_reader(path, &mut file)
+        } else if self.should_preprocess(path) {
+            self.search_preprocessor(path)
+        } else if self.should_decompress(path) {
+            self.search_decompress(path)
+        } else {
+            self.search_path(path)
+        }
     }
 
-    /// Returns true if and only if the given file path should be
-    /// decompressed before searching.
-    fn should_decompress(&self, path: &Path) -> bool {
-        if !self.config.search_zip {
-            return false;
-        }
-        self.decomp_builder.get_matcher().has_command(path)
+    /// Execute a `--jsonpath`/`--yamlpath` structured search over the file
+    /// at `path`, reporting matches at key-path granularity.
+    fn search_keypath(
+        &mut self,
+        path: &Path,
+        query: crate::keypath::KeyPathQuery,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_keypath_content(path, &content, &query)
     }
 
-    /// Returns true if and only if the given file path should be run through
-    /// the preprocessor.
-    fn should_preprocess(&self, path: &Path) -> bool {
-        if !self.config.preprocessor.is_some() {
-            return false;
-        }
-        if self.config.preprocessor_globs.is_empty() {
-            return true;
-        }
-        !self.config.preprocessor_globs.matched(path, false).is_ignore()
+    /// Same as `search_keypath`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_keypath_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: crate::keypath::KeyPathQuery,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_keypath_content(path, &content, &query)
     }
 
-    /// Search the given file path by first asking the preprocessor for the
-    /// data to search instead of opening the path directly.
-    fn search_preprocessor(
+    /// Select the values `query` matches out of `content` and check each
+    /// one's text against the search pattern, dispatching to whichever
+    /// regex engine is configured.
+    fn search_keypath_content(
         &mut self,
         path: &Path,
+        content: &str,
+        query: &crate::keypath::KeyPathQuery,
     ) -> io::Result<SearchResult> {
-        use std::{fs::File, process::Stdio};
+        use self::PatternMatcher::*;
 
-        let bin = self.config.preprocessor.as_ref().unwrap();
-        let mut cmd = std::process::Command::new(bin);
-        cmd.arg(path).stdin(Stdio::from(File::open(path)?));
+        match self.matcher {
+            RustRegex(ref m) => search_content_keypath(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                query,
+            ),
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => search_content_keypath(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                query,
+            ),
+        }
+    }
 
-        let mut rdr = self.command_builder.build(&mut cmd).map_err(|err| {
-            io::Error::new(
-                io::ErrorKind::Other,
-                format!(
-                    "preprocessor command could not start: '{:?}': {}",
-                    cmd, err,
-                ),
-            )
-        })?;
-        let result = self.search_reader(path, &mut rdr).map_err(|err| {
-            io::Error::new(
-                io::ErrorKind::Other,
-                format!("preprocessor command failed: '{:?}': {}", cmd, err),
-            )
-        });
-        let close_result = rdr.close();
-        let search_result = result?;
-        close_result?;
-        Ok(search_result)
+    /// Execute a `--csv-column` search over the file at `path`, reporting
+    /// matches at data-row granularity.
+    fn search_csv(
+        &mut self,
+        path: &Path,
+        query: crate::delimited::CsvColumnQuery,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_csv_content(path, &content, &query)
     }
 
-    /// Attempt to decompress the data at the given file path and search the
-    /// result. If the given file path isn't recognized as a compressed file,
-    /// then search it without doing any decompression.
-    fn search_decompress(&mut self, path: &Path) -> io::Result<SearchResult> {
-        let mut rdr = self.decomp_builder.build(path)?;
-        let result = self.search_reader(path, &mut rdr);
-        let close_result = rdr.close();
-        let search_result = result?;
-        close_result?;
-        Ok(search_result)
+    /// Same as `search_csv`, but for content read from `rdr` (e.g. stdin)
+    /// rather than a file at `path`.
+    fn search_csv_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: crate::delimited::CsvColumnQuery,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_csv_content(path, &content, &query)
     }
 
-    /// Search the contents of the given file path.
-    fn search_path(&mut self, path: &Path) -> io::Result<SearchResult> {
+    /// Select the column `query` names out of `content` and check each
+    /// row's value against the search pattern, dispatching to whichever
+    /// regex engine is configured.
+    fn search_csv_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        query: &crate::delimited::CsvColumnQuery,
+    ) -> io::Result<SearchResult> {
         use self::PatternMatcher::*;
 
-        let (searcher, printer) = (&mut self.searcher, &mut self.printer);
-        let use_ast_context = self.config.use_ast_context;
-        let syntax_highlighting = self.config.syntax_highlighting;
-        let semantic_search = self.config.semantic_search;
-        let pattern = self.pattern.as_deref();
+        let csv_row = self.config.csv_row;
         match self.matcher {
-            RustRegex(ref m) => search_path_with_context(
+            RustRegex(ref m) => search_content_csv(
                 m,
-                searcher,
-                printer,
+                &mut self.printer,
                 path,
-                use_ast_context,
-                syntax_highlighting,
-                semantic_search,
-                Some(&self.config),
-                pattern,
+                content,
+                query,
+                csv_row,
             ),
             #[cfg(feature = "pcre2")]
-            PCRE2(ref m) => search_path_with_context(
+            PCRE2(ref m) => search_content_csv(
                 m,
-                searcher,
-                printer,
+                &mut self.printer,
                 path,
-                use_ast_context,
-                syntax_highlighting,
-                semantic_search,
-                Some(&self.config),
-                pattern,
+                content,
+                query,
+                csv_row,
             ),
         }
     }
 
-    /// Executes a search on the given reader, which may or may not correspond
-    /// directly to the contents of the given file path. Instead, the reader
-    /// may actually cause something else to be searched (for example, when
-    /// a preprocessor is set or when decompression is enabled). In those
-    /// cases, the file path is used for visual purposes only.
+    /// Execute a `--pattern` structural search over the file at `path`,
+    /// reporting matches at AST-node granularity.
+    fn search_ast_pattern(
+        &mut self,
+        path: &Path,
+        query: crate::astpattern::AstPatternQuery,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_ast_pattern_content(path, &content, &query)
+    }
+
+    /// Same as `search_ast_pattern`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_ast_pattern_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: crate::astpattern::AstPatternQuery,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_ast_pattern_content(path, &content, &query)
+    }
+
+    /// Find every AST node `query` matches in `content` and print it.
     ///
-    /// Generally speaking, this method should only be used when there is no
-    /// other choice. Searching via `search_path` provides more opportunities
-    /// for optimizations (such as memory maps).
-    fn search_reader<R: io::Read>(
+    /// Unlike `--jsonpath`/`--csv-column`, there's no separate regex engine
+    /// involved: the structural pattern itself is the whole match
+    /// condition, so this doesn't dispatch on `self.matcher`.
+    fn search_ast_pattern_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        query: &crate::astpattern::AstPatternQuery,
+    ) -> io::Result<SearchResult> {
+        search_content_ast_pattern(&mut self.printer, path, content, query)
+    }
+
+    /// Execute a `--ts-query` search over the file at `path`, reporting
+    /// every capture the query produces.
+    fn search_ts_query(
+        &mut self,
+        path: &Path,
+        query: std::sync::Arc<crate::tsquery::TsQuery>,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_ts_query_content(path, &content, &query)
+    }
+
+    /// Same as `search_ts_query`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_ts_query_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: std::sync::Arc<crate::tsquery::TsQuery>,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_ts_query_content(path, &content, &query)
+    }
+
+    /// Find every capture `query` produces in `content` and print it.
+    ///
+    /// Like `--pattern`, this doesn't dispatch on `self.matcher`: the raw
+    /// tree-sitter query itself is the whole match condition.
+    fn search_ts_query_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        query: &crate::tsquery::TsQuery,
+    ) -> io::Result<SearchResult> {
+        search_content_ts_query(&mut self.printer, path, content, query)
+    }
+
+    /// Execute a `--only-in`/`--not-in` search over the file at `path`.
+    fn search_node_filter(&mut self, path: &Path) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_node_filter_content(path, &content)
+    }
+
+    /// Same as `search_node_filter`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_node_filter_reader<R: io::Read>(
         &mut self,
         path: &Path,
         rdr: &mut R,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_node_filter_content(path, &content)
+    }
+
+    /// Run the normal `self.matcher` search over `content`, but restricted
+    /// to the byte ranges `--only-in`/`--not-in` say are visible; see
+    /// [`crate::nodekind::visible_ranges`].
+    ///
+    /// Unlike `--pattern`/`--ts-query`, the regex or literal matcher still
+    /// does the actual matching here -- only which parts of the file it
+    /// gets to see is different -- so this does dispatch on `self.matcher`,
+    /// the same as `search_hex_content`.
+    fn search_node_filter_content(
+        &mut self,
+        path: &Path,
+        content: &str,
     ) -> io::Result<SearchResult> {
         use self::PatternMatcher::*;
 
-        let (searcher, printer) = (&mut self.searcher, &mut self.printer);
+        let ranges = crate::nodekind::visible_ranges(
+            path,
+            content,
+            &self.config.only_in,
+            &self.config.not_in,
+        );
         match self.matcher {
-            RustRegex(ref m) => search_reader(m, searcher, printer, path, rdr),
+            RustRegex(ref m) => search_content_node_filter(
+                m,
+                &mut self.searcher,
+                &mut self.printer,
+                path,
+                content,
+                &ranges,
+            ),
             #[cfg(feature = "pcre2")]
-            PCRE2(ref m) => search_reader(m, searcher, printer, path, rdr),
+            PCRE2(ref m) => search_content_node_filter(
+                m,
+                &mut self.searcher,
+                &mut self.printer,
+                path,
+                content,
+                &ranges,
+            ),
         }
     }
-}
 
-/// Search the contents of the given file path using the given matcher,
-/// searcher and printer, with optional AST context mode and semantic search.
-fn search_path_with_context<M: Matcher, W: WriteColor>(
-    matcher: M,
-    searcher: &mut grep::searcher::Searcher,
-    printer: &mut Printer<W>,
-    path: &Path,
-    use_ast_context: bool,
-    syntax_highlighting: bool,
-    semantic_search: bool,
-    semantic_config: Option<&Config>,
-    pattern: Option<&str>,
-) -> io::Result<SearchResult> {
-    if semantic_search {
-        search_path_semantic(matcher, searcher, printer, path, semantic_config, pattern)
-    } else if use_ast_context {
-        search_path_ast_context(
-            matcher,
-            searcher,
-            printer,
-            path,
-            syntax_highlighting,
-        )
-    } else {
-        search_path_standard(matcher, searcher, printer, path)
+    /// Execute a `--wasm-plugin` search over the file at `path`.
+    fn search_wasm_filter(
+        &mut self,
+        path: &Path,
+        filter: &std::sync::Mutex<crate::wasm_plugin::WasmFilter>,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_wasm_filter_content(path, &content, filter)
     }
-}
 
-/// Search using standard ripgrep context.
-fn search_path_standard<M: Matcher, W: WriteColor>(
-    matcher: M,
-    searcher: &mut grep::searcher::Searcher,
-    printer: &mut Printer<W>,
-    path: &Path,
-) -> io::Result<SearchResult> {
-    match *printer {
-        Printer::Standard(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
-            searcher.search_path(&matcher, path, &mut sink)?;
-            Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: sink.stats().map(|s| s.clone()),
-            })
-        }
-        Printer::Summary(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
-            searcher.search_path(&matcher, path, &mut sink)?;
-            Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: sink.stats().map(|s| s.clone()),
-            })
-        }
-        Printer::JSON(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
-            searcher.search_path(&matcher, path, &mut sink)?;
-            Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: Some(sink.stats().clone()),
+    /// Same as `search_wasm_filter`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_wasm_filter_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        filter: &std::sync::Mutex<crate::wasm_plugin::WasmFilter>,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_wasm_filter_content(path, &content, filter)
+    }
+
+    /// Run the normal `self.matcher` search over `content`, passing every
+    /// matched line through `filter` to decide whether it's kept, rewritten,
+    /// or dropped; see [`crate::wasm_plugin`].
+    fn search_wasm_filter_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        filter: &std::sync::Mutex<crate::wasm_plugin::WasmFilter>,
+    ) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+
+        match self.matcher {
+            RustRegex(ref m) => search_content_wasm_filter(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                filter,
+            ),
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => search_content_wasm_filter(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                filter,
+            ),
+        }
+    }
+
+    /// Execute a `--hex` search over the file at `path`.
+    fn search_hex(&mut self, path: &Path) -> io::Result<SearchResult> {
+        let content = std::fs::read(path)?;
+        self.search_hex_content(path, &content)
+    }
+
+    /// Same as `search_hex`, but for content read from `rdr` (e.g. stdin)
+    /// rather than a file at `path`.
+    fn search_hex_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+    ) -> io::Result<SearchResult> {
+        let mut content = Vec::new();
+        rdr.read_to_end(&mut content)?;
+        self.search_hex_content(path, &content)
+    }
+
+    /// If `content` looks like a binary file (i.e. it contains a `NUL`
+    /// byte, the same heuristic `--binary` itself relies on), find every
+    /// match directly in its raw bytes and render each one as a hex+ASCII
+    /// dump. Otherwise, `--hex` has nothing to do: fall back to the normal
+    /// line-oriented search path.
+    fn search_hex_content(
+        &mut self,
+        path: &Path,
+        content: &[u8],
+    ) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+
+        if !content.contains(&0) {
+            return self.search_reader(path, &mut io::Cursor::new(content));
+        }
+
+        let hex_context = self.config.hex_context;
+        match self.matcher {
+            RustRegex(ref m) => search_content_hex(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                hex_context,
+            ),
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => search_content_hex(
+                m,
+                &mut self.printer,
+                path,
+                content,
+                hex_context,
+            ),
+        }
+    }
+
+    /// Execute a `--rewrite` search over the file at `path`.
+    fn search_rewrite(
+        &mut self,
+        path: &Path,
+        query: crate::rewrite::RewriteQuery,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_rewrite_content(path, &content, &query, true)
+    }
+
+    /// Same as `search_rewrite`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`. Since there's nowhere to write
+    /// the result back to, `--write` has no effect here: stdin input is
+    /// always previewed.
+    fn search_rewrite_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: crate::rewrite::RewriteQuery,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_rewrite_content(path, &content, &query, false)
+    }
+
+    /// Find every match of `query`'s pattern in `content` and either
+    /// preview the rewrite as a unified diff (the default, and always when
+    /// `--dry-run` is given) or apply it in place and record it towards
+    /// the end-of-run summary printed by `finish_rewrite_summary`
+    /// (`--write`). `can_write` is false for stdin input, which has
+    /// nowhere to write a rewrite back to.
+    fn search_rewrite_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        query: &crate::rewrite::RewriteQuery,
+        can_write: bool,
+    ) -> io::Result<SearchResult> {
+        let edits = query.edits(content);
+        let has_match = !edits.is_empty();
+        if !has_match {
+            return Ok(SearchResult { has_match, stats: None });
+        }
+
+        let rewritten = query.apply(content);
+        let write = can_write
+            && self.config.rewrite_write
+            && !self.config.rewrite_dry_run;
+        if write {
+            write_atomic(path, &rewritten)?;
+            self.rewrite_edits_written += edits.len();
+            self.rewrite_files_written += 1;
+        }
+        print_rewrite_result(
+            &mut self.printer,
+            path,
+            &content,
+            &rewritten,
+            edits.len(),
+            write,
+        )?;
+        Ok(SearchResult { has_match, stats: None })
+    }
+
+    /// Print the `--rewrite --write` summary of replacements written
+    /// across every file, once the run completes.
+    ///
+    /// This is a no-op unless `--write` is in effect, since previews are
+    /// already printed per file as they're found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the underlying writer fails.
+    pub(crate) fn finish_rewrite_summary(&mut self) -> io::Result<()> {
+        if self.config.rewrite.is_none()
+            || !self.config.rewrite_write
+            || self.config.rewrite_dry_run
+        {
+            return Ok(());
+        }
+        if let Printer::JSON(ref mut p) = self.printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "rewrite_summary",
+                "data": {
+                    "files_written": self.rewrite_files_written,
+                    "edits_written": self.rewrite_edits_written,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!(
+                "--rewrite: {} replacement(s) written across {} file(s)",
+                self.rewrite_edits_written, self.rewrite_files_written
+            );
+        }
+        Ok(())
+    }
+
+    /// Execute a `--rules` lint check over the file at `path`.
+    fn search_lint(
+        &mut self,
+        path: &Path,
+        rules: &crate::lintrules::LintRuleSet,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_lint_content(path, &content, rules)
+    }
+
+    /// Same as `search_lint`, but for content read from `rdr` (e.g. stdin)
+    /// rather than a file at `path`.
+    fn search_lint_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        rules: &crate::lintrules::LintRuleSet,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_lint_content(path, &content, rules)
+    }
+
+    /// Check `content` against every applicable `--rules` rule and print
+    /// each violation.
+    fn search_lint_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        rules: &crate::lintrules::LintRuleSet,
+    ) -> io::Result<SearchResult> {
+        let violations = rules.check(path, content);
+        let has_match = !violations.is_empty();
+        print_lint_violations(&mut self.printer, path, &violations)?;
+        Ok(SearchResult { has_match, stats: None })
+    }
+
+    /// Execute a `--symbol` search over the file at `path`.
+    fn search_symbol(
+        &mut self,
+        path: &Path,
+        query: &crate::symbolsearch::SymbolQuery,
+    ) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_symbol_content(path, &content, query)
+    }
+
+    /// Same as `search_symbol`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_symbol_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        query: &crate::symbolsearch::SymbolQuery,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_symbol_content(path, &content, query)
+    }
+
+    /// Find every symbol definition in `content` named by `query` and
+    /// print it. Files whose language can't be determined from `path`'s
+    /// extension have no symbols to match, the same as any other file with
+    /// no matches.
+    fn search_symbol_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+        query: &crate::symbolsearch::SymbolQuery,
+    ) -> io::Result<SearchResult> {
+        use outgrep_ast_core::Language;
+
+        let matches = match outgrep_ast_language::SupportLang::from_path(path)
+        {
+            Some(lang) => query.matches(lang, content),
+            None => Vec::new(),
+        };
+        let has_match = !matches.is_empty();
+        print_symbol_matches(&mut self.printer, path, &matches)?;
+        Ok(SearchResult { has_match, stats: None })
+    }
+
+    /// Execute an `--ast-multiline` search over the file at `path`.
+    fn search_ast_symbols(&mut self, path: &Path) -> io::Result<SearchResult> {
+        let content = std::fs::read_to_string(path)?;
+        self.search_ast_symbols_content(path, &content)
+    }
+
+    /// Same as `search_ast_symbols`, but for content read from `rdr` (e.g.
+    /// stdin) rather than a file at `path`.
+    fn search_ast_symbols_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+    ) -> io::Result<SearchResult> {
+        let mut content = String::new();
+        rdr.read_to_string(&mut content)?;
+        self.search_ast_symbols_content(path, &content)
+    }
+
+    /// Match the search pattern against each AST symbol's
+    /// whitespace-normalized text instead of `content`'s lines, so a
+    /// pattern spanning a multi-line signature or a formatted call chain
+    /// matches reliably without `(?s)`/`--multiline`; see
+    /// [`crate::astmultiline`].
+    ///
+    /// Files whose language can't be determined from `path`'s extension
+    /// have no symbols to match, the same as any other file with no
+    /// matches.
+    fn search_ast_symbols_content(
+        &mut self,
+        path: &Path,
+        content: &str,
+    ) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+        use outgrep_ast_core::Language;
+
+        let symbols = match outgrep_ast_language::SupportLang::from_path(path)
+        {
+            Some(lang) => crate::astmultiline::ast_symbol_texts(lang, content),
+            None => Vec::new(),
+        };
+        match self.matcher {
+            RustRegex(ref m) => {
+                search_ast_symbol_texts(m, &mut self.printer, path, &symbols)
+            }
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => {
+                search_ast_symbol_texts(m, &mut self.printer, path, &symbols)
+            }
+        }
+    }
+
+    /// Sort, truncate, and print the matches collected by
+    /// `--semantic-top-k` across the whole run.
+    ///
+    /// This is a no-op unless `--semantic-top-k` is set, since matches are
+    /// otherwise printed immediately as each file is searched. The caller
+    /// is responsible for invoking this exactly once, after every haystack
+    /// has been searched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the underlying printer fails.
+    pub(crate) fn finish_semantic_top_k(&mut self) -> io::Result<()> {
+        let Some(top_k) = self.config.semantic_top_k else {
+            return Ok(());
+        };
+        self.semantic_top_k_matches
+            .sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        self.semantic_top_k_matches.truncate(top_k);
+
+        if let Printer::JSON(ref mut p) = self.printer {
+            use std::io::Write;
+
+            for m in self.semantic_top_k_matches.iter() {
+                let message = serde_json::json!({
+                    "type": "semantic_match",
+                    "data": {
+                        "path": {"text": m.path.display().to_string()},
+                        "byte_start": m.byte_range.start,
+                        "byte_end": m.byte_range.end,
+                        "line_start": m.line_start,
+                        "line_end": m.line_end,
+                        "score": m.similarity,
+                        "snippet": m.content,
+                    },
+                });
+                serde_json::to_writer(p.get_mut(), &message)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                p.get_mut().write_all(b"\n")?;
+            }
+        } else {
+            for m in self.semantic_top_k_matches.iter() {
+                println!(
+                    "{}:{}-{}: {:.1}% similarity",
+                    m.path.display(),
+                    m.byte_range.start,
+                    m.byte_range.end,
+                    m.similarity * 100.0
+                );
+                println!("{}", m.content);
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute a semantic search over the given file path, honoring
+    /// `--max-filesize`, binary detection, and `--pre`/`--search-zip` the
+    /// same way a normal (non-semantic) search does.
+    fn search_semantic(
+        &mut self,
+        path: &Path,
+        bin: &grep::searcher::BinaryDetection,
+    ) -> io::Result<SearchResult> {
+        if self.should_preprocess(path) {
+            return self.search_semantic_preprocessor(path, bin);
+        }
+        if self.should_decompress(path) {
+            return self.search_semantic_decompress(path, bin);
+        }
+        if let Some(max_filesize) = self.config.max_filesize {
+            if let Ok(meta) = std::fs::metadata(path) {
+                if meta.len() > max_filesize {
+                    log::trace!(
+                        "{}: skipping semantic search, file exceeds --max-filesize",
+                        path.display()
+                    );
+                    return Ok(SearchResult::default());
+                }
+            }
+        }
+        let bytes = std::fs::read(path)?;
+        self.search_semantic_bytes(path, bytes, bin)
+    }
+
+    /// Run the configured preprocessor over `path` and feed its output into
+    /// semantic search, mirroring `search_preprocessor`.
+    fn search_semantic_preprocessor(
+        &mut self,
+        path: &Path,
+        bin: &grep::searcher::BinaryDetection,
+    ) -> io::Result<SearchResult> {
+        use std::{fs::File, io::Read, process::Stdio};
+
+        let preprocessor = self.config.preprocessor.as_ref().unwrap();
+        let mut cmd = std::process::Command::new(preprocessor);
+        cmd.arg(path).stdin(Stdio::from(File::open(path)?));
+
+        let mut rdr = self.command_builder.build(&mut cmd).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "preprocessor command could not start: '{:?}': {}",
+                    cmd, err,
+                ),
+            )
+        })?;
+        let mut bytes = Vec::new();
+        let result = rdr
+            .read_to_end(&mut bytes)
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("preprocessor command failed: '{:?}': {}", cmd, err),
+                )
+            })
+            .and_then(|_| self.search_semantic_bytes(path, bytes, bin));
+        let close_result = rdr.close();
+        let search_result = result?;
+        close_result?;
+        Ok(search_result)
+    }
+
+    /// Decompress `path` and feed its output into semantic search,
+    /// mirroring `search_decompress`.
+    fn search_semantic_decompress(
+        &mut self,
+        path: &Path,
+        bin: &grep::searcher::BinaryDetection,
+    ) -> io::Result<SearchResult> {
+        use std::io::Read;
+
+        let mut rdr = self.decomp_builder.build(path)?;
+        let mut bytes = Vec::new();
+        let result = rdr
+            .read_to_end(&mut bytes)
+            .and_then(|_| self.search_semantic_bytes(path, bytes, bin));
+        let close_result = rdr.close();
+        let search_result = result?;
+        close_result?;
+        Ok(search_result)
+    }
+
+    /// Run semantic search over data read from an arbitrary reader (used for
+    /// stdin), applying the same binary detection as a normal search of
+    /// stdin would.
+    fn search_semantic_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+        bin: &grep::searcher::BinaryDetection,
+    ) -> io::Result<SearchResult> {
+        let mut bytes = Vec::new();
+        rdr.read_to_end(&mut bytes)?;
+        self.search_semantic_bytes(path, bytes, bin)
+    }
+
+    /// Apply binary detection to `bytes` and, if it passes, run semantic
+    /// search over its text content.
+    fn search_semantic_bytes(
+        &mut self,
+        path: &Path,
+        bytes: Vec<u8>,
+        bin: &grep::searcher::BinaryDetection,
+    ) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+
+        let content = resolve_semantic_content(bin, bytes);
+        let (searcher, printer, top_k_matches) = (
+            &mut self.searcher,
+            &mut self.printer,
+            &mut self.semantic_top_k_matches,
+        );
+        let pattern = self.pattern.as_deref();
+        match self.matcher {
+            RustRegex(ref m) => search_path_semantic(
+                m,
+                searcher,
+                printer,
+                path,
+                content,
+                Some(&self.config),
+                pattern,
+                top_k_matches,
+            ),
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => search_path_semantic(
+                m,
+                searcher,
+                printer,
+                path,
+                content,
+                Some(&self.config),
+                pattern,
+                top_k_matches,
+            ),
+        }
+    }
+
+    /// Return a mutable reference to the underlying printer.
+    pub(crate) fn printer(&mut self) -> &mut Printer<W> {
+        &mut self.printer
+    }
+
+    /// Returns true if and only if the given file path is within the
+    /// configured `--tests-only`/`--no-tests` scope.
+    ///
+    /// This only looks at the path, since the file hasn't been read yet at
+    /// this point; see `TestDetector` for the full set of heuristics used
+    /// once a file's contents are available (e.g. in `--analyze` mode).
+    fn path_in_test_scope(&self, path: &Path) -> bool {
+        use crate::diagnostics::TestDetector;
+        match self.config.test_scope {
+            crate::flags::TestScope::All => true,
+            crate::flags::TestScope::TestsOnly => {
+                TestDetector::is_test_path(path)
+            }
+            crate::flags::TestScope::NoTests => {
+                !TestDetector::is_test_path(path)
+            }
+        }
+    }
+
+    /// Returns true if and only if the given file path should be
+    /// decompressed before searching.
+    fn should_decompress(&self, path: &Path) -> bool {
+        if !self.config.search_zip {
+            return false;
+        }
+        self.decomp_builder.get_matcher().has_command(path)
+    }
+
+    /// Returns true if and only if the given file path should be run through
+    /// the preprocessor.
+    fn should_preprocess(&self, path: &Path) -> bool {
+        if !self.config.preprocessor.is_some() {
+            return false;
+        }
+        if self.config.preprocessor_globs.is_empty() {
+            return true;
+        }
+        !self.config.preprocessor_globs.matched(path, false).is_ignore()
+    }
+
+    /// Search the given file path by first asking the preprocessor for the
+    /// data to search instead of opening the path directly.
+    fn search_preprocessor(
+        &mut self,
+        path: &Path,
+    ) -> io::Result<SearchResult> {
+        use std::{fs::File, process::Stdio};
+
+        let bin = self.config.preprocessor.as_ref().unwrap();
+        let mut cmd = std::process::Command::new(bin);
+        cmd.arg(path).stdin(Stdio::from(File::open(path)?));
+
+        let mut rdr = self.command_builder.build(&mut cmd).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "preprocessor command could not start: '{:?}': {}",
+                    cmd, err,
+                ),
+            )
+        })?;
+        let result = self.search_reader(path, &mut rdr).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("preprocessor command failed: '{:?}': {}", cmd, err),
+            )
+        });
+        let close_result = rdr.close();
+        let search_result = result?;
+        close_result?;
+        Ok(search_result)
+    }
+
+    /// Attempt to decompress the data at the given file path and search the
+    /// result. If the given file path isn't recognized as a compressed file,
+    /// then search it without doing any decompression.
+    fn search_decompress(&mut self, path: &Path) -> io::Result<SearchResult> {
+        let mut rdr = self.decomp_builder.build(path)?;
+        let result = self.search_reader(path, &mut rdr);
+        let close_result = rdr.close();
+        let search_result = result?;
+        close_result?;
+        Ok(search_result)
+    }
+
+    /// Search the contents of the given file path.
+    fn search_path(&mut self, path: &Path) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+
+        let (searcher, printer, top_k_matches) = (
+            &mut self.searcher,
+            &mut self.printer,
+            &mut self.semantic_top_k_matches,
+        );
+        let use_ast_context = self.config.use_ast_context;
+        let context_kinds = self.config.context_kinds.as_slice();
+        let context_padding = self.config.context_padding;
+        let syntax_highlighting = self.config.syntax_highlighting;
+        let with_docs = self.config.with_docs;
+        let semantic_search = self.config.semantic_search;
+        let hybrid_search = self.config.hybrid_search;
+        let pattern = self.pattern.as_deref();
+        match self.matcher {
+            RustRegex(ref m) => search_path_with_context(
+                m,
+                searcher,
+                printer,
+                path,
+                use_ast_context,
+                context_kinds,
+                context_padding,
+                syntax_highlighting,
+                with_docs,
+                semantic_search,
+                hybrid_search,
+                Some(&self.config),
+                pattern,
+                top_k_matches,
+            ),
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => search_path_with_context(
+                m,
+                searcher,
+                printer,
+                path,
+                use_ast_context,
+                context_kinds,
+                context_padding,
+                syntax_highlighting,
+                with_docs,
+                semantic_search,
+                hybrid_search,
+                Some(&self.config),
+                pattern,
+                top_k_matches,
+            ),
+        }
+    }
+
+    /// Executes a search on the given reader, which may or may not correspond
+    /// directly to the contents of the given file path. Instead, the reader
+    /// may actually cause something else to be searched (for example, when
+    /// a preprocessor is set or when decompression is enabled). In those
+    /// cases, the file path is used for visual purposes only.
+    ///
+    /// Generally speaking, this method should only be used when there is no
+    /// other choice. Searching via `search_path` provides more opportunities
+    /// for optimizations (such as memory maps).
+    fn search_reader<R: io::Read>(
+        &mut self,
+        path: &Path,
+        rdr: &mut R,
+    ) -> io::Result<SearchResult> {
+        use self::PatternMatcher::*;
+
+        let (searcher, printer) = (&mut self.searcher, &mut self.printer);
+        let since = self.config.since;
+        let until = self.config.until;
+        match self.matcher {
+            RustRegex(ref m) => {
+                search_reader(m, searcher, printer, path, rdr, since, until)
+            }
+            #[cfg(feature = "pcre2")]
+            PCRE2(ref m) => {
+                search_reader(m, searcher, printer, path, rdr, since, until)
+            }
+        }
+    }
+
+    /// Search a chunk of bytes freshly appended to `path`, as `--tail` reads
+    /// them off the end of a growing file.
+    ///
+    /// This is just `search_reader` under a name that makes sense at the
+    /// call site in `--tail`'s follow loop: `chunk` isn't the whole contents
+    /// of `path`, only whatever has been read since the last time it grew.
+    pub(crate) fn search_tail_chunk(
+        &mut self,
+        path: &Path,
+        chunk: &[u8],
+    ) -> io::Result<SearchResult> {
+        self.search_reader(path, &mut io::Cursor::new(chunk))
+    }
+}
+
+/// Search the contents of the given file path using the given matcher,
+/// searcher and printer, with optional AST context mode and semantic search.
+fn search_path_with_context<M: Matcher, W: WriteColor>(
+    matcher: M,
+    searcher: &mut grep::searcher::Searcher,
+    printer: &mut Printer<W>,
+    path: &Path,
+    use_ast_context: bool,
+    context_kinds: &[String],
+    context_padding: (usize, usize),
+    syntax_highlighting: bool,
+    with_docs: bool,
+    semantic_search: bool,
+    hybrid_search: bool,
+    semantic_config: Option<&Config>,
+    pattern: Option<&str>,
+    top_k_matches: &mut Vec<GlobalSemanticMatch>,
+) -> io::Result<SearchResult> {
+    if hybrid_search {
+        search_path_hybrid(matcher, searcher, path, semantic_config, pattern)
+    } else if semantic_search {
+        // Reached only when `--hybrid` is also active (see
+        // `SearchWorker::search`, which otherwise intercepts semantic mode
+        // earlier to route it through proper content acquisition); fall
+        // back to reading the file directly here, same as before this
+        // function learned to accept prefetched content.
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Failed to read file for semantic search: {}", e),
+            )
+        })?;
+        search_path_semantic(
+            matcher,
+            searcher,
+            printer,
+            path,
+            Some(content),
+            semantic_config,
+            pattern,
+            top_k_matches,
+        )
+    } else if use_ast_context {
+        search_path_ast_context(
+            matcher,
+            searcher,
+            printer,
+            path,
+            context_kinds,
+            context_padding,
+            syntax_highlighting,
+            with_docs,
+        )
+    } else {
+        let since = semantic_config.and_then(|cfg| cfg.since);
+        let until = semantic_config.and_then(|cfg| cfg.until);
+        search_path_standard(matcher, searcher, printer, path, since, until)
+    }
+}
+
+/// Apply the given binary detection strategy to raw bytes read for semantic
+/// search, returning `None` if the content should be skipped entirely (the
+/// same outcome a normal search reaches when it hits `BinaryDetection::quit`
+/// or encounters invalid UTF-8, which semantic search can't meaningfully
+/// embed anyway).
+fn resolve_semantic_content(
+    bin: &grep::searcher::BinaryDetection,
+    mut bytes: Vec<u8>,
+) -> Option<String> {
+    if let Some(quit_byte) = bin.quit_byte() {
+        if bytes.contains(&quit_byte) {
+            return None;
+        }
+    } else if let Some(convert_byte) = bin.convert_byte() {
+        for b in bytes.iter_mut() {
+            if *b == convert_byte {
+                *b = b'\n';
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Search using standard ripgrep context.
+fn search_path_standard<M: Matcher, W: WriteColor>(
+    matcher: M,
+    searcher: &mut grep::searcher::Searcher,
+    printer: &mut Printer<W>,
+    path: &Path,
+    since: Option<crate::logtime::LogTimestamp>,
+    until: Option<crate::logtime::LogTimestamp>,
+) -> io::Result<SearchResult> {
+    match *printer {
+        Printer::Standard(ref mut p) => {
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
+            searcher.search_path(&matcher, path, &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().map(|s| s.clone()),
+            })
+        }
+        Printer::Summary(ref mut p) => {
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
+            searcher.search_path(&matcher, path, &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().map(|s| s.clone()),
+            })
+        }
+        Printer::JSON(ref mut p) => {
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
+            searcher.search_path(&matcher, path, &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: Some(sink.inner.stats().clone()),
+            })
+        }
+    }
+}
+
+/// Wraps a printer's `Sink` to drop matches on lines outside a
+/// `--since`/`--until` window.
+///
+/// Lines that don't start with a timestamp [`crate::logtime::LogTimestamp`]
+/// recognizes are always passed through, since only some lines in a log
+/// (continuation lines in a multi-line stack trace, for example) are
+/// expected to carry one.
+struct TimestampFilterSink<S> {
+    inner: S,
+    since: Option<crate::logtime::LogTimestamp>,
+    until: Option<crate::logtime::LogTimestamp>,
+}
+
+impl<S> TimestampFilterSink<S> {
+    fn new(
+        inner: S,
+        since: Option<crate::logtime::LogTimestamp>,
+        until: Option<crate::logtime::LogTimestamp>,
+    ) -> TimestampFilterSink<S> {
+        TimestampFilterSink { inner, since, until }
+    }
+
+    fn in_window(&self, bytes: &[u8]) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some((ts, _)) = crate::logtime::LogTimestamp::parse_prefix(bytes)
+        else {
+            return true;
+        };
+        if self.since.is_some_and(|since| ts < since) {
+            return false;
+        }
+        if self.until.is_some_and(|until| ts > until) {
+            return false;
+        }
+        true
+    }
+}
+
+impl<S: grep::searcher::Sink> grep::searcher::Sink for TimestampFilterSink<S> {
+    type Error = S::Error;
+
+    fn matched(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        mat: &grep::searcher::SinkMatch<'_>,
+    ) -> Result<bool, S::Error> {
+        if !self.in_window(mat.bytes()) {
+            return Ok(true);
+        }
+        self.inner.matched(searcher, mat)
+    }
+
+    fn context(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        context: &grep::searcher::SinkContext<'_>,
+    ) -> Result<bool, S::Error> {
+        self.inner.context(searcher, context)
+    }
+
+    fn context_break(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+    ) -> Result<bool, S::Error> {
+        self.inner.context_break(searcher)
+    }
+
+    fn binary_data(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        binary_byte_offset: u64,
+    ) -> Result<bool, S::Error> {
+        self.inner.binary_data(searcher, binary_byte_offset)
+    }
+
+    fn begin(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+    ) -> Result<bool, S::Error> {
+        self.inner.begin(searcher)
+    }
+
+    fn finish(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        sink_finish: &grep::searcher::SinkFinish,
+    ) -> Result<(), S::Error> {
+        self.inner.finish(searcher, sink_finish)
+    }
+}
+
+/// Wraps a printer's `Sink` to only forward lines that fall within one of a
+/// set of byte ranges, inserting a `context_break` (the usual `--`
+/// separator) between non-adjacent ranges.
+///
+/// This is how `--enclosing-symbol` shows only its AST-derived symbol
+/// blocks while still routing through the shared printer: the underlying
+/// searcher is run in passthru mode over the whole file (so every line
+/// comes through `matched`/`context` with correct line numbers and byte
+/// offsets), and this sink drops whatever falls outside `ranges`.
+struct SymbolRangeFilterSink<'r, S> {
+    inner: S,
+    ranges: &'r [std::ops::Range<usize>],
+    in_range: bool,
+    printed_any: bool,
+}
+
+impl<'r, S> SymbolRangeFilterSink<'r, S> {
+    fn new(inner: S, ranges: &'r [std::ops::Range<usize>]) -> Self {
+        SymbolRangeFilterSink {
+            inner,
+            ranges,
+            in_range: false,
+            printed_any: false,
+        }
+    }
+
+    fn visible(&self, offset: u64) -> bool {
+        let offset = offset as usize;
+        self.ranges.iter().any(|range| range.contains(&offset))
+    }
+}
+
+impl<'r, S: grep::searcher::Sink> SymbolRangeFilterSink<'r, S> {
+    /// Called just before forwarding a line that's inside a visible range.
+    /// Emits a `context_break` if this range isn't contiguous with the
+    /// previous one shown. Returns `false` if the inner sink wants to stop
+    /// searching.
+    fn enter_range(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+    ) -> Result<bool, S::Error> {
+        if !self.in_range && self.printed_any {
+            if !self.inner.context_break(searcher)? {
+                return Ok(false);
+            }
+        }
+        self.in_range = true;
+        self.printed_any = true;
+        Ok(true)
+    }
+}
+
+impl<'r, S: grep::searcher::Sink> grep::searcher::Sink
+    for SymbolRangeFilterSink<'r, S>
+{
+    type Error = S::Error;
+
+    fn matched(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        mat: &grep::searcher::SinkMatch<'_>,
+    ) -> Result<bool, S::Error> {
+        if !self.visible(mat.absolute_byte_offset()) {
+            self.in_range = false;
+            return Ok(true);
+        }
+        if !self.enter_range(searcher)? {
+            return Ok(false);
+        }
+        self.inner.matched(searcher, mat)
+    }
+
+    fn context(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        context: &grep::searcher::SinkContext<'_>,
+    ) -> Result<bool, S::Error> {
+        if !self.visible(context.absolute_byte_offset()) {
+            self.in_range = false;
+            return Ok(true);
+        }
+        if !self.enter_range(searcher)? {
+            return Ok(false);
+        }
+        self.inner.context(searcher, context)
+    }
+
+    fn context_break(
+        &mut self,
+        _searcher: &grep::searcher::Searcher,
+    ) -> Result<bool, S::Error> {
+        // Passthru searching (used for `--enclosing-symbol`) never produces
+        // its own breaks; ours are synthesized in `enter_range` instead.
+        Ok(true)
+    }
+
+    fn binary_data(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        binary_byte_offset: u64,
+    ) -> Result<bool, S::Error> {
+        self.inner.binary_data(searcher, binary_byte_offset)
+    }
+
+    fn begin(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+    ) -> Result<bool, S::Error> {
+        self.inner.begin(searcher)
+    }
+
+    fn finish(
+        &mut self,
+        searcher: &grep::searcher::Searcher,
+        sink_finish: &grep::searcher::SinkFinish,
+    ) -> Result<(), S::Error> {
+        self.inner.finish(searcher, sink_finish)
+    }
+}
+
+/// Run a `--jsonpath`/`--yamlpath` query over `content` and print every
+/// selected value whose text matches `matcher`.
+///
+/// Unlike a normal search, matches aren't tied to a line: each result is a
+/// key path (e.g. `dependencies.serde.version`) and the value found there,
+/// printed as `path:key.path: value`. `--json` output emits one
+/// `keypath_match` message per match instead, in the same spirit as
+/// `--semantic-top-k`'s bespoke `semantic_match` messages.
+fn search_content_keypath<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    query: &crate::keypath::KeyPathQuery,
+) -> io::Result<SearchResult> {
+    let selected = query.select(content).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })?;
+
+    let mut has_match = false;
+    for (key_path, value) in &selected {
+        let text = crate::keypath::value_text(value);
+        let is_match = matcher.is_match(text.as_bytes()).map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+        if !is_match {
+            continue;
+        }
+        has_match = true;
+
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "keypath_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "key_path": key_path,
+                    "value": value,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!("{}:{}: {}", path.display(), key_path, text);
+        }
+    }
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Run a `--csv-column` query over `content` and print every data row
+/// whose selected column matches `matcher`.
+///
+/// Unlike a normal search, matches aren't tied to a line: each result is a
+/// 1-based data row number (not counting a detected header row) and either
+/// the matching column's value or, if `whole_row` is set, the full row.
+/// `--json` output emits one `csv_match` message per match instead, in the
+/// same spirit as `--jsonpath`'s `keypath_match` messages.
+fn search_content_csv<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    query: &crate::delimited::CsvColumnQuery,
+    whole_row: bool,
+) -> io::Result<SearchResult> {
+    let rows = query.rows(content).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), e),
+        )
+    })?;
+
+    let mut has_match = false;
+    for row in &rows {
+        let is_match =
+            matcher.is_match(row.value.as_bytes()).map_err(|e| {
+                io::Error::new(io::ErrorKind::Other, e.to_string())
+            })?;
+        if !is_match {
+            continue;
+        }
+        has_match = true;
+
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "csv_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "row_number": row.row_number,
+                    "value": row.value,
+                    "line": row.line,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else if whole_row {
+            println!("{}:{}: {}", path.display(), row.row_number, row.line);
+        } else {
+            println!("{}:{}: {}", path.display(), row.row_number, row.value);
+        }
+    }
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Run a `--pattern` structural query over `content` and print every AST
+/// node it matches.
+///
+/// Unlike a normal search, matches aren't tied to a matched byte span
+/// within a line: each result is a whole AST node, labeled with the
+/// line/column it starts at. `--json` output emits one `ast_match` message
+/// per match instead, in the same spirit as `--jsonpath`'s `keypath_match`
+/// messages.
+fn search_content_ast_pattern<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    query: &crate::astpattern::AstPatternQuery,
+) -> io::Result<SearchResult> {
+    let matches = query.matches(content);
+    let has_match = !matches.is_empty();
+
+    for m in &matches {
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "ast_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "line_number": m.line,
+                    "column": m.column,
+                    "text": m.text,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!("{}:{}:{}: {}", path.display(), m.line, m.column, m.text);
+        }
+    }
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Run a `--ts-query` query over `content` and print every capture it
+/// produces.
+///
+/// Like `--pattern`'s `ast_match` messages, matches aren't tied to a byte
+/// span within a line: each result is a captured AST node, labeled with
+/// its capture name and the line/column it starts at. `--json` output
+/// emits one `ts_query_match` message per capture instead.
+fn search_content_ts_query<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    query: &crate::tsquery::TsQuery,
+) -> io::Result<SearchResult> {
+    let captures = query.captures(content);
+    let has_match = !captures.is_empty();
+
+    for c in &captures {
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "ts_query_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "line_number": c.line,
+                    "column": c.column,
+                    "capture": c.name,
+                    "text": c.text,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!(
+                "{}:{}:{}: @{} {}",
+                path.display(),
+                c.line,
+                c.column,
+                c.name,
+                c.text
+            );
+        }
+    }
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Run `matcher` over `content`, restricted to `ranges`, and print results
+/// through `printer` exactly as an ordinary search would.
+///
+/// Reuses [`SymbolRangeFilterSink`], the same mechanism `--symbol` relies on
+/// to restrict a whole-file search to a subset of it: searching the whole
+/// file (rather than each range's own sub-slice) keeps line numbers and
+/// byte offsets correct without any manual bookkeeping.
+fn search_content_node_filter<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    searcher: &mut grep::searcher::Searcher,
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    ranges: &[std::ops::Range<usize>],
+) -> io::Result<SearchResult> {
+    match printer {
+        Printer::Standard(p) => {
+            let mut sink = SymbolRangeFilterSink::new(
+                p.sink_with_path(matcher, path),
+                ranges,
+            );
+            searcher.search_slice(matcher, content.as_bytes(), &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().cloned(),
+            })
+        }
+        Printer::Summary(p) => {
+            let mut sink = SymbolRangeFilterSink::new(
+                p.sink_with_path(matcher, path),
+                ranges,
+            );
+            searcher.search_slice(matcher, content.as_bytes(), &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().cloned(),
+            })
+        }
+        Printer::JSON(p) => {
+            let mut sink = SymbolRangeFilterSink::new(
+                p.sink_with_path(matcher, path),
+                ranges,
+            );
+            searcher.search_slice(matcher, content.as_bytes(), &mut sink)?;
+            Ok(SearchResult {
+                has_match: sink.inner.has_match(),
+                stats: Some(sink.inner.stats().clone()),
             })
         }
     }
 }
 
+/// Run `matcher` over `content` and pass the line surrounding each match
+/// through the loaded `--wasm-plugin` module before printing it.
+///
+/// Like `--hex`/`--jsonpath`, this reports matches with its own bespoke
+/// JSON message (`wasm_match`) rather than the standard printer's per-line
+/// format, since the line printed here may have been rewritten by the
+/// plugin rather than being a verbatim slice of the file.
+fn search_content_wasm_filter<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &str,
+    filter: &std::sync::Mutex<crate::wasm_plugin::WasmFilter>,
+) -> io::Result<SearchResult> {
+    let bytes = content.as_bytes();
+    let mut has_match = false;
+    let mut result = Ok(());
+    matcher
+        .find_iter(bytes, |m| {
+            has_match = true;
+
+            let line_start = bytes[..m.start()]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1);
+            let line_end = bytes[m.end()..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |i| m.end() + i);
+            let line = &bytes[line_start..line_end];
+
+            let outcome =
+                filter.lock().unwrap_or_else(|e| e.into_inner()).apply(line);
+            let kept = match outcome {
+                Ok(kept) => kept,
+                Err(e) => {
+                    result = Err(io::Error::new(io::ErrorKind::Other, e));
+                    return false;
+                }
+            };
+            let Some(line) = kept else { return true };
+            let line = String::from_utf8_lossy(&line);
+
+            if let Printer::JSON(ref mut p) = printer {
+                use std::io::Write;
+
+                let message = serde_json::json!({
+                    "type": "wasm_match",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "line": line,
+                    },
+                });
+                if let Err(e) = serde_json::to_writer(p.get_mut(), &message)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                    .and_then(|_| p.get_mut().write_all(b"\n"))
+                {
+                    result = Err(e);
+                    return false;
+                }
+            } else {
+                println!("{}:{}", path.display(), line);
+            }
+            true
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    result?;
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Run a `--hex` search over the raw bytes of a binary file, rendering each
+/// match found by `matcher` as a hex+ASCII dump instead of printing the
+/// (likely unprintable) bytes of the line it falls in.
+///
+/// Like `--jsonpath`/`--csv-column`, this reports matches with its own
+/// bespoke JSON message (`hex_match`) rather than the standard printer's
+/// per-line format, since a match here has a byte range and a dump instead
+/// of a line and a column.
+fn search_content_hex<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    printer: &mut Printer<W>,
+    path: &Path,
+    content: &[u8],
+    hex_context: usize,
+) -> io::Result<SearchResult> {
+    let mut has_match = false;
+    let mut result = Ok(());
+    matcher
+        .find_iter(content, |m| {
+            has_match = true;
+            let dump = crate::hexdump::dump_window(
+                content,
+                m.start(),
+                m.end(),
+                hex_context,
+            );
+
+            if let Printer::JSON(ref mut p) = printer {
+                use std::io::Write;
+
+                let message = serde_json::json!({
+                    "type": "hex_match",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "start": m.start(),
+                        "end": m.end(),
+                        "dump": dump,
+                    },
+                });
+                if let Err(e) = serde_json::to_writer(p.get_mut(), &message)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                    .and_then(|_| p.get_mut().write_all(b"\n"))
+                {
+                    result = Err(e);
+                    return false;
+                }
+            } else {
+                println!(
+                    "{}:{}-{}:\n{}",
+                    path.display(),
+                    m.start(),
+                    m.end(),
+                    dump
+                );
+            }
+            true
+        })
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+    result?;
+    Ok(SearchResult { has_match, stats: None })
+}
+
+/// Print the result of rewriting one file for `--rewrite`.
+///
+/// If `written` is false (the default, and always with `--dry-run`), this
+/// prints a unified diff between `original` and `rewritten` so the change
+/// can be reviewed before it's applied. If `written` is true, `--write` has
+/// already applied the rewrite to disk, so this prints a short per-file
+/// summary instead. `--json` emits a bespoke `rewrite_match` message in
+/// either case, in the same spirit as `--pattern`'s `ast_match` messages.
+/// Overwrite the file at `path` with `contents` atomically: write to a new
+/// temp file in the same directory, then rename it into place.
+///
+/// `--rewrite --write` used to `std::fs::write` straight over the source
+/// file, which truncates it in place before the new contents are fully
+/// written; a crash, a full disk, or a kill signal mid-write left a
+/// corrupted, half-rewritten file with no way to recover the original. A
+/// rename within the same directory (and therefore the same filesystem) is
+/// atomic, so a reader always sees either the old contents or the new ones,
+/// never a partial write.
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    use std::io::Write;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+    tmp.write_all(contents.as_bytes())?;
+    // The temp file is created with the usual restrictive temp-file
+    // permissions, not the source file's own; without this, persisting it
+    // over `path` would silently change the file's mode (e.g. dropping an
+    // executable bit on a script).
+    if let Ok(metadata) = std::fs::metadata(path) {
+        tmp.as_file().set_permissions(metadata.permissions())?;
+    }
+    tmp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+fn print_rewrite_result<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    original: &str,
+    rewritten: &str,
+    edit_count: usize,
+    written: bool,
+) -> io::Result<()> {
+    if let Printer::JSON(ref mut p) = printer {
+        use std::io::Write;
+
+        let message = serde_json::json!({
+            "type": "rewrite_match",
+            "data": {
+                "path": {"text": path.display().to_string()},
+                "edits": edit_count,
+                "written": written,
+                "rewritten": if written { None } else { Some(rewritten) },
+            },
+        });
+        serde_json::to_writer(p.get_mut(), &message)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        p.get_mut().write_all(b"\n")?;
+        return Ok(());
+    }
+
+    if written {
+        println!("{}: {} replacement(s) written", path.display(), edit_count);
+        return Ok(());
+    }
+
+    use similar::{ChangeTag, TextDiff};
+
+    println!("{}", path.display());
+    let diff = TextDiff::from_lines(original, rewritten);
+    for change in diff.iter_all_changes() {
+        let sign = match change.tag() {
+            ChangeTag::Delete => "-",
+            ChangeTag::Insert => "+",
+            ChangeTag::Equal => " ",
+        };
+        print!("{}{}", sign, change);
+    }
+    Ok(())
+}
+
+/// Print every `--rules` violation found in one file.
+///
+/// Like `--pattern`'s `ast_match` messages, `--json` emits one bespoke
+/// `lint_match` message per violation instead of the standard printer's
+/// per-line format, since a violation is reported at a whole AST node
+/// alongside a rule id/severity/message rather than a matched byte span.
+fn print_lint_violations<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    violations: &[crate::lintrules::LintViolation],
+) -> io::Result<()> {
+    for v in violations {
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "lint_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "rule_id": v.rule_id,
+                    "severity": v.severity,
+                    "message": v.message,
+                    "line_number": v.line,
+                    "column": v.column,
+                    "text": v.text,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!(
+                "{}:{}:{}: [{}] {}: {}",
+                path.display(),
+                v.line,
+                v.column,
+                v.severity,
+                v.rule_id,
+                v.message
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print every `--symbol` match found in one file.
+fn print_symbol_matches<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    matches: &[crate::symbolsearch::SymbolMatch],
+) -> io::Result<()> {
+    for m in matches {
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "symbol_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "symbol_type": m.symbol_type,
+                    "name": m.name,
+                    "line_number": m.line,
+                    "column": m.column,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!(
+                "{}:{}:{}: {} {}",
+                path.display(),
+                m.line,
+                m.column,
+                m.symbol_type,
+                m.name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Run `matcher` against each of `symbols`' normalized text and print the
+/// ones that match, for `--ast-multiline`.
+///
+/// Like `--symbol`, matches aren't tied to a byte span within a line: each
+/// result is a whole AST symbol, labeled with the line/column it starts at
+/// rather than the span the pattern happened to match inside its
+/// normalized text.
+fn search_ast_symbol_texts<M: Matcher, W: WriteColor>(
+    matcher: &M,
+    printer: &mut Printer<W>,
+    path: &Path,
+    symbols: &[crate::astmultiline::AstSymbolText],
+) -> io::Result<SearchResult> {
+    let mut has_match = false;
+    for s in symbols {
+        let is_match = matcher.is_match(s.text.as_bytes()).map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, e.to_string())
+        })?;
+        if !is_match {
+            continue;
+        }
+        has_match = true;
+
+        if let Printer::JSON(ref mut p) = printer {
+            use std::io::Write;
+
+            let message = serde_json::json!({
+                "type": "ast_symbol_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "symbol_type": s.symbol_type,
+                    "name": s.name,
+                    "line_number": s.line,
+                    "column": s.column,
+                    "text": s.text,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        } else {
+            println!(
+                "{}:{}:{}: {} {}",
+                path.display(),
+                s.line,
+                s.column,
+                s.symbol_type,
+                s.name
+            );
+        }
+    }
+    Ok(SearchResult { has_match, stats: None })
+}
+
 /// Search using semantic vector embeddings.
+///
+/// `prefetched_content` is the file's content as already resolved by the
+/// caller (honoring `--max-filesize`, binary detection, `--pre`, and
+/// `--search-zip`). `None` means the caller already determined the content
+/// should be skipped (e.g. detected as binary). This function still has to
+/// run in that case rather than short-circuit earlier, since
+/// `--semantic-import` doesn't need file content at all.
 fn search_path_semantic<M: Matcher, W: WriteColor>(
     _matcher: M,
     _searcher: &mut grep::searcher::Searcher,
-    _printer: &mut Printer<W>,
+    printer: &mut Printer<W>,
+    path: &Path,
+    prefetched_content: Option<String>,
+    semantic_config: Option<&Config>,
+    pattern: Option<&str>,
+    top_k_matches: &mut Vec<GlobalSemanticMatch>,
+) -> io::Result<SearchResult> {
+    use grep::searcher::semantic::{
+        build_index, chunk_content, generate_embeddings_parallel,
+    };
+    use grep::searcher::{
+        create_ast_calculator_for_file, default_context_types,
+        is_supported_file, SemanticSearcher,
+    };
+
+    let config = build_semantic_config(semantic_config);
+    let import_path =
+        semantic_config.and_then(|cfg| cfg.semantic_import.as_deref());
+    let export_path =
+        semantic_config.and_then(|cfg| cfg.semantic_export.as_deref());
+
+    let mut stats = grep::printer::Stats::new();
+
+    // `--semantic-import` loads a previously exported index instead of
+    // re-embedding the file. The imported chunks weren't necessarily
+    // extracted from `path` on this machine, so line numbers can't be
+    // resolved against it; matches fall back to byte-offset-only reporting
+    // in that mode (see the `content` checks below).
+    let (index, content) = if let Some(import_path) = import_path {
+        let index = grep::searcher::semantic::import_index(import_path, &config)
+            .map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Failed to import semantic index: {}", e),
+                )
+            })?;
+        (index, None)
+    } else {
+        // Check if this file type supports semantic search
+        if !is_supported_file(path) {
+            return Ok(SearchResult { has_match: false, stats: None });
+        }
+
+        // The caller already read (and, for a real file, decided whether to
+        // skip) the content; a file search with no prefetched content means
+        // it was filtered out as binary or oversized upstream.
+        let content = match prefetched_content {
+            Some(content) => content,
+            None => {
+                return Ok(SearchResult { has_match: false, stats: None })
+            }
+        };
+
+        // Create AST calculator to extract functions
+        let ast_calculator = create_ast_calculator_for_file(
+            path,
+            &content,
+            Some(default_context_types()),
+        )
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("AST parsing failed: {}", e),
+            )
+        })?;
+
+        // Split the file into chunks according to the configured strategy (one
+        // chunk per symbol by default, a sliding window, or the whole file),
+        // then embed each chunk in parallel across all available cores.
+        let chunks = chunk_content(
+            &content,
+            Some(&ast_calculator),
+            config.chunking_strategy,
+            config.chunk_window_size,
+            config.chunk_window_overlap,
+        );
+        let snippets: Vec<String> =
+            chunks.iter().map(|c| c.content.clone()).collect();
+        let embed_started = std::time::Instant::now();
+        let embedded = generate_embeddings_parallel(&snippets, &config);
+        stats.add_embedding_elapsed(embed_started.elapsed());
+        stats.add_embeddings_generated(embedded.len() as u64);
+
+        let embeddings = embedded
+            .into_iter()
+            .zip(chunks)
+            .map(|(embedding, chunk)| {
+                (
+                    embedding,
+                    chunk.range,
+                    chunk.content,
+                    Some(path.to_path_buf()),
+                )
+            })
+            .collect();
+
+        // Build semantic index
+        (build_index(embeddings, &config), Some(content))
+    };
+
+    // `--semantic-export` writes out whatever index this search run built
+    // (or re-exports an imported one, which is a harmless no-op passthrough).
+    // TODO: this only captures the most recently searched file; a true
+    // multi-file aggregate index (one export covering a whole repository)
+    // needs a caller-level accumulator, since semantic search here runs
+    // per-file rather than over a workspace-wide index.
+    if let Some(export_path) = export_path {
+        grep::searcher::semantic::export_index(&index, &config, export_path)
+            .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("Failed to export semantic index: {}", e),
+            )
+        })?;
+    }
+
+    let similarity_threshold = config.similarity_threshold;
+    // `cluster_matches` needs its own config to re-embed matches with, since
+    // `SemanticSearcher` takes ownership of `config` below.
+    let cluster_config = config.clone();
+
+    // Create searcher and perform search
+    let mut semantic_searcher = SemanticSearcher::new(config);
+    semantic_searcher.set_index(index);
+
+    // `--similar-to` overrides the search pattern as the query: we embed the
+    // referenced snippet and rank the index by similarity to it instead of
+    // to literal query text.
+    let similar_to_snippet = semantic_config
+        .and_then(|cfg| cfg.similar_to.as_deref())
+        .map(read_similar_to_snippet)
+        .transpose()?;
+    let query = similar_to_snippet.as_deref().or(pattern).unwrap_or("search");
+    let semantic_queries = semantic_config
+        .map(|cfg| cfg.semantic_query.as_slice())
+        .unwrap_or(&[]);
+    let query_fusion = semantic_config
+        .map(|cfg| cfg.semantic_query_fusion)
+        .unwrap_or_default();
+    let semantic_top_k_cfg =
+        semantic_config.and_then(|cfg| cfg.semantic_top_k);
+    let semantic_cluster_cfg =
+        semantic_config.and_then(|cfg| cfg.semantic_cluster);
+    // `--semantic-stream` only applies when nothing else needs the full
+    // candidate set first: `--semantic-top-k` ranks across the whole run,
+    // `--semantic-cluster` groups the whole file's matches, and multi-query
+    // fusion scores every query against every candidate before fusing.
+    let stream =
+        semantic_config.map(|cfg| cfg.semantic_stream).unwrap_or(false)
+            && semantic_top_k_cfg.is_none()
+            && semantic_cluster_cfg.is_none()
+            && semantic_queries.is_empty();
+
+    let mut match_count = 0u64;
+    let has_match = if stream {
+        let quit_after_match =
+            semantic_config.map(|cfg| cfg.quit_after_match).unwrap_or(false);
+        let mut has_match = false;
+        let mut write_err = None;
+        semantic_searcher.search_streaming(query, |semantic_match| {
+            has_match = true;
+            match_count += 1;
+            let printed = print_semantic_match_streaming(
+                printer,
+                path,
+                &semantic_match,
+                &content,
+                similarity_threshold,
+            );
+            if let Err(e) = printed {
+                write_err = Some(e);
+                return false;
+            }
+            !quit_after_match
+        });
+        if let Some(e) = write_err {
+            return Err(e);
+        }
+        has_match
+    } else {
+        // `--semantic-query` (possibly repeated) takes precedence over
+        // `--similar-to`, which in turn takes precedence over the
+        // command-line pattern.
+        let matches = if !semantic_queries.is_empty() {
+            semantic_searcher.search_multi(semantic_queries, query_fusion)
+        } else {
+            semantic_searcher.search(query)
+        };
+        let has_match = !matches.is_empty();
+        match_count = matches.len() as u64;
+        print_semantic_matches_batch(
+            printer,
+            path,
+            matches,
+            &content,
+            similarity_threshold,
+            semantic_top_k_cfg,
+            semantic_cluster_cfg,
+            &cluster_config,
+            top_k_matches,
+        )?;
+        has_match
+    };
+
+    // A semantic "match" is a whole chunk (typically one AST symbol), so we
+    // treat each one as a single matched line for `--stats` purposes -- there
+    // isn't a per-line match count to report the way a literal search has.
+    stats.add_searches(1);
+    if has_match {
+        stats.add_searches_with_match(1);
+    }
+    stats.add_matches(match_count);
+    stats.add_matched_lines(match_count);
+    if let Some(ref content) = content {
+        stats.add_bytes_searched(content.len() as u64);
+    }
+
+    let history_has_match = if let Some(range) =
+        semantic_config.and_then(|cfg| cfg.semantic_history.as_deref())
+    {
+        search_semantic_history(
+            path,
+            range,
+            query,
+            similarity_threshold,
+            printer,
+            &mut stats,
+        )?
+    } else {
+        false
+    };
+
+    Ok(SearchResult {
+        has_match: has_match || history_has_match,
+        stats: Some(stats),
+    })
+}
+
+/// Print one semantic match the instant it's found, for `--semantic-stream`.
+///
+/// Unlike `print_semantic_matches_batch`, this writes through
+/// `printer.get_mut()` with fallible `write!`/`writeln!` calls rather than
+/// `println!`, so a broken output pipe (e.g. piping into `head`) is reported
+/// as an `io::Error` the caller can act on instead of panicking.
+fn print_semantic_match_streaming<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    semantic_match: &grep::searcher::semantic::SemanticMatch,
+    content: &Option<String>,
+    similarity_threshold: f32,
+) -> io::Result<()> {
+    use std::io::Write;
+
+    if let Printer::JSON(ref mut p) = *printer {
+        let line_start = content
+            .as_deref()
+            .map(|c| byte_to_line(c, semantic_match.byte_range.start));
+        let line_end = content
+            .as_deref()
+            .map(|c| byte_to_line(c, semantic_match.byte_range.end));
+        let message = serde_json::json!({
+            "type": "semantic_match",
+            "data": {
+                "path": {"text": path.display().to_string()},
+                "byte_start": semantic_match.byte_range.start,
+                "byte_end": semantic_match.byte_range.end,
+                "line_start": line_start,
+                "line_end": line_end,
+                "score": semantic_match.similarity,
+                "snippet": semantic_match.content,
+                "similarity_threshold": similarity_threshold,
+            },
+        });
+        serde_json::to_writer(p.get_mut(), &message)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        p.get_mut().write_all(b"\n")
+    } else {
+        let out = printer.get_mut();
+        writeln!(
+            out,
+            "{}:{}-{}: {:.1}% similarity",
+            path.display(),
+            semantic_match.byte_range.start,
+            semantic_match.byte_range.end,
+            semantic_match.similarity * 100.0
+        )?;
+        writeln!(out, "{}", semantic_match.content)
+    }
+}
+
+/// Print a file's already-scored-and-sorted semantic matches, handling
+/// `--semantic-top-k` deferral and `--semantic-cluster` grouping.
+///
+/// This is the non-streaming counterpart to
+/// `print_semantic_match_streaming`: it needs the full `matches` list up
+/// front, since both top-k ranking and clustering compare matches against
+/// each other rather than print one at a time.
+fn print_semantic_matches_batch<W: WriteColor>(
+    printer: &mut Printer<W>,
+    path: &Path,
+    matches: Vec<grep::searcher::semantic::SemanticMatch>,
+    content: &Option<String>,
+    similarity_threshold: f32,
+    semantic_top_k: Option<usize>,
+    semantic_cluster: Option<usize>,
+    cluster_config: &grep::searcher::semantic::SemanticConfig,
+    top_k_matches: &mut Vec<GlobalSemanticMatch>,
+) -> io::Result<()> {
+    if matches.is_empty() {
+        return Ok(());
+    }
+
+    if semantic_top_k.is_some() {
+        // `--semantic-top-k` ranks matches across the whole run, so defer
+        // printing until every file has been searched; see
+        // `SearchWorker::finish_semantic_top_k`.
+        for semantic_match in matches.iter() {
+            let line_start = content
+                .as_deref()
+                .map(|c| byte_to_line(c, semantic_match.byte_range.start));
+            let line_end = content
+                .as_deref()
+                .map(|c| byte_to_line(c, semantic_match.byte_range.end));
+            top_k_matches.push(GlobalSemanticMatch {
+                path: path.to_path_buf(),
+                similarity: semantic_match.similarity,
+                byte_range: semantic_match.byte_range.clone(),
+                content: semantic_match.content.clone(),
+                line_start,
+                line_end,
+            });
+        }
+        return Ok(());
+    }
+
+    if let Some(k) = semantic_cluster {
+        // `--semantic-cluster` summarizes a large result set down to one
+        // representative match per cluster; see `cluster_matches`.
+        let clusters = grep::searcher::semantic::cluster_matches(
+            matches,
+            k,
+            cluster_config,
+        );
+        if let Printer::JSON(ref mut p) = *printer {
+            use std::io::Write;
+
+            for cluster in clusters.iter() {
+                let semantic_match = &cluster.representative;
+                let line_start = content.as_deref().map(|c| {
+                    byte_to_line(c, semantic_match.byte_range.start)
+                });
+                let line_end = content.as_deref().map(|c| {
+                    byte_to_line(c, semantic_match.byte_range.end)
+                });
+                let message = serde_json::json!({
+                    "type": "semantic_match",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "byte_start": semantic_match.byte_range.start,
+                        "byte_end": semantic_match.byte_range.end,
+                        "line_start": line_start,
+                        "line_end": line_end,
+                        "score": semantic_match.similarity,
+                        "snippet": semantic_match.content,
+                        "similarity_threshold": similarity_threshold,
+                        "cluster_size": cluster.size,
+                    },
+                });
+                serde_json::to_writer(p.get_mut(), &message)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                p.get_mut().write_all(b"\n")?;
+            }
+        } else {
+            for cluster in clusters.iter() {
+                let semantic_match = &cluster.representative;
+                println!(
+                    "{}:{}-{}: {:.1}% similarity (cluster of {})",
+                    path.display(),
+                    semantic_match.byte_range.start,
+                    semantic_match.byte_range.end,
+                    semantic_match.similarity * 100.0,
+                    cluster.size
+                );
+                println!("{}", semantic_match.content);
+            }
+        }
+    } else if let Printer::JSON(ref mut p) = *printer {
+        use std::io::Write;
+
+        for semantic_match in matches.iter() {
+            let line_start = content
+                .as_deref()
+                .map(|c| byte_to_line(c, semantic_match.byte_range.start));
+            let line_end = content
+                .as_deref()
+                .map(|c| byte_to_line(c, semantic_match.byte_range.end));
+            let message = serde_json::json!({
+                "type": "semantic_match",
+                "data": {
+                    "path": {"text": path.display().to_string()},
+                    "byte_start": semantic_match.byte_range.start,
+                    "byte_end": semantic_match.byte_range.end,
+                    "line_start": line_start,
+                    "line_end": line_end,
+                    "score": semantic_match.similarity,
+                    "snippet": semantic_match.content,
+                    "similarity_threshold": similarity_threshold,
+                },
+            });
+            serde_json::to_writer(p.get_mut(), &message)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+            p.get_mut().write_all(b"\n")?;
+        }
+    } else {
+        for semantic_match in matches.iter() {
+            println!(
+                "{}:{}-{}: {:.1}% similarity",
+                path.display(),
+                semantic_match.byte_range.start,
+                semantic_match.byte_range.end,
+                semantic_match.similarity * 100.0
+            );
+            println!("{}", semantic_match.content);
+        }
+    }
+
+    Ok(())
+}
+
+/// Walk `range` (a Git revspec like `HEAD~50..HEAD`) and run `--semantic`
+/// over the version of `path` at each commit, so `--semantic-history` can
+/// surface when a concept was introduced or removed rather than just where
+/// it lives in the working tree today.
+///
+/// Revisions where `path` doesn't exist (it was added or removed partway
+/// through the range) are skipped rather than treated as an error, since
+/// that's the expected common case for any sufficiently long range.
+fn search_semantic_history<W: WriteColor>(
+    path: &Path,
+    range: &str,
+    query: &str,
+    similarity_threshold: f32,
+    printer: &mut Printer<W>,
+    stats: &mut grep::printer::Stats,
+) -> io::Result<bool> {
+    use grep::searcher::semantic::{
+        build_index, chunk_content, generate_embeddings_parallel,
+    };
+    use grep::searcher::{create_ast_calculator_for_file, default_context_types, SemanticSearcher};
+
+    let analyzer = crate::diagnostics::GitAnalyzer::new(
+        path.parent().unwrap_or_else(|| Path::new(".")),
+    );
+    if !analyzer.is_git_repo() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "--semantic-history requires {} to be inside a Git repository",
+                path.display()
+            ),
+        ));
+    }
+
+    let revisions = analyzer.list_revisions_in_range(range).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("--semantic-history: invalid range {range:?}: {err}"),
+        )
+    })?;
+
+    let mut has_match = false;
+    for revision in &revisions {
+        let content = match analyzer.get_file_at_revision(path, revision) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+
+        let ast_calculator = match create_ast_calculator_for_file(
+            path,
+            &content,
+            Some(default_context_types()),
+        ) {
+            Ok(calculator) => calculator,
+            Err(_) => continue,
+        };
+
+        let config = grep::searcher::SemanticConfig {
+            similarity_threshold,
+            ..grep::searcher::SemanticConfig::default()
+        };
+        let chunks = chunk_content(
+            &content,
+            Some(&ast_calculator),
+            config.chunking_strategy,
+            config.chunk_window_size,
+            config.chunk_window_overlap,
+        );
+        let snippets: Vec<String> =
+            chunks.iter().map(|c| c.content.clone()).collect();
+        let embed_started = std::time::Instant::now();
+        let embedded = generate_embeddings_parallel(&snippets, &config);
+        stats.add_embedding_elapsed(embed_started.elapsed());
+        stats.add_embeddings_generated(embedded.len() as u64);
+        // `None` for source_path: this indexes a historical revision's
+        // content, not what's currently on disk at `path`, so it isn't a
+        // candidate for `--semantic-gc` to tombstone by path existence.
+        let embeddings = embedded
+            .into_iter()
+            .zip(chunks)
+            .map(|(embedding, chunk)| {
+                (embedding, chunk.range, chunk.content, None)
+            })
+            .collect();
+        let index = build_index(embeddings, &config);
+
+        let mut semantic_searcher = SemanticSearcher::new(config);
+        semantic_searcher.set_index(index);
+        let matches = semantic_searcher.search(query);
+        stats.add_searches(1);
+        stats.add_bytes_searched(content.len() as u64);
+        if matches.is_empty() {
+            continue;
+        }
+        has_match = true;
+        stats.add_searches_with_match(1);
+        stats.add_matches(matches.len() as u64);
+        stats.add_matched_lines(matches.len() as u64);
+
+        if let Printer::JSON(ref mut p) = *printer {
+            use std::io::Write;
+            for semantic_match in matches.iter() {
+                let line_start = byte_to_line(&content, semantic_match.byte_range.start);
+                let line_end = byte_to_line(&content, semantic_match.byte_range.end);
+                let message = serde_json::json!({
+                    "type": "semantic_match",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "commit": revision,
+                        "byte_start": semantic_match.byte_range.start,
+                        "byte_end": semantic_match.byte_range.end,
+                        "line_start": line_start,
+                        "line_end": line_end,
+                        "score": semantic_match.similarity,
+                        "snippet": semantic_match.content,
+                        "similarity_threshold": similarity_threshold,
+                    },
+                });
+                serde_json::to_writer(p.get_mut(), &message)
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+                p.get_mut().write_all(b"\n")?;
+            }
+        } else {
+            for semantic_match in matches.iter() {
+                println!(
+                    "{}@{}:{}-{}: {:.1}% similarity",
+                    path.display(),
+                    revision,
+                    semantic_match.byte_range.start,
+                    semantic_match.byte_range.end,
+                    semantic_match.similarity * 100.0
+                );
+                println!("{}", semantic_match.content);
+            }
+        }
+    }
+
+    Ok(has_match)
+}
+
+/// Search using hybrid mode: run the literal regex matcher as usual, then
+/// re-rank the matches it finds by embedding similarity to the query.
+///
+/// Unlike `search_path_semantic`, this never surfaces a match the regex
+/// wouldn't have found on its own -- it only reorders the matches and
+/// reports a similarity score alongside each one.
+fn search_path_hybrid<M: Matcher>(
+    matcher: M,
+    searcher: &mut grep::searcher::Searcher,
     path: &Path,
     semantic_config: Option<&Config>,
     pattern: Option<&str>,
 ) -> io::Result<SearchResult> {
-    use grep::searcher::semantic::{build_index, generate_embedding};
-    use grep::searcher::{
-        create_ast_calculator_for_file, default_context_types,
-        is_supported_file, SemanticSearcher,
-    };
+    use grep::searcher::semantic::{cosine_similarity, generate_embedding};
 
-    // Check if this file type supports semantic search
-    if !is_supported_file(path) {
-        return Ok(SearchResult { has_match: false, stats: None });
+    let mut raw_matches = Vec::new();
+    {
+        let mut collector = MatchCollector::new(&mut raw_matches);
+        searcher.search_path(&matcher, path, &mut collector)?;
     }
 
-    // Read the file content
-    let content = std::fs::read_to_string(path).map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("Failed to read file for semantic search: {}", e),
-        )
-    })?;
+    if raw_matches.is_empty() {
+        let mut stats = grep::printer::Stats::new();
+        stats.add_searches(1);
+        return Ok(SearchResult { has_match: false, stats: Some(stats) });
+    }
+    let raw_match_count = raw_matches.len() as u64;
 
-    // Create AST calculator to extract functions
-    let ast_calculator = create_ast_calculator_for_file(
-        path,
-        &content,
-        Some(default_context_types()),
-    )
-    .map_err(|e| {
+    let content = std::fs::read_to_string(path).map_err(|e| {
         io::Error::new(
             io::ErrorKind::InvalidInput,
-            format!("AST parsing failed: {}", e),
+            format!("Failed to read file for hybrid search: {}", e),
         )
     })?;
 
-    // Extract individual functions using AST
     let config = build_semantic_config(semantic_config);
-    let mut embeddings = Vec::new();
-
-    // Extract all symbols by scanning through the file content
-    let mut symbols = Vec::new();
-    let mut unique_symbols = std::collections::HashSet::new();
-
-    // Scan through the file content to find all symbols
-    // We'll sample positions throughout the file to discover symbols
-    let sample_positions: Vec<usize> = (0..content.len())
-        .step_by(50) // Sample every 50 bytes
+    let query = pattern.unwrap_or("search");
+    let query_embedding = generate_embedding(query, &config);
+
+    // Re-rank by the similarity of each match's line to the query, rather
+    // than leaving matches in the file order the matcher found them in.
+    let mut ranked: Vec<(usize, usize, f32)> = raw_matches
+        .into_iter()
+        .map(|(start, end)| {
+            let (line_start, line_end) = line_bounds(&content, start, end);
+            let embedding = generate_embedding(&content[line_start..line_end], &config);
+            let similarity = cosine_similarity(&query_embedding, &embedding);
+            (start, end, similarity)
+        })
         .collect();
-
-    for pos in sample_positions {
-        if let Ok(context_result) =
-            ast_calculator.calculate_context(pos..pos + 1)
-        {
-            let symbol_start = context_result.range.start;
-            let symbol_end = context_result.range.end;
-            let symbol_key = (symbol_start, symbol_end);
-
-            // Only add unique symbols (avoid duplicates)
-            if unique_symbols.insert(symbol_key) {
-                symbols.push(context_result);
-            }
-        }
+    ranked.sort_by(|a, b| {
+        b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    for (start, end, similarity) in &ranked {
+        let line_number = content[..*start].matches('\n').count() + 1;
+        let (line_start, line_end) = line_bounds(&content, *start, *end);
+        println!(
+            "{}:{}: {:.1}% similarity: {}",
+            path.display(),
+            line_number,
+            similarity * 100.0,
+            content[line_start..line_end].trim(),
+        );
     }
 
-    // Extracted symbols for semantic search
+    let mut stats = grep::printer::Stats::new();
+    stats.add_searches(1);
+    stats.add_searches_with_match(1);
+    stats.add_matches(raw_match_count);
+    stats.add_matched_lines(raw_match_count);
+    stats.add_bytes_searched(content.len() as u64);
 
-    if symbols.is_empty() {
-        // Fallback: create embedding for entire file if no symbols found
-        let embedding = generate_embedding(&content, &config);
-        embeddings.push((embedding, 0..content.len(), content.clone()));
-    } else {
-        // Create embeddings for each individual symbol
-        for symbol in symbols {
-            let byte_range = symbol.range.clone();
-
-            // Extract symbol content from file using the range
-            let symbol_content = &content[byte_range.clone()];
-            let embedding = generate_embedding(symbol_content, &config);
-            embeddings.push((
-                embedding,
-                byte_range,
-                symbol_content.to_string(),
-            ));
-        }
-    }
+    Ok(SearchResult { has_match: true, stats: Some(stats) })
+}
 
-    // Build semantic index
-    let index = build_index(embeddings, &config);
+/// Return the 1-based line number containing the given byte offset.
+pub(crate) fn byte_to_line(content: &str, byte_offset: usize) -> usize {
+    content.as_bytes()[..byte_offset].iter().filter(|&&b| b == b'\n').count()
+        + 1
+}
 
-    // Create searcher and perform search
-    let mut semantic_searcher = SemanticSearcher::new(config);
-    semantic_searcher.set_index(index);
+/// Return the byte range of the line(s) containing `start..end`, so that a
+/// match can be re-embedded and displayed with its surrounding line of
+/// context rather than just the bare matched text.
+fn line_bounds(content: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end =
+        content[end..].find('\n').map(|i| end + i).unwrap_or(content.len());
+    (line_start, line_end)
+}
 
-    // Use the actual search pattern
-    let query = pattern.unwrap_or("search");
-    let matches = semantic_searcher.search(&query);
+/// Resolve a `--similar-to` spec of the form `FILE` or `FILE:START-END` into
+/// the snippet text that should be embedded as the query-by-example.
+///
+/// `START` and `END` are 1-based, inclusive line numbers.
+fn read_similar_to_snippet(spec: &str) -> io::Result<String> {
+    let (file, range) = match spec.rsplit_once(':') {
+        Some((file, range)) if range.contains('-') => (file, Some(range)),
+        _ => (spec, None),
+    };
 
-    // For now, just return whether we found semantic matches
-    let has_match = !matches.is_empty();
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Failed to read --similar-to file {}: {}", file, e),
+        )
+    })?;
 
-    if has_match {
-        for semantic_match in matches.iter() {
-            println!(
-                "{}:{}-{}: {:.1}% similarity",
-                path.display(),
-                semantic_match.byte_range.start,
-                semantic_match.byte_range.end,
-                semantic_match.similarity * 100.0
-            );
-            println!("{}", semantic_match.content);
-        }
-    }
+    let Some(range) = range else { return Ok(content) };
 
-    Ok(SearchResult { has_match, stats: None })
+    let (start, end) = range.split_once('-').ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid --similar-to line range: {}", range),
+        )
+    })?;
+    let parse_line = |s: &str| {
+        s.parse::<usize>().map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid --similar-to line number {}: {}", s, e),
+            )
+        })
+    };
+    let start = parse_line(start)?.max(1);
+    let end = parse_line(end)?.max(start);
+
+    let snippet: String = content
+        .lines()
+        .skip(start - 1)
+        .take(end - start + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(snippet)
 }
 
 /// Build a SemanticConfig from the search worker config
-fn build_semantic_config(config: Option<&Config>) -> grep::searcher::SemanticConfig {
+fn build_semantic_config(
+    config: Option<&Config>,
+) -> grep::searcher::SemanticConfig {
     use grep::searcher::SemanticConfig;
-    
+
     let default_config = SemanticConfig::default();
-    
+
     match config {
         Some(cfg) => SemanticConfig {
-            similarity_threshold: cfg.semantic_similarity_threshold.unwrap_or(default_config.similarity_threshold),
-            max_results: cfg.semantic_max_results.unwrap_or(default_config.max_results),
-            embedding_dimensions: cfg.semantic_dimensions.unwrap_or(default_config.embedding_dimensions),
-            model_path: cfg.semantic_model_path.as_ref().map(|p| p.to_string_lossy().to_string()),
+            similarity_threshold: cfg
+                .semantic_similarity_threshold
+                .unwrap_or(default_config.similarity_threshold),
+            max_results: cfg
+                .semantic_max_results
+                .unwrap_or(default_config.max_results),
+            embedding_dimensions: cfg
+                .semantic_dimensions
+                .unwrap_or(default_config.embedding_dimensions),
+            model_path: cfg
+                .semantic_model_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string()),
             model_name: cfg.semantic_model.clone(),
+            ef_search: cfg.semantic_ef_search,
+            chunking_strategy: cfg.semantic_chunking,
+            chunk_window_size: cfg
+                .semantic_chunk_size
+                .unwrap_or(default_config.chunk_window_size),
+            chunk_window_overlap: cfg
+                .semantic_chunk_overlap
+                .unwrap_or(default_config.chunk_window_overlap),
+            backend: cfg.semantic_backend,
+            quantize: cfg.semantic_quantize,
+            rerank: cfg.semantic_rerank,
+            rerank_model: cfg.semantic_rerank_model.clone(),
+            remote_embedding_url: default_config.remote_embedding_url.clone(),
+            remote_embedding_api_key: default_config
+                .remote_embedding_api_key
+                .clone(),
+            dimension_mismatch: cfg.semantic_dimension_mismatch,
         },
         None => default_config,
     }
 }
 
+/// Map `--context-kind` values to the [`grep::searcher::AstContextType`]s
+/// they enable for `--enclosing-symbol`. Falls back to
+/// `default_context_types()` when `kinds` is empty, i.e. `--context-kind`
+/// was never given.
+fn resolve_context_kinds(
+    kinds: &[String],
+) -> Vec<grep::searcher::AstContextType> {
+    use grep::searcher::AstContextType::*;
+
+    if kinds.is_empty() {
+        return grep::searcher::default_context_types();
+    }
+
+    let mut types = Vec::new();
+    for kind in kinds {
+        let mapped: &[grep::searcher::AstContextType] = match kind.as_str() {
+            // A caller asking for "function" almost always wants methods
+            // included too, since both are function-shaped from the
+            // caller's point of view.
+            "function" => &[Function, Method],
+            "class" => &[Class],
+            "module" => &[Module],
+            "block" => &[Block],
+            _ => &[],
+        };
+        for context_type in mapped {
+            if !types.contains(context_type) {
+                types.push(context_type.clone());
+            }
+        }
+    }
+    types
+}
+
 /// Search using AST-based enclosing symbol context.
 fn search_path_ast_context<M: Matcher, W: WriteColor>(
     matcher: M,
     searcher: &mut grep::searcher::Searcher,
     printer: &mut Printer<W>,
     path: &Path,
+    context_kinds: &[String],
+    context_padding: (usize, usize),
     syntax_highlighting: bool,
+    with_docs: bool,
 ) -> io::Result<SearchResult> {
-    use grep::searcher::{
-        create_ast_calculator_for_file, default_context_types,
-        is_supported_file,
-    };
+    use grep::searcher::{create_ast_calculator_for_file, is_supported_file};
 
     // Check if this file type supports AST parsing - if not, skip entirely
     if !is_supported_file(path) {
-        return Ok(SearchResult { has_match: false, stats: None });
+        let mut stats = grep::printer::Stats::new();
+        stats.add_searches(1);
+        return Ok(SearchResult { has_match: false, stats: Some(stats) });
     }
 
     // Read the file content for AST parsing
@@ -741,7 +3868,7 @@ fn search_path_ast_context<M: Matcher, W: WriteColor>(
     let ast_calculator = create_ast_calculator_for_file(
         path,
         &content,
-        Some(default_context_types()),
+        Some(resolve_context_kinds(context_kinds)),
     )
     .map_err(|e| {
         io::Error::new(
@@ -758,7 +3885,10 @@ fn search_path_ast_context<M: Matcher, W: WriteColor>(
     }
 
     if temp_matches.is_empty() {
-        return Ok(SearchResult { has_match: false, stats: None });
+        let mut stats = grep::printer::Stats::new();
+        stats.add_searches(1);
+        stats.add_bytes_searched(content.len() as u64);
+        return Ok(SearchResult { has_match: false, stats: Some(stats) });
     }
 
     // Create AST-aware sink that uses the proper printer infrastructure
@@ -769,7 +3899,9 @@ fn search_path_ast_context<M: Matcher, W: WriteColor>(
         ast_calculator,
         content,
         temp_matches,
+        context_padding,
         syntax_highlighting,
+        with_docs,
     );
 
     // Process all the matches through the AST sink
@@ -834,383 +3966,16 @@ impl<'a> grep::searcher::Sink for MatchCollector<'a> {
     }
 }
 
-/// Syntax highlighter that applies colors to different AST node types.
-struct SyntaxHighlighter {
-    colors: SyntaxColors,
-}
-
-/// Color scheme for syntax highlighting.
-struct SyntaxColors {
-    keyword: String,
-    string: String,
-    comment: String,
-    number: String,
-    identifier: String,
-    function: String,
-    type_name: String,
-    operator: String,
-    punctuation: String,
-    normal: String,
-}
-
-impl SyntaxColors {
-    fn new() -> Self {
-        Self {
-            keyword: "\x1b[35m".to_string(),     // Purple
-            string: "\x1b[32m".to_string(),      // Green
-            comment: "\x1b[90m".to_string(),     // Gray
-            number: "\x1b[36m".to_string(),      // Cyan
-            identifier: "\x1b[37m".to_string(),  // White
-            function: "\x1b[33m".to_string(),    // Yellow
-            type_name: "\x1b[34m".to_string(),   // Blue
-            operator: "\x1b[91m".to_string(),    // Bright red
-            punctuation: "\x1b[37m".to_string(), // White
-            normal: "\x1b[0m".to_string(),       // Reset
-        }
-    }
-}
-
-impl SyntaxHighlighter {
-    fn new() -> Self {
-        Self { colors: SyntaxColors::new() }
-    }
-
-    /// Apply syntax highlighting to source code using AST information.
-    fn highlight_with_ast(
-        &self,
-        source: &str,
-        ast_calculator: &grep::searcher::AstContextCalculatorWrapper,
-        symbol_offset: usize,
-    ) -> String {
-        match ast_calculator {
-            grep::searcher::AstContextCalculatorWrapper::Calculator(calc) => {
-                self.highlight_with_ast_nodes(source, calc, symbol_offset)
-            }
-        }
-    }
-
-    fn highlight_with_ast_nodes(
-        &self,
-        source: &str,
-        calc: &Box<dyn grep::searcher::AstCalculator>,
-        symbol_offset: usize,
-    ) -> String {
-        // Get AST nodes for the full file
-        let syntax_nodes = calc.get_syntax_nodes();
-
-        if syntax_nodes.is_empty() {
-            return source.to_string();
-        }
-
-        // NOW WE CAN DO THIS PROPERLY!
-        // Convert file-relative ranges to source-relative ranges
-        let source_end = symbol_offset + source.len();
-        let relevant_nodes: Vec<_> = syntax_nodes
-            .into_iter()
-            .filter_map(|(range, kind)| {
-                // Only keep nodes that overlap with our source excerpt
-                if range.end <= symbol_offset || range.start >= source_end {
-                    return None; // Node is outside our excerpt
-                }
-
-                // Adjust range to be relative to source start
-                let source_start = range.start.saturating_sub(symbol_offset);
-                let source_range_end =
-                    (range.end.saturating_sub(symbol_offset))
-                        .min(source.len());
-
-                if source_start >= source_range_end {
-                    return None; // Invalid range
-                }
-
-                Some((source_start..source_range_end, kind))
-            })
-            .collect();
-
-        if relevant_nodes.is_empty() {
-            return source.to_string();
-        }
-
-        // Apply highlighting using the adjusted ranges
-        let mut result = String::new();
-        let mut current_pos = 0;
-        let source_bytes = source.as_bytes();
-
-        for (range, kind) in relevant_nodes {
-            // Add unhighlighted text before this node
-            if range.start > current_pos {
-                if let Ok(text) = std::str::from_utf8(
-                    &source_bytes[current_pos..range.start],
-                ) {
-                    result.push_str(text);
-                }
-            }
-
-            // Add highlighted node
-            if let Ok(node_text) =
-                std::str::from_utf8(&source_bytes[range.start..range.end])
-            {
-                result.push_str(&self.colorize_by_ast_kind(node_text, &kind));
-            }
-
-            current_pos = range.end;
-        }
-
-        // Add remaining unhighlighted text
-        if current_pos < source.len() {
-            if let Ok(text) = std::str::from_utf8(&source_bytes[current_pos..])
-            {
-                result.push_str(text);
-            }
-        }
-
-        result
-    }
-
-    fn highlight_with_smart_patterns(&self, source: &str) -> String {
-        // Smarter pattern-based highlighting that avoids false positives
-        let mut result = source.to_string();
-
-        // Only highlight keywords in specific contexts to avoid false positives
-        let rust_keywords = [
-            ("fn ", "keyword"),     // Function declarations
-            ("let ", "keyword"),    // Variable declarations
-            ("if ", "keyword"),     // Control flow
-            ("else", "keyword"),    // Control flow
-            ("for ", "keyword"),    // Loops
-            ("while ", "keyword"),  // Loops
-            ("match ", "keyword"),  // Pattern matching (only with space after)
-            ("return", "keyword"),  // Return statements
-            ("struct ", "keyword"), // Type definitions
-            ("enum ", "keyword"),   // Type definitions
-            ("impl ", "keyword"),   // Implementations
-            ("trait ", "keyword"),  // Trait definitions
-            ("pub ", "keyword"),    // Visibility
-            ("use ", "keyword"),    // Imports
-            ("mod ", "keyword"),    // Modules
-        ];
-
-        let python_keywords = [
-            ("def ", "keyword"),
-            ("class ", "keyword"),
-            ("if ", "keyword"),
-            ("elif ", "keyword"),
-            ("else:", "keyword"),
-            ("for ", "keyword"),
-            ("while ", "keyword"),
-            ("try:", "keyword"),
-            ("except", "keyword"),
-            ("finally:", "keyword"),
-            ("import ", "keyword"),
-            ("from ", "keyword"),
-            ("return", "keyword"),
-        ];
-
-        // Apply Rust keyword highlighting
-        for (pattern, kind) in rust_keywords.iter() {
-            result = self.highlight_pattern(&result, pattern, kind);
-        }
-
-        // Apply Python keyword highlighting
-        for (pattern, kind) in python_keywords.iter() {
-            result = self.highlight_pattern(&result, pattern, kind);
-        }
-
-        // Highlight strings
-        result = self.highlight_strings(result);
-
-        // Highlight comments
-        result = self.highlight_comments(result);
-
-        result
-    }
-
-    fn highlight_pattern(
-        &self,
-        source: &str,
-        pattern: &str,
-        kind: &str,
-    ) -> String {
-        let mut result = String::new();
-        let mut last_end = 0;
-
-        for start in source.match_indices(pattern).map(|(i, _)| i) {
-            // Add text before the pattern
-            result.push_str(&source[last_end..start]);
-
-            // Add highlighted pattern
-            let end = start + pattern.len();
-            result.push_str(
-                &self.colorize_by_ast_kind(&source[start..end], kind),
-            );
-
-            last_end = end;
-        }
-
-        // Add remaining text
-        result.push_str(&source[last_end..]);
-        result
-    }
-
-    fn highlight_strings(&self, source: String) -> String {
-        let result = source;
-
-        // Handle double-quoted strings
-        let mut new_result = String::new();
-        let mut chars = result.chars().peekable();
-        let mut in_string = false;
-        let mut string_start = 0;
-        let mut current_string = String::new();
-        let mut pos = 0;
-
-        while let Some(ch) = chars.next() {
-            if ch == '"' && !in_string {
-                // Start of string
-                new_result.push_str(&result[string_start..pos]);
-                in_string = true;
-                current_string.clear();
-                current_string.push(ch);
-                string_start = pos;
-            } else if ch == '"' && in_string {
-                // End of string
-                current_string.push(ch);
-                new_result.push_str(
-                    &self.colorize_by_ast_kind(&current_string, "string"),
-                );
-                in_string = false;
-                string_start = pos + 1;
-            } else if in_string {
-                current_string.push(ch);
-            }
-            pos += ch.len_utf8();
-        }
-
-        // Add any remaining text
-        if string_start < result.len() {
-            new_result.push_str(&result[string_start..]);
-        }
-
-        new_result
-    }
-
-    fn highlight_comments(&self, source: String) -> String {
-        let mut result = String::new();
-
-        for line in source.lines() {
-            if let Some(comment_start) = line.find("//") {
-                // Add text before comment
-                result.push_str(&line[..comment_start]);
-                // Add highlighted comment
-                result.push_str(
-                    &self.colorize_by_ast_kind(
-                        &line[comment_start..],
-                        "comment",
-                    ),
-                );
-            } else if let Some(comment_start) = line.find("#") {
-                // Python-style comment
-                result.push_str(&line[..comment_start]);
-                result.push_str(
-                    &self.colorize_by_ast_kind(
-                        &line[comment_start..],
-                        "comment",
-                    ),
-                );
-            } else {
-                result.push_str(line);
-            }
-            result.push('\n');
-        }
-
-        // Remove trailing newline if source didn't have one
-        if !source.ends_with('\n') && result.ends_with('\n') {
-            result.pop();
-        }
-
-        result
-    }
-
-    fn colorize_by_ast_kind(&self, text: &str, kind: &str) -> String {
-        let color = match kind {
-            // Rust/JavaScript/Python/Go keywords - using AST semantic types
-            kind if kind.contains("keyword")
-                || kind == "fn"
-                || kind == "let"
-                || kind == "const"
-                || kind == "function"
-                || kind == "def"
-                || kind == "class"
-                || kind == "if"
-                || kind == "else"
-                || kind == "for"
-                || kind == "while"
-                || kind == "return"
-                || kind == "import"
-                || kind == "export"
-                || kind == "struct"
-                || kind == "enum"
-                || kind == "impl"
-                || kind == "trait"
-                || kind == "pub"
-                || kind == "async"
-                || kind == "await" =>
-            {
-                &self.colors.keyword
-            }
-
-            // String literals
-            kind if kind.contains("string")
-                || kind.contains("char_literal") =>
-            {
-                &self.colors.string
-            }
-
-            // Numbers
-            kind if kind.contains("number")
-                || kind.contains("integer")
-                || kind.contains("float")
-                || kind.contains("decimal") =>
-            {
-                &self.colors.number
-            }
-
-            // Comments
-            kind if kind.contains("comment") => &self.colors.comment,
-
-            // Function names and calls
-            kind if kind.contains("function")
-                || kind.contains("call")
-                || kind == "function_item"
-                || kind == "function_declaration" =>
-            {
-                &self.colors.function
-            }
-
-            // Type identifiers
-            kind if kind.contains("type")
-                || kind == "type_identifier"
-                || kind.contains("primitive_type") =>
-            {
-                &self.colors.type_name
-            }
-
-            // Operators
-            kind if kind.contains("operator")
-                || kind.contains("binary")
-                || kind.contains("unary")
-                || kind.contains("assignment") =>
-            {
-                &self.colors.operator
-            }
-
-            _ => &self.colors.normal,
-        };
-
-        format!("{}{}{}", color, text, self.colors.normal)
-    }
-}
-
 /// AST-aware sink that outputs enclosing symbols with proper formatting.
+///
+/// Unlike a plain search, matches here don't drive output directly: each
+/// match is expanded to its enclosing symbol via `ast_calculator`, and the
+/// symbol's byte range (plus any `--before-context`/`--after-context`
+/// padding and `--with-docs` doc comment) is what actually gets shown.
+/// Rendering itself is delegated entirely to `printer` via
+/// `SymbolRangeFilterSink`, so `--color`, `--no-heading`, `--vimgrep`,
+/// hyperlinks and `--stats` all behave exactly as they do for every other
+/// search mode.
 struct AstSymbolSink<'a, M, W> {
     printer: &'a mut Printer<W>,
     matcher: &'a M,
@@ -1219,7 +3984,24 @@ struct AstSymbolSink<'a, M, W> {
     content: String,
     original_matches: Vec<(usize, usize)>,
     has_match: bool,
+    stats: Option<grep::printer::Stats>,
+    /// Extra lines of context to show before/after each symbol, from
+    /// `--before-context`/`--after-context`/`--context` combined with
+    /// `--enclosing-symbol`.
+    context_padding: (usize, usize),
+    /// Whether to print a `-- <kind> <name> --` summary line ahead of a
+    /// file's symbol blocks (Standard printer only; there's no sensible
+    /// place for it in `--json` output).
     syntax_highlighting: bool,
+    with_docs: bool,
+}
+
+/// A single AST-derived symbol to display, in file-byte-offset coordinates,
+/// carrying the metadata `calculate_context` already computed about it.
+struct AstSymbolBlock {
+    range: std::ops::Range<usize>,
+    symbol_name: Option<String>,
+    context_type: grep::searcher::AstContextType,
 }
 
 impl<'a, M: Matcher, W: WriteColor> AstSymbolSink<'a, M, W> {
@@ -1230,7 +4012,9 @@ impl<'a, M: Matcher, W: WriteColor> AstSymbolSink<'a, M, W> {
         ast_calculator: grep::searcher::AstContextCalculatorWrapper,
         content: String,
         original_matches: Vec<(usize, usize)>,
+        context_padding: (usize, usize),
         syntax_highlighting: bool,
+        with_docs: bool,
     ) -> Self {
         Self {
             printer,
@@ -1240,7 +4024,10 @@ impl<'a, M: Matcher, W: WriteColor> AstSymbolSink<'a, M, W> {
             content,
             original_matches,
             has_match: false,
+            stats: None,
+            context_padding,
             syntax_highlighting,
+            with_docs,
         }
     }
 
@@ -1248,191 +4035,203 @@ impl<'a, M: Matcher, W: WriteColor> AstSymbolSink<'a, M, W> {
         &mut self,
         searcher: &mut grep::searcher::Searcher,
     ) -> io::Result<bool> {
-        let mut output_ranges = std::collections::HashSet::new();
-        let matches_copy = self.original_matches.clone();
-
-        for (match_start, match_end) in matches_copy {
-            let match_range = match_start..match_end;
-
-            match self.ast_calculator.calculate_context(match_range) {
-                Ok(context_result) => {
-                    // Avoid outputting the same symbol multiple times
-                    if output_ranges.insert((
-                        context_result.range.start,
-                        context_result.range.end,
-                    )) {
-                        self.output_symbol(searcher, &context_result)?;
-                        self.has_match = true;
-                    }
-                }
-                Err(_ast_error) => {
-                    // Skip matches that don't have enclosing symbols
-                }
+        let (pad_before, pad_after) = self.context_padding;
+        let with_docs = self.with_docs;
+        let mut seen = std::collections::HashSet::new();
+        let mut blocks: Vec<AstSymbolBlock> = Vec::new();
+
+        for (match_start, match_end) in self.original_matches.clone() {
+            let context_result = match self
+                .ast_calculator
+                .calculate_context(match_start..match_end)
+            {
+                Ok(context_result) => context_result,
+                // The match isn't inside any recognized symbol (e.g. it's
+                // at the top level); nothing to show for it in this mode.
+                Err(_ast_error) => continue,
+            };
+            // Avoid showing the same symbol multiple times when several
+            // matches land inside it.
+            if !seen
+                .insert((context_result.range.start, context_result.range.end))
+            {
+                continue;
             }
-        }
-
-        Ok(self.has_match)
-    }
-
-    fn output_symbol(
-        &mut self,
-        _searcher: &mut grep::searcher::Searcher,
-        context_result: &grep::searcher::AstContextResult,
-    ) -> io::Result<()> {
-        let symbol_start = context_result.range.start;
-        let symbol_end = context_result.range.end;
-
-        // Print file path header
-        println!("\x1b[36m{}\x1b[0m", self.path.display()); // Cyan file path
-
-        // Extract the symbol content
-        let symbol_content = &self.content[symbol_start..symbol_end];
-
-        // Apply AST-based syntax highlighting if enabled
-        let highlighted_content = if self.syntax_highlighting {
-            let highlighter = SyntaxHighlighter::new();
-            highlighter.highlight_with_ast(
-                symbol_content,
-                &self.ast_calculator,
-                symbol_start,
-            )
-        } else {
-            symbol_content.to_string()
-        };
 
-        // Add line numbers to the output with match highlighting
-        let start_line = self.byte_to_line(symbol_start);
-        let original_lines: Vec<&str> = symbol_content.lines().collect();
-
-        for (i, line) in highlighted_content.lines().enumerate() {
-            let current_line = start_line + i;
-            let original_line = original_lines.get(i).unwrap_or(&"");
-
-            // Calculate byte positions for this line within the symbol
-            let line_start_byte = symbol_start
-                + original_lines
-                    .iter()
-                    .take(i)
-                    .map(|l| l.len() + 1) // +1 for newline
-                    .sum::<usize>();
-            let line_end_byte = line_start_byte + original_line.len();
-
-            // Find matches within this line
-            let line_matches: Vec<(usize, usize)> = self
-                .original_matches
-                .iter()
-                .filter_map(|(match_start, match_end)| {
-                    if *match_start >= line_start_byte
-                        && *match_start < line_end_byte
-                    {
-                        // Convert to line-relative positions
-                        let line_match_start =
-                            match_start.saturating_sub(line_start_byte);
-                        let line_match_end = (*match_end)
-                            .min(line_end_byte)
-                            .saturating_sub(line_start_byte);
-                        Some((line_match_start, line_match_end))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            let final_line = if !line_matches.is_empty() {
-                // For lines with matches, apply highlighting to original line first, then syntax
-                let match_highlighted = self.highlight_search_matches_simple(
-                    original_line,
-                    &line_matches,
-                );
-                if self.syntax_highlighting {
-                    // Apply syntax highlighting while preserving search match highlighting
-                    self.apply_syntax_around_matches(
-                        &match_highlighted,
-                        &line_matches,
-                    )
-                } else {
-                    match_highlighted
-                }
+            let doc_lines = if with_docs {
+                context_result
+                    .doc_comment
+                    .as_ref()
+                    .map_or(0, |doc| doc.lines().count())
             } else {
-                line.to_string()
+                0
             };
-
-            if !line_matches.is_empty() {
-                println!("\x1b[1;32m{}\x1b[0m:{}", current_line, final_line); // Green bold line number
-            } else {
-                println!("{}:{}", current_line, final_line);
-            }
+            let start = self.expand_start(
+                context_result.range.start,
+                pad_before + doc_lines,
+            );
+            let end = self.expand_end(context_result.range.end, pad_after);
+            blocks.push(AstSymbolBlock {
+                range: start..end,
+                symbol_name: context_result.symbol_name,
+                context_type: context_result.context_type,
+            });
         }
 
-        Ok(())
-    }
+        if blocks.is_empty() {
+            let mut stats = grep::printer::Stats::new();
+            stats.add_searches(1);
+            stats.add_bytes_searched(self.content.len() as u64);
+            self.stats = Some(stats);
+            return Ok(false);
+        }
 
-    fn byte_to_line(&self, byte_offset: usize) -> usize {
-        self.content.bytes().take(byte_offset).filter(|&b| b == b'\n').count()
-            + 1
-    }
+        // Merge overlapping/adjacent symbol ranges so touching blocks (e.g.
+        // padding from one symbol reaching into the next) render as one
+        // contiguous block instead of two with a spurious "--" between them.
+        blocks.sort_by_key(|block| block.range.start);
+        let mut merged: Vec<AstSymbolBlock> = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            match merged.last_mut() {
+                Some(prev) if block.range.start <= prev.range.end => {
+                    prev.range.end = prev.range.end.max(block.range.end);
+                }
+                _ => merged.push(block),
+            }
+        }
 
-    fn stats(&self) -> Option<grep::printer::Stats> {
-        // For now, return None - we could implement proper stats later
-        None
+        self.has_match = self.render_blocks(searcher, &merged)?;
+        Ok(self.has_match)
     }
 
-    fn highlight_search_matches_simple(
-        &self,
-        line: &str,
-        matches: &[(usize, usize)],
-    ) -> String {
-        if matches.is_empty() {
-            return line.to_string();
+    /// Render the given symbol blocks through `self.printer`, restricting
+    /// the search over the whole file to only the lines each block covers.
+    ///
+    /// Searching the whole file (rather than each block's own sub-slice)
+    /// keeps line numbers, hyperlinks and byte offsets that `printer`
+    /// reports correct without any manual bookkeeping on our part.
+    fn render_blocks(
+        &mut self,
+        searcher: &mut grep::searcher::Searcher,
+        blocks: &[AstSymbolBlock],
+    ) -> io::Result<bool> {
+        if self.syntax_highlighting
+            && matches!(*self.printer, Printer::Standard(_))
+        {
+            self.write_symbol_summary(blocks)?;
         }
 
-        // Debug: check if all matches are out of bounds
-        let valid_matches: Vec<_> = matches
-            .iter()
-            .filter(|(start, end)| {
-                *start < line.len() && *end <= line.len() && start < end
-            })
-            .collect();
+        let ranges: Vec<std::ops::Range<usize>> =
+            blocks.iter().map(|block| block.range.clone()).collect();
 
-        if valid_matches.is_empty() {
-            // No valid matches within this line - highlight entire line for now to show something is matching
-            // TODO: Fix the position calculation
-            return format!("\x1b[1;48;2;212;147;113m{}\x1b[0m", line);
+        match *self.printer {
+            Printer::Standard(ref mut p) => {
+                let mut sink = SymbolRangeFilterSink::new(
+                    p.sink_with_path(self.matcher, self.path),
+                    &ranges,
+                );
+                searcher.search_slice(
+                    self.matcher,
+                    self.content.as_bytes(),
+                    &mut sink,
+                )?;
+                self.stats = sink.inner.stats().cloned();
+                Ok(sink.inner.has_match())
+            }
+            Printer::Summary(ref mut p) => {
+                let mut sink = SymbolRangeFilterSink::new(
+                    p.sink_with_path(self.matcher, self.path),
+                    &ranges,
+                );
+                searcher.search_slice(
+                    self.matcher,
+                    self.content.as_bytes(),
+                    &mut sink,
+                )?;
+                self.stats = sink.inner.stats().cloned();
+                Ok(sink.inner.has_match())
+            }
+            Printer::JSON(ref mut p) => {
+                let mut sink = SymbolRangeFilterSink::new(
+                    p.sink_with_path(self.matcher, self.path),
+                    &ranges,
+                );
+                searcher.search_slice(
+                    self.matcher,
+                    self.content.as_bytes(),
+                    &mut sink,
+                )?;
+                self.stats = Some(sink.inner.stats().clone());
+                Ok(sink.inner.has_match())
+            }
         }
+    }
 
-        let mut result = String::new();
-        let mut last_pos = 0;
-
-        for (start, end) in valid_matches {
-            // Add text before match
-            if *start > last_pos {
-                result.push_str(&line[last_pos..*start]);
+    /// Print a `-- <kind> <name>, ... --` line naming the symbols about to
+    /// be shown for this file, using the AST metadata `calculate_context`
+    /// computes but that per-line output has no other place to put.
+    fn write_symbol_summary(
+        &mut self,
+        blocks: &[AstSymbolBlock],
+    ) -> io::Result<()> {
+        use std::io::Write;
+
+        let wtr = self.printer.get_mut();
+        write!(wtr, "--")?;
+        for block in blocks {
+            let kind = match block.context_type {
+                grep::searcher::AstContextType::Function => "function",
+                grep::searcher::AstContextType::Class => "class",
+                grep::searcher::AstContextType::Method => "method",
+                grep::searcher::AstContextType::Block => "block",
+                grep::searcher::AstContextType::Module => "module",
+                grep::searcher::AstContextType::TypeDef => "type",
+            };
+            match &block.symbol_name {
+                Some(name) => write!(wtr, " {} {},", kind, name)?,
+                None => write!(wtr, " {},", kind)?,
             }
-
-            // Add highlighted match - bright red background
-            result.push_str("\x1b[1;48;2;212;147;113m"); // Custom RGB background
-            result.push_str(&line[*start..*end]);
-            result.push_str("\x1b[0m"); // Reset
-
-            last_pos = *end;
         }
+        writeln!(wtr, " --")?;
+        Ok(())
+    }
 
-        // Add remaining text
-        if last_pos < line.len() {
-            result.push_str(&line[last_pos..]);
+    /// Move `start` back to the beginning of the line `lines_before` lines
+    /// above it, stopping at the beginning of the file.
+    fn expand_start(&self, start: usize, lines_before: usize) -> usize {
+        let mut pos = self.content[..start].rfind('\n').map_or(0, |i| i + 1);
+        for _ in 0..lines_before {
+            if pos == 0 {
+                break;
+            }
+            pos = self.content[..pos - 1].rfind('\n').map_or(0, |i| i + 1);
         }
+        pos
+    }
 
-        result
+    /// Move `end` forward to the end of the line `lines_after` lines below
+    /// it, stopping at the end of the file.
+    fn expand_end(&self, end: usize, lines_after: usize) -> usize {
+        let len = self.content.len();
+        let mut pos =
+            self.content[end..].find('\n').map_or(len, |i| end + i + 1);
+        for _ in 0..lines_after {
+            if pos >= len {
+                break;
+            }
+            pos = self.content[pos..].find('\n').map_or(len, |i| pos + i + 1);
+        }
+        pos
     }
 
-    fn apply_syntax_around_matches(
-        &self,
-        line: &str,
-        _matches: &[(usize, usize)],
-    ) -> String {
-        // For now, let's keep it simple - just return the line with match highlighting
-        // The search highlighting takes precedence
-        line.to_string()
+    /// Aggregate statistics for this file's AST search, for `--stats`.
+    ///
+    /// Always returns `Some`, even when nothing was shown: `--stats`
+    /// aggregates results across every searched file with `+=`, so a
+    /// missing value here (rather than an honest zero) would panic that
+    /// aggregation instead of just reporting zero matches for this file.
+    fn stats(&self) -> Option<grep::printer::Stats> {
+        Some(self.stats.clone().unwrap_or_default())
     }
 }
 
@@ -1443,7 +4242,7 @@ fn search_path<M: Matcher, W: WriteColor>(
     printer: &mut Printer<W>,
     path: &Path,
 ) -> io::Result<SearchResult> {
-    search_path_standard(matcher, searcher, printer, path)
+    search_path_standard(matcher, searcher, printer, path, None, None)
 }
 
 /// Search the contents of the given reader using the given matcher, searcher
@@ -1454,30 +4253,44 @@ fn search_reader<M: Matcher, R: io::Read, W: WriteColor>(
     printer: &mut Printer<W>,
     path: &Path,
     mut rdr: R,
+    since: Option<crate::logtime::LogTimestamp>,
+    until: Option<crate::logtime::LogTimestamp>,
 ) -> io::Result<SearchResult> {
     match *printer {
         Printer::Standard(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
             searcher.search_reader(&matcher, &mut rdr, &mut sink)?;
             Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: sink.stats().map(|s| s.clone()),
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().map(|s| s.clone()),
             })
         }
         Printer::Summary(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
             searcher.search_reader(&matcher, &mut rdr, &mut sink)?;
             Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: sink.stats().map(|s| s.clone()),
+                has_match: sink.inner.has_match(),
+                stats: sink.inner.stats().map(|s| s.clone()),
             })
         }
         Printer::JSON(ref mut p) => {
-            let mut sink = p.sink_with_path(&matcher, path);
+            let mut sink = TimestampFilterSink::new(
+                p.sink_with_path(&matcher, path),
+                since,
+                until,
+            );
             searcher.search_reader(&matcher, &mut rdr, &mut sink)?;
             Ok(SearchResult {
-                has_match: sink.has_match(),
-                stats: Some(sink.stats().clone()),
+                has_match: sink.inner.has_match(),
+                stats: Some(sink.inner.stats().clone()),
             })
         }
     }
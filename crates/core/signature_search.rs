@@ -0,0 +1,75 @@
+/*!
+Implements `og --signature QUERY`, a search for functions matching a
+parameter/return type shape.
+
+Like [`crate::symbols`], this walks the search paths and parses each file
+with the same AST extraction `--tree --syntax` uses, but instead of listing
+every symbol it reports only the functions whose extracted
+[`FunctionSignature`](crate::diagnostics::types::FunctionSignature) matches
+a [`crate::diagnostics::SignatureQuery`], e.g. `(Path, &str) -> Result`.
+*/
+
+use crate::diagnostics::types::SymbolInfo;
+use crate::diagnostics::{extract_ast_structure, SignatureQuery};
+use crate::flags::HiArgs;
+
+/// Print every function definition under `args`'s search paths whose
+/// signature matches `query`, respecting the walker's usual ignore rules.
+/// Returns whether any matches were found.
+pub(crate) fn run(args: &HiArgs, query: &str) -> anyhow::Result<bool> {
+    let query = SignatureQuery::parse(query);
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut found_any = false;
+    for haystack in haystacks {
+        let path = haystack.path();
+        // Unsupported languages and files that fail to parse have no
+        // functions to report, the same as a file with no matches.
+        let Some(structure) = extract_ast_structure(path) else { continue };
+        let matches: Vec<&SymbolInfo> = structure
+            .symbols
+            .functions
+            .iter()
+            .filter(|f| {
+                f.signature.as_ref().is_some_and(|sig| query.matches(sig))
+            })
+            .collect();
+        if matches.is_empty() {
+            continue;
+        }
+        found_any = true;
+
+        if args.json_output() {
+            for symbol in &matches {
+                let message = serde_json::json!({
+                    "type": "signature",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "name": symbol.name,
+                        "line_number": symbol.line,
+                        "column": symbol.column,
+                        "signature": symbol.signature,
+                    },
+                });
+                println!("{}", message);
+            }
+        } else {
+            println!("{}", path.display());
+            for symbol in &matches {
+                println!(
+                    "  {}:{} {}",
+                    symbol.line, symbol.column, symbol.name
+                );
+            }
+        }
+    }
+
+    if !found_any && !args.json_output() {
+        println!("No functions matching that signature found under the search paths.");
+    }
+    Ok(found_any)
+}
@@ -0,0 +1,144 @@
+/*!
+Implements `og --symbols`, a ctags-like outline of the symbols defined
+under the search paths.
+
+Every file is parsed with the same AST extraction `--tree --syntax` uses
+([`crate::diagnostics::extract_ast_structure`]), and each function, class,
+type, and module it finds is printed with its line number, grouped by
+file. This is meant for a quick overview of what a file or tree defines,
+or for an editor to build a symbol picker from `--json` output, without
+the rest of `--tree`'s directory structure and metrics.
+
+`--symbols --json`'s `symbol` field is a
+[`crate::diagnostics::DocumentSymbol`], nested the same way LSP's
+`textDocument/documentSymbol` nests a method under its class, for editors
+that want a tree rather than a flat list to build a symbol picker from.
+
+Results are cached in a [`SymbolDatabase`] at `~/.config/outgrep/symbols.db`,
+keyed by each file's mtime, so that re-running `--symbols` over a mostly
+unchanged tree doesn't re-parse every file from scratch. The cache is a
+performance optimization only: failing to open or query it just falls back
+to always re-parsing, the same as before it existed.
+*/
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+
+use crate::diagnostics::types::{AstSymbolSummary, SymbolInfo};
+use crate::diagnostics::{
+    extract_ast_structure, nest_document_symbols, SymbolDatabase,
+};
+use crate::flags::HiArgs;
+
+/// Returns the path to the local symbol cache, creating its parent
+/// directory if it doesn't already exist.
+fn symbol_db_path() -> anyhow::Result<PathBuf> {
+    let home_dir =
+        dirs::home_dir().context("could not determine home directory")?;
+    let dir = home_dir.join(".config").join("outgrep");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+    Ok(dir.join("symbols.db"))
+}
+
+/// Symbols for `path`, either read from `db`'s cache (if it's still fresh)
+/// or extracted fresh and written back to `db` on a miss. Returns `None`
+/// for unsupported languages and files that fail to parse, the same as a
+/// file with no matches.
+fn symbols_for(
+    db: Option<&mut SymbolDatabase>,
+    path: &Path,
+) -> Option<AstSymbolSummary> {
+    let key = path.display().to_string();
+    let mtime = std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64);
+
+    if let (Some(db), Some(mtime)) = (db.as_deref(), mtime) {
+        if let Ok(Some(summary)) = db.cached_summary(&key, mtime) {
+            return Some(summary);
+        }
+    }
+
+    let summary = extract_ast_structure(path)?.symbols;
+    if let (Some(db), Some(mtime)) = (db, mtime) {
+        // A cache write failing (e.g. a locked database) shouldn't stop
+        // `--symbols` from reporting what it just parsed.
+        let _ = db.upsert_summary(&key, mtime, &summary);
+    }
+    Some(summary)
+}
+
+/// Print a ctags-like outline of every symbol found under `args`'s search
+/// paths, respecting the walker's usual ignore rules. Returns whether any
+/// symbols were found.
+pub(crate) fn run(args: &HiArgs) -> anyhow::Result<bool> {
+    let haystack_builder = args.haystack_builder();
+    let haystacks = args
+        .walk_builder()?
+        .build()
+        .filter_map(|result| haystack_builder.build_from_result(result));
+
+    let mut db = match symbol_db_path().and_then(SymbolDatabase::open) {
+        Ok(db) => Some(db),
+        Err(err) => {
+            log::warn!(
+                "failed to open symbol cache, re-parsing every file: {err}"
+            );
+            None
+        }
+    };
+
+    let mut found_any = false;
+    for haystack in haystacks {
+        let path = haystack.path();
+        let Some(summary) = symbols_for(db.as_mut(), path) else { continue };
+        let mut symbols: Vec<&SymbolInfo> = summary
+            .functions
+            .iter()
+            .chain(&summary.classes)
+            .chain(&summary.types)
+            .chain(&summary.modules)
+            .collect();
+        if symbols.is_empty() {
+            continue;
+        }
+        symbols.sort_by_key(|s| s.line);
+        found_any = true;
+
+        if args.json_output() {
+            let roots =
+                nest_document_symbols(summary.clone()).unwrap_or_default();
+            for symbol in &roots {
+                let message = serde_json::json!({
+                    "type": "symbol",
+                    "data": {
+                        "path": {"text": path.display().to_string()},
+                        "symbol": symbol,
+                    },
+                });
+                println!("{}", message);
+            }
+        } else {
+            println!("{}", path.display());
+            for symbol in &symbols {
+                println!(
+                    "  {}:{} {} {}",
+                    symbol.line,
+                    symbol.column,
+                    symbol.symbol_type,
+                    symbol.name
+                );
+            }
+        }
+    }
+
+    if !found_any && !args.json_output() {
+        println!("No symbols found under the search paths.");
+    }
+    Ok(found_any)
+}
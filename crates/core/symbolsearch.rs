@@ -0,0 +1,112 @@
+/*!
+Symbol-name search over source code, for `--symbol`.
+
+Normally the search pattern is matched against lines of text, which means a
+function name like `parse_config` also matches every call site, every
+mention in a comment, and every string that happens to contain it. This
+flag instead parses the whole file with tree-sitter and matches the query
+only against the names of symbol *definitions* -- functions, classes,
+types, and modules -- extracted by the same AST layer that powers `og
+--tree --analyze`'s symbol summary. Results are reported at the position of
+the symbol's name, one per matching definition.
+*/
+
+use outgrep_ast_language::SupportLang;
+
+use crate::diagnostics::types::SymbolInfo;
+
+/// A single symbol definition matched by a [`SymbolQuery`].
+#[derive(Debug, Clone)]
+pub(crate) struct SymbolMatch {
+    /// 1-based line the symbol's name starts on.
+    pub(crate) line: usize,
+    /// 1-based, character-based column the symbol's name starts on.
+    pub(crate) column: usize,
+    /// The AST node kind backing this symbol, e.g. `function_item`.
+    pub(crate) symbol_type: String,
+    /// The symbol's name.
+    pub(crate) name: String,
+}
+
+impl From<SymbolInfo> for SymbolMatch {
+    fn from(info: SymbolInfo) -> SymbolMatch {
+        SymbolMatch {
+            line: info.line as usize,
+            column: info.column as usize,
+            symbol_type: info.symbol_type,
+            name: info.name,
+        }
+    }
+}
+
+/// A parsed `--symbol` query: the exact symbol name to look for.
+#[derive(Debug, Clone)]
+pub(crate) struct SymbolQuery {
+    name: String,
+}
+
+impl SymbolQuery {
+    /// Build a query that matches symbol definitions named exactly `name`.
+    pub(crate) fn new(name: String) -> SymbolQuery {
+        SymbolQuery { name }
+    }
+
+    /// Parse `content` as `lang` and return every symbol definition named
+    /// exactly this query's name, in the order they appear in the file.
+    ///
+    /// Returns an empty list if `lang` isn't supported by the AST layer or
+    /// if `content` fails to parse, the same as a file with no matches.
+    pub(crate) fn matches(
+        &self,
+        lang: SupportLang,
+        content: &str,
+    ) -> Vec<SymbolMatch> {
+        let Some(structure) =
+            crate::diagnostics::ast_extractor::extract_ast_info_for_language(
+                lang, content,
+            )
+        else {
+            return Vec::new();
+        };
+        let symbols = structure.symbols;
+        symbols
+            .functions
+            .into_iter()
+            .chain(symbols.classes)
+            .chain(symbols.types)
+            .chain(symbols.modules)
+            .filter(|s| s.name == self.name)
+            .map(SymbolMatch::from)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_a_function_definition() {
+        let query = SymbolQuery::new("parse_config".to_string());
+        let content =
+            "fn parse_config() {}\nfn call_site() { parse_config(); }\n";
+        let matches = query.matches(SupportLang::Rust, content);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 1);
+        assert_eq!(matches[0].symbol_type, "function_item");
+    }
+
+    #[test]
+    fn does_not_match_call_sites_or_comments() {
+        let query = SymbolQuery::new("helper".to_string());
+        let content = "// calls helper eventually\nfn other() { helper(); }\n";
+        assert!(query.matches(SupportLang::Rust, content).is_empty());
+    }
+
+    #[test]
+    fn no_match_returns_empty() {
+        let query = SymbolQuery::new("does_not_exist".to_string());
+        let content = "fn parse_config() {}\n";
+        assert!(query.matches(SupportLang::Rust, content).is_empty());
+    }
+}
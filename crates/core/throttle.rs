@@ -0,0 +1,117 @@
+/*!
+IO and CPU pacing for `--throttle`, so a background run (`--watch`, a
+daemon, or a scheduled index build) doesn't compete with a developer's
+foreground work for disk bandwidth or CPU time.
+
+Pacing happens once per file rather than per byte read: after a file is
+searched, [`Throttle::pace`] sleeps long enough to cap the *average* read
+rate to the configured limit, then yields the thread so the scheduler can
+run other work before the next file starts. This is coarser than metering
+every read syscall, but it's enough to keep a background scan from
+saturating IO, and it composes trivially with the rest of the search
+pipeline instead of threading a rate limiter through every reader.
+*/
+
+/// Paces file reads to a configured maximum throughput, for `--throttle`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Throttle {
+    max_bytes_per_sec: Option<u64>,
+}
+
+impl Throttle {
+    /// Build a throttle from `--throttle`'s megabytes-per-second value.
+    /// `None` disables pacing entirely.
+    pub(crate) fn new(max_mb_per_sec: Option<f64>) -> Throttle {
+        let max_bytes_per_sec =
+            max_mb_per_sec.map(|mb| (mb * 1024.0 * 1024.0) as u64);
+        Throttle { max_bytes_per_sec }
+    }
+
+    /// Whether any pacing is configured.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.max_bytes_per_sec.is_some()
+    }
+
+    /// Sleep long enough that reading `bytes` just now stays within the
+    /// configured rate, then yield the thread so other work gets a turn
+    /// before the next file starts.
+    ///
+    /// A no-op when no `--throttle` rate was configured.
+    pub(crate) fn pace(&self, bytes: u64) {
+        let Some(max_bytes_per_sec) = self.max_bytes_per_sec else { return };
+        if max_bytes_per_sec > 0 && bytes > 0 {
+            let seconds = bytes as f64 / max_bytes_per_sec as f64;
+            std::thread::sleep(std::time::Duration::from_secs_f64(seconds));
+        }
+        std::thread::yield_now();
+    }
+
+    /// Lower this process's OS scheduling priority so it yields CPU time to
+    /// other processes under contention, for `--throttle`.
+    ///
+    /// This is best-effort: platforms without a "nice" concept, or where
+    /// the call fails (e.g. insufficient privilege to go the other
+    /// direction), are left at their default priority rather than treated
+    /// as an error, since `--throttle`'s IO pacing is the primary
+    /// mechanism and this is a secondary nicety.
+    pub(crate) fn lower_process_priority() {
+        #[cfg(unix)]
+        {
+            // SAFETY: `nice` is a plain libc call with no preconditions
+            // beyond a valid `inc`; we don't use its process-wide side
+            // effect for anything safety-critical, only as a best-effort
+            // scheduling hint.
+            let result = unsafe { libc_nice(10) };
+            if result == -1 {
+                log::debug!(
+                    "--throttle: failed to lower process priority via nice(2)"
+                );
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            log::debug!(
+                "--throttle: lowering process priority is only supported on Unix"
+            );
+        }
+    }
+}
+
+#[cfg(unix)]
+extern "C" {
+    #[link_name = "nice"]
+    fn libc_nice(inc: i32) -> i32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(!Throttle::new(None).is_enabled());
+    }
+
+    #[test]
+    fn enabled_when_a_rate_is_given() {
+        assert!(Throttle::new(Some(5.0)).is_enabled());
+    }
+
+    #[test]
+    fn paces_proportionally_to_the_configured_rate() {
+        let throttle = Throttle::new(Some(100.0 * 1024.0 * 1024.0));
+        let start = std::time::Instant::now();
+        throttle.pace(10 * 1024 * 1024);
+        // At 100 MB/s, pacing 10 MB should sleep at least ~100ms, well under
+        // a flaky test's budget but enough to prove the sleep happened.
+        assert!(start.elapsed().as_millis() >= 50);
+    }
+
+    #[test]
+    fn does_not_sleep_when_disabled() {
+        let throttle = Throttle::new(None);
+        let start = std::time::Instant::now();
+        throttle.pace(1024 * 1024 * 1024);
+        assert!(start.elapsed().as_millis() < 50);
+    }
+}
@@ -0,0 +1,126 @@
+/*!
+Raw tree-sitter S-expression queries over source code, for `--ts-query`.
+
+`--pattern` matches an ast-grep style pattern -- source code with
+metavariables like `$X` -- against the parsed tree. This flag instead
+compiles a query written in tree-sitter's own S-expression query syntax
+(`(function_item name: (identifier) @name)`) and runs it directly with
+`tree_sitter::Query`/`QueryCursor`, giving power users who already know a
+grammar's node/field names direct access to the parsers vendored in
+`outgrep_ast_language`, without going through the pattern/metavariable
+matching engine at all.
+*/
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+use tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+
+/// A single capture produced by running a [`TsQuery`] over source text.
+#[derive(Debug, Clone)]
+pub(crate) struct TsQueryCapture {
+    /// 1-based line the capture starts on.
+    pub(crate) line: usize,
+    /// 1-based, byte-offset column the capture starts on, as tree-sitter
+    /// itself reports it (unlike `--pattern`'s character-based column).
+    pub(crate) column: usize,
+    /// The capture's name, e.g. `name` for `@name`.
+    pub(crate) name: String,
+    /// The exact source text of the captured node.
+    pub(crate) text: String,
+}
+
+/// A parsed `--ts-query` query: a raw tree-sitter S-expression query paired
+/// with the `--lang` it was compiled for, e.g.
+/// `(function_item name: (identifier) @name)` for [`SupportLang::Rust`].
+pub(crate) struct TsQuery {
+    lang: SupportLang,
+    query: Query,
+}
+
+impl TsQuery {
+    /// Compile `expr` as a tree-sitter query for `lang`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `expr` isn't a valid query for `lang`'s
+    /// grammar, e.g. it references a node or field name the grammar
+    /// doesn't have.
+    pub(crate) fn parse(
+        lang: SupportLang,
+        expr: &str,
+    ) -> anyhow::Result<TsQuery> {
+        let query =
+            Query::new(&lang.get_ts_language(), expr).map_err(|e| {
+                anyhow::anyhow!("invalid --ts-query for {}: {}", lang, e)
+            })?;
+        Ok(TsQuery { lang, query })
+    }
+
+    /// Parse `content` as this query's language and return every capture
+    /// its query produces, in the order tree-sitter reports them.
+    pub(crate) fn captures(&self, content: &str) -> Vec<TsQueryCapture> {
+        let mut parser = Parser::new();
+        parser.set_language(&self.lang.get_ts_language()).expect(
+            "SupportLang always provides a valid tree-sitter language",
+        );
+        let Some(tree) = parser.parse(content, None) else {
+            return Vec::new();
+        };
+
+        let names = self.query.capture_names();
+        let mut cursor = QueryCursor::new();
+        let mut captures =
+            cursor.captures(&self.query, tree.root_node(), content.as_bytes());
+        let mut out = Vec::new();
+        while let Some((query_match, capture_ix)) = captures.next() {
+            let capture = query_match.captures[*capture_ix];
+            let start = capture.node.start_position();
+            let text = capture
+                .node
+                .utf8_text(content.as_bytes())
+                .unwrap_or_default()
+                .to_string();
+            out.push(TsQueryCapture {
+                line: start.row + 1,
+                column: start.column + 1,
+                name: names[capture.index as usize].to_string(),
+                text,
+            });
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_named_function() {
+        let query = TsQuery::parse(
+            SupportLang::Rust,
+            "(function_item name: (identifier) @name)",
+        )
+        .unwrap();
+        let content = "fn one() {}\nfn two() {}\n";
+        let captures = query.captures(content);
+        let names: Vec<&str> =
+            captures.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(names, vec!["one", "two"]);
+        assert_eq!(captures[0].line, 1);
+        assert_eq!(captures[1].line, 2);
+    }
+
+    #[test]
+    fn no_captures_returns_empty() {
+        let query =
+            TsQuery::parse(SupportLang::Rust, "(struct_item) @s").unwrap();
+        let content = "fn f() {}\n";
+        assert!(query.captures(content).is_empty());
+    }
+
+    #[test]
+    fn invalid_query_is_rejected() {
+        assert!(TsQuery::parse(SupportLang::Rust, "(((").is_err());
+    }
+}
@@ -0,0 +1,140 @@
+/*!
+In-process WASM filters and transformations, for `--wasm-plugin`.
+
+Complements the subprocess plugins in [`crate::plugins`]: a subprocess is
+spawned once per invocation, which is fine for a project-wide report but
+too slow to call per match. `--wasm-plugin` instead loads a `.wasm` module
+once with wasmtime and calls into it in-process for every match found by
+the normal search pipeline; see [`crate::search::SearchWorker`].
+
+## Guest ABI
+
+A plugin module must export:
+
+- `memory`: the module's linear memory.
+- `outgrep_alloc(len: i32) -> i32`: allocate `len` bytes in `memory` and
+  return the offset. Called once per match to give the guest a scratch
+  buffer to copy the matched line into.
+- `outgrep_filter(ptr: i32, len: i32) -> i64`: passed the offset and
+  length of a matched line already written into memory at `ptr` (via
+  `outgrep_alloc`). Returns a packed `(out_ptr << 32) | out_len` pair
+  pointing at the line to keep, which may be the same bytes or a rewritten
+  replacement, or `-1` to drop the match entirely.
+
+This mode is only available in binaries built with the `wasm-plugins`
+feature (on by default).
+*/
+
+#[cfg(feature = "wasm-plugins")]
+mod imp {
+    use std::path::Path;
+
+    use wasmtime::{Engine, Instance, Memory, Module, Store, TypedFunc};
+
+    /// A loaded `--wasm-plugin` module, ready to filter or rewrite matched
+    /// lines in-process.
+    pub(crate) struct WasmFilter {
+        store: Store<()>,
+        memory: Memory,
+        alloc: TypedFunc<i32, i32>,
+        filter: TypedFunc<(i32, i32), i64>,
+    }
+
+    impl WasmFilter {
+        /// Compile and instantiate the `.wasm` module at `path`.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if `path` can't be read, isn't valid
+        /// WebAssembly, or doesn't export `memory`, `outgrep_alloc`, and
+        /// `outgrep_filter` with the signatures the guest ABI requires.
+        pub(crate) fn load(path: &Path) -> anyhow::Result<WasmFilter> {
+            let engine = Engine::default();
+            let module = Module::from_file(&engine, path)?;
+            let mut store = Store::new(&engine, ());
+            let instance = Instance::new(&mut store, &module, &[])?;
+
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{}: wasm plugin has no exported `memory`",
+                        path.display()
+                    )
+                })?;
+            let alloc = instance
+                .get_typed_func::<i32, i32>(&mut store, "outgrep_alloc")
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "{}: wasm plugin has no exported \
+                         `outgrep_alloc(i32) -> i32`",
+                        path.display()
+                    )
+                })?;
+            let filter = instance
+                .get_typed_func::<(i32, i32), i64>(
+                    &mut store,
+                    "outgrep_filter",
+                )
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "{}: wasm plugin has no exported \
+                         `outgrep_filter(i32, i32) -> i64`",
+                        path.display()
+                    )
+                })?;
+
+            Ok(WasmFilter { store, memory, alloc, filter })
+        }
+
+        /// Run the plugin over one matched line.
+        ///
+        /// Returns `Some(bytes)` with the line to keep -- unchanged or
+        /// rewritten by the guest -- or `None` if the plugin says to drop
+        /// the match.
+        pub(crate) fn apply(
+            &mut self,
+            line: &[u8],
+        ) -> anyhow::Result<Option<Vec<u8>>> {
+            let len = i32::try_from(line.len())?;
+            let ptr = self.alloc.call(&mut self.store, len)?;
+            self.memory.write(&mut self.store, ptr as usize, line)?;
+
+            let packed = self.filter.call(&mut self.store, (ptr, len))?;
+            if packed < 0 {
+                return Ok(None);
+            }
+            let out_ptr = (packed >> 32) as u32 as usize;
+            let out_len = (packed & 0xFFFF_FFFF) as u32 as usize;
+            let mut out = vec![0u8; out_len];
+            self.memory.read(&self.store, out_ptr, &mut out)?;
+            Ok(Some(out))
+        }
+    }
+}
+
+#[cfg(feature = "wasm-plugins")]
+pub(crate) use imp::WasmFilter;
+
+#[cfg(not(feature = "wasm-plugins"))]
+pub(crate) struct WasmFilter;
+
+#[cfg(not(feature = "wasm-plugins"))]
+impl WasmFilter {
+    pub(crate) fn load(path: &std::path::Path) -> anyhow::Result<WasmFilter> {
+        anyhow::bail!(
+            "outgrep was built without the `wasm-plugins` feature; \
+             rebuild with `--features wasm-plugins` to use {}",
+            path.display()
+        )
+    }
+
+    pub(crate) fn apply(
+        &mut self,
+        _line: &[u8],
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        unreachable!(
+            "WasmFilter::load always fails without the wasm-plugins feature"
+        )
+    }
+}
@@ -0,0 +1,33 @@
+//! `diagnostics`: run a file's compiler/linter and return its diagnostics.
+//!
+//! Delegates to [`ripgrep::diagnostics::CompilerDiagnosticsRunner`], the
+//! same `cargo check`/`tsc`/`pyflakes`/etc. integration `--watch`'s
+//! diagnostics-on-save uses, so an editor extension gets the identical
+//! output the CLI would produce, without spawning `og` and parsing its
+//! stdout.
+
+use std::path::Path;
+
+use outgrep_ast_language::SupportLang;
+use ripgrep::diagnostics::compiler::CompilerDiagnosticsRunner;
+
+pub(crate) fn diagnostics_for_file(
+    path: &Path,
+    language: Option<&str>,
+) -> anyhow::Result<String> {
+    // `run_diagnostics` matches on the same `{:?}` language name
+    // `extract_ast_structure` reports (see `ast_extractor.rs`), so detect
+    // from the extension the same way when the caller doesn't pass one.
+    let detected =
+        SupportLang::from_path(path).map(|lang| format!("{lang:?}"));
+    let language = language.or(detected.as_deref());
+    let diagnostics =
+        CompilerDiagnosticsRunner::run_diagnostics(path, language)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{}: no compiler diagnostics available for this language",
+                    path.display()
+                )
+            })?;
+    Ok(serde_json::to_string(&diagnostics)?)
+}
@@ -0,0 +1,9 @@
+//! Shared helpers for converting outgrep's `anyhow::Result`-based APIs into
+//! the `napi::Result` the N-API bindings expect.
+
+/// Convert an `anyhow::Error` into a `napi::Error` carrying the same
+/// message, so a failed search/outline/diagnostics call surfaces as an
+/// ordinary JavaScript exception instead of a panic.
+pub(crate) fn to_napi_err(err: anyhow::Error) -> napi::Error {
+    napi::Error::from_reason(err.to_string())
+}
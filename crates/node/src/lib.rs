@@ -0,0 +1,90 @@
+/*!
+Node.js N-API bindings for outgrep's search, outline, and diagnostics
+engines, so a VS Code extension can use outgrep in-process for instant
+results instead of spawning the `og` binary and parsing its stdout.
+
+## Functionality
+
+- `searchWithCallback(pattern, path, callback)`: regex search over one
+  file, invoking `callback` once per match instead of collecting a result
+  array up front.
+- `outline(path)`: a file's symbol tree, as a JSON string.
+- `diagnostics(path, language?)`: a file's compiler/linter diagnostics, as
+  a JSON string.
+
+## Usage
+
+```javascript
+const { searchWithCallback, outline, diagnostics } = require("outgrep-node");
+
+searchWithCallback("TODO", "src/main.rs", (m) => {
+  console.log(m.lineNumber, m.text);
+});
+
+console.log(JSON.parse(outline("src/main.rs")));
+console.log(JSON.parse(diagnostics("src/main.rs")));
+```
+
+## Architecture
+
+Like `outgrep-capi` and `outgrep-py`, this crate is a thin binding layer,
+not a reimplementation: `searchWithCallback` calls the same `grep` facade
+crate `crates/core/search.rs` builds its `SearchWorker` on, and
+`outline`/`diagnostics` call straight into `ripgrep::diagnostics`.
+`SearchWorker` itself isn't reused directly for the same reason it isn't in
+`outgrep-capi`: it's tied to CLI flag parsing rather than exposed as a
+reusable library entry point.
+
+This crate is unrelated to `outgrep-ast-language`'s `napi-lang` feature
+flag, which controls compiling that crate's tree-sitter grammars for a
+leaner, pattern-matching-only WASM/N-API addon; this crate instead wraps
+the full `ripgrep` facade, the same way `outgrep-capi` does for C.
+
+## Dependencies
+
+- `grep`: the regex matcher and searcher backing `searchWithCallback`.
+- `ripgrep`: the `diagnostics` module backing `outline`/`diagnostics`.
+- `napi`/`napi-derive`: the Node.js N-API glue.
+*/
+
+mod diagnostics;
+mod ffi;
+mod outline;
+mod search;
+
+use std::path::Path;
+
+use napi::JsFunction;
+use napi_derive::napi;
+
+use ffi::to_napi_err;
+
+/// Run a regex search for `pattern` over `path`, invoking `callback` once
+/// per match with `{ lineNumber, text }`.
+#[napi]
+pub fn search_with_callback(
+    env: napi::Env,
+    pattern: String,
+    path: String,
+    callback: JsFunction,
+) -> napi::Result<()> {
+    search::search_with_callback(&env, &pattern, Path::new(&path), &callback)
+}
+
+/// Extract `path`'s symbol tree as a JSON string.
+#[napi]
+pub fn outline(path: String) -> napi::Result<String> {
+    outline::outline_file(Path::new(&path)).map_err(to_napi_err)
+}
+
+/// Run `path`'s compiler/linter and return its diagnostics as a JSON
+/// string. `language` overrides language detection (e.g. `"TypeScript"`);
+/// omit it to detect from the file extension.
+#[napi]
+pub fn diagnostics(
+    path: String,
+    language: Option<String>,
+) -> napi::Result<String> {
+    diagnostics::diagnostics_for_file(Path::new(&path), language.as_deref())
+        .map_err(to_napi_err)
+}
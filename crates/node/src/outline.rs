@@ -0,0 +1,23 @@
+//! `outline`: extract a file's symbol tree.
+//!
+//! Delegates straight to [`ripgrep::diagnostics::extract_ast_structure`],
+//! the same tree-sitter-backed extractor `--tree`/`--analyze` and
+//! `outgrep-capi`'s `outgrep_outline_json` use. Returned as a JSON string
+//! rather than a typed N-API object: `AstStructure` is a deeply nested,
+//! still-evolving type, and hand-mirroring its shape with `#[napi(object)]`
+//! would just be another place for the two to drift apart. Callers use
+//! `JSON.parse` on the result.
+
+use std::path::Path;
+
+use ripgrep::diagnostics::extract_ast_structure;
+
+pub(crate) fn outline_file(path: &Path) -> anyhow::Result<String> {
+    let structure = extract_ast_structure(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{}: unsupported language, unreadable, or empty",
+            path.display()
+        )
+    })?;
+    Ok(serde_json::to_string(&structure)?)
+}
@@ -0,0 +1,80 @@
+//! `search_with_callback`: run a regex search over a single file, invoking a
+//! JavaScript callback per match instead of collecting a `Vec` up front, so
+//! an editor extension can start rendering results before the whole file
+//! has been scanned.
+//!
+//! Like `outgrep-capi`'s `outgrep_search_json`, this calls straight into
+//! the `grep` facade crate rather than `SearchWorker`, which is tied to CLI
+//! flag parsing (`HiArgs`) and isn't a reusable library entry point.
+
+use std::path::Path;
+
+use grep::{
+    regex::RegexMatcherBuilder,
+    searcher::{Sink, SinkMatch},
+};
+use napi::JsFunction;
+use serde::Serialize;
+
+use crate::ffi::to_napi_err;
+
+/// One matching line, passed to the JavaScript callback as
+/// `{ lineNumber, text }`.
+#[napi(object)]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    pub line_number: Option<i64>,
+    pub text: String,
+}
+
+struct CallbackSink<'a> {
+    env: &'a napi::Env,
+    callback: &'a JsFunction,
+    error: Option<napi::Error>,
+}
+
+impl<'a> Sink for CallbackSink<'a> {
+    type Error = std::io::Error;
+
+    fn matched(
+        &mut self,
+        _searcher: &grep::searcher::Searcher,
+        mat: &SinkMatch<'_>,
+    ) -> Result<bool, std::io::Error> {
+        let text = String::from_utf8_lossy(mat.bytes()).into_owned();
+        let m = SearchMatch {
+            line_number: mat.line_number().map(|n| n as i64),
+            text: text.trim_end_matches(['\n', '\r']).to_string(),
+        };
+        let result = (|| -> napi::Result<()> {
+            let arg = self.env.to_js_value(&m)?;
+            self.callback.call(None, &[arg])?;
+            Ok(())
+        })();
+        if let Err(e) = result {
+            self.error = Some(e);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+pub(crate) fn search_with_callback(
+    env: &napi::Env,
+    pattern: &str,
+    path: &Path,
+    callback: &JsFunction,
+) -> napi::Result<()> {
+    let matcher = RegexMatcherBuilder::new()
+        .build(pattern)
+        .map_err(|e| to_napi_err(anyhow::anyhow!(e)))?;
+    let mut sink = CallbackSink { env, callback, error: None };
+    grep::searcher::Searcher::new()
+        .search_path(&matcher, path, &mut sink)
+        .map_err(|e| to_napi_err(anyhow::anyhow!(e)))?;
+    match sink.error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
@@ -16,6 +16,7 @@ pub fn default_color_specs() -> Vec<UserColorSpec> {
         "line:fg:green".parse().unwrap(),
         "match:fg:red".parse().unwrap(),
         "match:style:bold".parse().unwrap(),
+        "heading:style:bold".parse().unwrap(),
     ]
 }
 
@@ -90,6 +91,8 @@ pub struct ColorSpecs {
     line: ColorSpec,
     column: ColorSpec,
     matched: ColorSpec,
+    heading: ColorSpec,
+    number: ColorSpec,
 }
 
 /// A single color specification provided by the user.
@@ -99,7 +102,8 @@ pub struct ColorSpecs {
 /// The format of a `Spec` is a triple: `{type}:{attribute}:{value}`. Each
 /// component is defined as follows:
 ///
-/// * `{type}` can be one of `path`, `line`, `column` or `match`.
+/// * `{type}` can be one of `path`, `line`, `column`, `match`, `heading` or
+///   `number`.
 /// * `{attribute}` can be one of `fg`, `bg` or `style`. `{attribute}` may also
 ///   be the special value `none`, in which case, `{value}` can be omitted.
 /// * `{value}` is either a color name (for `fg`/`bg`) or a style instruction.
@@ -181,6 +185,8 @@ enum OutType {
     Line,
     Column,
     Match,
+    Heading,
+    Number,
 }
 
 /// The specification type.
@@ -214,6 +220,8 @@ impl ColorSpecs {
                 OutType::Line => spec.merge_into(&mut merged.line),
                 OutType::Column => spec.merge_into(&mut merged.column),
                 OutType::Match => spec.merge_into(&mut merged.matched),
+                OutType::Heading => spec.merge_into(&mut merged.heading),
+                OutType::Number => spec.merge_into(&mut merged.number),
             }
         }
         merged
@@ -247,6 +255,18 @@ impl ColorSpecs {
     pub fn matched(&self) -> &ColorSpec {
         &self.matched
     }
+
+    /// Return the color specification for coloring headings, e.g. in the
+    /// `--stats` summary or an `--analyze` report.
+    pub fn heading(&self) -> &ColorSpec {
+        &self.heading
+    }
+
+    /// Return the color specification for coloring plain numeric values,
+    /// e.g. counts in the `--stats` summary or an `--analyze` report.
+    pub fn number(&self) -> &ColorSpec {
+        &self.number
+    }
 }
 
 impl UserColorSpec {
@@ -340,6 +360,8 @@ impl std::str::FromStr for OutType {
             "line" => Ok(OutType::Line),
             "column" => Ok(OutType::Column),
             "match" => Ok(OutType::Match),
+            "heading" => Ok(OutType::Heading),
+            "number" => Ok(OutType::Number),
             _ => Err(ColorError::UnrecognizedOutType(s.to_string())),
         }
     }
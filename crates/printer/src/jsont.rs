@@ -45,6 +45,44 @@ impl<'a> serde::Serialize for Message<'a> {
     }
 }
 
+/// A message reporting a single path, with no associated match data. Used
+/// by the path printer's JSON mode (e.g. `--files --json-output`), which
+/// unlike the other message types here isn't emitted through a `Sink`.
+pub(crate) struct PathMessage<'a> {
+    pub(crate) path: &'a Path,
+}
+
+impl<'a> serde::Serialize for PathMessage<'a> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = s.serialize_struct("PathMessage", 2)?;
+        state.serialize_field("type", &"path")?;
+        state.serialize_field("data", &PathData { path: self.path })?;
+        state.end()
+    }
+}
+
+struct PathData<'a> {
+    path: &'a Path,
+}
+
+impl<'a> serde::Serialize for PathData<'a> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = s.serialize_struct("PathData", 1)?;
+        state.serialize_field("path", &Data::from_path(self.path))?;
+        state.end()
+    }
+}
+
 pub(crate) struct Begin<'a> {
     pub(crate) path: Option<&'a Path>,
 }
@@ -15,6 +15,8 @@ struct Config {
     hyperlink: HyperlinkConfig,
     separator: Option<u8>,
     terminator: u8,
+    #[cfg(feature = "serde")]
+    json: bool,
 }
 
 impl Default for Config {
@@ -24,6 +26,8 @@ impl Default for Config {
             hyperlink: HyperlinkConfig::default(),
             separator: None,
             terminator: b'\n',
+            #[cfg(feature = "serde")]
+            json: false,
         }
     }
 }
@@ -120,6 +124,19 @@ impl PathPrinterBuilder {
         self.config.terminator = terminator;
         self
     }
+
+    /// When enabled, paths are printed one per line as a JSON object instead
+    /// of as plain (optionally colored/hyperlinked) text. Color, hyperlinks
+    /// and the configured separator/terminator are all ignored in this mode,
+    /// matching how the other printers in this crate treat JSON output.
+    ///
+    /// This is disabled by default. Only available when this crate's
+    /// `serde` feature is enabled, which it is by default.
+    #[cfg(feature = "serde")]
+    pub fn json(&mut self, yes: bool) -> &mut PathPrinterBuilder {
+        self.config.json = yes;
+        self
+    }
 }
 
 /// A printer file paths, with optional color and hyperlink support.
@@ -148,6 +165,10 @@ pub struct PathPrinter<W> {
 impl<W: WriteColor> PathPrinter<W> {
     /// Write the given path to the underlying writer.
     pub fn write(&mut self, path: &Path) -> io::Result<()> {
+        #[cfg(feature = "serde")]
+        if self.config.json {
+            return self.write_json(path);
+        }
         let ppath = PrinterPath::new(path.as_ref())
             .with_separator(self.config.separator);
         if !self.wtr.supports_color() {
@@ -162,6 +183,17 @@ impl<W: WriteColor> PathPrinter<W> {
         self.wtr.write_all(&[self.config.terminator])
     }
 
+    /// Write the given path as a single JSON line, for `--json-output`.
+    #[cfg(feature = "serde")]
+    fn write_json(&mut self, path: &Path) -> io::Result<()> {
+        serde_json::to_writer(
+            &mut self.wtr,
+            &crate::jsont::PathMessage { path },
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        self.wtr.write_all(b"\n")
+    }
+
     /// Starts a hyperlink span when applicable.
     fn start_hyperlink(
         &mut self,
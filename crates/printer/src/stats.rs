@@ -18,6 +18,8 @@ pub struct Stats {
     bytes_printed: u64,
     matched_lines: u64,
     matches: u64,
+    embeddings_generated: u64,
+    embedding_elapsed: NiceDuration,
 }
 
 impl Stats {
@@ -102,6 +104,26 @@ impl Stats {
     pub fn add_matches(&mut self, n: u64) {
         self.matches += n;
     }
+
+    /// Return the total number of semantic search embeddings generated.
+    pub fn embeddings_generated(&self) -> u64 {
+        self.embeddings_generated
+    }
+
+    /// Return the total amount of time spent generating embeddings.
+    pub fn embedding_elapsed(&self) -> Duration {
+        self.embedding_elapsed.0
+    }
+
+    /// Add to the total number of semantic search embeddings generated.
+    pub fn add_embeddings_generated(&mut self, n: u64) {
+        self.embeddings_generated += n;
+    }
+
+    /// Add to the total amount of time spent generating embeddings.
+    pub fn add_embedding_elapsed(&mut self, duration: Duration) {
+        self.embedding_elapsed.0 += duration;
+    }
 }
 
 impl Add for Stats {
@@ -125,6 +147,11 @@ impl<'a> Add<&'a Stats> for Stats {
             bytes_printed: self.bytes_printed + rhs.bytes_printed,
             matched_lines: self.matched_lines + rhs.matched_lines,
             matches: self.matches + rhs.matches,
+            embeddings_generated: self.embeddings_generated
+                + rhs.embeddings_generated,
+            embedding_elapsed: NiceDuration(
+                self.embedding_elapsed.0 + rhs.embedding_elapsed.0,
+            ),
         }
     }
 }
@@ -144,6 +171,8 @@ impl<'a> AddAssign<&'a Stats> for Stats {
         self.bytes_printed += rhs.bytes_printed;
         self.matched_lines += rhs.matched_lines;
         self.matches += rhs.matches;
+        self.embeddings_generated += rhs.embeddings_generated;
+        self.embedding_elapsed.0 += rhs.embedding_elapsed.0;
     }
 }
 
@@ -155,7 +184,7 @@ impl serde::Serialize for Stats {
     ) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
 
-        let mut state = s.serialize_struct("Stats", 7)?;
+        let mut state = s.serialize_struct("Stats", 9)?;
         state.serialize_field("elapsed", &self.elapsed)?;
         state.serialize_field("searches", &self.searches)?;
         state.serialize_field(
@@ -166,6 +195,11 @@ impl serde::Serialize for Stats {
         state.serialize_field("bytes_printed", &self.bytes_printed)?;
         state.serialize_field("matched_lines", &self.matched_lines)?;
         state.serialize_field("matches", &self.matches)?;
+        state.serialize_field(
+            "embeddings_generated",
+            &self.embeddings_generated,
+        )?;
+        state.serialize_field("embedding_elapsed", &self.embedding_elapsed)?;
         state.end()
     }
 }
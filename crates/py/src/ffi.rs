@@ -0,0 +1,12 @@
+//! Shared helpers for converting outgrep's `anyhow::Result`-based APIs into
+//! the `PyResult` pyo3 expects.
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::PyErr;
+
+/// Convert an `anyhow::Error` into a Python `RuntimeError` carrying the same
+/// message, so a failed search/outline/metrics/semantic call surfaces as an
+/// ordinary Python exception instead of a panic.
+pub(crate) fn to_pyerr(err: anyhow::Error) -> PyErr {
+    PyRuntimeError::new_err(err.to_string())
+}
@@ -0,0 +1,130 @@
+/*!
+Python bindings for outgrep's search, outline, metrics, and semantic query
+engines, for data-science-style repo analysis (pandas/notebook workflows)
+without shelling out to the `og` binary and parsing its stdout.
+
+## Functionality
+
+- `SearchSession(root)`: constructed once per repository, then reused for:
+  - `.search(pattern, path)`: regex search over one file, returned as an
+    iterator of `SearchMatch` objects.
+  - `.outline(path)`: a file's symbol tree, as a JSON string.
+  - `.metrics(path)`: code metrics for a file, as a JSON string.
+  - `.semantic_query(query, path, top_k=10)`: embed a file's chunks and rank
+    them against a natural-language query, returned as a list of
+    `SemanticMatch` objects.
+
+## Usage
+
+```python
+from outgrep_py import SearchSession
+
+session = SearchSession("/path/to/repo")
+for m in session.search("TODO", "src/main.rs"):
+    print(m.line_number, m.text)
+
+for m in session.semantic_query("parses command line flags", "src/main.rs"):
+    print(m.similarity, m.content)
+```
+
+## Architecture
+
+Like `outgrep-capi`, this crate is a thin binding layer, not a
+reimplementation: `.search` calls the same `grep` facade crate
+`crates/core/search.rs` builds its `SearchWorker` on, `.outline`/`.metrics`
+call straight into `ripgrep::diagnostics`, and `.semantic_query` runs the
+same chunk/embed/index/rank pipeline `--semantic-query` does, minus the CLI
+flag plumbing (`HiArgs`) that pipeline is otherwise built on top of.
+`SearchWorker` itself isn't reused directly for the same reason it isn't in
+`outgrep-capi`: it's tied to CLI flag parsing rather than exposed as a
+reusable library entry point.
+
+Relative paths passed to any `SearchSession` method are resolved against
+`root`; absolute paths are used as-is.
+
+## Dependencies
+
+- `grep`: the regex matcher, searcher, and semantic search engine backing
+  `.search` and `.semantic_query`.
+- `ripgrep`: the `diagnostics` module backing `.outline`/`.metrics`.
+- `pyo3`: the Python extension-module glue.
+*/
+
+mod ffi;
+mod metrics;
+mod outline;
+mod search;
+mod semantic;
+
+use std::path::{Path, PathBuf};
+
+use pyo3::prelude::*;
+
+use ffi::to_pyerr;
+pub use search::{SearchMatch, SearchMatches};
+pub use semantic::SemanticMatch;
+
+/// Entry point for the Python bindings: constructed once with a repository
+/// root, then reused across many `.search`/`.outline`/`.metrics`/
+/// `.semantic_query` calls.
+#[pyclass]
+pub struct SearchSession {
+    root: PathBuf,
+}
+
+impl SearchSession {
+    fn resolve(&self, path: &str) -> PathBuf {
+        let path = Path::new(path);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root.join(path)
+        }
+    }
+}
+
+#[pymethods]
+impl SearchSession {
+    #[new]
+    fn new(root: String) -> Self {
+        SearchSession { root: PathBuf::from(root) }
+    }
+
+    /// Run a regex search for `pattern` over `path`, returned as an
+    /// iterator of `SearchMatch` objects.
+    fn search(&self, pattern: &str, path: &str) -> PyResult<SearchMatches> {
+        search::search_file(pattern, &self.resolve(path)).map_err(to_pyerr)
+    }
+
+    /// Extract `path`'s symbol tree as a JSON string.
+    fn outline(&self, path: &str) -> PyResult<String> {
+        outline::outline_file(&self.resolve(path)).map_err(to_pyerr)
+    }
+
+    /// Calculate code metrics for `path` as a JSON string.
+    fn metrics(&self, path: &str) -> PyResult<String> {
+        metrics::metrics_for_file(&self.resolve(path)).map_err(to_pyerr)
+    }
+
+    /// Embed `path`'s chunks and rank the top `top_k` against `query`.
+    #[pyo3(signature = (query, path, top_k=10))]
+    fn semantic_query(
+        &self,
+        query: &str,
+        path: &str,
+        top_k: usize,
+    ) -> PyResult<Vec<SemanticMatch>> {
+        semantic::semantic_query_file(&self.resolve(path), query, top_k)
+            .map_err(to_pyerr)
+    }
+}
+
+/// The `outgrep_py` Python module: `from outgrep_py import SearchSession`.
+#[pymodule]
+fn outgrep_py(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<SearchSession>()?;
+    m.add_class::<SearchMatch>()?;
+    m.add_class::<SearchMatches>()?;
+    m.add_class::<SemanticMatch>()?;
+    Ok(())
+}
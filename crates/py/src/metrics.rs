@@ -0,0 +1,16 @@
+//! `SearchSession.metrics`: calculate code metrics for a single file.
+//!
+//! Delegates to [`ripgrep::diagnostics::MetricsCalculator`], the same
+//! calculator `--analyze` and `outgrep-capi`'s `outgrep_metrics_json` use,
+//! and serializes the result to JSON for the same reason `outline` does.
+
+use std::{fs, path::Path};
+
+use ripgrep::diagnostics::MetricsCalculator;
+
+pub(crate) fn metrics_for_file(path: &Path) -> anyhow::Result<String> {
+    let content = fs::read_to_string(path)?;
+    let metrics = MetricsCalculator::calculate_metrics(path, &content)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    Ok(serde_json::to_string(&metrics)?)
+}
@@ -0,0 +1,23 @@
+//! `SearchSession.outline`: extract a file's symbol tree.
+//!
+//! Delegates straight to [`ripgrep::diagnostics::extract_ast_structure`],
+//! the same tree-sitter-backed extractor `--tree`/`--analyze` and
+//! `outgrep-capi`'s `outgrep_outline_json` use. The result is serialized to
+//! JSON rather than mirrored as a tree of pyclasses: `AstStructure` is a
+//! deeply nested, still-evolving type, and duplicating its shape on the
+//! Python side would just be another place for the two to drift apart.
+//! Callers use the standard library's `json` module to walk it.
+
+use std::path::Path;
+
+use ripgrep::diagnostics::extract_ast_structure;
+
+pub(crate) fn outline_file(path: &Path) -> anyhow::Result<String> {
+    let structure = extract_ast_structure(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{}: unsupported language, unreadable, or empty",
+            path.display()
+        )
+    })?;
+    Ok(serde_json::to_string(&structure)?)
+}
@@ -0,0 +1,78 @@
+//! `SearchSession.search`: run a regex search over a single file and hand
+//! the matches back to Python as an iterator.
+//!
+//! Like `outgrep-capi`'s `outgrep_search_json`, this calls straight into the
+//! `grep` facade crate rather than `SearchWorker`, which is tied to CLI flag
+//! parsing (`HiArgs`) and isn't a reusable library entry point.
+
+use std::path::Path;
+
+use grep::{
+    regex::RegexMatcherBuilder,
+    searcher::{Sink, SinkMatch},
+};
+use pyo3::prelude::*;
+
+/// One matching line from `SearchSession.search`.
+#[pyclass]
+#[derive(Clone)]
+pub struct SearchMatch {
+    #[pyo3(get)]
+    line_number: Option<u64>,
+    #[pyo3(get)]
+    text: String,
+}
+
+/// Iterator over a search's [`SearchMatch`] results, returned by
+/// `SearchSession.search` so callers can `for m in session.search(...)`
+/// without materializing the whole result set up front on the Python side.
+#[pyclass]
+pub struct SearchMatches {
+    inner: std::vec::IntoIter<SearchMatch>,
+}
+
+#[pymethods]
+impl SearchMatches {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> Option<SearchMatch> {
+        slf.inner.next()
+    }
+}
+
+struct CollectMatches<'a> {
+    matches: &'a mut Vec<SearchMatch>,
+}
+
+impl<'a> Sink for CollectMatches<'a> {
+    type Error = std::io::Error;
+
+    fn matched(
+        &mut self,
+        _searcher: &grep::searcher::Searcher,
+        mat: &SinkMatch<'_>,
+    ) -> Result<bool, std::io::Error> {
+        let text = String::from_utf8_lossy(mat.bytes()).into_owned();
+        self.matches.push(SearchMatch {
+            line_number: mat.line_number(),
+            text: text.trim_end_matches(['\n', '\r']).to_string(),
+        });
+        Ok(true)
+    }
+}
+
+pub(crate) fn search_file(
+    pattern: &str,
+    path: &Path,
+) -> anyhow::Result<SearchMatches> {
+    let matcher = RegexMatcherBuilder::new().build(pattern)?;
+    let mut matches = Vec::new();
+    grep::searcher::Searcher::new().search_path(
+        &matcher,
+        path,
+        CollectMatches { matches: &mut matches },
+    )?;
+    Ok(SearchMatches { inner: matches.into_iter() })
+}
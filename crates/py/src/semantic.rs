@@ -0,0 +1,84 @@
+//! `SearchSession.semantic_query`: embed a file's contents and rank its
+//! chunks against a natural-language query.
+//!
+//! This mirrors the indexing pipeline `crates/core/search.rs` runs for
+//! `--semantic-*`: split the file into chunks with
+//! [`grep::searcher::semantic::chunk_content`], embed each chunk with
+//! [`grep::searcher::semantic::generate_embeddings_parallel`], build a
+//! one-file [`grep::searcher::semantic::SemanticIndex`], then rank it with
+//! [`grep::searcher::SemanticSearcher`] -- just without the CLI flag
+//! plumbing (`HiArgs`) that pipeline is otherwise built on.
+
+use std::{fs, path::Path};
+
+use grep::searcher::semantic::{
+    build_index, chunk_content, generate_embeddings_parallel, SemanticConfig,
+};
+use grep::searcher::{
+    create_ast_calculator_for_file, default_context_types, SemanticSearcher,
+};
+use pyo3::prelude::*;
+
+/// One ranked chunk from `SearchSession.semantic_query`.
+#[pyclass]
+#[derive(Clone)]
+pub struct SemanticMatch {
+    #[pyo3(get)]
+    similarity: f32,
+    #[pyo3(get)]
+    start_byte: usize,
+    #[pyo3(get)]
+    end_byte: usize,
+    #[pyo3(get)]
+    content: String,
+}
+
+pub(crate) fn semantic_query_file(
+    path: &Path,
+    query: &str,
+    top_k: usize,
+) -> anyhow::Result<Vec<SemanticMatch>> {
+    let content = fs::read_to_string(path)?;
+    let mut config = SemanticConfig::default();
+    config.max_results = top_k;
+
+    let ast_calculator = create_ast_calculator_for_file(
+        path,
+        &content,
+        Some(default_context_types()),
+    )
+    .map_err(|e| anyhow::anyhow!("AST parsing failed: {e}"))?;
+
+    let chunks = chunk_content(
+        &content,
+        Some(&ast_calculator),
+        config.chunking_strategy,
+        config.chunk_window_size,
+        config.chunk_window_overlap,
+    );
+    let snippets: Vec<String> =
+        chunks.iter().map(|c| c.content.clone()).collect();
+    let embedded = generate_embeddings_parallel(&snippets, &config);
+    let embeddings = embedded
+        .into_iter()
+        .zip(chunks)
+        .map(|(embedding, chunk)| {
+            (embedding, chunk.range, chunk.content, Some(path.to_path_buf()))
+        })
+        .collect();
+
+    let index = build_index(embeddings, &config);
+    let mut searcher = SemanticSearcher::new(config);
+    searcher.set_index(index);
+
+    Ok(searcher
+        .search(query)
+        .into_iter()
+        .map(|m| SemanticMatch {
+            similarity: m.similarity,
+            start_byte: m.byte_range.start,
+            end_byte: m.byte_range.end,
+            content: m.content,
+        })
+        .collect())
+}
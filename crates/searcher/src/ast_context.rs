@@ -6,6 +6,7 @@ methods, etc.) around search matches using Abstract Syntax Tree analysis.
 */
 
 use outgrep_ast_core::{AstGrep, Doc, Node};
+use outgrep_ast_language::SupportLang;
 use std::ops::Range;
 
 /// Types of AST nodes that can provide meaningful context.
@@ -36,6 +37,9 @@ pub struct AstContextResult {
     pub symbol_name: Option<String>,
     /// Nesting level of the symbol
     pub depth: u32,
+    /// Leading doc comment block immediately preceding the symbol, if any.
+    /// Always populated when present; callers decide whether to display it.
+    pub doc_comment: Option<String>,
 }
 
 /// Calculator for AST-based context using ast-grep.
@@ -44,6 +48,10 @@ pub struct AstContextCalculator<D: Doc> {
     ast_grep: AstGrep<D>,
     /// Types of context nodes we're interested in
     context_types: Vec<AstContextType>,
+    /// The language this calculator was built for, used to pick a
+    /// highlight query for syntax-highlighting token output. `None` for
+    /// callers (tests, mainly) that don't care about highlighting.
+    support_lang: Option<SupportLang>,
 }
 
 impl<D: Doc> AstContextCalculator<D> {
@@ -52,7 +60,18 @@ impl<D: Doc> AstContextCalculator<D> {
         ast_grep: AstGrep<D>,
         context_types: Vec<AstContextType>,
     ) -> Self {
-        Self { ast_grep, context_types }
+        Self { ast_grep, context_types, support_lang: None }
+    }
+
+    /// Create a new AST context calculator that also knows which
+    /// [`SupportLang`] it was built for, so it can run that language's
+    /// highlight query.
+    pub fn with_support_lang(
+        ast_grep: AstGrep<D>,
+        context_types: Vec<AstContextType>,
+        support_lang: SupportLang,
+    ) -> Self {
+        Self { ast_grep, context_types, support_lang: Some(support_lang) }
     }
 
     /// Get the root node for syntax highlighting.
@@ -60,6 +79,11 @@ impl<D: Doc> AstContextCalculator<D> {
         self.ast_grep.root()
     }
 
+    /// The [`SupportLang`] this calculator was built for, if any.
+    pub fn support_lang(&self) -> Option<SupportLang> {
+        self.support_lang
+    }
+
     /// Calculate the enclosing symbol context for a given match range.
     pub fn calculate_context(
         &self,
@@ -82,12 +106,14 @@ impl<D: Doc> AstContextCalculator<D> {
         if let Some(node) = best_node {
             let context_type = self.classify_node(&node)?;
             let symbol_name = self.extract_symbol_name(&node);
+            let doc_comment = extract_doc_comment(&node);
 
             Ok(AstContextResult {
                 range: node.range().start..node.range().end,
                 context_type,
                 symbol_name,
                 depth: best_depth,
+                doc_comment,
             })
         } else {
             Err(AstContextError::NoEnclosingSymbol { range: match_range })
@@ -257,6 +283,109 @@ impl<D: Doc> AstContextCalculator<D> {
         }
         None
     }
+
+    /// Walk the entire tree once and return every node matching one of our
+    /// context types, rather than just the single best-enclosing node for a
+    /// particular match range.
+    ///
+    /// This is used for symbol-based embedding chunking, where we want one
+    /// chunk per function/class/module rather than a single lookup per
+    /// match.
+    pub fn find_all_context_nodes(&self) -> Vec<AstContextResult> {
+        let mut results = Vec::new();
+        self.collect_context_nodes(self.ast_grep.root(), 0, &mut results);
+        results
+    }
+
+    /// Recursively collect every context node under `node` into `results`.
+    ///
+    /// Unlike `find_enclosing_node_recursive`, this doesn't stop at the
+    /// deepest match for a single range -- it records every context node it
+    /// sees and keeps descending, since sibling functions/classes all need
+    /// their own chunk.
+    fn collect_context_nodes<'a>(
+        &self,
+        node: Node<'a, D>,
+        depth: u32,
+        results: &mut Vec<AstContextResult>,
+    ) {
+        if self.is_context_node(&node) {
+            // classify_node can only fail for a node type we didn't match
+            // against context_types, which can't happen here since
+            // is_context_node just confirmed a match.
+            if let Ok(context_type) = self.classify_node(&node) {
+                let symbol_name = self.extract_symbol_name(&node);
+                let doc_comment = extract_doc_comment(&node);
+                results.push(AstContextResult {
+                    range: node.range().start..node.range().end,
+                    context_type,
+                    symbol_name,
+                    depth,
+                    doc_comment,
+                });
+            }
+        }
+
+        for child in node.children() {
+            self.collect_context_nodes(child, depth + 1, results);
+        }
+    }
+}
+
+/// Check if a node type represents a comment, across the languages we
+/// support (tree-sitter grammars name these differently).
+fn is_comment_kind(kind: &str) -> bool {
+    matches!(kind, "comment" | "line_comment" | "block_comment")
+}
+
+/// Strip a single comment line's leading marker (`///`, `//!`, `//`, `#`,
+/// or the `*`/`/**`/`*/` used in block comments) and surrounding
+/// whitespace, so doc comments read the same regardless of language.
+fn strip_comment_marker(line: &str) -> String {
+    let line = line.trim();
+    let line = line
+        .strip_prefix("///")
+        .or_else(|| line.strip_prefix("//!"))
+        .or_else(|| line.strip_prefix("//"))
+        .or_else(|| line.strip_prefix("/**"))
+        .or_else(|| line.strip_prefix("/*"))
+        .unwrap_or(line);
+    let line = line.strip_suffix("*/").unwrap_or(line);
+    let line = line.strip_prefix('#').unwrap_or(line);
+    let line = line.strip_prefix('*').unwrap_or(line);
+    line.trim().to_string()
+}
+
+/// Extract the leading doc comment block immediately preceding `node`, if
+/// any. Consecutive comment siblings directly above the symbol are treated
+/// as a single block, oldest first.
+fn extract_doc_comment<D: Doc>(node: &Node<D>) -> Option<String> {
+    let mut comments = Vec::new();
+    let mut current = node.prev();
+    while let Some(sibling) = current {
+        if !is_comment_kind(&sibling.kind()) {
+            break;
+        }
+        comments.push(sibling.text().to_string());
+        current = sibling.prev();
+    }
+    if comments.is_empty() {
+        return None;
+    }
+    comments.reverse();
+    let text = comments
+        .iter()
+        .flat_map(|c| c.lines())
+        .map(strip_comment_marker)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
 }
 
 /// Errors that can occur during AST context calculation.
@@ -0,0 +1,91 @@
+/*!
+Real tree-sitter highlight queries backing
+[`AstCalculator::get_syntax_nodes`](crate::language_detection::AstCalculator::get_syntax_nodes).
+
+`get_syntax_nodes` used to guess at tokens with substring search over
+keyword lists and quote characters, which meant e.g. the "if" inside an
+identifier like `gifted` or a `//` inside a string literal could be
+misreported. This module instead runs an actual `tree_sitter::Query`
+against the parsed tree and reports each capture's real node range, so a
+token is only ever a keyword, string, etc. if the grammar says so.
+
+Queries live under `highlight_queries/` as one `.scm` file per supported
+language, embedded with `include_str!`. They're hand-written rather than
+copied from the upstream grammars' own `highlights.scm` files (which carry
+their own licenses and cover far more capture names than we use), and are
+deliberately small: just the `comment`, `string`, `keyword`, `function`
+and `type` captures that `get_syntax_nodes` has always reported.
+
+Only a subset of [`SupportLang`] has a query today (Rust, Python,
+JavaScript, TypeScript, Go, Java, C and C++); [`query_source`] returns
+`None` for the rest, and `get_syntax_nodes` falls back to an empty token
+list rather than guessing. Extending coverage to the remaining languages
+is tracked as follow-on work, one `.scm` file at a time.
+*/
+
+use std::ops::Range;
+
+use outgrep_ast_core::tree_sitter::LanguageExt;
+use outgrep_ast_language::SupportLang;
+use tree_sitter::{Query, QueryCursor, StreamingIterator};
+
+/// Return the highlight query source for `lang`, or `None` if we don't
+/// ship one yet.
+pub fn query_source(lang: SupportLang) -> Option<&'static str> {
+    Some(match lang {
+        SupportLang::Rust => {
+            include_str!("highlight_queries/rust.scm")
+        }
+        SupportLang::Python => {
+            include_str!("highlight_queries/python.scm")
+        }
+        SupportLang::JavaScript => {
+            include_str!("highlight_queries/javascript.scm")
+        }
+        SupportLang::TypeScript => {
+            include_str!("highlight_queries/typescript.scm")
+        }
+        SupportLang::Go => include_str!("highlight_queries/go.scm"),
+        SupportLang::Java => include_str!("highlight_queries/java.scm"),
+        SupportLang::C => include_str!("highlight_queries/c.scm"),
+        SupportLang::Cpp => include_str!("highlight_queries/cpp.scm"),
+        _ => return None,
+    })
+}
+
+/// Run `lang`'s highlight query over `root`/`source`, returning
+/// `(byte_range, capture_name)` pairs sorted by start position. Capture
+/// names are exactly the `@name`s used in the `.scm` query (`"keyword"`,
+/// `"string"`, `"comment"`, `"function"`, `"type"`).
+///
+/// Returns an empty list if `lang` has no query yet, or if the query fails
+/// to compile against the language (which would indicate a bug in the
+/// `.scm` file, not the input source).
+pub fn highlight<L: LanguageExt>(
+    lang: &L,
+    support_lang: SupportLang,
+    root: tree_sitter::Node,
+    source: &[u8],
+) -> Vec<(Range<usize>, String)> {
+    let Some(query_src) = query_source(support_lang) else {
+        return Vec::new();
+    };
+    let ts_lang = lang.get_ts_language();
+    let query = match Query::new(&ts_lang, query_src) {
+        Ok(query) => query,
+        Err(_) => return Vec::new(),
+    };
+    let capture_names = query.capture_names();
+
+    let mut tokens = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut captures = cursor.captures(&query, root, source);
+    while let Some((query_match, capture_index)) = captures.next() {
+        let capture = query_match.captures[*capture_index];
+        let name = capture_names[capture.index as usize];
+        tokens.push((capture.node.byte_range(), name.to_string()));
+    }
+
+    tokens.sort_by_key(|(range, _)| range.start);
+    tokens
+}
@@ -68,7 +68,7 @@ impl AstContextCalculatorWrapper {
                     });
                 }
 
-                Box::new(AstContextCalculator::new(ast_grep, context_types.clone())) as Box<dyn AstCalculator>
+                Box::new(AstContextCalculator::with_support_lang(ast_grep, context_types.clone(), lang)) as Box<dyn AstCalculator>
             }};
         }
 
@@ -149,6 +149,15 @@ impl AstContextCalculatorWrapper {
             SupportLang::Tsx => {
                 create_calculator!(outgrep_ast_language::Tsx, "TSX")
             }
+            SupportLang::Zig => {
+                create_calculator!(outgrep_ast_language::Zig, "Zig")
+            }
+            SupportLang::Dart => {
+                create_calculator!(outgrep_ast_language::Dart, "Dart")
+            }
+            SupportLang::Nim => {
+                create_calculator!(outgrep_ast_language::Nim, "Nim")
+            }
         };
 
         Ok(Self::Calculator(calculator))
@@ -163,6 +172,14 @@ impl AstContextCalculatorWrapper {
             Self::Calculator(calc) => calc.calculate_context(match_range),
         }
     }
+
+    /// Find every function/class/module-level symbol in the file, for use
+    /// as embedding chunk boundaries.
+    pub fn find_all_symbols(&self) -> Vec<crate::ast_context::AstContextResult> {
+        match self {
+            Self::Calculator(calc) => calc.find_all_symbols(),
+        }
+    }
 }
 
 /// Trait for type-erased AST calculators.
@@ -175,6 +192,10 @@ pub trait AstCalculator {
 
     /// Get syntax highlighting information as (range, kind) pairs.
     fn get_syntax_nodes(&self) -> Vec<(std::ops::Range<usize>, String)>;
+
+    /// Find every function/class/module-level symbol in the file, for use
+    /// as embedding chunk boundaries.
+    fn find_all_symbols(&self) -> Vec<crate::ast_context::AstContextResult>;
 }
 
 impl<D> AstCalculator for AstContextCalculator<StrDoc<D>>
@@ -188,195 +209,21 @@ where
         self.calculate_context(match_range)
     }
 
-    fn get_syntax_nodes(&self) -> Vec<(std::ops::Range<usize>, String)> {
-        // Simple string-based approach for clean syntax highlighting
-        // This avoids AST node fragmentation issues and external dependencies
+    fn find_all_symbols(&self) -> Vec<crate::ast_context::AstContextResult> {
+        self.find_all_context_nodes()
+    }
 
+    fn get_syntax_nodes(&self) -> Vec<(std::ops::Range<usize>, String)> {
+        let Some(support_lang) = self.support_lang() else {
+            return Vec::new();
+        };
         let root = self.get_root_node();
-        let content = root.text();
-        let mut tokens = Vec::new();
-
-        // Define keywords for different languages
-        let keywords = [
-            // Rust keywords
-            "fn", "let", "mut", "const", "if", "else", "for", "while", "loop",
-            "match", "return", "struct", "enum", "impl", "trait", "pub",
-            "use", "mod", "crate", "self", "super", "where", "unsafe",
-            "async", "await", "true", "false", "None", "Some",
-            // Python keywords
-            "def", "class", "import", "from", "elif", "try", "except",
-            "finally", "with", "as", "yield", "break", "continue", "pass",
-            "lambda", "global", "nonlocal", "True", "False",
-            // Common keywords across languages
-            "if", "else", "for", "while", "return", "import", "true", "false",
-            "null",
-        ];
-
-        // Find keyword matches
-        for keyword in keywords.iter() {
-            let mut start = 0;
-            while let Some(pos) = content[start..].find(keyword) {
-                let abs_pos = start + pos;
-                let end_pos = abs_pos + keyword.len();
-
-                // Check word boundaries (simple approach)
-                let before_ok = abs_pos == 0
-                    || !content
-                        .chars()
-                        .nth(abs_pos - 1)
-                        .unwrap_or(' ')
-                        .is_alphanumeric();
-                let after_ok = end_pos >= content.len()
-                    || !content
-                        .chars()
-                        .nth(end_pos)
-                        .unwrap_or(' ')
-                        .is_alphanumeric();
-
-                if before_ok && after_ok {
-                    let range = abs_pos..end_pos;
-
-                    // Check for overlaps
-                    let overlaps = tokens.iter().any(
-                        |(existing_range, _): &(
-                            std::ops::Range<usize>,
-                            String,
-                        )| {
-                            range.start < existing_range.end
-                                && existing_range.start < range.end
-                        },
-                    );
-
-                    if !overlaps {
-                        tokens.push((range, "keyword".to_string()));
-                    }
-                }
-
-                start = abs_pos + 1;
-            }
-        }
-
-        // Find string literals (simple quotes)
-        let string_patterns = ['"', '\''];
-        for quote in string_patterns.iter() {
-            let mut start = 0;
-            while let Some(start_pos) = content[start..].find(*quote) {
-                let abs_start = start + start_pos;
-                if let Some(end_pos) = content[abs_start + 1..].find(*quote) {
-                    let abs_end = abs_start + 1 + end_pos + 1;
-                    let range = abs_start..abs_end;
-
-                    // Check for overlaps
-                    let overlaps = tokens.iter().any(
-                        |(existing_range, _): &(
-                            std::ops::Range<usize>,
-                            String,
-                        )| {
-                            range.start < existing_range.end
-                                && existing_range.start < range.end
-                        },
-                    );
-
-                    if !overlaps {
-                        tokens.push((range, "string".to_string()));
-                    }
-
-                    start = abs_end;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        // Find comments
-        let mut start = 0;
-        while let Some(pos) = content[start..].find("//") {
-            let abs_pos = start + pos;
-            if let Some(end_pos) = content[abs_pos..].find('\n') {
-                let abs_end = abs_pos + end_pos;
-                let range = abs_pos..abs_end;
-
-                // Check for overlaps
-                let overlaps = tokens.iter().any(
-                    |(existing_range, _): &(
-                        std::ops::Range<usize>,
-                        String,
-                    )| {
-                        range.start < existing_range.end
-                            && existing_range.start < range.end
-                    },
-                );
-
-                if !overlaps {
-                    tokens.push((range, "comment".to_string()));
-                }
-
-                start = abs_end;
-            } else {
-                // Comment to end of file
-                let range = abs_pos..content.len();
-                let overlaps = tokens.iter().any(
-                    |(existing_range, _): &(
-                        std::ops::Range<usize>,
-                        String,
-                    )| {
-                        range.start < existing_range.end
-                            && existing_range.start < range.end
-                    },
-                );
-
-                if !overlaps {
-                    tokens.push((range, "comment".to_string()));
-                }
-                break;
-            }
-        }
-
-        // Find Python-style comments
-        start = 0;
-        while let Some(pos) = content[start..].find('#') {
-            let abs_pos = start + pos;
-            if let Some(end_pos) = content[abs_pos..].find('\n') {
-                let abs_end = abs_pos + end_pos;
-                let range = abs_pos..abs_end;
-
-                let overlaps = tokens.iter().any(
-                    |(existing_range, _): &(
-                        std::ops::Range<usize>,
-                        String,
-                    )| {
-                        range.start < existing_range.end
-                            && existing_range.start < range.end
-                    },
-                );
-
-                if !overlaps {
-                    tokens.push((range, "comment".to_string()));
-                }
-
-                start = abs_end;
-            } else {
-                let range = abs_pos..content.len();
-                let overlaps = tokens.iter().any(
-                    |(existing_range, _): &(
-                        std::ops::Range<usize>,
-                        String,
-                    )| {
-                        range.start < existing_range.end
-                            && existing_range.start < range.end
-                    },
-                );
-
-                if !overlaps {
-                    tokens.push((range, "comment".to_string()));
-                }
-                break;
-            }
-        }
-
-        // Sort by start position
-        tokens.sort_by_key(|(range, _)| range.start);
-        tokens
+        crate::highlight::highlight(
+            root.lang(),
+            support_lang,
+            root.get_inner_node(),
+            root.text().as_bytes(),
+        )
     }
 }
 
@@ -98,8 +98,9 @@ pub use crate::{
         SearcherBuilder,
     },
     semantic::{
-        Embedding, SemanticConfig, SemanticIndex, SemanticMatch,
-        SemanticSearcher,
+        cluster_matches, ChunkingStrategy, DimensionMismatchPolicy, Embedding,
+        QueryFusion, SemanticBackend, SemanticCluster, SemanticConfig,
+        SemanticIndex, SemanticMatch, SemanticQuantize, SemanticSearcher,
     },
     sink::{
         sinks, Sink, SinkContext, SinkContextKind, SinkError, SinkFinish,
@@ -111,6 +112,7 @@ pub use crate::{
 mod macros;
 
 mod ast_context;
+mod highlight;
 mod language_detection;
 mod line_buffer;
 mod lines;
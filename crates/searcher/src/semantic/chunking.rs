@@ -0,0 +1,155 @@
+use std::ops::Range;
+
+use crate::AstContextCalculatorWrapper;
+
+/// How a file's content is divided into chunks before being embedded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkingStrategy {
+    /// One chunk per function/class/module-level symbol discovered by
+    /// walking the AST once. Falls back to a single file-level chunk when
+    /// no symbols are found, e.g. for unsupported languages.
+    Symbol,
+    /// Fixed-size, overlapping windows over the raw text, ignoring AST
+    /// structure entirely.
+    SlidingWindow,
+    /// The entire file as a single chunk.
+    File,
+}
+
+impl Default for ChunkingStrategy {
+    fn default() -> ChunkingStrategy {
+        ChunkingStrategy::Symbol
+    }
+}
+
+/// A single unit of content to embed, along with the byte range it was
+/// taken from.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    /// The byte range of this chunk within the original file content.
+    pub range: Range<usize>,
+    /// The chunk's text.
+    pub content: String,
+}
+
+/// Split `content` into chunks according to `strategy`.
+///
+/// `ast_calculator` is only consulted for `ChunkingStrategy::Symbol`; it is
+/// ignored for `SlidingWindow` and `File`.
+pub fn chunk_content(
+    content: &str,
+    ast_calculator: Option<&AstContextCalculatorWrapper>,
+    strategy: ChunkingStrategy,
+    window_size: usize,
+    window_overlap: usize,
+) -> Vec<Chunk> {
+    match strategy {
+        ChunkingStrategy::File => vec![whole_file_chunk(content)],
+        ChunkingStrategy::SlidingWindow => {
+            chunk_sliding_window(content, window_size, window_overlap)
+        }
+        ChunkingStrategy::Symbol => {
+            let symbol_chunks = ast_calculator
+                .map(|calc| {
+                    calc.find_all_symbols()
+                        .into_iter()
+                        .filter(|result| !result.range.is_empty())
+                        .map(|result| Chunk {
+                            content: content[result.range.clone()]
+                                .to_string(),
+                            range: result.range,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if symbol_chunks.is_empty() {
+                vec![whole_file_chunk(content)]
+            } else {
+                symbol_chunks
+            }
+        }
+    }
+}
+
+/// A single chunk covering the entire file.
+fn whole_file_chunk(content: &str) -> Chunk {
+    Chunk { range: 0..content.len(), content: content.to_string() }
+}
+
+/// Split `content` into fixed-size, overlapping windows.
+///
+/// Window boundaries are adjusted to land on UTF-8 character boundaries so
+/// chunks never split a multi-byte character.
+fn chunk_sliding_window(
+    content: &str,
+    window_size: usize,
+    overlap: usize,
+) -> Vec<Chunk> {
+    if content.is_empty() {
+        return Vec::new();
+    }
+
+    let window_size = window_size.max(1);
+    let step = window_size.saturating_sub(overlap).max(1);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < content.len() {
+        let end = floor_char_boundary(content, (start + window_size).min(content.len()));
+        if end <= start {
+            break;
+        }
+        chunks.push(Chunk {
+            content: content[start..end].to_string(),
+            range: start..end,
+        });
+        if end == content.len() {
+            break;
+        }
+        start += step;
+    }
+    chunks
+}
+
+/// Walk backwards from `idx` until we land on a UTF-8 character boundary.
+fn floor_char_boundary(content: &str, mut idx: usize) -> usize {
+    while idx > 0 && !content.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_strategy_yields_one_chunk() {
+        let chunks =
+            chunk_content("fn a() {}\nfn b() {}", None, ChunkingStrategy::File, 100, 0);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].range, 0..20);
+    }
+
+    #[test]
+    fn test_symbol_strategy_falls_back_without_calculator() {
+        let chunks =
+            chunk_content("fn a() {}", None, ChunkingStrategy::Symbol, 100, 0);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "fn a() {}");
+    }
+
+    #[test]
+    fn test_sliding_window_overlap() {
+        let content = "0123456789";
+        let chunks = chunk_sliding_window(content, 4, 2);
+        assert_eq!(chunks[0].range, 0..4);
+        assert_eq!(chunks[1].range, 2..6);
+        assert!(chunks.last().unwrap().range.end == content.len());
+    }
+
+    #[test]
+    fn test_sliding_window_empty_content() {
+        assert!(chunk_sliding_window("", 10, 2).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use super::embedding::generate_embeddings_parallel;
+use super::search::cosine_similarity;
+use super::types::{SemanticConfig, SemanticMatch};
+
+/// A group of semantically similar matches, collapsed down to one
+/// representative so `--semantic-cluster` can summarize a large result set
+/// instead of listing every near-duplicate hit individually.
+#[derive(Debug, Clone)]
+pub struct SemanticCluster {
+    /// The most similar-to-query match in this cluster, shown in place of
+    /// the whole group.
+    pub representative: SemanticMatch,
+    /// How many matches (including the representative) fell into this
+    /// cluster.
+    pub size: usize,
+}
+
+/// Cosine similarity above which a match joins an existing cluster rather
+/// than starting a new one, while there's still room under `k`.
+const SIMILARITY_JOIN_THRESHOLD: f32 = 0.85;
+
+/// Group `matches` into at most `k` clusters by embedding similarity.
+///
+/// This uses a single-pass leader/canopy algorithm: `matches` are visited in
+/// the order given (already sorted by similarity to the query by
+/// `search_semantic`), and each one either joins the nearest existing
+/// cluster's leader, if their cosine similarity exceeds
+/// `SIMILARITY_JOIN_THRESHOLD`, or becomes the leader of a new cluster, up to
+/// `k` clusters. Once `k` clusters exist, every further match joins whichever
+/// leader is closest even if that's below the threshold, since
+/// `--semantic-cluster`'s job is to put a hard cap on how many groups are
+/// shown rather than to produce an unbounded number of singleton clusters.
+///
+/// `SemanticMatch` doesn't retain the embedding vector it was scored with
+/// (only the resulting similarity), so this re-embeds each match's `content`
+/// with `config` to get vectors to compare. For a result set of a few
+/// hundred matches that's cheap relative to the original indexing pass, but
+/// it is an additional `matches.len()` embedding calls.
+///
+/// TODO: clustering only ever sees the matches from a single file search;
+/// there's no cross-file accumulator the way `--semantic-top-k` has with
+/// `GlobalSemanticMatch`, so `--semantic-cluster` doesn't yet summarize
+/// results gathered across a whole run.
+pub fn cluster_matches(
+    matches: Vec<SemanticMatch>,
+    k: usize,
+    config: &SemanticConfig,
+) -> Vec<SemanticCluster> {
+    if matches.is_empty() || k == 0 {
+        return Vec::new();
+    }
+    if matches.len() <= k {
+        return matches
+            .into_iter()
+            .map(|m| SemanticCluster { representative: m, size: 1 })
+            .collect();
+    }
+
+    let snippets: Vec<String> =
+        matches.iter().map(|m| m.content.clone()).collect();
+    let vectors: Vec<Vec<f32>> =
+        generate_embeddings_parallel(&snippets, config)
+            .into_iter()
+            .map(|e| e.vector)
+            .collect();
+
+    // Each leader is (vector, member indices into `matches`/`vectors`).
+    let mut leaders: Vec<(Vec<f32>, Vec<usize>)> = Vec::new();
+    for (idx, vector) in vectors.iter().enumerate() {
+        let nearest = leaders
+            .iter()
+            .enumerate()
+            .map(|(li, (leader, _))| (li, cosine_similarity(vector, leader)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        let join_leader = match nearest {
+            Some((li, sim))
+                if sim >= SIMILARITY_JOIN_THRESHOLD || leaders.len() >= k =>
+            {
+                Some(li)
+            }
+            _ => None,
+        };
+
+        match join_leader {
+            Some(li) => leaders[li].1.push(idx),
+            None => leaders.push((vector.clone(), vec![idx])),
+        }
+    }
+
+    let mut matches: Vec<Option<SemanticMatch>> =
+        matches.into_iter().map(Some).collect();
+    let mut clusters: Vec<SemanticCluster> = leaders
+        .into_iter()
+        .map(|(_, members)| {
+            // `matches` is sorted by similarity to the query (descending),
+            // so the lowest member index is the most similar one.
+            let representative_idx = *members.iter().min().unwrap();
+            let representative = matches[representative_idx]
+                .take()
+                .expect("each match index belongs to exactly one cluster");
+            SemanticCluster { representative, size: members.len() }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| {
+        b.representative
+            .similarity
+            .partial_cmp(&a.representative.similarity)
+            .unwrap()
+    });
+    clusters
+}
@@ -5,17 +5,44 @@ This module handles downloading, verifying, and caching semantic search models
 from the model registry.
 */
 
-use std::fs;
-use std::io::Write;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
-use super::registry::{ModelInfo, ModelRegistry};
+use super::registry::{ModelFile, ModelInfo, ModelRegistry};
 
-/// Progress callback for download operations
+/// Progress callback for download operations. Called with `(downloaded,
+/// total)` bytes after each chunk is written; `total` is 0 if the server
+/// didn't report a `Content-Length`.
 pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send + Sync>;
 
+/// Size of the buffer used to stream a download to disk and report progress.
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A single row of a semantic model registry listing, as produced by
+/// `ModelDownloader::model_report` for `--semantic-list-models`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelReport {
+    /// The model's name, as it appears in the registry.
+    pub name: String,
+    /// The model's embedding dimensions.
+    pub dimensions: usize,
+    /// The model's declared size, in megabytes, from the registry.
+    pub size_mb: u64,
+    /// The actual size, in bytes, of the model's files already present in
+    /// the local model cache. 0 if the model hasn't been downloaded.
+    pub size_on_disk_bytes: u64,
+    /// Whether the model's files are present locally and pass checksum
+    /// verification.
+    pub downloaded: bool,
+    /// Whether this is the registry's `default` recommendation.
+    pub is_default: bool,
+}
+
 /// Model downloader and manager
 pub struct ModelDownloader {
     /// Registry containing model definitions
@@ -37,18 +64,22 @@ impl ModelDownloader {
     pub fn is_model_available(&self, model_name: &str) -> Result<bool> {
         let model_info = self.registry.validate_model(model_name)?;
         let model_dir = self.storage_path.join(model_name);
-        
+
         // Check if both required files exist
         let model_file = model_dir.join(&model_info.files.model.filename);
         let tokenizer_file = model_dir.join(&model_info.files.tokenizer.filename);
-        
+
         if !model_file.exists() || !tokenizer_file.exists() {
             return Ok(false);
         }
 
-        // TODO: Add hash verification here
-        // For now, just check file existence
-        Ok(true)
+        Ok(verify_checksum(&model_file, &model_info.files.model.sha256)
+            .is_ok()
+            && verify_checksum(
+                &tokenizer_file,
+                &model_info.files.tokenizer.sha256,
+            )
+            .is_ok())
     }
 
     /// Download a model if not already available
@@ -60,8 +91,24 @@ impl ModelDownloader {
         self.download_model(model_name)
     }
 
-    /// Download a specific model
+    /// Download a specific model, reporting progress to stderr.
     pub fn download_model(&self, model_name: &str) -> Result<PathBuf> {
+        self.download_model_with_progress(model_name, None)
+    }
+
+    /// Download a specific model, invoking `progress` (in addition to the
+    /// stderr progress bar) after every chunk written to disk.
+    ///
+    /// Each of the model's files is downloaded with HTTP range resume: if a
+    /// previous attempt left a partial file on disk, the download picks up
+    /// where it left off instead of starting over. Once a file is fully
+    /// downloaded, its SHA256 checksum is verified against the registry
+    /// before the model is considered available.
+    pub fn download_model_with_progress(
+        &self,
+        model_name: &str,
+        progress: Option<&ProgressCallback>,
+    ) -> Result<PathBuf> {
         let model_info = self.registry.validate_model(model_name)?;
         let model_dir = self.storage_path.join(model_name);
 
@@ -73,65 +120,123 @@ impl ModelDownloader {
 
         // Download model file
         let model_path = model_dir.join(&model_info.files.model.filename);
-        self.download_file(&model_info.files.model.url, &model_path)
+        self.download_file(&model_info.files.model, &model_path, progress)
             .with_context(|| format!("Failed to download model file for {}", model_name))?;
 
         // Download tokenizer file
         let tokenizer_path = model_dir.join(&model_info.files.tokenizer.filename);
-        self.download_file(&model_info.files.tokenizer.url, &tokenizer_path)
+        self.download_file(&model_info.files.tokenizer, &tokenizer_path, progress)
             .with_context(|| format!("Failed to download tokenizer file for {}", model_name))?;
 
         println!("Successfully downloaded model: {}", model_name);
         Ok(model_dir)
     }
 
-    /// Download a file from URL to local path
-    fn download_file(&self, url: &str, local_path: &Path) -> Result<()> {
-        println!("  Downloading: {} -> {}", url, local_path.display());
-        
-        // Make HTTP request
-        let response = reqwest::blocking::get(url)
-            .with_context(|| format!("Failed to make HTTP request to {}", url))?;
-        
-        // Check if request was successful
+    /// Download `file` to `local_path`, resuming a previous partial download
+    /// if one is present, reporting progress on stderr (and to `progress`,
+    /// if given), and verifying the result against `file.sha256`.
+    fn download_file(
+        &self,
+        file: &ModelFile,
+        local_path: &Path,
+        progress: Option<&ProgressCallback>,
+    ) -> Result<()> {
+        if let Some(parent) = local_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create parent directory: {}", parent.display())
+            })?;
+        }
+
+        let mut existing_len =
+            local_path.metadata().map(|m| m.len()).unwrap_or(0);
+
+        let client = reqwest::blocking::Client::new();
+        let mut request = client.get(&file.url);
+        if existing_len > 0 {
+            request = request.header("Range", format!("bytes={}-", existing_len));
+        }
+        let mut response = request
+            .send()
+            .with_context(|| format!("Failed to make HTTP request to {}", file.url))?;
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            // The on-disk bytes we tried to resume from don't correspond to
+            // a valid range for this file anymore -- most likely it's a
+            // fully-downloaded but corrupt file that already failed
+            // checksum verification once. Drop it and fetch fresh instead
+            // of bailing out permanently.
+            fs::remove_file(local_path).ok();
+            existing_len = 0;
+            response = client.get(&file.url).send().with_context(|| {
+                format!("Failed to make HTTP request to {}", file.url)
+            })?;
+        }
         if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "HTTP request failed with status: {} for URL: {}", 
-                response.status(), 
-                url
-            ));
+            bail!(
+                "HTTP request failed with status: {} for URL: {}",
+                response.status(),
+                file.url
+            );
         }
-        
-        let total_size = response.content_length().unwrap_or(0);
-        println!("  File size: {} bytes", total_size);
-        
-        // Create the parent directory if it doesn't exist
-        if let Some(parent) = local_path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create parent directory: {}", parent.display()))?;
+
+        let resuming = existing_len > 0
+            && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        let mut handle = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(!resuming)
+            .open(local_path)
+            .with_context(|| format!("Failed to open file: {}", local_path.display()))?;
+        let mut downloaded = if resuming {
+            handle.seek(SeekFrom::End(0))?
+        } else {
+            0
+        };
+        let base = if resuming { existing_len } else { 0 };
+        let total = base + response.content_length().unwrap_or(0);
+
+        eprintln!("  Downloading: {} -> {}", file.url, local_path.display());
+        let mut buf = [0u8; DOWNLOAD_CHUNK_SIZE];
+        loop {
+            let n = response
+                .read(&mut buf)
+                .with_context(|| format!("Failed to read response body from {}", file.url))?;
+            if n == 0 {
+                break;
+            }
+            handle.write_all(&buf[..n]).with_context(|| {
+                format!("Failed to write to file: {}", local_path.display())
+            })?;
+            downloaded += n as u64;
+            print_progress_bar(downloaded, total);
+            if let Some(progress) = progress {
+                progress(downloaded, total);
+            }
         }
-        
-        // Create the file and download content
-        let mut file = fs::File::create(local_path)
-            .with_context(|| format!("Failed to create file: {}", local_path.display()))?;
-        
-        let content = response.bytes()
-            .with_context(|| format!("Failed to read response body from {}", url))?;
-            
-        file.write_all(&content)
-            .with_context(|| format!("Failed to write to file: {}", local_path.display()))?;
-        
-        let downloaded = content.len() as u64;
-        println!("  Downloaded: {} bytes", downloaded);
-        
-        if total_size > 0 && downloaded != total_size {
-            return Err(anyhow::anyhow!(
-                "Download incomplete: expected {} bytes, got {} bytes", 
-                total_size, 
+        eprintln!();
+        drop(handle);
+
+        if total > 0 && downloaded != total {
+            bail!(
+                "Download incomplete: expected {} bytes, got {} bytes",
+                total,
                 downloaded
-            ));
+            );
         }
-        
+
+        if let Err(err) = verify_checksum(local_path, &file.sha256) {
+            // Leaving a corrupt file in place would make every future
+            // attempt "resume" from the same bad bytes and hit the same
+            // checksum failure forever; remove it so the next attempt
+            // starts a clean download.
+            fs::remove_file(local_path).ok();
+            return Err(err).with_context(|| {
+                format!(
+                    "Checksum verification failed for {}",
+                    local_path.display()
+                )
+            });
+        }
+
         println!("  Successfully downloaded: {}", local_path.display());
         Ok(())
     }
@@ -204,6 +309,43 @@ impl ModelDownloader {
     pub fn storage_path(&self) -> &Path {
         &self.storage_path
     }
+
+    /// Build a report row for every model in the registry, sorted by name,
+    /// for `--semantic-list-models`.
+    pub fn model_report(&self) -> Vec<ModelReport> {
+        let default_model =
+            self.registry.recommendations.get("default").map(String::as_str);
+        let mut report: Vec<ModelReport> = self
+            .registry
+            .models
+            .iter()
+            .map(|(name, info)| ModelReport {
+                name: name.clone(),
+                dimensions: info.dimensions,
+                size_mb: info.size_mb,
+                size_on_disk_bytes: self.disk_size_bytes(name),
+                downloaded: self.is_model_available(name).unwrap_or(false),
+                is_default: default_model == Some(name.as_str()),
+            })
+            .collect();
+        report.sort_by(|a, b| a.name.cmp(&b.name));
+        report
+    }
+
+    /// Sum the sizes of `model_name`'s files already present in the storage
+    /// directory. Returns 0 if the model hasn't been downloaded, or isn't
+    /// in the registry at all.
+    fn disk_size_bytes(&self, model_name: &str) -> u64 {
+        let Ok(model_info) = self.registry.validate_model(model_name) else {
+            return 0;
+        };
+        let model_dir = self.storage_path.join(model_name);
+        [&model_info.files.model.filename, &model_info.files.tokenizer.filename]
+            .iter()
+            .filter_map(|filename| model_dir.join(filename).metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
 }
 
 /// Model management utilities
@@ -279,6 +421,70 @@ impl ModelManager {
             );
         }
     }
+
+    /// Print a semantic model registry listing in a table format, for
+    /// `--semantic-list-models`.
+    pub fn print_model_report(report: &[ModelReport]) {
+        println!(
+            "{:<25} {:<11} {:<10} {:<14} {:<11} {:<8}",
+            "Model",
+            "Dimensions",
+            "Size MB",
+            "Bytes on disk",
+            "Downloaded",
+            "Default"
+        );
+        println!("{}", "-".repeat(90));
+
+        for row in report {
+            println!(
+                "{:<25} {:<11} {:<10} {:<14} {:<11} {:<8}",
+                row.name,
+                row.dimensions,
+                row.size_mb,
+                row.size_on_disk_bytes,
+                if row.downloaded { "yes" } else { "no" },
+                if row.is_default { "yes" } else { "no" },
+            );
+        }
+    }
+}
+
+/// Print a one-line progress indicator for a download to stderr, overwriting
+/// the previous line.
+fn print_progress_bar(downloaded: u64, total: u64) {
+    if total > 0 {
+        let pct = (downloaded as f64 / total as f64 * 100.0).min(100.0);
+        eprint!("\r  {:>3.0}% ({} / {} bytes)", pct, downloaded, total);
+    } else {
+        eprint!("\r  {} bytes downloaded", downloaded);
+    }
+    let _ = std::io::stderr().flush();
+}
+
+/// Verify that the file at `path` hashes to `expected_sha256`.
+fn verify_checksum(path: &Path, expected_sha256: &str) -> Result<()> {
+    let mut file = fs::File::open(path)
+        .with_context(|| format!("Failed to open file for checksum verification: {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; DOWNLOAD_CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let actual = format!("{:x}", hasher.finalize());
+    if actual != expected_sha256 {
+        bail!(
+            "checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected_sha256,
+            actual
+        );
+    }
+    Ok(())
 }
 
 /// Truncate a string to a maximum length with ellipsis
@@ -333,4 +539,24 @@ mod tests {
         let path = ModelManager::default_storage_path().unwrap();
         assert!(path.to_string_lossy().contains(".cache/outgrep/models"));
     }
+
+    #[test]
+    fn test_verify_checksum_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.bin");
+        fs::write(&path, b"hello world").unwrap();
+
+        let expected =
+            "b94d27b9934d3e08a52e52d7da7dacefbd9f3cf674c5d80d87b2913c0c2c0f9";
+        assert!(verify_checksum(&path, expected).is_ok());
+    }
+
+    #[test]
+    fn test_verify_checksum_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("data.bin");
+        fs::write(&path, b"hello world").unwrap();
+
+        assert!(verify_checksum(&path, "not-the-right-hash").is_err());
+    }
 }
\ No newline at end of file
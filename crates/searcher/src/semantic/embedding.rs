@@ -4,8 +4,76 @@ use ndarray::{Array2, ArrayD, CowArray};
 use ort::{
     Environment, GraphOptimizationLevel, Session, SessionBuilder, Value,
 };
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Which backend should run embedding inference.
+///
+/// `Cpu`, `Cuda`, and `Metal` all run inference locally through
+/// `OnnxEmbedder`; they only differ in which `ort` execution provider is
+/// selected. `Cuda` and `Metal` require both the corresponding provider to
+/// have been compiled in (the `cuda`/`coreml` Cargo features, off by
+/// default) and the underlying hardware/drivers to actually be usable on
+/// the host; when either isn't true, [`execution_providers_for`] still
+/// appends `Cpu` last so `ort` falls back to it automatically instead of
+/// failing the whole session.
+///
+/// `Remote` instead calls an OpenAI-compatible `/embeddings` HTTP endpoint
+/// via `RemoteEmbedder`, for users without local model support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SemanticBackend {
+    /// Run on CPU. Always available.
+    #[default]
+    Cpu,
+    /// Run on an NVIDIA GPU via CUDA.
+    Cuda,
+    /// Run on Apple GPUs via Metal (through ONNX Runtime's CoreML provider).
+    Metal,
+    /// Call a remote OpenAI-compatible `/embeddings` endpoint instead of
+    /// running inference locally.
+    Remote,
+}
+
+/// Build the `ort` execution provider list for `backend`, in priority order.
+///
+/// `ort` tries each provider in turn and falls through to the next one if a
+/// provider can't be initialized (missing drivers, no compatible device,
+/// etc.), so CPU is always appended last as a guaranteed-available fallback.
+fn execution_providers_for(
+    backend: SemanticBackend,
+) -> Vec<ort::ExecutionProviderDispatch> {
+    let mut providers = Vec::new();
+    match backend {
+        #[cfg(feature = "cuda")]
+        SemanticBackend::Cuda => {
+            providers.push(ort::CUDAExecutionProvider::default().build());
+        }
+        #[cfg(not(feature = "cuda"))]
+        SemanticBackend::Cuda => {
+            log::warn!(
+                "--semantic-backend cuda requested, but this build of outgrep \
+                 was compiled without the `cuda` feature; falling back to CPU"
+            );
+        }
+        #[cfg(feature = "coreml")]
+        SemanticBackend::Metal => {
+            providers.push(ort::CoreMLExecutionProvider::default().build());
+        }
+        #[cfg(not(feature = "coreml"))]
+        SemanticBackend::Metal => {
+            log::warn!(
+                "--semantic-backend metal requested, but this build of outgrep \
+                 was compiled without the `coreml` feature; falling back to CPU"
+            );
+        }
+        // `Remote` never constructs an `OnnxEmbedder` (see `generate_embedding`),
+        // but is included here so this match stays exhaustive.
+        SemanticBackend::Cpu | SemanticBackend::Remote => {}
+    }
+    providers.push(ort::CPUExecutionProvider::default().build());
+    providers
+}
 
 /// ONNX-based embedder supporting configurable models
 pub struct OnnxEmbedder {
@@ -16,8 +84,14 @@ pub struct OnnxEmbedder {
 }
 
 impl OnnxEmbedder {
-    /// Create new embedder with specified model files and dimensions
-    pub fn new(model_path: &Path, tokenizer_path: &Path, dimensions: usize) -> Result<Self, Box<dyn std::error::Error>> {
+    /// Create new embedder with specified model files, dimensions, and
+    /// execution backend
+    pub fn new(
+        model_path: &Path,
+        tokenizer_path: &Path,
+        dimensions: usize,
+        backend: SemanticBackend,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         if !model_path.exists() || !tokenizer_path.exists() {
             return Err(
                 format!(
@@ -35,6 +109,7 @@ impl OnnxEmbedder {
 
         // Create session
         let session = SessionBuilder::new(&environment)?
+            .with_execution_providers(execution_providers_for(backend))?
             .with_optimization_level(GraphOptimizationLevel::Level1)?
             .with_model_from_file(model_path)?;
 
@@ -52,15 +127,15 @@ impl OnnxEmbedder {
         )?;
 
         let model_name = config.model_name.as_deref().unwrap_or("all-MiniLM-L6-v2");
-        
+
         // Ensure model is available (download if needed)
         downloader.ensure_model_available(model_name)?;
-        
+
         // Get model paths and info
         let (model_path, tokenizer_path) = downloader.get_model_paths(model_name)?;
         let model_info = downloader.get_model_info(model_name)?;
-        
-        Self::new(&model_path, &tokenizer_path, model_info.dimensions)
+
+        Self::new(&model_path, &tokenizer_path, model_info.dimensions, config.backend)
     }
 
     /// Generate embedding using ONNX model
@@ -172,10 +247,250 @@ impl OnnxEmbedder {
     }
 }
 
+/// Embedder that calls a remote OpenAI-compatible `/embeddings` HTTP
+/// endpoint, for [`SemanticBackend::Remote`].
+///
+/// Requests are batched (see [`REMOTE_EMBEDDING_BATCH_SIZE`]) and retried
+/// with a fixed delay on transient failures (see
+/// [`REMOTE_EMBEDDING_MAX_ATTEMPTS`]).
+pub struct RemoteEmbedder {
+    client: reqwest::blocking::Client,
+    url: String,
+    api_key: Option<String>,
+    model_name: String,
+}
+
+/// Maximum number of texts sent in a single `/embeddings` request.
+///
+/// OpenAI-compatible endpoints generally accept large batches, but an
+/// unbounded batch means one pathological file (thousands of symbols) turns
+/// into a single enormous request body. Mirrors [`EMBEDDING_BATCH_SIZE`]'s
+/// role for local inference.
+const REMOTE_EMBEDDING_BATCH_SIZE: usize = 96;
+
+/// Number of attempts `RemoteEmbedder::embed_batch` makes against the
+/// endpoint, with [`REMOTE_EMBEDDING_RETRY_DELAY`] between attempts, before
+/// giving up on a batch.
+const REMOTE_EMBEDDING_MAX_ATTEMPTS: u32 = 3;
+
+/// Delay between retry attempts in `RemoteEmbedder::embed_batch`.
+///
+/// TODO: back this off exponentially (and honor a `Retry-After` header on
+/// 429s) once we have a real deployment to tune it against; a fixed delay is
+/// a reasonable starting point for the common case of a brief network blip.
+const REMOTE_EMBEDDING_RETRY_DELAY: std::time::Duration =
+    std::time::Duration::from_millis(500);
+
+#[derive(serde::Deserialize)]
+struct RemoteEmbeddingResponse {
+    data: Vec<RemoteEmbeddingDatum>,
+}
+
+#[derive(serde::Deserialize)]
+struct RemoteEmbeddingDatum {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+impl RemoteEmbedder {
+    /// Resolve the endpoint URL, API key, and model name for `config`.
+    ///
+    /// `config.remote_embedding_url`/`remote_embedding_api_key` take
+    /// precedence; otherwise the `OUTGREP_EMBEDDING_API_URL` and
+    /// `OUTGREP_EMBEDDING_API_KEY` environment variables are used. The URL
+    /// falls back to the public OpenAI API if neither is set.
+    pub fn from_config(
+        config: &SemanticConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let url = config
+            .remote_embedding_url
+            .clone()
+            .or_else(|| std::env::var("OUTGREP_EMBEDDING_API_URL").ok())
+            .unwrap_or_else(|| "https://api.openai.com/v1".to_string());
+        let api_key = config
+            .remote_embedding_api_key
+            .clone()
+            .or_else(|| std::env::var("OUTGREP_EMBEDDING_API_KEY").ok());
+        let model_name = config
+            .model_name
+            .clone()
+            .unwrap_or_else(|| "text-embedding-3-small".to_string());
+        Ok(Self {
+            client: reqwest::blocking::Client::new(),
+            url,
+            api_key,
+            model_name,
+        })
+    }
+
+    /// Embed `texts`, splitting into batches of `REMOTE_EMBEDDING_BATCH_SIZE`
+    /// and retrying each batch on failure. Results preserve the order of
+    /// `texts`.
+    pub fn embed_batch(
+        &self,
+        texts: &[String],
+    ) -> Result<Vec<Embedding>, Box<dyn std::error::Error>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(REMOTE_EMBEDDING_BATCH_SIZE) {
+            embeddings.extend(self.embed_batch_with_retry(batch)?);
+        }
+        Ok(embeddings)
+    }
+
+    fn embed_batch_with_retry(
+        &self,
+        batch: &[String],
+    ) -> Result<Vec<Embedding>, Box<dyn std::error::Error>> {
+        let mut last_err = None;
+        for attempt in 1..=REMOTE_EMBEDDING_MAX_ATTEMPTS {
+            match self.embed_batch_once(batch) {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(e) => {
+                    log::warn!(
+                        "remote embedding request failed (attempt {}/{}): {}",
+                        attempt,
+                        REMOTE_EMBEDDING_MAX_ATTEMPTS,
+                        e,
+                    );
+                    last_err = Some(e);
+                    if attempt < REMOTE_EMBEDDING_MAX_ATTEMPTS {
+                        std::thread::sleep(REMOTE_EMBEDDING_RETRY_DELAY);
+                    }
+                }
+            }
+        }
+        Err(last_err.expect("loop runs at least once"))
+    }
+
+    fn embed_batch_once(
+        &self,
+        batch: &[String],
+    ) -> Result<Vec<Embedding>, Box<dyn std::error::Error>> {
+        let endpoint =
+            format!("{}/embeddings", self.url.trim_end_matches('/'));
+        let mut request =
+            self.client.post(&endpoint).json(&serde_json::json!({
+                "model": self.model_name,
+                "input": batch,
+            }));
+        if let Some(ref api_key) = self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send()?.error_for_status()?;
+        let parsed: RemoteEmbeddingResponse = response.json()?;
+
+        let mut ordered: Vec<Option<Vec<f32>>> = vec![None; batch.len()];
+        for datum in parsed.data {
+            if let Some(slot) = ordered.get_mut(datum.index) {
+                *slot = Some(datum.embedding);
+            }
+        }
+        ordered
+            .into_iter()
+            .enumerate()
+            .map(|(i, vector)| {
+                let vector = vector.ok_or_else(|| {
+                    format!("remote embedding response missing index {}", i)
+                })?;
+                let dimensions = vector.len();
+                Ok(Embedding { vector, dimensions })
+            })
+            .collect()
+    }
+}
+
+/// Process-wide cache of `RemoteEmbedder`s, keyed by endpoint URL and model
+/// name, for the same reason as [`embedder_cache`]: building a new HTTP
+/// client per symbol would be wasteful, and this keeps `generate_embedding`
+/// cheap to call in a loop.
+fn remote_embedder_cache(
+) -> &'static Mutex<HashMap<String, Arc<RemoteEmbedder>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Arc<RemoteEmbedder>>>> =
+        OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Get (loading and caching if necessary) the `RemoteEmbedder` for `config`.
+fn cached_remote_embedder(
+    config: &SemanticConfig,
+) -> Result<Arc<RemoteEmbedder>, Box<dyn std::error::Error>> {
+    let key = format!(
+        "{}::{}",
+        config.remote_embedding_url.as_deref().unwrap_or(""),
+        config.model_name.as_deref().unwrap_or("text-embedding-3-small"),
+    );
+
+    if let Some(embedder) = remote_embedder_cache().lock().unwrap().get(&key) {
+        return Ok(Arc::clone(embedder));
+    }
+
+    let embedder = Arc::new(RemoteEmbedder::from_config(config)?);
+    remote_embedder_cache().lock().unwrap().insert(key, Arc::clone(&embedder));
+    Ok(embedder)
+}
+
+/// Process-wide cache of loaded `OnnxEmbedder`s, keyed by model name and
+/// storage path.
+///
+/// Constructing an `OnnxEmbedder` loads a tokenizer and builds an ONNX
+/// Runtime session, which is far too expensive to redo for every symbol
+/// embedded during a single search. `generate_embedding` is called once per
+/// symbol, so without this cache a file with a hundred functions would
+/// reload the model a hundred times.
+fn embedder_cache() -> &'static Mutex<HashMap<String, Arc<OnnxEmbedder>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Arc<OnnxEmbedder>>>> =
+        OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Get (loading and caching if necessary) the `OnnxEmbedder` for `config`.
+fn cached_embedder(
+    config: &SemanticConfig,
+) -> Result<Arc<OnnxEmbedder>, Box<dyn std::error::Error>> {
+    let key = format!(
+        "{}::{}::{:?}",
+        config.model_name.as_deref().unwrap_or("all-MiniLM-L6-v2"),
+        config.model_path.as_deref().unwrap_or(""),
+        config.backend,
+    );
+
+    if let Some(embedder) = embedder_cache().lock().unwrap().get(&key) {
+        return Ok(Arc::clone(embedder));
+    }
+
+    let embedder = Arc::new(OnnxEmbedder::from_config(config)?);
+    embedder_cache().lock().unwrap().insert(key, Arc::clone(&embedder));
+    Ok(embedder)
+}
+
 /// Generate embedding for a code snippet
 pub fn generate_embedding(code: &str, config: &SemanticConfig) -> Embedding {
+    if config.backend == SemanticBackend::Remote {
+        match cached_remote_embedder(config)
+            .and_then(|embedder| embedder.embed_batch(&[code.to_string()]))
+        {
+            Ok(mut embeddings) if embeddings.len() == 1 => {
+                let mut vector = embeddings.remove(0).vector;
+                if vector.len() != config.embedding_dimensions {
+                    vector.resize(config.embedding_dimensions, 0.0);
+                }
+                return Embedding {
+                    vector,
+                    dimensions: config.embedding_dimensions,
+                };
+            }
+            Ok(_) => {
+                eprintln!("remote embedding response had an unexpected shape");
+            }
+            Err(e) => {
+                eprintln!("remote embedding generation failed: {}", e);
+            }
+        }
+        return fallback_embedding(code, config.embedding_dimensions);
+    }
+
     // Try ONNX model first, fall back to hash-based
-    match OnnxEmbedder::from_config(config) {
+    match cached_embedder(config) {
         Ok(embedder) => {
             match embedder.embed(code) {
                 Ok(embedding) => {
@@ -198,11 +513,75 @@ pub fn generate_embedding(code: &str, config: &SemanticConfig) -> Embedding {
         }
     }
 
-    // Fallback to hash-based embedding with configured dimensions
+    fallback_embedding(code, config.embedding_dimensions)
+}
+
+/// Hash-based embedding used when neither the local ONNX model nor the
+/// remote embedding backend is available. Not semantically meaningful, but
+/// keeps indexing and search functional (falling back to effectively random
+/// but stable vectors) instead of failing outright.
+fn fallback_embedding(code: &str, dimensions: usize) -> Embedding {
     let hash = simple_hash(code);
-    let vector = hash_to_vector(hash, config.embedding_dimensions);
+    let vector = hash_to_vector(hash, dimensions);
+    Embedding { vector, dimensions }
+}
 
-    Embedding { vector, dimensions: config.embedding_dimensions }
+/// The number of snippets embedded per rayon batch in
+/// `generate_embeddings_parallel`.
+///
+/// Embedding a whole batch at once (rather than one giant `par_iter` over
+/// every snippet in the file) bounds how many snippets have their tokenized
+/// input tensors resident in memory at the same time, so indexing a very
+/// large file doesn't spike memory proportionally to its size.
+const EMBEDDING_BATCH_SIZE: usize = 64;
+
+/// Generate embeddings for many code snippets in parallel, saturating all
+/// available CPU cores via rayon's global thread pool.
+///
+/// The cached `OnnxEmbedder` (see [`cached_embedder`]) is loaded once and
+/// shared (via `Arc`) across every worker thread; ONNX Runtime sessions
+/// support concurrent `run` calls, so this scales with core count instead of
+/// serializing on the model. Results are returned in the same order as
+/// `snippets`.
+pub fn generate_embeddings_parallel(
+    snippets: &[String],
+    config: &SemanticConfig,
+) -> Vec<Embedding> {
+    use rayon::prelude::*;
+
+    if config.backend == SemanticBackend::Remote {
+        // Route the whole batch through `RemoteEmbedder::embed_batch` so it
+        // can chunk snippets into a handful of HTTP requests, rather than
+        // making one request per snippet via `generate_embedding` below.
+        match cached_remote_embedder(config)
+            .and_then(|embedder| embedder.embed_batch(snippets))
+        {
+            Ok(embeddings) if embeddings.len() == snippets.len() => {
+                return embeddings;
+            }
+            Ok(_) => {
+                eprintln!("remote embedding response had an unexpected shape");
+            }
+            Err(e) => {
+                eprintln!("remote embedding generation failed: {}", e);
+            }
+        }
+        return snippets
+            .iter()
+            .map(|text| fallback_embedding(text, config.embedding_dimensions))
+            .collect();
+    }
+
+    let mut embeddings = Vec::with_capacity(snippets.len());
+    for batch in snippets.chunks(EMBEDDING_BATCH_SIZE) {
+        embeddings.extend(
+            batch
+                .par_iter()
+                .map(|text| generate_embedding(text, config))
+                .collect::<Vec<_>>(),
+        );
+    }
+    embeddings
 }
 
 /// Calculate cosine similarity between two embeddings
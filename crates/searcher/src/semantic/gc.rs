@@ -0,0 +1,192 @@
+/*!
+`--semantic-gc`: tombstone and compact a semantic index file.
+
+A semantic index file built by `export_index` has no way to notice that one
+of its source files was later deleted or renamed -- the stale chunks just sit
+there forever, wasting space and occasionally surfacing matches against
+content that no longer exists anywhere. This module provides the other half:
+a one-shot compaction pass that drops entries whose recorded source path is
+gone and rewrites the file.
+*/
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::indexing::build_index;
+use super::serialize::{export_index, import_index, index_stats};
+use super::types::{DimensionMismatchPolicy, Embedding, SemanticConfig};
+
+/// Outcome of a `--semantic-gc` run, for reporting reclaimed space.
+#[derive(Debug, Clone, Serialize)]
+pub struct GcReport {
+    /// Number of chunks in the index before garbage collection.
+    pub chunks_before: u64,
+    /// Number of chunks remaining after garbage collection.
+    pub chunks_after: u64,
+    /// Number of tombstoned chunks removed.
+    pub chunks_removed: u64,
+    /// Size of the index file on disk before garbage collection, in bytes.
+    pub bytes_before: u64,
+    /// Size of the index file on disk after garbage collection, in bytes.
+    pub bytes_after: u64,
+}
+
+/// Compact `path`, a semantic index file previously written by
+/// `export_index`, by dropping every chunk whose recorded source file no
+/// longer exists on disk, then rewriting the file in place with the
+/// survivors.
+///
+/// A chunk with no recorded source path (an entry from a format version 1
+/// index, or one indexed from content that was never associated with a real
+/// file, e.g. `--semantic-history`) is conservatively kept: there is no way
+/// to tell whether it is stale, and GC should never silently drop data it
+/// cannot justify dropping.
+///
+/// This cannot distinguish a deleted file from a renamed one -- both look
+/// identical from here, a path that no longer resolves -- so a rename is
+/// tombstoned exactly like a deletion. Re-indexing the file under its new
+/// name is the only way to recover those chunks.
+///
+/// GC reads and rewrites the file's own embeddings unchanged, so it always
+/// imports using the index's own recorded dimensionality rather than
+/// `config.embedding_dimensions` -- a GC run shouldn't fail just because
+/// the currently configured model differs from the one the index was built
+/// with (that mismatch only matters for querying, not compaction).
+pub fn gc_index(path: &Path, config: &SemanticConfig) -> Result<GcReport> {
+    let bytes_before = std::fs::metadata(path)
+        .with_context(|| format!("failed to stat {}", path.display()))?
+        .len();
+
+    let native_dimensions = index_stats(path)
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .embedding_dimensions;
+    let config = &SemanticConfig {
+        embedding_dimensions: native_dimensions,
+        dimension_mismatch: DimensionMismatchPolicy::Project,
+        ..config.clone()
+    };
+
+    let index = import_index(path, config)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let chunks_before = index.metadata.len() as u64;
+
+    let mut survivors = Vec::new();
+    for i in 0..index.metadata.len() {
+        let tombstoned = match &index.source_paths[i] {
+            Some(source_path) => !source_path.exists(),
+            None => false,
+        };
+        if tombstoned {
+            continue;
+        }
+        let vector = index.embeddings[i].to_f32();
+        let dimensions = vector.len();
+        survivors.push((
+            Embedding { vector, dimensions },
+            index.metadata[i].byte_range.clone(),
+            index.metadata[i].content.clone(),
+            index.source_paths[i].clone(),
+        ));
+    }
+    let chunks_after = survivors.len() as u64;
+
+    let compacted = build_index(survivors, config);
+    export_index(&compacted, config, path)
+        .with_context(|| format!("failed to rewrite {}", path.display()))?;
+
+    let bytes_after = std::fs::metadata(path)
+        .with_context(|| format!("failed to stat {}", path.display()))?
+        .len();
+
+    Ok(GcReport {
+        chunks_before,
+        chunks_after,
+        chunks_removed: chunks_before - chunks_after,
+        bytes_before,
+        bytes_after,
+    })
+}
+
+/// Print `report` as a plain-text summary, for `--semantic-gc`.
+pub fn print_gc_report(path: &Path, report: &GcReport) {
+    println!("Index: {}", path.display());
+    println!(
+        "  Chunks:      {} -> {} ({} removed)",
+        report.chunks_before, report.chunks_after, report.chunks_removed
+    );
+    println!(
+        "  File size:   {} -> {} bytes ({} bytes reclaimed)",
+        report.bytes_before,
+        report.bytes_after,
+        report.bytes_before.saturating_sub(report.bytes_after)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Range;
+
+    fn entry(
+        path: Option<&str>,
+        content: &str,
+        vector: Vec<f32>,
+    ) -> (Embedding, Range<usize>, String, Option<std::path::PathBuf>) {
+        let dimensions = vector.len();
+        (
+            Embedding { vector, dimensions },
+            Range { start: 0, end: content.len() },
+            content.to_string(),
+            path.map(std::path::PathBuf::from),
+        )
+    }
+
+    #[test]
+    fn gc_removes_chunks_for_deleted_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept_file = dir.path().join("kept.rs");
+        std::fs::write(&kept_file, "fn kept() {}").unwrap();
+        let deleted_file = dir.path().join("deleted.rs");
+        std::fs::write(&deleted_file, "fn deleted() {}").unwrap();
+
+        let config = SemanticConfig {
+            embedding_dimensions: 2,
+            ..SemanticConfig::default()
+        };
+        let embeddings = vec![
+            entry(
+                Some(kept_file.to_str().unwrap()),
+                "fn kept() {}",
+                vec![1.0, 0.0],
+            ),
+            entry(
+                Some(deleted_file.to_str().unwrap()),
+                "fn deleted() {}",
+                vec![0.0, 1.0],
+            ),
+            entry(None, "fn unknown() {}", vec![1.0, 1.0]),
+        ];
+        let index = build_index(embeddings, &config);
+
+        let index_path = dir.path().join("index.ogsx");
+        export_index(&index, &config, &index_path).unwrap();
+
+        // The file that went away between indexing and GC.
+        std::fs::remove_file(&deleted_file).unwrap();
+
+        let report = gc_index(&index_path, &config).unwrap();
+        assert_eq!(report.chunks_before, 3);
+        assert_eq!(report.chunks_after, 2);
+        assert_eq!(report.chunks_removed, 1);
+
+        let compacted = import_index(&index_path, &config).unwrap();
+        assert_eq!(compacted.metadata.len(), 2);
+        let contents: Vec<&str> =
+            compacted.metadata.iter().map(|m| m.content.as_str()).collect();
+        assert!(contents.contains(&"fn kept() {}"));
+        assert!(contents.contains(&"fn unknown() {}"));
+        assert!(!contents.contains(&"fn deleted() {}"));
+    }
+}
@@ -1,18 +1,25 @@
-use super::types::{Embedding, EmbeddingPoint, SemanticIndex, SemanticMatch, SemanticConfig};
+use super::types::{
+    Embedding, EmbeddingPoint, QuantizedVector, SemanticConfig, SemanticIndex,
+    SemanticMatch,
+};
 use instant_distance::{Builder, Search};
 use std::ops::Range;
+use std::path::PathBuf;
 
-/// Build index from embeddings and their associated data
+/// Build index from embeddings and their associated data. `source_path` (the
+/// fourth tuple element) records which file each chunk came from, if known;
+/// see `SemanticIndex::source_paths`.
 pub fn build_index(
-    embeddings: Vec<(Embedding, Range<usize>, String)>,
+    embeddings: Vec<(Embedding, Range<usize>, String, Option<PathBuf>)>,
     config: &SemanticConfig,
 ) -> SemanticIndex {
     let mut embedding_vectors = Vec::new();
     let mut metadata = Vec::new();
+    let mut source_paths = Vec::new();
     let mut points = Vec::new();
     let mut values = Vec::new();
 
-    for (idx, (embedding, range, content)) in
+    for (idx, (embedding, range, content, source_path)) in
         embeddings.into_iter().enumerate()
     {
         // Ensure embedding matches configured dimensions
@@ -30,19 +37,33 @@ pub fn build_index(
         points.push(EmbeddingPoint(vector));
         values.push(idx);
 
-        embedding_vectors.push(embedding);
+        embedding_vectors
+            .push(QuantizedVector::quantize(&embedding.vector, config.quantize));
         metadata.push(SemanticMatch {
             similarity: 1.0,
             byte_range: range,
             content,
         });
+        source_paths.push(source_path);
     }
 
-    // Build the HNSW map
-    let hnsw_map = Builder::default().build(points, values);
+    // Build the HNSW map. `ef_search` controls the recall/speed tradeoff:
+    // a higher value visits more candidates per query (better recall, slower
+    // search), a lower value visits fewer (faster, lower recall).
+    let mut builder = Builder::default();
+    if let Some(ef_search) = config.ef_search {
+        builder = builder.ef_search(ef_search);
+    }
+    let hnsw_map = builder.build(points, values);
     let search = Search::default();
 
-    SemanticIndex { hnsw_map, search, embeddings: embedding_vectors, metadata }
+    SemanticIndex {
+        hnsw_map,
+        search,
+        embeddings: embedding_vectors,
+        metadata,
+        source_paths,
+    }
 }
 
 /// Add new embedding to existing index
@@ -51,13 +72,18 @@ pub fn add_to_index(
     embedding: Embedding,
     range: Range<usize>,
     content: String,
+    source_path: Option<PathBuf>,
+    config: &SemanticConfig,
 ) {
     // Note: instant-distance doesn't support dynamic insertion easily
     // For now, just add to the data structures without rebuilding the index
-    index.embeddings.push(embedding);
+    index
+        .embeddings
+        .push(QuantizedVector::quantize(&embedding.vector, config.quantize));
     index.metadata.push(SemanticMatch {
         similarity: 1.0,
         byte_range: range,
         content,
     });
+    index.source_paths.push(source_path);
 }
@@ -1,13 +1,21 @@
+pub use chunking::*;
+pub use cluster::*;
 pub use downloader::*;
 pub use embedding::*;
+pub use gc::*;
 pub use indexing::*;
 pub use registry::*;
 pub use search::*;
+pub use serialize::*;
 pub use types::*;
 
+mod chunking;
+mod cluster;
 mod downloader;
 mod embedding;
+mod gc;
 mod indexing;
 mod registry;
 mod search;
+mod serialize;
 mod types;
@@ -3,6 +3,33 @@ use super::types::{
     EmbeddingPoint, SemanticConfig, SemanticIndex, SemanticMatch,
 };
 
+/// How scores from multiple `--semantic-query` flags are fused into one
+/// ranking by [`search_semantic_multi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryFusion {
+    /// Keep each chunk's highest score across all queries: "match whichever
+    /// of these queries looks closest" (logical OR).
+    #[default]
+    Or,
+    /// Keep each chunk's lowest score across all queries: "must look
+    /// relevant to every one of these queries" (logical AND).
+    And,
+}
+
+/// Cosine similarity between two vectors, clamped to `[-1, 1]`. Returns `0.0`
+/// if either vector is all zeros, since cosine similarity is undefined for a
+/// zero-magnitude vector.
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot_product: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let a_magnitude: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let b_magnitude: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if a_magnitude > 0.0 && b_magnitude > 0.0 {
+        (dot_product / (a_magnitude * b_magnitude)).max(-1.0).min(1.0)
+    } else {
+        0.0
+    }
+}
+
 /// Perform semantic search against an index
 pub fn search_semantic(
     query: &str,
@@ -24,43 +51,22 @@ pub fn search_semantic(
     for neighbor in nearest.take(config.max_results * 2) {
         let idx = *neighbor.value;
         if let Some(stored_embedding) = index.embeddings.get(idx) {
-            // Calculate proper cosine similarity
-            let dot_product: f32 = query_embedding
-                .vector
-                .iter()
-                .zip(stored_embedding.vector.iter())
-                .map(|(a, b)| a * b)
-                .sum();
-
-            let query_magnitude: f32 = query_embedding
-                .vector
-                .iter()
-                .map(|x| x * x)
-                .sum::<f32>()
-                .sqrt();
-
-            let stored_magnitude: f32 = stored_embedding
-                .vector
-                .iter()
-                .map(|x| x * x)
-                .sum::<f32>()
-                .sqrt();
-
-            let similarity = if query_magnitude > 0.0 && stored_magnitude > 0.0
-            {
-                dot_product / (query_magnitude * stored_magnitude)
-            } else {
-                0.0
-            };
-
-            // Clamp to [-1, 1] range for safety
-            let similarity = similarity.max(-1.0).min(1.0);
+            // Dequantize back to f32 before scoring, regardless of how the
+            // embedding is stored (see `SemanticQuantize`).
+            let stored_vector = stored_embedding.to_f32();
+            let similarity =
+                cosine_similarity(&query_embedding.vector, &stored_vector);
             similarities.push((idx, similarity));
         }
     }
 
     // Sort by similarity (descending) and take top results
     similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    if config.rerank {
+        similarities = rerank_candidates(query, similarities, index);
+    }
+
     similarities.truncate(config.max_results);
 
     similarities
@@ -79,6 +85,187 @@ pub fn search_semantic(
         .collect()
 }
 
+/// Perform semantic search with multiple queries (one index entry per
+/// `--semantic-query` flag), fusing each chunk's per-query similarity into a
+/// single score via `fusion` instead of ranking against just one query
+/// embedding.
+///
+/// Each query's own ANN search contributes its nearest neighbors to a shared
+/// candidate pool -- the union, not the intersection -- so a chunk that's
+/// only a strong match for one query is still considered once every query's
+/// score is computed for it. This matters most for [`QueryFusion::And`]: a
+/// chunk mediocre-but-present across every query should be able to outrank
+/// one that's excellent on a single query and irrelevant to the rest, and
+/// that can't happen if we only ever look at each query's own top
+/// candidates in isolation.
+///
+/// TODO: `--semantic-rerank` isn't applied here, since `rerank_candidates`
+/// expects a single query string to compute term overlap against. Fusing it
+/// across queries first needs a decision on how per-query overlap should
+/// combine (sum, same `fusion` rule, etc.) once there's real usage to tune
+/// against.
+pub fn search_semantic_multi(
+    queries: &[String],
+    index: &mut SemanticIndex,
+    config: &SemanticConfig,
+    fusion: QueryFusion,
+) -> Vec<SemanticMatch> {
+    if queries.is_empty() {
+        return Vec::new();
+    }
+    if queries.len() == 1 {
+        return search_semantic(&queries[0], index, config);
+    }
+
+    let query_embeddings: Vec<_> =
+        queries.iter().map(|q| generate_embedding(q, config)).collect();
+
+    let mut candidate_idxs = std::collections::BTreeSet::new();
+    for embedding in &query_embeddings {
+        let mut query_vector = embedding.vector.clone();
+        query_vector.resize(config.embedding_dimensions, 0.0);
+        let query_point = EmbeddingPoint(query_vector);
+        let nearest = index.hnsw_map.search(&query_point, &mut index.search);
+        for neighbor in nearest.take(config.max_results * 2) {
+            candidate_idxs.insert(*neighbor.value);
+        }
+    }
+
+    let mut fused: Vec<(usize, f32)> = candidate_idxs
+        .into_iter()
+        .filter_map(|idx| {
+            let stored_vector = index.embeddings.get(idx)?.to_f32();
+            let scores = query_embeddings
+                .iter()
+                .map(|q| cosine_similarity(&q.vector, &stored_vector));
+            let fused_score = match fusion {
+                QueryFusion::Or => scores.fold(f32::MIN, f32::max),
+                QueryFusion::And => scores.fold(f32::MAX, f32::min),
+            };
+            Some((idx, fused_score))
+        })
+        .collect();
+
+    fused.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    fused.truncate(config.max_results);
+
+    fused
+        .into_iter()
+        .filter_map(|(idx, similarity)| {
+            if similarity >= config.similarity_threshold {
+                index.metadata.get(idx).map(|match_data| SemanticMatch {
+                    similarity,
+                    byte_range: match_data.byte_range.clone(),
+                    content: match_data.content.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Search `index` for chunks matching `query`, invoking `on_match` as soon as
+/// each one clears `config.similarity_threshold`, instead of collecting,
+/// sorting and truncating the whole result set first the way
+/// [`search_semantic`] does.
+///
+/// This trades away `search_semantic`'s global top-`max_results` ranking (and
+/// its `--semantic-rerank` pass, which needs the same full candidate list)
+/// for the ability to act on a match the instant it's found: matches are
+/// reported in whatever order `index.embeddings` stores them in, not sorted
+/// by descending similarity.
+///
+/// It also does not consult `index.hnsw_map` at all. An approximate
+/// nearest-neighbor graph exists specifically to avoid scoring every chunk
+/// when ranking a huge index, which is the opposite of what streaming needs
+/// here; instead this does an exact linear scan over every stored embedding.
+///
+/// `on_match` returning `false` stops the scan early, e.g. for
+/// `--quit-after-match`.
+pub fn search_semantic_streaming(
+    query: &str,
+    index: &SemanticIndex,
+    config: &SemanticConfig,
+    mut on_match: impl FnMut(SemanticMatch) -> bool,
+) {
+    let query_embedding = generate_embedding(query, config);
+    for (idx, stored_embedding) in index.embeddings.iter().enumerate() {
+        let stored_vector = stored_embedding.to_f32();
+        let similarity =
+            cosine_similarity(&query_embedding.vector, &stored_vector);
+        if similarity < config.similarity_threshold {
+            continue;
+        }
+        let Some(match_data) = index.metadata.get(idx) else { continue };
+        let keep_going = on_match(SemanticMatch {
+            similarity,
+            byte_range: match_data.byte_range.clone(),
+            content: match_data.content.clone(),
+        });
+        if !keep_going {
+            break;
+        }
+    }
+}
+
+/// Rescore ANN candidates with a cheap joint query/document scorer and
+/// re-sort by the result.
+///
+/// A bi-encoder (what [`search_semantic`] uses to shortlist `candidates`)
+/// embeds the query and each document independently, so it can never see how
+/// specific query terms line up with a specific document. A cross-encoder
+/// closes that gap by scoring the query and document together, at the cost
+/// of being too slow to run over the whole index - so it only ever reranks
+/// the small shortlist a bi-encoder already narrowed down.
+///
+/// This reranks by blending each candidate's ANN cosine similarity with the
+/// fraction of query tokens it contains, which rewards candidates that
+/// actually mention the query's terms over ones that are merely nearby in
+/// embedding space. It does not run an ONNX cross-encoder model.
+///
+/// TODO: Once a cross-encoder model (e.g. `ms-marco-MiniLM-L-6-v2`) is added
+/// to the model registry, replace the token-overlap heuristic below with a
+/// real forward pass over `(query, candidate.content)` pairs, selected via
+/// `SemanticConfig::rerank_model`.
+pub fn rerank_candidates(
+    query: &str,
+    candidates: Vec<(usize, f32)>,
+    index: &SemanticIndex,
+) -> Vec<(usize, f32)> {
+    let query_tokens: Vec<String> =
+        query.split_whitespace().map(|t| t.to_lowercase()).collect();
+    if query_tokens.is_empty() {
+        return candidates;
+    }
+
+    let mut rescored: Vec<(usize, f32)> = candidates
+        .into_iter()
+        .map(|(idx, cosine_similarity)| {
+            let overlap = index
+                .metadata
+                .get(idx)
+                .map(|match_data| {
+                    term_overlap_score(&query_tokens, &match_data.content)
+                })
+                .unwrap_or(0.0);
+            let reranked = 0.7 * cosine_similarity + 0.3 * overlap;
+            (idx, reranked)
+        })
+        .collect();
+
+    rescored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    rescored
+}
+
+/// Fraction of `query_tokens` that appear (case-insensitively) in `content`.
+fn term_overlap_score(query_tokens: &[String], content: &str) -> f32 {
+    let content_lower = content.to_lowercase();
+    let matched =
+        query_tokens.iter().filter(|t| content_lower.contains(t.as_str())).count();
+    matched as f32 / query_tokens.len() as f32
+}
+
 /// Coordinator for semantic search operations
 pub struct SemanticSearcher {
     index: Option<SemanticIndex>,
@@ -103,4 +290,33 @@ impl SemanticSearcher {
             None => Vec::new(),
         }
     }
+
+    /// Perform semantic search against the index with multiple queries,
+    /// fusing their scores according to `fusion`. See
+    /// [`search_semantic_multi`].
+    pub fn search_multi(
+        &mut self,
+        queries: &[String],
+        fusion: QueryFusion,
+    ) -> Vec<SemanticMatch> {
+        match &mut self.index {
+            Some(index) => {
+                search_semantic_multi(queries, index, &self.config, fusion)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Perform semantic search against the index, invoking `on_match` as
+    /// soon as each result clears the similarity threshold rather than
+    /// waiting to return a sorted `Vec`. See [`search_semantic_streaming`].
+    pub fn search_streaming(
+        &self,
+        query: &str,
+        on_match: impl FnMut(SemanticMatch) -> bool,
+    ) {
+        if let Some(index) = &self.index {
+            search_semantic_streaming(query, index, &self.config, on_match);
+        }
+    }
 }
@@ -0,0 +1,571 @@
+/*!
+On-disk export/import format for a built `SemanticIndex`.
+
+This backs `--semantic-export FILE` and `--semantic-import FILE`: CI (or
+anyone with a beefier machine) can build the index once and commit or
+publish the resulting file, and developers can load it directly instead of
+re-embedding every file locally.
+
+The format stores each indexed chunk's quantized embedding plus its
+metadata (byte range, content, similarity placeholder). It deliberately
+does not serialize the HNSW graph itself -- `instant-distance` doesn't
+expose a stable on-disk representation for it, and rebuilding the graph
+from embeddings on import (the same work `build_index` already does) is
+cheap relative to re-running the embedding model over every file.
+*/
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use super::indexing::build_index;
+use super::types::{
+    DimensionMismatchPolicy, Embedding, QuantizedVector, SemanticConfig,
+    SemanticIndex, SemanticQuantize,
+};
+
+/// Magic bytes identifying an outgrep semantic index file.
+const MAGIC: &[u8; 4] = b"OGSX";
+
+/// The current on-disk format version. Bump this whenever the binary layout
+/// changes, and reject older/newer versions on import rather than guessing.
+///
+/// Version 2 added each chunk's source file path (see
+/// `SemanticIndex::source_paths`), used by `--semantic-gc` to tell which
+/// entries are stale; version 1 files are rejected rather than read without
+/// paths, to keep `import_index` callers from silently treating every entry
+/// as path-less.
+///
+/// Version 3 added the embedding dimensionality and (if known) model name to
+/// the header, used by `import_index` to detect a model switch between the
+/// index's build and import; version 1 and 2 files are rejected rather than
+/// imported without that check, since guessing the dimensionality from the
+/// first chunk (as `index_stats` does) isn't enough to catch a mismatch when
+/// the file happens to be empty.
+const FORMAT_VERSION: u32 = 3;
+
+/// Write `index` to `path` in outgrep's versioned semantic index format.
+pub fn export_index(
+    index: &SemanticIndex,
+    config: &SemanticConfig,
+    path: &Path,
+) -> Result<()> {
+    let file = File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let mut w = BufWriter::new(file);
+
+    w.write_all(MAGIC)?;
+    write_u32(&mut w, FORMAT_VERSION)?;
+    write_u32(&mut w, config.embedding_dimensions as u32)?;
+    write_string(&mut w, config.model_name.as_deref().unwrap_or(""))?;
+    write_u64(&mut w, index.embeddings.len() as u64)?;
+
+    for ((embedding, meta), source_path) in index
+        .embeddings
+        .iter()
+        .zip(index.metadata.iter())
+        .zip(index.source_paths.iter())
+    {
+        write_quantized_vector(&mut w, embedding)?;
+        write_u64(&mut w, meta.byte_range.start as u64)?;
+        write_u64(&mut w, meta.byte_range.end as u64)?;
+        write_f32(&mut w, meta.similarity)?;
+        write_string(&mut w, &meta.content)?;
+        match source_path {
+            Some(p) => {
+                w.write_all(&[1u8])?;
+                write_string(&mut w, &p.to_string_lossy())?;
+            }
+            None => w.write_all(&[0u8])?,
+        }
+    }
+
+    w.flush()?;
+    Ok(())
+}
+
+/// Read a semantic index previously written by `export_index`, rebuilding
+/// the HNSW graph under `config` (in particular, `config.quantize` decides
+/// how the re-imported embeddings are stored in memory; it need not match
+/// the quantization the file was exported with).
+///
+/// # Errors
+///
+/// Returns an error if the file isn't a recognized index, or if its
+/// embedding dimensionality doesn't match `config.embedding_dimensions`
+/// (i.e. the index was built with a different `--semantic-model` than is
+/// currently configured) and `config.dimension_mismatch` is
+/// [`DimensionMismatchPolicy::Reject`].
+pub fn import_index(
+    path: &Path,
+    config: &SemanticConfig,
+) -> Result<SemanticIndex> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut r = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)
+        .context("failed to read semantic index header")?;
+    if &magic != MAGIC {
+        bail!(
+            "{}: not an outgrep semantic index file (bad magic)",
+            path.display()
+        );
+    }
+
+    let version = read_u32(&mut r)?;
+    if version != FORMAT_VERSION {
+        bail!(
+            "{}: unsupported semantic index format version {version} \
+             (this build of outgrep reads version {FORMAT_VERSION})",
+            path.display()
+        );
+    }
+
+    let source_dimensions = read_u32(&mut r)? as usize;
+    let source_model = read_string(&mut r)?;
+    let source_model =
+        if source_model.is_empty() { None } else { Some(source_model) };
+
+    let project = source_dimensions != config.embedding_dimensions
+        && source_dimensions != 0;
+    if project && config.dimension_mismatch == DimensionMismatchPolicy::Reject
+    {
+        bail!(
+            "{}: embedding dimension mismatch: this index was built with {} \
+             dimensions{}, but the configured model produces {} dimensions. \
+             Either rebuild the index with --semantic-export under the \
+             current model, switch --semantic-model back to the one the \
+             index was built with, or pass \
+             --semantic-dimension-mode project to import anyway using a \
+             naive linear projection",
+            path.display(),
+            source_dimensions,
+            source_model
+                .map(|m| format!(" (model '{m}')"))
+                .unwrap_or_default(),
+            config.embedding_dimensions,
+        );
+    }
+
+    let count = read_u64(&mut r)? as usize;
+    let mut embeddings = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut vector = read_quantized_vector(&mut r)?.to_f32();
+        let start = read_u64(&mut r)? as usize;
+        let end = read_u64(&mut r)? as usize;
+        let _similarity = read_f32(&mut r)?;
+        let content = read_string(&mut r)?;
+        let mut has_path = [0u8; 1];
+        r.read_exact(&mut has_path)?;
+        let source_path = if has_path[0] != 0 {
+            Some(std::path::PathBuf::from(read_string(&mut r)?))
+        } else {
+            None
+        };
+
+        if project {
+            vector = project_dimensions(&vector, config.embedding_dimensions);
+        }
+
+        let dimensions = vector.len();
+        embeddings.push((
+            Embedding { vector, dimensions },
+            Range { start, end },
+            content,
+            source_path,
+        ));
+    }
+
+    Ok(build_index(embeddings, config))
+}
+
+/// Linearly reshape `vector` to `target_dimensions` components: truncating
+/// trailing components if `vector` is longer, zero-padding if it's shorter.
+///
+/// This is deliberately not a fitted PCA projection -- there's no training
+/// data or stored projection matrix to fit one from here, just the raw
+/// vectors being imported. It lets two different-dimension models'
+/// embeddings coexist in one index without crashing, but similarity scores
+/// between projected and native vectors are not meaningful comparisons, only
+/// a best-effort approximation.
+///
+/// TODO: a real cross-model projection needs a projection matrix fitted
+/// offline on paired embeddings from both models and shipped alongside the
+/// model (e.g. in the model registry), so `import_index` could apply a
+/// proper learned transform instead of this truncate/pad fallback.
+pub fn project_dimensions(
+    vector: &[f32],
+    target_dimensions: usize,
+) -> Vec<f32> {
+    let mut projected = vector.to_vec();
+    projected.resize(target_dimensions, 0.0);
+    projected
+}
+
+/// Summary statistics about an on-disk semantic index, for
+/// `--semantic-index-stats`.
+///
+/// TODO: each chunk records its source file path (see
+/// `SemanticIndex::source_paths`), but this function only peeks the header
+/// for speed, so it doesn't decode paths and can't yet report per-file
+/// counts or staleness from them; see `--semantic-gc` for that.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    /// The on-disk format version the index was written with.
+    pub format_version: u32,
+    /// Number of indexed chunks (roughly, symbols or code blocks).
+    pub chunk_count: u64,
+    /// Embedding vector length, from the header.
+    pub embedding_dimensions: usize,
+    /// The `--semantic-model` the index was built with, if recorded. Indexes
+    /// exported with a model explicitly configured via `--semantic-model`
+    /// record it here; `None` means the default model was used or the model
+    /// wasn't known at export time.
+    pub model_name: Option<String>,
+    /// Size of the index file on disk, in bytes.
+    pub file_size_bytes: u64,
+}
+
+/// Read summary statistics from an index file written by `export_index`,
+/// without rebuilding the HNSW graph or decoding any chunk -- only the
+/// header is needed.
+pub fn index_stats(path: &Path) -> Result<IndexStats> {
+    let file_size_bytes = std::fs::metadata(path)
+        .with_context(|| format!("failed to stat {}", path.display()))?
+        .len();
+
+    let file = File::open(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let mut r = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)
+        .context("failed to read semantic index header")?;
+    if &magic != MAGIC {
+        bail!(
+            "{}: not an outgrep semantic index file (bad magic)",
+            path.display()
+        );
+    }
+
+    let format_version = read_u32(&mut r)?;
+    if format_version != FORMAT_VERSION {
+        bail!(
+            "{}: unsupported semantic index format version {format_version} \
+             (this build of outgrep reads version {FORMAT_VERSION})",
+            path.display()
+        );
+    }
+
+    let embedding_dimensions = read_u32(&mut r)? as usize;
+    let model_name = read_string(&mut r)?;
+    let model_name =
+        if model_name.is_empty() { None } else { Some(model_name) };
+    let chunk_count = read_u64(&mut r)?;
+
+    Ok(IndexStats {
+        format_version,
+        chunk_count,
+        embedding_dimensions,
+        model_name,
+        file_size_bytes,
+    })
+}
+
+/// Print `stats` as a plain-text report, for `--semantic-index-stats`.
+pub fn print_index_stats(path: &Path, stats: &IndexStats) {
+    println!("Index: {}", path.display());
+    println!("  Format version:        {}", stats.format_version);
+    println!("  Indexed chunks:        {}", stats.chunk_count);
+    println!("  Embedding dimensions:  {}", stats.embedding_dimensions);
+    println!(
+        "  Model:                 {}",
+        stats.model_name.as_deref().unwrap_or("(unknown)")
+    );
+    println!("  File size:             {} bytes", stats.file_size_bytes);
+    println!();
+    println!(
+        "  Not reported: per-file chunk counts and staleness (this command \
+         doesn't decode per-chunk source paths; see --semantic-gc)."
+    );
+}
+
+fn write_quantized_vector<W: Write>(
+    w: &mut W,
+    v: &QuantizedVector,
+) -> Result<()> {
+    match v {
+        QuantizedVector::F32(values) => {
+            w.write_all(&[0u8])?;
+            write_u32(w, values.len() as u32)?;
+            for &value in values {
+                write_f32(w, value)?;
+            }
+        }
+        QuantizedVector::Int8 { values, scale } => {
+            w.write_all(&[1u8])?;
+            write_u32(w, values.len() as u32)?;
+            write_f32(w, *scale)?;
+            for &value in values {
+                w.write_all(&value.to_le_bytes())?;
+            }
+        }
+        QuantizedVector::F16(values) => {
+            w.write_all(&[2u8])?;
+            write_u32(w, values.len() as u32)?;
+            for value in values {
+                w.write_all(&value.to_bits().to_le_bytes())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_quantized_vector<R: Read>(r: &mut R) -> Result<QuantizedVector> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)?;
+    let len = read_u32(r)? as usize;
+    match tag[0] {
+        0 => {
+            let mut values = Vec::with_capacity(len);
+            for _ in 0..len {
+                values.push(read_f32(r)?);
+            }
+            Ok(QuantizedVector::F32(values))
+        }
+        1 => {
+            let scale = read_f32(r)?;
+            let mut values = Vec::with_capacity(len);
+            for _ in 0..len {
+                let mut buf = [0u8; 1];
+                r.read_exact(&mut buf)?;
+                values.push(buf[0] as i8);
+            }
+            Ok(QuantizedVector::Int8 { values, scale })
+        }
+        2 => {
+            let mut values = Vec::with_capacity(len);
+            for _ in 0..len {
+                let mut buf = [0u8; 2];
+                r.read_exact(&mut buf)?;
+                values.push(half::f16::from_bits(u16::from_le_bytes(buf)));
+            }
+            Ok(QuantizedVector::F16(values))
+        }
+        other => bail!("corrupt semantic index: unknown vector tag {other}"),
+    }
+}
+
+fn write_u32<W: Write>(w: &mut W, v: u32) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn write_u64<W: Write>(w: &mut W, v: u64) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn write_f32<W: Write>(w: &mut W, v: f32) -> io::Result<()> {
+    w.write_all(&v.to_le_bytes())
+}
+
+fn write_string<W: Write>(w: &mut W, s: &str) -> io::Result<()> {
+    write_u32(w, s.len() as u32)?;
+    w.write_all(s.as_bytes())
+}
+
+fn read_u32<R: Read>(r: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(r: &mut R) -> io::Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+fn read_string<R: Read>(r: &mut R) -> Result<String> {
+    let len = read_u32(r)? as usize;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf)?;
+    String::from_utf8(buf).context("semantic index content is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::indexing::build_index;
+
+    #[test]
+    fn export_then_import_round_trips_matches() {
+        let config = SemanticConfig {
+            embedding_dimensions: 4,
+            quantize: SemanticQuantize::None,
+            ..SemanticConfig::default()
+        };
+        let embeddings = vec![
+            (
+                Embedding { vector: vec![1.0, 0.0, 0.0, 0.0], dimensions: 4 },
+                Range { start: 0, end: 10 },
+                "fn foo() {}".to_string(),
+                Some(std::path::PathBuf::from("src/foo.rs")),
+            ),
+            (
+                Embedding { vector: vec![0.0, 1.0, 0.0, 0.0], dimensions: 4 },
+                Range { start: 10, end: 20 },
+                "fn bar() {}".to_string(),
+                None,
+            ),
+        ];
+        let index = build_index(embeddings, &config);
+
+        let dir = std::env::temp_dir();
+        let path = dir.join("outgrep-semantic-index-round-trip-test.ogsx");
+        export_index(&index, &config, &path).unwrap();
+        let imported = import_index(&path, &config).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(index.metadata.len(), imported.metadata.len());
+        for (original, round_tripped) in
+            index.metadata.iter().zip(imported.metadata.iter())
+        {
+            assert_eq!(original.content, round_tripped.content);
+            assert_eq!(original.byte_range, round_tripped.byte_range);
+        }
+        assert_eq!(imported.source_paths[0].as_deref(), Some(Path::new("src/foo.rs")));
+        assert_eq!(imported.source_paths[1], None);
+    }
+
+    #[test]
+    fn index_stats_reports_chunk_count_and_dimensions() {
+        let config = SemanticConfig {
+            embedding_dimensions: 4,
+            model_name: Some("test-model".to_string()),
+            quantize: SemanticQuantize::None,
+            ..SemanticConfig::default()
+        };
+        let embeddings = vec![
+            (
+                Embedding { vector: vec![1.0, 0.0, 0.0, 0.0], dimensions: 4 },
+                Range { start: 0, end: 10 },
+                "fn foo() {}".to_string(),
+                None,
+            ),
+            (
+                Embedding { vector: vec![0.0, 1.0, 0.0, 0.0], dimensions: 4 },
+                Range { start: 10, end: 20 },
+                "fn bar() {}".to_string(),
+                None,
+            ),
+        ];
+        let index = build_index(embeddings, &config);
+
+        let dir = std::env::temp_dir();
+        let path = dir.join("outgrep-semantic-index-stats-test.ogsx");
+        export_index(&index, &config, &path).unwrap();
+        let stats = index_stats(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(stats.format_version, FORMAT_VERSION);
+        assert_eq!(stats.chunk_count, 2);
+        assert_eq!(stats.embedding_dimensions, 4);
+        assert_eq!(stats.model_name.as_deref(), Some("test-model"));
+        assert!(stats.file_size_bytes > 0);
+    }
+
+    #[test]
+    fn import_rejects_bad_magic() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("outgrep-semantic-index-bad-magic-test.ogsx");
+        std::fs::write(&path, b"NOPE").unwrap();
+
+        let err = import_index(&path, &SemanticConfig::default()).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(err.to_string().contains("not an outgrep semantic index"));
+    }
+
+    #[test]
+    fn import_rejects_dimension_mismatch_by_default() {
+        let built_config = SemanticConfig {
+            embedding_dimensions: 4,
+            ..SemanticConfig::default()
+        };
+        let embeddings = vec![(
+            Embedding { vector: vec![1.0, 0.0, 0.0, 0.0], dimensions: 4 },
+            Range { start: 0, end: 10 },
+            "fn foo() {}".to_string(),
+            None,
+        )];
+        let index = build_index(embeddings, &built_config);
+
+        let dir = std::env::temp_dir();
+        let path =
+            dir.join("outgrep-semantic-index-dimension-mismatch-test.ogsx");
+        export_index(&index, &built_config, &path).unwrap();
+
+        let reader_config = SemanticConfig {
+            embedding_dimensions: 8,
+            ..SemanticConfig::default()
+        };
+        let err = import_index(&path, &reader_config).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(err.to_string().contains("dimension mismatch"));
+    }
+
+    #[test]
+    fn import_projects_dimension_mismatch_when_opted_in() {
+        let built_config = SemanticConfig {
+            embedding_dimensions: 4,
+            ..SemanticConfig::default()
+        };
+        let embeddings = vec![(
+            Embedding { vector: vec![1.0, 2.0, 3.0, 4.0], dimensions: 4 },
+            Range { start: 0, end: 10 },
+            "fn foo() {}".to_string(),
+            None,
+        )];
+        let index = build_index(embeddings, &built_config);
+
+        let dir = std::env::temp_dir();
+        let path =
+            dir.join("outgrep-semantic-index-dimension-project-test.ogsx");
+        export_index(&index, &built_config, &path).unwrap();
+
+        let reader_config = SemanticConfig {
+            embedding_dimensions: 6,
+            dimension_mismatch: DimensionMismatchPolicy::Project,
+            ..SemanticConfig::default()
+        };
+        let imported = import_index(&path, &reader_config).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            imported.embeddings[0].to_f32(),
+            vec![1.0, 2.0, 3.0, 4.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn project_dimensions_truncates_and_pads() {
+        assert_eq!(project_dimensions(&[1.0, 2.0, 3.0], 2), vec![1.0, 2.0]);
+        assert_eq!(
+            project_dimensions(&[1.0, 2.0], 4),
+            vec![1.0, 2.0, 0.0, 0.0]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use instant_distance::{HnswMap, Point, Search};
 use std::ops::Range;
 
+use crate::semantic::{ChunkingStrategy, SemanticBackend};
+
 /// Wrapper around Vec<f32> that implements Point trait
 #[derive(Debug, Clone)]
 pub struct EmbeddingPoint(pub Vec<f32>);
@@ -26,6 +28,92 @@ pub struct Embedding {
     pub dimensions: usize,
 }
 
+/// How stored embedding vectors are compressed in the semantic index.
+///
+/// Quantization trades a small amount of similarity-score precision for a
+/// smaller per-embedding memory footprint: `Int8` cuts storage roughly 4x
+/// versus `None` (`f32`), `F16` cuts it roughly 2x. Scoring always
+/// dequantizes to `f32` first (see [`QuantizedVector::to_f32`]), so the
+/// choice of quantization is transparent to search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SemanticQuantize {
+    /// Store embeddings at full `f32` precision.
+    #[default]
+    None,
+    /// Scalar-quantize each component to a signed byte, scaled by the
+    /// vector's max absolute value.
+    Int8,
+    /// Store each component as an IEEE 754 half-precision float.
+    F16,
+}
+
+/// How `--semantic-import` handles an index whose embeddings have a
+/// different dimensionality than the currently configured model.
+///
+/// This mismatch happens when an index built with one `--semantic-model` is
+/// imported while a different model is configured: the two models' vectors
+/// aren't comparable, so mixing them into one HNSW graph produces similarity
+/// scores that look valid but are meaningless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DimensionMismatchPolicy {
+    /// Refuse to import with a clear error naming both dimensionalities.
+    /// This is the default: silently corrupting scores is worse than
+    /// failing loudly.
+    #[default]
+    Reject,
+    /// Linearly project each embedding to the configured dimensionality
+    /// (truncating extra components or zero-padding missing ones) and
+    /// import anyway. See [`crate::semantic::serialize::project_dimensions`]
+    /// for the caveats this entails.
+    Project,
+}
+
+/// An embedding vector stored in a [`SemanticIndex`], in whichever
+/// representation its [`SemanticQuantize`] mode produces.
+#[derive(Debug, Clone)]
+pub enum QuantizedVector {
+    F32(Vec<f32>),
+    Int8 { values: Vec<i8>, scale: f32 },
+    F16(Vec<half::f16>),
+}
+
+impl QuantizedVector {
+    /// Quantize `vector` according to `mode`.
+    pub fn quantize(vector: &[f32], mode: SemanticQuantize) -> QuantizedVector {
+        match mode {
+            SemanticQuantize::None => QuantizedVector::F32(vector.to_vec()),
+            SemanticQuantize::Int8 => {
+                let max_abs =
+                    vector.iter().fold(0.0_f32, |acc, &v| acc.max(v.abs()));
+                let scale =
+                    if max_abs > 0.0 { max_abs / i8::MAX as f32 } else { 1.0 };
+                let values = vector
+                    .iter()
+                    .map(|&v| {
+                        (v / scale).round().clamp(i8::MIN as f32, i8::MAX as f32)
+                            as i8
+                    })
+                    .collect();
+                QuantizedVector::Int8 { values, scale }
+            }
+            SemanticQuantize::F16 => QuantizedVector::F16(
+                vector.iter().map(|&v| half::f16::from_f32(v)).collect(),
+            ),
+        }
+    }
+
+    /// Dequantize back to `f32` for scoring.
+    pub fn to_f32(&self) -> Vec<f32> {
+        match self {
+            QuantizedVector::F32(v) => v.clone(),
+            QuantizedVector::Int8 { values, scale } => {
+                values.iter().map(|&v| v as f32 * scale).collect()
+            }
+            QuantizedVector::F16(v) => v.iter().map(|v| v.to_f32()).collect(),
+        }
+    }
+}
+
 /// Search result with similarity score
 #[derive(Debug, Clone)]
 pub struct SemanticMatch {
@@ -50,6 +138,40 @@ pub struct SemanticConfig {
     pub model_path: Option<String>,
     /// Model name to use for embeddings
     pub model_name: Option<String>,
+    /// The HNSW `ef` parameter to search (and, if unset at construction,
+    /// build) the index with. Higher values trade search speed for recall;
+    /// `None` uses the library's default.
+    pub ef_search: Option<usize>,
+    /// How to split a file's content into chunks before embedding each one.
+    pub chunking_strategy: ChunkingStrategy,
+    /// Target chunk size in bytes, used by `ChunkingStrategy::SlidingWindow`.
+    pub chunk_window_size: usize,
+    /// Number of bytes consecutive sliding windows overlap by, used by
+    /// `ChunkingStrategy::SlidingWindow`.
+    pub chunk_window_overlap: usize,
+    /// Which execution backend to run embedding inference on. Falls back to
+    /// CPU automatically if the requested backend is unavailable.
+    pub backend: SemanticBackend,
+    /// How stored embeddings are quantized to reduce index memory usage.
+    pub quantize: SemanticQuantize,
+    /// Whether to rescore the top ANN candidates with a reranking pass
+    /// before applying `similarity_threshold` and truncating to
+    /// `max_results`. See [`crate::semantic::search::rerank_candidates`].
+    pub rerank: bool,
+    /// Which model the reranking pass should use, looked up in the model
+    /// registry. `None` uses the reranker's built-in default.
+    pub rerank_model: Option<String>,
+    /// Base URL of an OpenAI-compatible `/embeddings` endpoint, used when
+    /// `backend` is [`SemanticBackend::Remote`]. Falls back to the
+    /// `OUTGREP_EMBEDDING_API_URL` environment variable, then to
+    /// `https://api.openai.com/v1` if both are unset.
+    pub remote_embedding_url: Option<String>,
+    /// API key sent as a `Bearer` token to `remote_embedding_url`. Falls
+    /// back to the `OUTGREP_EMBEDDING_API_KEY` environment variable.
+    pub remote_embedding_api_key: Option<String>,
+    /// How `--semantic-import` should handle an index built with a
+    /// different embedding dimensionality than `embedding_dimensions`.
+    pub dimension_mismatch: DimensionMismatchPolicy,
 }
 
 /// Index for fast vector similarity search
@@ -58,10 +180,19 @@ pub struct SemanticIndex {
     pub hnsw_map: HnswMap<EmbeddingPoint, usize>,
     /// Search helper
     pub search: Search,
-    /// Embeddings for direct similarity calculation
-    pub embeddings: Vec<Embedding>,
+    /// Embeddings for direct similarity calculation, quantized according to
+    /// the `SemanticConfig` the index was built with.
+    pub embeddings: Vec<QuantizedVector>,
     /// Metadata for each indexed item
     pub metadata: Vec<SemanticMatch>,
+    /// The source file each entry in `metadata`/`embeddings` was chunked
+    /// from, parallel to those two vectors. `None` for entries built without
+    /// a known path (e.g. `--semantic-import`ing a pre-v2 index file).
+    ///
+    /// This exists so `--semantic-gc` can tell which entries are stale: if
+    /// the recorded path no longer exists, the file was deleted or renamed
+    /// since the entry was indexed.
+    pub source_paths: Vec<Option<std::path::PathBuf>>,
 }
 
 impl Default for SemanticConfig {
@@ -72,6 +203,17 @@ impl Default for SemanticConfig {
             embedding_dimensions: 384,
             model_path: None,
             model_name: None,
+            ef_search: None,
+            chunking_strategy: ChunkingStrategy::default(),
+            chunk_window_size: 2000,
+            chunk_window_overlap: 200,
+            backend: SemanticBackend::default(),
+            quantize: SemanticQuantize::default(),
+            rerank: false,
+            rerank_model: None,
+            remote_embedding_url: None,
+            remote_embedding_api_key: None,
+            dimension_mismatch: DimensionMismatchPolicy::default(),
         }
     }
 }